@@ -0,0 +1,280 @@
+//! M/M/1 queue simulated on an `EventQueue`: customers arrive at
+//! exponentially distributed intervals and are served one at a time, in
+//! arrival order, for exponentially distributed times. Arrivals and
+//! departures are the events; the queue always pops the earliest.
+//!
+//! Times are integer ticks, so several events can fall on the same tick.
+//! The `EventQueue` releases those in the order they were scheduled, which
+//! makes a run a pure function of its seed: the report is identical for
+//! every heap arity. The measured utilization and waits are printed next
+//! to the M/M/1 formulas for comparison.
+//!
+//! ```text
+//! cargo run --release --example mm1
+//! ```
+//!
+//! `cargo test --example mm1` checks the measurements against the formulas
+//! and the determinism across seeds and arities.
+
+use d_ary_heap::EventQueue;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Arrival,
+    Departure,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Config {
+    /// Mean ticks between arrivals, `1 / λ`.
+    mean_interarrival: f64,
+    /// Mean ticks of service, `1 / μ`.
+    mean_service: f64,
+    customers: u64,
+    seed: u64,
+    /// Arity of the event heap.
+    d: usize,
+}
+
+impl Config {
+    /// Server load `ρ = λ / μ`.
+    fn load(&self) -> f64 {
+        self.mean_service / self.mean_interarrival
+    }
+
+    /// Expected wait in the queue, `Wq = ρ / (μ - λ)`.
+    fn expected_wait(&self) -> f64 {
+        self.load() * self.mean_service / (1.0 - self.load())
+    }
+
+    /// Expected time in the system, `W = 1 / (μ - λ)`.
+    fn expected_sojourn(&self) -> f64 {
+        self.mean_service / (1.0 - self.load())
+    }
+
+    /// Expected customers waiting, `Lq = ρ² / (1 - ρ)`.
+    fn expected_queue_len(&self) -> f64 {
+        self.load() * self.load() / (1.0 - self.load())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Report {
+    served: u64,
+    /// Tick of the last departure.
+    elapsed: u64,
+    /// Ticks the server spent serving.
+    busy: u64,
+    /// Ticks spent waiting for the server, summed over customers.
+    total_wait: u64,
+    /// Ticks from arrival to departure, summed over customers.
+    total_sojourn: u64,
+    max_queue_len: usize,
+    /// Events popped on the same tick as the event before them.
+    same_tick: u64,
+}
+
+// CAST: tick totals stay far below 2^53, exact in f64.
+#[allow(clippy::cast_precision_loss)]
+impl Report {
+    fn utilization(&self) -> f64 {
+        self.busy as f64 / self.elapsed as f64
+    }
+
+    fn mean_wait(&self) -> f64 {
+        self.total_wait as f64 / self.served as f64
+    }
+
+    fn mean_sojourn(&self) -> f64 {
+        self.total_sojourn as f64 / self.served as f64
+    }
+
+    /// Time-averaged number waiting: the summed waits are the area under
+    /// the queue-length curve.
+    fn mean_queue_len(&self) -> f64 {
+        self.total_wait as f64 / self.elapsed as f64
+    }
+}
+
+/// Xorshift generator: a fixed seed gives a fixed stream.
+struct Rng(u64);
+
+impl Rng {
+    /// Exponentially distributed ticks of the given mean, rounded.
+    fn exponential(&mut self, mean: f64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        // CAST: 53-bit values, exact in f64; the draw is non-negative and
+        // far below 2^64.
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let ticks = {
+            let uniform = ((self.0 >> 11) + 1) as f64 / (1u64 << 53) as f64;
+            (-uniform.ln() * mean).round() as u64
+        };
+        ticks
+    }
+}
+
+fn simulate(config: &Config) -> Report {
+    let mut rng = Rng(config.seed);
+    let mut events = EventQueue::new(config.d).expect("positive arity");
+    // Arrival ticks of the customers waiting, and of the one in service
+    let mut waiting: VecDeque<u64> = VecDeque::new();
+    let mut in_service: Option<u64> = None;
+    let mut report = Report {
+        served: 0,
+        elapsed: 0,
+        busy: 0,
+        total_wait: 0,
+        total_sojourn: 0,
+        max_queue_len: 0,
+        same_tick: 0,
+    };
+
+    let mut arrived = 1;
+    events.schedule(rng.exponential(config.mean_interarrival), Event::Arrival);
+    let mut last_tick = None;
+    while let Some((now, event)) = events.pop() {
+        if last_tick == Some(now) {
+            report.same_tick += 1;
+        }
+        last_tick = Some(now);
+
+        let start_service = match event {
+            Event::Arrival => {
+                if arrived < config.customers {
+                    let next = now + rng.exponential(config.mean_interarrival);
+                    events.schedule(next, Event::Arrival);
+                    arrived += 1;
+                }
+                if in_service.is_some() {
+                    waiting.push_back(now);
+                    report.max_queue_len = report.max_queue_len.max(waiting.len());
+                    None
+                } else {
+                    Some(now)
+                }
+            }
+            Event::Departure => {
+                let arrival = in_service.take().expect("a customer in service");
+                report.served += 1;
+                report.total_sojourn += now - arrival;
+                report.elapsed = now;
+                waiting.pop_front()
+            }
+        };
+        if let Some(arrival) = start_service {
+            report.total_wait += now - arrival;
+            let service = rng.exponential(config.mean_service);
+            report.busy += service;
+            in_service = Some(arrival);
+            events.schedule(now + service, Event::Departure);
+        }
+    }
+    report
+}
+
+fn main() {
+    println!(
+        "{:>4} {:>8} | {:>6} {:>6} | {:>8} {:>8} | {:>8} {:>8} | {:>6} {:>6} | {:>5} {:>9}",
+        "ρ",
+        "served",
+        "util",
+        "ρ",
+        "Wq",
+        "theory",
+        "W",
+        "theory",
+        "Lq",
+        "theory",
+        "max q",
+        "same tick"
+    );
+    for load in [0.5, 0.7, 0.8, 0.9] {
+        let config = Config {
+            mean_interarrival: 1_000.0,
+            mean_service: 1_000.0 * load,
+            customers: 1_000_000,
+            seed: 0x9e37_79b9_7f4a_7c15,
+            d: 4,
+        };
+        let report = simulate(&config);
+        println!(
+            "{load:>4} {:>8} | {:>6.3} {:>6.3} | {:>8.0} {:>8.0} | {:>8.0} {:>8.0} | {:>6.2} {:>6.2} | {:>5} {:>9}",
+            report.served,
+            report.utilization(),
+            config.load(),
+            report.mean_wait(),
+            config.expected_wait(),
+            report.mean_sojourn(),
+            config.expected_sojourn(),
+            report.mean_queue_len(),
+            config.expected_queue_len(),
+            report.max_queue_len,
+            report.same_tick,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simulate, Config};
+
+    fn config(load: f64, customers: u64, seed: u64, d: usize) -> Config {
+        Config {
+            mean_interarrival: 1_000.0,
+            mean_service: 1_000.0 * load,
+            customers,
+            seed,
+            d,
+        }
+    }
+
+    fn assert_close(measured: f64, expected: f64, tolerance: f64, what: &str) {
+        let error = (measured - expected).abs() / expected;
+        assert!(
+            error < tolerance,
+            "{what}: measured {measured:.3}, expected {expected:.3}"
+        );
+    }
+
+    #[test]
+    fn measurements_match_the_mm1_formulas() {
+        for (load, tolerance) in [(0.5, 0.05), (0.8, 0.12)] {
+            let config = config(load, 200_000, 0x2545_f491_4f6c_dd1d, 4);
+            let report = simulate(&config);
+            assert_eq!(report.served, config.customers);
+            assert_close(report.utilization(), config.load(), 0.02, "utilization");
+            assert_close(report.mean_wait(), config.expected_wait(), tolerance, "Wq");
+            assert_close(
+                report.mean_sojourn(),
+                config.expected_sojourn(),
+                tolerance,
+                "W",
+            );
+            assert_close(
+                report.mean_queue_len(),
+                config.expected_queue_len(),
+                tolerance,
+                "Lq",
+            );
+        }
+    }
+
+    #[test]
+    fn a_run_depends_on_its_seed_only() {
+        let base = simulate(&config(0.9, 20_000, 7, 4));
+        // Ticks are coarse enough for events to share one
+        assert!(base.same_tick > 0);
+        for d in [1, 2, 3, 8, 16] {
+            assert_eq!(simulate(&config(0.9, 20_000, 7, d)), base, "d = {d}");
+        }
+        assert_ne!(simulate(&config(0.9, 20_000, 8, 4)), base);
+    }
+}
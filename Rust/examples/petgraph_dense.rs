@@ -0,0 +1,140 @@
+//! Dijkstra on dense random graphs: `petgraph::algo::dijkstra` (binary heap,
+//! lazy deletion) against `d_ary_heap::petgraph::dijkstra_with_arity`
+//! (d-ary heap, decrease-key) and `dijkstra_adaptive` (arity chosen during
+//! the run, shown as `auto` with the arity it ended on).
+//!
+//! Both runs use a cost type that counts its own comparisons, so the
+//! comparison totals are exact and machine-independent; times are the best
+//! of five runs.
+//!
+//! ```text
+//! cargo run --release --features petgraph --example petgraph_dense
+//! ```
+
+use d_ary_heap::petgraph::AdaptiveArity;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::Add;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A path cost that counts every comparison made on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Counted(u64);
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        COMPARISONS.with(|c| c.set(c.get() + 1));
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Add for Counted {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+/// `nodes` nodes, each edge present with probability `density`, weights 1..=100.
+fn dense_graph(nodes: usize, density: f64, seed: u64) -> DiGraph<(), u64> {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut graph = DiGraph::with_capacity(nodes, 0);
+    for _ in 0..nodes {
+        graph.add_node(());
+    }
+    // CAST: u64 → f64 on a value below 2^53, then f64 → u64 on a threshold in [0, 2^53].
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    let threshold = (density * (1u64 << 53) as f64) as u64;
+    for a in 0..nodes {
+        for b in 0..nodes {
+            if a != b && next() >> 11 < threshold {
+                graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), next() % 100 + 1);
+            }
+        }
+    }
+    graph
+}
+
+/// Best time of five runs of `run`, and the comparisons of one.
+fn measure<R>(mut run: impl FnMut() -> R) -> (Duration, u64, R) {
+    COMPARISONS.with(|c| c.set(0));
+    let result = run();
+    let comparisons = COMPARISONS.with(Cell::get);
+    let best = (0..5)
+        .map(|_| {
+            let started = Instant::now();
+            std::hint::black_box(run());
+            started.elapsed()
+        })
+        .min()
+        .unwrap_or_default();
+    (best, comparisons, result)
+}
+
+fn main() {
+    println!(
+        "{:>6} {:>8} {:>9} | {:>14} {:>10} | {:>3} {:>14} {:>10}",
+        "nodes", "density", "edges", "petgraph cmps", "time", "d", "d-heap cmps", "time"
+    );
+    for (nodes, density) in [(1_000, 0.05), (1_000, 0.5), (2_000, 0.5), (2_000, 1.0)] {
+        let graph = dense_graph(nodes, density, 0x9e37_79b9_7f4a_7c15);
+        let start = NodeIndex::new(0);
+        let cost = |e: petgraph::graph::EdgeReference<'_, u64>| Counted(*e.weight());
+
+        let (base_time, base_cmps, expected) =
+            measure(|| petgraph::algo::dijkstra(&graph, start, None, cost));
+        for d in [2, 4, 8] {
+            let (time, cmps, distances) = measure(|| {
+                d_ary_heap::petgraph::dijkstra_with_arity(&graph, start, None, cost, d)
+                    .expect("positive arity")
+            });
+            assert!(distances
+                .iter()
+                .all(|(node, dist)| expected[node].0 == dist.0));
+            println!(
+                "{nodes:>6} {density:>8} {:>9} | {base_cmps:>14} {:>8.1}ms | {d:>3} {cmps:>14} {:>8.1}ms",
+                graph.edge_count(),
+                base_time.as_secs_f64() * 1e3,
+                time.as_secs_f64() * 1e3,
+            );
+        }
+        let (time, cmps, (distances, report)) = measure(|| {
+            d_ary_heap::petgraph::dijkstra_adaptive(
+                &graph,
+                start,
+                None,
+                cost,
+                AdaptiveArity::default(),
+            )
+            .expect("valid policy")
+        });
+        assert!(distances
+            .iter()
+            .all(|(node, dist)| expected[node].0 == dist.0));
+        println!(
+            "{nodes:>6} {density:>8} {:>9} | {base_cmps:>14} {:>8.1}ms | {:>3} {cmps:>14} {:>8.1}ms  auto: {} switch(es), {} rebuild cmps",
+            graph.edge_count(),
+            base_time.as_secs_f64() * 1e3,
+            report.final_arity,
+            time.as_secs_f64() * 1e3,
+            report.switches.len(),
+            report.rebuild_comparisons(),
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Best-first search over implicit graphs.
+//!
+//! [`best_first`] is one search engine parameterised by three closures: the
+//! successors of a node with the cost of each step, the priority to queue a
+//! node at given the cost of the best path found to it, and the goal test.
+//! The frontier is a d-ary heap holding one entry per node: when a cheaper
+//! path to a queued node is found, the heap's identity map finds its entry
+//! and `update_priority` moves it, instead of queueing a duplicate.
+//!
+//! The classic searches are configurations of it:
+//!
+//! | Search | Priority of a node `n` reached at cost `g` |
+//! |--------|--------------------------------------------|
+//! | [`uniform_cost`] (Dijkstra) | `g` |
+//! | [`astar`] | `g + h(n)` |
+//! | [`greedy_best_first`] | `h(n)` |
+//!
+//! Nodes are generated on demand, so the graph may be implicit (puzzle
+//! states, grid cells) or too large to build. For petgraph graphs, the
+//! `petgraph` module's functions avoid hashing node ids.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::algorithms::{astar, uniform_cost};
+//!
+//! // Walk the number line from 0 to 13 with steps of +1 (cost 1) or ×2 (cost 2)
+//! let moves = |&n: &u32| [(n + 1, 1), (n * 2, 2)];
+//! let (cost, path) = uniform_cost(0, moves, |&n| n == 13).unwrap();
+//! assert_eq!(cost, 8);
+//! assert_eq!(path, [0, 1, 2, 3, 6, 12, 13]);
+//!
+//! // The same search, guided by a lower bound on the remaining cost
+//! let estimate = |&n: &u32| u32::from(n < 13);
+//! assert_eq!(astar(0, moves, estimate, |&n| n == 13).unwrap().0, 8);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The `examples/dijkstra/` programs show uniform-cost search in
+//!   every language.
+
+use crate::{PriorityCompare, PriorityQueue, DEFAULT_ARITY};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Add;
+
+/// A path's total cost and its nodes, first to last.
+pub type CostedPath<K, N> = (K, Vec<N>);
+
+/// A frontier node queued at `priority`; identity is the node alone, so a
+/// reprioritised copy finds the queued entry.
+#[derive(Clone)]
+struct Queued<N, P> {
+    node: N,
+    priority: P,
+}
+
+impl<N: PartialEq, P> PartialEq for Queued<N, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<N: Eq, P> Eq for Queued<N, P> {}
+
+impl<N: Hash, P> Hash for Queued<N, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+    }
+}
+
+/// Lowest priority value first; `PartialOrd` so float costs work.
+struct Lowest;
+
+impl<N, P: PartialOrd> PriorityCompare<Queued<N, P>> for Lowest {
+    fn higher_priority(&self, a: &Queued<N, P>, b: &Queued<N, P>) -> bool {
+        a.priority < b.priority
+    }
+}
+
+/// Searches from `start` for a node satisfying `is_goal`, always expanding
+/// the queued node of lowest `priority(node, cost)`, where `cost` is the
+/// cheapest path to `node` found so far. Returns that path and its cost, or
+/// `None` if no goal is reachable.
+///
+/// `successors(node)` lists each neighbour with the cost of the step to it,
+/// which must not be negative. A node whose cost improves is requeued, even
+/// after it was expanded, so the path found is the cheapest whenever the
+/// priority never overestimates the cost of reaching a goal through a node
+/// (as for [`uniform_cost`] and [`astar`] with an admissible estimate).
+///
+/// **Time Complexity**: `O(E · d · log_d V)` for the `V` nodes and `E` steps
+/// explored, when no node is expanded twice
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::algorithms::best_first;
+///
+/// // Cheapest route over an adjacency list of (neighbour, cost)
+/// let graph = [vec![(1, 4), (2, 1)], vec![(3, 1)], vec![(1, 1), (3, 5)], vec![]];
+/// let (cost, path) = best_first(
+///     0usize,
+///     |&n| graph[n].clone(),
+///     |_, cost: u32| cost,
+///     |&n| n == 3,
+/// )
+/// .unwrap();
+/// assert_eq!((cost, path), (3, vec![0, 2, 1, 3]));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn best_first<N, K, P, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut priority: impl FnMut(&N, K) -> P,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<CostedPath<K, N>>
+where
+    N: Eq + Hash + Clone,
+    K: Copy + PartialOrd + Add<Output = K> + Default,
+    P: PartialOrd + Clone,
+    I: IntoIterator<Item = (N, K)>,
+{
+    // EXPLICIT: the only error is `InvalidArity`, and the arity is positive.
+    let mut frontier = PriorityQueue::new(DEFAULT_ARITY, Lowest).ok()?;
+    // Cheapest known cost of each reached node, and the node it came from
+    let mut reached: HashMap<N, (K, Option<N>)> = HashMap::new();
+    let zero = K::default();
+    reached.insert(start.clone(), (zero, None));
+    frontier.insert(Queued {
+        priority: priority(&start, zero),
+        node: start,
+    });
+
+    while let Some(Queued { node, .. }) = frontier.pop() {
+        if is_goal(&node) {
+            return Some(path_to(node, &reached));
+        }
+        // EXPLICIT: every queued node was reached first.
+        let cost = reached.get(&node)?.0;
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match reached.entry(next.clone()) {
+                Entry::Occupied(mut known) => {
+                    if next_cost < known.get().0 {
+                        known.insert((next_cost, Some(node.clone())));
+                    } else {
+                        continue;
+                    }
+                }
+                Entry::Vacant(unknown) => {
+                    unknown.insert((next_cost, Some(node.clone())));
+                }
+            }
+            let queued = Queued {
+                priority: priority(&next, next_cost),
+                node: next,
+            };
+            if frontier.contains(&queued) {
+                // EXPLICIT: just found in the frontier.
+                let _ = frontier.update_priority(&queued);
+            } else {
+                frontier.insert(queued);
+            }
+        }
+    }
+    None
+}
+
+/// The cost of `goal` and the path to it, by following `reached`'s parents.
+fn path_to<N, K>(goal: N, reached: &HashMap<N, (K, Option<N>)>) -> CostedPath<K, N>
+where
+    N: Eq + Hash + Clone,
+    K: Copy,
+{
+    let cost = reached[&goal].0;
+    let mut path = vec![goal];
+    while let Some((_, Some(parent))) = path.last().and_then(|n| reached.get(n)) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    (cost, path)
+}
+
+/// Cheapest path from `start` to a goal: [`best_first`] by path cost alone,
+/// i.e. Dijkstra's algorithm stopping at the first goal.
+///
+/// **Time Complexity**: `O(E · d · log_d V)`
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::algorithms::uniform_cost;
+///
+/// let (cost, path) = uniform_cost('a', |&c| match c {
+///     'a' => vec![('b', 4), ('c', 1)],
+///     'c' => vec![('b', 2)],
+///     _ => vec![],
+/// }, |&c| c == 'b').unwrap();
+/// assert_eq!((cost, path), (3, vec!['a', 'c', 'b']));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn uniform_cost<N, K, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<CostedPath<K, N>>
+where
+    N: Eq + Hash + Clone,
+    K: Copy + PartialOrd + Add<Output = K> + Default,
+    I: IntoIterator<Item = (N, K)>,
+{
+    best_first(start, successors, |_, cost| cost, is_goal)
+}
+
+/// Cheapest path from `start` to a goal, guided by `estimate`: [`best_first`]
+/// by path cost plus the estimated cost left. The path is the cheapest if
+/// `estimate` never overestimates.
+///
+/// **Time Complexity**: `O(E · d · log_d V)` for a consistent estimate
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::algorithms::astar;
+///
+/// // 4-connected moves on an open 10×10 grid, guided by Manhattan distance
+/// let goal = (7i32, 3i32);
+/// let moves = |&(x, y): &(i32, i32)| {
+///     [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+///         .into_iter()
+///         .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+///         .map(|p| (p, 1))
+/// };
+/// let manhattan = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+/// let (cost, path) = astar((0, 0), moves, manhattan, |&p| p == goal).unwrap();
+/// assert_eq!(cost, 10);
+/// assert_eq!(path.len(), 11);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn astar<N, K, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    mut estimate: impl FnMut(&N) -> K,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<CostedPath<K, N>>
+where
+    N: Eq + Hash + Clone,
+    K: Copy + PartialOrd + Add<Output = K> + Default,
+    I: IntoIterator<Item = (N, K)>,
+{
+    best_first(
+        start,
+        successors,
+        |node, cost| cost + estimate(node),
+        is_goal,
+    )
+}
+
+/// A path from `start` to a goal, always expanding the node `estimate`
+/// rates closest to one: [`best_first`] by estimate alone. It typically
+/// expands far fewer nodes than [`astar`], but the path need not be the
+/// cheapest.
+///
+/// **Time Complexity**: `O(E · d · log_d V)` when no node is expanded twice
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::algorithms::greedy_best_first;
+///
+/// let goal = 40u32;
+/// let moves = |&n: &u32| [(n + 1, 1u32), (n * 3, 1)];
+/// let distance = |&n: &u32| n.abs_diff(goal);
+/// let (_, path) = greedy_best_first(1, moves, distance, |&n| n == goal).unwrap();
+/// assert_eq!(path, [1, 3, 9, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn greedy_best_first<N, K, P, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    mut estimate: impl FnMut(&N) -> P,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<CostedPath<K, N>>
+where
+    N: Eq + Hash + Clone,
+    K: Copy + PartialOrd + Add<Output = K> + Default,
+    P: PartialOrd + Clone,
+    I: IntoIterator<Item = (N, K)>,
+{
+    best_first(start, successors, |node, _| estimate(node), is_goal)
+}
@@ -0,0 +1,302 @@
+//! Least-loaded worker selection over a d-ary min-heap.
+//!
+//! A [`LoadBalancer`] keeps its workers in a min-heap by the number of
+//! requests each is serving. [`acquire`](LoadBalancer::acquire) hands out
+//! the least loaded one, raising its load with `update_priority`, and the
+//! returned [`WorkerGuard`] lowers it again when dropped, so a request's
+//! load is released on every exit path, `?` and panics included.
+//!
+//! The balancer is a cheap-to-clone handle on shared state behind a
+//! `Mutex`: clones and guards can move to other threads, and each
+//! operation holds the lock for one `O(d · log_d n)` heap update.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::LoadBalancer;
+//!
+//! let pool = LoadBalancer::new(4).unwrap();
+//! for backend in ["a", "b", "c"] {
+//!     pool.add_worker(backend);
+//! }
+//!
+//! let first = pool.acquire().unwrap();
+//! let second = pool.acquire().unwrap();
+//! assert_ne!(first.worker(), second.worker());
+//! assert_eq!(pool.load(first.worker()), Some(1));
+//!
+//! let busy = *first.worker();
+//! drop(first);
+//! assert_eq!(pool.load(&busy), Some(0));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Other languages can build it from `insert`, `front` and the
+//!   priority updates, releasing in a `defer` / destructor / `finally`.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A worker and the requests it is serving; identity is the worker alone,
+/// so a copy with any load finds the queued entry.
+#[derive(Debug, Clone)]
+struct Loaded<W> {
+    worker: W,
+    load: u64,
+    /// Which `add_worker` call queued this entry, so guards taken before a
+    /// remove and re-add release nothing.
+    generation: u64,
+}
+
+impl<W: PartialEq> PartialEq for Loaded<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.worker == other.worker
+    }
+}
+
+impl<W: Eq> Eq for Loaded<W> {}
+
+impl<W: Hash> Hash for Loaded<W> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.worker.hash(state);
+    }
+}
+
+/// Least loaded first.
+#[derive(Debug, Clone, Copy)]
+struct LeastLoaded;
+
+impl<W> PriorityCompare<Loaded<W>> for LeastLoaded {
+    fn higher_priority(&self, a: &Loaded<W>, b: &Loaded<W>) -> bool {
+        a.load < b.load
+    }
+}
+
+/// State shared by a balancer's clones and guards.
+struct Workers<W>
+where
+    W: Eq + Hash + Clone,
+{
+    heap: PriorityQueue<Loaded<W>, LeastLoaded, NoOpStats>,
+    /// `add_worker` calls so far.
+    generations: u64,
+}
+
+impl<W> Workers<W>
+where
+    W: Eq + Hash + Clone,
+{
+    /// `worker`'s queued entry.
+    fn entry(&self, worker: &W) -> Option<&Loaded<W>> {
+        let key = Loaded {
+            worker: worker.clone(),
+            load: 0,
+            generation: 0,
+        };
+        self.heap.get_position(&key)?.get_at(&self.heap)
+    }
+}
+
+/// Locks the shared state. A panic while it was held cannot leave it
+/// inconsistent (every change is a single heap call), so poisoning is
+/// ignored.
+fn lock<W>(workers: &Mutex<Workers<W>>) -> MutexGuard<'_, Workers<W>>
+where
+    W: Eq + Hash + Clone,
+{
+    workers.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Hands out the least-loaded of a set of workers `W`; see the
+/// [module docs](self). Cloning shares the same workers.
+pub struct LoadBalancer<W>
+where
+    W: Eq + Hash + Clone,
+{
+    workers: Arc<Mutex<Workers<W>>>,
+}
+
+impl<W> LoadBalancer<W>
+where
+    W: Eq + Hash + Clone,
+{
+    /// An empty balancer over a heap of arity `d`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize) -> Result<Self, Error> {
+        let heap = PriorityQueue::new(d, LeastLoaded)?;
+        Ok(Self {
+            workers: Arc::new(Mutex::new(Workers {
+                heap,
+                generations: 0,
+            })),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Workers<W>> {
+        lock(&self.workers)
+    }
+
+    /// Adds `worker` with no load. Returns `false`, changing nothing, if it
+    /// is already present.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    pub fn add_worker(&self, worker: W) -> bool {
+        let mut workers = self.lock();
+        if workers.entry(&worker).is_some() {
+            return false;
+        }
+        workers.generations += 1;
+        let generation = workers.generations;
+        workers.heap.insert(Loaded {
+            worker,
+            load: 0,
+            generation,
+        });
+        true
+    }
+
+    /// Removes `worker`, returning the load it had, or `None` if absent.
+    /// Its outstanding guards stay valid; dropping them releases nothing.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    pub fn remove_worker(&self, worker: &W) -> Option<u64> {
+        let mut workers = self.lock();
+        let entry = workers.entry(worker)?.clone();
+        workers.heap.remove_many(std::slice::from_ref(&entry));
+        Some(entry.load)
+    }
+
+    /// The least-loaded worker, with its load raised by one until the
+    /// returned guard is dropped; `None` if there are no workers. Workers
+    /// with equal load are handed out in no particular order.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    #[must_use = "dropping the guard releases the worker at once"]
+    pub fn acquire(&self) -> Option<WorkerGuard<W>> {
+        let mut workers = self.lock();
+        let least = workers.heap.peek()?.clone();
+        let busier = Loaded {
+            load: least.load + 1,
+            ..least
+        };
+        // EXPLICIT: `least` was just read from the heap, so it is present.
+        let _ = workers.heap.update_priority(&busier);
+        Some(WorkerGuard {
+            worker: busier.worker,
+            generation: busier.generation,
+            workers: Arc::clone(&self.workers),
+        })
+    }
+
+    /// Requests `worker` is serving, or `None` if it is not in the balancer.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn load(&self, worker: &W) -> Option<u64> {
+        self.lock().entry(worker).map(|entry| entry.load)
+    }
+
+    /// Number of workers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().heap.len()
+    }
+
+    /// Whether there are no workers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().heap.is_empty()
+    }
+}
+
+impl<W> Clone for LoadBalancer<W>
+where
+    W: Eq + Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            workers: Arc::clone(&self.workers),
+        }
+    }
+}
+
+// Manual impl: lists each worker's load rather than the heap's internals.
+impl<W> Debug for LoadBalancer<W>
+where
+    W: Eq + Hash + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let workers = self.lock();
+        f.debug_map()
+            .entries(
+                workers
+                    .heap
+                    .iter_with_positions()
+                    .map(|(_, entry)| (&entry.worker, entry.load)),
+            )
+            .finish()
+    }
+}
+
+/// A worker handed out by [`LoadBalancer::acquire`]; dropping it lowers the
+/// worker's load by one.
+pub struct WorkerGuard<W>
+where
+    W: Eq + Hash + Clone,
+{
+    worker: W,
+    generation: u64,
+    workers: Arc<Mutex<Workers<W>>>,
+}
+
+impl<W> WorkerGuard<W>
+where
+    W: Eq + Hash + Clone,
+{
+    /// The worker to send the request to.
+    #[must_use]
+    pub const fn worker(&self) -> &W {
+        &self.worker
+    }
+}
+
+impl<W> Drop for WorkerGuard<W>
+where
+    W: Eq + Hash + Clone,
+{
+    fn drop(&mut self) {
+        let mut workers = lock(&self.workers);
+        // A worker removed since the acquire has nothing left to release,
+        // even if it was added back.
+        let Some(entry) = workers.entry(&self.worker) else {
+            return;
+        };
+        if entry.generation != self.generation {
+            return;
+        }
+        let lighter = Loaded {
+            load: entry.load - 1,
+            ..entry.clone()
+        };
+        // EXPLICIT: `entry` was just read from the heap, so it is present.
+        let _ = workers.heap.update_priority(&lighter);
+    }
+}
+
+// Manual impl: the shared balancer state is not shown.
+impl<W> Debug for WorkerGuard<W>
+where
+    W: Eq + Hash + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("WorkerGuard")
+            .field("worker", &self.worker)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,246 @@
+//! Beam search with a bounded frontier.
+//!
+//! [`BeamSearch`] explores a search tree depth by depth, keeping only the
+//! `beam_width` best-scored candidates of each depth. The candidates of a
+//! depth are offered one by one to a d-ary heap ordered worst first and
+//! bounded at `beam_width`: once it is full, a better candidate replaces
+//! the worst kept with a single `push_pop`, and a worse one is rejected in
+//! O(1). Memory is therefore `O(beam_width)` whatever the branching factor.
+//!
+//! Two hooks configure it: `expand(node)` lists the children of a node, and
+//! `score(node)` rates one, higher being better. For sequence decoding, a
+//! node is a partial sequence and its score the sequence's log-probability;
+//! for planning, a node is a state and its score a heuristic value.
+//!
+//! Beam search is not complete: the best answer may be pruned on the way.
+//! Widening the beam trades time for quality; a beam of 1 is greedy search.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::BeamSearch;
+//! use std::num::NonZeroUsize;
+//!
+//! // Build 4-letter words from "abc", scoring each letter's weight
+//! let weight = |c: char| match c { 'a' => 3, 'b' => 2, _ => 1 };
+//! let mut beam = BeamSearch::new(
+//!     4,
+//!     NonZeroUsize::new(2).unwrap(),
+//!     |word: &String| "abc".chars().map(|c| format!("{word}{c}")).collect::<Vec<_>>(),
+//!     |word: &String| word.chars().map(weight).sum::<i32>(),
+//! )
+//! .unwrap();
+//!
+//! let kept = beam.run(String::new(), 4);
+//! assert_eq!(kept, [(12, "aaaa".to_string()), (11, "aaab".to_string())]);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. The bounded frontier only needs `insert`, `len` and a
+//!   replace-the-front, which every implementation can compose.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+
+/// A scored candidate; identity is its arrival number, so equal nodes may be
+/// kept side by side.
+#[derive(Clone)]
+struct Candidate<N, S> {
+    arrival: u64,
+    score: S,
+    node: N,
+}
+
+impl<N, S> PartialEq for Candidate<N, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.arrival == other.arrival
+    }
+}
+
+impl<N, S> Eq for Candidate<N, S> {}
+
+impl<N, S> Hash for Candidate<N, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.arrival.hash(state);
+    }
+}
+
+/// Lowest score first, the later arrival first among equal scores, so the
+/// front is the one to evict and ties keep the earlier candidate.
+struct WorstFirst;
+
+impl<N, S: PartialOrd> PriorityCompare<Candidate<N, S>> for WorstFirst {
+    fn higher_priority(&self, a: &Candidate<N, S>, b: &Candidate<N, S>) -> bool {
+        a.score < b.score || (a.score == b.score && a.arrival > b.arrival)
+    }
+}
+
+/// Depth-by-depth search keeping the `beam_width` best candidates per
+/// depth; see the [module docs](self).
+pub struct BeamSearch<N, S, E, F> {
+    d: usize,
+    beam_width: NonZeroUsize,
+    expand: E,
+    score: F,
+    expanded: u64,
+    pruned: u64,
+    _types: PhantomData<fn(&N) -> S>,
+}
+
+impl<N, S, E, F> Debug for BeamSearch<N, S, E, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("BeamSearch")
+            .field("d", &self.d)
+            .field("beam_width", &self.beam_width)
+            .field("expanded", &self.expanded)
+            .field("pruned", &self.pruned)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N, S, E, F, I> BeamSearch<N, S, E, F>
+where
+    N: Clone,
+    S: PartialOrd + Clone,
+    E: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> S,
+{
+    /// Keeps up to `beam_width` candidates per depth in a heap of arity
+    /// `d`, listing children with `expand` and rating them with `score`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, beam_width: NonZeroUsize, expand: E, score: F) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            d,
+            beam_width,
+            expand,
+            score,
+            expanded: 0,
+            pruned: 0,
+            _types: PhantomData,
+        })
+    }
+
+    /// Expands every node of `beam` and returns the `beam_width` best
+    /// children with their scores, best first. Among equal scores, the
+    /// child generated first is kept and ranked first.
+    ///
+    /// **Time Complexity**: `O(c · d · log_d w)` for `c` children and a
+    /// beam of width `w`
+    pub fn step(&mut self, beam: impl IntoIterator<Item = N>) -> Vec<(S, N)> {
+        let width = self.beam_width.get();
+        // EXPLICIT: the arity was checked in `new`.
+        let Ok(mut kept) = PriorityQueue::<_, _, NoOpStats>::new(self.d, WorstFirst) else {
+            return Vec::new();
+        };
+        let mut arrival = 0;
+        for node in beam {
+            self.expanded += 1;
+            for child in (self.expand)(&node) {
+                let candidate = Candidate {
+                    arrival,
+                    score: (self.score)(&child),
+                    node: child,
+                };
+                arrival += 1;
+                if kept.len() < width {
+                    kept.insert(candidate);
+                } else {
+                    kept.push_pop(candidate);
+                    self.pruned += 1;
+                }
+            }
+        }
+        let mut best_first: Vec<(S, N)> = kept
+            .pop_many(width)
+            .into_iter()
+            .map(|c| (c.score, c.node))
+            .collect();
+        best_first.reverse();
+        best_first
+    }
+
+    /// Searches `depth` levels below `start` and returns the final beam,
+    /// best first. The beam is empty if every path ends earlier; with
+    /// `depth == 0` it holds `start` alone.
+    ///
+    /// **Time Complexity**: `O(depth · w · b · d · log_d w)` for a beam of
+    /// width `w` and branching factor `b`
+    pub fn run(&mut self, start: N, depth: usize) -> Vec<(S, N)> {
+        let mut beam = vec![((self.score)(&start), start)];
+        for _ in 0..depth {
+            if beam.is_empty() {
+                break;
+            }
+            beam = self.step(beam.into_iter().map(|(_, node)| node));
+        }
+        beam
+    }
+
+    /// Searches up to `max_depth` levels below `start` for nodes satisfying
+    /// `is_goal` and returns the best-scored one found, or `None`.
+    ///
+    /// A goal kept in the beam is finished: it is set aside rather than
+    /// expanded, as an end-of-sequence token ends a decoded sequence, and
+    /// the search goes on with the rest of the beam until it is empty or
+    /// `max_depth` is reached. Among equally scored goals, the shallowest
+    /// is returned.
+    ///
+    /// **Time Complexity**: as [`run`](BeamSearch::run)
+    pub fn search(
+        &mut self,
+        start: N,
+        max_depth: usize,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Option<(S, N)> {
+        if is_goal(&start) {
+            return Some(((self.score)(&start), start));
+        }
+        let mut best: Option<(S, N)> = None;
+        let mut beam = vec![start];
+        for _ in 0..max_depth {
+            if beam.is_empty() {
+                break;
+            }
+            let mut open = Vec::with_capacity(self.beam_width.get());
+            for (score, node) in self.step(beam) {
+                if !is_goal(&node) {
+                    open.push(node);
+                } else if best.as_ref().is_none_or(|(top, _)| score > *top) {
+                    best = Some((score, node));
+                }
+            }
+            beam = open;
+        }
+        best
+    }
+
+    /// Nodes expanded so far, over all calls.
+    #[must_use]
+    pub const fn expanded(&self) -> u64 {
+        self.expanded
+    }
+
+    /// Children discarded or evicted because the beam was full, over all
+    /// calls.
+    #[must_use]
+    pub const fn pruned(&self) -> u64 {
+        self.pruned
+    }
+
+    /// Most candidates kept per depth.
+    #[must_use]
+    pub const fn beam_width(&self) -> NonZeroUsize {
+        self.beam_width
+    }
+}
@@ -0,0 +1,223 @@
+//! Best-first branch and bound.
+//!
+//! [`BranchAndBound`] minimises a cost over a tree of partial solutions. It
+//! is configured by three hooks: `branch(node)` splits a node into its
+//! children, `bound(node)` gives a lower bound on the cost of every solution
+//! below it, and `evaluate(node)` gives the node's own cost if it is a
+//! feasible solution. The best solution found so far is the incumbent.
+//!
+//! The frontier is a d-ary heap ordered by bound, so the most promising
+//! node is always expanded next. A node whose bound is no better than the
+//! incumbent cannot lead to an improvement: it is not queued, and when a
+//! new incumbent is found, every queued node it rules out is removed at
+//! once with `drain_where`, keeping the frontier at the size of the nodes
+//! still worth exploring. The search ends when the frontier is empty: the
+//! incumbent is then optimal.
+//!
+//! To maximise, minimise the negated value, with `bound` an upper bound on
+//! the value, negated.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::BranchAndBound;
+//!
+//! // Pick one number from each row with the smallest sum, never the same
+//! // column twice: an assignment problem.
+//! let cost = [[9, 2, 7], [6, 4, 3], [5, 8, 1]];
+//! let mut solver = BranchAndBound::new(
+//!     4,
+//!     // A node is the columns chosen for the first rows
+//!     |cols: &Vec<usize>| {
+//!         (0..3)
+//!             .filter(|c| !cols.contains(c))
+//!             .map(|c| [cols.as_slice(), &[c]].concat())
+//!             .collect::<Vec<_>>()
+//!     },
+//!     // Cost so far plus the cheapest entry of each remaining row
+//!     |cols: &Vec<usize>| {
+//!         let so_far: u32 = cols.iter().enumerate().map(|(r, &c)| cost[r][c]).sum();
+//!         let rest: u32 = cost[cols.len()..].iter().map(|row| row.iter().min().unwrap()).sum();
+//!         so_far + rest
+//!     },
+//!     |cols: &Vec<usize>| {
+//!         (cols.len() == 3).then(|| cols.iter().enumerate().map(|(r, &c)| cost[r][c]).sum())
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let (total, assignment) = solver.solve(Vec::new()).unwrap();
+//! assert_eq!((total, assignment), (9, vec![1, 0, 2]));
+//! assert!(solver.stats().expanded < 10);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. The driver needs `insert`, `pop` and a bulk removal by
+//!   predicate.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// Counters describing a [`BranchAndBound::solve`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchAndBoundStats {
+    /// Nodes created, the root included.
+    pub generated: u64,
+    /// Nodes popped and branched.
+    pub expanded: u64,
+    /// Nodes never queued, their bound no better than the incumbent.
+    pub pruned_on_arrival: u64,
+    /// Queued nodes removed because a later incumbent ruled them out.
+    pub pruned_from_frontier: u64,
+    /// Times a better solution replaced the incumbent.
+    pub incumbent_updates: u64,
+    /// Largest number of nodes queued at once.
+    pub max_frontier: usize,
+}
+
+/// A queued node with its bound; identity is its arrival number.
+#[derive(Clone)]
+struct Open<N, K> {
+    arrival: u64,
+    bound: K,
+    node: N,
+}
+
+impl<N, K> PartialEq for Open<N, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.arrival == other.arrival
+    }
+}
+
+impl<N, K> Eq for Open<N, K> {}
+
+impl<N, K> Hash for Open<N, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.arrival.hash(state);
+    }
+}
+
+/// Lowest bound first, the latest arrival first among equal bounds, so the
+/// search dives towards a first incumbent.
+struct MostPromising;
+
+impl<N, K: PartialOrd> PriorityCompare<Open<N, K>> for MostPromising {
+    fn higher_priority(&self, a: &Open<N, K>, b: &Open<N, K>) -> bool {
+        a.bound < b.bound || (a.bound == b.bound && a.arrival > b.arrival)
+    }
+}
+
+/// Best-first branch and bound minimising a cost; see the
+/// [module docs](self).
+pub struct BranchAndBound<N, K, B, L, V> {
+    d: usize,
+    branch: B,
+    bound: L,
+    evaluate: V,
+    stats: BranchAndBoundStats,
+    _types: PhantomData<fn(&N) -> K>,
+}
+
+impl<N, K, B, L, V> Debug for BranchAndBound<N, K, B, L, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("BranchAndBound")
+            .field("d", &self.d)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N, K, B, L, V, I> BranchAndBound<N, K, B, L, V>
+where
+    N: Clone,
+    K: Copy + PartialOrd,
+    B: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    L: FnMut(&N) -> K,
+    V: FnMut(&N) -> Option<K>,
+{
+    /// Explores with a frontier of arity `d`, splitting nodes with
+    /// `branch`, bounding them with `bound` and costing solutions with
+    /// `evaluate`.
+    ///
+    /// `bound(node)` must not exceed the cost of any solution below `node`,
+    /// `node` included, or an optimal solution may be pruned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, branch: B, bound: L, evaluate: V) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            d,
+            branch,
+            bound,
+            evaluate,
+            stats: BranchAndBoundStats::default(),
+            _types: PhantomData,
+        })
+    }
+
+    /// Searches the tree below `root` and returns the cheapest solution and
+    /// its cost, or `None` if no node is a solution.
+    ///
+    /// Every node is evaluated when created, so a solution is found as soon
+    /// as it is generated. A solution is still branched if its bound
+    /// promises cheaper ones below it. Among solutions of equal cost, the
+    /// first found is kept. Statistics restart with each call.
+    ///
+    /// **Time Complexity**: `O(m · d · log_d m)` for the `m` nodes queued,
+    /// plus O(f) for each incumbent update with `f` nodes queued
+    pub fn solve(&mut self, root: N) -> Option<(K, N)> {
+        self.stats = BranchAndBoundStats::default();
+        // EXPLICIT: the arity was checked in `new`.
+        let mut frontier = PriorityQueue::<_, _, NoOpStats>::new(self.d, MostPromising).ok()?;
+        let mut incumbent: Option<(K, N)> = None;
+        let mut arrival = 0;
+
+        let mut pending = vec![root];
+        loop {
+            for node in pending.drain(..) {
+                self.stats.generated += 1;
+                if let Some(cost) = (self.evaluate)(&node) {
+                    if incumbent.as_ref().is_none_or(|(best, _)| cost < *best) {
+                        self.stats.incumbent_updates += 1;
+                        let ruled_out = frontier.drain_where(|open| open.bound >= cost);
+                        self.stats.pruned_from_frontier += ruled_out.len() as u64;
+                        incumbent = Some((cost, node.clone()));
+                    }
+                }
+                let bound = (self.bound)(&node);
+                if incumbent.as_ref().is_some_and(|(best, _)| bound >= *best) {
+                    self.stats.pruned_on_arrival += 1;
+                    continue;
+                }
+                frontier.insert(Open {
+                    arrival,
+                    bound,
+                    node,
+                });
+                arrival += 1;
+            }
+            self.stats.max_frontier = self.stats.max_frontier.max(frontier.len());
+
+            let Some(Open { node, .. }) = frontier.pop() else {
+                break;
+            };
+            self.stats.expanded += 1;
+            pending.extend((self.branch)(&node));
+        }
+        incumbent
+    }
+
+    /// Counters of the last [`solve`](BranchAndBound::solve).
+    #[must_use]
+    pub const fn stats(&self) -> &BranchAndBoundStats {
+        &self.stats
+    }
+}
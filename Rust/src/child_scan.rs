@@ -0,0 +1,46 @@
+//! Child scan order for sift-down.
+//!
+//! Each level of a sift-down scans a full sibling group to find the
+//! highest-priority child. [`ChildScan`] chooses where that scan starts. The
+//! default, [`ChildScan::Leftmost`], scans left to right like the other
+//! languages. [`ChildScan::FollowBest`] starts each level's scan at the
+//! offset where the previous level found its best child, a locality
+//! heuristic for the research harness to measure across arities.
+//!
+//! Both policies make exactly one comparison per child after the first, so
+//! comparison counts are identical. They can differ only in which of several
+//! equal-priority children wins, and so in the array layout. That is why the
+//! default keeps the layout the other languages produce.
+//!
+//! There is no early-exit policy. Stopping at the first child that outranks
+//! the sifted item can leave a better sibling under it and break heap order.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{ChildScan, MinBy, PriorityQueue};
+//!
+//! let mut heap = PriorityQueue::new(8, MinBy(|x: &u32| *x)).unwrap();
+//! heap.set_child_scan(ChildScan::FollowBest);
+//! heap.insert_many((0..100).rev());
+//! assert_eq!(heap.pop_many(3), vec![0, 1, 2]);
+//! assert_eq!(heap.child_scan(), ChildScan::FollowBest);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The other languages always scan leftmost-first.
+
+/// Where a sift-down starts scanning each sibling group; see the
+/// [module docs](self).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChildScan {
+    /// Scan every sibling group left to right; ties go to the leftmost
+    /// child. Matches the other languages.
+    #[default]
+    Leftmost,
+    /// Start each level's scan at the sibling offset of the best child one
+    /// level up, wrapping around the group; ties go to the first child
+    /// scanned. The first level starts at the leftmost child.
+    FollowBest,
+}
@@ -0,0 +1,125 @@
+//! Read-only navigation of the implicit d-ary tree.
+//!
+//! The heap stores its tree implicitly in one array: the children of
+//! position `i` are `i·d + 1 ..= i·d + d` and its parent is `(i - 1) / d`. A
+//! [`Cursor`] wraps that arithmetic so visualizers, structural assertions and
+//! teaching tools can walk the tree without re-deriving it, and without any
+//! access that could break the heap invariant.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, Position, PriorityQueue};
+//!
+//! let mut pq = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+//! pq.insert_many(vec![1, 4, 2, 3, 9]);
+//!
+//! let root = pq.cursor_at(Position::ROOT).unwrap();
+//! assert_eq!(root.item(), &1);
+//! let children: Vec<i32> = root.children().map(|c| *c.item()).collect();
+//! assert_eq!(children, vec![4, 2, 3]);
+//!
+//! let leaf = root.children().next().unwrap().children().next().unwrap();
+//! assert_eq!(leaf.item(), &9);
+//! assert_eq!(leaf.parent().unwrap().position(), Position::new(1));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Other languages expose `to_array()` and leave the index
+//!   arithmetic to the caller.
+
+use crate::Position;
+
+/// A read-only view of one node of the heap's implicit tree.
+///
+/// Obtained from [`PriorityQueue::cursor_at`](crate::PriorityQueue::cursor_at).
+/// A cursor borrows the heap, so the heap cannot change while it is alive.
+#[derive(Debug)]
+pub struct Cursor<'a, T> {
+    items: &'a [T],
+    d: usize,
+    /// Array index of this node.
+    index: usize,
+}
+
+// Manual impls: derives would require `T: Clone` / `T: Copy`, but a cursor
+// only holds a shared slice.
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cursor<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns `None` unless `position` is in bounds.
+    pub(crate) fn new(items: &'a [T], d: usize, position: Position) -> Option<Self> {
+        let index = position.index();
+        (index < items.len()).then_some(Self { items, d, index })
+    }
+
+    /// The item at this node.
+    #[must_use]
+    pub fn item(&self) -> &'a T {
+        // INDEX: every constructor checks index < items.len().
+        &self.items[self.index]
+    }
+
+    /// This node's position in the backing array (as reported by
+    /// `get_position`).
+    #[must_use]
+    pub const fn position(&self) -> Position {
+        Position::new(self.index)
+    }
+
+    /// Distance from the root (the root is at level 0).
+    #[must_use]
+    pub fn level(&self) -> usize {
+        let mut level = 0;
+        let mut i = self.index;
+        // EXPLICIT: climbs parent links; a closed form needs floating-point logs.
+        while i > 0 {
+            i = (i - 1) / self.d;
+            level += 1;
+        }
+        level
+    }
+
+    /// Whether this node is the root.
+    #[must_use]
+    pub const fn is_root(&self) -> bool {
+        self.index == 0
+    }
+
+    /// Whether this node has no children.
+    #[must_use]
+    pub fn is_leaf(&self) -> bool {
+        self.first_child() >= self.items.len()
+    }
+
+    /// The parent node, or `None` at the root.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        if self.is_root() {
+            return None;
+        }
+        Self::new(self.items, self.d, Position::new((self.index - 1) / self.d))
+    }
+
+    /// The existing children of this node, leftmost first (at most `d`; the
+    /// last internal node may have fewer).
+    #[must_use]
+    pub fn children(&self) -> impl ExactSizeIterator<Item = Cursor<'a, T>> + 'a {
+        let items = self.items;
+        let d = self.d;
+        let first = self.first_child().min(items.len());
+        let last = first.saturating_add(d).min(items.len());
+        (first..last).map(move |index| Cursor { items, d, index })
+    }
+
+    fn first_child(&self) -> usize {
+        self.index.saturating_mul(self.d).saturating_add(1)
+    }
+}
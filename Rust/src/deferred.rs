@@ -0,0 +1,138 @@
+//! Deferred heapification for batch ingestion.
+//!
+//! [`PriorityQueue::insert_many`](crate::PriorityQueue::insert_many) needs
+//! the whole batch up front. When items arrive one at a time (parsed from a
+//! file, received from a channel) and nothing is read until the batch ends,
+//! sifting each one on arrival is wasted work. [`DeferredInsert`] appends
+//! them without sifting and restores heap order once, when it is dropped:
+//! with one O(n) heapify when the batch is large, or by sifting just the new
+//! items when it is small next to the heap.
+//!
+//! The guard borrows the heap mutably, so no read can observe the heap
+//! while it is out of order: the next `peek`, `front` or `pop` necessarily
+//! comes after the restore.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+//! let mut batch = heap.bulk_insert_deferred();
+//! for line in "42\n7\n19\n".lines() {
+//!     batch.push(line.parse().unwrap());
+//! }
+//! drop(batch); // heap order is restored here
+//! assert_eq!(heap.front(), &7);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages call `insert_many` on the collected batch.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use crate::{HashIndex, Position, PositionIndex, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// Appends items to a heap without sifting; heap order is restored when the
+/// guard is dropped.
+///
+/// Obtained from
+/// [`PriorityQueue::bulk_insert_deferred`](crate::PriorityQueue::bulk_insert_deferred).
+/// Leaking the guard (e.g. with `std::mem::forget`) skips the restore and
+/// leaves the heap out of order.
+pub struct DeferredInsert<'a, T, C, S, P = HashIndex>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    heap: &'a mut PriorityQueue<T, C, S, P>,
+    /// Heap length when the guard was created: items from here on are
+    /// appended and not yet sifted.
+    start: usize,
+}
+
+impl<'a, T, C, S, P> DeferredInsert<'a, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    pub(crate) fn new(heap: &'a mut PriorityQueue<T, C, S, P>) -> Self {
+        let start = heap.container.len();
+        Self { heap, start }
+    }
+
+    /// Appends `item` without sifting it.
+    ///
+    /// **Time Complexity**: O(1) amortized
+    pub fn push(&mut self, item: T) {
+        let i = Position::new(self.heap.container.len());
+        self.heap.positions.insert(&item, i);
+        self.heap.container.push(item);
+    }
+
+    /// Number of items appended through this guard.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.heap.container.len() - self.start
+    }
+}
+
+impl<T, C, S, P> Extend<T> for DeferredInsert<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, C, S, P> Drop for DeferredInsert<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    /// Restores heap order as one `insert` operation for the stats collector.
+    fn drop(&mut self) {
+        let appended = self.pending();
+        if appended == 0 {
+            return;
+        }
+        let start = self.start;
+        self.heap.bracket_from(OperationType::Insert, start, |s| {
+            if s.rebuild_pays(appended) {
+                s.heapify();
+            } else {
+                for i in start..s.container.len() {
+                    s.sift_up(i);
+                }
+            }
+        });
+    }
+}
+
+// Manual impl: the heap's comparator need not be `Debug`.
+impl<T, C, S, P> Debug for DeferredInsert<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DeferredInsert")
+            .field("pending", &self.pending())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,89 @@
+//! Item-level differences between two queues.
+//!
+//! `PartialEq` on `PriorityQueue` says whether two queues hold the same items
+//! at the same priorities;
+//! [`PriorityQueue::diff`](crate::PriorityQueue::diff) says how they differ
+//! when they do not. The [`HeapDiff`] it returns lists the items only the left
+//! queue holds, the items only the right one holds, and the items both hold
+//! at different priorities. Items are matched by identity, as everywhere
+//! else, and priorities are compared with the left queue's comparator, so
+//! two schedulers that drifted apart can be compared whatever their array
+//! layouts.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//! use std::hash::{Hash, Hasher};
+//!
+//! #[derive(Clone, Debug)]
+//! struct Job {
+//!     id: u32,
+//!     due: u32,
+//! }
+//! impl PartialEq for Job {
+//!     fn eq(&self, other: &Self) -> bool { self.id == other.id }
+//! }
+//! impl Eq for Job {}
+//! impl Hash for Job {
+//!     fn hash<H: Hasher>(&self, state: &mut H) { self.id.hash(state) }
+//! }
+//!
+//! let job = |id, due| Job { id, due };
+//! let mut primary = PriorityQueue::new(4, MinBy(|j: &Job| j.due)).unwrap();
+//! primary.insert_many([job(1, 10), job(2, 20), job(3, 30)]);
+//! let mut replica = PriorityQueue::new(4, MinBy(|j: &Job| j.due)).unwrap();
+//! replica.insert_many([job(2, 25), job(3, 30), job(4, 40)]);
+//!
+//! let diff = primary.diff(&replica);
+//! assert_eq!(diff.only_left, [&job(1, 10)]);
+//! assert_eq!(diff.only_right, [&job(4, 40)]);
+//! let (left, right) = diff.changed[0];
+//! assert_eq!((left.due, right.due), (20, 25));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None.
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+/// How two queues' contents differ, by identity; returned by
+/// [`PriorityQueue::diff`](crate::PriorityQueue::diff). See the
+/// [module docs](self).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeapDiff<'a, T> {
+    /// Items only the left queue holds, in its array order.
+    pub only_left: Vec<&'a T>,
+    /// Items only the right queue holds, in its array order.
+    pub only_right: Vec<&'a T>,
+    /// Items both queues hold at different priorities, as (left, right)
+    /// pairs in the left queue's array order.
+    pub changed: Vec<(&'a T, &'a T)>,
+}
+
+impl<T> HeapDiff<'_, T> {
+    /// Whether the two queues hold the same items at the same priorities.
+    /// Unlike `==` on the queues, this ignores their arities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// One line per difference: `- item` for the left queue only, `+ item` for
+/// the right queue only, `~ left -> right` for a changed priority.
+impl<T: Debug> Display for HeapDiff<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for item in &self.only_left {
+            writeln!(f, "- {item:?}")?;
+        }
+        for item in &self.only_right {
+            writeln!(f, "+ {item:?}")?;
+        }
+        for (left, right) in &self.changed {
+            writeln!(f, "~ {left:?} -> {right:?}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Custom rendering of a queue's items.
+//!
+//! `PriorityQueue` implements `Display` only when its items do, and prints
+//! each with that impl.
+//! [`PriorityQueue::format_with`](crate::PriorityQueue::format_with)
+//! renders the queue through a closure instead, for items with no `Display`
+//! or with one too verbose for a log line. It returns a [`DisplayAdapter`]
+//! that formats lazily, in the same `{a, b, c}` heap-array layout, so the
+//! queue is only walked if the log line is written.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! #[derive(Clone, PartialEq, Eq, Hash)]
+//! struct Job {
+//!     name: &'static str,
+//!     due: u32,
+//! }
+//!
+//! let mut jobs = PriorityQueue::new(2, MinBy(|j: &Job| j.due)).unwrap();
+//! jobs.insert(Job { name: "backup", due: 30 });
+//! jobs.insert(Job { name: "report", due: 10 });
+//!
+//! let shown = jobs.format_with(|j| format!("{}@{}", j.name, j.due));
+//! assert_eq!(shown.to_string(), "{report@10, backup@30}");
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The other languages' `toString()` takes no formatter.
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+/// Renders a queue's items through a closure; returned by
+/// [`PriorityQueue::format_with`](crate::PriorityQueue::format_with). See
+/// the [module docs](self).
+pub struct DisplayAdapter<'a, T, F> {
+    items: &'a [T],
+    format: F,
+}
+
+impl<'a, T, F> DisplayAdapter<'a, T, F>
+where
+    F: Fn(&T) -> String,
+{
+    pub(crate) const fn new(items: &'a [T], format: F) -> Self {
+        Self { items, format }
+    }
+}
+
+impl<T, F> Display for DisplayAdapter<'_, T, F>
+where
+    F: Fn(&T) -> String,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{{")?;
+        for (idx, item) in self.items.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", (self.format)(item))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Formats like `Display`, so an adapter can also go through `{:?}`.
+impl<T, F> Debug for DisplayAdapter<'_, T, F>
+where
+    F: Fn(&T) -> String,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,219 @@
+//! Timed events with cancellation tokens.
+//!
+//! An [`EventQueue`] holds events keyed by the time they are due, earliest
+//! first, with events due at the same time leaving in scheduling order.
+//! [`schedule`](EventQueue::schedule) returns a [`CancellationToken`];
+//! cancelling through it removes the event from the heap through the
+//! position map rather than leaving a tombstone to be popped and skipped,
+//! so timer-heavy code that arms and cancels many timeouts keeps the heap
+//! at the size of its live events.
+//!
+//! A token can be cancelled wherever it was handed to (another task,
+//! another thread): [`CancellationToken::cancel`] only records the event's
+//! id, and the queue removes recorded events, in `O(d · log_d n)` each,
+//! before its next read. [`EventQueue::cancel`] removes at once and returns
+//! the event.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::EventQueue;
+//!
+//! let mut timers = EventQueue::new(4).unwrap();
+//! timers.schedule(30, "retransmit");
+//! let timeout = timers.schedule(10, "connect timeout");
+//! timers.schedule(20, "keepalive");
+//!
+//! timeout.cancel(); // connected in time
+//! assert_eq!(timers.pop_due(25), Some((20, "keepalive")));
+//! assert_eq!(timers.pop_due(25), None);
+//! assert_eq!(timers.next_time(), Some(30));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Other languages can key a heap by `(time, sequence)` and
+//!   cancel with `remove` through their own position maps.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+/// A queued event's time and id; identity is the id alone.
+#[derive(Debug, Clone, Copy)]
+struct Due<T> {
+    at: T,
+    /// Scheduling order, which also breaks ties between equal times.
+    id: u64,
+}
+
+impl<T> PartialEq for Due<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Due<T> {}
+
+impl<T> Hash for Due<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Earliest first, then first scheduled.
+#[derive(Debug, Clone, Copy)]
+struct Earliest;
+
+impl<T: Ord> PriorityCompare<Due<T>> for Earliest {
+    fn higher_priority(&self, a: &Due<T>, b: &Due<T>) -> bool {
+        (&a.at, a.id) < (&b.at, b.id)
+    }
+}
+
+/// Ids cancelled through tokens, not yet removed from the queue.
+type Inbox = Mutex<Vec<u64>>;
+
+/// Events `E` due at times `T`, earliest first; see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct EventQueue<T, E>
+where
+    T: Ord + Copy,
+{
+    heap: PriorityQueue<Due<T>, Earliest, NoOpStats>,
+    /// Every queued event, by id, with its time.
+    events: HashMap<u64, (T, E)>,
+    next_id: u64,
+    cancelled: Arc<Inbox>,
+}
+
+impl<T, E> EventQueue<T, E>
+where
+    T: Ord + Copy,
+{
+    /// An empty queue over a heap of arity `d`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize) -> Result<Self, Error> {
+        Ok(Self {
+            heap: PriorityQueue::new(d, Earliest)?,
+            events: HashMap::new(),
+            next_id: 0,
+            cancelled: Arc::default(),
+        })
+    }
+
+    /// Queues `event` at time `at` and returns the token that cancels it.
+    /// The token may be dropped if the event will never be cancelled.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    pub fn schedule(&mut self, at: T, event: E) -> CancellationToken {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.heap.insert(Due { at, id });
+        self.events.insert(id, (at, event));
+        CancellationToken {
+            id,
+            queue: Arc::downgrade(&self.cancelled),
+        }
+    }
+
+    /// Removes the event `token` was returned for and returns it, or `None`
+    /// if it already left the queue or `token` is from another queue.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    pub fn cancel(&mut self, token: &CancellationToken) -> Option<E> {
+        if !Weak::ptr_eq(&token.queue, &Arc::downgrade(&self.cancelled)) {
+            return None;
+        }
+        self.remove(token.id)
+    }
+
+    fn remove(&mut self, id: u64) -> Option<E> {
+        let (at, event) = self.events.remove(&id)?;
+        self.heap.remove(&Due { at, id });
+        Some(event)
+    }
+
+    /// Removes the events cancelled through tokens since the last call.
+    fn apply_cancellations(&mut self) {
+        let ids = std::mem::take(
+            &mut *self
+                .cancelled
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        for id in ids {
+            self.remove(id);
+        }
+    }
+
+    /// Removes and returns the earliest event, whatever its time.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    pub fn pop(&mut self) -> Option<(T, E)> {
+        self.apply_cancellations();
+        let due = self.heap.pop()?;
+        self.events.remove(&due.id)
+    }
+
+    /// Removes and returns the earliest event if it is due at or before
+    /// `now`.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    pub fn pop_due(&mut self, now: T) -> Option<(T, E)> {
+        self.apply_cancellations();
+        let due = self.heap.pop_if(|due| due.at <= now)?;
+        self.events.remove(&due.id)
+    }
+
+    /// Time of the earliest event, e.g. to sleep until it. Takes `&mut self`
+    /// to drop cancelled events first.
+    ///
+    /// **Time Complexity**: O(1), plus pending cancellations
+    pub fn next_time(&mut self) -> Option<T> {
+        self.apply_cancellations();
+        self.heap.peek().map(|due| due.at)
+    }
+
+    /// Number of queued events. Takes `&mut self` to drop cancelled events
+    /// first.
+    pub fn len(&mut self) -> usize {
+        self.apply_cancellations();
+        self.events.len()
+    }
+
+    /// Whether no events are queued. Takes `&mut self` to drop cancelled
+    /// events first.
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Cancels one event scheduled on an [`EventQueue`]. Can be cloned and
+/// sent to other threads; cancelling after the event fired, after the
+/// queue was dropped, or twice does nothing.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    id: u64,
+    queue: Weak<Inbox>,
+}
+
+impl CancellationToken {
+    /// Cancels the event: the queue removes it before its next read, so it
+    /// is never returned.
+    ///
+    /// **Time Complexity**: O(1); the removal costs the queue `O(d · log_d n)`
+    pub fn cancel(&self) {
+        if let Some(inbox) = self.queue.upgrade() {
+            inbox
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(self.id);
+        }
+    }
+}
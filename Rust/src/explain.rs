@@ -0,0 +1,216 @@
+//! Step-by-step operation trace ("explain mode") for teaching material.
+//!
+//! [`ExplainTrace`] is a [`StatsCollector`] that, instead of only counting
+//! comparisons, records one [`ExplainStep`] per public heap operation: which
+//! operation ran, how many comparisons it made, and the exact sequence of
+//! position swaps it performed. Because the trace is produced by the Rust core
+//! itself, course slides and the TypeScript playground can render identical
+//! step sequences from its JSON export.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! let mut pq = PriorityQueue::with_explain(2, MinBy(|x: &i32| *x)).unwrap();
+//! pq.insert(5);
+//! pq.insert(3);
+//! assert_eq!(
+//!     pq.stats().to_json(),
+//!     r#"[{"operation":"insert","comparisons":0,"swaps":[]},{"operation":"insert","comparisons":1,"swaps":[[1,0]]}]"#
+//! );
+//! ```
+//!
+//! # Reconstructing intermediate states
+//!
+//! A step stores swaps, not array snapshots, so the trace stays independent
+//! of `T`. [`ExplainStep::replay`] rebuilds the intermediate arrays from the
+//! array as it stood when sifting began:
+//!
+//! - `insert`: the previous array with the new item appended.
+//! - `pop`: the previous array. The first swap moves the root into the last
+//!   slot; that slot is never touched again and is dropped when `pop` returns.
+//! - priority updates: the previous array with the updated item in place.
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Other languages render the Rust-produced JSON rather than
+//!   re-deriving the steps, so the sequences cannot drift apart.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt::Write as _;
+
+/// One public heap operation as recorded by [`ExplainTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainStep {
+    /// Operation that produced this step.
+    pub operation: OperationType,
+    /// Number of priority comparisons made during the operation.
+    pub comparisons: u64,
+    /// Position pairs swapped, in the order the heap swapped them.
+    pub swaps: Vec<(usize, usize)>,
+}
+
+impl ExplainStep {
+    /// Rebuilds the intermediate array states of this step.
+    ///
+    /// `start` is the backing array as it stood when sifting began (see the
+    /// module docs for what that means per operation). The result holds
+    /// `start` followed by one array per recorded swap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a recorded swap is out of bounds for `start`, i.e. `start`
+    /// is not the array this step was recorded against.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue};
+    ///
+    /// let mut pq = PriorityQueue::with_explain(2, MinBy(|x: &i32| *x)).unwrap();
+    /// pq.insert(5);
+    /// pq.insert(3);
+    /// let step = &pq.stats().steps()[1];
+    /// assert_eq!(step.replay(&[5, 3]), vec![vec![5, 3], vec![3, 5]]);
+    /// ```
+    #[must_use]
+    pub fn replay<T: Clone>(&self, start: &[T]) -> Vec<Vec<T>> {
+        let mut states = Vec::with_capacity(self.swaps.len() + 1);
+        let mut current = start.to_vec();
+        states.push(current.clone());
+        for &(i, j) in &self.swaps {
+            current.swap(i, j);
+            states.push(current.clone());
+        }
+        states
+    }
+}
+
+/// Explain-mode policy: records an [`ExplainStep`] per operation.
+///
+/// Uses interior mutability (`RefCell` / `Cell`) so the `&self` trait methods
+/// can append to the trace, like `ComparisonStats` does for its counters.
+/// Comparisons and swaps made while no operation is in flight are dropped.
+#[derive(Default, Debug, Clone)]
+pub struct ExplainTrace {
+    steps: RefCell<Vec<ExplainStep>>,
+    in_flight: Cell<bool>,
+}
+
+impl ExplainTrace {
+    /// The steps recorded so far, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn steps(&self) -> Ref<'_, Vec<ExplainStep>> {
+        self.steps.borrow()
+    }
+
+    /// Removes and returns all recorded steps, leaving the trace empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue};
+    ///
+    /// let mut pq = PriorityQueue::with_explain(2, MinBy(|x: &i32| *x)).unwrap();
+    /// pq.insert(1);
+    /// assert_eq!(pq.stats().take_steps().len(), 1);
+    /// assert!(pq.stats().steps().is_empty());
+    /// ```
+    #[must_use]
+    pub fn take_steps(&self) -> Vec<ExplainStep> {
+        self.steps.take()
+    }
+
+    /// Serialises the recorded steps as a JSON array.
+    ///
+    /// Each element has the shape
+    /// `{"operation":"<name>","comparisons":<n>,"swaps":[[i,j],...]}`, where
+    /// `<name>` is [`OperationType::as_str`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (k, step) in self.steps.borrow().iter().enumerate() {
+            if k > 0 {
+                out.push(',');
+            }
+            // EXPLICIT: writing to a `String` is infallible; the `fmt::Result`
+            // is discarded deliberately.
+            let _ = write!(
+                out,
+                r#"{{"operation":"{}","comparisons":{},"swaps":["#,
+                step.operation.as_str(),
+                step.comparisons
+            );
+            for (s, (i, j)) in step.swaps.iter().enumerate() {
+                if s > 0 {
+                    out.push(',');
+                }
+                let _ = write!(out, "[{i},{j}]");
+            }
+            out.push_str("]}");
+        }
+        out.push(']');
+        out
+    }
+
+    fn with_current(&self, f: impl FnOnce(&mut ExplainStep)) {
+        if self.in_flight.get() {
+            if let Some(step) = self.steps.borrow_mut().last_mut() {
+                f(step);
+            }
+        }
+    }
+}
+
+impl StatsCollector for ExplainTrace {
+    fn start_operation(&self, op: OperationType) {
+        self.steps.borrow_mut().push(ExplainStep {
+            operation: op,
+            comparisons: 0,
+            swaps: Vec::new(),
+        });
+        self.in_flight.set(true);
+    }
+
+    fn end_operation(&self) {
+        self.in_flight.set(false);
+    }
+
+    fn count_comparison(&self) {
+        self.with_current(|step| step.comparisons += 1);
+    }
+
+    fn total(&self) -> u64 {
+        self.steps
+            .borrow()
+            .iter()
+            .map(|step| step.comparisons)
+            .sum()
+    }
+
+    fn reset(&self) {
+        self.steps.borrow_mut().clear();
+        self.in_flight.set(false);
+    }
+
+    fn record_swap(&self, i: usize, j: usize) {
+        self.with_current(|step| step.swaps.push((i, j)));
+    }
+}
@@ -0,0 +1,211 @@
+//! Weighted-fair scheduling over per-class d-ary heaps.
+//!
+//! A [`FairQueue`] multiplexes several logical classes (tenants, request
+//! types, priority bands) over one d-ary [`PriorityQueue`] per class. Within a
+//! class, items leave in priority order; across classes, pops are shared out
+//! by deficit round robin: when a class's turn comes it is credited its
+//! weight, each pop spends one unit of credit, and the turn passes on when the
+//! credit runs out or the class empties. A class of weight `w` therefore
+//! receives `w / Σw` of the pops while it has work queued, and no class, however
+//! low its weight or its items' priorities, waits more than one round.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{FairQueue, MinBy};
+//! use std::num::NonZeroU32;
+//!
+//! let mut q = FairQueue::new(4, MinBy(|job: &u32| *job)).unwrap();
+//! q.add_class("interactive", NonZeroU32::new(3).unwrap());
+//! q.add_class("batch", NonZeroU32::new(1).unwrap());
+//! for job in 0..6 {
+//!     q.push(&"interactive", job).unwrap();
+//!     q.push(&"batch", 100 + job).unwrap();
+//! }
+//!
+//! let classes: Vec<&str> = std::iter::from_fn(|| q.pop()).take(8).map(|(c, _)| c).collect();
+//! assert_eq!(classes, ["interactive", "interactive", "interactive", "batch",
+//!                      "interactive", "interactive", "interactive", "batch"]);
+//! assert_eq!(q.stats(&"batch").unwrap().dequeued, 2);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. This is the reference "scheduler built on the heap"; other
+//!   languages can port it on top of their own queues.
+
+use crate::{Error, PriorityCompare, PriorityQueue};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::num::NonZeroU32;
+
+/// Per-class counters reported by [`FairQueue::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassStats {
+    /// Items pushed into the class.
+    pub enqueued: u64,
+    /// Items popped from the class.
+    pub dequeued: u64,
+    /// Turns the class has been given (each credits it its weight).
+    pub turns: u64,
+}
+
+#[derive(Debug)]
+struct FairClass<T, C>
+where
+    T: Eq + Hash + Clone,
+{
+    heap: PriorityQueue<T, C>,
+    weight: NonZeroU32,
+    /// Pops left in the current turn; 0 between turns.
+    deficit: u32,
+    stats: ClassStats,
+}
+
+/// Weighted-fair queue of classes `K`, each a d-ary heap of `T` ordered by
+/// `C`; see the [module docs](self).
+#[derive(Debug)]
+pub struct FairQueue<K, T, C>
+where
+    T: Eq + Hash + Clone,
+{
+    classes: HashMap<K, FairClass<T, C>>,
+    /// Classes with queued items, in service order; the front is being served.
+    active: VecDeque<K>,
+    comparator: C,
+    depth: usize,
+    len: usize,
+}
+
+impl<K, T, C> FairQueue<K, T, C>
+where
+    K: Eq + Hash + Clone,
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T> + Clone,
+{
+    /// Creates an empty queue. Each class heap has arity `d` and a clone of
+    /// `comparator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            classes: HashMap::new(),
+            active: VecDeque::new(),
+            comparator,
+            depth: d,
+            len: 0,
+        })
+    }
+
+    /// Registers `class` with `weight`, or changes the weight of an existing
+    /// class (taking effect from its next turn; queued items are kept).
+    pub fn add_class(&mut self, class: K, weight: NonZeroU32) {
+        if let Some(existing) = self.classes.get_mut(&class) {
+            existing.weight = weight;
+            return;
+        }
+        // EXPLICIT: skips `new`'s arity check, which `FairQueue::new` has
+        // already performed.
+        let heap = PriorityQueue::with_checked_arity(self.depth, self.comparator.clone());
+        self.classes.insert(
+            class,
+            FairClass {
+                heap,
+                weight,
+                deficit: 0,
+                stats: ClassStats::default(),
+            },
+        );
+    }
+
+    /// Queues `item` in `class`.
+    ///
+    /// As in [`PriorityQueue::insert`], an item's identity must be unique
+    /// within its class.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if `class` has not been registered with
+    /// `add_class`.
+    pub fn push(&mut self, class: &K, item: T) -> Result<(), Error> {
+        let entry = self.classes.get_mut(class).ok_or(Error::ItemNotFound)?;
+        if entry.heap.is_empty() {
+            self.active.push_back(class.clone());
+        }
+        entry.heap.insert(item);
+        entry.stats.enqueued += 1;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the next item in weighted-fair order: the highest-priority
+    /// item of the class whose turn it is. Returns `None` when every class
+    /// is empty.
+    ///
+    /// **Time Complexity**: `O(d · log_d m)` for a class holding `m` items
+    pub fn pop(&mut self) -> Option<(K, T)> {
+        let class = self.active.front()?.clone();
+        // INDEX: `active` only holds registered classes with queued items.
+        let entry = self.classes.get_mut(&class)?;
+        if entry.deficit == 0 {
+            entry.deficit = entry.weight.get();
+            entry.stats.turns += 1;
+        }
+        let item = entry.heap.pop()?;
+        entry.deficit -= 1;
+        entry.stats.dequeued += 1;
+        self.len -= 1;
+
+        if entry.heap.is_empty() {
+            // An emptied class forfeits the rest of its turn.
+            entry.deficit = 0;
+            self.active.pop_front();
+        } else if entry.deficit == 0 {
+            self.active.rotate_left(1);
+        }
+        Some((class, item))
+    }
+
+    /// The item the next `pop` would return, with its class.
+    #[must_use]
+    pub fn peek(&self) -> Option<(&K, &T)> {
+        let class = self.active.front()?;
+        let entry = self.classes.get(class)?;
+        entry.heap.peek().map(|item| (class, item))
+    }
+
+    /// Total number of queued items across all classes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether every class is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of items queued in `class`, or `None` if it is not registered.
+    #[must_use]
+    pub fn class_len(&self, class: &K) -> Option<usize> {
+        self.classes.get(class).map(|entry| entry.heap.len())
+    }
+
+    /// The weight of `class`, or `None` if it is not registered.
+    #[must_use]
+    pub fn weight(&self, class: &K) -> Option<NonZeroU32> {
+        self.classes.get(class).map(|entry| entry.weight)
+    }
+
+    /// Counters for `class`, or `None` if it is not registered.
+    #[must_use]
+    pub fn stats(&self, class: &K) -> Option<ClassStats> {
+        self.classes.get(class).map(|entry| entry.stats)
+    }
+}
@@ -0,0 +1,376 @@
+//! Heaps over partially ordered priorities.
+//!
+//! A [`PriorityCompare`] must order every pair of items; given a `NaN`
+//! cost, or two versions no scheme can rank against each other, it has no
+//! right answer, and whatever it returns quietly leaves the heap in an order
+//! no later operation can repair. [`TryPriorityCompare`] lets the comparison
+//! say so: `try_higher_priority` returns `None` for an incomparable pair.
+//!
+//! [`TryPriorityQueue`] is a heap over such a comparator. Before each
+//! mutation it replays, read-only, the exact comparisons the mutation would
+//! make; if one of them is incomparable, it returns [`Error::Incomparable`]
+//! and leaves the heap untouched. Otherwise it runs the mutation, which then
+//! meets only the pairs just checked. Every operation therefore costs about
+//! twice the comparisons of its [`PriorityQueue`] counterpart, and the heap
+//! is always consistent with the comparisons it has made.
+//!
+//! Only pairs an operation actually compares are checked: an item may sit in
+//! the heap beside one it cannot be ranked against, and a later `pop` that
+//! needs that pair fails, leaving the heap as it was.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{Error, TryMinBy, TryPriorityQueue};
+//! use std::hash::{Hash, Hasher};
+//!
+//! // Jobs identified by id, costed in floating point
+//! #[derive(Clone, Debug)]
+//! struct Job { id: u32, cost: f64 }
+//! impl PartialEq for Job { fn eq(&self, o: &Self) -> bool { self.id == o.id } }
+//! impl Eq for Job {}
+//! impl Hash for Job { fn hash<H: Hasher>(&self, h: &mut H) { self.id.hash(h) } }
+//!
+//! let mut jobs = TryPriorityQueue::new(2, TryMinBy(|j: &Job| j.cost)).unwrap();
+//! jobs.insert(Job { id: 1, cost: 2.5 }).unwrap();
+//! jobs.insert(Job { id: 2, cost: 0.5 }).unwrap();
+//! assert_eq!(jobs.insert(Job { id: 3, cost: f64::NAN }), Err(Error::Incomparable));
+//! assert_eq!(
+//!     jobs.update_priority(&Job { id: 1, cost: f64::NAN }),
+//!     Err(Error::Incomparable)
+//! );
+//!
+//! assert_eq!(jobs.len(), 2);
+//! assert_eq!(jobs.pop().unwrap().map(|j| j.id), Some(2));
+//! assert_eq!(jobs.peek().map(|j| j.cost), Some(2.5));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. C++, Go, Zig and TypeScript comparators return a plain boolean.
+
+use crate::{Error, NoOpStats, Position, PriorityCompare, PriorityQueue};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::Hash;
+
+/// A comparison that can fail on incomparable pairs.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::TryPriorityCompare;
+///
+/// /// Versions `(scheme, number)` rank only within a scheme.
+/// struct Newest;
+/// impl TryPriorityCompare<(char, u32)> for Newest {
+///     fn try_higher_priority(&self, a: &(char, u32), b: &(char, u32)) -> Option<bool> {
+///         (a.0 == b.0).then(|| a.1 > b.1)
+///     }
+/// }
+/// assert_eq!(Newest.try_higher_priority(&('v', 2), &('v', 1)), Some(true));
+/// assert_eq!(Newest.try_higher_priority(&('v', 2), &('r', 1)), None);
+/// ```
+pub trait TryPriorityCompare<T> {
+    /// `Some(true)` if `a` should come before `b` in the heap, `Some(false)`
+    /// if not, and `None` if the two cannot be compared.
+    fn try_higher_priority(&self, a: &T, b: &T) -> Option<bool>;
+}
+
+/// Fallible comparator for min-heaps over a `PartialOrd` key: smaller keys
+/// first, `None` where `partial_cmp` is `None` (e.g. `NaN`).
+#[derive(Clone, Copy)]
+pub struct TryMinBy<F>(pub F);
+
+// Manual impl: closures are not `Debug`.
+impl<F> Debug for TryMinBy<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("TryMinBy").finish_non_exhaustive()
+    }
+}
+
+impl<T, F, K> TryPriorityCompare<T> for TryMinBy<F>
+where
+    F: Fn(&T) -> K,
+    K: PartialOrd,
+{
+    #[inline]
+    fn try_higher_priority(&self, a: &T, b: &T) -> Option<bool> {
+        (self.0)(a)
+            .partial_cmp(&(self.0)(b))
+            .map(|order| order == Ordering::Less)
+    }
+}
+
+/// Fallible comparator for max-heaps over a `PartialOrd` key: larger keys
+/// first, `None` where `partial_cmp` is `None` (e.g. `NaN`).
+#[derive(Clone, Copy)]
+pub struct TryMaxBy<F>(pub F);
+
+// Manual impl: closures are not `Debug`.
+impl<F> Debug for TryMaxBy<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("TryMaxBy").finish_non_exhaustive()
+    }
+}
+
+impl<T, F, K> TryPriorityCompare<T> for TryMaxBy<F>
+where
+    F: Fn(&T) -> K,
+    K: PartialOrd,
+{
+    #[inline]
+    fn try_higher_priority(&self, a: &T, b: &T) -> Option<bool> {
+        (self.0)(a)
+            .partial_cmp(&(self.0)(b))
+            .map(|order| order == Ordering::Greater)
+    }
+}
+
+/// Adapts a fallible comparator for the inner heap. It is only shown pairs
+/// the precheck has already compared, so the fallback is never taken.
+struct Checked<C>(C);
+
+impl<T, C: TryPriorityCompare<T>> PriorityCompare<T> for Checked<C> {
+    #[inline]
+    fn higher_priority(&self, a: &T, b: &T) -> bool {
+        self.0.try_higher_priority(a, b).unwrap_or(false)
+    }
+}
+
+/// A read-only replay of a mutation's sifts: the slots the mutation would
+/// have written so far, over the unchanged backing array.
+struct Replay<'a, T, C> {
+    items: &'a [T],
+    written: HashMap<usize, &'a T>,
+    len: usize,
+    depth: usize,
+    comparator: &'a C,
+}
+
+impl<'a, T, C: TryPriorityCompare<T>> Replay<'a, T, C> {
+    fn new(items: &'a [T], depth: usize, comparator: &'a C) -> Self {
+        Self {
+            items,
+            written: HashMap::new(),
+            len: items.len(),
+            depth,
+            comparator,
+        }
+    }
+
+    fn get(&self, i: usize) -> &'a T {
+        self.written
+            .get(&i)
+            .copied()
+            .unwrap_or_else(|| &self.items[i])
+    }
+
+    fn set(&mut self, i: usize, item: &'a T) {
+        self.written.insert(i, item);
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        let (a, b) = (self.get(i), self.get(j));
+        self.set(i, b);
+        self.set(j, a);
+    }
+
+    fn compare(&self, i: usize, j: usize) -> Result<bool, Error> {
+        self.comparator
+            .try_higher_priority(self.get(i), self.get(j))
+            .ok_or(Error::Incomparable)
+    }
+
+    /// Mirrors `PriorityQueue::sift_up`.
+    fn sift_up(&mut self, mut i: usize) -> Result<(), Error> {
+        while i > 0 {
+            let p = (i - 1) / self.depth;
+            if !self.compare(i, p)? {
+                break;
+            }
+            self.swap(i, p);
+            i = p;
+        }
+        Ok(())
+    }
+
+    /// Mirrors `PriorityQueue::sift_down` and its best-child scan.
+    fn sift_down(&mut self, mut i: usize) -> Result<(), Error> {
+        loop {
+            let left = i * self.depth + 1;
+            if left >= self.len {
+                return Ok(());
+            }
+            let right = ((i + 1) * self.depth).min(self.len - 1);
+            let mut best = left;
+            for p in (left + 1)..=right {
+                if self.compare(p, best)? {
+                    best = p;
+                }
+            }
+            if !self.compare(best, i)? {
+                return Ok(());
+            }
+            self.swap(i, best);
+            i = best;
+        }
+    }
+}
+
+/// A d-ary heap over a [`TryPriorityCompare`]; see the [module docs](self).
+pub struct TryPriorityQueue<T, C>
+where
+    T: Eq + Clone,
+{
+    heap: PriorityQueue<T, Checked<C>, NoOpStats>,
+}
+
+impl<T: Eq + Clone + Debug, C> Debug for TryPriorityQueue<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TryPriorityQueue")
+            .field("container", &self.heap.container)
+            .field("d", &self.heap.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, C> TryPriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone,
+    C: TryPriorityCompare<T>,
+{
+    /// Creates an empty heap of arity `d` ordered by `comparator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+        Ok(Self {
+            heap: PriorityQueue::new(d, Checked(comparator))?,
+        })
+    }
+
+    fn replay(&self) -> Replay<'_, T, C> {
+        Replay::new(
+            &self.heap.container,
+            self.heap.depth,
+            &self.heap.comparator.0,
+        )
+    }
+
+    /// Inserts `item`.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Incomparable`, and leaves the heap unchanged, if
+    /// `item` cannot be compared with an item it would be sifted past.
+    pub fn insert(&mut self, item: T) -> Result<(), Error> {
+        let mut replay = self.replay();
+        replay.len += 1;
+        replay.set(replay.len - 1, &item);
+        replay.sift_up(replay.len - 1)?;
+        self.heap.insert(item);
+        Ok(())
+    }
+
+    /// Removes and returns the highest-priority item, or `Ok(None)` if the
+    /// heap is empty.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Incomparable`, and leaves the heap unchanged, if
+    /// restoring heap order needs a comparison between incomparable items.
+    pub fn pop(&mut self) -> Result<Option<T>, Error> {
+        if self.heap.is_empty() {
+            return Ok(None);
+        }
+        let mut replay = self.replay();
+        replay.swap(0, replay.len - 1);
+        replay.len -= 1;
+        replay.sift_down(0)?;
+        Ok(self.heap.pop())
+    }
+
+    /// Replaces the stored copy of `updated_item` (matched by identity) and
+    /// moves it in whichever direction its new priority requires.
+    ///
+    /// **Time Complexity**: `O((d+1) · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// - `Error::ItemNotFound` if the item is not in the heap.
+    /// - `Error::Incomparable` if the new priority cannot be compared with
+    ///   an item it would be sifted past. The heap is left unchanged.
+    pub fn update_priority(&mut self, updated_item: &T) -> Result<(), Error> {
+        let i = self
+            .heap
+            .get_position(updated_item)
+            .ok_or(Error::ItemNotFound)?
+            .index();
+        let mut replay = self.replay();
+        replay.set(i, updated_item);
+        replay.sift_up(i)?;
+        replay.sift_down(i)?;
+        self.heap.update_priority(updated_item)
+    }
+
+    /// The highest-priority item, or `None` if empty.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Whether `item` (by identity) is in the heap.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn contains(&self, item: &T) -> bool {
+        self.heap.contains(item)
+    }
+
+    /// The position of `item` (by identity), or `None`.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn get_position(&self, item: &T) -> Option<Position> {
+        self.heap.get_position(item)
+    }
+
+    /// Number of items in the heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether the heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The arity of the heap.
+    #[must_use]
+    pub fn d(&self) -> usize {
+        self.heap.d()
+    }
+
+    /// The items in heap order (not sorted); index 0 is the front.
+    #[must_use]
+    pub fn to_array(&self) -> Vec<T> {
+        self.heap.to_array()
+    }
+
+    /// Removes every item, keeping the arity.
+    pub fn clear(&mut self) {
+        // EXPLICIT: `None` keeps the arity, which cannot fail.
+        let _ = self.heap.clear(None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Per-operation sift histograms, for research into how arity shapes cost.
+//!
+//! Comparison totals (see [`ComparisonStats`]) say how much work a workload
+//! did; they do not say *where*. [`HistogramStats`] is a [`StatsCollector`]
+//! that additionally buckets, per operation type:
+//!
+//! - **sift-up depth**: levels an item climbed in one sift-up,
+//! - **sift-down depth**: levels an item descended in one sift-down,
+//! - **children scanned**: siblings compared in one best-child selection.
+//!
+//! With `d` children per node, a sift-down of depth `k` scans about `k·d`
+//! children, so these three distributions separate the two factors behind the
+//! `O(d·log_d n)` pop cost on a real workload.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, OperationType, PriorityQueue};
+//!
+//! let mut pq = PriorityQueue::with_histograms(4, MinBy(|x: &i32| *x)).unwrap();
+//! for v in (0..20).rev() {
+//!     pq.insert(v);
+//! }
+//! pq.pop();
+//!
+//! let pop = pq.stats().histogram(OperationType::Pop);
+//! assert_eq!(pop.sift_down_depth.iter().sum::<u64>(), 1);
+//! assert_eq!(pop.total_children_scanned(), pq.stats().comparisons().pop());
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Other languages report comparison totals only.
+
+use crate::instrumentation::{ComparisonStats, OperationType, StatsCollector};
+use std::cell::{Cell, RefCell};
+
+/// Distributions recorded for one operation type.
+///
+/// Each vector is indexed by value and holds how many times that value was
+/// observed: `sift_down_depth[2] == 7` means seven sift-downs moved their item
+/// exactly two levels. Vectors grow on demand and have no trailing zeros.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiftHistogram {
+    /// Levels moved per sift-up.
+    pub sift_up_depth: Vec<u64>,
+    /// Levels moved per sift-down.
+    pub sift_down_depth: Vec<u64>,
+    /// Children compared per best-child selection.
+    pub children_scanned: Vec<u64>,
+}
+
+impl SiftHistogram {
+    /// Total children scanned, `Σ k · children_scanned[k]`.
+    ///
+    /// This is also the number of comparisons the sift-downs made: picking
+    /// the best of `k` children takes `k - 1`, and comparing the winner
+    /// against the sifting item takes one more.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::SiftHistogram;
+    ///
+    /// let h = SiftHistogram { children_scanned: vec![0, 1, 0, 2], ..Default::default() };
+    /// assert_eq!(h.total_children_scanned(), 7);
+    /// ```
+    #[must_use]
+    pub fn total_children_scanned(&self) -> u64 {
+        weighted_sum(&self.children_scanned)
+    }
+
+    /// Mean levels moved per sift-down, or `None` if none were recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_sift_down_depth(&self) -> Option<f64> {
+        let count: u64 = self.sift_down_depth.iter().sum();
+        (count > 0).then(|| weighted_sum(&self.sift_down_depth) as f64 / count as f64)
+    }
+
+    /// Mean levels moved per sift-up, or `None` if none were recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_sift_up_depth(&self) -> Option<f64> {
+        let count: u64 = self.sift_up_depth.iter().sum();
+        (count > 0).then(|| weighted_sum(&self.sift_up_depth) as f64 / count as f64)
+    }
+}
+
+/// All five per-operation histograms, as returned by
+/// [`HistogramStats::snapshot`]. A plain-data export: every field is public.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiftHistograms {
+    /// `insert` / `insert_many`.
+    pub insert: SiftHistogram,
+    /// `pop`.
+    pub pop: SiftHistogram,
+    /// `decrease_priority` / `decrease_priority_by_index`.
+    pub decrease_priority: SiftHistogram,
+    /// `increase_priority` / `increase_priority_by_index`.
+    pub increase_priority: SiftHistogram,
+    /// `update_priority` / `update_priority_by_index`.
+    pub update_priority: SiftHistogram,
+}
+
+impl SiftHistograms {
+    fn get_mut(&mut self, op: OperationType) -> Option<&mut SiftHistogram> {
+        match op {
+            OperationType::Insert => Some(&mut self.insert),
+            OperationType::Pop => Some(&mut self.pop),
+            OperationType::DecreasePriority => Some(&mut self.decrease_priority),
+            OperationType::IncreasePriority => Some(&mut self.increase_priority),
+            OperationType::UpdatePriority => Some(&mut self.update_priority),
+            // INDEX: nothing is in flight; matches ComparisonStats dropping
+            // unattributed comparisons.
+            OperationType::None => None,
+        }
+    }
+}
+
+/// Histogram policy: comparison counts plus per-operation [`SiftHistogram`]s.
+///
+/// Uses interior mutability (`RefCell` / `Cell`) so the `&self` trait methods
+/// can record, like `ComparisonStats` does for its counters. Observations made
+/// while no operation is in flight are dropped.
+#[derive(Default, Debug, Clone)]
+pub struct HistogramStats {
+    comparisons: ComparisonStats,
+    histograms: RefCell<SiftHistograms>,
+    current_op: Cell<OperationType>,
+}
+
+impl HistogramStats {
+    /// The comparison counters, bucketed exactly as `ComparisonStats`.
+    #[must_use]
+    pub const fn comparisons(&self) -> &ComparisonStats {
+        &self.comparisons
+    }
+
+    /// A copy of the histograms for one operation type (empty for
+    /// `OperationType::None`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn histogram(&self, op: OperationType) -> SiftHistogram {
+        self.snapshot()
+            .get_mut(op)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// A copy of all five per-operation histograms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn snapshot(&self) -> SiftHistograms {
+        self.histograms.borrow().clone()
+    }
+
+    fn record(&self, value: usize, pick: impl FnOnce(&mut SiftHistogram) -> &mut Vec<u64>) {
+        let mut histograms = self.histograms.borrow_mut();
+        if let Some(h) = histograms.get_mut(self.current_op.get()) {
+            let buckets = pick(h);
+            if buckets.len() <= value {
+                buckets.resize(value + 1, 0);
+            }
+            // INDEX: resized above so `value` is in bounds.
+            buckets[value] += 1;
+        }
+    }
+}
+
+impl StatsCollector for HistogramStats {
+    fn start_operation(&self, op: OperationType) {
+        self.comparisons.start_operation(op);
+        self.current_op.set(op);
+    }
+
+    fn end_operation(&self) {
+        self.comparisons.end_operation();
+        self.current_op.set(OperationType::None);
+    }
+
+    fn count_comparison(&self) {
+        self.comparisons.count_comparison();
+    }
+
+    fn total(&self) -> u64 {
+        self.comparisons.total()
+    }
+
+    fn reset(&self) {
+        self.comparisons.reset();
+        self.histograms.take();
+        self.current_op.set(OperationType::None);
+    }
+
+    fn record_sift_up(&self, levels: usize) {
+        self.record(levels, |h| &mut h.sift_up_depth);
+    }
+
+    fn record_sift_down(&self, levels: usize) {
+        self.record(levels, |h| &mut h.sift_down_depth);
+    }
+
+    fn record_children_scanned(&self, count: usize) {
+        self.record(count, |h| &mut h.children_scanned);
+    }
+}
+
+fn weighted_sum(buckets: &[u64]) -> u64 {
+    (0u64..).zip(buckets).map(|(k, &n)| k * n).sum()
+}
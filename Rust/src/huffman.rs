@@ -0,0 +1,138 @@
+//! Huffman coding on a d-ary min-heap.
+//!
+//! [`code_table`] builds an optimal prefix code from symbol frequencies with
+//! the textbook algorithm: keep one heap entry per subtree, weighted by the
+//! total frequency of its symbols, and repeatedly merge the two lightest.
+//! Each merge is a `pop` and a `push_pop`: the merged subtree takes the
+//! second-lightest one's place at the root, so it costs one sift-down
+//! rather than a second pop and an insert.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::huffman::code_table;
+//!
+//! let table = code_table([('a', 45), ('b', 13), ('c', 12), ('d', 16), ('e', 9), ('f', 5)]);
+//! assert_eq!(table[&'a'], "0");
+//! assert_eq!(table[&'c'], "100");
+//! assert_eq!(table[&'b'], "101");
+//! assert_eq!(table[&'f'], "1100");
+//! assert_eq!(table[&'e'], "1101");
+//! assert_eq!(table[&'d'], "111");
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages can run the same loop with `pop` and `insert`.
+
+use crate::{DenseIndex, MinBy, PriorityQueue, DEFAULT_ARITY};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+const OVERFLOW: &str = "frequencies sum past u64::MAX";
+
+/// A subtree queued at its total weight. Ids `0..n` are the leaves, in
+/// first-appearance order, and merged subtrees take the next ones, so ties
+/// on weight break the same way on every run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Subtree {
+    weight: u64,
+    id: usize,
+}
+
+/// An optimal prefix code for `frequencies`, as `'0'`/`'1'` strings: no
+/// code is a prefix of another, and no prefix code encodes the symbols in
+/// fewer total bits. Among subtrees of equal weight, the one first seen
+/// goes to the `'0'` branch.
+///
+/// A symbol listed more than once has its frequencies added. A lone symbol
+/// gets the code `"0"`; no symbols give an empty table.
+///
+/// **Time Complexity**: `O(n · d · log_d n)` for n distinct symbols, plus
+/// the total length of the codes
+///
+/// # Panics
+///
+/// Panics if the frequencies sum past `u64::MAX`.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::huffman::code_table;
+///
+/// let text = "abracadabra";
+/// let table = code_table(text.chars().map(|c| (c, 1)));
+/// let bits: usize = text.chars().map(|c| table[&c].len()).sum();
+/// assert_eq!(bits, 23); // 5 a's × 1 bit, 6 others × 3 bits
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+#[must_use]
+pub fn code_table<S, I>(frequencies: I) -> HashMap<S, String>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut ids: HashMap<S, usize> = HashMap::new();
+    let mut leaves: Vec<(S, u64)> = Vec::new();
+    for (symbol, weight) in frequencies {
+        match ids.entry(symbol.clone()) {
+            Entry::Occupied(id) => {
+                // INDEX: `ids` only holds indices into `leaves`.
+                let total = &mut leaves[*id.get()].1;
+                *total = total.checked_add(weight).expect(OVERFLOW);
+            }
+            Entry::Vacant(id) => {
+                id.insert(leaves.len());
+                leaves.push((symbol, weight));
+            }
+        }
+    }
+    let n = leaves.len();
+    if n == 1 {
+        return leaves.into_iter().map(|(s, _)| (s, "0".into())).collect();
+    }
+
+    let index = DenseIndex::with_capacity(|s: &Subtree| s.id, 2 * n);
+    let by_weight = MinBy(|s: &Subtree| (s.weight, s.id));
+    let mut heap = PriorityQueue::with_position_index(DEFAULT_ARITY, by_weight, index)
+        .expect("DEFAULT_ARITY is positive");
+    heap.insert_many(
+        leaves
+            .iter()
+            .enumerate()
+            .map(|(id, &(_, weight))| Subtree { weight, id }),
+    );
+    // `children[k]` holds the ('0', '1') subtrees merged into id `n + k`
+    let mut children = Vec::with_capacity(n.saturating_sub(1));
+    while heap.len() > 1 {
+        let Some(lightest) = heap.pop() else { break };
+        let next = *heap.front();
+        let merged = Subtree {
+            weight: lightest.weight.checked_add(next.weight).expect(OVERFLOW),
+            id: n + children.len(),
+        };
+        children.push((lightest.id, next.id));
+        // `merged` outweighs `next`, or ties it with a larger id, so it
+        // replaces `next` at the root.
+        heap.push_pop(merged);
+    }
+
+    let mut table = HashMap::with_capacity(n);
+    let mut pending = Vec::new();
+    if let Some(root) = heap.pop() {
+        pending.push((root.id, String::new()));
+    }
+    while let Some((id, code)) = pending.pop() {
+        if id < n {
+            table.insert(leaves[id].0.clone(), code);
+        } else {
+            // INDEX: merged ids run over `n..n + children.len()`.
+            let (zero, one) = children[id - n];
+            pending.push((one, code.clone() + "1"));
+            pending.push((zero, code + "0"));
+        }
+    }
+    table
+}
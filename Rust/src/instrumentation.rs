@@ -63,6 +63,30 @@ pub enum OperationType {
     UpdatePriority,
 }
 
+impl OperationType {
+    /// Stable `snake_case` name of the operation, as used in the
+    /// [`ExplainTrace`](crate::ExplainTrace) JSON output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::OperationType;
+    ///
+    /// assert_eq!(OperationType::DecreasePriority.as_str(), "decrease_priority");
+    /// ```
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Insert => "insert",
+            Self::Pop => "pop",
+            Self::DecreasePriority => "decrease_priority",
+            Self::IncreasePriority => "increase_priority",
+            Self::UpdatePriority => "update_priority",
+        }
+    }
+}
+
 /// Trait the heap drives during operations.
 ///
 /// All methods take `&self` (not `&mut self`) so the heap's `&self` query
@@ -86,6 +110,56 @@ pub trait StatsCollector {
 
     /// Zero all counters and reset the active-operation tag.
     fn reset(&self);
+
+    /// Record that the heap swapped the items at positions `i` and `j` of its
+    /// backing array. Called once per swap performed during sift-up /
+    /// sift-down (and for the root/last exchange in `pop`).
+    ///
+    /// Defaults to a no-op so comparison-only collectors need not implement
+    /// it; [`ExplainTrace`](crate::ExplainTrace) overrides it to build its
+    /// step-by-step record.
+    #[inline]
+    fn record_swap(&self, _i: usize, _j: usize) {}
+
+    /// Record the number of items in the heap as an operation starts. Called
+    /// once per operation, right after `start_operation`.
+    ///
+    /// Defaults to a no-op; [`ProfileTrace`](crate::ProfileTrace) overrides
+    /// it to report cost against heap size.
+    #[inline]
+    fn record_len(&self, _len: usize) {}
+
+    /// Record the number of items in the heap as an operation ends. Called
+    /// once per operation, right before `end_operation`, and by `clear`.
+    ///
+    /// Defaults to a no-op; `MetricsStats` (`metrics` feature) overrides it
+    /// to publish the queue length and count the items inserted or removed.
+    #[inline]
+    fn record_final_len(&self, _len: usize) {}
+
+    /// Record that a sift-up finished after moving its item `levels` levels
+    /// towards the root (0 when the item was already in place).
+    ///
+    /// Defaults to a no-op; [`HistogramStats`](crate::HistogramStats)
+    /// overrides it.
+    #[inline]
+    fn record_sift_up(&self, _levels: usize) {}
+
+    /// Record that a sift-down finished after moving its item `levels` levels
+    /// towards the leaves (0 when the item was already in place).
+    ///
+    /// Defaults to a no-op; [`HistogramStats`](crate::HistogramStats)
+    /// overrides it.
+    #[inline]
+    fn record_sift_down(&self, _levels: usize) {}
+
+    /// Record that one best-child selection scanned `count` children
+    /// (between 1 and `d`; fewer than `d` only at the last internal node).
+    ///
+    /// Defaults to a no-op; [`HistogramStats`](crate::HistogramStats)
+    /// overrides it.
+    #[inline]
+    fn record_children_scanned(&self, _count: usize) {}
 }
 
 /// Zero-sized policy: every method is an empty no-op.
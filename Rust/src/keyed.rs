@@ -0,0 +1,525 @@
+//! Queues whose keys and priorities are separate values.
+//!
+//! A `PriorityQueue` reads both identity and priority from its items:
+//! identity through `T`'s `Eq` and `Hash`, priority through the comparator.
+//! Items therefore need hand-written `Eq` and `Hash` impls that ignore the
+//! priority field, and a derived impl that includes it compiles but loses
+//! track of every item whose priority changes.
+//!
+//! [`KeyedPriorityQueue<K, P, C>`](KeyedPriorityQueue) takes the key and the
+//! priority as separate arguments, as `insert(key, priority)`, and orders
+//! the priorities with a comparator over `P` alone. Any `K: Eq + Hash`
+//! works as a key, derived impls included, and priorities are read and
+//! changed through the key: [`get_priority`](KeyedPriorityQueue::get_priority),
+//! [`change_priority`](KeyedPriorityQueue::change_priority). Membership is
+//! listed without popping by [`keys`](KeyedPriorityQueue::keys), and with
+//! the priorities by [`items`](KeyedPriorityQueue::items). Underneath it is
+//! a `PriorityQueue` of key–priority entries identified by key, so every
+//! operation costs what the same operation costs there.
+//!
+//! A `HashMap<K, P>` of priorities converts into a queue with `From`, at
+//! [`DEFAULT_ARITY`] and the comparator's `Default`, and a queue converts
+//! back into a `HashMap<K, P>`.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{KeyedPriorityQueue, MinBy};
+//!
+//! let mut frontier = KeyedPriorityQueue::new(4, MinBy(|cost: &u32| *cost)).unwrap();
+//! frontier.insert("b", 7);
+//! frontier.insert("c", 9);
+//! frontier.insert("d", 4);
+//!
+//! // A shorter path to c: change its priority through the key
+//! assert_eq!(frontier.change_priority(&"c", 3), Ok(9));
+//! assert_eq!(frontier.get_priority(&"c"), Some(&3));
+//! assert_eq!(frontier.pop(), Some(("c", 3)));
+//! assert_eq!(frontier.peek(), Some((&"d", &4)));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The other languages identify items by their own equality, as
+//!   `PriorityQueue` does.
+
+use crate::{
+    Error, NoOpStats, OperationType, Position, PriorityCompare, PriorityQueue, DEFAULT_ARITY,
+};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::AddAssign;
+
+/// A key and its priority; identity is the key alone.
+///
+/// The position index hashes and compares the key alone, so a priority
+/// can be overwritten in the heap array without telling the index.
+#[derive(Debug, Clone)]
+struct Entry<K, P> {
+    key: K,
+    priority: P,
+}
+
+impl<K: PartialEq, P> PartialEq for Entry<K, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Eq, P> Eq for Entry<K, P> {}
+
+impl<K: Hash, P> Hash for Entry<K, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// Lets the position index be searched by key: an entry hashes and compares
+/// as its key.
+impl<K, P> Borrow<K> for Entry<K, P> {
+    fn borrow(&self) -> &K {
+        &self.key
+    }
+}
+
+/// Orders entries by priority alone.
+#[derive(Debug, Clone, Copy)]
+struct ByPriority<C>(C);
+
+impl<K, P, C: PriorityCompare<P>> PriorityCompare<Entry<K, P>> for ByPriority<C> {
+    #[inline]
+    fn higher_priority(&self, a: &Entry<K, P>, b: &Entry<K, P>) -> bool {
+        self.0.higher_priority(&a.priority, &b.priority)
+    }
+}
+
+/// Keys `K` queued at priorities `P`, ordered by a comparator over `P`; see
+/// the [module docs](self).
+#[derive(Debug)]
+pub struct KeyedPriorityQueue<K, P, C>
+where
+    K: Eq + Clone,
+    P: Clone,
+{
+    heap: PriorityQueue<Entry<K, P>, ByPriority<C>, NoOpStats>,
+}
+
+impl<K, P, C> KeyedPriorityQueue<K, P, C>
+where
+    K: Eq + Hash + Clone,
+    P: Clone,
+    C: PriorityCompare<P>,
+{
+    /// Creates an empty queue of arity `d` whose priorities are ordered by
+    /// `comparator`.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArity`] if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{Error, KeyedPriorityQueue, MinBy};
+    ///
+    /// let queue = KeyedPriorityQueue::<&str, u32, _>::new(4, MinBy(|p: &u32| *p)).unwrap();
+    /// assert!(queue.is_empty());
+    /// assert_eq!(queue.d(), 4);
+    /// assert!(matches!(KeyedPriorityQueue::<&str, u32, _>::new(0, MinBy(|p: &u32| *p)), Err(Error::InvalidArity)));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+        Ok(Self {
+            heap: PriorityQueue::new(d, ByPriority(comparator))?,
+        })
+    }
+
+    /// Position of `key` in the heap array.
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.heap
+            .positions
+            .get_by(key, &self.heap.container)
+            .map(Position::index)
+    }
+
+    /// Queues `key` at `priority`. If `key` is already queued, its priority
+    /// is changed instead, as by [`change_priority`](Self::change_priority),
+    /// and the old priority is returned.
+    ///
+    /// **Time Complexity**: `O(log_d n)`; `O((d+1) · log_d n)` when `key` is
+    /// already queued
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// assert_eq!(queue.insert("a", 5), None);
+    /// assert_eq!(queue.insert("a", 2), Some(5)); // already queued: priority changed
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn insert(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(i) = self.index_of(&key) {
+            return Some(self.set_priority_at(i, priority));
+        }
+        self.heap.insert(Entry { key, priority });
+        None
+    }
+
+    /// Changes the priority of `key` to `priority`, in either direction,
+    /// and returns the old priority.
+    ///
+    /// **Time Complexity**: `O((d+1) · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ItemNotFound`] if `key` is not queued.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{Error, KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// assert_eq!(queue.change_priority(&"a", 1), Ok(5));
+    /// assert_eq!(queue.peek(), Some((&"a", &1)));
+    /// assert_eq!(queue.change_priority(&"z", 1), Err(Error::ItemNotFound));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Result<P, Error> {
+        let i = self.index_of(key).ok_or(Error::ItemNotFound)?;
+        Ok(self.set_priority_at(i, priority))
+    }
+
+    /// Adds `delta` to the priority of `key` and returns the new priority:
+    /// a relative update for counters such as "retries remaining" or
+    /// "accumulated lateness", with no read-modify-write round trip through
+    /// the caller. A negative `delta`, or any `delta` the `AddAssign` impl
+    /// lowers the priority with, moves the key the other way; either
+    /// direction is re-sifted.
+    ///
+    /// **Time Complexity**: `O((d+1) · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ItemNotFound`] if `key` is not queued.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MaxOrd};
+    ///
+    /// // Jobs by retries remaining, most first
+    /// let mut jobs = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+    /// jobs.insert("fetch", 3);
+    /// jobs.insert("parse", 2);
+    ///
+    /// assert_eq!(jobs.adjust_priority(&"fetch", -2), Ok(1));
+    /// assert_eq!(jobs.peek(), Some((&"parse", &2)));
+    /// assert_eq!(jobs.adjust_priority(&"fetch", 4), Ok(5));
+    /// assert_eq!(jobs.peek(), Some((&"fetch", &5)));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn adjust_priority<D>(&mut self, key: &K, delta: D) -> Result<P, Error>
+    where
+        P: AddAssign<D>,
+    {
+        let i = self.index_of(key).ok_or(Error::ItemNotFound)?;
+        let mut priority = self.heap.container[i].priority.clone();
+        priority += delta;
+        self.set_priority_at(i, priority.clone());
+        Ok(priority)
+    }
+
+    /// Overwrites the priority of the entry at `i` and re-sifts it.
+    fn set_priority_at(&mut self, i: usize, priority: P) -> P {
+        self.heap.bracket(OperationType::UpdatePriority, |heap| {
+            let old = std::mem::replace(&mut heap.container[i].priority, priority);
+            heap.sift_up(i);
+            heap.sift_down(i);
+            old
+        })
+    }
+
+    /// Priority of `key`, or `None` if it is not queued.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MaxOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// assert_eq!(queue.get_priority(&"a"), Some(&5));
+    /// assert_eq!(queue.get_priority(&"b"), None);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn get_priority(&self, key: &K) -> Option<&P> {
+        let i = self.index_of(key)?;
+        Some(&self.heap.container[i].priority)
+    }
+
+    /// Whether `key` is queued.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MaxOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// assert!(queue.contains_key(&"a"));
+    /// assert!(!queue.contains_key(&"b"));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    /// The highest-priority key and its priority, or `None` if empty.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.peek().map(|entry| (&entry.key, &entry.priority))
+    }
+
+    /// Removes and returns the highest-priority key and its priority, or
+    /// `None` if empty.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// assert_eq!(queue.pop(), Some(("b", 3)));
+    /// assert_eq!(queue.pop(), Some(("a", 5)));
+    /// assert_eq!(queue.pop(), None);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.heap.pop().map(|entry| (entry.key, entry.priority))
+    }
+
+    /// Removes `key` and returns its priority, or `None` if it is not
+    /// queued. Work is counted as `PriorityQueue::remove` counts it.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// assert_eq!(queue.remove(&"b"), Some(3));
+    /// assert_eq!(queue.remove(&"b"), None);
+    /// assert_eq!(queue.peek(), Some((&"a", &5)));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = self.index_of(key)?;
+        Some(self.heap.remove_found(i).priority)
+    }
+
+    /// Queued keys and their priorities, in heap-array order (root first,
+    /// not sorted). Same as [`items`](Self::items).
+    ///
+    /// **Time Complexity**: O(1) to create, O(n) to exhaust
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// assert_eq!(queue.iter().next(), Some((&"b", &3))); // the root comes first
+    /// assert_eq!(queue.iter().map(|(_, p)| p).sum::<u32>(), 8);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&K, &P)> {
+        self.items()
+    }
+
+    /// Queued keys, in heap-array order (root first, not sorted), for
+    /// enumerating membership without the priorities.
+    ///
+    /// **Time Complexity**: O(1) to create, O(n) to exhaust
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MinOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// let mut keys: Vec<&str> = queue.keys().copied().collect();
+    /// keys.sort_unstable();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> {
+        self.heap.container.iter().map(|entry| &entry.key)
+    }
+
+    /// Queued keys and their priorities as `(key, priority)` pairs, in
+    /// heap-array order (root first, not sorted).
+    ///
+    /// **Time Complexity**: O(1) to create, O(n) to exhaust
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{KeyedPriorityQueue, MaxOrd};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut queue = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+    /// queue.insert("a", 5);
+    /// queue.insert("b", 3);
+    /// let items: HashMap<&str, u32> = queue.items().map(|(k, p)| (*k, *p)).collect();
+    /// assert_eq!(items, HashMap::from([("a", 5), ("b", 3)]));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn items(&self) -> impl ExactSizeIterator<Item = (&K, &P)> {
+        self.heap
+            .container
+            .iter()
+            .map(|entry| (&entry.key, &entry.priority))
+    }
+
+    /// Number of queued keys.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no keys are queued.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Arity of the heap.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn d(&self) -> usize {
+        self.heap.d()
+    }
+}
+
+/// Heapifies a map of keys to priorities into a [`DEFAULT_ARITY`] queue
+/// ordered by `C::default()`, so code that keeps priorities in a `HashMap`
+/// can switch with `.into()` and gain `pop` and `peek`.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{KeyedPriorityQueue, MinOrd};
+/// use std::collections::HashMap;
+///
+/// let costs = HashMap::from([("b", 7), ("c", 3), ("d", 4)]);
+/// let mut frontier: KeyedPriorityQueue<_, _, MinOrd> = costs.into();
+/// assert_eq!(frontier.pop(), Some(("c", 3)));
+/// assert_eq!(frontier.d(), 4);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<K, P, C, H> From<HashMap<K, P, H>> for KeyedPriorityQueue<K, P, C>
+where
+    K: Eq + Hash + Clone,
+    P: Clone,
+    C: PriorityCompare<P> + Default,
+{
+    fn from(priorities: HashMap<K, P, H>) -> Self {
+        let mut heap = PriorityQueue::with_checked_arity(DEFAULT_ARITY, ByPriority(C::default()));
+        heap.insert_many(
+            priorities
+                .into_iter()
+                .map(|(key, priority)| Entry { key, priority }),
+        );
+        Self { heap }
+    }
+}
+
+/// Moves the queued keys and their priorities into a map, the reverse of
+/// `From<HashMap<K, P>>`.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{KeyedPriorityQueue, MaxOrd};
+/// use std::collections::HashMap;
+///
+/// let mut queue = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+/// queue.insert("a", 1);
+/// queue.insert("b", 5);
+/// queue.change_priority(&"a", 9).unwrap();
+/// let priorities: HashMap<_, _> = queue.into();
+/// assert_eq!(priorities, HashMap::from([("a", 9), ("b", 5)]));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<K, P, C, H> From<KeyedPriorityQueue<K, P, C>> for HashMap<K, P, H>
+where
+    K: Eq + Hash + Clone,
+    P: Clone,
+    H: BuildHasher + Default,
+{
+    fn from(queue: KeyedPriorityQueue<K, P, C>) -> Self {
+        Vec::from(queue.heap)
+            .into_iter()
+            .map(|entry| (entry.key, entry.priority))
+            .collect()
+    }
+}
@@ -17,6 +17,93 @@
 //!   via the [`StatsCollector`] trait. Default `S = NoOpStats` is zero-cost
 //!   (monomorphisation + ZST layout); see [`PriorityQueue::with_stats`] and
 //!   [`InstrumentedPriorityQueue`].
+//! - **Explain mode**: step-by-step record of the swaps and comparisons each
+//!   operation performs, exportable as JSON for teaching material; see
+//!   [`PriorityQueue::with_explain`] and [`ExplainedPriorityQueue`].
+//! - **Sift histograms**: per-operation distributions of sift-up depth,
+//!   sift-down depth and children scanned, for studying how arity shapes
+//!   comparison counts; see [`PriorityQueue::with_histograms`] and
+//!   [`HistogramPriorityQueue`].
+//! - **Profiling export**: one row per operation (heap size, comparisons,
+//!   swaps, nanoseconds) written as CSV for empirical complexity plots; see
+//!   [`PriorityQueue::with_profile`] and [`ProfiledPriorityQueue`].
+//! - **Child scan policy**: [`PriorityQueue::set_child_scan`] picks where
+//!   each sift-down level starts scanning its children, for measuring scan
+//!   order across arities; see the [`child_scan`] module.
+//! - **Production metrics** (`metrics` feature): `MetricsStats` publishes
+//!   queue length, inserts, pops, updates, comparisons and the deepest sift
+//!   through the `metrics` facade, for Prometheus and other exporters; see
+//!   `PriorityQueue::with_metrics`.
+//! - **Serialization** (`serde` feature): queues serialize as their arity
+//!   and heap array and rebuild the positions map on load, for checkpoints
+//!   and messages; see `PriorityQueue::deserialize_with` for closure
+//!   comparators.
+//! - **Deferred heapification**: [`PriorityQueue::bulk_insert_deferred`]
+//!   appends items without sifting and restores heap order once when the
+//!   returned [`DeferredInsert`] guard drops. See the [`deferred`] module.
+//! - **Time-sliced rebuilds**: [`PriorityQueue::start_rebuild`] spreads a
+//!   full O(n) rebuild over [`IncrementalRebuild::rebuild_step`] calls of
+//!   bounded work. See the [`rebuild`] module.
+//! - **Pluggable position index**: [`PriorityQueue::with_position_index`]
+//!   swaps the default [`HashIndex`], which stores each item once, for any
+//!   [`PositionIndex`], such as the hash-free [`DenseIndex`] for small
+//!   integer ids. See the [`position_index`] module.
+//! - **Tree navigation**: read-only [`Cursor`] over the implicit d-ary tree
+//!   (`parent`, `children`, `level`) and a per-level slice iterator for
+//!   visualizers and structural tests; see [`PriorityQueue::cursor_at`] and
+//!   [`PriorityQueue::levels`].
+//! - **Snapshots**: opt-in O(1) frozen copies for monitoring threads:
+//!   [`SnapshotPriorityQueue`] shares its storage copy-on-write with every
+//!   [`Snapshot`] and [`FrozenHeap`] it hands out. See the [`snapshot`]
+//!   module.
+//! - **Partially ordered priorities**: [`TryPriorityQueue`] runs on a
+//!   [`TryPriorityCompare`] that may find a pair incomparable (`NaN` costs,
+//!   unrelated versions) and rejects the operation with
+//!   `Error::Incomparable` instead of corrupting the heap; see the
+//!   [`fallible`] module.
+//! - **Write-ahead log persistence**: [`DurablePriorityQueue`] logs each
+//!   mutation to disk before applying it and replays the log on
+//!   [`DurablePriorityQueue::recover`], with periodic compaction. See the
+//!   [`wal`] module.
+//! - **Weak-entry mode**: queue [`WeakEntry`] handles to caller-owned `Rc`s;
+//!   dropping the `Rc` cancels the item, and [`PriorityQueue::pop_live`] skips
+//!   it. See the [`weak`] module.
+//! - **Separate keys and priorities**: [`KeyedPriorityQueue`] queues a key
+//!   at a priority given alongside it, `insert(key, priority)`, and changes
+//!   or reads the priority through the key, so keys need no `Eq` and `Hash`
+//!   that skip a priority field. See the [`keyed`] module.
+//! - **Weighted-fair scheduling**: [`FairQueue`] shares pops between classes
+//!   by deficit round robin, one d-ary heap per class, so no class starves.
+//! - **Timed events**: [`EventQueue`] pops events in time order and hands
+//!   out a [`CancellationToken`] per event; cancelled events are removed
+//!   from the heap, not left to be skipped.
+//! - **Least-loaded worker selection**: [`LoadBalancer`] hands out the
+//!   least-loaded worker as a [`WorkerGuard`] that releases the load on drop.
+//! - **Multi-level feedback queue**: [`MlfqScheduler`] runs the classic MLFQ
+//!   demotion and boost rules over one d-ary heap per level.
+//! - **Monotone queues**: [`MonotoneQueue`] asserts in debug builds that
+//!   nothing popped or inserted outranks the last item popped, catching
+//!   negative weights and relaxation bugs where they happen.
+//! - **Best-first search**: `algorithms::best_first` is one search engine
+//!   over implicit graphs, with `uniform_cost`, `astar` and
+//!   `greedy_best_first` as configurations of it.
+//! - **Beam search**: [`BeamSearch`] keeps the best `beam_width` candidates
+//!   per depth in a bounded heap that evicts the worst on overflow.
+//! - **Branch and bound**: [`BranchAndBound`] expands the most promising
+//!   node first and drops queued nodes a new incumbent rules out with
+//!   [`PriorityQueue::drain_where`].
+//! - **petgraph shortest paths** (`petgraph` feature): `petgraph::dijkstra`
+//!   and `petgraph::astar` take the arguments of petgraph's own and run on a
+//!   d-ary heap with decrease-key; `petgraph::dijkstra_adaptive` switches
+//!   arity during the search.
+//! - **Huffman coding**: `huffman::code_table` builds an optimal prefix code
+//!   by merging the two lightest subtrees with `pop` and `push_pop`.
+//! - **Streaming top-k**: [`TopK`] keeps approximate top-k frequent items of
+//!   a stream in a bounded min-heap (Space-Saving), with per-item error
+//!   bounds.
+//! - **Priority-ordered futures** (`async` feature): `PriorityFutures` polls
+//!   a set of futures highest-priority-first, using a d-ary heap as the ready
+//!   index.
 //!
 //! ## Cross-Language Consistency
 //!
@@ -27,12 +114,78 @@
 //!
 //! All implementations share identical time complexities and method semantics.
 
+pub mod algorithms;
+pub mod balancer;
+pub mod beam;
+pub mod branch_bound;
+pub mod child_scan;
+pub mod cursor;
+pub mod deferred;
+pub mod diff;
+pub mod display;
+pub mod events;
+pub mod explain;
+pub mod fair;
+pub mod fallible;
+pub mod histogram;
+pub mod huffman;
 pub mod instrumentation;
+pub mod keyed;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod mlfq;
+pub mod monotone;
+#[cfg(feature = "petgraph")]
+pub mod petgraph;
+pub mod position;
+pub mod position_index;
+#[cfg(feature = "async")]
+pub mod priority_futures;
+pub mod profile;
+pub mod rebuild;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod snapshot;
+pub mod sorted;
+pub mod top_k;
+pub mod wal;
+pub mod weak;
+pub use balancer::{LoadBalancer, WorkerGuard};
+pub use beam::BeamSearch;
+pub use branch_bound::{BranchAndBound, BranchAndBoundStats};
+pub use child_scan::ChildScan;
+pub use cursor::Cursor;
+pub use deferred::DeferredInsert;
+pub use diff::HeapDiff;
+pub use display::DisplayAdapter;
+pub use events::{CancellationToken, EventQueue};
+pub use explain::{ExplainStep, ExplainTrace};
+pub use fair::{ClassStats, FairQueue};
+pub use fallible::{TryMaxBy, TryMinBy, TryPriorityCompare, TryPriorityQueue};
+pub use histogram::{HistogramStats, SiftHistogram, SiftHistograms};
 pub use instrumentation::{ComparisonStats, NoOpStats, OperationType, StatsCollector};
+pub use keyed::KeyedPriorityQueue;
+#[cfg(feature = "metrics")]
+pub use metrics::MetricsStats;
+pub use mlfq::{Dispatch, MlfqConfig, MlfqScheduler, RunOutcome};
+pub use monotone::MonotoneQueue;
+pub use position::Position;
+pub use position_index::{DenseIndex, HashIndex, PositionIndex};
+#[cfg(feature = "async")]
+pub use priority_futures::PriorityFutures;
+pub use profile::{ProfileRecord, ProfileTrace, PROFILE_CSV_HEADER};
+pub use rebuild::IncrementalRebuild;
+pub use snapshot::{FrozenHeap, Snapshot, SnapshotPriorityQueue};
+pub use sorted::{DrainSorted, IntoIterSorted};
+pub use top_k::{FrequencyEstimate, TopK};
+pub use wal::{DurablePriorityQueue, WalError, WalItem};
+pub use weak::{WeakEntry, WeakPriorityQueue};
 
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::collections::BinaryHeap;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::hash::Hash;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
 
 /// Error types for d-ary heap operations.
 ///
@@ -51,6 +204,16 @@ pub enum Error {
     IndexOutOfBounds,
     /// Operation requires a non-empty queue.
     EmptyQueue,
+    /// Raw parts disagree: the position map does not index the container exactly.
+    InconsistentPositions,
+    /// Raw parts violate the heap invariant: some child outranks its parent.
+    HeapInvariantViolated,
+    /// A fallible comparator could not rank two items an operation had to
+    /// compare; see [`TryPriorityQueue`].
+    Incomparable,
+    /// An in-place adjustment changed the item's identity (its `Eq` /
+    /// `Hash` fields); see [`PriorityQueue::adjust_priority`].
+    IdentityChanged,
 }
 
 impl Display for Error {
@@ -60,19 +223,22 @@ impl Display for Error {
             Error::ItemNotFound => write!(f, "Item not found"),
             Error::IndexOutOfBounds => write!(f, "Index out of bounds"),
             Error::EmptyQueue => write!(f, "Operation called on empty priority queue"),
+            Error::InconsistentPositions => {
+                write!(f, "Position map does not match the container")
+            }
+            Error::HeapInvariantViolated => write!(f, "Heap invariant violated"),
+            Error::Incomparable => write!(f, "Priorities cannot be compared"),
+            Error::IdentityChanged => write!(f, "Adjustment changed the item's identity"),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
-/// Type alias for position indices, providing cross-language consistency.
-///
-/// **Cross-language equivalents**:
-/// - C++: `TOOLS::PriorityQueue<T>::Position`
-/// - Zig: `DHeap.Position`
-/// - TypeScript: `Position` type alias
-pub type Position = usize;
+/// Arity used where the caller does not choose one: the `From` conversions
+/// and the `petgraph` adapter. Four children per node is the usual sweet
+/// spot for decrease-key-heavy workloads (see the crate README).
+pub const DEFAULT_ARITY: usize = 4;
 
 /// Trait defining priority comparison for heap ordering.
 ///
@@ -104,8 +270,12 @@ pub trait PriorityCompare<T> {
 /// d-ary heap priority queue with O(1) item lookup.
 ///
 /// **Type Parameters**:
-/// - `T`: Item type (must implement `Eq + Hash + Clone`)
+/// - `T`: Item type (must implement `Eq + Clone`, and `Hash` for the
+///   default position index)
 /// - `C`: Comparator implementing `PriorityCompare<T>`
+/// - `S`: [`StatsCollector`] policy, `NoOpStats` by default
+/// - `P`: [`PositionIndex`] from item to position, [`HashIndex`] by
+///   default; see [`PriorityQueue::with_position_index`]
 ///
 /// **Cross-language equivalents**:
 /// - C++: `TOOLS::PriorityQueue<T, THash, TComparisonPredicate, TEqual>`
@@ -137,14 +307,18 @@ pub trait PriorityCompare<T> {
 /// - `contains()`: O(1)
 /// - `len()`/`is_empty()`/`d()`: O(1)
 #[derive(Debug)]
-pub struct PriorityQueue<T, C, S = NoOpStats>
+pub struct PriorityQueue<T, C, S = NoOpStats, P = HashIndex>
 where
-    T: Eq + Hash + Clone,
+    T: Eq + Clone,
 {
+    /// Heap-ordered backing array; index 0 is the root.
     container: Vec<T>,
-    positions: HashMap<T, Position>,
+    /// Item-identity → position index over `container`.
+    positions: P,
     comparator: C,
     depth: usize,
+    /// Where `sift_down` starts scanning each sibling group.
+    child_scan: ChildScan,
     /// Phase 2 instrumentation policy. With the default `NoOpStats` (a
     /// zero-sized type), this field collapses to zero bytes via Rust's ZST
     /// layout — no runtime cost. With `ComparisonStats` (via the
@@ -152,16 +326,45 @@ where
     stats: S,
 }
 
+/// Min-heap on `T`'s own [`Ord`]: the smallest item is the front. Build one
+/// with [`PriorityQueue::with_arity`].
+pub type MinHeap<T> = PriorityQueue<T, MinOrd>;
+
+/// Max-heap on `T`'s own [`Ord`], like [`BinaryHeap`]: the largest item is
+/// the front. Build one with [`PriorityQueue::with_arity`].
+pub type MaxHeap<T> = PriorityQueue<T, MaxOrd>;
+
 /// Convenience alias for a heap parameterised over `ComparisonStats`. Use this
 /// when you want comparison-count instrumentation; the default
 /// `PriorityQueue<T, C>` stays zero-overhead via `NoOpStats`.
 pub type InstrumentedPriorityQueue<T, C> = PriorityQueue<T, C, ComparisonStats>;
 
-impl<T, C, S> PriorityQueue<T, C, S>
+/// Convenience alias for a heap parameterised over `ExplainTrace`. Use this
+/// when you want a per-operation record of swaps and comparisons (e.g. to
+/// render step sequences in slides or the TypeScript playground).
+pub type ExplainedPriorityQueue<T, C> = PriorityQueue<T, C, ExplainTrace>;
+
+/// Convenience alias for a heap parameterised over `HistogramStats`. Use this
+/// when you want distributions of sift depths and children scanned per
+/// operation, not just comparison totals.
+pub type HistogramPriorityQueue<T, C> = PriorityQueue<T, C, HistogramStats>;
+
+/// Convenience alias for a heap parameterised over `ProfileTrace`. Use this
+/// when you want one timed record per operation, exportable as CSV.
+pub type ProfiledPriorityQueue<T, C> = PriorityQueue<T, C, ProfileTrace>;
+
+/// Convenience alias for a heap parameterised over `MetricsStats` (`metrics`
+/// feature). Use this when a running service should export the queue's
+/// length and activity.
+#[cfg(feature = "metrics")]
+pub type MetricsPriorityQueue<T, C> = PriorityQueue<T, C, MetricsStats>;
+
+impl<T, C, S, P> PriorityQueue<T, C, S, P>
 where
-    T: Eq + Hash + Clone,
+    T: Eq + Clone,
     C: PriorityCompare<T>,
     S: StatsCollector,
+    P: PositionIndex<T>,
 {
     // The `new` and `with_first` constructors live on the dedicated
     // `impl PriorityQueue<T, C, NoOpStats>` block at the bottom of this file.
@@ -246,6 +449,73 @@ where
         self.container.is_empty()
     }
 
+    /// Number of items the heap can hold before its array reallocates. The
+    /// positions map grows separately, on its own schedule.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let heap = PriorityQueue::with_capacity(4, MinBy(|x: &u32| *x), 100).unwrap();
+    /// assert!(heap.capacity() >= 100);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.container.capacity()
+    }
+
+    /// Makes room for at least `additional` more items in both the array and
+    /// the positions map, so that many inserts reallocate neither, e.g.
+    /// before queueing every node of a graph. A [`DenseIndex`] is sized by
+    /// ids rather than items and ignores this.
+    ///
+    /// **Time Complexity**: O(n) when it reallocates
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert(7);
+    /// heap.reserve(1_000);
+    /// assert!(heap.capacity() >= 1_001);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn reserve(&mut self, additional: usize) {
+        self.container.reserve(additional);
+        self.positions.reserve(additional);
+    }
+
+    /// Releases the spare capacity of both the array and the positions map,
+    /// e.g. after a burst of work has drained.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::with_capacity(4, MinBy(|x: &u32| *x), 1_000).unwrap();
+    /// heap.insert_many(vec![3, 1, 2]);
+    /// heap.shrink_to_fit();
+    /// assert!(heap.capacity() < 1_000);
+    /// assert_eq!(heap.front(), &1);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn shrink_to_fit(&mut self) {
+        self.container.shrink_to_fit();
+        self.positions.shrink_to_fit();
+    }
+
     /// Checks if an item exists in the heap by identity (O(1) lookup).
     ///
     /// **Time Complexity**: O(1)
@@ -268,7 +538,77 @@ where
     #[inline]
     #[must_use]
     pub fn contains(&self, item: &T) -> bool {
-        self.positions.contains_key(item)
+        self.positions.contains(item, &self.container)
+    }
+
+    /// Checks a batch of items by identity in one pass, collecting every item
+    /// that is not in the heap.
+    ///
+    /// Meant for admission control: a task is scheduled only once all of its
+    /// dependencies are queued, and the missing ones are reported together
+    /// rather than one `contains` call at a time.
+    ///
+    /// **Time Complexity**: O(k) for k items
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(missing)` when any item is absent, with the absent items
+    /// in iteration order (duplicates kept).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(heap.contains_all(&[1, 3]), Ok(()));
+    /// assert_eq!(heap.contains_all(&[4, 2, 5]), Err(vec![&4, &5]));
+    /// assert_eq!(heap.contains_all(&[]), Ok(()));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn contains_all<'a>(&self, items: impl IntoIterator<Item = &'a T>) -> Result<(), Vec<&'a T>>
+    where
+        T: 'a,
+    {
+        let missing: Vec<&T> = items
+            .into_iter()
+            .filter(|item| !self.contains(item))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Checks whether at least one of a batch of items is in the heap,
+    /// stopping at the first one found.
+    ///
+    /// **Time Complexity**: O(k) for k items
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 2, 3]);
+    ///
+    /// assert!(heap.contains_any(&[7, 2]));
+    /// assert!(!heap.contains_any(&[7, 8]));
+    /// assert!(!heap.contains_any(&[]));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn contains_any<'a>(&self, items: impl IntoIterator<Item = &'a T>) -> bool
+    where
+        T: 'a,
+    {
+        items.into_iter().any(|item| self.contains(item))
     }
 
     /// Returns the position (index) of an item in the heap, or `None` if not found.
@@ -278,14 +618,14 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy};
+    /// use d_ary_heap::{PriorityQueue, MinBy, Position};
     ///
     /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
     /// heap.insert(5);
     /// heap.insert(3);
     ///
     /// // Root item (highest priority) is at position 0
-    /// assert_eq!(heap.get_position(&3), Some(0));
+    /// assert_eq!(heap.get_position(&3), Some(Position::ROOT));
     /// assert!(heap.get_position(&5).is_some());
     /// assert_eq!(heap.get_position(&99), None);
     /// ```
@@ -298,7 +638,126 @@ where
     #[inline]
     #[must_use]
     pub fn get_position(&self, item: &T) -> Option<Position> {
-        self.positions.get(item).copied()
+        self.positions.get(item, &self.container)
+    }
+
+    /// Returns a read-only [`Cursor`] on the node at `position` of the
+    /// implicit tree, or `None` if `position` is out of bounds.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Position};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 2, 3, 4]);
+    ///
+    /// let node = heap.cursor_at(heap.get_position(&4).unwrap()).unwrap();
+    /// assert_eq!(node.parent().unwrap().item(), &2);
+    /// assert_eq!(node.level(), 2);
+    /// assert!(node.is_leaf());
+    /// assert!(heap.cursor_at(Position::new(4)).is_none());
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn cursor_at(&self, position: Position) -> Option<Cursor<'_, T>> {
+        Cursor::new(&self.container, self.depth, position)
+    }
+
+    /// Returns an iterator over the levels of the implicit tree, root first,
+    /// each level as a slice of the backing array.
+    ///
+    /// Level `k` holds up to `d^k` items; only the last level may be partial.
+    /// Useful for rendering the tree row by row and for per-level diagnostics.
+    ///
+    /// **Time Complexity**: O(1) per level, `O(log_d n)` levels
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(1..=6);
+    ///
+    /// let levels: Vec<&[i32]> = heap.levels().collect();
+    /// assert_eq!(levels, vec![&[1][..], &[2, 3, 4][..], &[5, 6][..]]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn levels(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let d = self.depth;
+        let mut start = 0usize;
+        let mut width = 1usize;
+        std::iter::from_fn(move || {
+            if start >= self.container.len() {
+                return None;
+            }
+            let end = start.saturating_add(width).min(self.container.len());
+            // INDEX: start < len and start <= end <= len.
+            let level = &self.container[start..end];
+            start = end;
+            width = width.saturating_mul(d);
+            Some(level)
+        })
+    }
+
+    /// Returns an iterator over the items in array order, root first: heap
+    /// order, not sorted. Use [`into_iter_sorted`](Self::into_iter_sorted)
+    /// or [`drain_sorted`](Self::drain_sorted) for priority order.
+    ///
+    /// **Time Complexity**: O(1) per item
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(heap.iter().next(), Some(&1));
+    /// assert_eq!(heap.iter().sum::<i32>(), 6);
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.container.iter()
+    }
+
+    /// Returns an iterator over `(position, &item)` pairs in array order,
+    /// root first.
+    ///
+    /// Maps every item to its tree coordinates in one pass over the backing
+    /// array, without a `get_position` lookup per item; combine with
+    /// [`Cursor`] arithmetic or `levels()` for diagnostics and visualizers.
+    ///
+    /// **Time Complexity**: O(1) per item
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Position};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![3, 1, 2]);
+    ///
+    /// for (position, item) in heap.iter_with_positions() {
+    ///     assert_eq!(heap.get_position(item), Some(position));
+    /// }
+    /// assert_eq!(heap.iter_with_positions().next(), Some((Position::ROOT, &1)));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn iter_with_positions(&self) -> impl ExactSizeIterator<Item = (Position, &T)> + '_ {
+        self.container
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Position::new(i), item))
     }
 
     /// Clears all items from the heap, optionally changing the arity.
@@ -343,9 +802,123 @@ where
         }
         self.container.clear();
         self.positions.clear();
+        self.stats.record_final_len(0);
+        Ok(())
+    }
+
+    /// Changes the arity and rebuilds the heap under it, keeping every item.
+    ///
+    /// `clear(Some(d))` changes the arity of an empty heap; this one keeps
+    /// the contents, for a workload whose best arity shifts mid-run (see
+    /// `petgraph::dijkstra_adaptive`). The rebuild is Floyd's heapify, at
+    /// most about `n · d / (d - 1)` comparisons, attributed to
+    /// `OperationType::UpdatePriority` like the rebuild of
+    /// `update_priorities`. Positions change; the same arity is a no-op.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![9, 4, 7, 1, 8]);
+    ///
+    /// heap.set_arity(8).unwrap();
+    /// assert_eq!(heap.d(), 8);
+    /// assert_eq!(heap.pop_many(3), vec![1, 4, 7]);
+    /// assert!(heap.set_arity(0).is_err());
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only; other languages
+    /// change the arity only through `clear`).
+    pub fn set_arity(&mut self, d: usize) -> Result<(), Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        if d != self.depth {
+            self.bracket(OperationType::UpdatePriority, |s| {
+                s.depth = d;
+                s.heapify();
+            });
+        }
         Ok(())
     }
 
+    /// Starts rebuilding the heap in slices, for when every priority has
+    /// changed at once and one O(n) heapify would stall too long. See the
+    /// [`rebuild`] module.
+    ///
+    /// The returned [`IncrementalRebuild`] owns the heap; each
+    /// `rebuild_step(budget)` performs at most `budget` sift-downs, and the
+    /// heap comes back from `finish` or `into_heap`. The result is the heap a
+    /// single rebuild would produce.
+    ///
+    /// **Time Complexity**: O(1); O(n) over all steps
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(0..1000);
+    ///
+    /// let mut rebuild = heap.start_rebuild();
+    /// assert_eq!(rebuild.remaining(), 500);
+    /// assert!(!rebuild.rebuild_step(300));
+    /// assert!(rebuild.rebuild_step(300));
+    /// let heap = rebuild.into_heap().unwrap();
+    /// assert_eq!(heap.front(), &0);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn start_rebuild(self) -> IncrementalRebuild<T, C, S, P> {
+        IncrementalRebuild::new(self)
+    }
+
+    /// The order in which sift-downs scan each sibling group; see the
+    /// [`child_scan`] module.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub const fn child_scan(&self) -> ChildScan {
+        self.child_scan
+    }
+
+    /// Sets the order in which sift-downs scan each sibling group.
+    ///
+    /// Every policy keeps heap order, so the heap is not rebuilt; the
+    /// policy applies from the next sift-down on. Comparison counts do not
+    /// change, only which of several equal-priority children wins.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{ChildScan, MinBy, PriorityQueue};
+    ///
+    /// let mut heap = PriorityQueue::with_stats(4, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many((0..64).rev());
+    /// heap.set_child_scan(ChildScan::FollowBest);
+    ///
+    /// assert_eq!(heap.pop_many(4), vec![0, 1, 2, 3]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn set_child_scan(&mut self, scan: ChildScan) {
+        self.child_scan = scan;
+    }
+
     /// Returns a reference to the highest-priority item.
     ///
     /// **Time Complexity**: O(1)
@@ -428,10 +1001,10 @@ where
     /// - TypeScript: `insert(item)`
     pub fn insert(&mut self, t: T) {
         self.bracket(OperationType::Insert, |s| {
-            s.container.push(t.clone());
-            let i = s.container.len() - 1;
-            s.positions.insert(t, i);
-            s.move_up(i);
+            let i = s.container.len();
+            s.positions.insert(&t, Position::new(i));
+            s.container.push(t);
+            s.sift_up(i);
         });
     }
 
@@ -446,17 +1019,17 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy, Error};
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
     ///
     /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
     /// heap.insert(10);
     /// heap.insert(5);
     ///
     /// // Increase priority of item at index 1
-    /// heap.increase_priority_by_index(1).unwrap();
+    /// heap.increase_priority_by_index(Position::new(1)).unwrap();
     ///
     /// // Error on out of bounds
-    /// assert_eq!(heap.increase_priority_by_index(99), Err(Error::IndexOutOfBounds));
+    /// assert_eq!(heap.increase_priority_by_index(Position::new(99)), Err(Error::IndexOutOfBounds));
     /// ```
     ///
     /// **Cross-language equivalents**:
@@ -464,16 +1037,52 @@ where
     /// - Zig: `increasePriorityByIndex(index)` (returns `!void`)
     /// - TypeScript: `increasePriorityByIndex(index)` (throws)
     /// - Go: `IncreasePriorityByIndex(index)` (returns `error`)
-    pub fn increase_priority_by_index(&mut self, i: usize) -> Result<(), Error> {
+    pub fn increase_priority_by_index(&mut self, i: Position) -> Result<(), Error> {
         self.bracket(OperationType::IncreasePriority, |s| {
-            if i >= s.container.len() {
+            if !i.is_valid_for(s) {
                 return Err(Error::IndexOutOfBounds);
             }
-            s.move_up(i);
+            s.sift_up(i.index());
             Ok(())
         })
     }
 
+    /// [`increase_priority_by_index`](PriorityQueue::increase_priority_by_index)
+    /// for an index the caller knows is in bounds, e.g. one just returned by
+    /// [`get_position`](PriorityQueue::get_position): no `Result`.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of bounds: by debug assertion in debug builds, and on
+    /// the array index in release builds unless `i == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 10]);
+    ///
+    /// if let Some(i) = heap.get_position(&10) {
+    ///     heap.increase_priority_by_index_unchecked(i);
+    /// }
+    /// assert_eq!(heap.front(), &5);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn increase_priority_by_index_unchecked(&mut self, i: Position) {
+        self.bracket(OperationType::IncreasePriority, |s| {
+            debug_assert!(
+                i.is_valid_for(s),
+                "increase_priority_by_index_unchecked: index {i} out of bounds"
+            );
+            s.sift_up(i.index());
+        });
+    }
+
     /// Decreases priority of item at specified index (moves down if needed).
     ///
     /// **Time Complexity**: `O(d · log_d n)`
@@ -485,17 +1094,17 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy, Error};
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
     ///
     /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
     /// heap.insert(10);
     /// heap.insert(5);
     ///
     /// // Decrease priority of item at index 0 (root)
-    /// heap.decrease_priority_by_index(0).unwrap();
+    /// heap.decrease_priority_by_index(Position::ROOT).unwrap();
     ///
     /// // Error on out of bounds
-    /// assert_eq!(heap.decrease_priority_by_index(99), Err(Error::IndexOutOfBounds));
+    /// assert_eq!(heap.decrease_priority_by_index(Position::new(99)), Err(Error::IndexOutOfBounds));
     /// ```
     ///
     /// **Cross-language equivalents**:
@@ -503,12 +1112,12 @@ where
     /// - Zig: `decreasePriorityByIndex(index)` (returns `!void`)
     /// - TypeScript: `decreasePriorityByIndex(index)` (throws)
     /// - Go: `DecreasePriorityByIndex(index)` (returns `error`)
-    pub fn decrease_priority_by_index(&mut self, i: usize) -> Result<(), Error> {
+    pub fn decrease_priority_by_index(&mut self, i: Position) -> Result<(), Error> {
         self.bracket(OperationType::DecreasePriority, |s| {
-            if i >= s.container.len() {
+            if !i.is_valid_for(s) {
                 return Err(Error::IndexOutOfBounds);
             }
-            s.move_down(i);
+            s.sift_down(i.index());
             Ok(())
         })
     }
@@ -527,17 +1136,17 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy, Error};
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
     ///
     /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
     /// heap.insert(10);
     /// heap.insert(5);
     ///
     /// // Update priority at index - direction is determined automatically
-    /// heap.update_priority_by_index(0).unwrap();
+    /// heap.update_priority_by_index(Position::ROOT).unwrap();
     ///
     /// // Error on out of bounds
-    /// assert_eq!(heap.update_priority_by_index(99), Err(Error::IndexOutOfBounds));
+    /// assert_eq!(heap.update_priority_by_index(Position::new(99)), Err(Error::IndexOutOfBounds));
     /// ```
     ///
     /// **Cross-language equivalents**:
@@ -545,13 +1154,14 @@ where
     /// - Zig: Not available
     /// - TypeScript: Not available
     /// - Go: Not available
-    pub fn update_priority_by_index(&mut self, i: usize) -> Result<(), Error> {
+    pub fn update_priority_by_index(&mut self, i: Position) -> Result<(), Error> {
         self.bracket(OperationType::UpdatePriority, |s| {
-            if i >= s.container.len() {
+            if !i.is_valid_for(s) {
                 return Err(Error::IndexOutOfBounds);
             }
-            s.move_up(i);
-            s.move_down(i);
+            let i = i.index();
+            s.sift_up(i);
+            s.sift_down(i);
             Ok(())
         })
     }
@@ -591,21 +1201,73 @@ where
     /// - Go: `IncreasePriority(item)` (returns `error`)
     pub fn increase_priority(&mut self, updated_item: &T) -> Result<(), Error> {
         self.bracket(OperationType::IncreasePriority, |s| {
-            let &i = s.positions.get(updated_item).ok_or(Error::ItemNotFound)?;
+            let i = s
+                .positions
+                .get(updated_item, &s.container)
+                .ok_or(Error::ItemNotFound)?
+                .index();
 
-            // Update positions: remove old key and insert the new (updated) item.
-            // Since Hash/Eq are based on identity (not priority), updated_item can be used
-            // directly to remove the old entry — no need to clone the old item.
-            s.positions.remove(updated_item);
-            s.positions.insert(updated_item.clone(), i);
+            // Hash/Eq are based on identity (not priority), so the updated
+            // item keeps its position; an index holding its own copy of the
+            // old item refreshes it.
+            s.positions.refresh(updated_item, Position::new(i));
             s.container[i] = updated_item.clone();
 
             // Move up after priority increase
-            s.move_up(i);
+            s.sift_up(i);
             Ok(())
         })
     }
 
+    /// [`increase_priority`](PriorityQueue::increase_priority) for callers
+    /// that have just checked `contains(updated_item)`, as a Dijkstra
+    /// relaxation does: no `Result` to check, and no `ItemNotFound` error
+    /// path in the hot loop.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if `updated_item` is not in the heap. Release builds
+    /// leave the heap unchanged instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Node { id: u32, dist: u32 }
+    /// impl PartialEq for Node { fn eq(&self, o: &Self) -> bool { self.id == o.id } }
+    /// impl Eq for Node {}
+    /// impl std::hash::Hash for Node {
+    ///     fn hash<H: std::hash::Hasher>(&self, h: &mut H) { self.id.hash(h) }
+    /// }
+    ///
+    /// let mut heap = PriorityQueue::new(4, MinBy(|n: &Node| n.dist)).unwrap();
+    /// heap.insert_many(vec![Node { id: 1, dist: 10 }, Node { id: 2, dist: 20 }]);
+    ///
+    /// let relaxed = Node { id: 2, dist: 5 };
+    /// if heap.contains(&relaxed) {
+    ///     heap.increase_priority_unchecked(&relaxed);
+    /// }
+    /// assert_eq!(heap.front().id, 2);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn increase_priority_unchecked(&mut self, updated_item: &T) {
+        self.bracket(OperationType::IncreasePriority, |s| {
+            let Some(i) = s.positions.get(updated_item, &s.container) else {
+                debug_assert!(false, "increase_priority_unchecked: item not in heap");
+                return;
+            };
+            s.positions.refresh(updated_item, i);
+            let i = i.index();
+            s.container[i] = updated_item.clone();
+            s.sift_up(i);
+        });
+    }
+
     /// Decreases priority of existing item (moves toward leaves if needed).
     ///
     /// **Important**: Only call this when you know the item's priority has decreased
@@ -646,17 +1308,20 @@ where
     /// - Go: `DecreasePriority(item)` (returns `error`)
     pub fn decrease_priority(&mut self, updated_item: &T) -> Result<(), Error> {
         self.bracket(OperationType::DecreasePriority, |s| {
-            let &i = s.positions.get(updated_item).ok_or(Error::ItemNotFound)?;
+            let i = s
+                .positions
+                .get(updated_item, &s.container)
+                .ok_or(Error::ItemNotFound)?
+                .index();
 
-            // Update positions: remove old key and insert the new (updated) item.
-            // Since Hash/Eq are based on identity (not priority), updated_item can be used
-            // directly to remove the old entry — no need to clone the old item.
-            s.positions.remove(updated_item);
-            s.positions.insert(updated_item.clone(), i);
+            // Hash/Eq are based on identity (not priority), so the updated
+            // item keeps its position; an index holding its own copy of the
+            // old item refreshes it.
+            s.positions.refresh(updated_item, Position::new(i));
             s.container[i] = updated_item.clone();
 
             // Move down after priority decrease (item became less important)
-            s.move_down(i);
+            s.sift_down(i);
             Ok(())
         })
     }
@@ -695,38 +1360,189 @@ where
     /// - Go: `UpdatePriority(item)` (returns `error`)
     pub fn update_priority(&mut self, updated_item: &T) -> Result<(), Error> {
         self.bracket(OperationType::UpdatePriority, |s| {
-            let &i = s.positions.get(updated_item).ok_or(Error::ItemNotFound)?;
+            let i = s
+                .positions
+                .get(updated_item, &s.container)
+                .ok_or(Error::ItemNotFound)?
+                .index();
 
-            // Update positions: remove old key and insert the new (updated) item.
-            s.positions.remove(updated_item);
-            s.positions.insert(updated_item.clone(), i);
+            // Same identity, same position: only an index holding its own
+            // copy of the old item has anything to refresh.
+            s.positions.refresh(updated_item, Position::new(i));
             s.container[i] = updated_item.clone();
 
             // Check both directions since we don't know if priority increased or decreased
-            s.move_up(i);
-            s.move_down(i);
+            s.sift_up(i);
+            s.sift_down(i);
             Ok(())
         })
     }
 
-    /// Removes and returns the highest-priority item from the heap.
+    /// Adjusts the stored copy of `item` in place and re-sifts it.
     ///
-    /// Returns `None` if the heap is empty.
+    /// `adjust` receives a mutable reference to the item held by the heap, so
+    /// relative changes ("one fewer retry", "add 5 ms of lateness") need no
+    /// read-modify-write round trip through the caller. Either direction is
+    /// handled.
     ///
-    /// **Time Complexity**: `O(d · log_d n)`
+    /// This takes a closure rather than a numeric `delta`: a `PriorityQueue`
+    /// has no priority value of its own to add a delta to. The priority is
+    /// whatever the comparator reads from `T`, a field, a computed key or
+    /// several fields at once, so only the caller knows what "add 5" means.
+    /// A closure over the item says it for any comparator. A
+    /// [`KeyedPriorityQueue`], whose priorities are separate values, takes a
+    /// delta directly: [`KeyedPriorityQueue::adjust_priority`].
+    ///
+    /// **Time Complexity**: `O((d+1) · log_d n)` worst case
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if item is not in the heap, and
+    /// `Error::IdentityChanged` if `adjust` changes the item's identity (its
+    /// `Eq` / `Hash` fields). On error the heap is left unchanged: `adjust`
+    /// runs on a copy, which is discarded.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy};
-    ///
-    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
-    /// heap.insert(5);
-    /// heap.insert(3);
-    /// heap.insert(7);
+    /// use d_ary_heap::{PriorityQueue, MaxBy};
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Job { id: u32, retries_left: i32 }
+    /// impl PartialEq for Job { fn eq(&self, o: &Self) -> bool { self.id == o.id } }
+    /// impl Eq for Job {}
+    /// impl Hash for Job { fn hash<H: Hasher>(&self, h: &mut H) { self.id.hash(h) } }
+    ///
+    /// let mut heap = PriorityQueue::new(2, MaxBy(|j: &Job| j.retries_left)).unwrap();
+    /// heap.insert(Job { id: 1, retries_left: 3 });
+    /// heap.insert(Job { id: 2, retries_left: 2 });
+    ///
+    /// let key = Job { id: 1, retries_left: 0 }; // only the identity matters
+    /// heap.adjust_priority(&key, |j| j.retries_left -= 2).unwrap();
+    /// assert_eq!(heap.front().id, 2);
+    /// assert_eq!(heap.pop().unwrap().retries_left, 2);
+    /// assert_eq!(heap.pop().unwrap().retries_left, 1);
+    /// ```
     ///
-    /// assert_eq!(heap.pop(), Some(3));
-    /// assert_eq!(heap.pop(), Some(5));
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn adjust_priority(&mut self, item: &T, adjust: impl FnOnce(&mut T)) -> Result<(), Error> {
+        self.bracket(OperationType::UpdatePriority, |s| {
+            let i = s
+                .positions
+                .get(item, &s.container)
+                .ok_or(Error::ItemNotFound)?
+                .index();
+            let mut adjusted = s.container[i].clone();
+            adjust(&mut adjusted);
+            if adjusted != s.container[i] {
+                return Err(Error::IdentityChanged);
+            }
+
+            // Same bookkeeping as update_priority: an index holding its own
+            // copy of the item refreshes it.
+            s.positions.refresh(&adjusted, Position::new(i));
+            s.container[i] = adjusted;
+
+            s.sift_up(i);
+            s.sift_down(i);
+            Ok(())
+        })
+    }
+
+    /// Applies many priority updates at once, as if by `update_priority` on
+    /// each item in order (a later update to the same item wins).
+    ///
+    /// Each item is matched by identity (`Eq` / `Hash`) and replaces the
+    /// stored item, just like `update_priority`. When the batch is small the
+    /// items are re-sifted one by one; once it reaches roughly `n / log_d n`
+    /// updates, per-item sifting would cost more than a rebuild, so the new
+    /// items are written in place and the heap is rebuilt once in O(n).
+    /// Label-correcting graph searches, which relax many edges in bursts,
+    /// are the intended use.
+    ///
+    /// **Time Complexity**: `O(min(k · (d+1) · log_d n, n + k))` for `k` updates
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if any item is not in the heap. The
+    /// batch is checked before anything is applied, so on error the heap is
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Node { id: u32, dist: u32 }
+    /// impl PartialEq for Node { fn eq(&self, o: &Self) -> bool { self.id == o.id } }
+    /// impl Eq for Node {}
+    /// impl Hash for Node { fn hash<H: Hasher>(&self, h: &mut H) { self.id.hash(h) } }
+    ///
+    /// let mut heap = PriorityQueue::new(4, MinBy(|n: &Node| n.dist)).unwrap();
+    /// heap.insert_many((0..8).map(|id| Node { id, dist: 100 }));
+    ///
+    /// heap.update_priorities((0..8).map(|id| Node { id, dist: 100 - id * 10 })).unwrap();
+    /// assert_eq!(heap.front().id, 7);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn update_priorities(
+        &mut self,
+        updated_items: impl IntoIterator<Item = T>,
+    ) -> Result<(), Error> {
+        let updates: Vec<T> = updated_items.into_iter().collect();
+        if !updates
+            .iter()
+            .all(|u| self.positions.contains(u, &self.container))
+        {
+            return Err(Error::ItemNotFound);
+        }
+        self.bracket(OperationType::UpdatePriority, |s| {
+            let rebuild = s.rebuild_pays(updates.len());
+
+            for updated_item in updates {
+                // Looked up now, not before the loop: earlier sifts move items.
+                // EXPLICIT: every item was checked above, so `continue` is
+                // unreachable.
+                let Some(i) = s.positions.get(&updated_item, &s.container) else {
+                    continue;
+                };
+                s.positions.refresh(&updated_item, i);
+                let i = i.index();
+                s.container[i] = updated_item;
+                if !rebuild {
+                    s.sift_up(i);
+                    s.sift_down(i);
+                }
+            }
+            if rebuild {
+                s.heapify();
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes and returns the highest-priority item from the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(5);
+    /// heap.insert(3);
+    /// heap.insert(7);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(5));
     /// assert_eq!(heap.pop(), Some(7));
     /// assert_eq!(heap.pop(), None);
     /// ```
@@ -737,25 +1553,183 @@ where
     /// - TypeScript: `pop()` (returns `T | undefined`)
     /// - Go: `Pop()` (returns `T, bool`)
     pub fn pop(&mut self) -> Option<T> {
+        self.bracket(OperationType::Pop, Self::pop_root)
+    }
+
+    /// Pops the front only if `predicate` accepts it, in one call: under a
+    /// `Mutex<PriorityQueue>`, "pop the next event if it is due" cannot race
+    /// with another thread between a `peek` and a `pop`.
+    ///
+    /// Returns `None`, leaving the heap unchanged, if the heap is empty or the
+    /// predicate rejects the front.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)` when it pops, O(1) otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// // Events keyed by due time
+    /// let mut events = PriorityQueue::new(4, MinBy(|due: &u64| *due)).unwrap();
+    /// events.insert_many(vec![30, 10, 20]);
+    ///
+    /// let now = 25;
+    /// assert_eq!(events.pop_if(|&due| due <= now), Some(10));
+    /// assert_eq!(events.pop_if(|&due| due <= now), Some(20));
+    /// assert_eq!(events.pop_if(|&due| due <= now), None);
+    /// assert_eq!(events.len(), 1);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop_if(&mut self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
         self.bracket(OperationType::Pop, |s| {
-            if s.container.is_empty() {
-                return None;
+            if predicate(s.container.first()?) {
+                s.pop_root()
+            } else {
+                None
             }
-            let last = s.container.len() - 1;
-            s.swap(0, last);
-            // `?` instead of `.unwrap()` — semantically unreachable
-            // (the early-return above guarantees `container` is non-empty)
-            // but clippy::missing_panics_doc fires on the unwrap. The `?`
-            // collapses to the same dead-code path under release.
-            let removed = s.container.pop()?;
-            s.positions.remove(&removed);
-            if !s.container.is_empty() {
-                s.move_down(0);
+        })
+    }
+
+    /// Returns whether the front has strictly higher priority than `probe`
+    /// under the heap's comparator, `false` if the heap is empty. `probe`
+    /// need not be in the heap; a front that ties with it is not better.
+    ///
+    /// This is the test a pruning loop needs ("stop expanding once the best
+    /// open node cannot beat the incumbent") without restating the
+    /// comparator; [`pop_if_better_than`](PriorityQueue::pop_if_better_than)
+    /// pops on the same test.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// // Frontier of (bound, node) pairs, lowest bound first
+    /// let mut frontier = PriorityQueue::new(4, MinBy(|open: &(u32, char)| open.0)).unwrap();
+    /// frontier.insert_many(vec![(7, 'a'), (3, 'b'), (9, 'c')]);
+    ///
+    /// let incumbent = (7, '-');
+    /// let mut expanded = Vec::new();
+    /// while frontier.front_better_than(&incumbent) {
+    ///     expanded.push(frontier.pop().unwrap().1);
+    /// }
+    /// assert_eq!(expanded, ['b']);
+    /// assert!(!PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap().front_better_than(&0));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn front_better_than(&self, probe: &T) -> bool {
+        self.container
+            .first()
+            .is_some_and(|front| self.compare(front, probe))
+    }
+
+    /// Pops the front only if it has strictly higher priority than
+    /// `threshold` under the heap's comparator: [`pop_if`](PriorityQueue::pop_if)
+    /// with the comparator as the predicate. `threshold` need not be in the
+    /// heap; a front that ties with it stays.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)` when it pops, O(1) otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MaxBy};
+    ///
+    /// let mut bids = PriorityQueue::new(2, MaxBy(|bid: &u32| *bid)).unwrap();
+    /// bids.insert_many(vec![120, 95, 100]);
+    ///
+    /// // Take bids above the reserve price of 100
+    /// assert_eq!(bids.pop_if_better_than(&100), Some(120));
+    /// assert_eq!(bids.pop_if_better_than(&100), None);
+    /// assert_eq!(bids.front(), &100);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop_if_better_than(&mut self, threshold: &T) -> Option<T> {
+        self.bracket(OperationType::Pop, |s| {
+            if s.compare(s.container.first()?, threshold) {
+                s.pop_root()
+            } else {
+                None
             }
-            Some(removed)
         })
     }
 
+    /// Inserts `item` and pops the front, in one sift-down: if the front has
+    /// strictly higher priority than `item`, `item` takes its place at the
+    /// root and the front is returned; otherwise `item` itself is returned
+    /// and the heap is unchanged. Same result as `insert` then `pop`, at
+    /// about half the cost.
+    ///
+    /// `item` must not already be in the heap, as for `insert`.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`, O(1) when `item` comes out
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// // Keep the 3 largest values seen: the heap's front is the smallest kept
+    /// let mut top = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// top.insert_many(vec![40, 10, 30]);
+    /// assert_eq!(top.push_pop(20), 10); // 20 replaces 10
+    /// assert_eq!(top.push_pop(5), 5); // 5 is never kept
+    /// assert_eq!(top.pop_many(3), vec![20, 30, 40]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn push_pop(&mut self, item: T) -> T {
+        self.bracket(OperationType::Pop, |s| match s.container.first() {
+            Some(front) if s.compare(front, &item) => s.replace_root(item),
+            _ => item,
+        })
+    }
+
+    /// Pops the front and inserts `item`, in one sift-down: `item` takes the
+    /// root's place and sinks to its level. Unlike
+    /// [`push_pop`](Self::push_pop), the front leaves even when `item`
+    /// outranks it, like Python's `heapq.heapreplace`. Same result as `pop`
+    /// then `insert`, at about half the cost. On an empty heap `item` is
+    /// inserted and `None` is returned.
+    ///
+    /// `item` must not already be in the heap, unless it is the front
+    /// itself.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// // Merge sorted runs: the front's successor from its run replaces it
+    /// let mut heads = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// heads.insert_many(vec![1, 4, 7]);
+    /// assert_eq!(heads.replace_front(2), Some(1));
+    /// assert_eq!(heads.replace_front(9), Some(2)); // leaves even if 9 ranks lower
+    /// assert_eq!(heads.to_array()[0], 4);
+    ///
+    /// let mut empty = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// assert_eq!(empty.replace_front(5), None);
+    /// assert_eq!(empty.front(), &5);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn replace_front(&mut self, item: T) -> Option<T> {
+        if self.container.is_empty() {
+            self.insert(item);
+            return None;
+        }
+        Some(self.bracket(OperationType::Pop, |s| s.replace_root(item)))
+    }
+
     /// Returns a copy of the heap contents as a Vec.
     ///
     /// The root element (highest priority) is at index 0. The internal heap
@@ -788,6 +1762,61 @@ where
         self.container.clone()
     }
 
+    /// Borrows the heap contents in array order: the zero-copy form of
+    /// [`to_array`](PriorityQueue::to_array), for debugging and serializers
+    /// that only need to read.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 3, 7]);
+    ///
+    /// assert_eq!(heap.as_slice()[0], 3);
+    /// assert_eq!(heap.as_slice(), heap.to_array().as_slice());
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only; other languages
+    /// return `to_array()` copies).
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.container
+    }
+
+    /// Renders the queue with `format` applied to each item, in the
+    /// `{a, b, c}` layout of `Display`, for items that have no `Display` or
+    /// one too verbose to log.
+    ///
+    /// The returned [`DisplayAdapter`] formats lazily: nothing is walked
+    /// until it is written, and each write walks the items again.
+    ///
+    /// **Time Complexity**: O(1); O(n) calls to `format` per write
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &(u8, u32)| x.1)).unwrap();
+    /// heap.insert((1, 20));
+    /// heap.insert((2, 10));
+    ///
+    /// let shown = heap.format_with(|&(id, cost)| format!("#{id}:{cost}"));
+    /// assert_eq!(format!("queue {shown}"), "queue {#2:10, #1:20}");
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn format_with<F>(&self, format: F) -> DisplayAdapter<'_, T, F>
+    where
+        F: Fn(&T) -> String,
+    {
+        DisplayAdapter::new(&self.container, format)
+    }
+
     /// Inserts multiple items into the heap using Floyd's heapify algorithm.
     ///
     /// This is more efficient than inserting items one at a time when adding
@@ -822,21 +1851,46 @@ where
             // Add all items to container and positions
             let start_idx = s.container.len();
             for (i, item) in items.into_iter().enumerate() {
-                s.positions.insert(item.clone(), start_idx + i);
+                s.positions.insert(&item, Position::new(start_idx + i));
                 s.container.push(item);
             }
 
-            // Floyd's heapify: sift down from the last non-leaf to the root
-            // This achieves O(n) instead of O(n log n) for individual inserts
-            if s.container.len() > 1 {
-                let last_non_leaf = (s.container.len() - 2) / s.depth;
-                for i in (0..=last_non_leaf).rev() {
-                    s.move_down(i);
-                }
-            }
+            s.heapify();
         });
     }
 
+    /// Starts a batch of inserts whose sifting is deferred: the returned
+    /// guard appends items in O(1) each and restores heap order once, when it
+    /// is dropped. See the [`deferred`] module.
+    ///
+    /// Prefer [`insert_many`](PriorityQueue::insert_many) when the whole
+    /// batch is already in hand; the guard is for items that arrive one at a
+    /// time with no reads in between.
+    ///
+    /// **Time Complexity**: `O(min(k · log_d n, n + k))` for `k` items, paid
+    /// when the guard is dropped
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MaxBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MaxBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(10);
+    /// {
+    ///     let mut batch = heap.bulk_insert_deferred();
+    ///     batch.push(3);
+    ///     batch.extend([25, 8]);
+    ///     assert_eq!(batch.pending(), 3);
+    /// }
+    /// assert_eq!(heap.pop_many(4), vec![25, 10, 8, 3]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn bulk_insert_deferred(&mut self) -> DeferredInsert<'_, T, C, S, P> {
+        DeferredInsert::new(self)
+    }
+
     /// Removes and returns multiple highest-priority items from the heap.
     ///
     /// Returns up to `count` items in priority order (highest priority first).
@@ -884,121 +1938,1339 @@ where
         result
     }
 
-    /// Read-only access to the heap's stats collector. With the default
-    /// `S = NoOpStats`, this returns a reference to a zero-sized type whose
-    /// query methods all return 0 — matching the C++ `pq.stats()` semantics.
-    /// Use the `InstrumentedPriorityQueue` alias to get a heap whose `stats()`
-    /// actually counts.
-    #[inline]
-    #[must_use]
-    pub fn stats(&self) -> &S {
-        &self.stats
-    }
-
-    /// Comparator wrapper that increments the stats counter for the
-    /// currently-active operation. With `S = NoOpStats`, the
-    /// `count_comparison()` call is an empty inline body and the wrapper
-    /// inlines to a bare `comparator.higher_priority(a, b)` — zero overhead.
-    #[inline]
-    fn compare(&self, a: &T, b: &T) -> bool {
-        self.stats.count_comparison();
-        self.comparator.higher_priority(a, b)
-    }
-
-    /// Bracket a single public mutator with `start_operation` / `end_operation`
-    /// around `f`. Closure-based instead of RAII because Rust's borrow checker
-    /// rejects an RAII guard that holds `&self.stats` while the body wants
-    /// `&mut self` for `move_up` / `move_down`. The closure captures `&mut Self`
-    /// only for its own duration; after it returns, the bracket calls
-    /// `end_operation` on the now-free reference. With `S = NoOpStats`, both
-    /// trait calls are empty and the entire bracket inlines away.
+    /// Consumes the queue into an iterator that pops its items in priority
+    /// order, highest first. Each `next()` is one `pop`, so taking the first
+    /// `k` items costs `k` pops, not a full sort.
     ///
-    /// Note: panic-safety is intentionally NOT preserved (a panic inside `f`
-    /// skips `end_operation`). Acceptable because (a) panics in the heap are
-    /// programmer-error territory, (b) the next operation calls
-    /// `start_operation` which overwrites `current_op`, so stats remain
-    /// internally consistent across operations.
-    #[inline]
-    fn bracket<R>(&mut self, op: OperationType, f: impl FnOnce(&mut Self) -> R) -> R {
-        self.stats.start_operation(op);
-        let result = f(self);
-        self.stats.end_operation();
-        result
+    /// **Time Complexity**: `O(d · log_d n)` per item
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, -2, 9, 0]);
+    ///
+    /// let sorted: Vec<i32> = heap.into_iter_sorted().collect();
+    /// assert_eq!(sorted, vec![-2, 0, 5, 9]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, C, S, P> {
+        IntoIterSorted::new(self)
     }
 
-    #[inline]
-    fn parent(&self, i: usize) -> usize {
-        assert!(i > 0 && self.depth > 0);
-        (i - 1) / self.depth
+    /// Returns an iterator that pops the queue's items in priority order,
+    /// highest first. The queue is empty once the iterator is dropped: items
+    /// not popped by then are removed unsorted, in O(n).
+    ///
+    /// **Time Complexity**: `O(d · log_d n)` per item
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MaxBy};
+    ///
+    /// let mut heap = PriorityQueue::new(4, MaxBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![3, 8, 1, 6]);
+    ///
+    /// assert_eq!(heap.drain_sorted().take(2).collect::<Vec<_>>(), vec![8, 6]);
+    /// assert!(heap.is_empty());
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C, S, P> {
+        DrainSorted::new(self)
     }
 
-    fn best_child_position(&self, i: usize) -> usize {
-        let n = self.container.len();
-        let left = i * self.depth + 1;
-        if left >= n {
-            return left;
-        }
-        let right = ((i + 1) * self.depth).min(n - 1);
-        let mut best = left;
-        for p in (left + 1)..=right {
-            if self.compare(&self.container[p], &self.container[best]) {
-                best = p;
+    /// Pops and processes items in priority order until `budget` has elapsed,
+    /// the heap is empty, or `f` returns `ControlFlow::Break`. Returns the
+    /// number of items passed to `f`.
+    ///
+    /// The clock is checked before each pop, so no item is removed once the
+    /// budget is spent and unprocessed items stay queued for the next frame.
+    /// A single call to `f` cannot be interrupted: keep per-item work small
+    /// relative to the budget, or break out early from `f`.
+    ///
+    /// **Time Complexity**: `O(k · d · log_d n)` for `k` items processed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    /// use std::ops::ControlFlow;
+    /// use std::time::Duration;
+    ///
+    /// let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 4, 2, 3]);
+    ///
+    /// let mut done = Vec::new();
+    /// let frame = Duration::from_millis(16);
+    /// let n = heap.pop_for(frame, |job| {
+    ///     done.push(job);
+    ///     if job == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    /// });
+    /// assert_eq!((n, done), (3, vec![1, 2, 3]));
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop_for(&mut self, budget: Duration, mut f: impl FnMut(T) -> ControlFlow<()>) -> usize {
+        // Like `pop_many`, delegates to `pop()` so each pop is bracketed
+        // individually; the time spent in `f` is not attributed to the heap.
+        let start = Instant::now();
+        let mut processed = 0;
+        while start.elapsed() < budget {
+            let Some(item) = self.pop() else {
+                break;
+            };
+            processed += 1;
+            if f(item).is_break() {
+                break;
             }
         }
-        best
-    }
-
-    fn swap(&mut self, i: usize, j: usize) {
-        if i == j {
-            return;
-        }
-        self.container.swap(i, j);
-        let ti = self.container[i].clone();
-        let tj = self.container[j].clone();
-        self.positions.insert(ti, i);
-        self.positions.insert(tj, j);
+        processed
     }
 
-    fn move_up(&mut self, mut i: usize) {
-        while i > 0 {
-            let p = self.parent(i);
-            if self.compare(&self.container[i], &self.container[p]) {
-                self.swap(i, p);
-                i = p;
-            } else {
-                break;
+    /// Removes and returns every item that is "close enough" to the current
+    /// highest-priority item, in one relaxed batch.
+    ///
+    /// `within(best, candidate)` decides whether `candidate` belongs in the
+    /// same batch as the current front `best`, e.g. `|b, c| c.cost - b.cost <= 5`.
+    /// Items inside the batch are returned in heap-array order, not priority
+    /// order (the front always comes first); this relaxed ordering is the
+    /// point of the method for load shedding and other throughput-bound
+    /// consumers that only need "roughly the best" items.
+    ///
+    /// **Error bound**: provided `within` is monotone (a candidate that fails
+    /// implies every lower-priority candidate fails too, as any tolerance on
+    /// a priority key is), the batch is *exactly* the set of items for which
+    /// `within(best, item)` holds. So no returned item is worse than the best
+    /// by more than the tolerance, and no item that qualifies is left behind.
+    /// The heap stays valid even if `within` is not monotone, but the batch
+    /// is then unspecified.
+    ///
+    /// The search visits only the qualifying items and their children, since
+    /// heap order lets it prune every subtree whose root fails `within`.
+    ///
+    /// **Time Complexity**: `O(k · d · log_d n)` for a batch of `k` items
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![12, 3, 40, 5, 7, 20, 4]);
+    ///
+    /// let mut batch = heap.pop_within(|best, x| x - best <= 4);
+    /// assert_eq!(batch[0], 3);
+    /// batch.sort_unstable();
+    /// assert_eq!(batch, vec![3, 4, 5, 7]);
+    /// assert_eq!(heap.front(), &12);
+    /// assert_eq!(heap.pop_within(|_, _| false), vec![12]); // the front always qualifies
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop_within(&mut self, within: impl Fn(&T, &T) -> bool) -> Vec<T> {
+        self.bracket(OperationType::Pop, |s| {
+            let n = s.container.len();
+            if n == 0 {
+                return Vec::new();
+            }
+            let mut batch = vec![0];
+            let mut stack: Vec<usize> = vec![0];
+            while let Some(i) = stack.pop() {
+                let first = i.saturating_mul(s.depth).saturating_add(1).min(n);
+                let last = first.saturating_add(s.depth).min(n);
+                for c in first..last {
+                    if within(&s.container[0], &s.container[c]) {
+                        batch.push(c);
+                        stack.push(c);
+                    }
+                }
+            }
+
+            // EXPLICIT: remove deepest first. The item moved into a freed slot
+            // comes from past every batch position still pending, so it is
+            // outside the batch and (by monotonicity) never sifts above a
+            // pending batch item: pending positions stay valid.
+            batch.sort_unstable_by(|a, b| b.cmp(a));
+            let mut items: Vec<T> = batch.into_iter().map(|i| s.remove_at(i)).collect();
+            items.reverse();
+            items
+        })
+    }
+
+    /// Removes `item` (matched by identity) from anywhere in the heap and
+    /// returns it, or `None` if it is not in the heap.
+    ///
+    /// The last item takes the removed one's slot and is sifted up or down
+    /// from there, so cancelling a pending task costs no more than a `pop`.
+    /// Comparisons and swaps are attributed to `OperationType::Pop`.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(3, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![40, 10, 30, 20]);
+    ///
+    /// assert_eq!(heap.remove(&30), Some(30));
+    /// assert_eq!(heap.remove(&30), None);
+    /// assert_eq!(heap.pop_many(3), vec![10, 20, 40]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn remove(&mut self, item: &T) -> Option<T> {
+        let i = self.positions.get(item, &self.container)?;
+        Some(self.remove_found(i.index()))
+    }
+
+    /// Body of [`remove`](PriorityQueue::remove) once the item is found at
+    /// `i`; keyed queues share it so both count the work alike.
+    fn remove_found(&mut self, i: usize) -> T {
+        self.bracket(OperationType::Pop, |s| s.remove_at(i))
+    }
+
+    /// Removes every listed item that is in the heap, matched by identity
+    /// (`Eq` / `Hash`), and returns the removed items in the order listed.
+    ///
+    /// Items not in the heap, and repeats of an item already removed, are
+    /// skipped, so a cancellation list may be stale. A small batch is removed
+    /// item by item; once it reaches roughly `n / log_d n` items the
+    /// survivors are compacted and the heap rebuilt once in O(n), as in
+    /// `update_priorities`. Bulk cancellation ("cancel all jobs of tenant
+    /// X", with the job list kept in an external index) is the intended use.
+    /// Comparisons and swaps are attributed to `OperationType::Pop`.
+    ///
+    /// **Time Complexity**: `O(min(k · d · log_d n, n + k))` for `k` items
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(1..=10);
+    ///
+    /// let removed = heap.remove_many(&[8, 2, 42, 2]);
+    /// assert_eq!(removed, vec![8, 2]);
+    /// assert_eq!(heap.len(), 8);
+    /// assert_eq!(heap.pop_many(3), vec![1, 3, 4]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn remove_many<'a>(&mut self, items: impl IntoIterator<Item = &'a T>) -> Vec<T>
+    where
+        T: 'a,
+    {
+        let items: Vec<&T> = items.into_iter().collect();
+        self.bracket(OperationType::Pop, |s| {
+            if !s.rebuild_pays(items.len()) {
+                return items
+                    .into_iter()
+                    .filter_map(|item| {
+                        let i = s.positions.get(item, &s.container)?;
+                        Some(s.remove_at(i.index()))
+                    })
+                    .collect();
+            }
+
+            // Take the listed items out of their slots, compact the rest,
+            // re-index the survivors and rebuild.
+            let mut targets: Vec<Position> = Vec::with_capacity(items.len());
+            for item in items {
+                if let Some(i) = s.positions.get(item, &s.container) {
+                    s.positions.remove(item, i);
+                    targets.push(i);
+                }
+            }
+            let mut slots: Vec<Option<T>> = std::mem::take(&mut s.container)
+                .into_iter()
+                .map(Some)
+                .collect();
+            let removed: Vec<T> = targets
+                .into_iter()
+                // INDEX: positions are in bounds and distinct (each removed
+                // from `positions` once), so every slot is taken once.
+                .filter_map(|i| slots[i.index()].take())
+                .collect();
+            let positions = &mut s.positions;
+            let mut kept = Vec::with_capacity(slots.len() - removed.len());
+            for (from, item) in slots.into_iter().enumerate() {
+                if let Some(item) = item {
+                    positions.reposition(&item, Position::new(from), Position::new(kept.len()));
+                    kept.push(item);
+                }
+            }
+            s.container = kept;
+            s.heapify();
+            removed
+        })
+    }
+
+    /// Removes every item for which `predicate` returns `true` and returns
+    /// them in heap-array order.
+    ///
+    /// The predicate sees each item once. If it matches nothing, the heap is
+    /// left untouched; otherwise the survivors are compacted and the heap is
+    /// rebuilt once in O(n), however many items match. Pruning a search
+    /// frontier against a new incumbent ("drop every node whose bound is no
+    /// better") is the intended use. Comparisons and swaps are attributed to
+    /// `OperationType::Pop`.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(1..=10);
+    ///
+    /// let mut drained = heap.drain_where(|&x| x > 7);
+    /// drained.sort_unstable();
+    /// assert_eq!(drained, vec![8, 9, 10]);
+    /// assert_eq!(heap.len(), 7);
+    /// assert_eq!(heap.front(), &1);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn drain_where(&mut self, predicate: impl FnMut(&T) -> bool) -> Vec<T> {
+        self.bracket(OperationType::Pop, |s| {
+            let doomed: Vec<bool> = s.container.iter().map(predicate).collect();
+            if !doomed.contains(&true) {
+                return Vec::new();
+            }
+
+            let mut drained = Vec::new();
+            let mut kept = Vec::with_capacity(s.container.len());
+            let items = std::mem::take(&mut s.container);
+            let positions = &mut s.positions;
+            for (from, (item, doomed)) in items.into_iter().zip(doomed).enumerate() {
+                if doomed {
+                    positions.remove(&item, Position::new(from));
+                    drained.push(item);
+                } else {
+                    positions.reposition(&item, Position::new(from), Position::new(kept.len()));
+                    kept.push(item);
+                }
+            }
+            s.container = kept;
+            s.heapify();
+            drained
+        })
+    }
+
+    /// Keeps only the items for which `predicate` returns `true`:
+    /// [`drain_where`](PriorityQueue::drain_where) with the predicate
+    /// negated, dropping the removed items.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MaxBy};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MaxBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![4, -2, 9, -7, 1]);
+    ///
+    /// heap.retain(|&x| x > 0);
+    /// assert_eq!(heap.pop_many(5), vec![9, 4, 1]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn retain(&mut self, mut predicate: impl FnMut(&T) -> bool) {
+        self.drain_where(|item| !predicate(item));
+    }
+
+    /// Keeps only the items also in `other`, matched by identity, and
+    /// returns the others in heap-array order.
+    ///
+    /// The kept items keep this queue's priorities; `other` only says which
+    /// identities survive, so its comparator, stats and arity do not matter
+    /// ("keep only the jobs the authoritative store still lists"). Each item
+    /// is looked up in `other`'s position index once and the heap is rebuilt
+    /// at most once, as in [`drain_where`](PriorityQueue::drain_where).
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, MaxBy};
+    ///
+    /// let mut local = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// local.insert_many(1..=6);
+    /// let mut store = PriorityQueue::new(2, MaxBy(|x: &u32| *x)).unwrap();
+    /// store.insert_many(vec![2, 4, 6, 8]);
+    ///
+    /// let mut dropped = local.intersect_with(&store);
+    /// dropped.sort_unstable();
+    /// assert_eq!(dropped, vec![1, 3, 5]);
+    /// assert_eq!(local.pop_many(3), vec![2, 4, 6]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn intersect_with<C2, S2, P2>(&mut self, other: &PriorityQueue<T, C2, S2, P2>) -> Vec<T>
+    where
+        P2: PositionIndex<T>,
+    {
+        self.drain_where(|item| other.positions.get(item, &other.container).is_none())
+    }
+
+    /// Removes the items also in `other`, matched by identity, and returns
+    /// them in heap-array order.
+    ///
+    /// The complement of [`intersect_with`](PriorityQueue::intersect_with):
+    /// the items that stay keep this queue's priorities, and the heap is
+    /// rebuilt at most once.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut pending = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// pending.insert_many(1..=6);
+    /// let mut done = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// done.insert_many(vec![2, 3, 9]);
+    ///
+    /// let mut removed = pending.difference_with(&done);
+    /// removed.sort_unstable();
+    /// assert_eq!(removed, vec![2, 3]);
+    /// assert_eq!(pending.pop_many(4), vec![1, 4, 5, 6]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn difference_with<C2, S2, P2>(&mut self, other: &PriorityQueue<T, C2, S2, P2>) -> Vec<T>
+    where
+        P2: PositionIndex<T>,
+    {
+        self.drain_where(|item| other.positions.get(item, &other.container).is_some())
+    }
+
+    /// Lists how this queue's contents differ from `other`'s: the items
+    /// only one of them holds, and the items both hold at different
+    /// priorities. See the [`diff`] module.
+    ///
+    /// Items are matched by identity and priorities compared with this
+    /// queue's comparator, as `==` does; array layouts and arities are
+    /// ignored. The diff borrows from both queues.
+    ///
+    /// **Time Complexity**: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut left = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// left.insert_many(vec![1, 2, 3]);
+    /// let mut right = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    /// right.insert_many(vec![3, 2, 4]);
+    ///
+    /// let diff = left.diff(&right);
+    /// assert_eq!(diff.only_left, [&1]);
+    /// assert_eq!(diff.only_right, [&4]);
+    /// assert!(diff.changed.is_empty());
+    /// assert_eq!(diff.to_string(), "- 1\n+ 4\n");
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn diff<'a, C2, S2, P2>(
+        &'a self,
+        other: &'a PriorityQueue<T, C2, S2, P2>,
+    ) -> HeapDiff<'a, T>
+    where
+        P2: PositionIndex<T>,
+    {
+        let mut only_left = Vec::new();
+        let mut changed = Vec::new();
+        for a in &self.container {
+            match other.positions.get(a, &other.container) {
+                None => only_left.push(a),
+                Some(j) => {
+                    // INDEX: `positions` only holds in-bounds positions.
+                    let b = &other.container[j.index()];
+                    if self.comparator.higher_priority(a, b)
+                        || self.comparator.higher_priority(b, a)
+                    {
+                        changed.push((a, b));
+                    }
+                }
             }
         }
+        let only_right = other
+            .container
+            .iter()
+            .filter(|b| self.positions.get(b, &self.container).is_none())
+            .collect();
+        HeapDiff {
+            only_left,
+            only_right,
+            changed,
+        }
     }
 
-    fn move_down(&mut self, mut i: usize) {
-        let n = self.container.len();
-        loop {
-            let first_child = i * self.depth + 1;
-            if first_child >= n {
-                break;
+    /// Sifts the item at `i` toward the root until its parent ranks at least
+    /// as high, and returns the item's final position.
+    ///
+    /// This is the primitive behind `insert` and `increase_priority`, exposed
+    /// for composing custom bulk operations. It restores the heap invariant
+    /// only if the item at `i` is the sole item out of place — e.g. one whose
+    /// priority lives behind interior mutability (`Cell`, `Rc<Cell<_>>`) and
+    /// was just raised. Comparisons and swaps are attributed to
+    /// `OperationType::IncreasePriority`.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::IndexOutOfBounds` if index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 5, 3, 7]);
+    ///
+    /// // 7 is already below its parent 5, so it stays put
+    /// let leaf = Position::new(3);
+    /// assert_eq!(heap.move_up(leaf), Ok(leaf));
+    /// assert_eq!(heap.move_up(Position::new(99)), Err(Error::IndexOutOfBounds));
+    /// ```
+    ///
+    /// **Cross-language equivalents**:
+    /// - C++: `move_up(i)` (private)
+    /// - Zig: `moveUp(i)` (private)
+    /// - TypeScript: `moveUp(i)` (private)
+    /// - Go: `moveUp(i)` (unexported)
+    pub fn move_up(&mut self, i: Position) -> Result<Position, Error> {
+        self.bracket(OperationType::IncreasePriority, |s| {
+            if !i.is_valid_for(s) {
+                return Err(Error::IndexOutOfBounds);
             }
-            let best = self.best_child_position(i);
-            if self.compare(&self.container[best], &self.container[i]) {
-                self.swap(i, best);
-                i = best;
-            } else {
-                break;
+            Ok(Position::new(s.sift_up(i.index())))
+        })
+    }
+
+    /// Sifts the item at `i` toward the leaves, swapping with its
+    /// highest-priority child until no child outranks it, and returns the
+    /// item's final position.
+    ///
+    /// The counterpart of [`move_up`](Self::move_up), behind `pop` and
+    /// `decrease_priority`; the same "sole item out of place" precondition
+    /// applies. Comparisons and swaps are attributed to
+    /// `OperationType::DecreasePriority`.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::IndexOutOfBounds` if index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 5, 3, 7]);
+    ///
+    /// // The root already outranks both children
+    /// assert_eq!(heap.move_down(Position::ROOT), Ok(Position::ROOT));
+    /// assert_eq!(heap.move_down(Position::new(99)), Err(Error::IndexOutOfBounds));
+    /// ```
+    ///
+    /// **Cross-language equivalents**:
+    /// - C++: `move_down(i)` (private)
+    /// - Zig: `moveDown(i)` (private)
+    /// - TypeScript: `moveDown(i)` (private)
+    /// - Go: `moveDown(i)` (unexported)
+    pub fn move_down(&mut self, i: Position) -> Result<Position, Error> {
+        self.bracket(OperationType::DecreasePriority, |s| {
+            if !i.is_valid_for(s) {
+                return Err(Error::IndexOutOfBounds);
             }
+            Ok(Position::new(s.sift_down(i.index())))
+        })
+    }
+
+    /// Returns the position of the highest-priority child of the item at `i`,
+    /// or `None` if `i` is out of bounds or a leaf.
+    ///
+    /// Ties go to the leftmost child, matching the choice `move_down` makes.
+    /// Called outside any operation, so its comparisons are not counted by
+    /// `ComparisonStats`.
+    ///
+    /// **Time Complexity**: `O(d)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Position};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![1, 5, 3, 7]);
+    ///
+    /// let at = Position::new;
+    /// assert_eq!(heap.best_child_position(at(0)), Some(at(2))); // 3 beats 5
+    /// assert_eq!(heap.best_child_position(at(1)), Some(at(3))); // only child
+    /// assert_eq!(heap.best_child_position(at(2)), None);        // leaf
+    /// ```
+    ///
+    /// **Cross-language equivalents**:
+    /// - C++: `best_child_position(i)` (private)
+    /// - Zig: `bestChildPosition(i)` (private)
+    /// - TypeScript: `bestChildPosition(i)` (private)
+    /// - Go: `bestChildPosition(i)` (unexported)
+    #[must_use]
+    pub fn best_child_position(&self, i: Position) -> Option<Position> {
+        let i = i.index();
+        // INDEX: i * depth + 1 >= len covers both "out of bounds" and "leaf"
+        // (for i >= len, the first child index is past the end as well).
+        if i.saturating_mul(self.depth).saturating_add(1) >= self.container.len() {
+            return None;
+        }
+        Some(Position::new(self.best_child(i, 0)))
+    }
+
+    /// Consumes the heap and returns its storage: the container in heap
+    /// order, the item-to-position index (a [`HashIndex`] unless the heap was
+    /// built with [`with_position_index`](PriorityQueue::with_position_index)),
+    /// and the arity.
+    ///
+    /// Nothing is copied. Pair with [`from_raw_parts`](PriorityQueue::from_raw_parts)
+    /// to hand the storage to FFI, a serializer, or an arena and rebuild the
+    /// queue later. The comparator and stats collector are dropped.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Position, PositionIndex};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    ///
+    /// let (container, positions, d) = heap.into_raw_parts();
+    /// assert_eq!(container[0], 1);
+    /// assert_eq!(positions.get(&1, &container), Some(Position::ROOT));
+    /// assert_eq!(d, 2);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only; other languages
+    /// expose `to_array()` copies).
+    #[must_use]
+    pub fn into_raw_parts(self) -> (Vec<T>, P, usize) {
+        (self.container, self.positions, self.depth)
+    }
+
+    /// Read-only access to the heap's stats collector. With the default
+    /// `S = NoOpStats`, this returns a reference to a zero-sized type whose
+    /// query methods all return 0 — matching the C++ `pq.stats()` semantics.
+    /// Use the `InstrumentedPriorityQueue` alias to get a heap whose `stats()`
+    /// actually counts.
+    #[inline]
+    #[must_use]
+    pub fn stats(&self) -> &S {
+        &self.stats
+    }
+
+    /// Body of [`pop`](PriorityQueue::pop), without the operation bracket.
+    fn pop_root(&mut self) -> Option<T> {
+        if self.container.is_empty() {
+            return None;
+        }
+        let last = self.container.len() - 1;
+        self.swap(0, last);
+        // `?` instead of `.unwrap()` — semantically unreachable
+        // (the early-return above guarantees `container` is non-empty)
+        // but clippy::missing_panics_doc fires on the unwrap. The `?`
+        // collapses to the same dead-code path under release.
+        let removed = self.container.pop()?;
+        self.positions.remove(&removed, Position::new(last));
+        if !self.container.is_empty() {
+            self.sift_down(0);
+        }
+        Some(removed)
+    }
+
+    /// Puts `item` at the root (which must exist) in place of the front,
+    /// sifts it down and returns the old front.
+    fn replace_root(&mut self, item: T) -> T {
+        let front = std::mem::replace(&mut self.container[0], item);
+        // Unindex the old front before indexing `item`, which may equal it
+        self.positions.remove(&front, Position::ROOT);
+        self.positions.insert(&self.container[0], Position::ROOT);
+        self.sift_down(0);
+        front
+    }
+
+    /// Comparator wrapper that increments the stats counter for the
+    /// currently-active operation. With `S = NoOpStats`, the
+    /// `count_comparison()` call is an empty inline body and the wrapper
+    /// inlines to a bare `comparator.higher_priority(a, b)` — zero overhead.
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> bool {
+        self.stats.count_comparison();
+        self.comparator.higher_priority(a, b)
+    }
+
+    /// Bracket a single public mutator with `start_operation` / `end_operation`
+    /// around `f`. Closure-based instead of RAII because Rust's borrow checker
+    /// rejects an RAII guard that holds `&self.stats` while the body wants
+    /// `&mut self` for `sift_up` / `sift_down`. The closure captures `&mut Self`
+    /// only for its own duration; after it returns, the bracket calls
+    /// `end_operation` on the now-free reference. With `S = NoOpStats`, both
+    /// trait calls are empty and the entire bracket inlines away.
+    ///
+    /// Note: panic-safety is intentionally NOT preserved (a panic inside `f`
+    /// skips `end_operation`). Acceptable because (a) panics in the heap are
+    /// programmer-error territory, (b) the next operation calls
+    /// `start_operation` which overwrites `current_op`, so stats remain
+    /// internally consistent across operations.
+    #[inline]
+    fn bracket<R>(&mut self, op: OperationType, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.bracket_from(op, self.container.len(), f)
+    }
+
+    /// [`bracket`](Self::bracket) for an operation whose items were added
+    /// before it started, as by [`DeferredInsert`]: reports `start_len` as
+    /// the length the operation began from.
+    #[inline]
+    fn bracket_from<R>(
+        &mut self,
+        op: OperationType,
+        start_len: usize,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.stats.start_operation(op);
+        self.stats.record_len(start_len);
+        let result = f(self);
+        self.stats.record_final_len(self.container.len());
+        self.stats.end_operation();
+        result
+    }
+
+    #[inline]
+    fn parent(&self, i: usize) -> usize {
+        assert!(i > 0 && self.depth > 0);
+        (i - 1) / self.depth
+    }
+
+    /// Whether a batch of `k` changes is cheaper as one O(n) rebuild than as
+    /// `k` sifts of `O(log_d n)` levels each: once `k` reaches `n / log_d n`.
+    fn rebuild_pays(&self, k: usize) -> bool {
+        let n = self.container.len();
+        let mut height = 1;
+        let mut capacity = 1;
+        // EXPLICIT: integer log_d n, one level per iteration.
+        while capacity < n && self.depth > 1 {
+            capacity = capacity.saturating_mul(self.depth);
+            height += 1;
+        }
+        self.depth > 1 && k.saturating_mul(height) >= n
+    }
+
+    /// Floyd's heapify: sift down from the last non-leaf to the root.
+    /// This achieves O(n) instead of O(n log n) for individual inserts.
+    fn heapify(&mut self) {
+        if self.container.len() > 1 {
+            let last_non_leaf = (self.container.len() - 2) / self.depth;
+            for i in (0..=last_non_leaf).rev() {
+                self.sift_down(i);
+            }
+        }
+    }
+
+    /// Position of the highest-priority child of `i`. The scan starts at
+    /// sibling offset `start` (the leftmost child if the group is shorter)
+    /// and wraps around; ties go to the first child scanned.
+    fn best_child(&self, i: usize, start: usize) -> usize {
+        let n = self.container.len();
+        let left = i * self.depth + 1;
+        if left >= n {
+            return left;
+        }
+        let right = ((i + 1) * self.depth).min(n - 1);
+        let count = right - left + 1;
+        self.stats.record_children_scanned(count);
+        let mut best = if start < count { left + start } else { left };
+        let mut p = best;
+        for _ in 1..count {
+            p = if p == right { left } else { p + 1 };
+            if self.compare(&self.container[p], &self.container[best]) {
+                best = p;
+            }
+        }
+        best
+    }
+
+    /// Removes the item at `i` (which must be in bounds) by moving the last
+    /// item into its slot and re-sifting that item.
+    fn remove_at(&mut self, i: usize) -> T {
+        let last = self.container.len() - 1;
+        self.swap(i, last);
+        let removed = self
+            .container
+            .pop()
+            .expect("remove_at on an in-bounds index");
+        self.positions.remove(&removed, Position::new(last));
+        if i < self.container.len() {
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        removed
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        if i == j {
+            return;
+        }
+        self.stats.record_swap(i, j);
+        self.container.swap(i, j);
+        // Both items are indexed already: move their entries in place rather
+        // than cloning them into fresh ones.
+        let positions = &mut self.positions;
+        positions.reposition(&self.container[i], Position::new(j), Position::new(i));
+        positions.reposition(&self.container[j], Position::new(i), Position::new(j));
+    }
+
+    fn sift_up(&mut self, mut i: usize) -> usize {
+        let mut levels = 0;
+        while i > 0 {
+            let p = self.parent(i);
+            if self.compare(&self.container[i], &self.container[p]) {
+                self.swap(i, p);
+                i = p;
+                levels += 1;
+            } else {
+                break;
+            }
+        }
+        self.stats.record_sift_up(levels);
+        i
+    }
+
+    fn sift_down(&mut self, mut i: usize) -> usize {
+        let n = self.container.len();
+        let mut levels = 0;
+        let mut start = 0;
+        loop {
+            let first_child = i * self.depth + 1;
+            if first_child >= n {
+                break;
+            }
+            let best = self.best_child(i, start);
+            if self.child_scan == ChildScan::FollowBest {
+                start = best - first_child;
+            }
+            if self.compare(&self.container[best], &self.container[i]) {
+                self.swap(i, best);
+                i = best;
+                levels += 1;
+            } else {
+                break;
+            }
+        }
+        self.stats.record_sift_down(levels);
+        i
+    }
+}
+
+/// Constructors that produce the default (zero-overhead) heap. These live on
+/// the concrete `PriorityQueue<T, C, NoOpStats>` (= `PriorityQueue<T, C>` via
+/// the struct's defaulted type parameter) so that calls like
+/// `PriorityQueue::new(d, c)` resolve without requiring a type annotation.
+impl<T, C> PriorityQueue<T, C, NoOpStats>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Creates a new empty d-ary heap with specified arity and comparator.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - Arity (number of children per node). Must be ≥ 1.
+    /// * `comparator` - Defines priority order (min-heap or max-heap)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, MaxBy};
+    ///
+    /// // Binary heap (d=2) with min-heap ordering
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    ///
+    /// // Quaternary heap (d=4) with max-heap ordering
+    /// let mut heap = PriorityQueue::new(4, MaxBy(|x: &i32| *x)).unwrap();
+    ///
+    /// // Invalid arity returns error
+    /// assert!(PriorityQueue::new(0, MinBy(|x: &i32| *x)).is_err());
+    /// ```
+    ///
+    /// **Cross-language equivalents**:
+    /// - C++: `PriorityQueue<T>(d)`
+    /// - Zig: `DHeap.init(d, comparator, allocator)` (returns `!T`)
+    /// - TypeScript: `new PriorityQueue({d, comparator, keyExtractor})` (throws)
+    /// - Go: `New(d, comparator)` (returns `*T, error`)
+    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self::with_checked_arity(d, comparator))
+    }
+
+    /// Body of [`new`](PriorityQueue::new) once `d` is known to be ≥ 1; the
+    /// crate's wrappers, which check their arity up front, build through it.
+    pub(crate) fn with_checked_arity(d: usize, comparator: C) -> Self {
+        debug_assert!(d >= 1, "arity must be checked by the caller");
+        Self {
+            container: Vec::new(),
+            positions: HashIndex::new(),
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        }
+    }
+
+    /// Creates an empty d-ary heap whose array and positions map both hold
+    /// `capacity` items before reallocating: for queues whose peak size is
+    /// known, such as every node of a graph.
+    ///
+    /// **Time Complexity**: O(capacity) to allocate
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::with_capacity(4, MinBy(|x: &u32| *x), 10_000).unwrap();
+    /// heap.insert_many(0..10_000);
+    /// assert!(heap.capacity() >= 10_000);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn with_capacity(d: usize, comparator: C, capacity: usize) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            container: Vec::with_capacity(capacity),
+            positions: HashIndex::with_capacity(capacity),
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        })
+    }
+
+    /// Creates a new d-ary heap with specified arity, inserting the first item.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - Arity (number of children per node). Must be ≥ 1.
+    /// * `comparator` - Defines priority order
+    /// * `t` - First item to insert
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::with_first(3, MinBy(|x: &i32| *x), 42).unwrap();
+    /// assert_eq!(heap.front(), &42);
+    /// ```
+    pub fn with_first(d: usize, comparator: C, t: T) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        let mut positions = HashIndex::with_capacity(1);
+        positions.insert(&t, Position::ROOT);
+        let container = vec![t];
+        Ok(Self {
+            container,
+            positions,
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        })
+    }
+
+    /// Creates an empty heap of arity `d` with the comparator's default
+    /// value, for comparators that need no closure: [`MinOrd`] and
+    /// [`MaxOrd`], through the [`MinHeap`] and [`MaxHeap`] aliases.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MaxHeap, MinHeap};
+    ///
+    /// let mut low = MinHeap::with_arity(4).unwrap();
+    /// low.insert_many(vec![7, 2, 9]);
+    /// assert_eq!(low.pop(), Some(2));
+    ///
+    /// let mut high: MaxHeap<&str> = MaxHeap::with_arity(2).unwrap();
+    /// high.insert_many(vec!["pear", "apple", "quince"]);
+    /// assert_eq!(high.pop(), Some("quince"));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn with_arity(d: usize) -> Result<Self, Error>
+    where
+        C: Default,
+    {
+        Self::new(d, C::default())
+    }
+
+    /// Builds a heap of arity `d` from `items` in O(n), with Floyd's heapify.
+    ///
+    /// This is the conversion from a `Vec` with a chosen arity and
+    /// comparator; `PriorityQueue::from(vec)` picks [`DEFAULT_ARITY`] and
+    /// [`MaxOrd`], as `BinaryHeap::from(vec)` does.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let mut heap = PriorityQueue::from_vec(vec![5, 1, 3], 3, MinBy(|x: &i32| *x)).unwrap();
+    /// assert_eq!(heap.pop_many(3), vec![1, 3, 5]);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only; other languages call
+    /// `insert_many` on a new queue).
+    pub fn from_vec(items: Vec<T>, d: usize, comparator: C) -> Result<Self, Error> {
+        let mut heap = Self::new(d, comparator)?;
+        heap.insert_many(items);
+        Ok(heap)
+    }
+
+    /// Builds a heap of arity `d` from `items` already sorted highest
+    /// priority first, without heapifying: a sorted array satisfies the
+    /// d-heap property for every `d`, so only the positions map is built.
+    ///
+    /// Heapify makes no swaps on sorted input but still compares every
+    /// non-leaf with its children, about `n` comparisons; this skips them.
+    /// The order is checked only by a debug assertion, so an unsorted `items`
+    /// yields a heap whose pops are out of order in release builds. Use
+    /// [`from_vec`](PriorityQueue::from_vec) when the order is not certain.
+    ///
+    /// **Time Complexity**: O(n), with no comparisons
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if an item outranks the one before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy};
+    ///
+    /// let deadlines = vec![3, 8, 8, 15, 42];
+    /// let mut heap = PriorityQueue::from_sorted_vec(deadlines, 4, MinBy(|x: &u32| *x)).unwrap();
+    /// assert_eq!(heap.front(), &3);
+    /// heap.insert(1);
+    /// assert_eq!(heap.pop(), Some(1));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn from_sorted_vec(items: Vec<T>, d: usize, comparator: C) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        debug_assert!(
+            items
+                .windows(2)
+                .all(|pair| !comparator.higher_priority(&pair[1], &pair[0])),
+            "from_sorted_vec: items are not sorted highest priority first"
+        );
+        let mut positions = HashIndex::with_capacity(items.len());
+        for (i, item) in items.iter().enumerate() {
+            positions.insert(item, Position::new(i));
+        }
+        Ok(Self {
+            container: items,
+            positions,
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        })
+    }
+}
+
+/// Whether `positions` maps every item of `container` to its own index and
+/// holds nothing else. Equal lengths plus "every item maps to its own index"
+/// rule out both stale extra entries and duplicate items in the container.
+fn indexes_exactly<T, P: PositionIndex<T>>(positions: &P, container: &[T]) -> bool {
+    positions.len() == container.len()
+        && container
+            .iter()
+            .enumerate()
+            .all(|(i, item)| positions.get(item, container) == Some(Position::new(i)))
+}
+
+/// Constructor for a heap with a caller-supplied position index. Kept apart
+/// from `new` for the same reason as the stats constructors: `new` must
+/// resolve on the default heap without annotations.
+impl<T, C, P> PriorityQueue<T, C, NoOpStats, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    P: PositionIndex<T>,
+{
+    /// Creates an empty heap that tracks item positions with `index`
+    /// instead of the default [`HashIndex`]. With a [`DenseIndex`], items identified by
+    /// small integers (graph node ids) are located without hashing, and `T`
+    /// need not implement `Hash`. See the [`position_index`] module.
+    ///
+    /// `index` should be empty; entries it already holds are not in the heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{DenseIndex, MinBy, Position, PriorityQueue};
+    ///
+    /// // Node ids 0..100, prioritised by a distance table
+    /// let dist: Vec<u32> = (0..100).map(|i| (i * 37) % 101).collect();
+    /// let index = DenseIndex::with_capacity(|&node: &usize| node, dist.len());
+    /// let mut heap = PriorityQueue::with_position_index(4, MinBy(|&node: &usize| dist[node]), index).unwrap();
+    /// heap.insert_many(0..100);
+    /// assert_eq!(heap.front(), &0);
+    /// assert_eq!(heap.get_position(&0), Some(Position::ROOT));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn with_position_index(d: usize, comparator: C, index: P) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            container: Vec::new(),
+            positions: index,
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        })
+    }
+
+    /// Rebuilds a heap from storage produced by
+    /// [`into_raw_parts`](PriorityQueue::into_raw_parts), without copying.
+    ///
+    /// The parts are validated before they are accepted: `positions` must map
+    /// every item of `container` to its own index (and nothing else), and no
+    /// child may outrank its parent under `comparator`.
+    ///
+    /// **Time Complexity**: O(n) (one position lookup and one comparison per item)
+    ///
+    /// # Errors
+    ///
+    /// - `Error::InvalidArity` if `d == 0`.
+    /// - `Error::InconsistentPositions` if `positions` does not index `container` exactly.
+    /// - `Error::HeapInvariantViolated` if `container` is not in heap order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{PriorityQueue, MinBy, Error, Position};
+    /// use std::collections::HashMap;
+    ///
+    /// // The index comes back with the array; any `PositionIndex` is accepted
+    ///
+    /// let mut heap = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    /// let (container, positions, d) = heap.into_raw_parts();
+    ///
+    /// let mut heap = PriorityQueue::from_raw_parts(container, positions, d, MinBy(|x: &i32| *x)).unwrap();
+    /// assert_eq!(heap.pop(), Some(1));
+    ///
+    /// // A container out of heap order is rejected
+    /// let positions = HashMap::from([(5, Position::new(0)), (1, Position::new(1))]);
+    /// let result = PriorityQueue::from_raw_parts(vec![5, 1], positions, 2, MinBy(|x: &i32| *x));
+    /// assert_eq!(result.err(), Some(Error::HeapInvariantViolated));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn from_raw_parts(
+        container: Vec<T>,
+        positions: P,
+        d: usize,
+        comparator: C,
+    ) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        if !indexes_exactly(&positions, &container) {
+            return Err(Error::InconsistentPositions);
+        }
+        // INDEX: i >= 1 and (i - 1) / d < i, so the parent index is in bounds.
+        if (1..container.len())
+            .any(|i| comparator.higher_priority(&container[i], &container[(i - 1) / d]))
+        {
+            return Err(Error::HeapInvariantViolated);
+        }
+        Ok(Self {
+            container,
+            positions,
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: NoOpStats,
+        })
+    }
+}
+
+/// Constructor that produces the instrumented (`ComparisonStats`) heap.
+/// Distinct name from `new` so `PriorityQueue::new(...)` stays unambiguous on
+/// the default heap.
+impl<T, C> PriorityQueue<T, C, ComparisonStats>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Creates a new instrumented d-ary heap. The companion to `new` for
+    /// callers that want comparison-count statistics. Counters start at zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - Arity (number of children per node). Must be ≥ 1.
+    /// * `comparator` - Defines priority order (min-heap or max-heap)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{InstrumentedPriorityQueue, MinBy, PriorityQueue, StatsCollector};
+    ///
+    /// let mut heap: InstrumentedPriorityQueue<i32, MinBy<_>>
+    ///     = PriorityQueue::with_stats(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(5);
+    /// heap.insert(3);
+    /// assert_eq!(heap.stats().total(), heap.stats().insert());
+    /// ```
+    pub fn with_stats(d: usize, comparator: C) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        Ok(Self {
+            container: Vec::new(),
+            positions: HashIndex::new(),
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: ComparisonStats::default(),
+        })
+    }
+}
+
+/// Constructor that produces the explain-mode (`ExplainTrace`) heap.
+impl<T, C> PriorityQueue<T, C, ExplainTrace>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Creates a new d-ary heap that records every operation step by step.
+    /// The trace starts empty; read it back through `stats()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - Arity (number of children per node). Must be ≥ 1.
+    /// * `comparator` - Defines priority order (min-heap or max-heap)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{ExplainedPriorityQueue, MinBy, PriorityQueue};
+    ///
+    /// let mut heap: ExplainedPriorityQueue<i32, MinBy<_>>
+    ///     = PriorityQueue::with_explain(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(5);
+    /// heap.insert(3);
+    /// assert_eq!(heap.stats().steps().len(), 2);
+    /// assert_eq!(heap.stats().steps()[1].swaps, vec![(1, 0)]);
+    /// ```
+    pub fn with_explain(d: usize, comparator: C) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
         }
+        Ok(Self {
+            container: Vec::new(),
+            positions: HashIndex::new(),
+            comparator,
+            depth: d,
+            child_scan: ChildScan::Leftmost,
+            stats: ExplainTrace::default(),
+        })
     }
 }
 
-/// Constructors that produce the default (zero-overhead) heap. These live on
-/// the concrete `PriorityQueue<T, C, NoOpStats>` (= `PriorityQueue<T, C>` via
-/// the struct's defaulted type parameter) so that calls like
-/// `PriorityQueue::new(d, c)` resolve without requiring a type annotation.
-impl<T, C> PriorityQueue<T, C, NoOpStats>
+/// Constructor that produces the histogram (`HistogramStats`) heap.
+impl<T, C> PriorityQueue<T, C, HistogramStats>
 where
     T: Eq + Hash + Clone,
     C: PriorityCompare<T>,
 {
-    /// Creates a new empty d-ary heap with specified arity and comparator.
+    /// Creates a new d-ary heap that records sift-depth and children-scanned
+    /// histograms per operation, alongside comparison counts. Read them back
+    /// through `stats()`.
     ///
     /// # Arguments
     ///
@@ -1012,43 +3284,44 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy, MaxBy};
-    ///
-    /// // Binary heap (d=2) with min-heap ordering
-    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
-    ///
-    /// // Quaternary heap (d=4) with max-heap ordering
-    /// let mut heap = PriorityQueue::new(4, MaxBy(|x: &i32| *x)).unwrap();
+    /// use d_ary_heap::{HistogramPriorityQueue, MinBy, OperationType, PriorityQueue};
     ///
-    /// // Invalid arity returns error
-    /// assert!(PriorityQueue::new(0, MinBy(|x: &i32| *x)).is_err());
+    /// let mut heap: HistogramPriorityQueue<i32, MinBy<_>>
+    ///     = PriorityQueue::with_histograms(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(5);
+    /// heap.insert(3); // moves one level up
+    /// let insert = heap.stats().histogram(OperationType::Insert);
+    /// assert_eq!(insert.sift_up_depth, vec![1, 1]);
     /// ```
-    ///
-    /// **Cross-language equivalents**:
-    /// - C++: `PriorityQueue<T>(d)`
-    /// - Zig: `DHeap.init(d, comparator, allocator)` (returns `!T`)
-    /// - TypeScript: `new PriorityQueue({d, comparator, keyExtractor})` (throws)
-    /// - Go: `New(d, comparator)` (returns `*T, error`)
-    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+    pub fn with_histograms(d: usize, comparator: C) -> Result<Self, Error> {
         if d == 0 {
             return Err(Error::InvalidArity);
         }
         Ok(Self {
             container: Vec::new(),
-            positions: HashMap::new(),
+            positions: HashIndex::new(),
             comparator,
             depth: d,
-            stats: NoOpStats,
+            child_scan: ChildScan::Leftmost,
+            stats: HistogramStats::default(),
         })
     }
+}
 
-    /// Creates a new d-ary heap with specified arity, inserting the first item.
+/// Constructor that produces the profiling (`ProfileTrace`) heap.
+impl<T, C> PriorityQueue<T, C, ProfileTrace>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Creates a new d-ary heap that records the heap size, comparisons,
+    /// swaps and wall-clock time of every operation. Read the records back,
+    /// or export them as CSV, through `stats()`.
     ///
     /// # Arguments
     ///
     /// * `d` - Arity (number of children per node). Must be ≥ 1.
-    /// * `comparator` - Defines priority order
-    /// * `t` - First item to insert
+    /// * `comparator` - Defines priority order (min-heap or max-heap)
     ///
     /// # Errors
     ///
@@ -1057,43 +3330,48 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{PriorityQueue, MinBy};
+    /// use d_ary_heap::{MinBy, OperationType, PriorityQueue, ProfiledPriorityQueue};
     ///
-    /// let mut heap = PriorityQueue::with_first(3, MinBy(|x: &i32| *x), 42).unwrap();
-    /// assert_eq!(heap.front(), &42);
+    /// let mut heap: ProfiledPriorityQueue<i32, MinBy<_>>
+    ///     = PriorityQueue::with_profile(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert(5);
+    /// heap.insert(3);
+    /// let second = heap.stats().records()[1];
+    /// assert_eq!(second.operation, OperationType::Insert);
+    /// assert_eq!((second.len, second.comparisons, second.swaps), (1, 1, 1));
     /// ```
-    pub fn with_first(d: usize, comparator: C, t: T) -> Result<Self, Error> {
+    pub fn with_profile(d: usize, comparator: C) -> Result<Self, Error> {
         if d == 0 {
             return Err(Error::InvalidArity);
         }
-        let container = vec![t.clone()];
-        let mut positions = HashMap::with_capacity(1);
-        positions.insert(t, 0);
         Ok(Self {
-            container,
-            positions,
+            container: Vec::new(),
+            positions: HashIndex::new(),
             comparator,
             depth: d,
-            stats: NoOpStats,
+            child_scan: ChildScan::Leftmost,
+            stats: ProfileTrace::default(),
         })
     }
 }
 
-/// Constructor that produces the instrumented (`ComparisonStats`) heap.
-/// Distinct name from `new` so `PriorityQueue::new(...)` stays unambiguous on
-/// the default heap.
-impl<T, C> PriorityQueue<T, C, ComparisonStats>
+/// Constructor that produces the `metrics`-exporting (`MetricsStats`) heap.
+#[cfg(feature = "metrics")]
+impl<T, C> PriorityQueue<T, C, MetricsStats>
 where
     T: Eq + Hash + Clone,
     C: PriorityCompare<T>,
 {
-    /// Creates a new instrumented d-ary heap. The companion to `new` for
-    /// callers that want comparison-count statistics. Counters start at zero.
+    /// Creates a new d-ary heap that publishes its length and activity
+    /// through the `metrics` facade, labelled `queue = <queue>`. Install the
+    /// `metrics` recorder (e.g. a Prometheus exporter) before calling this:
+    /// the metric handles are registered here.
     ///
     /// # Arguments
     ///
     /// * `d` - Arity (number of children per node). Must be ≥ 1.
     /// * `comparator` - Defines priority order (min-heap or max-heap)
+    /// * `queue` - Value of the `queue` label on every metric
     ///
     /// # Errors
     ///
@@ -1102,24 +3380,28 @@ where
     /// # Examples
     ///
     /// ```rust
-    /// use d_ary_heap::{InstrumentedPriorityQueue, MinBy, PriorityQueue, StatsCollector};
+    /// use d_ary_heap::{MetricsPriorityQueue, MinBy, PriorityQueue, StatsCollector};
     ///
-    /// let mut heap: InstrumentedPriorityQueue<i32, MinBy<_>>
-    ///     = PriorityQueue::with_stats(2, MinBy(|x: &i32| *x)).unwrap();
+    /// let mut heap: MetricsPriorityQueue<i32, MinBy<_>>
+    ///     = PriorityQueue::with_metrics(2, MinBy(|x: &i32| *x), "scheduler").unwrap();
     /// heap.insert(5);
-    /// heap.insert(3);
-    /// assert_eq!(heap.stats().total(), heap.stats().insert());
+    /// heap.insert(3); // moves one level up
+    /// assert_eq!(heap.stats().total(), 1);
+    /// assert_eq!(heap.stats().max_sift_depth(), 1);
     /// ```
-    pub fn with_stats(d: usize, comparator: C) -> Result<Self, Error> {
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn with_metrics(d: usize, comparator: C, queue: &str) -> Result<Self, Error> {
         if d == 0 {
             return Err(Error::InvalidArity);
         }
         Ok(Self {
             container: Vec::new(),
-            positions: HashMap::new(),
+            positions: HashIndex::new(),
             comparator,
             depth: d,
-            stats: ComparisonStats::default(),
+            child_scan: ChildScan::Leftmost,
+            stats: MetricsStats::new(queue),
         })
     }
 }
@@ -1145,9 +3427,9 @@ where
 /// // Uses Display trait
 /// println!("{}", heap); // Output: {3, 5}
 /// ```
-impl<T, C, S> Display for PriorityQueue<T, C, S>
+impl<T, C, S, P> Display for PriorityQueue<T, C, S, P>
 where
-    T: Eq + Hash + Clone + Display,
+    T: Eq + Clone + Display,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{{")?;
@@ -1161,6 +3443,331 @@ where
     }
 }
 
+/// Copies the queue: its array, position index, comparator and stats
+/// collector. Both queues stay fully usable and independent, so a
+/// branch-and-bound or game-tree search can fork its frontier per branch
+/// without rebuilding it item by item. An instrumented clone keeps counting
+/// from the original's totals, independently of it. To share one array
+/// among many read-only copies instead, see [`SnapshotPriorityQueue`].
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{PriorityQueue, MinBy};
+///
+/// let mut frontier = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+/// frontier.insert_many(vec![5, 2, 8]);
+///
+/// let mut branch = frontier.clone();
+/// branch.insert(1);
+/// assert_eq!(branch.pop(), Some(1));
+/// assert_eq!(branch.pop(), Some(2));
+/// assert_eq!(frontier.len(), 3); // unchanged
+/// assert_eq!(frontier.front(), &2);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T, C, S, P> Clone for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: Clone,
+    S: Clone,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            container: self.container.clone(),
+            positions: self.positions.clone(),
+            comparator: self.comparator.clone(),
+            depth: self.depth,
+            child_scan: self.child_scan,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+/// Queues are equal when they have the same arity and the same items by
+/// identity, each at the same priority — whatever their array layout, so
+/// heaps built by different sequences of operations compare equal.
+///
+/// Priorities are compared with the left-hand queue's comparator: an item
+/// equals its counterpart when neither has higher priority than the other.
+/// The comparators themselves cannot be compared, so queues are assumed to
+/// order items the same way; the comparator and stats types may differ,
+/// which lets two closures, or a plain and an instrumented heap, be compared.
+/// Together with the `Debug` of [`MinBy`] / [`MaxBy`] this makes
+/// `assert_eq!(expected, actual)` work without draining either queue.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{PriorityQueue, MinBy};
+///
+/// let mut a = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+/// a.insert_many(vec![4, 1, 3, 2]);
+/// let mut b = PriorityQueue::new(3, MinBy(|x: &i32| *x)).unwrap();
+/// for x in [2, 4, 3, 1, 9] {
+///     b.insert(x);
+/// }
+/// assert_ne!(a, b);
+/// b.remove_many(&[9]);
+/// assert_eq!(a, b);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T, C, C2, S, S2, P, P2> PartialEq<PriorityQueue<T, C2, S2, P2>> for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    P2: PositionIndex<T>,
+{
+    fn eq(&self, other: &PriorityQueue<T, C2, S2, P2>) -> bool {
+        self.depth == other.depth
+            && self.container.len() == other.container.len()
+            && self.container.iter().all(|a| {
+                other.positions.get(a, &other.container).is_some_and(|j| {
+                    // INDEX: `positions` only holds in-bounds positions.
+                    let b = &other.container[j.index()];
+                    !self.comparator.higher_priority(a, b) && !self.comparator.higher_priority(b, a)
+                })
+            })
+    }
+}
+
+/// Equality is total whenever the comparator is a strict weak order, which
+/// the heap requires anyway: an item never has higher priority than
+/// itself, so every queue equals itself. This lets queues sit in types that
+/// derive `Eq`, such as search states memoised by content.
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T, C, S, P> Eq for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    P: PositionIndex<T>,
+{
+}
+
+/// Heapifies `items` into a [`DEFAULT_ARITY`] max-heap, the counterpart of
+/// `BinaryHeap::from(vec)`; see [`PriorityQueue::from_vec`] to choose the
+/// arity and comparator.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MaxOrd, PriorityQueue};
+///
+/// let mut heap: PriorityQueue<_, MaxOrd> = vec![2, 9, 4].into();
+/// assert_eq!(heap.pop(), Some(9));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T> From<Vec<T>> for PriorityQueue<T, MaxOrd>
+where
+    T: Ord + Hash + Clone,
+{
+    fn from(items: Vec<T>) -> Self {
+        let mut heap = Self::with_checked_arity(DEFAULT_ARITY, MaxOrd);
+        heap.insert_many(items);
+        heap
+    }
+}
+
+/// Moves a [`BinaryHeap`] into a [`DEFAULT_ARITY`] max-heap with the same
+/// pop order, so code built on `BinaryHeap` can switch with `.into()` and
+/// gain `increase_priority` and friends.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MaxOrd, PriorityQueue};
+/// use std::collections::BinaryHeap;
+///
+/// let std_heap = BinaryHeap::from(vec![4, 7, 1]);
+/// let mut heap: PriorityQueue<_, MaxOrd> = std_heap.into();
+/// assert_eq!(heap.pop_many(3), vec![7, 4, 1]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T> From<BinaryHeap<T>> for PriorityQueue<T, MaxOrd>
+where
+    T: Ord + Hash + Clone,
+{
+    fn from(heap: BinaryHeap<T>) -> Self {
+        heap.into_vec().into()
+    }
+}
+
+/// Moves the items out in heap (storage) order, like
+/// [`BinaryHeap::into_vec`]; use [`PriorityQueue::pop_many`] for priority
+/// order.
+///
+/// **Time Complexity**: O(1)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MinBy, PriorityQueue};
+///
+/// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+/// heap.insert_many(vec![3, 1, 2]);
+/// let mut items = Vec::from(heap);
+/// assert_eq!(items[0], 1);
+/// items.sort_unstable();
+/// assert_eq!(items, vec![1, 2, 3]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only; other languages expose
+/// `to_array()` copies).
+impl<T, C, S, P> From<PriorityQueue<T, C, S, P>> for Vec<T>
+where
+    T: Eq + Clone,
+{
+    fn from(heap: PriorityQueue<T, C, S, P>) -> Self {
+        heap.container
+    }
+}
+
+/// Collects into a [`DEFAULT_ARITY`] max-heap, like collecting into a
+/// `BinaryHeap`; the items are heapified once, as by `From<Vec<T>>`.
+/// Collect into a `Vec` and call [`PriorityQueue::from_vec`] to choose the
+/// arity and comparator.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MaxOrd, PriorityQueue};
+///
+/// let mut heap: PriorityQueue<_, MaxOrd> = "a-heap".chars().collect();
+/// assert_eq!(heap.pop(), Some('p'));
+/// assert_eq!(heap.d(), 4);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T> FromIterator<T> for PriorityQueue<T, MaxOrd>
+where
+    T: Ord + Hash + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        Self::from(items.into_iter().collect::<Vec<T>>())
+    }
+}
+
+/// Inserts every item, restoring heap order once at the end as
+/// [`PriorityQueue::bulk_insert_deferred`] does: by sifting just the new
+/// items, or by one rebuild when the batch is large next to the heap.
+///
+/// **Time Complexity**: `O(min(k · log_d n, n + k))` for `k` items
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MinBy, PriorityQueue};
+///
+/// let mut heap = PriorityQueue::new(3, MinBy(|x: &u32| *x)).unwrap();
+/// heap.extend([40, 10, 30]);
+/// heap.extend((1..5).map(|x| x * 7));
+/// assert_eq!(heap.pop_many(3), vec![7, 10, 14]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only; other languages call
+/// `insert_many(items)`).
+impl<T, C, S, P> Extend<T> for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.bulk_insert_deferred().extend(items);
+    }
+}
+
+/// Iterates over the items in array order, as
+/// [`PriorityQueue::iter`] does.
+impl<'a, T, C, S, P> IntoIterator for &'a PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.container.iter()
+    }
+}
+
+/// Moves the items out in array order (not sorted), like
+/// [`BinaryHeap`]'s `into_iter`; see
+/// [`PriorityQueue::into_iter_sorted`] for priority order.
+///
+/// **Time Complexity**: O(1)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MinBy, PriorityQueue};
+///
+/// let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+/// heap.insert_many(vec![4, 2, 6]);
+///
+/// let mut items: Vec<u32> = heap.into_iter().collect();
+/// items.sort_unstable();
+/// assert_eq!(items, vec![2, 4, 6]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T, C, S, P> IntoIterator for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Vec::from(self).into_iter()
+    }
+}
+
+/// Moves the items into a [`BinaryHeap`], which orders them by `T`'s own
+/// [`Ord`]: the queue's comparator and arity are dropped, so a queue built
+/// with [`MaxOrd`] pops in the same order afterwards and any other
+/// comparator may not.
+///
+/// **Time Complexity**: O(n)
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MaxOrd, PriorityQueue};
+/// use std::collections::BinaryHeap;
+///
+/// let heap: PriorityQueue<_, MaxOrd> = vec![5, 8, 2].into();
+/// let std_heap = BinaryHeap::from(heap);
+/// assert_eq!(std_heap.into_sorted_vec(), vec![2, 5, 8]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+impl<T, C, S, P> From<PriorityQueue<T, C, S, P>> for BinaryHeap<T>
+where
+    T: Ord + Clone,
+{
+    fn from(heap: PriorityQueue<T, C, S, P>) -> Self {
+        Vec::from(heap).into()
+    }
+}
+
 // Note on `pq.to_string()`: the `impl Display` above gives `PriorityQueue<T, C>`
 // the `to_string()` method automatically via the blanket `ToString` impl in std,
 // providing API parity with the C++ / Zig / TypeScript / Go `to_string()` /
@@ -1193,7 +3800,16 @@ where
 /// struct Task { priority: i32 }
 /// let mut heap = PriorityQueue::new(3, MinBy(|t: &Task| t.priority)).unwrap();
 /// ```
+#[derive(Clone, Copy)]
 pub struct MinBy<F>(pub F);
+
+// Manual impl: closures are not `Debug`; `MinBy(..)` keeps queues built on
+// one printable in `assert_eq!` failures.
+impl<F> Debug for MinBy<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("MinBy").finish_non_exhaustive()
+    }
+}
 impl<T, F, K> PriorityCompare<T> for MinBy<F>
 where
     F: Fn(&T) -> K,
@@ -1230,7 +3846,16 @@ where
 /// struct Task { priority: i32 }
 /// let mut heap = PriorityQueue::new(3, MaxBy(|t: &Task| t.priority)).unwrap();
 /// ```
+#[derive(Clone, Copy)]
 pub struct MaxBy<F>(pub F);
+
+// Manual impl: closures are not `Debug`; `MaxBy(..)` keeps queues built on
+// one printable in `assert_eq!` failures.
+impl<F> Debug for MaxBy<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("MaxBy").finish_non_exhaustive()
+    }
+}
 impl<T, F, K> PriorityCompare<T> for MaxBy<F>
 where
     F: Fn(&T) -> K,
@@ -1241,3 +3866,53 @@ where
         (self.0)(a) > (self.0)(b)
     }
 }
+
+/// Max-heap on `T`'s own [`Ord`], like [`BinaryHeap`]: the comparator of
+/// the queues `From<Vec<T>>` and `From<BinaryHeap<T>>` build. Wrap items in
+/// [`std::cmp::Reverse`] for a min-heap, as with `BinaryHeap`.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MaxOrd, PriorityQueue};
+///
+/// let mut heap = PriorityQueue::new(4, MaxOrd).unwrap();
+/// heap.insert_many(vec![3, 8, 1]);
+/// assert_eq!(heap.front(), &8);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaxOrd;
+
+impl<T: Ord> PriorityCompare<T> for MaxOrd {
+    #[inline]
+    fn higher_priority(&self, a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+/// Min-heap on `T`'s own [`Ord`]: the comparator of [`MinHeap`], for items
+/// that are their own key, with no closure and no [`std::cmp::Reverse`]
+/// wrapping.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::{MinOrd, PriorityQueue};
+///
+/// let mut heap = PriorityQueue::new(4, MinOrd).unwrap();
+/// heap.insert_many(vec![3, 8, 1]);
+/// assert_eq!(heap.front(), &1);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MinOrd;
+
+impl<T: Ord> PriorityCompare<T> for MinOrd {
+    #[inline]
+    fn higher_priority(&self, a: &T, b: &T) -> bool {
+        a < b
+    }
+}
@@ -0,0 +1,196 @@
+//! Production metrics through the `metrics` facade (`metrics` feature).
+//!
+//! [`MetricsStats`] is a [`StatsCollector`] that publishes a queue's size and
+//! activity as [`metrics`] counters and gauges, so a scheduler built on the
+//! heap shows up in Prometheus (through `metrics-exporter-prometheus`) or any
+//! other `metrics` backend with no glue code:
+//!
+//! | Metric | Kind | Value |
+//! |--------|------|-------|
+//! | `d_ary_heap_len` | gauge | Items queued after the latest operation |
+//! | `d_ary_heap_inserts_total` | counter | Items inserted |
+//! | `d_ary_heap_pops_total` | counter | Items removed by `pop` and the batch removals |
+//! | `d_ary_heap_updates_total` | counter | Priority-update calls |
+//! | `d_ary_heap_comparisons_total` | counter | Priority comparisons |
+//! | `d_ary_heap_max_sift_depth` | gauge | Most levels one sift has moved an item |
+//!
+//! Every metric carries a `queue` label, so one process can export several
+//! queues. The handles are registered once, when the heap is created, with
+//! the recorder installed at that time: install the exporter first.
+//! Comparisons are tallied per operation and published when it ends, so the
+//! per-comparison cost stays that of [`ComparisonStats`](crate::ComparisonStats).
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! // With a recorder installed, e.g.
+//! // metrics_exporter_prometheus::PrometheusBuilder::new().install()?;
+//! let mut jobs = PriorityQueue::with_metrics(4, MinBy(|x: &u32| *x), "jobs").unwrap();
+//! jobs.insert_many(vec![5, 1, 3]);
+//! jobs.pop();
+//! assert_eq!(jobs.stats().queue(), "jobs");
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages read `ComparisonStats` and export it themselves.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use metrics::{Counter, Gauge};
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// Metric names, registered with the `queue` label.
+const LEN: &str = "d_ary_heap_len";
+const INSERTS: &str = "d_ary_heap_inserts_total";
+const POPS: &str = "d_ary_heap_pops_total";
+const UPDATES: &str = "d_ary_heap_updates_total";
+const COMPARISONS: &str = "d_ary_heap_comparisons_total";
+const MAX_SIFT_DEPTH: &str = "d_ary_heap_max_sift_depth";
+
+/// Metrics policy: publishes queue length and activity through the
+/// [`metrics`] facade; see the [module docs](self).
+#[derive(Clone)]
+pub struct MetricsStats {
+    /// Value of the `queue` label.
+    queue: String,
+    len: Gauge,
+    inserts: Counter,
+    pops: Counter,
+    updates: Counter,
+    comparisons: Counter,
+    max_sift_depth: Gauge,
+    /// Operation in flight, `OperationType::None` between operations.
+    active: Cell<OperationType>,
+    /// Queue length when the operation in flight started.
+    start_len: Cell<usize>,
+    /// Comparisons made by the operation in flight, published when it ends.
+    pending: Cell<u64>,
+    /// Comparisons since creation or the last `reset`.
+    total: Cell<u64>,
+    /// Deepest sift since creation or the last `reset`.
+    deepest: Cell<usize>,
+}
+
+impl MetricsStats {
+    /// Registers the metrics of a queue labelled `queue` with the installed
+    /// recorder (a no-op recorder if none is installed).
+    #[must_use]
+    pub fn new(queue: impl Into<String>) -> Self {
+        let queue = queue.into();
+        metrics::describe_gauge!(LEN, "Items queued after the latest operation");
+        metrics::describe_counter!(INSERTS, "Items inserted");
+        metrics::describe_counter!(POPS, "Items removed by pop and batch removals");
+        metrics::describe_counter!(UPDATES, "Priority-update calls");
+        metrics::describe_counter!(COMPARISONS, "Priority comparisons");
+        metrics::describe_gauge!(MAX_SIFT_DEPTH, "Most levels one sift has moved an item");
+        Self {
+            len: metrics::gauge!(LEN, "queue" => queue.clone()),
+            inserts: metrics::counter!(INSERTS, "queue" => queue.clone()),
+            pops: metrics::counter!(POPS, "queue" => queue.clone()),
+            updates: metrics::counter!(UPDATES, "queue" => queue.clone()),
+            comparisons: metrics::counter!(COMPARISONS, "queue" => queue.clone()),
+            max_sift_depth: metrics::gauge!(MAX_SIFT_DEPTH, "queue" => queue.clone()),
+            queue,
+            active: Cell::new(OperationType::None),
+            start_len: Cell::new(0),
+            pending: Cell::new(0),
+            total: Cell::new(0),
+            deepest: Cell::new(0),
+        }
+    }
+
+    /// Value of the `queue` label.
+    #[must_use]
+    pub fn queue(&self) -> &str {
+        &self.queue
+    }
+
+    /// Most levels one sift has moved an item since creation or the last
+    /// `reset`.
+    #[must_use]
+    pub fn max_sift_depth(&self) -> usize {
+        self.deepest.get()
+    }
+
+    fn record_sift(&self, levels: usize) {
+        if levels > self.deepest.get() {
+            self.deepest.set(levels);
+            // CAST: usize → f64, exact below 2^53 levels.
+            #[allow(clippy::cast_precision_loss)]
+            self.max_sift_depth.set(levels as f64);
+        }
+    }
+}
+
+// Manual impl: the `metrics` handles are not `Debug`.
+impl Debug for MetricsStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("MetricsStats")
+            .field("queue", &self.queue)
+            .field("comparisons", &self.total.get())
+            .field("max_sift_depth", &self.deepest.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatsCollector for MetricsStats {
+    fn start_operation(&self, op: OperationType) {
+        self.active.set(op);
+    }
+
+    fn end_operation(&self) {
+        self.comparisons.increment(self.pending.take());
+        self.active.set(OperationType::None);
+    }
+
+    fn count_comparison(&self) {
+        if self.active.get() != OperationType::None {
+            self.pending.set(self.pending.get() + 1);
+            self.total.set(self.total.get() + 1);
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.total.get()
+    }
+
+    /// Zeroes `total` and `max_sift_depth`. The exported counters are
+    /// monotonic and keep their values.
+    fn reset(&self) {
+        self.total.set(0);
+        self.deepest.set(0);
+        self.active.set(OperationType::None);
+        self.pending.set(0);
+    }
+
+    fn record_len(&self, len: usize) {
+        self.start_len.set(len);
+    }
+
+    fn record_final_len(&self, len: usize) {
+        let start = self.start_len.get();
+        // CAST: usize → u64 is lossless; usize → f64 is exact below 2^53 items.
+        match self.active.get() {
+            OperationType::Insert => self.inserts.increment(len.saturating_sub(start) as u64),
+            OperationType::Pop => self.pops.increment(start.saturating_sub(len) as u64),
+            OperationType::DecreasePriority
+            | OperationType::IncreasePriority
+            | OperationType::UpdatePriority => self.updates.increment(1),
+            // EXPLICIT: `clear` reports its length outside any operation.
+            OperationType::None => {}
+        }
+        #[allow(clippy::cast_precision_loss)]
+        self.len.set(len as f64);
+    }
+
+    fn record_sift_up(&self, levels: usize) {
+        self.record_sift(levels);
+    }
+
+    fn record_sift_down(&self, levels: usize) {
+        self.record_sift(levels);
+    }
+}
@@ -0,0 +1,325 @@
+//! Multi-level feedback queue (MLFQ) scheduler built from d-ary heaps.
+//!
+//! [`MlfqScheduler`] keeps one [`PriorityQueue`] per level and applies the
+//! classic MLFQ rules, with the heap's comparator breaking ties inside a level
+//! where a textbook MLFQ would use round robin:
+//!
+//! 1. Jobs on a higher level (level 0 is the highest) are dispatched first.
+//! 2. Within a level, the comparator's highest-priority job is dispatched.
+//! 3. New jobs enter at level 0.
+//! 4. Each level has an allotment of ticks; a job that has used up its
+//!    allotment is demoted one level. A job that yields or blocks early keeps
+//!    its level and the unused part of the allotment.
+//! 5. Every `boost_interval` ticks, all jobs return to level 0, so long-running
+//!    jobs cannot starve.
+//!
+//! The scheduler does not run anything: [`MlfqScheduler::dispatch`] hands out
+//! the next job with the ticks it may use, and the caller reports back through
+//! [`MlfqScheduler::complete`] how many it used and whether the job finished,
+//! is still runnable, or blocked (to be resumed with [`MlfqScheduler::wake`]).
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, MlfqConfig, MlfqScheduler, RunOutcome};
+//!
+//! let mut sched = MlfqScheduler::new(4, MinBy(|pid: &u32| *pid), MlfqConfig::default()).unwrap();
+//! sched.submit(7);
+//! sched.submit(3);
+//!
+//! let run = sched.dispatch().unwrap();
+//! assert_eq!((run.job, run.level, run.quantum), (3, 0, 8));
+//! sched.complete(run.job, run.quantum, RunOutcome::Runnable).unwrap(); // CPU-bound
+//! assert_eq!(sched.level_of(&3), Some(1));
+//!
+//! let run = sched.dispatch().unwrap(); // 7 is still on level 0
+//! assert_eq!(run.job, 7);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Like [`FairQueue`](crate::FairQueue), this is a reference
+//!   component built on the heap rather than part of the cross-language API.
+
+use crate::{Error, PriorityCompare, PriorityQueue};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
+
+/// Tuning for [`MlfqScheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MlfqConfig {
+    /// Number of levels.
+    pub levels: NonZeroUsize,
+    /// Allotment, in ticks, of level 0.
+    pub base_quantum: NonZeroU32,
+    /// Factor by which the allotment grows per level (1 keeps it constant).
+    pub quantum_growth: NonZeroU32,
+    /// Ticks between priority boosts, or `None` to never boost.
+    pub boost_interval: Option<NonZeroU64>,
+}
+
+impl Default for MlfqConfig {
+    /// Three levels with allotments of 8, 16 and 32 ticks, boosted every
+    /// 1000 ticks.
+    fn default() -> Self {
+        Self {
+            levels: NonZeroUsize::new(3).expect("3 is non-zero"),
+            base_quantum: NonZeroU32::new(8).expect("8 is non-zero"),
+            quantum_growth: NonZeroU32::new(2).expect("2 is non-zero"),
+            boost_interval: NonZeroU64::new(1000),
+        }
+    }
+}
+
+/// How a dispatched job's run ended, as reported to
+/// [`MlfqScheduler::complete`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The job is done; the scheduler forgets it.
+    Finished,
+    /// The job can run again (its quantum expired or it yielded).
+    Runnable,
+    /// The job is waiting; it is requeued by [`MlfqScheduler::wake`].
+    Blocked,
+}
+
+/// A job handed out by [`MlfqScheduler::dispatch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dispatch<T> {
+    /// The job to run.
+    pub job: T,
+    /// The level it was dispatched from.
+    pub level: usize,
+    /// Ticks it may use before being demoted (what is left of its allotment).
+    pub quantum: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobState {
+    Queued,
+    Running,
+    Blocked,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Job {
+    state: JobState,
+    level: usize,
+    /// Ticks used of the current level's allotment.
+    used: u32,
+}
+
+/// Multi-level feedback queue over d-ary heaps; see the [module docs](self).
+#[derive(Debug)]
+pub struct MlfqScheduler<T, C>
+where
+    T: Eq + Hash + Clone,
+{
+    levels: Vec<PriorityQueue<T, C>>,
+    jobs: HashMap<T, Job>,
+    config: MlfqConfig,
+    ticks_since_boost: u64,
+}
+
+impl<T, C> MlfqScheduler<T, C>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T> + Clone,
+{
+    /// Creates an empty scheduler whose level heaps have arity `d` and a
+    /// clone of `comparator` each.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, comparator: C, config: MlfqConfig) -> Result<Self, Error> {
+        if d == 0 {
+            return Err(Error::InvalidArity);
+        }
+        let mut levels: Vec<_> = (1..config.levels.get())
+            .map(|_| empty_level(d, comparator.clone()))
+            .collect();
+        levels.push(empty_level(d, comparator));
+        Ok(Self {
+            levels,
+            jobs: HashMap::new(),
+            config,
+            ticks_since_boost: 0,
+        })
+    }
+
+    /// The allotment, in ticks, of `level` (saturating at `u32::MAX`).
+    #[must_use]
+    pub fn allotment(&self, level: usize) -> u32 {
+        let mut quantum = self.config.base_quantum.get();
+        for _ in 0..level {
+            quantum = quantum.saturating_mul(self.config.quantum_growth.get());
+        }
+        quantum
+    }
+
+    /// Queues a new job at level 0. Returns `false`, and changes nothing, if
+    /// the job is already known to the scheduler (queued, running or
+    /// blocked).
+    pub fn submit(&mut self, job: T) -> bool {
+        if self.jobs.contains_key(&job) {
+            return false;
+        }
+        self.jobs.insert(
+            job.clone(),
+            Job {
+                state: JobState::Queued,
+                level: 0,
+                used: 0,
+            },
+        );
+        self.levels[0].insert(job);
+        true
+    }
+
+    /// Removes the next job to run: the highest-priority job of the highest
+    /// non-empty level. Returns `None` if nothing is queued.
+    ///
+    /// **Time Complexity**: `O(L + d · log_d m)` for `L` levels and a level
+    /// holding `m` jobs
+    pub fn dispatch(&mut self) -> Option<Dispatch<T>> {
+        let (level, heap) = self
+            .levels
+            .iter_mut()
+            .enumerate()
+            .find(|(_, heap)| !heap.is_empty())?;
+        let job = heap.pop()?;
+        let state = self.jobs.get_mut(&job)?;
+        state.state = JobState::Running;
+        let used = state.used;
+        Some(Dispatch {
+            quantum: self.allotment(level) - used,
+            job,
+            level,
+        })
+    }
+
+    /// Reports that a dispatched job ran for `used` ticks and ended with
+    /// `outcome`. Charges the ticks to the job's allotment (demoting it once
+    /// the allotment is used up), requeues it if it is still runnable, and
+    /// triggers a priority boost when `boost_interval` ticks have elapsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if `job` is not currently dispatched.
+    pub fn complete(&mut self, job: T, used: u32, outcome: RunOutcome) -> Result<(), Error> {
+        let last_level = self.levels.len() - 1;
+        let state = match self.jobs.get(&job) {
+            Some(state) if state.state == JobState::Running => *state,
+            _ => return Err(Error::ItemNotFound),
+        };
+
+        let mut level = state.level;
+        let mut charged = state.used.saturating_add(used);
+        if charged >= self.allotment(level) {
+            level = (level + 1).min(last_level);
+            charged = 0;
+        }
+
+        match outcome {
+            RunOutcome::Finished => {
+                self.jobs.remove(&job);
+            }
+            RunOutcome::Runnable | RunOutcome::Blocked => {
+                let queued = outcome == RunOutcome::Runnable;
+                self.jobs.insert(
+                    job.clone(),
+                    Job {
+                        state: if queued {
+                            JobState::Queued
+                        } else {
+                            JobState::Blocked
+                        },
+                        level,
+                        used: charged,
+                    },
+                );
+                if queued {
+                    self.levels[level].insert(job);
+                }
+            }
+        }
+
+        self.ticks_since_boost = self.ticks_since_boost.saturating_add(u64::from(used));
+        if let Some(interval) = self.config.boost_interval {
+            if self.ticks_since_boost >= interval.get() {
+                self.boost();
+            }
+        }
+        Ok(())
+    }
+
+    /// Requeues a blocked job at the level it blocked on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if `job` is not blocked.
+    pub fn wake(&mut self, job: &T) -> Result<(), Error> {
+        let state = self.jobs.get_mut(job).ok_or(Error::ItemNotFound)?;
+        if state.state != JobState::Blocked {
+            return Err(Error::ItemNotFound);
+        }
+        state.state = JobState::Queued;
+        self.levels[state.level].insert(job.clone());
+        Ok(())
+    }
+
+    /// Moves every job back to level 0 with a fresh allotment. Called
+    /// automatically every `boost_interval` ticks; callable directly for
+    /// event-driven boosts.
+    pub fn boost(&mut self) {
+        self.ticks_since_boost = 0;
+        for state in self.jobs.values_mut() {
+            state.level = 0;
+            state.used = 0;
+        }
+        let (top, lower) = self.levels.split_at_mut(1);
+        for heap in lower {
+            let empty = empty_level(heap.depth, heap.comparator.clone());
+            let (items, _, _) = std::mem::replace(heap, empty).into_raw_parts();
+            top[0].insert_many(items);
+        }
+    }
+
+    /// The level `job` is on (queued, running or blocked), or `None` if the
+    /// scheduler does not know it.
+    #[must_use]
+    pub fn level_of(&self, job: &T) -> Option<usize> {
+        self.jobs.get(job).map(|state| state.level)
+    }
+
+    /// Number of jobs queued on `level` (0 if out of range).
+    #[must_use]
+    pub fn level_len(&self, level: usize) -> usize {
+        self.levels.get(level).map_or(0, PriorityQueue::len)
+    }
+
+    /// Number of queued jobs across all levels (running and blocked jobs are
+    /// not counted).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.levels.iter().map(PriorityQueue::len).sum()
+    }
+
+    /// Whether no job is queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.levels.iter().all(PriorityQueue::is_empty)
+    }
+}
+
+/// An empty level heap. Skips `PriorityQueue::new`'s arity check, which
+/// `MlfqScheduler::new` has already made.
+fn empty_level<T, C>(d: usize, comparator: C) -> PriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    PriorityQueue::with_checked_arity(d, comparator)
+}
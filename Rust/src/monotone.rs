@@ -0,0 +1,190 @@
+//! Monotone priority queues, checked in debug builds.
+//!
+//! Dijkstra's algorithm, event simulation and other label-setting loops
+//! rely on a monotone queue: nothing popped ranks above an item popped
+//! before it, and nothing inserted ranks above the last item popped. A
+//! negative edge weight, a relaxation against the wrong label or a
+//! comparator that is not a strict weak order all break that property, and
+//! the algorithm then returns a wrong answer rather than failing.
+//!
+//! [`MonotoneQueue`] wraps a [`PriorityQueue`] and, in debug builds, checks
+//! both halves of the property with the heap's own comparator: an `insert`
+//! or priority increase that would outrank the last popped item panics at
+//! the call that makes it, and so does a `pop` that comes out of order. In
+//! release builds the checks and the bookkeeping behind them compile out,
+//! and the queue costs what the `PriorityQueue` inside it costs.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, MonotoneQueue};
+//!
+//! // Dijkstra on a 4-node graph with non-negative weights
+//! let edges = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+//! let mut dist = [u32::MAX; 4];
+//! let mut queue = MonotoneQueue::new(4, MinBy(|e: &(u32, usize)| e.0)).unwrap();
+//! dist[0] = 0;
+//! queue.insert((0, 0));
+//! while let Some((d, u)) = queue.pop() {
+//!     for &(_, v, w) in edges.iter().filter(|e| e.0 == u) {
+//!         if d + w < dist[v] {
+//!             dist[v] = d + w;
+//!             queue.insert((d + w, v));
+//!         }
+//!     }
+//! }
+//! assert_eq!(dist, [0, 3, 1, 4]);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Callers in other languages can compare each popped priority with
+//!   the previous one.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::hash::Hash;
+
+/// A [`PriorityQueue`] whose monotonicity is asserted in debug builds; see
+/// the [module docs](self).
+#[derive(Debug)]
+pub struct MonotoneQueue<T, C>
+where
+    T: Eq + Clone,
+{
+    heap: PriorityQueue<T, C, NoOpStats>,
+    /// The last item popped, which nothing inserted or popped may outrank.
+    #[cfg(debug_assertions)]
+    last_popped: Option<T>,
+}
+
+impl<T, C> MonotoneQueue<T, C>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Creates an empty monotone queue of arity `d` ordered by
+    /// `comparator`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, comparator: C) -> Result<Self, Error> {
+        Ok(Self {
+            heap: PriorityQueue::new(d, comparator)?,
+            #[cfg(debug_assertions)]
+            last_popped: None,
+        })
+    }
+
+    /// Panics if `item` outranks the last popped item.
+    #[cfg(debug_assertions)]
+    fn check(&self, item: &T, action: &str) {
+        if let Some(last) = &self.last_popped {
+            assert!(
+                !self.heap.comparator.higher_priority(item, last),
+                "MonotoneQueue: {action} an item that outranks the last one popped"
+            );
+        }
+    }
+
+    /// Inserts `item`.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if `item` outranks the last item popped.
+    pub fn insert(&mut self, item: T) {
+        #[cfg(debug_assertions)]
+        self.check(&item, "inserted");
+        self.heap.insert(item);
+    }
+
+    /// Removes and returns the highest-priority item, or `None` if empty.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the item popped outranks the one popped before
+    /// it, which a comparator that is not a strict weak order can cause.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.heap.pop()?;
+        #[cfg(debug_assertions)]
+        {
+            self.check(&item, "popped");
+            self.last_popped = Some(item.clone());
+        }
+        Some(item)
+    }
+
+    /// Raises the priority of `updated_item` (matched by identity), as
+    /// decrease-key does in Dijkstra's algorithm.
+    ///
+    /// **Time Complexity**: `O(log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if the item is not in the queue.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the new priority outranks the last item popped.
+    pub fn increase_priority(&mut self, updated_item: &T) -> Result<(), Error> {
+        #[cfg(debug_assertions)]
+        self.check(updated_item, "raised");
+        self.heap.increase_priority(updated_item)
+    }
+
+    /// Lowers the priority of `updated_item` (matched by identity). This
+    /// can never break monotonicity.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ItemNotFound` if the item is not in the queue.
+    pub fn decrease_priority(&mut self, updated_item: &T) -> Result<(), Error> {
+        self.heap.decrease_priority(updated_item)
+    }
+
+    /// The highest-priority item, or `None` if empty.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Whether `item` (by identity) is in the queue.
+    ///
+    /// **Time Complexity**: O(1)
+    #[must_use]
+    pub fn contains(&self, item: &T) -> bool {
+        self.heap.contains(item)
+    }
+
+    /// Number of items in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The underlying heap, for read-only queries.
+    #[must_use]
+    pub const fn as_heap(&self) -> &PriorityQueue<T, C, NoOpStats> {
+        &self.heap
+    }
+
+    /// Gives up the checks and returns the underlying heap.
+    #[must_use]
+    pub fn into_inner(self) -> PriorityQueue<T, C, NoOpStats> {
+        self.heap
+    }
+}
@@ -0,0 +1,621 @@
+//! Drop-in `dijkstra` / `astar` for [`petgraph`] graphs (`petgraph` feature).
+//!
+//! petgraph's own shortest-path functions keep a binary heap with lazy
+//! deletion: every edge relaxation pushes a fresh entry and stale entries are
+//! skipped when they surface, so the heap grows with the number of edges
+//! relaxed. The functions here take the same arguments and return the same
+//! results, but keep one entry per discovered node in a d-ary heap and move
+//! it with `increase_priority` (decrease-key) on relaxation, which is the
+//! textbook `O(E log_d V)` algorithm. On dense graphs, where relaxations far
+//! outnumber nodes, that means a smaller heap and fewer comparisons; run the
+//! `petgraph_dense` example to measure it. Fewer comparisons is not the same
+//! as less time: with plain integer costs, the bookkeeping of decrease-key
+//! costs about as much as the comparisons it saves.
+//!
+//! The heap finds each node's slot through a [`DenseIndex`] over the graph's
+//! node indices rather than by hashing, which is why the graph must be
+//! `NodeIndexable`; petgraph's own graph types all are.
+//!
+//! Switching over is a change of import:
+//!
+//! ```rust
+//! use d_ary_heap::petgraph::dijkstra; // was: use petgraph::algo::dijkstra;
+//! use petgraph::graph::DiGraph;
+//!
+//! let mut graph = DiGraph::<&str, u32>::new();
+//! let a = graph.add_node("a");
+//! let b = graph.add_node("b");
+//! let c = graph.add_node("c");
+//! graph.extend_with_edges([(a, b, 4), (a, c, 1), (c, b, 2)]);
+//!
+//! let distances = dijkstra(&graph, a, None, |e| *e.weight());
+//! assert_eq!(distances[&b], 3);
+//! ```
+//!
+//! The `_with_arity` variants choose the heap's arity instead of
+//! [`DEFAULT_ARITY`]. [`dijkstra_adaptive`] picks it as it goes: it watches
+//! how many sift-ups (inserts and decrease-keys) each pop brings and
+//! switches arity when another one would cost fewer comparisons, reporting
+//! what it did in an [`AdaptiveReport`].
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The `examples/dijkstra/` programs show the same algorithm in
+//!   every language, on their own graph types.
+
+pub use crate::DEFAULT_ARITY;
+
+use crate::{DenseIndex, Error, PriorityCompare, PriorityQueue};
+use ::petgraph::algo::Measure;
+use ::petgraph::visit::{EdgeRef, IntoEdges, NodeIndexable, VisitMap, Visitable};
+use std::cell::Cell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+pub use crate::algorithms::CostedPath;
+
+/// A node queued at `score`; identity is the node alone, so a rescored copy
+/// finds the queued entry.
+#[derive(Clone, Copy)]
+struct Scored<N, K> {
+    node: N,
+    score: K,
+}
+
+impl<N: PartialEq, K> PartialEq for Scored<N, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<N: Eq, K> Eq for Scored<N, K> {}
+
+impl<N: Hash, K> Hash for Scored<N, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+    }
+}
+
+/// Lowest score first. `MinBy` needs `Ord`, but petgraph measures such as
+/// `f64` are only `PartialOrd`.
+struct LowestScore;
+
+impl<N, K: PartialOrd> PriorityCompare<Scored<N, K>> for LowestScore {
+    fn higher_priority(&self, a: &Scored<N, K>, b: &Scored<N, K>) -> bool {
+        a.score < b.score
+    }
+}
+
+/// Lowest score first, counting every comparison into a shared cell.
+struct CountedLowestScore<'a>(&'a Cell<u64>);
+
+impl<N, K: PartialOrd> PriorityCompare<Scored<N, K>> for CountedLowestScore<'_> {
+    fn higher_priority(&self, a: &Scored<N, K>, b: &Scored<N, K>) -> bool {
+        self.0.set(self.0.get() + 1);
+        a.score < b.score
+    }
+}
+
+/// When [`dijkstra_adaptive`] may change the heap's arity, and to what.
+///
+/// Every `window` pops, the run looks at the comparisons the window's pops
+/// and sift-ups (inserts plus decrease-keys) made. A pop makes `d`
+/// comparisons per level it sinks and a sift-up one per level it climbs
+/// plus one to stop, and a level at arity `d'` spans `ln d' / ln d` levels
+/// at arity `d`; scaling the measured counts that way predicts what the
+/// window would have cost at each candidate arity. The run switches to the
+/// cheapest, rebuilding the heap in O(n), when the comparisons it would
+/// have saved exceed the rebuild's `n · d' / (d' - 1)`.
+///
+/// The candidates are `min`, `max` and the powers of two between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdaptiveArity {
+    /// Arity the heap starts with.
+    pub initial: usize,
+    /// Smallest arity the run may switch to.
+    pub min: usize,
+    /// Largest arity the run may switch to.
+    pub max: usize,
+    /// Pops between two looks at the observed ratio.
+    pub window: usize,
+}
+
+impl AdaptiveArity {
+    /// A policy that keeps arity `d` for the whole run: a baseline with the
+    /// same instrumentation, for [`AdaptiveReport::paid_off`].
+    #[must_use]
+    pub const fn fixed(d: usize) -> Self {
+        Self {
+            initial: d,
+            min: d,
+            max: d,
+            window: usize::MAX,
+        }
+    }
+
+    /// The arities this policy may switch to, ascending. Arity 1 never is:
+    /// its heap is a sorted list.
+    fn candidates(&self) -> Vec<usize> {
+        let min = self.min.max(2);
+        if min > self.max {
+            return Vec::new();
+        }
+        let mut candidates = vec![min];
+        let mut d = min.next_power_of_two();
+        while d < self.max {
+            if d > min {
+                candidates.push(d);
+            }
+            d = d.saturating_mul(2);
+        }
+        if self.max > min {
+            candidates.push(self.max);
+        }
+        candidates
+    }
+}
+
+impl Default for AdaptiveArity {
+    /// Starts at [`DEFAULT_ARITY`], may move between 2 and 64, and looks
+    /// every 256 pops.
+    fn default() -> Self {
+        Self {
+            initial: DEFAULT_ARITY,
+            min: 2,
+            max: 64,
+            window: 256,
+        }
+    }
+}
+
+/// One arity change made by [`dijkstra_adaptive`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AritySwitch {
+    /// Pops made before the switch.
+    pub at_pop: u64,
+    /// Arity before the switch.
+    pub from: usize,
+    /// Arity after the switch.
+    pub to: usize,
+    /// Sift-ups per pop in the window that triggered it.
+    pub ratio: f64,
+    /// Comparisons the rebuild made.
+    pub rebuild_comparisons: u64,
+}
+
+/// What a [`dijkstra_adaptive`] run did. Comparison counts are measured, not
+/// modelled: compare against a run under [`AdaptiveArity::fixed`] to see
+/// whether adapting paid off.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AdaptiveReport {
+    /// Nodes popped (settled).
+    pub pops: u64,
+    /// Nodes queued for the first time.
+    pub inserts: u64,
+    /// Queued nodes whose cost improved (decrease-key).
+    pub decrease_keys: u64,
+    /// Cost comparisons made by the heap, rebuilds included.
+    pub comparisons: u64,
+    /// Arity changes, in order.
+    pub switches: Vec<AritySwitch>,
+    /// Arity when the run ended.
+    pub final_arity: usize,
+}
+
+impl AdaptiveReport {
+    /// Comparisons spent rebuilding the heap on arity changes.
+    #[must_use]
+    pub fn rebuild_comparisons(&self) -> u64 {
+        self.switches.iter().map(|s| s.rebuild_comparisons).sum()
+    }
+
+    /// Whether this run made fewer comparisons than `baseline`, typically
+    /// the same search under [`AdaptiveArity::fixed`].
+    #[must_use]
+    pub const fn paid_off(&self, baseline: &Self) -> bool {
+        self.comparisons < baseline.comparisons
+    }
+}
+
+/// Operations and comparisons since [`dijkstra_adaptive`] last looked.
+#[derive(Default)]
+struct Window {
+    pops: usize,
+    sift_ups: u64,
+    pop_comparisons: u64,
+    sift_up_comparisons: u64,
+}
+
+impl Window {
+    /// The candidate this window would have been cheapest at, if it beats
+    /// the current arity `d` by more than rebuilding a heap of `n` items.
+    fn cheaper_arity(&self, d: usize, n: usize, candidates: &[usize]) -> Option<usize> {
+        // CAST: u64/usize → f64 on counts far below 2^53.
+        #[allow(clippy::cast_precision_loss)]
+        let (from, n, sift_ups, pop_cmps, sift_up_cmps) = (
+            d as f64,
+            n as f64,
+            self.sift_ups as f64,
+            self.pop_comparisons as f64,
+            self.sift_up_comparisons as f64,
+        );
+        // Pop comparisons scale with `to / ln to`; sift-up comparisons
+        // beyond the one that stops each climb with `1 / ln to`.
+        let predict = |to: usize| {
+            // CAST: usize → f64, arities are far below 2^53.
+            #[allow(clippy::cast_precision_loss)]
+            let to = to as f64;
+            let levels = from.ln() / to.ln();
+            pop_cmps * to / from * levels + sift_ups + (sift_up_cmps - sift_ups) * levels
+        };
+        let best = candidates
+            .iter()
+            .copied()
+            .min_by(|&a, &b| predict(a).total_cmp(&predict(b)))?;
+        // CAST: usize → f64, arities are far below 2^53.
+        #[allow(clippy::cast_precision_loss)]
+        let rebuild = n * best as f64 / (best as f64 - 1.0);
+        let saving = pop_cmps + sift_up_cmps - predict(best);
+        (best != d && saving > rebuild).then_some(best)
+    }
+
+    /// Sift-ups per pop.
+    fn ratio(&self) -> f64 {
+        // CAST: u64/usize → f64 on counts far below 2^53.
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = self.sift_ups as f64 / self.pops as f64;
+        ratio
+    }
+}
+
+/// Heap position index addressed by `graph`'s node indices, which run over
+/// `0..node_bound()`.
+fn node_index<G: NodeIndexable + Copy, K>(
+    graph: G,
+) -> DenseIndex<impl Fn(&Scored<G::NodeId, K>) -> usize + Clone> {
+    DenseIndex::with_capacity(
+        move |scored: &Scored<G::NodeId, K>| graph.to_index(scored.node),
+        graph.node_bound(),
+    )
+}
+
+/// Shortest-path costs from `start`, with the signature and results of
+/// `petgraph::algo::dijkstra`: the map holds every node reached, and the
+/// search stops early once `goal` is settled. The map is a `std` `HashMap`
+/// where petgraph returns a `hashbrown` one; both index and iterate alike.
+///
+/// Edge costs must be non-negative.
+///
+/// **Time Complexity**: `O(E log_d V)` with `d` = [`DEFAULT_ARITY`]
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::petgraph::dijkstra;
+/// use petgraph::graph::UnGraph;
+///
+/// let graph = UnGraph::<(), f64>::from_edges([(0, 1, 1.5), (1, 2, 0.5), (0, 2, 3.0)]);
+/// let distances = dijkstra(&graph, 0.into(), None, |e| *e.weight());
+/// assert_eq!(distances[&2.into()], 2.0);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn dijkstra<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    edge_cost: F,
+) -> HashMap<G::NodeId, K>
+where
+    G: IntoEdges + Visitable + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    // EXPLICIT: the only error is `InvalidArity`, and the arity is positive.
+    dijkstra_with_arity(graph, start, goal, edge_cost, DEFAULT_ARITY).unwrap_or_default()
+}
+
+/// [`dijkstra`] on a heap of arity `d`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArity`] when `d == 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::petgraph::dijkstra_with_arity;
+/// use petgraph::graph::DiGraph;
+///
+/// let graph = DiGraph::<(), u32>::from_edges([(0, 1, 7), (0, 2, 2), (2, 1, 3)]);
+/// let distances = dijkstra_with_arity(&graph, 0.into(), Some(1.into()), |e| *e.weight(), 8).unwrap();
+/// assert_eq!(distances[&1.into()], 5);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn dijkstra_with_arity<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    edge_cost: F,
+    d: usize,
+) -> Result<HashMap<G::NodeId, K>, Error>
+where
+    G: IntoEdges + Visitable + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    dijkstra_adaptive(graph, start, goal, edge_cost, AdaptiveArity::fixed(d))
+        .map(|(scores, _)| scores)
+}
+
+/// [`dijkstra`] on a heap whose arity follows the search: starting from
+/// `policy.initial`, it switches (with an O(n) rebuild) whenever the
+/// observed ratio of sift-ups to pops makes another arity cheaper, as
+/// described on [`AdaptiveArity`]. Returns the distances and an
+/// [`AdaptiveReport`] of what changed and how many comparisons it took.
+///
+/// The ratio is not known up front and shifts during a run: early pops
+/// discover many new nodes, late ones mostly improve queued ones or find
+/// nothing. Whether adapting beats a fixed arity depends on the graph, so
+/// the report is measured; run the same search under
+/// [`AdaptiveArity::fixed`] to compare.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArity`] when `policy` has a zero arity or
+/// window, or `min > max`.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::petgraph::{dijkstra_adaptive, AdaptiveArity};
+/// use petgraph::graph::DiGraph;
+///
+/// let graph = DiGraph::<(), u32>::from_edges([(0, 1, 7), (0, 2, 2), (2, 1, 3)]);
+/// let (distances, report) =
+///     dijkstra_adaptive(&graph, 0.into(), None, |e| *e.weight(), AdaptiveArity::default()).unwrap();
+/// assert_eq!(distances[&1.into()], 5);
+/// assert_eq!(report.pops, 3);
+/// assert_eq!(report.decrease_keys, 1);
+/// assert!(report.switches.is_empty()); // too short to look
+///
+/// let (_, fixed) =
+///     dijkstra_adaptive(&graph, 0.into(), None, |e| *e.weight(), AdaptiveArity::fixed(4)).unwrap();
+/// assert!(!report.paid_off(&fixed));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn dijkstra_adaptive<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    mut edge_cost: F,
+    policy: AdaptiveArity,
+) -> Result<(HashMap<G::NodeId, K>, AdaptiveReport), Error>
+where
+    G: IntoEdges + Visitable + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    if policy.min == 0 || policy.min > policy.max || policy.window == 0 {
+        return Err(Error::InvalidArity);
+    }
+    let comparisons = Cell::new(0);
+    let mut heap = PriorityQueue::with_position_index(
+        policy.initial,
+        CountedLowestScore(&comparisons),
+        node_index(graph),
+    )?;
+    let candidates = policy.candidates();
+    let mut report = AdaptiveReport::default();
+    let mut window = Window::default();
+
+    let mut settled = graph.visit_map();
+    let mut scores = HashMap::new();
+    let zero = K::default();
+    scores.insert(start, zero);
+    heap.insert(Scored {
+        node: start,
+        score: zero,
+    });
+    report.inserts += 1;
+    loop {
+        let before = comparisons.get();
+        let Some(Scored { node, score }) = heap.pop() else {
+            break;
+        };
+        window.pop_comparisons += comparisons.get() - before;
+        report.pops += 1;
+        if goal == Some(node) {
+            break;
+        }
+        settled.visit(node);
+        let before = comparisons.get();
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if settled.is_visited(&next) {
+                continue;
+            }
+            let next_score = score + edge_cost(edge);
+            let entry = Scored {
+                node: next,
+                score: next_score,
+            };
+            match scores.entry(next) {
+                Entry::Occupied(mut known) => {
+                    if next_score < *known.get() {
+                        known.insert(next_score);
+                        // Reached and not settled, so still queued
+                        heap.increase_priority_unchecked(&entry);
+                        report.decrease_keys += 1;
+                        window.sift_ups += 1;
+                    }
+                }
+                Entry::Vacant(unknown) => {
+                    unknown.insert(next_score);
+                    heap.insert(entry);
+                    report.inserts += 1;
+                    window.sift_ups += 1;
+                }
+            }
+        }
+        window.sift_up_comparisons += comparisons.get() - before;
+
+        window.pops += 1;
+        if window.pops == policy.window {
+            let d = heap.d();
+            if let Some(to) = window.cheaper_arity(d, heap.len(), &candidates) {
+                let before = comparisons.get();
+                heap.set_arity(to)?;
+                report.switches.push(AritySwitch {
+                    at_pop: report.pops,
+                    from: d,
+                    to,
+                    ratio: window.ratio(),
+                    rebuild_comparisons: comparisons.get() - before,
+                });
+            }
+            window = Window::default();
+        }
+    }
+    report.comparisons = comparisons.get();
+    report.final_arity = heap.d();
+    Ok((scores, report))
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`, guided by
+/// `estimate_cost`, with the signature and results of `petgraph::algo::astar`:
+/// the path's cost and its nodes from `start` to the goal, or `None` if no
+/// goal is reachable.
+///
+/// Edge costs must be non-negative and `estimate_cost` must never
+/// overestimate for the path to be optimal. An inconsistent estimate is
+/// handled by requeueing nodes whose cost improves after they were expanded.
+///
+/// **Time Complexity**: `O(E log_d V)` with `d` = [`DEFAULT_ARITY`], for a
+/// consistent estimate
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::petgraph::astar;
+/// use petgraph::graph::DiGraph;
+///
+/// let graph = DiGraph::<(), u32>::from_edges([(0, 1, 2), (1, 3, 2), (0, 2, 1), (2, 3, 5)]);
+/// let goal = 3.into();
+/// let (cost, path) = astar(&graph, 0.into(), |n| n == goal, |e| *e.weight(), |_| 0).unwrap();
+/// assert_eq!(cost, 4);
+/// assert_eq!(path, [0.into(), 1.into(), goal]);
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn astar<G, F, H, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    is_goal: IsGoal,
+    edge_cost: F,
+    estimate_cost: H,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdges + Visitable + NodeIndexable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    // EXPLICIT: no error is reachable, as in `dijkstra`.
+    astar_with_arity(
+        graph,
+        start,
+        is_goal,
+        edge_cost,
+        estimate_cost,
+        DEFAULT_ARITY,
+    )
+    .ok()
+    .flatten()
+}
+
+/// [`astar`] on a heap of arity `d`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArity`] when `d == 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::petgraph::astar_with_arity;
+/// use petgraph::graph::UnGraph;
+///
+/// let graph = UnGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1)]);
+/// let found = astar_with_arity(&graph, 0.into(), |n| n.index() == 2, |e| *e.weight(), |_| 0, 2);
+/// assert_eq!(found.unwrap().map(|(cost, _)| cost), Some(2));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub fn astar_with_arity<G, F, H, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    mut is_goal: IsGoal,
+    mut edge_cost: F,
+    mut estimate_cost: H,
+    d: usize,
+) -> Result<Option<CostedPath<K, G::NodeId>>, Error>
+where
+    G: IntoEdges + Visitable + NodeIndexable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    let mut heap = PriorityQueue::with_position_index(d, LowestScore, node_index(graph))?;
+    // Cheapest known cost from `start`, and the node it was reached from
+    let mut costs: HashMap<G::NodeId, K> = HashMap::new();
+    let mut came_from: HashMap<G::NodeId, G::NodeId> = HashMap::new();
+    costs.insert(start, K::default());
+    heap.insert(Scored {
+        node: start,
+        score: estimate_cost(start),
+    });
+    while let Some(Scored { node, .. }) = heap.pop() {
+        let cost = costs[&node];
+        if is_goal(node) {
+            let mut path = vec![node];
+            let mut current = node;
+            while let Some(&previous) = came_from.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Ok(Some((cost, path)));
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            let next_cost = cost + edge_cost(edge);
+            if costs.get(&next).is_some_and(|&known| next_cost >= known) {
+                continue;
+            }
+            costs.insert(next, next_cost);
+            came_from.insert(next, node);
+            let entry = Scored {
+                node: next,
+                score: next_cost + estimate_cost(next),
+            };
+            if heap.contains(&entry) {
+                heap.increase_priority_unchecked(&entry);
+            } else {
+                // New, or expanded before and now cheaper: (re)open it
+                heap.insert(entry);
+            }
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,105 @@
+//! Typed heap positions.
+//!
+//! A [`Position`] is an index into a heap's backing array, as returned by
+//! `get_position` and taken by the `*_by_index` updates and `cursor_at`.
+//! It is a `#[repr(transparent)]` wrapper around `usize`, so it costs
+//! nothing at runtime, but it cannot be passed where an unrelated index
+//! (a node id, a slice offset) is expected, or the other way round, without
+//! an explicit conversion.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, Position, PriorityQueue};
+//!
+//! let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+//! heap.insert_many([30, 10, 20]);
+//!
+//! let pos = heap.get_position(&10).unwrap();
+//! assert_eq!(pos, Position::ROOT);
+//! assert_eq!(pos.get_at(&heap), Some(&10));
+//! assert!(!Position::new(3).is_valid_for(&heap));
+//! assert_eq!(usize::from(pos), 0);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - C++: `TOOLS::PriorityQueue<T>::Position`
+//! - Zig: `DHeap.Position`
+//! - TypeScript: `Position` type alias
+//!
+//! The other languages alias a plain integer; only Rust makes it a distinct
+//! type.
+
+use crate::PriorityQueue;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Index of an item in a heap's backing array.
+///
+/// Positions are only meaningful for the heap, and the heap state, they came
+/// from: any mutation may move items. Convert from and to `usize` with
+/// [`Position::new`] / [`Position::index`] or the `From` impls.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position(usize);
+
+impl Position {
+    /// The root's position, where the highest-priority item lives.
+    pub const ROOT: Self = Self(0);
+
+    /// Wraps an array index.
+    #[must_use]
+    pub const fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// The array index.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+
+    /// The item at this position in `heap`, or `None` if out of bounds.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn get_at<T, C, S, P>(self, heap: &PriorityQueue<T, C, S, P>) -> Option<&T>
+    where
+        T: Eq + Clone,
+    {
+        heap.container.get(self.0)
+    }
+
+    /// Whether this position holds an item of `heap`.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn is_valid_for<T, C, S, P>(self, heap: &PriorityQueue<T, C, S, P>) -> bool
+    where
+        T: Eq + Clone,
+    {
+        self.0 < heap.container.len()
+    }
+}
+
+impl From<usize> for Position {
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+impl From<Position> for usize {
+    fn from(position: Position) -> Self {
+        position.0
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
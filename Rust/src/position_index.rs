@@ -0,0 +1,500 @@
+//! Item → position indexes behind a heap's O(1) lookups.
+//!
+//! Every heap keeps an index from each item's identity to its slot in the
+//! backing array, which is what makes `contains`, `get_position` and the
+//! priority updates O(1). [`PositionIndex`] abstracts that index:
+//!
+//! - [`HashIndex`] (the default) works for any `T: Hash`. It stores each
+//!   item's hash and position, not the item: a lookup finds the positions
+//!   filed under the probe's hash and compares the probe with the items at
+//!   those positions in the heap array. Every item is therefore stored once,
+//!   in the array, which matters for large items such as strings or search
+//!   states.
+//! - `HashMap<T, Position>` keys each entry by a clone of its item, so
+//!   every item is held twice: once in the array and once in the map. A map
+//!   with another hasher can be passed in, too.
+//! - [`DenseIndex`] is a plain array addressed by a small integer id taken
+//!   from each item, such as a graph node index. It never hashes and stores
+//!   no items, at the cost of one slot per possible id.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{DenseIndex, MinBy, PriorityQueue};
+//!
+//! // (node, distance) pairs, identified by node
+//! #[derive(Clone, Copy, Debug)]
+//! struct Entry { node: u32, dist: u64 }
+//! impl PartialEq for Entry { fn eq(&self, o: &Self) -> bool { self.node == o.node } }
+//! impl Eq for Entry {}
+//!
+//! let index = DenseIndex::new(|e: &Entry| e.node as usize);
+//! let mut heap = PriorityQueue::with_position_index(4, MinBy(|e: &Entry| e.dist), index).unwrap();
+//! heap.insert(Entry { node: 7, dist: 30 });
+//! heap.insert(Entry { node: 2, dist: 50 });
+//! heap.increase_priority(&Entry { node: 2, dist: 10 }).unwrap();
+//! assert_eq!(heap.pop().map(|e| e.node), Some(2));
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages key their position maps by a hashable identity.
+
+use crate::Position;
+use std::borrow::Borrow;
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+
+/// An index from item identity to heap position.
+///
+/// Implementations must treat two items as the same exactly when the heap's
+/// `T: Eq` does. The heap keeps the index consistent with its array: it
+/// inserts each item once, tells the index about every move, and removes
+/// an item only from the position it is indexed at. Lookups receive the
+/// heap array, so an index may confirm a match by comparing with the item
+/// stored there instead of keeping a copy of its own.
+pub trait PositionIndex<T>: Clone {
+    /// Position of `item`, or `None` if it is not indexed. `items` is the
+    /// heap array the index describes.
+    fn get(&self, item: &T, items: &[T]) -> Option<Position>;
+
+    /// Indexes `item`, which the heap holds at `position`.
+    fn insert(&mut self, item: &T, position: Position);
+
+    /// Moves an indexed `item` from `from` to `to`. Called on every swap,
+    /// while the array is mid-swap, so it must not look items up.
+    fn reposition(&mut self, item: &T, from: Position, to: Position);
+
+    /// Removes `item`, which is indexed at `position`.
+    fn remove(&mut self, item: &T, position: Position);
+
+    /// Notes that `item` has replaced an equal item at `position`, as a
+    /// priority update does. The default does nothing; an index that keeps
+    /// copies of its items replaces its copy.
+    fn refresh(&mut self, _item: &T, _position: Position) {}
+
+    /// Number of indexed items.
+    fn len(&self) -> usize;
+
+    /// Whether no items are indexed.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry.
+    fn clear(&mut self);
+
+    /// An empty index configured like this one, for `clear` on a heap whose
+    /// index is still shared with a snapshot.
+    #[must_use]
+    fn empty(&self) -> Self;
+
+    /// Whether `item` is indexed.
+    fn contains(&self, item: &T, items: &[T]) -> bool {
+        self.get(item, items).is_some()
+    }
+
+    /// Makes room for at least `additional` more items, for
+    /// [`PriorityQueue::reserve`](crate::PriorityQueue::reserve). The
+    /// default does nothing, for indexes sized by something other than the
+    /// item count.
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// Releases spare capacity, for
+    /// [`PriorityQueue::shrink_to_fit`](crate::PriorityQueue::shrink_to_fit).
+    /// The default does nothing.
+    fn shrink_to_fit(&mut self) {}
+}
+
+/// Hasher for keys that are already hashes: a `u64` passes through.
+#[derive(Default)]
+struct HashBits(u64);
+
+impl Hasher for HashBits {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(byte);
+        }
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+}
+
+type ByHash<V> = HashMap<u64, V, BuildHasherDefault<HashBits>>;
+
+/// Position index that hashes items but stores only their positions; the
+/// default index of a [`PriorityQueue`](crate::PriorityQueue).
+///
+/// Each entry is an item's 64-bit hash and its position in the heap array.
+/// A lookup hashes the probe, then compares it with the item at each
+/// position filed under that hash, which is almost always exactly one. The
+/// items themselves live only in the array, so inserting an item does not
+/// clone it.
+///
+/// `S` builds the item hasher, [`RandomState`] by default, as for a
+/// `HashMap`.
+///
+/// **Space**: one hash and one position per item
+#[derive(Clone)]
+pub struct HashIndex<S = RandomState> {
+    /// Position of the item with each hash, or of the first one indexed
+    /// when several items share it.
+    first: ByHash<Position>,
+    /// Positions of the other items sharing a hash with one in `first`.
+    /// Full 64-bit collisions are rare, so this is almost always empty.
+    more: ByHash<Vec<Position>>,
+    len: usize,
+    hasher: S,
+}
+
+impl HashIndex {
+    /// An empty index.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty index with room for `capacity` items before it grows.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            first: ByHash::with_capacity_and_hasher(capacity, BuildHasherDefault::default()),
+            ..Self::default()
+        }
+    }
+}
+
+impl<S> HashIndex<S> {
+    /// An empty index that hashes items with `hasher`.
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            first: ByHash::default(),
+            more: ByHash::default(),
+            len: 0,
+            hasher,
+        }
+    }
+}
+
+impl<S: Default> Default for HashIndex<S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+// Manual impl: the hasher need not be `Debug`.
+impl<S> Debug for HashIndex<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("HashIndex")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: BuildHasher> HashIndex<S> {
+    /// Position of the item in `items` that borrows as `key`. Items must
+    /// hash as the keys they borrow as, which `Borrow` requires anyway.
+    pub(crate) fn get_by<T, Q>(&self, key: &Q, items: &[T]) -> Option<Position>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let is_key = |position: &Position| {
+            items
+                .get(position.index())
+                .is_some_and(|item| item.borrow() == key)
+        };
+        let first = self.first.get(&hash)?;
+        if is_key(first) {
+            return Some(*first);
+        }
+        self.more.get(&hash)?.iter().find(|p| is_key(p)).copied()
+    }
+}
+
+impl<T, S> PositionIndex<T> for HashIndex<S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    #[inline]
+    fn get(&self, item: &T, items: &[T]) -> Option<Position> {
+        self.get_by(item, items)
+    }
+
+    #[inline]
+    fn insert(&mut self, item: &T, position: Position) {
+        let hash = self.hasher.hash_one(item);
+        match self.first.entry(hash) {
+            Entry::Vacant(slot) => {
+                slot.insert(position);
+            }
+            Entry::Occupied(_) => self.more.entry(hash).or_default().push(position),
+        }
+        self.len += 1;
+    }
+
+    #[inline]
+    fn reposition(&mut self, item: &T, from: Position, to: Position) {
+        // Positions are only matched, never looked up in the array: during
+        // a swap two entries of one hash may briefly hold the same position,
+        // and moving either one leaves the right pair.
+        let hash = self.hasher.hash_one(item);
+        if let Some(first) = self.first.get_mut(&hash) {
+            if *first == from {
+                *first = to;
+                return;
+            }
+        }
+        if let Some(slot) = self
+            .more
+            .get_mut(&hash)
+            .and_then(|more| more.iter_mut().find(|p| **p == from))
+        {
+            *slot = to;
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, item: &T, position: Position) {
+        let hash = self.hasher.hash_one(item);
+        if self.first.get(&hash) == Some(&position) {
+            // Promote another position of the same hash, if any
+            match self.more.get_mut(&hash).and_then(Vec::pop) {
+                Some(next) => {
+                    self.first.insert(hash, next);
+                    if self.more.get(&hash).is_some_and(Vec::is_empty) {
+                        self.more.remove(&hash);
+                    }
+                }
+                None => {
+                    self.first.remove(&hash);
+                }
+            }
+            self.len -= 1;
+        } else if let Some(more) = self.more.get_mut(&hash) {
+            if let Some(k) = more.iter().position(|&p| p == position) {
+                more.swap_remove(k);
+                if more.is_empty() {
+                    self.more.remove(&hash);
+                }
+                self.len -= 1;
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        self.first.clear();
+        self.more.clear();
+        self.len = 0;
+    }
+
+    fn empty(&self) -> Self {
+        Self::with_hasher(self.hasher.clone())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.first.reserve(additional);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.first.shrink_to_fit();
+        self.more.shrink_to_fit();
+    }
+}
+
+impl<T, H> PositionIndex<T> for HashMap<T, Position, H>
+where
+    T: Eq + Hash + Clone,
+    H: BuildHasher + Clone,
+{
+    #[inline]
+    fn get(&self, item: &T, _items: &[T]) -> Option<Position> {
+        HashMap::get(self, item).copied()
+    }
+
+    #[inline]
+    fn insert(&mut self, item: &T, position: Position) {
+        HashMap::insert(self, item.clone(), position);
+    }
+
+    #[inline]
+    fn reposition(&mut self, item: &T, _from: Position, to: Position) {
+        if let Some(slot) = self.get_mut(item) {
+            *slot = to;
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, item: &T, _position: Position) {
+        HashMap::remove(self, item);
+    }
+
+    /// Replaces the stored key: `HashMap::insert` keeps the old key on a
+    /// hit, so it is removed first.
+    fn refresh(&mut self, item: &T, position: Position) {
+        HashMap::remove(self, item);
+        HashMap::insert(self, item.clone(), position);
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self);
+    }
+
+    fn empty(&self) -> Self {
+        Self::with_hasher(self.hasher().clone())
+    }
+
+    #[inline]
+    fn contains(&self, item: &T, _items: &[T]) -> bool {
+        self.contains_key(item)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        HashMap::reserve(self, additional);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        HashMap::shrink_to_fit(self);
+    }
+}
+
+/// Marks a [`DenseIndex`] slot whose id is not in the heap.
+const ABSENT: Position = Position::new(usize::MAX);
+
+/// Position index stored as an array indexed by `id(item)`, for items whose
+/// identity is a small non-negative integer: graph node ids, slot numbers,
+/// interned symbols.
+///
+/// Lookups and updates are one array access, with no hashing. The array
+/// grows to the largest id inserted, so ids should be dense; `id` must map
+/// equal items to the same id and different items to different ids.
+///
+/// **Space**: one `usize` per id up to the largest inserted
+#[derive(Clone)]
+pub struct DenseIndex<F> {
+    /// `slots[id]` is the position of the item with that id, or `ABSENT`.
+    slots: Vec<Position>,
+    /// Slots that are not `ABSENT`.
+    len: usize,
+    id: F,
+}
+
+impl<F> DenseIndex<F> {
+    /// An empty index that identifies items by `id`.
+    #[must_use]
+    pub const fn new(id: F) -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+            id,
+        }
+    }
+
+    /// An empty index with room for ids `0..ids` before it grows, e.g. a
+    /// graph's node count.
+    #[must_use]
+    pub fn with_capacity(id: F, ids: usize) -> Self {
+        Self {
+            slots: vec![ABSENT; ids],
+            len: 0,
+            id,
+        }
+    }
+}
+
+// Manual impl: the id closure is not `Debug`.
+impl<F> Debug for DenseIndex<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DenseIndex")
+            .field("ids", &self.slots.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F> PositionIndex<T> for DenseIndex<F>
+where
+    F: Fn(&T) -> usize + Clone,
+{
+    #[inline]
+    fn get(&self, item: &T, _items: &[T]) -> Option<Position> {
+        self.slots
+            .get((self.id)(item))
+            .copied()
+            .filter(|&position| position != ABSENT)
+    }
+
+    #[inline]
+    fn insert(&mut self, item: &T, position: Position) {
+        let id = (self.id)(item);
+        if id >= self.slots.len() {
+            self.slots.resize(id + 1, ABSENT);
+        }
+        // INDEX: resized above to cover `id`.
+        if std::mem::replace(&mut self.slots[id], position) == ABSENT {
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    fn reposition(&mut self, item: &T, _from: Position, to: Position) {
+        if let Some(slot) = self.slots.get_mut((self.id)(item)) {
+            *slot = to;
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, item: &T, _position: Position) {
+        if let Some(slot) = self.slots.get_mut((self.id)(item)) {
+            if std::mem::replace(slot, ABSENT) != ABSENT {
+                self.len -= 1;
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        // Keeps the capacity; `insert` refills slots as ids come back.
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    fn empty(&self) -> Self {
+        Self::new(self.id.clone())
+    }
+
+    // `reserve` keeps the default: slots follow the largest id, not the
+    // item count.
+
+    fn shrink_to_fit(&mut self) {
+        // Slots past the largest id still indexed are only spare room.
+        let used = self
+            .slots
+            .iter()
+            .rposition(|&position| position != ABSENT)
+            .map_or(0, |last| last + 1);
+        self.slots.truncate(used);
+        self.slots.shrink_to_fit();
+    }
+}
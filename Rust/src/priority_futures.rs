@@ -0,0 +1,253 @@
+//! Priority-ordered polling of a set of futures (`async` feature).
+//!
+//! [`PriorityFutures`] is the priority-aware counterpart of the futures
+//! crate's `FuturesUnordered`: it owns a set of futures, each pushed with a
+//! priority, and yields their outputs as they complete. When several futures
+//! have been woken, the one with the highest priority is polled first; equal
+//! priorities are polled in push order. The ready set is a d-ary
+//! [`PriorityQueue`] keyed by task id, so re-waking an already-ready task is
+//! an O(1) membership check rather than a duplicate entry.
+//!
+//! The adapter uses only `std::task`; it exposes [`PriorityFutures::poll_next`]
+//! and the `async fn` [`PriorityFutures::next`] instead of implementing a
+//! `Stream` trait, so the crate keeps its zero-dependency policy. Wrapping it
+//! in a `Stream` is a short `impl` in the caller's crate. All futures in one
+//! set share a type; box them (`Pin<Box<dyn Future<Output = T>>>`) to mix
+//! `async` blocks.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::PriorityFutures;
+//! use std::future::ready;
+//!
+//! # async fn run() {
+//! let mut jobs = PriorityFutures::new(4);
+//! jobs.push(ready("background"), 1);
+//! jobs.push(ready("interactive"), 10);
+//!
+//! // Both are ready on their first poll; the higher priority wins.
+//! assert_eq!(jobs.next().await, Some("interactive"));
+//! assert_eq!(jobs.next().await, Some("background"));
+//! assert_eq!(jobs.next().await, None);
+//! # }
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Go (goroutines), TypeScript (promises) and Zig have no poll-based
+//!   futures to schedule.
+
+use crate::{PriorityCompare, PriorityQueue};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Identifies one pushed future; unique for the lifetime of the set.
+type TaskId = u64;
+
+/// Entry in the ready index: a woken task and the key it is ordered by.
+#[derive(Debug, Clone)]
+struct Ready<P> {
+    id: TaskId,
+    /// `(priority, Reverse(id))`: higher priority first, then push order.
+    key: (P, Reverse<TaskId>),
+}
+
+impl<P> PartialEq for Ready<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<P> Eq for Ready<P> {}
+
+impl<P> Hash for Ready<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Max-heap on `key`.
+#[derive(Debug, Clone, Copy, Default)]
+struct HighestFirst;
+
+impl<P: Ord> PriorityCompare<Ready<P>> for HighestFirst {
+    fn higher_priority(&self, a: &Ready<P>, b: &Ready<P>) -> bool {
+        a.key.cmp(&b.key) == Ordering::Greater
+    }
+}
+
+/// Wake-ups recorded by task wakers, drained into the ready index on the
+/// next `poll_next`. Shared with the wakers, hence `Arc<Mutex<_>>`.
+#[derive(Default)]
+struct WakeQueue {
+    woken: Vec<TaskId>,
+    parent: Option<Waker>,
+}
+
+struct TaskWaker {
+    id: TaskId,
+    queue: Arc<Mutex<WakeQueue>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let parent = {
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            queue.woken.push(self.id);
+            queue.parent.clone()
+        };
+        if let Some(parent) = parent {
+            parent.wake();
+        }
+    }
+}
+
+struct Task<F, P> {
+    future: Pin<Box<F>>,
+    priority: P,
+    waker: Waker,
+}
+
+/// A set of futures polled in priority order; see the [module docs](self).
+pub struct PriorityFutures<F: Future, P: Clone> {
+    tasks: HashMap<TaskId, Task<F, P>>,
+    ready: PriorityQueue<Ready<P>, HighestFirst>,
+    wake_queue: Arc<Mutex<WakeQueue>>,
+    next_id: TaskId,
+}
+
+impl<F, P> PriorityFutures<F, P>
+where
+    F: Future,
+    P: Ord + Clone,
+{
+    /// Creates an empty set whose ready index is a `d`-ary heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::PriorityFutures;
+    /// use std::future::Ready;
+    ///
+    /// let jobs: PriorityFutures<Ready<()>, u8> = PriorityFutures::new(4);
+    /// assert!(jobs.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new(d: usize) -> Self {
+        Self {
+            tasks: HashMap::new(),
+            ready: PriorityQueue::new(d, HighestFirst).expect("arity must be >= 1"),
+            wake_queue: Arc::default(),
+            next_id: 0,
+        }
+    }
+
+    /// Number of futures that have not yet completed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Whether every pushed future has completed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Adds `future` with `priority` (higher polls first). It is polled for
+    /// the first time on the next call to `poll_next`.
+    pub fn push(&mut self, future: F, priority: P) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            queue: Arc::clone(&self.wake_queue),
+        }));
+        self.ready.insert(Ready {
+            id,
+            key: (priority.clone(), Reverse(id)),
+        });
+        self.tasks.insert(
+            id,
+            Task {
+                future: Box::pin(future),
+                priority,
+                waker,
+            },
+        );
+    }
+
+    /// Polls ready futures, highest priority first, until one completes.
+    ///
+    /// Returns `Ready(Some(output))` for a completed future, `Ready(None)`
+    /// once the set is empty, and `Pending` when no woken future completed.
+    /// To stay cooperative, at most `len()` futures are polled per call; if
+    /// that budget runs out the current task is re-woken.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+        let woken = {
+            let mut queue = self
+                .wake_queue
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            queue.parent = Some(cx.waker().clone());
+            std::mem::take(&mut queue.woken)
+        };
+        for id in woken {
+            // A stale wake-up for a finished task, or a duplicate, is ignored.
+            if let Some(task) = self.tasks.get(&id) {
+                let entry = Ready {
+                    id,
+                    key: (task.priority.clone(), Reverse(id)),
+                };
+                if !self.ready.contains(&entry) {
+                    self.ready.insert(entry);
+                }
+            }
+        }
+
+        let budget = self.tasks.len();
+        for _ in 0..budget {
+            let Some(Ready { id, .. }) = self.ready.pop() else {
+                break;
+            };
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            let waker = task.waker.clone();
+            if let Poll::Ready(output) = task.future.as_mut().poll(&mut Context::from_waker(&waker))
+            {
+                self.tasks.remove(&id);
+                return Poll::Ready(Some(output));
+            }
+        }
+
+        if self.tasks.is_empty() {
+            Poll::Ready(None)
+        } else {
+            if !self.ready.is_empty() {
+                cx.waker().wake_by_ref();
+            }
+            Poll::Pending
+        }
+    }
+
+    /// Resolves to the output of the next future to complete, or `None`
+    /// once the set is empty.
+    pub async fn next(&mut self) -> Option<F::Output> {
+        std::future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+}
@@ -0,0 +1,231 @@
+//! Per-operation profiling with CSV export, for empirical complexity plots.
+//!
+//! [`ProfileTrace`] is a [`StatsCollector`] that records one [`ProfileRecord`]
+//! per public heap operation: which operation ran, the heap size when it
+//! started, how many comparisons and swaps it made, and its wall-clock time.
+//! Plotting comparisons or time against `len` over a long workload gives the
+//! `log_d n` curves the `experiment/` write-ups are built around, without an
+//! external profiler.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! let mut pq = PriorityQueue::with_profile(4, MinBy(|x: &i32| *x)).unwrap();
+//! for v in (0..100).rev() {
+//!     pq.insert(v);
+//! }
+//! pq.pop();
+//!
+//! let mut csv = Vec::new();
+//! pq.stats().write_csv(&mut csv).unwrap();
+//! let csv = String::from_utf8(csv).unwrap();
+//! assert!(csv.starts_with("operation,len,comparisons,swaps,nanoseconds\n"));
+//! assert_eq!(csv.lines().count(), 1 + 101);
+//! ```
+//!
+//! Only CSV is produced; columnar formats such as Parquet would need a
+//! dependency, and every plotting stack reads CSV.
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. Timings are only comparable within one implementation, so the
+//!   other languages keep to comparison counts.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use std::cell::{Cell, Ref, RefCell};
+use std::fs::OpenOptions;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Column names written by [`ProfileTrace::write_csv`], newline-terminated.
+pub const PROFILE_CSV_HEADER: &str = "operation,len,comparisons,swaps,nanoseconds\n";
+
+/// One public heap operation as recorded by [`ProfileTrace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileRecord {
+    /// Operation that produced this record.
+    pub operation: OperationType,
+    /// Number of items in the heap when the operation started.
+    pub len: usize,
+    /// Number of priority comparisons made during the operation.
+    pub comparisons: u64,
+    /// Number of position swaps made during the operation.
+    pub swaps: u64,
+    /// Wall-clock time from `start_operation` to `end_operation`.
+    pub elapsed: Duration,
+}
+
+/// Profiling policy: records a [`ProfileRecord`] per operation.
+///
+/// Uses interior mutability (`RefCell` / `Cell`) so the `&self` trait methods
+/// can append records, like `ExplainTrace` does for its steps. Timings include
+/// the collector's own bookkeeping, which is a few counter updates per
+/// comparison; compare them across arities rather than reading them as
+/// absolute costs.
+#[derive(Default, Debug, Clone)]
+pub struct ProfileTrace {
+    records: RefCell<Vec<ProfileRecord>>,
+    started: Cell<Option<Instant>>,
+}
+
+impl ProfileTrace {
+    /// The records collected so far, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn records(&self) -> Ref<'_, Vec<ProfileRecord>> {
+        self.records.borrow()
+    }
+
+    /// Removes and returns all records, leaving the trace empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    #[must_use]
+    pub fn take_records(&self) -> Vec<ProfileRecord> {
+        self.records.take()
+    }
+
+    /// Writes [`PROFILE_CSV_HEADER`] followed by one row per record.
+    ///
+    /// `operation` is [`OperationType::as_str`] and `nanoseconds` is the
+    /// elapsed time in whole nanoseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `writer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(PROFILE_CSV_HEADER.as_bytes())?;
+        write_rows(&mut writer, &self.records.borrow())
+    }
+
+    /// Appends the records to the CSV file at `path` and clears them, so
+    /// calling this periodically streams a long run to disk. The header is
+    /// written only when the file is new or empty. Returns the number of
+    /// rows written.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from opening or writing the file. The records are
+    /// cleared only once they have been written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while the heap is mid-operation (not reachable
+    /// through the public heap API).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue};
+    ///
+    /// let path = std::env::temp_dir().join("d_ary_heap_profile_doctest.csv");
+    /// let _ = std::fs::remove_file(&path);
+    ///
+    /// let mut pq = PriorityQueue::with_profile(2, MinBy(|x: &i32| *x)).unwrap();
+    /// pq.insert(1);
+    /// assert_eq!(pq.stats().append_csv(&path).unwrap(), 1);
+    /// pq.insert(2);
+    /// assert_eq!(pq.stats().append_csv(&path).unwrap(), 1);
+    ///
+    /// let csv = std::fs::read_to_string(&path).unwrap();
+    /// assert_eq!(csv.lines().count(), 3); // one header, two rows
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn append_csv(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+        let mut writer = BufWriter::new(file);
+        if is_empty {
+            writer.write_all(PROFILE_CSV_HEADER.as_bytes())?;
+        }
+        let mut records = self.records.borrow_mut();
+        write_rows(&mut writer, &records)?;
+        writer.flush()?;
+        let written = records.len();
+        records.clear();
+        Ok(written)
+    }
+
+    fn with_current(&self, f: impl FnOnce(&mut ProfileRecord)) {
+        if self.started.get().is_some() {
+            if let Some(record) = self.records.borrow_mut().last_mut() {
+                f(record);
+            }
+        }
+    }
+}
+
+fn write_rows(writer: &mut impl Write, records: &[ProfileRecord]) -> io::Result<()> {
+    for r in records {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            r.operation.as_str(),
+            r.len,
+            r.comparisons,
+            r.swaps,
+            r.elapsed.as_nanos()
+        )?;
+    }
+    Ok(())
+}
+
+impl StatsCollector for ProfileTrace {
+    fn start_operation(&self, op: OperationType) {
+        self.records.borrow_mut().push(ProfileRecord {
+            operation: op,
+            len: 0,
+            comparisons: 0,
+            swaps: 0,
+            elapsed: Duration::ZERO,
+        });
+        self.started.set(Some(Instant::now()));
+    }
+
+    fn end_operation(&self) {
+        if let Some(started) = self.started.get() {
+            let elapsed = started.elapsed();
+            self.with_current(|record| record.elapsed = elapsed);
+        }
+        self.started.set(None);
+    }
+
+    fn count_comparison(&self) {
+        self.with_current(|record| record.comparisons += 1);
+    }
+
+    fn total(&self) -> u64 {
+        self.records
+            .borrow()
+            .iter()
+            .map(|record| record.comparisons)
+            .sum()
+    }
+
+    fn reset(&self) {
+        self.records.borrow_mut().clear();
+        self.started.set(None);
+    }
+
+    fn record_swap(&self, _i: usize, _j: usize) {
+        self.with_current(|record| record.swaps += 1);
+    }
+
+    fn record_len(&self, len: usize) {
+        self.with_current(|record| record.len = len);
+    }
+}
@@ -0,0 +1,159 @@
+//! Time-sliced heap rebuilds.
+//!
+//! When every priority changes at once, because the comparator reads state
+//! that was just updated or because items hold their priorities in `Cell`s
+//! that were all rewritten, the heap has to be rebuilt. Floyd's heapify
+//! does that in O(n), but for ten million items O(n) is a stall of several
+//! milliseconds, too long for one frame of a game or a control loop.
+//!
+//! [`IncrementalRebuild`] runs the same heapify in slices. It takes the
+//! heap by value, and each [`rebuild_step`](IncrementalRebuild::rebuild_step)
+//! performs at most `budget` sift-downs. The finished heap is identical to
+//! the one a single heapify would produce. Because the rebuild owns the
+//! heap, nothing can read or change it while it is half built: the heap
+//! comes back from [`finish`](IncrementalRebuild::finish), or from
+//! [`into_heap`](IncrementalRebuild::into_heap) once the last step is done.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//! use std::cell::Cell;
+//! use std::rc::Rc;
+//!
+//! // Every cost is scaled by a shared weight that may change between frames
+//! let weight = Rc::new(Cell::new(1i64));
+//! let w = Rc::clone(&weight);
+//! let mut heap = PriorityQueue::new(4, MinBy(move |x: &i64| *x * w.get())).unwrap();
+//! heap.insert_many(-500..500);
+//!
+//! weight.set(-1); // the order flips; the heap must be rebuilt
+//! let mut rebuild = heap.start_rebuild();
+//! let mut frames = 0;
+//! while !rebuild.rebuild_step(64) {
+//!     frames += 1; // render a frame, handle input, ...
+//! }
+//! let mut heap = rebuild.into_heap().unwrap();
+//! assert_eq!(heap.pop(), Some(499));
+//! assert!(frames > 1);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. The other languages rebuild only as part of an operation.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use crate::{HashIndex, PositionIndex, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// A heap being rebuilt a few sift-downs at a time; see the
+/// [module docs](self).
+///
+/// Obtained from
+/// [`PriorityQueue::start_rebuild`](crate::PriorityQueue::start_rebuild).
+pub struct IncrementalRebuild<T, C, S, P = HashIndex>
+where
+    T: Eq + Clone,
+{
+    heap: PriorityQueue<T, C, S, P>,
+    /// Sift-downs left: the nodes `0..remaining`, sifted from the last down.
+    remaining: usize,
+}
+
+impl<T, C, S, P> IncrementalRebuild<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    pub(crate) fn new(heap: PriorityQueue<T, C, S, P>) -> Self {
+        let n = heap.container.len();
+        // Every node with a child, as in `heapify`.
+        let remaining = if n > 1 { (n - 2) / heap.depth + 1 } else { 0 };
+        Self { heap, remaining }
+    }
+
+    /// Performs at most `budget` sift-downs and returns whether the rebuild
+    /// is complete.
+    ///
+    /// Each sift-down costs `O(d · log_d n)` at worst and the whole rebuild
+    /// O(n), so a fixed budget bounds the work per call. The step counts as
+    /// one `OperationType::UpdatePriority` for the stats collector, like
+    /// the rebuild of `set_arity`.
+    ///
+    /// **Time Complexity**: `O(budget · d · log_d n)`
+    pub fn rebuild_step(&mut self, budget: usize) -> bool {
+        let steps = budget.min(self.remaining);
+        if steps > 0 {
+            let remaining = &mut self.remaining;
+            self.heap.bracket(OperationType::UpdatePriority, |s| {
+                for _ in 0..steps {
+                    *remaining -= 1;
+                    s.sift_down(*remaining);
+                }
+            });
+        }
+        self.is_done()
+    }
+
+    /// Whether every sift-down has been performed.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Number of sift-downs still to perform.
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Number of items in the heap being rebuilt.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.container.len()
+    }
+
+    /// Whether the heap being rebuilt is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.container.is_empty()
+    }
+
+    /// Performs the sift-downs still to do and returns the heap.
+    ///
+    /// **Time Complexity**: O(n) at most
+    #[must_use]
+    pub fn finish(mut self) -> PriorityQueue<T, C, S, P> {
+        self.rebuild_step(self.remaining);
+        self.heap
+    }
+
+    /// Returns the heap if the rebuild is complete, or the rebuild itself
+    /// if steps remain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(self)` while [`is_done`](Self::is_done) is `false`.
+    pub fn into_heap(self) -> Result<PriorityQueue<T, C, S, P>, Self> {
+        if self.is_done() {
+            Ok(self.heap)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+// Manual impl: the heap's comparator need not be `Debug`.
+impl<T, C, S, P> Debug for IncrementalRebuild<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("IncrementalRebuild")
+            .field("len", &self.heap.container.len())
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
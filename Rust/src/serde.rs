@@ -0,0 +1,140 @@
+//! Serialization of queues (`serde` feature).
+//!
+//! A `PriorityQueue` serializes as its arity and its heap array, in array
+//! order: `{"d": 4, "items": [...]}` in JSON. The positions map is not
+//! written; deserializing rebuilds it from the array, so a checkpoint costs
+//! the items and nothing more. Comparators and stats collectors are not
+//! written either, and neither is the [`ChildScan`](crate::ChildScan)
+//! policy, which comes back as the default.
+//!
+//! A comparator with a [`Default`], such as [`MinOrd`](crate::MinOrd) or
+//! [`MaxOrd`](crate::MaxOrd), is rebuilt by `Deserialize` itself. A closure
+//! cannot be, so queues ordered by [`MinBy`](crate::MinBy) or
+//! [`MaxBy`](crate::MaxBy) are loaded with
+//! [`PriorityQueue::deserialize_with`](crate::PriorityQueue::deserialize_with),
+//! which takes the comparator as an argument.
+//!
+//! Loading heapifies the array in O(n). An array saved from a queue with the
+//! same comparator is already a heap, so heapifying moves nothing and the
+//! queue comes back with the same layout; an array edited by hand or saved
+//! under another ordering is put back in heap order. Items that repeat an
+//! identity are rejected with `Error::InconsistentPositions`, and an arity
+//! of 0 with `Error::InvalidArity`.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, MinHeap, PriorityQueue};
+//!
+//! let mut heap = MinHeap::with_arity(4).unwrap();
+//! heap.insert_many(vec![30, 10, 20]);
+//! let json = serde_json::to_string(&heap).unwrap();
+//! assert_eq!(json, r#"{"d":4,"items":[10,30,20]}"#);
+//!
+//! let mut loaded: MinHeap<u32> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(loaded, heap);
+//! assert_eq!(loaded.pop(), Some(10));
+//!
+//! // A closure comparator is supplied on load
+//! let mut deserializer = serde_json::Deserializer::from_str(&json);
+//! let by_last_digit =
+//!     PriorityQueue::deserialize_with(&mut deserializer, MinBy(|x: &u32| x % 7)).unwrap();
+//! assert_eq!(by_last_digit.front(), &30);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages can save `to_array()` and the arity, and reload
+//!   them with `insert_many`.
+
+use crate::{Error, PriorityCompare, PriorityQueue};
+use ::serde::de::{Deserialize, Deserializer, Error as _};
+use ::serde::ser::{Serialize, Serializer};
+use std::hash::Hash;
+
+/// What a queue serializes as.
+#[derive(::serde::Serialize)]
+#[serde(rename = "PriorityQueue")]
+struct SavedRef<'a, T> {
+    d: usize,
+    items: &'a [T],
+}
+
+/// What a queue deserializes from.
+#[derive(::serde::Deserialize)]
+#[serde(rename = "PriorityQueue")]
+struct Saved<T> {
+    d: usize,
+    items: Vec<T>,
+}
+
+/// Writes the arity and the heap array; see the [module docs](self).
+impl<T, C, S, P> Serialize for PriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone + Serialize,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        SavedRef {
+            d: self.depth,
+            items: &self.container,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Reads a queue ordered by `C::default()`; see the [module docs](self).
+impl<'de, T, C> Deserialize<'de> for PriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone + Deserialize<'de>,
+    C: PriorityCompare<T> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with(deserializer, C::default())
+    }
+}
+
+impl<T, C> PriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone,
+    C: PriorityCompare<T>,
+{
+    /// Deserializes a queue saved with `Serialize`, ordering it by
+    /// `comparator`: the form of `Deserialize` for comparators that cannot
+    /// be rebuilt from nothing, such as closures. The positions map is
+    /// rebuilt from the saved array.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the input is not a saved queue,
+    /// or one carrying `Error::InvalidArity` for an arity of 0 and
+    /// `Error::InconsistentPositions` for items that repeat an identity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MaxBy, PriorityQueue};
+    ///
+    /// let json = r#"{"d":2,"items":["pear","fig","banana"]}"#;
+    /// let mut deserializer = serde_json::Deserializer::from_str(json);
+    /// let mut heap =
+    ///     PriorityQueue::deserialize_with(&mut deserializer, MaxBy(|s: &&str| s.len())).unwrap();
+    /// assert_eq!(heap.pop(), Some("banana"));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn deserialize_with<'de, D>(deserializer: D, comparator: C) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        let Saved { d, items } = Saved::deserialize(deserializer)?;
+        let count = items.len();
+        let heap = Self::from_vec(items, d, comparator).map_err(D::Error::custom)?;
+        if heap.len() != count || !crate::indexes_exactly(&heap.positions, &heap.container) {
+            return Err(D::Error::custom(Error::InconsistentPositions));
+        }
+        Ok(heap)
+    }
+}
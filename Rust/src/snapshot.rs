@@ -0,0 +1,670 @@
+//! Frozen, shareable views of a heap.
+//!
+//! A [`PriorityQueue`] owns its array and position
+//! index outright. For O(1) snapshots, wrap it in a
+//! [`SnapshotPriorityQueue`], which keeps that storage behind `Arc`s:
+//! [`snapshot`](SnapshotPriorityQueue::snapshot) only bumps two reference
+//! counts. The queue and its snapshots share the storage until the queue's
+//! next mutation, which copies it (copy-on-write) and leaves every snapshot
+//! pointing at the state it was taken from. A monitoring thread can thus
+//! capture a consistent view of a very large queue without the producer
+//! paying for a deep clone at capture time; the copy happens once, on the
+//! producer's next mutation, and only if a snapshot is still alive.
+//!
+//! The sharing is opt-in because it has costs a plain heap does not pay:
+//! every mutation checks the reference counts, and the wrapper is `Send`
+//! only when `T` is both `Send` and `Sync`.
+//!
+//! [`freeze`](SnapshotPriorityQueue::freeze) returns a [`FrozenHeap`]: a
+//! snapshot that also keeps a copy of the comparator, so readers can ask
+//! for the best `k` items in priority order with
+//! [`k_smallest`](FrozenHeap::k_smallest) without popping anything. Many
+//! reader threads can share one behind an `Arc` while the writer prepares
+//! the next generation.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+//!
+//! let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+//! heap.insert_many(vec![7, 3, 9]);
+//! let mut pq = SnapshotPriorityQueue::new(heap);
+//!
+//! let snapshot = pq.snapshot();
+//! pq.pop();
+//! pq.insert(1);
+//!
+//! // The snapshot still shows the queue as it was
+//! assert_eq!(snapshot.peek(), Some(&3));
+//! assert_eq!(snapshot.len(), 3);
+//! assert!(!snapshot.contains(&1));
+//!
+//! // and can move to another thread
+//! let total: u32 = std::thread::spawn(move || snapshot.as_slice().iter().sum())
+//!     .join()
+//!     .unwrap();
+//! assert_eq!(total, 19);
+//!
+//! // A frozen heap also ranks its contents
+//! let frozen = pq.freeze();
+//! assert_eq!(frozen.k_smallest(2), [&1, &7]);
+//!
+//! // Any other operation runs on the heap itself
+//! assert_eq!(pq.modify(|heap| heap.remove(&9)), Some(9));
+//! assert_eq!(pq.into_inner().to_array(), [1, 7]);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages expose `to_array()` copies.
+
+use crate::instrumentation::StatsCollector;
+use crate::{
+    Cursor, DenseIndex, HashIndex, NoOpStats, Position, PositionIndex, PriorityCompare,
+    PriorityQueue,
+};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+/// An immutable view of a heap's contents at one point in time.
+///
+/// Obtained from [`SnapshotPriorityQueue::snapshot`] in O(1). Cloning a snapshot is O(1) as well. It keeps no comparator, so
+/// it answers queries about the captured state but cannot be mutated.
+#[derive(Debug)]
+pub struct Snapshot<T, P = HashIndex> {
+    /// Heap-ordered backing array as captured; index 0 is the root.
+    container: Arc<Vec<T>>,
+    /// Item-identity → position index as captured.
+    positions: Arc<P>,
+    /// Arity of the heap the snapshot was taken from.
+    depth: usize,
+}
+
+// Manual impl: a derive would require `T: Clone`, but a clone only bumps
+// the reference counts.
+impl<T, P> Clone for Snapshot<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            container: Arc::clone(&self.container),
+            positions: Arc::clone(&self.positions),
+            depth: self.depth,
+        }
+    }
+}
+
+impl<T, P> Snapshot<T, P>
+where
+    P: PositionIndex<T>,
+{
+    pub(crate) const fn new(container: Arc<Vec<T>>, positions: Arc<P>, depth: usize) -> Self {
+        Self {
+            container,
+            positions,
+            depth,
+        }
+    }
+
+    /// Number of items captured.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.container.len()
+    }
+
+    /// Whether the heap was empty when captured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.container.is_empty()
+    }
+
+    /// Arity of the heap the snapshot was taken from.
+    #[must_use]
+    pub const fn d(&self) -> usize {
+        self.depth
+    }
+
+    /// The highest-priority item at capture time, or `None` if empty.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.container.first()
+    }
+
+    /// Whether `item` (by identity) was in the heap at capture time.
+    #[must_use]
+    pub fn contains(&self, item: &T) -> bool {
+        self.positions.contains(item, &self.container)
+    }
+
+    /// The position `item` (by identity) held at capture time.
+    #[must_use]
+    pub fn get_position(&self, item: &T) -> Option<Position> {
+        self.positions.get(item, &self.container)
+    }
+
+    /// The captured backing array, in heap order (not sorted).
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.container
+    }
+
+    /// `(position, &item)` pairs in array order, root first.
+    #[must_use]
+    pub fn iter_with_positions(&self) -> impl ExactSizeIterator<Item = (Position, &T)> + '_ {
+        self.container
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Position::new(i), item))
+    }
+
+    /// A read-only [`Cursor`] on the captured tree at `position`, or `None`
+    /// if `position` is out of bounds.
+    #[must_use]
+    pub fn cursor_at(&self, position: Position) -> Option<Cursor<'_, T>> {
+        Cursor::new(&self.container, self.depth, position)
+    }
+}
+
+/// A [`Snapshot`] that keeps the heap's comparator, so it can rank what it
+/// captured.
+///
+/// Obtained from [`SnapshotPriorityQueue::freeze`] in O(1). It is immutable, `Send` and `Sync` when `T`, `C` and `P` are, and
+/// cloning it is O(1) plus a clone of the comparator, so reader threads can
+/// share one (or one each) while the heap moves on.
+pub struct FrozenHeap<T, C, P = HashIndex> {
+    snapshot: Snapshot<T, P>,
+    comparator: C,
+}
+
+impl<T, C: Clone, P> Clone for FrozenHeap<T, C, P> {
+    fn clone(&self) -> Self {
+        Self {
+            snapshot: self.snapshot.clone(),
+            comparator: self.comparator.clone(),
+        }
+    }
+}
+
+impl<T: Debug, C, P> Debug for FrozenHeap<T, C, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FrozenHeap")
+            .field("container", &self.snapshot.container)
+            .field("d", &self.snapshot.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Orders positions of a captured array by the items they hold.
+struct ByItem<'a, T, C> {
+    items: &'a [T],
+    comparator: &'a C,
+}
+
+impl<T, C: PriorityCompare<T>> PriorityCompare<usize> for ByItem<'_, T, C> {
+    fn higher_priority(&self, a: &usize, b: &usize) -> bool {
+        self.comparator
+            .higher_priority(&self.items[*a], &self.items[*b])
+    }
+}
+
+impl<T, C, P> FrozenHeap<T, C, P>
+where
+    C: PriorityCompare<T>,
+    P: PositionIndex<T>,
+{
+    pub(crate) const fn new(snapshot: Snapshot<T, P>, comparator: C) -> Self {
+        Self {
+            snapshot,
+            comparator,
+        }
+    }
+
+    /// Number of items captured.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.snapshot.len()
+    }
+
+    /// Whether the heap was empty when frozen.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snapshot.is_empty()
+    }
+
+    /// Arity of the heap the view was taken from.
+    #[must_use]
+    pub const fn d(&self) -> usize {
+        self.snapshot.d()
+    }
+
+    /// The highest-priority item at freeze time, or `None` if empty.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.snapshot.peek()
+    }
+
+    /// Whether `item` (by identity) was in the heap at freeze time.
+    #[must_use]
+    pub fn contains(&self, item: &T) -> bool {
+        self.snapshot.contains(item)
+    }
+
+    /// The position `item` (by identity) held at freeze time.
+    #[must_use]
+    pub fn get_position(&self, item: &T) -> Option<Position> {
+        self.snapshot.get_position(item)
+    }
+
+    /// The `k` highest-priority items, best first: the `k` smallest under
+    /// a min-heap comparator, the `k` largest under a max-heap one. Fewer
+    /// if fewer were captured. Ties come out in no particular order.
+    ///
+    /// Walks the captured tree best-first with a side heap of candidate
+    /// positions, so only the items ranked and their children are looked at;
+    /// the view itself is not changed.
+    ///
+    /// **Time Complexity**: `O(k · d · log_d(k·d))`
+    #[must_use]
+    pub fn k_smallest(&self, k: usize) -> Vec<&T> {
+        let items = self.snapshot.as_slice();
+        let k = k.min(items.len());
+        let mut ranked = Vec::with_capacity(k);
+        if k == 0 {
+            return ranked;
+        }
+        let by_item = ByItem {
+            items,
+            comparator: &self.comparator,
+        };
+        let Ok(mut candidates) = PriorityQueue::<usize, _, NoOpStats, _>::with_position_index(
+            self.d(),
+            by_item,
+            DenseIndex::new(|&i: &usize| i),
+        ) else {
+            return ranked;
+        };
+        candidates.insert(0);
+        while ranked.len() < k {
+            let Some(i) = candidates.pop() else {
+                break;
+            };
+            ranked.push(&items[i]);
+            let first_child = i * self.d() + 1;
+            let last_child = (first_child + self.d()).min(items.len());
+            for child in first_child..last_child {
+                candidates.insert(child);
+            }
+        }
+        ranked
+    }
+
+    /// Iterates over the captured items in array order (not sorted).
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.snapshot.as_slice().iter()
+    }
+
+    /// The captured backing array, in heap order (not sorted).
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.snapshot.as_slice()
+    }
+
+    /// The view without its comparator.
+    #[must_use]
+    pub const fn snapshot(&self) -> &Snapshot<T, P> {
+        &self.snapshot
+    }
+}
+
+impl<'a, T, C, P> IntoIterator for &'a FrozenHeap<T, C, P>
+where
+    C: PriorityCompare<T>,
+    P: PositionIndex<T>,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A [`PriorityQueue`] whose storage is shared copy-on-write with the
+/// [`Snapshot`]s and [`FrozenHeap`]s it hands out; see the
+/// [module docs](self).
+///
+/// Reads go through [`view`](Self::view), which answers every `Snapshot`
+/// query on the current contents. Mutations go through
+/// [`modify`](Self::modify), which lends out the heap itself, or the
+/// [`insert`](Self::insert) and [`pop`](Self::pop) shortcuts.
+pub struct SnapshotPriorityQueue<T, C, S = NoOpStats, P = HashIndex>
+where
+    T: Eq + Clone,
+{
+    /// The current contents, shared with every live snapshot.
+    shared: Snapshot<T, P>,
+    /// Comparator, stats collector and scan policy. Its array and index
+    /// are empty except while `modify` lends it out.
+    heap: PriorityQueue<T, C, S, P>,
+}
+
+impl<T, C, S, P> SnapshotPriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    P: PositionIndex<T>,
+{
+    /// Moves `heap`'s storage behind shared pointers, without copying it.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    /// let pq = SnapshotPriorityQueue::new(heap);
+    /// assert_eq!(pq.peek(), Some(&1));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn new(mut heap: PriorityQueue<T, C, S, P>) -> Self {
+        let container = std::mem::take(&mut heap.container);
+        let empty = heap.positions.empty();
+        let positions = std::mem::replace(&mut heap.positions, empty);
+        Self {
+            shared: Snapshot::new(Arc::new(container), Arc::new(positions), heap.depth),
+            heap,
+        }
+    }
+
+    /// Returns the heap, copying its storage if a snapshot still shares it.
+    ///
+    /// **Time Complexity**: O(1), or O(n) while a snapshot is alive
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+    ///
+    /// let heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// let mut pq = SnapshotPriorityQueue::new(heap);
+    /// pq.insert(4);
+    /// let mut heap = pq.into_inner();
+    /// assert_eq!(heap.pop(), Some(4));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn into_inner(self) -> PriorityQueue<T, C, S, P> {
+        let Self { shared, mut heap } = self;
+        heap.container = Arc::unwrap_or_clone(shared.container);
+        heap.positions = Arc::unwrap_or_clone(shared.positions);
+        heap
+    }
+
+    /// Returns an O(1) frozen copy of the current contents.
+    ///
+    /// The queue and the [`Snapshot`] share storage until the queue's next
+    /// mutation, which copies it once (O(n)) if the snapshot is still alive;
+    /// later mutations run at full speed again. The snapshot is `Send` and
+    /// `Sync` when `T` and `P` are, so a monitoring thread can inspect a
+    /// consistent view while the owner keeps working.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    /// let mut pq = SnapshotPriorityQueue::new(heap);
+    ///
+    /// let before = pq.snapshot();
+    /// pq.pop();
+    /// assert_eq!(before.peek(), Some(&1));
+    /// assert_eq!(pq.peek(), Some(&3));
+    /// assert_eq!(before.len(), 3);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot<T, P> {
+        self.shared.clone()
+    }
+
+    /// Returns an O(1) [`FrozenHeap`]: a [`snapshot`](Self::snapshot) that
+    /// also carries a clone of the comparator, so readers can rank the
+    /// captured items with [`FrozenHeap::k_smallest`] as well as `peek`,
+    /// `contains` and iterate.
+    ///
+    /// Storage is shared copy-on-write as for a snapshot. The view is
+    /// `Send` and `Sync` when `T`, the comparator and `P` are, so many
+    /// reader threads can inspect a stable generation while the owner
+    /// prepares the next one.
+    ///
+    /// **Time Complexity**: O(1), plus a clone of the comparator
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MaxBy, PriorityQueue, SnapshotPriorityQueue};
+    /// use std::sync::Arc;
+    ///
+    /// let mut heap = PriorityQueue::new(4, MaxBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 8, 3, 9]);
+    /// let mut pq = SnapshotPriorityQueue::new(heap);
+    ///
+    /// let frozen = Arc::new(pq.freeze());
+    /// pq.pop();
+    ///
+    /// let readers: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let frozen = Arc::clone(&frozen);
+    ///         std::thread::spawn(move || frozen.k_smallest(3).into_iter().copied().collect::<Vec<_>>())
+    ///     })
+    ///     .collect();
+    /// for reader in readers {
+    ///     assert_eq!(reader.join().unwrap(), [9, 8, 5]);
+    /// }
+    /// assert_eq!(pq.peek(), Some(&8));
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub fn freeze(&self) -> FrozenHeap<T, C, P>
+    where
+        C: PriorityCompare<T> + Clone,
+    {
+        FrozenHeap::new(self.snapshot(), self.heap.comparator.clone())
+    }
+
+    /// The current contents, for reading without taking a snapshot: `len`,
+    /// `peek`, `contains`, `get_position`, `as_slice`, `iter_with_positions`
+    /// and `cursor_at`.
+    ///
+    /// **Time Complexity**: O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    /// let pq = SnapshotPriorityQueue::new(heap);
+    /// assert!(pq.view().contains(&3));
+    /// assert_eq!(pq.view().as_slice()[0], 1);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    #[must_use]
+    pub const fn view(&self) -> &Snapshot<T, P> {
+        &self.shared
+    }
+
+    /// Number of items in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Whether the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.shared.is_empty()
+    }
+
+    /// The highest-priority item, or `None` if empty.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.shared.peek()
+    }
+
+    /// Runs `f` on the heap and returns its result: the form every
+    /// `PriorityQueue` operation takes on a shared queue. The storage is
+    /// copied first if a snapshot still shares it.
+    ///
+    /// **Time Complexity**: that of `f`, plus O(n) on the first call after
+    /// a snapshot that is still alive
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, SnapshotPriorityQueue};
+    ///
+    /// let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    /// heap.insert_many(vec![5, 1, 3]);
+    /// let mut pq = SnapshotPriorityQueue::new(heap);
+    ///
+    /// let before = pq.snapshot();
+    /// let popped = pq.modify(|heap| heap.pop_many(2));
+    /// assert_eq!(popped, [1, 3]);
+    /// assert_eq!(before.len(), 3);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut PriorityQueue<T, C, S, P>) -> R) -> R {
+        let lent = Lent::new(self);
+        f(&mut lent.queue.heap)
+    }
+}
+
+impl<T, C, S, P> SnapshotPriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    /// Inserts `item`; shorthand for `modify(|heap| heap.insert(item))`.
+    ///
+    /// **Time Complexity**: `O(log_d n)`, plus O(n) on the first mutation
+    /// after a snapshot that is still alive
+    pub fn insert(&mut self, item: T) {
+        self.modify(|heap| heap.insert(item));
+    }
+
+    /// Removes and returns the highest-priority item; shorthand for
+    /// `modify(PriorityQueue::pop)`.
+    ///
+    /// **Time Complexity**: `O(d · log_d n)`, plus O(n) on the first
+    /// mutation after a snapshot that is still alive
+    pub fn pop(&mut self) -> Option<T> {
+        self.modify(PriorityQueue::pop)
+    }
+}
+
+impl<T, C, S, P> From<PriorityQueue<T, C, S, P>> for SnapshotPriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    P: PositionIndex<T>,
+{
+    fn from(heap: PriorityQueue<T, C, S, P>) -> Self {
+        Self::new(heap)
+    }
+}
+
+/// Copies the queue in O(1): the clone shares the storage copy-on-write,
+/// as a snapshot does, and whichever queue is mutated first copies it then.
+/// The comparator and stats collector are cloned.
+impl<T, C, S, P> Clone for SnapshotPriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: Clone,
+    S: Clone,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+// Manual impl: the heap's comparator need not be `Debug`.
+impl<T, C, S, P> Debug for SnapshotPriorityQueue<T, C, S, P>
+where
+    T: Eq + Clone + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SnapshotPriorityQueue")
+            .field("container", &self.shared.container)
+            .field("d", &self.shared.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Moves a queue's storage into its heap for one `modify`, copying it
+/// first if a snapshot shares it, and moves it back when dropped, so a
+/// panicking closure leaves the queue whole.
+struct Lent<'a, T, C, S, P>
+where
+    T: Eq + Clone,
+    P: PositionIndex<T>,
+{
+    queue: &'a mut SnapshotPriorityQueue<T, C, S, P>,
+}
+
+impl<'a, T, C, S, P> Lent<'a, T, C, S, P>
+where
+    T: Eq + Clone,
+    P: PositionIndex<T>,
+{
+    fn new(queue: &'a mut SnapshotPriorityQueue<T, C, S, P>) -> Self {
+        std::mem::swap(
+            &mut queue.heap.container,
+            Arc::make_mut(&mut queue.shared.container),
+        );
+        std::mem::swap(
+            &mut queue.heap.positions,
+            Arc::make_mut(&mut queue.shared.positions),
+        );
+        Self { queue }
+    }
+}
+
+impl<T, C, S, P> Drop for Lent<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    P: PositionIndex<T>,
+{
+    fn drop(&mut self) {
+        // Nothing could share the storage while it was lent, so
+        // `make_mut` copies nothing here.
+        let queue = &mut *self.queue;
+        std::mem::swap(
+            Arc::make_mut(&mut queue.shared.container),
+            &mut queue.heap.container,
+        );
+        std::mem::swap(
+            Arc::make_mut(&mut queue.shared.positions),
+            &mut queue.heap.positions,
+        );
+        queue.shared.depth = queue.heap.depth;
+    }
+}
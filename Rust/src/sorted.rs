@@ -0,0 +1,190 @@
+//! Iteration in priority order.
+//!
+//! [`PriorityQueue::iter`](crate::PriorityQueue::iter) walks the backing
+//! array, which is in heap order, not sorted. To visit items best first,
+//! [`PriorityQueue::into_iter_sorted`](crate::PriorityQueue::into_iter_sorted)
+//! consumes the queue and [`PriorityQueue::drain_sorted`](crate::PriorityQueue::drain_sorted)
+//! empties it in place. Both pop one item per `next()`, so stopping early
+//! costs only the pops made, `O(k · d · log_d n)` for the first `k` items,
+//! rather than a full sort.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue};
+//!
+//! let mut heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+//! heap.insert_many(vec![30, 10, 40, 20]);
+//!
+//! let best_two: Vec<u32> = heap.drain_sorted().take(2).collect();
+//! assert_eq!(best_two, vec![10, 20]);
+//! assert!(heap.is_empty()); // the rest were dropped with the iterator
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Other languages pop in a loop.
+
+use crate::instrumentation::StatsCollector;
+use crate::{PositionIndex, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::iter::FusedIterator;
+
+/// Pops a queue it owns, best first; returned by
+/// [`PriorityQueue::into_iter_sorted`](crate::PriorityQueue::into_iter_sorted).
+pub struct IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    heap: PriorityQueue<T, C, S, P>,
+}
+
+impl<T, C, S, P> IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    pub(crate) const fn new(heap: PriorityQueue<T, C, S, P>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T, C, S, P> Iterator for IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, C, S, P> ExactSizeIterator for IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+}
+
+impl<T, C, S, P> FusedIterator for IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+}
+
+// Manual impl: the heap's comparator need not be `Debug`.
+impl<T, C, S, P> Debug for IntoIterSorted<T, C, S, P>
+where
+    T: Eq + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("IntoIterSorted")
+            .field("remaining", &self.heap.container.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Pops a borrowed queue, best first; returned by
+/// [`PriorityQueue::drain_sorted`](crate::PriorityQueue::drain_sorted).
+/// Items not yet popped when it is dropped are removed without being
+/// sorted, so the queue is always left empty.
+pub struct DrainSorted<'a, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    heap: &'a mut PriorityQueue<T, C, S, P>,
+}
+
+impl<'a, T, C, S, P> DrainSorted<'a, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    pub(crate) fn new(heap: &'a mut PriorityQueue<T, C, S, P>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T, C, S, P> Iterator for DrainSorted<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, C, S, P> ExactSizeIterator for DrainSorted<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+}
+
+impl<T, C, S, P> FusedIterator for DrainSorted<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+}
+
+impl<T, C, S, P> Drop for DrainSorted<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    /// Drops the items not popped in O(n), without sorting them.
+    fn drop(&mut self) {
+        // `None` keeps the arity, so this cannot fail.
+        let _ = self.heap.clear(None);
+    }
+}
+
+// Manual impl: the heap's comparator need not be `Debug`.
+impl<T, C, S, P> Debug for DrainSorted<'_, T, C, S, P>
+where
+    T: Eq + Clone,
+    C: PriorityCompare<T>,
+    S: StatsCollector,
+    P: PositionIndex<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DrainSorted")
+            .field("remaining", &self.heap.len())
+            .finish_non_exhaustive()
+    }
+}
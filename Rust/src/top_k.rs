@@ -0,0 +1,192 @@
+//! Streaming top-k frequent items in bounded memory.
+//!
+//! [`TopK`] implements the Space-Saving algorithm (Metwally, Agrawal and El
+//! Abbadi, 2005): it tracks at most `k` items in a d-ary min-heap ordered by
+//! count. An observed item already tracked has its count raised in place
+//! with `adjust_priority`; an untracked one takes the place of the least
+//! frequent, inheriting its count plus one, with a single `push_pop`.
+//!
+//! Counts are therefore estimates, but with known error: after `n`
+//! observations, every item seen more than `n / k` times is tracked, and
+//! each reported count overstates the true one by at most the reported
+//! `error`, itself at most `n / k`. Track more items than you need to rank
+//! to tighten both.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::TopK;
+//! use std::num::NonZeroUsize;
+//!
+//! let mut top = TopK::new(4, NonZeroUsize::new(3).unwrap()).unwrap();
+//! for word in "the cat and the dog and the bird".split(' ') {
+//!     top.observe(word);
+//! }
+//!
+//! let ranked = top.top_k();
+//! assert_eq!((ranked[0].item, ranked[0].count, ranked[0].error), ("the", 3, 0));
+//! // "bird" came last and took over the evicted "and" counter: seen once,
+//! // estimated at 3, of which only 1 is guaranteed
+//! assert_eq!((ranked[1].item, ranked[1].count, ranked[1].guaranteed()), ("bird", 3, 1));
+//! assert_eq!(top.observed(), 8);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None yet. The algorithm only needs `insert`, a priority update and a
+//!   replace-the-front, which every implementation can compose.
+
+use crate::{Error, NoOpStats, PriorityCompare, PriorityQueue};
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+
+/// An item's estimated frequency, as reported by [`TopK::top_k`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyEstimate<T> {
+    /// The item.
+    pub item: T,
+    /// Estimated occurrences: never below the true count.
+    pub count: u64,
+    /// Most by which `count` may overstate the true count.
+    pub error: u64,
+}
+
+impl<T> FrequencyEstimate<T> {
+    /// Occurrences the item is certain to have had: `count - error`.
+    #[must_use]
+    pub const fn guaranteed(&self) -> u64 {
+        self.count - self.error
+    }
+}
+
+/// A tracked item; identity is the item alone, so a zero-count copy finds
+/// the tracked entry.
+#[derive(Debug, Clone)]
+struct Counter<T> {
+    item: T,
+    count: u64,
+    error: u64,
+}
+
+impl<T: PartialEq> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item
+    }
+}
+
+impl<T: Eq> Eq for Counter<T> {}
+
+impl<T: Hash> Hash for Counter<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.item.hash(state);
+    }
+}
+
+/// Least frequent first, so the front is the one to evict.
+#[derive(Debug, Clone, Copy)]
+struct FewestFirst;
+
+impl<T> PriorityCompare<Counter<T>> for FewestFirst {
+    fn higher_priority(&self, a: &Counter<T>, b: &Counter<T>) -> bool {
+        a.count < b.count
+    }
+}
+
+/// Approximate top-k frequent items of a stream, in memory bounded by `k`;
+/// see the [module docs](self).
+#[derive(Debug)]
+pub struct TopK<T>
+where
+    T: Eq + Hash + Clone,
+{
+    heap: PriorityQueue<Counter<T>, FewestFirst, NoOpStats>,
+    k: NonZeroUsize,
+    observed: u64,
+}
+
+impl<T> TopK<T>
+where
+    T: Eq + Hash + Clone,
+{
+    /// Tracks up to `k` items in a heap of arity `d`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArity` if `d == 0`.
+    pub fn new(d: usize, k: NonZeroUsize) -> Result<Self, Error> {
+        Ok(Self {
+            heap: PriorityQueue::new(d, FewestFirst)?,
+            k,
+            observed: 0,
+        })
+    }
+
+    /// Counts one occurrence of `item`.
+    ///
+    /// **Time Complexity**: `O(d · log_d k)`
+    pub fn observe(&mut self, item: T) {
+        self.observed += 1;
+        let key = Counter {
+            item,
+            count: 0,
+            error: 0,
+        };
+        if self.heap.adjust_priority(&key, |c| c.count += 1).is_ok() {
+            return;
+        }
+        if self.heap.len() < self.k.get() {
+            self.heap.insert(Counter { count: 1, ..key });
+        } else {
+            // The least frequent item is evicted; the newcomer may have
+            // been it, so it inherits that count as its error.
+            let floor = self.heap.front().count;
+            self.heap.push_pop(Counter {
+                count: floor + 1,
+                error: floor,
+                ..key
+            });
+        }
+    }
+
+    /// The tracked items, most frequent first (ties: least error first).
+    ///
+    /// **Time Complexity**: O(k log k)
+    #[must_use]
+    pub fn top_k(&self) -> Vec<FrequencyEstimate<T>> {
+        let mut ranked: Vec<FrequencyEstimate<T>> = self
+            .heap
+            .iter_with_positions()
+            .map(|(_, c)| FrequencyEstimate {
+                item: c.item.clone(),
+                count: c.count,
+                error: c.error,
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.count.cmp(&a.count).then(a.error.cmp(&b.error)));
+        ranked
+    }
+
+    /// Occurrences observed so far, tracked or not.
+    #[must_use]
+    pub const fn observed(&self) -> u64 {
+        self.observed
+    }
+
+    /// Number of items tracked: `k` once that many distinct items were seen.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether nothing has been observed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Most items tracked at once.
+    #[must_use]
+    pub const fn k(&self) -> NonZeroUsize {
+        self.k
+    }
+}
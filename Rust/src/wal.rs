@@ -0,0 +1,738 @@
+//! Write-ahead log persistence: queues that survive a crash.
+//!
+//! A [`DurablePriorityQueue`] appends every mutating operation to a log file,
+//! and syncs it to disk, *before* applying the operation to its in-memory
+//! heap. [`DurablePriorityQueue::recover`] replays the log, so after a crash
+//! the queue comes back holding exactly the operations that returned `Ok`:
+//! a job system that acknowledged an `insert` never loses the job.
+//!
+//! Replaying the same operations in the same order rebuilds the same array
+//! layout, so even items of equal priority pop in the same order after a
+//! restart. Every [`DEFAULT_COMPACT_EVERY`] records (see
+//! [`DurablePriorityQueue::set_compact_every`]) the log is compacted: the
+//! current contents are written to a fresh log, in array order, which then
+//! replaces the old one atomically, so the log stays proportional to the
+//! queue rather than to its history.
+//!
+//! Items are written through the [`WalItem`] trait, implemented here for
+//! integers, `String`, `Vec<u8>` and pairs; implement it for your own item
+//! types.
+//!
+//! # Log format
+//!
+//! The file starts with [`WAL_MAGIC`], followed by records of the form
+//! `tag: u8, length: u32, payload, checksum: u32` (little-endian; the
+//! checksum is FNV-1a over tag, length and payload). A record cut short by a
+//! crash, or the last record failing its checksum, is a torn write: recovery
+//! drops it, since its operation never returned `Ok`. A damaged record
+//! anywhere else is [`WalError::Corrupt`].
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{DurablePriorityQueue, MinBy};
+//!
+//! let path = std::env::temp_dir().join("d_ary_heap_wal_module_doctest.log");
+//! # let _ = std::fs::remove_file(&path);
+//! let mut jobs = DurablePriorityQueue::recover(&path, 4, MinBy(|x: &u32| *x)).unwrap();
+//! jobs.insert(30).unwrap();
+//! jobs.insert(10).unwrap();
+//! jobs.insert(20).unwrap();
+//! assert_eq!(jobs.pop().unwrap(), Some(10));
+//! drop(jobs); // crash
+//!
+//! let mut jobs = DurablePriorityQueue::recover(&path, 4, MinBy(|x: &u32| *x)).unwrap();
+//! assert_eq!(jobs.len(), 2);
+//! assert_eq!(jobs.pop().unwrap(), Some(20));
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Persistence is Rust-only; other languages can export `to_array()`.
+
+use crate::{Error, PriorityCompare, PriorityQueue};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hash;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+
+/// First bytes of every log file: a magic string ending in the format version.
+pub const WAL_MAGIC: &[u8; 8] = b"DHEAPWL1";
+
+/// Records appended between automatic compactions, unless changed with
+/// [`DurablePriorityQueue::set_compact_every`].
+pub const DEFAULT_COMPACT_EVERY: u64 = 10_000;
+
+/// Tag, length and checksum bytes around each record's payload.
+const FRAME_BYTES: usize = 1 + 4 + 4;
+
+/// An item that can be written to, and read back from, a write-ahead log.
+///
+/// `decode` must invert `encode`: an item read back must be equal (same
+/// identity, same priority) to the one written.
+///
+/// # Examples
+///
+/// ```rust
+/// use d_ary_heap::WalItem;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Job {
+///     id: u32,
+///     cost: u32,
+/// }
+///
+/// impl WalItem for Job {
+///     fn encode(&self, out: &mut Vec<u8>) {
+///         (self.id, self.cost).encode(out);
+///     }
+///
+///     fn decode(bytes: &[u8]) -> Option<Self> {
+///         let (id, cost) = <(u32, u32)>::decode(bytes)?;
+///         Some(Job { id, cost })
+///     }
+/// }
+///
+/// let mut bytes = Vec::new();
+/// Job { id: 7, cost: 3 }.encode(&mut bytes);
+/// assert_eq!(Job::decode(&bytes), Some(Job { id: 7, cost: 3 }));
+/// ```
+///
+/// **Cross-language equivalents**: None (Rust-only).
+pub trait WalItem: Sized {
+    /// Appends the item's bytes to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Reads an item from exactly the bytes `encode` wrote, or `None` if they
+    /// are not a valid item.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! int_wal_item {
+    ($($int:ty),*) => {$(
+        impl WalItem for $int {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(bytes: &[u8]) -> Option<Self> {
+                Some(Self::from_le_bytes(bytes.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+int_wal_item!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl WalItem for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        // BORROW: the log buffer is reused, so the item owns a copy.
+        std::str::from_utf8(bytes).ok().map(str::to_owned)
+    }
+}
+
+impl WalItem for Vec<u8> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+}
+
+impl<A: WalItem, B: WalItem> WalItem for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        encode_prefixed(&self.0, out);
+        self.1.encode(out);
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let (first, rest) = split_prefixed(bytes)?;
+        Some((A::decode(first)?, B::decode(rest)?))
+    }
+}
+
+/// Appends `item` preceded by its encoded length as a little-endian `u32`.
+///
+/// # Panics
+///
+/// Panics if `item` encodes to more than `u32::MAX` bytes.
+fn encode_prefixed(item: &impl WalItem, out: &mut Vec<u8>) {
+    let start = out.len();
+    out.extend_from_slice(&[0; 4]);
+    item.encode(out);
+    let len = u32::try_from(out.len() - start - 4).expect("log items are under 4 GiB");
+    // INDEX: the four placeholder bytes were pushed above.
+    out[start..start + 4].copy_from_slice(&len.to_le_bytes());
+}
+
+/// Splits a length-prefixed item off the front of `bytes`.
+fn split_prefixed(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (len, rest) = bytes.split_first_chunk::<4>()?;
+    // CAST: u32 → usize, lossless on the 32- and 64-bit targets the crate supports.
+    let len = u32::from_le_bytes(*len) as usize;
+    (len <= rest.len()).then(|| rest.split_at(len))
+}
+
+/// Errors from a [`DurablePriorityQueue`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WalError {
+    /// Reading, writing or syncing the log failed. An operation whose record
+    /// could not be written was not applied.
+    Io(io::Error),
+    /// The heap rejected the operation; nothing was logged.
+    Heap(Error),
+    /// The file is not a queue log, or a record before the last one is
+    /// damaged or cannot be replayed; `offset` is its byte offset.
+    Corrupt {
+        /// Byte offset of the damaged record (0 for a bad header).
+        offset: u64,
+    },
+}
+
+impl Display for WalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            WalError::Io(err) => write!(f, "Write-ahead log I/O failed: {err}"),
+            WalError::Heap(err) => write!(f, "{err}"),
+            WalError::Corrupt { offset } => {
+                write!(f, "Write-ahead log corrupt at byte {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WalError::Io(err) => Some(err),
+            WalError::Heap(err) => Some(err),
+            WalError::Corrupt { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for WalError {
+    fn from(err: io::Error) -> Self {
+        WalError::Io(err)
+    }
+}
+
+impl From<Error> for WalError {
+    fn from(err: Error) -> Self {
+        WalError::Heap(err)
+    }
+}
+
+/// Logged operations. Each replays through the heap method of the same name,
+/// so a replay repeats the original sifts exactly.
+// EXHAUSTIVE: private; a new tag is a new log format version.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Insert = 1,
+    InsertMany = 2,
+    Pop = 3,
+    Remove = 4,
+    IncreasePriority = 5,
+    DecreasePriority = 6,
+    UpdatePriority = 7,
+    Clear = 8,
+}
+
+impl Op {
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(Op::Insert),
+            2 => Some(Op::InsertMany),
+            3 => Some(Op::Pop),
+            4 => Some(Op::Remove),
+            5 => Some(Op::IncreasePriority),
+            6 => Some(Op::DecreasePriority),
+            7 => Some(Op::UpdatePriority),
+            8 => Some(Op::Clear),
+            _ => None,
+        }
+    }
+}
+
+/// FNV-1a, 32-bit: enough to tell a torn or damaged record from a whole one.
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Appends one framed record to `out`, its payload written by `payload`.
+fn frame(out: &mut Vec<u8>, op: Op, payload: impl FnOnce(&mut Vec<u8>)) -> io::Result<()> {
+    let start = out.len();
+    // CAST: fieldless enum → its u8 discriminant.
+    out.push(op as u8);
+    out.extend_from_slice(&[0; 4]);
+    payload(out);
+    let len = u32::try_from(out.len() - start - 5)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "log record over 4 GiB"))?;
+    // INDEX: the tag and four placeholder bytes were pushed above.
+    out[start + 1..start + 5].copy_from_slice(&len.to_le_bytes());
+    // INDEX: `start` is within `out`, which only grew since.
+    let sum = checksum(&out[start..]);
+    out.extend_from_slice(&sum.to_le_bytes());
+    Ok(())
+}
+
+/// A priority queue whose operations are logged to a file before they are
+/// applied, and replayed by [`recover`](Self::recover); see the
+/// [module docs](self).
+///
+/// Read access goes through [`heap`](Self::heap); mutations go through the
+/// methods here, each of which writes and syncs one log record.
+#[derive(Debug)]
+pub struct DurablePriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone,
+{
+    heap: PriorityQueue<T, C>,
+    /// The log, positioned at its end.
+    file: File,
+    path: PathBuf,
+    /// Bytes of intact log; a failed append is truncated back to this length.
+    log_len: u64,
+    /// Records in the log since it was last compacted.
+    records: u64,
+    /// Compact before the next append once `records` reaches this.
+    compact_every: Option<NonZeroU64>,
+}
+
+impl<T, C> DurablePriorityQueue<T, C>
+where
+    T: Eq + Hash + Clone + WalItem,
+    C: PriorityCompare<T>,
+{
+    /// Opens the log at `path` and replays it into a heap of arity `d`
+    /// ordered by `comparator`, or creates an empty log if there is none.
+    ///
+    /// A torn final record, left by a crash during an append, is dropped and
+    /// cut from the file.
+    ///
+    /// **Time Complexity**: `O(r log_d n)` for `r` logged records
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Heap`] with [`Error::InvalidArity`] when `d == 0`.
+    /// Returns [`WalError::Corrupt`] if the file is not a queue log or a
+    /// record before the last is damaged or cannot be replayed.
+    /// Returns [`WalError::Io`] on a failure to open, read or write the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{DurablePriorityQueue, MaxBy};
+    ///
+    /// let path = std::env::temp_dir().join("d_ary_heap_wal_recover_doctest.log");
+    /// # let _ = std::fs::remove_file(&path);
+    /// let mut q = DurablePriorityQueue::recover(&path, 2, MaxBy(|x: &i64| *x)).unwrap();
+    /// q.insert_many(vec![3, 9, 4]).unwrap();
+    /// drop(q);
+    ///
+    /// let q = DurablePriorityQueue::recover(&path, 2, MaxBy(|x: &i64| *x)).unwrap();
+    /// assert_eq!(q.peek(), Some(&9));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn recover(path: impl AsRef<Path>, d: usize, comparator: C) -> Result<Self, WalError> {
+        let path = path.as_ref().to_path_buf();
+        let heap = PriorityQueue::new(d, comparator)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        let mut queue = Self {
+            heap,
+            file,
+            path,
+            log_len: 0,
+            records: 0,
+            compact_every: NonZeroU64::new(DEFAULT_COMPACT_EVERY),
+        };
+        let mut bytes = Vec::new();
+        queue.file.read_to_end(&mut bytes)?;
+        if bytes.is_empty() {
+            queue.file.write_all(WAL_MAGIC)?;
+            queue.file.sync_data()?;
+            queue.log_len = WAL_MAGIC.len() as u64;
+            return Ok(queue);
+        }
+        if !bytes.starts_with(WAL_MAGIC) {
+            return Err(WalError::Corrupt { offset: 0 });
+        }
+        let intact = queue.replay(&bytes)?;
+        // CAST: usize → u64, lossless.
+        queue.log_len = intact as u64;
+        if intact < bytes.len() {
+            queue.file.set_len(queue.log_len)?;
+            queue.file.sync_data()?;
+        }
+        queue.file.seek(SeekFrom::Start(queue.log_len))?;
+        Ok(queue)
+    }
+
+    /// Applies the records of `bytes` after the header, returning the length
+    /// of the intact prefix (everything but a torn final record).
+    fn replay(&mut self, bytes: &[u8]) -> Result<usize, WalError> {
+        let mut offset = WAL_MAGIC.len();
+        while offset < bytes.len() {
+            // CAST: usize → u64, lossless.
+            let corrupt = WalError::Corrupt {
+                offset: offset as u64,
+            };
+            // INDEX: `offset < bytes.len()` by the loop condition.
+            let Some((head, _)) = bytes[offset..].split_first_chunk::<5>() else {
+                break;
+            };
+            let [tag, len @ ..] = *head;
+            // CAST: u32 → usize, lossless on the targets the crate supports.
+            let end = offset + FRAME_BYTES + u32::from_le_bytes(len) as usize;
+            if end > bytes.len() {
+                break;
+            }
+            // INDEX: `end <= bytes.len()` was checked above.
+            let (body, sum) = bytes[offset..end].split_at(end - offset - 4);
+            if sum != checksum(body).to_le_bytes() {
+                if end == bytes.len() {
+                    break;
+                }
+                return Err(corrupt);
+            }
+            let Some(op) = Op::from_tag(tag) else {
+                return Err(corrupt);
+            };
+            // INDEX: the body holds the tag and length, then the payload.
+            if !self.apply(op, &body[5..]) {
+                return Err(corrupt);
+            }
+            self.records += 1;
+            offset = end;
+        }
+        Ok(offset)
+    }
+
+    /// Replays one record, returning whether it applied cleanly.
+    fn apply(&mut self, op: Op, payload: &[u8]) -> bool {
+        if op == Op::Clear {
+            return self.heap.clear(None).is_ok();
+        }
+        if op == Op::InsertMany {
+            let mut items = Vec::new();
+            let mut rest = payload;
+            while !rest.is_empty() {
+                let Some((item, tail)) = split_prefixed(rest) else {
+                    return false;
+                };
+                let Some(item) = T::decode(item) else {
+                    return false;
+                };
+                items.push(item);
+                rest = tail;
+            }
+            self.heap.insert_many(items);
+            return true;
+        }
+        let Some(item) = T::decode(payload) else {
+            return false;
+        };
+        match op {
+            Op::Insert => {
+                self.heap.insert(item);
+                true
+            }
+            Op::Pop => self.heap.pop().is_some_and(|popped| popped == item),
+            Op::Remove => self.heap.remove(&item).is_some(),
+            Op::IncreasePriority => self.heap.increase_priority(&item).is_ok(),
+            Op::DecreasePriority => self.heap.decrease_priority(&item).is_ok(),
+            Op::UpdatePriority => self.heap.update_priority(&item).is_ok(),
+            // EXPLICIT: handled before decoding, as they carry no single item.
+            Op::InsertMany | Op::Clear => false,
+        }
+    }
+
+    /// Writes and syncs one record, compacting the log first if it is due.
+    /// Nothing is applied here: callers mutate the heap only once this
+    /// returns `Ok`.
+    fn log(&mut self, op: Op, payload: impl FnOnce(&mut Vec<u8>)) -> Result<(), WalError> {
+        if self
+            .compact_every
+            .is_some_and(|every| self.records >= every.get())
+        {
+            self.compact()?;
+        }
+        let mut record = Vec::new();
+        frame(&mut record, op, payload)?;
+        if let Err(err) = self
+            .file
+            .write_all(&record)
+            .and_then(|()| self.file.sync_data())
+        {
+            // Best effort: cut a partial record so later appends stay readable.
+            // If this fails too, recovery still drops it as a torn write.
+            let _ = self.file.set_len(self.log_len);
+            let _ = self.file.seek(SeekFrom::Start(self.log_len));
+            return Err(err.into());
+        }
+        // CAST: usize → u64, lossless.
+        self.log_len += record.len() as u64;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// The in-memory heap, for read access (`peek`, `contains`,
+    /// `get_position`, `to_array`, ...).
+    #[must_use]
+    pub const fn heap(&self) -> &PriorityQueue<T, C> {
+        &self.heap
+    }
+
+    /// The log file's path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of items in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The highest-priority item, or `None` if the queue is empty.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Logs, then inserts, `item`. As with [`PriorityQueue::insert`], the
+    /// queue must not already hold an item of the same identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the record cannot be written; the item is
+    /// then not inserted.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn insert(&mut self, item: T) -> Result<(), WalError> {
+        self.log(Op::Insert, |out| item.encode(out))?;
+        self.heap.insert(item);
+        Ok(())
+    }
+
+    /// Logs, then inserts, all of `items` with one record and one sync.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the record cannot be written; no item is
+    /// then inserted.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn insert_many(&mut self, items: impl IntoIterator<Item = T>) -> Result<(), WalError> {
+        let items: Vec<T> = items.into_iter().collect();
+        self.log(Op::InsertMany, |out| {
+            for item in &items {
+                encode_prefixed(item, out);
+            }
+        })?;
+        self.heap.insert_many(items);
+        Ok(())
+    }
+
+    /// Logs, then removes, the highest-priority item. An empty queue logs
+    /// nothing and returns `Ok(None)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the record cannot be written; the item then
+    /// stays queued.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop(&mut self) -> Result<Option<T>, WalError> {
+        let Some(top) = self.heap.peek() else {
+            return Ok(None);
+        };
+        let top = top.clone();
+        self.log(Op::Pop, |out| top.encode(out))?;
+        Ok(self.heap.pop())
+    }
+
+    /// Logs, then removes, the item with `item`'s identity, returning it.
+    /// An absent item logs nothing and returns `Ok(None)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the record cannot be written; the item then
+    /// stays queued.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn remove(&mut self, item: &T) -> Result<Option<T>, WalError> {
+        if !self.heap.contains(item) {
+            return Ok(None);
+        }
+        self.log(Op::Remove, |out| item.encode(out))?;
+        Ok(self.heap.remove(item))
+    }
+
+    /// Logs, then applies, [`PriorityQueue::increase_priority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Heap`] with [`Error::ItemNotFound`] if the item is
+    /// not in the queue; nothing is logged.
+    /// Returns [`WalError::Io`] if the record cannot be written; the priority
+    /// is then not changed.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn increase_priority(&mut self, updated_item: &T) -> Result<(), WalError> {
+        self.log_update(Op::IncreasePriority, updated_item)?;
+        Ok(self.heap.increase_priority(updated_item)?)
+    }
+
+    /// Logs, then applies, [`PriorityQueue::decrease_priority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Heap`] with [`Error::ItemNotFound`] if the item is
+    /// not in the queue; nothing is logged.
+    /// Returns [`WalError::Io`] if the record cannot be written; the priority
+    /// is then not changed.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn decrease_priority(&mut self, updated_item: &T) -> Result<(), WalError> {
+        self.log_update(Op::DecreasePriority, updated_item)?;
+        Ok(self.heap.decrease_priority(updated_item)?)
+    }
+
+    /// Logs, then applies, [`PriorityQueue::update_priority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Heap`] with [`Error::ItemNotFound`] if the item is
+    /// not in the queue; nothing is logged.
+    /// Returns [`WalError::Io`] if the record cannot be written; the priority
+    /// is then not changed.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn update_priority(&mut self, updated_item: &T) -> Result<(), WalError> {
+        self.log_update(Op::UpdatePriority, updated_item)?;
+        Ok(self.heap.update_priority(updated_item)?)
+    }
+
+    fn log_update(&mut self, op: Op, updated_item: &T) -> Result<(), WalError> {
+        if !self.heap.contains(updated_item) {
+            return Err(WalError::Heap(Error::ItemNotFound));
+        }
+        self.log(op, |out| updated_item.encode(out))
+    }
+
+    /// Logs, then removes, every item. The arity is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the record cannot be written; the queue
+    /// then keeps its items.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn clear(&mut self) -> Result<(), WalError> {
+        self.log(Op::Clear, |_| {})?;
+        Ok(self.heap.clear(None)?)
+    }
+
+    /// Sets how many records may accumulate before the log is compacted,
+    /// or `None` to compact only on [`compact`](Self::compact). The check
+    /// runs before each append.
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn set_compact_every(&mut self, records: Option<NonZeroU64>) {
+        self.compact_every = records;
+    }
+
+    /// Rewrites the log as one insert per item, in array order, so replaying
+    /// it rebuilds the current heap exactly. The new log is written beside
+    /// the old one, synced, and renamed over it, so a crash at any point
+    /// leaves one complete log.
+    ///
+    /// **Time Complexity**: O(n) plus the I/O
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WalError::Io`] if the new log cannot be written or renamed;
+    /// the old log then stays in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{DurablePriorityQueue, MinBy};
+    ///
+    /// let path = std::env::temp_dir().join("d_ary_heap_wal_compact_doctest.log");
+    /// # let _ = std::fs::remove_file(&path);
+    /// let mut q = DurablePriorityQueue::recover(&path, 2, MinBy(|x: &u64| *x)).unwrap();
+    /// for x in 0..100 {
+    ///     q.insert(x).unwrap();
+    ///     q.pop().unwrap();
+    /// }
+    /// q.insert(7).unwrap();
+    /// let before = std::fs::metadata(&path).unwrap().len();
+    /// q.compact().unwrap();
+    /// assert!(std::fs::metadata(&path).unwrap().len() < before);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn compact(&mut self) -> Result<(), WalError> {
+        let mut staged = self.path.clone().into_os_string();
+        staged.push(".compact");
+        let staged = PathBuf::from(staged);
+        let mut bytes = WAL_MAGIC.to_vec();
+        for (_, item) in self.heap.iter_with_positions() {
+            frame(&mut bytes, Op::Insert, |out| item.encode(out))?;
+        }
+        let mut writer = BufWriter::new(File::create(&staged)?);
+        writer.write_all(&bytes)?;
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&staged, &self.path)?;
+        sync_parent(&self.path);
+        let mut file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        // CAST: usize → u64, lossless.
+        self.log_len = bytes.len() as u64;
+        file.seek(SeekFrom::Start(self.log_len))?;
+        self.file = file;
+        self.records = 0;
+        Ok(())
+    }
+}
+
+/// Syncs the directory holding `path`, so a rename into it is durable.
+/// Best effort: not every platform can open a directory as a file.
+fn sync_parent(path: &Path) {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+}
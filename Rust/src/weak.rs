@@ -0,0 +1,199 @@
+//! Weak-entry mode: queued items that expire when their owner drops them.
+//!
+//! A [`WeakEntry`] holds a [`Weak`] reference to a caller-owned `Rc<T>`
+//! together with the priority it was queued at. The caller keeps the strong
+//! `Rc`; dropping it "forgets" the queued item without a call into the heap.
+//! [`PriorityQueue::pop_live`] skips (and discards) entries whose item has been
+//! dropped, and [`PriorityQueue::purge_dead`] removes them eagerly.
+//!
+//! Entries compare and hash by allocation, not by value, so two `Rc`s holding
+//! equal values are still distinct entries, and `increase_priority` /
+//! `decrease_priority` find an entry from any `WeakEntry::new(&rc, _)` built on
+//! the same `Rc`.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use d_ary_heap::{MinBy, PriorityQueue, WeakEntry};
+//! use std::rc::Rc;
+//!
+//! let mut pq = PriorityQueue::new(2, MinBy(|e: &WeakEntry<&str, u32>| *e.priority())).unwrap();
+//! let urgent = Rc::new("urgent");
+//! let routine = Rc::new("routine");
+//! pq.insert(WeakEntry::new(&urgent, 1));
+//! pq.insert(WeakEntry::new(&routine, 5));
+//!
+//! drop(urgent); // cancelled by its owner
+//! assert_eq!(pq.pop_live().as_deref(), Some(&"routine"));
+//! assert_eq!(pq.pop_live(), None);
+//! ```
+//!
+//! # Cross-language equivalents
+//!
+//! - None. Go, TypeScript and Zig have no weak-reference ownership to model;
+//!   C++ callers can queue `std::weak_ptr` with a custom hash.
+
+use crate::instrumentation::{OperationType, StatsCollector};
+use crate::{HashIndex, Position, PositionIndex, PriorityCompare, PriorityQueue};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
+use std::rc::{Rc, Weak};
+
+/// A queued weak reference to a caller-owned item, plus its priority.
+pub struct WeakEntry<T, P> {
+    item: Weak<T>,
+    priority: P,
+}
+
+impl<T, P> WeakEntry<T, P> {
+    /// Creates an entry for `item` at `priority`. The entry does not keep
+    /// `item` alive.
+    #[must_use]
+    pub fn new(item: &Rc<T>, priority: P) -> Self {
+        Self {
+            item: Rc::downgrade(item),
+            priority,
+        }
+    }
+
+    /// The priority this entry was queued (or last updated) at.
+    #[must_use]
+    pub const fn priority(&self) -> &P {
+        &self.priority
+    }
+
+    /// The item, if its owner still holds it.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Rc<T>> {
+        self.item.upgrade()
+    }
+
+    /// Whether the item is still alive.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.item.strong_count() > 0
+    }
+}
+
+// Manual impls: identity is the allocation, so none of these may depend on
+// `T` (which may not even be `Clone` / `Eq` / `Hash`).
+impl<T, P: Clone> Clone for WeakEntry<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            item: Weak::clone(&self.item),
+            priority: self.priority.clone(),
+        }
+    }
+}
+
+impl<T, P> PartialEq for WeakEntry<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        Weak::ptr_eq(&self.item, &other.item)
+    }
+}
+
+impl<T, P> Eq for WeakEntry<T, P> {}
+
+impl<T, P> Hash for WeakEntry<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The allocation outlives the last strong reference for as long as
+        // this `Weak` exists, so the address is a stable identity.
+        std::ptr::hash(self.item.as_ptr(), state);
+    }
+}
+
+impl<T, P: Debug> Debug for WeakEntry<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("WeakEntry")
+            .field("item", &self.item)
+            .field("priority", &self.priority)
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+/// Convenience alias for a heap of [`WeakEntry`] items.
+pub type WeakPriorityQueue<T, P, C> = PriorityQueue<WeakEntry<T, P>, C>;
+
+impl<T, P, C, S> PriorityQueue<WeakEntry<T, P>, C, S>
+where
+    P: Clone,
+    C: PriorityCompare<WeakEntry<T, P>>,
+    S: StatsCollector,
+{
+    /// Removes and returns the highest-priority item that is still alive,
+    /// discarding any expired entries ranked above it.
+    ///
+    /// `len()` counts expired entries until they are popped or purged.
+    ///
+    /// **Time Complexity**: `O(k · d · log_d n)` for `k` entries removed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, WeakEntry};
+    /// use std::rc::Rc;
+    ///
+    /// let mut pq = PriorityQueue::new(4, MinBy(|e: &WeakEntry<u32, u32>| *e.priority())).unwrap();
+    /// let jobs: Vec<Rc<u32>> = (0..5).map(Rc::new).collect();
+    /// for job in &jobs {
+    ///     pq.insert(WeakEntry::new(job, **job));
+    /// }
+    ///
+    /// let mut jobs = jobs;
+    /// jobs.remove(0); // job 0 is dropped
+    /// assert_eq!(pq.pop_live().map(|j| *j), Some(1));
+    /// assert_eq!(pq.len(), 3);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn pop_live(&mut self) -> Option<Rc<T>> {
+        // EXPLICIT: each iteration is a full bracketed pop(); expired entries
+        // are simply dropped.
+        loop {
+            if let Some(item) = self.pop()?.upgrade() {
+                return Some(item);
+            }
+        }
+    }
+
+    /// Removes every expired entry and restores the heap invariant, returning
+    /// how many were removed. Comparisons made while rebuilding are
+    /// attributed to `OperationType::Pop`.
+    ///
+    /// **Time Complexity**: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use d_ary_heap::{MinBy, PriorityQueue, WeakEntry};
+    /// use std::rc::Rc;
+    ///
+    /// let mut pq = PriorityQueue::new(2, MinBy(|e: &WeakEntry<u32, u32>| *e.priority())).unwrap();
+    /// let keep = Rc::new(1);
+    /// pq.insert(WeakEntry::new(&keep, 10));
+    /// pq.insert(WeakEntry::new(&Rc::new(2), 5)); // dropped immediately
+    ///
+    /// assert_eq!(pq.purge_dead(), 1);
+    /// assert_eq!(pq.len(), 1);
+    /// assert_eq!(*pq.front().priority(), 10);
+    /// ```
+    ///
+    /// **Cross-language equivalents**: None (Rust-only).
+    pub fn purge_dead(&mut self) -> usize {
+        self.bracket(OperationType::Pop, |s| {
+            let before = s.container.len();
+            s.container.retain(WeakEntry::is_alive);
+            let removed = before - s.container.len();
+            if removed > 0 {
+                let mut positions = HashIndex::with_capacity(s.container.len());
+                for (i, entry) in s.container.iter().enumerate() {
+                    positions.insert(entry, Position::new(i));
+                }
+                s.positions = positions;
+                s.heapify();
+            }
+            removed
+        })
+    }
+}
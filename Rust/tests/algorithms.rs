@@ -0,0 +1,213 @@
+//! Integration tests for best-first search (`algorithms`).
+
+use d_ary_heap::algorithms::{astar, best_first, greedy_best_first, uniform_cost};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Deterministic xorshift generator, so failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// Adjacency lists of `nodes` nodes and `edges` random edges of cost 1..=20.
+fn random_graph(seed: u64, nodes: usize, edges: usize) -> Vec<Vec<(usize, u32)>> {
+    let mut rng = Rng(seed);
+    let mut graph = vec![Vec::new(); nodes];
+    for _ in 0..edges {
+        let a = usize::try_from(rng.below(nodes as u64)).unwrap();
+        let b = usize::try_from(rng.below(nodes as u64)).unwrap();
+        graph[a].push((b, u32::try_from(rng.below(20)).unwrap() + 1));
+    }
+    graph
+}
+
+/// Reference distances by lazy-deletion Dijkstra on `std`'s `BinaryHeap`.
+fn reference_distances(graph: &[Vec<(usize, u32)>], start: usize) -> HashMap<usize, u32> {
+    let mut dist = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+    while let Some(Reverse((d, n))) = heap.pop() {
+        if dist.contains_key(&n) {
+            continue;
+        }
+        dist.insert(n, d);
+        for &(m, w) in &graph[n] {
+            heap.push(Reverse((d + w, m)));
+        }
+    }
+    dist
+}
+
+/// Checks that `path` starts at `start`, follows edges of `graph` and costs `cost`.
+fn check_path(graph: &[Vec<(usize, u32)>], start: usize, cost: u32, path: &[usize]) {
+    assert_eq!(path[0], start);
+    let mut total = 0;
+    for step in path.windows(2) {
+        total += graph[step[0]]
+            .iter()
+            .filter(|&&(m, _)| m == step[1])
+            .map(|&(_, w)| w)
+            .min()
+            .expect("path follows an edge");
+    }
+    assert!(total <= cost, "path costs {total}, reported {cost}");
+}
+
+#[test]
+fn uniform_cost_matches_dijkstra_for_every_target() {
+    for seed in 1..=5 {
+        let graph = random_graph(seed, 80, 400);
+        let expected = reference_distances(&graph, 0);
+        for target in 0..80 {
+            let found = uniform_cost(0, |&n| graph[n].clone(), |&n| n == target);
+            match (found, expected.get(&target)) {
+                (Some((cost, path)), Some(&d)) => {
+                    assert_eq!(cost, d, "seed {seed}, target {target}");
+                    assert_eq!(*path.last().unwrap(), target);
+                    check_path(&graph, 0, cost, &path);
+                }
+                (None, None) => {}
+                (found, expected) => panic!("seed {seed}, {target}: {found:?} vs {expected:?}"),
+            }
+        }
+    }
+}
+
+type Cell = (i32, i32);
+
+/// A 40×40 grid with a wall along x = 20 open only at y = 39.
+fn grid_moves(expansions: &mut usize) -> impl FnMut(&Cell) -> Vec<(Cell, u32)> + '_ {
+    |&(x, y)| {
+        *expansions += 1;
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..40).contains(&x) && (0..40).contains(&y))
+            .filter(|&(x, y)| x != 20 || y == 39)
+            .map(|p| (p, 1))
+            .collect()
+    }
+}
+
+#[test]
+fn astar_is_optimal_and_expands_less_than_uniform_cost() {
+    let goal = (39, 0);
+    let manhattan =
+        |&(x, y): &(i32, i32)| (goal.0 - x).unsigned_abs() + (goal.1 - y).unsigned_abs();
+
+    let mut ucs_expansions = 0;
+    let (ucs_cost, _) =
+        uniform_cost((0, 0), grid_moves(&mut ucs_expansions), |&p| p == goal).unwrap();
+    let mut astar_expansions = 0;
+    let (astar_cost, path) = astar((0, 0), grid_moves(&mut astar_expansions), manhattan, |&p| {
+        p == goal
+    })
+    .unwrap();
+    let mut greedy_expansions = 0;
+    let (greedy_cost, greedy_path) = greedy_best_first(
+        (0, 0),
+        grid_moves(&mut greedy_expansions),
+        manhattan,
+        |&p| p == goal,
+    )
+    .unwrap();
+
+    // Around the wall: up 39, across 39, down 39
+    assert_eq!(ucs_cost, 117);
+    assert_eq!(astar_cost, 117);
+    assert_eq!(path.len(), 118);
+    assert!(
+        astar_expansions < ucs_expansions,
+        "{astar_expansions} vs {ucs_expansions}"
+    );
+    assert!(greedy_cost >= 117);
+    assert_eq!(u32::try_from(greedy_path.len() - 1).unwrap(), greedy_cost);
+    assert!(
+        greedy_expansions <= astar_expansions,
+        "{greedy_expansions} vs {astar_expansions}"
+    );
+}
+
+#[test]
+fn admissible_but_inconsistent_estimates_stay_optimal() {
+    for seed in 1..=10 {
+        let graph = random_graph(seed, 60, 300);
+        let expected = reference_distances(&graph, 0);
+        let target = 59;
+        // Admissible (never above the true cost left) but jumpy between neighbours
+        let exact_left: HashMap<usize, u32> = (0..60)
+            .filter_map(|n| {
+                let from_n = reference_distances(&graph, n);
+                from_n.get(&target).map(|&d| (n, d))
+            })
+            .collect();
+        let estimate = |n: &usize| {
+            let left = exact_left.get(n).copied().unwrap_or(0);
+            if n.is_multiple_of(2) {
+                left
+            } else {
+                0
+            }
+        };
+        let found = astar(0, |&n| graph[n].clone(), estimate, |&n| n == target);
+        assert_eq!(
+            found.map(|(c, _)| c),
+            expected.get(&target).copied(),
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn float_costs() {
+    let graph = [vec![(1, 0.5), (2, 2.0)], vec![(2, 0.25)], vec![]];
+    let (cost, path) = uniform_cost(0usize, |&n| graph[n].clone(), |&n| n == 2).unwrap();
+    assert!((cost - 0.75_f64).abs() < 1e-12);
+    assert_eq!(path, [0, 1, 2]);
+}
+
+#[test]
+fn expansion_follows_the_priority() {
+    // A star: the root's five leaves all cost 1
+    let leaves = |&n: &u32| {
+        if n == 0 {
+            (1..=5).map(|m| (m, 1u32)).collect()
+        } else {
+            vec![]
+        }
+    };
+    let mut order = Vec::new();
+    let found = best_first(
+        0,
+        |&n| {
+            order.push(n);
+            leaves(&n)
+        },
+        |&n, cost| (cost, Reverse(n)),
+        |_| false,
+    );
+    assert_eq!(found, None);
+    assert_eq!(order, [0, 5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn trivial_and_unreachable_goals() {
+    let graph = [vec![(1, 3u32)], vec![], vec![]];
+    assert_eq!(
+        uniform_cost(0usize, |&n| graph[n].clone(), |&n| n == 0),
+        Some((0, vec![0]))
+    );
+    assert_eq!(
+        uniform_cost(0usize, |&n| graph[n].clone(), |&n| n == 2),
+        None
+    );
+    assert_eq!(
+        greedy_best_first(0usize, |&n| graph[n].clone(), |_| 0u8, |&n| n == 2),
+        None::<(u32, Vec<usize>)>
+    );
+}
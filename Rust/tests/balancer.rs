@@ -0,0 +1,111 @@
+//! Integration tests for least-loaded worker selection (`LoadBalancer`).
+
+use d_ary_heap::{Error, LoadBalancer};
+use std::collections::HashMap;
+
+fn pool(workers: u32) -> LoadBalancer<u32> {
+    let pool = LoadBalancer::new(4).unwrap();
+    for w in 0..workers {
+        assert!(pool.add_worker(w));
+    }
+    pool
+}
+
+#[test]
+fn acquires_spread_evenly() {
+    let pool = pool(5);
+    let guards: Vec<_> = (0..23).map(|_| pool.acquire().unwrap()).collect();
+    let mut per_worker: HashMap<u32, u64> = HashMap::new();
+    for guard in &guards {
+        *per_worker.entry(*guard.worker()).or_default() += 1;
+    }
+    // 23 over 5 workers: each serves 4 or 5
+    assert_eq!(per_worker.len(), 5);
+    assert!(per_worker.values().all(|&n| n == 4 || n == 5));
+    for (w, n) in &per_worker {
+        assert_eq!(pool.load(w), Some(*n));
+    }
+    drop(guards);
+    assert!((0..5).all(|w| pool.load(&w) == Some(0)));
+}
+
+#[test]
+fn released_worker_is_picked_next() {
+    let pool = pool(3);
+    let a = pool.acquire().unwrap();
+    let b = pool.acquire().unwrap();
+    let c = pool.acquire().unwrap();
+    let freed = *b.worker();
+    drop(b);
+    let next = pool.acquire().unwrap();
+    assert_eq!(*next.worker(), freed);
+    drop((a, c, next));
+}
+
+#[test]
+fn guards_release_across_threads() {
+    let pool = pool(8);
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                for _ in 0..500 {
+                    let guard = pool.acquire().unwrap();
+                    assert!(pool.load(guard.worker()).unwrap() >= 1);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!((0..8).all(|w| pool.load(&w) == Some(0)));
+}
+
+#[test]
+fn a_panicking_request_still_releases() {
+    let pool = pool(1);
+    let shared = pool.clone();
+    let result = std::thread::spawn(move || {
+        let _guard = shared.acquire().unwrap();
+        panic!("request failed");
+    })
+    .join();
+    assert!(result.is_err());
+    assert_eq!(pool.load(&0), Some(0));
+}
+
+#[test]
+fn removed_workers_ignore_stale_guards() {
+    let pool = pool(2);
+    let g0 = pool.acquire().unwrap();
+    let g1 = pool.acquire().unwrap();
+    let gone = *g0.worker();
+    assert_eq!(pool.remove_worker(&gone), Some(1));
+    assert_eq!(pool.remove_worker(&gone), None);
+    assert_eq!(pool.len(), 1);
+
+    // Re-added with no load: the old guard must not lower it
+    assert!(pool.add_worker(gone));
+    let fresh = pool.acquire().unwrap();
+    assert_eq!(*fresh.worker(), gone);
+    drop(g0);
+    assert_eq!(pool.load(&gone), Some(1));
+    drop((g1, fresh));
+    assert_eq!(pool.load(&gone), Some(0));
+}
+
+#[test]
+fn construction_and_empty_pool() {
+    assert_eq!(LoadBalancer::<u32>::new(0).err(), Some(Error::InvalidArity));
+    let pool = LoadBalancer::<&str>::new(2).unwrap();
+    assert!(pool.is_empty());
+    assert!(pool.acquire().is_none());
+    assert!(pool.add_worker("a"));
+    assert!(!pool.add_worker("a"));
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.load(&"b"), None);
+    let guard = pool.acquire().unwrap();
+    assert_eq!(format!("{pool:?}"), r#"{"a": 1}"#);
+    assert_eq!(format!("{guard:?}"), r#"WorkerGuard { worker: "a", .. }"#);
+}
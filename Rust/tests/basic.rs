@@ -124,7 +124,7 @@ fn position_type_alias() {
     pq.insert(Item { id: 1, cost: 10 });
 
     // Test that Position type alias works
-    let pos: Position = 0;
+    let pos = Position::ROOT;
     pq.increase_priority_by_index(pos).unwrap();
 
     assert_eq!(pq.len(), 1);
@@ -0,0 +1,199 @@
+//! Integration tests for beam search (`BeamSearch`).
+
+use d_ary_heap::{BeamSearch, Error};
+use std::cmp::Reverse;
+use std::num::NonZeroUsize;
+
+fn width(w: usize) -> NonZeroUsize {
+    NonZeroUsize::new(w).unwrap()
+}
+
+/// Digit sequences over `0..base`, scored by a pseudo-random weight per
+/// (position, digit), so every prefix has a distinct, order-dependent score.
+fn weight(position: usize, digit: u32) -> i64 {
+    let mut x = (position as u64) << 32 | u64::from(digit);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    // CAST: reduced below 1000 first.
+    #[allow(clippy::cast_possible_wrap)]
+    let w = (x % 1000) as i64;
+    w
+}
+
+fn digits(base: u32) -> impl FnMut(&Vec<u32>) -> Vec<Vec<u32>> {
+    move |seq: &Vec<u32>| {
+        (0..base)
+            .map(|digit| {
+                let mut next = seq.clone();
+                next.push(digit);
+                next
+            })
+            .collect()
+    }
+}
+
+fn total(seq: &[u32]) -> i64 {
+    seq.iter()
+        .enumerate()
+        .map(|(i, &digit)| weight(i, digit))
+        .sum()
+}
+
+/// Every sequence of `len` digits, best first, as a beam wide enough never
+/// to prune should return them.
+fn exhaustive(base: u32, len: usize) -> Vec<(i64, Vec<u32>)> {
+    let mut all = vec![Vec::new()];
+    for _ in 0..len {
+        all = all.iter().flat_map(digits(base)).collect();
+    }
+    let mut scored: Vec<(i64, Vec<u32>)> = all.into_iter().map(|s| (total(&s), s)).collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored
+}
+
+#[test]
+fn invalid_arity_is_rejected() {
+    let beam = BeamSearch::new(0, width(2), digits(2), |s: &Vec<u32>| total(s));
+    assert_eq!(beam.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn wide_beam_matches_exhaustive_search() {
+    for d in [1, 2, 4] {
+        let mut beam =
+            BeamSearch::new(d, width(3usize.pow(4)), digits(3), |s: &Vec<u32>| total(s)).unwrap();
+        let mut kept = beam.run(Vec::new(), 4);
+        assert!(kept.windows(2).all(|w| w[0].0 >= w[1].0));
+        kept.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        assert_eq!(kept, exhaustive(3, 4), "d = {d}");
+        assert_eq!(beam.pruned(), 0);
+    }
+}
+
+#[test]
+fn each_depth_keeps_the_best_children_of_the_previous_beam() {
+    let w = 5;
+    let mut beam = BeamSearch::new(4, width(w), digits(4), |s: &Vec<u32>| total(s)).unwrap();
+    let mut kept = beam.run(Vec::new(), 1);
+    for _ in 0..5 {
+        let mut children: Vec<(i64, Vec<u32>)> = kept
+            .iter()
+            .flat_map(|(_, seq)| digits(4)(seq))
+            .map(|s| (total(&s), s))
+            .collect();
+        children.sort_by_key(|c| Reverse(c.0));
+        children.truncate(w);
+
+        kept = beam.step(kept.into_iter().map(|(_, seq)| seq));
+        assert_eq!(kept, children);
+    }
+}
+
+#[test]
+fn frontier_is_bounded_and_counts_what_it_prunes() {
+    let mut beam = BeamSearch::new(2, width(3), digits(10), |s: &Vec<u32>| total(s)).unwrap();
+    let kept = beam.run(Vec::new(), 3);
+    assert_eq!(kept.len(), 3);
+    // Depth 1: 1 node, 10 children; depths 2 and 3: 3 nodes, 30 children
+    assert_eq!(beam.expanded(), 1 + 3 + 3);
+    assert_eq!(beam.pruned(), (10 - 3) + (30 - 3) * 2);
+}
+
+#[test]
+fn width_one_is_greedy() {
+    let mut beam = BeamSearch::new(3, width(1), digits(5), |s: &Vec<u32>| total(s)).unwrap();
+    let kept = beam.run(Vec::new(), 6);
+
+    let mut greedy = Vec::new();
+    for position in 0..6 {
+        let best = (0..5).max_by_key(|&digit| (weight(position, digit), -i64::from(digit)));
+        greedy.push(best.unwrap());
+    }
+    assert_eq!(kept, [(total(&greedy), greedy)]);
+}
+
+#[test]
+fn ties_keep_the_child_generated_first() {
+    let mut beam = BeamSearch::new(
+        2,
+        width(2),
+        |&n: &u32| [n * 10 + 1, n * 10 + 2, n * 10 + 3],
+        |_: &u32| 0,
+    )
+    .unwrap();
+    assert_eq!(beam.run(0, 2), [(0, 11), (0, 12)]);
+}
+
+#[test]
+fn float_scores_work() {
+    let mut beam = BeamSearch::new(
+        4,
+        width(2),
+        |&x: &f64| [x + 0.5, x - 0.25, x * 2.0],
+        |&x: &f64| -(x - 3.0).abs(),
+    )
+    .unwrap();
+    let kept = beam.run(1.0, 2);
+    assert!((kept[0].1 - 3.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn run_ends_empty_when_every_path_ends_earlier() {
+    let mut beam = BeamSearch::new(
+        2,
+        width(4),
+        |&n: &u32| if n < 2 { vec![n + 1] } else { vec![] },
+        |&n| n,
+    )
+    .unwrap();
+    assert_eq!(beam.run(0, 0), [(0, 0)]);
+    assert_eq!(beam.run(0, 2), [(2, 2)]);
+    assert!(beam.run(0, 3).is_empty());
+}
+
+#[test]
+fn search_sets_finished_sequences_aside() {
+    // Decoding with an end token `0`: a sequence is finished once it ends
+    // in 0, and scores fall with length, so short finished sequences
+    // compete with longer open ones.
+    let eos = |seq: &Vec<u32>| seq.last() == Some(&0);
+    let expand = |seq: &Vec<u32>| -> Vec<Vec<u32>> {
+        (0..3)
+            .map(|token| {
+                let mut next = seq.clone();
+                next.push(token);
+                next
+            })
+            .collect()
+    };
+    let score = |seq: &Vec<u32>| -> i64 {
+        seq.iter()
+            .map(|&t| match t {
+                0 => -5,
+                1 => -1,
+                _ => -2,
+            })
+            .sum()
+    };
+
+    let mut beam = BeamSearch::new(2, width(2), expand, score).unwrap();
+    // Depth 1: [1] (-1), [2] (-2); depth 2: [1,1] (-2), [1,2] (-3), as
+    // [1,0] (-6) is pruned; depth 3: [1,1,1] (-3), [1,1,2] (-4): no goal
+    // is ever kept, so the search fails.
+    assert_eq!(beam.search(Vec::new(), 3, eos), None);
+
+    let mut beam = BeamSearch::new(2, width(4), expand, score).unwrap();
+    // A wider beam keeps [0] (-5) at depth 1; [1,0] (-6) is pruned at
+    // depth 2 and would not beat it anyway.
+    assert_eq!(beam.search(Vec::new(), 2, eos), Some((-5, vec![0])));
+}
+
+#[test]
+fn search_returns_the_start_if_it_is_a_goal() {
+    let mut beam = BeamSearch::new(2, width(1), |&n: &u32| [n + 1], |&n| n).unwrap();
+    assert_eq!(beam.search(7, 10, |&n| n == 7), Some((7, 7)));
+    assert_eq!(beam.expanded(), 0);
+    assert_eq!(beam.search(0, 10, |&n| n == 4), Some((4, 4)));
+    assert_eq!(beam.search(0, 3, |&n| n == 4), None);
+}
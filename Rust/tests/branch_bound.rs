@@ -0,0 +1,323 @@
+//! Integration tests for best-first branch and bound (`BranchAndBound`).
+
+use d_ary_heap::{BranchAndBound, BranchAndBoundStats, Error};
+
+/// Pseudo-random values in `1..=modulus`, from a fixed seed.
+fn values(seed: u64, len: usize, modulus: u64) -> Vec<u64> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % modulus + 1
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// 0/1 knapsack, maximised by minimising the negated value
+// -----------------------------------------------------------------------------
+
+struct Knapsack {
+    weights: Vec<i64>,
+    values: Vec<i64>,
+    capacity: i64,
+}
+
+/// Items decided so far: `taken[i]` for the first `taken.len()` items.
+type Decisions = Vec<bool>;
+
+fn signed(values: Vec<u64>) -> Vec<i64> {
+    values
+        .into_iter()
+        .map(|v| i64::try_from(v).unwrap())
+        .collect()
+}
+
+impl Knapsack {
+    fn new(seed: u64, len: usize) -> Self {
+        let weights = signed(values(seed, len, 30));
+        let capacity = weights.iter().sum::<i64>() / 2;
+        Self {
+            weights,
+            values: signed(values(seed ^ 0x9e37_79b9, len, 50)),
+            capacity,
+        }
+    }
+
+    fn weight(&self, taken: &Decisions) -> i64 {
+        taken
+            .iter()
+            .zip(&self.weights)
+            .filter(|(&t, _)| t)
+            .map(|(_, w)| w)
+            .sum()
+    }
+
+    fn value(&self, taken: &Decisions) -> i64 {
+        taken
+            .iter()
+            .zip(&self.values)
+            .filter(|(&t, _)| t)
+            .map(|(_, v)| v)
+            .sum()
+    }
+
+    fn branch(&self, taken: &Decisions) -> Vec<Decisions> {
+        if taken.len() == self.weights.len() {
+            return Vec::new();
+        }
+        let mut children = vec![[taken.as_slice(), &[false]].concat()];
+        if self.weight(taken) + self.weights[taken.len()] <= self.capacity {
+            children.push([taken.as_slice(), &[true]].concat());
+        }
+        children
+    }
+
+    /// Negated fractional-relaxation value, rounded down: the undecided
+    /// items, best value per weight first, with the last one cut to fit.
+    fn bound(&self, taken: &Decisions) -> i64 {
+        let mut rest: Vec<usize> = (taken.len()..self.weights.len()).collect();
+        rest.sort_by(|&a, &b| {
+            (self.values[b] * self.weights[a]).cmp(&(self.values[a] * self.weights[b]))
+        });
+        let mut room = self.capacity - self.weight(taken);
+        let mut value = self.value(taken);
+        for i in rest {
+            let (w, v) = (self.weights[i], self.values[i]);
+            if w <= room {
+                room -= w;
+                value += v;
+            } else {
+                value += v * room / w;
+                break;
+            }
+        }
+        -value
+    }
+
+    fn optimum(&self) -> i64 {
+        let capacity = usize::try_from(self.capacity).unwrap();
+        let mut best = vec![0i64; capacity + 1];
+        for (&w, &v) in self.weights.iter().zip(&self.values) {
+            let w = usize::try_from(w).unwrap();
+            for c in (w..=capacity).rev() {
+                best[c] = best[c].max(best[c - w] + v);
+            }
+        }
+        best[capacity]
+    }
+}
+
+#[test]
+fn knapsack_matches_dynamic_programming() {
+    for seed in 1..=12u64 {
+        let problem = Knapsack::new(seed * 0x2545_f491, 18);
+        for d in [1, 2, 4, 8] {
+            let mut solver = BranchAndBound::new(
+                d,
+                |t: &Decisions| problem.branch(t),
+                |t: &Decisions| problem.bound(t),
+                // Every node is a feasible packing of the items decided so far
+                |t: &Decisions| Some(-problem.value(t)),
+            )
+            .unwrap();
+            let (cost, taken) = solver.solve(Vec::new()).unwrap();
+            let optimum = problem.optimum();
+            assert_eq!(-cost, optimum, "seed={seed} d={d}");
+            assert_eq!(problem.value(&taken), optimum);
+            assert!(problem.weight(&taken) <= problem.capacity);
+        }
+    }
+}
+
+#[test]
+fn bounds_prune_most_of_the_tree() {
+    let problem = Knapsack::new(0x5eed, 20);
+    let mut solver = BranchAndBound::new(
+        4,
+        |t: &Decisions| problem.branch(t),
+        |t: &Decisions| problem.bound(t),
+        // Every node is a feasible packing of the items decided so far
+        |t: &Decisions| Some(-problem.value(t)),
+    )
+    .unwrap();
+    solver.solve(Vec::new()).unwrap();
+    let stats = *solver.stats();
+    // The full tree has up to 2^21 - 1 nodes
+    assert!(stats.generated < 20_000, "{stats:?}");
+    assert!(stats.incumbent_updates >= 1);
+    assert!(stats.pruned_on_arrival > 0);
+    assert!(stats.max_frontier > 0);
+}
+
+#[test]
+fn stats_account_for_every_node() {
+    for seed in 1..=6u64 {
+        let problem = Knapsack::new(seed, 14);
+        let mut children = 0u64;
+        let mut solver = BranchAndBound::new(
+            2,
+            |t: &Decisions| {
+                let c = problem.branch(t);
+                children += c.len() as u64;
+                c
+            },
+            |t: &Decisions| problem.bound(t),
+            // Every node is a feasible packing of the items decided so far
+            |t: &Decisions| Some(-problem.value(t)),
+        )
+        .unwrap();
+        solver.solve(Vec::new()).unwrap();
+        let stats = *solver.stats();
+
+        assert_eq!(stats.generated, children + 1);
+        // The frontier ends empty: every queued node was either expanded or
+        // removed by a later incumbent.
+        assert_eq!(
+            stats.generated,
+            stats.expanded + stats.pruned_on_arrival + stats.pruned_from_frontier
+        );
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Assignment problem, checked against every permutation
+// -----------------------------------------------------------------------------
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    permutations(n - 1)
+        .into_iter()
+        .flat_map(|p| {
+            (0..n).map(move |at| {
+                let mut q = p.clone();
+                q.insert(at, n - 1);
+                q
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn assignment_matches_brute_force() {
+    let n = 6;
+    for seed in 1..=8u64 {
+        let flat = values(seed * 7919, n * n, 100);
+        let cost = |r: usize, c: usize| flat[r * n + c];
+        let total =
+            |cols: &Vec<usize>| -> u64 { cols.iter().enumerate().map(|(r, &c)| cost(r, c)).sum() };
+
+        let mut solver = BranchAndBound::new(
+            3,
+            |cols: &Vec<usize>| {
+                (0..n)
+                    .filter(|c| !cols.contains(c))
+                    .map(|c| [cols.as_slice(), &[c]].concat())
+                    .collect::<Vec<_>>()
+            },
+            |cols: &Vec<usize>| {
+                let rest: u64 = (cols.len()..n)
+                    .map(|r| {
+                        (0..n)
+                            .filter(|c| !cols.contains(c))
+                            .map(|c| cost(r, c))
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                total(cols) + rest
+            },
+            |cols: &Vec<usize>| (cols.len() == n).then(|| total(cols)),
+        )
+        .unwrap();
+
+        let (best, cols) = solver.solve(Vec::new()).unwrap();
+        let brute = permutations(n).iter().map(total).min().unwrap();
+        assert_eq!(best, brute, "seed={seed}");
+        assert_eq!(total(&cols), best);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Edge cases
+// -----------------------------------------------------------------------------
+
+#[test]
+fn invalid_arity_is_rejected() {
+    let solver = BranchAndBound::new(0, |_: &u32| Vec::new(), |&n: &u32| n, |_: &u32| None);
+    assert_eq!(solver.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn no_solution_explores_everything() {
+    // A binary tree of depth 4 with no feasible node
+    let mut solver = BranchAndBound::new(
+        2,
+        |&n: &u32| {
+            if n < 16 {
+                vec![2 * n, 2 * n + 1]
+            } else {
+                vec![]
+            }
+        },
+        |&n: &u32| n,
+        |_: &u32| None::<u32>,
+    )
+    .unwrap();
+    assert_eq!(solver.solve(1), None);
+    assert_eq!(
+        *solver.stats(),
+        BranchAndBoundStats {
+            generated: 31,
+            expanded: 31,
+            max_frontier: 16,
+            ..BranchAndBoundStats::default()
+        }
+    );
+}
+
+#[test]
+fn a_solution_is_still_branched_while_cheaper_ones_may_lie_below() {
+    // Every node is a solution costing its label, and its children are
+    // cheaper, down to 0: the root's incumbent must be improved on.
+    let mut solver = BranchAndBound::new(
+        2,
+        |&n: &u32| if n > 0 { vec![n - 1] } else { vec![] },
+        |_: &u32| 0,
+        |&n: &u32| Some(n),
+    )
+    .unwrap();
+    assert_eq!(solver.solve(5), Some((0, 0)));
+    assert_eq!(solver.stats().incumbent_updates, 6);
+
+    // Statistics restart with each call
+    assert_eq!(solver.solve(0), Some((0, 0)));
+    assert_eq!(solver.stats().generated, 1);
+}
+
+#[test]
+fn a_new_incumbent_drains_the_frontier_it_rules_out() {
+    // The root has children bounded 1..=9; child 1 is a solution costing 3,
+    // which rules out the queued children bounded 3 and above.
+    let mut solver = BranchAndBound::new(
+        4,
+        |&n: &u32| {
+            if n == 0 {
+                (1..=9).rev().collect()
+            } else {
+                vec![]
+            }
+        },
+        |&n: &u32| n,
+        |&n: &u32| (n == 1).then_some(3),
+    )
+    .unwrap();
+    assert_eq!(solver.solve(0), Some((3, 1)));
+    let stats = solver.stats();
+    assert_eq!(stats.pruned_from_frontier, 7);
+    assert_eq!(stats.expanded, 3);
+}
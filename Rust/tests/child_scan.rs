@@ -0,0 +1,111 @@
+//! Integration tests for the sift-down child scan policy (`ChildScan`).
+
+use d_ary_heap::{ChildScan, MinBy, PriorityCompare, PriorityQueue, StatsCollector};
+
+/// Pseudo-random values below `modulus`, from a fixed seed.
+fn values(seed: u64, len: usize, modulus: u64) -> Vec<u32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            u32::try_from(state % modulus).unwrap()
+        })
+        .collect()
+}
+
+/// Runs the same inserts and pops on `heap` under `scan` and returns the
+/// popped items and the layout left behind.
+fn run<C, S>(
+    heap: &mut PriorityQueue<u32, C, S>,
+    scan: ChildScan,
+    seed: u64,
+) -> (Vec<u32>, Vec<u32>)
+where
+    C: PriorityCompare<u32>,
+    S: StatsCollector,
+{
+    heap.set_child_scan(scan);
+    let mut popped = Vec::new();
+    for (round, chunk) in values(seed, 600, 1_000_000).chunks(60).enumerate() {
+        // Distinct ids: the round number keeps chunks apart
+        heap.insert_many(
+            chunk
+                .iter()
+                .map(|&v| v * 16 + u32::try_from(round).unwrap()),
+        );
+        popped.extend(heap.pop_many(25));
+    }
+    (popped, heap.to_array())
+}
+
+fn assert_heap_order<C: PriorityCompare<u32>>(items: &[u32], d: usize, comparator: &C) {
+    for i in 1..items.len() {
+        let parent = (i - 1) / d;
+        assert!(
+            !comparator.higher_priority(&items[i], &items[parent]),
+            "d={d}: item at {i} outranks its parent"
+        );
+    }
+}
+
+#[test]
+fn leftmost_is_the_default() {
+    let heap = PriorityQueue::new(4, MinBy(|x: &u32| *x)).unwrap();
+    assert_eq!(heap.child_scan(), ChildScan::Leftmost);
+}
+
+#[test]
+fn follow_best_pops_in_priority_order_with_ties() {
+    // Keys collide in groups of 64, so most sibling groups hold ties
+    let key = |x: &u32| *x / 64;
+    for d in [1, 2, 3, 4, 8, 16] {
+        for seed in 1..=4u64 {
+            let mut heap = PriorityQueue::new(d, MinBy(key)).unwrap();
+            let (follow, layout) = run(&mut heap, ChildScan::FollowBest, seed);
+            let mut heap = PriorityQueue::new(d, MinBy(key)).unwrap();
+            let (leftmost, _) = run(&mut heap, ChildScan::Leftmost, seed);
+
+            let keys = |items: &[u32]| items.iter().map(key).collect::<Vec<_>>();
+            assert_eq!(keys(&follow), keys(&leftmost), "d={d} seed={seed}");
+            assert_heap_order(&layout, d, &MinBy(key));
+        }
+    }
+}
+
+#[test]
+fn distinct_priorities_give_the_same_layout_and_comparisons() {
+    for d in [2, 3, 4, 8] {
+        let mut a = PriorityQueue::with_stats(d, MinBy(|x: &u32| *x)).unwrap();
+        let mut b = PriorityQueue::with_stats(d, MinBy(|x: &u32| *x)).unwrap();
+        let leftmost = run(&mut a, ChildScan::Leftmost, 7);
+        let follow = run(&mut b, ChildScan::FollowBest, 7);
+        assert_eq!(follow, leftmost, "d={d}");
+        assert_eq!(b.stats().pop(), a.stats().pop(), "d={d}");
+    }
+}
+
+#[test]
+fn ties_can_resolve_to_a_different_child() {
+    let key = |x: &u32| *x / 1_000_000;
+    let differs = (1..=8u64).any(|seed| {
+        let mut heap = PriorityQueue::new(4, MinBy(key)).unwrap();
+        let follow = run(&mut heap, ChildScan::FollowBest, seed).1;
+        let mut heap = PriorityQueue::new(4, MinBy(key)).unwrap();
+        follow != run(&mut heap, ChildScan::Leftmost, seed).1
+    });
+    assert!(differs);
+}
+
+#[test]
+fn switching_policy_mid_run_keeps_the_contents() {
+    let mut heap = PriorityQueue::new(3, MinBy(|x: &u32| *x)).unwrap();
+    heap.insert_many((0..100).map(|i| i * 37 % 100));
+
+    heap.set_child_scan(ChildScan::FollowBest);
+    let half = heap.pop_many(50);
+    heap.set_child_scan(ChildScan::Leftmost);
+    let rest = heap.pop_many(50);
+    assert_eq!([half, rest].concat(), (0..100).collect::<Vec<_>>());
+}
@@ -7,9 +7,18 @@
 //
 // Licensed under the Apache License, Version 2.0 (the "License")
 
-use d_ary_heap::{Error, MaxBy, MinBy, Position, PriorityQueue};
+use d_ary_heap::{
+    DenseIndex, Error, HashIndex, MaxBy, MaxHeap, MaxOrd, MinBy, MinHeap, Position, PositionIndex,
+    PriorityQueue, SnapshotPriorityQueue, StatsCollector, DEFAULT_ARITY,
+};
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::ControlFlow;
+use std::rc::Rc;
+use std::time::Duration;
 
 // =============================================================================
 // Test Item Type
@@ -216,6 +225,180 @@ fn test_pop_empty() {
     assert_eq!(pq.pop(), None);
 }
 
+#[test]
+fn test_pop_if() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.pop_if(|_| true), None);
+    pq.insert_many(vec![Item::new(1, 30), Item::new(2, 10), Item::new(3, 20)]);
+
+    assert_eq!(pq.pop_if(|front| front.cost > 10), None);
+    assert_eq!(pq.len(), 3);
+    assert_eq!(pq.pop_if(|front| front.cost <= 20).map(|x| x.id), Some(2));
+    assert_eq!(pq.pop_if(|front| front.cost <= 20).map(|x| x.id), Some(3));
+    assert_eq!(pq.pop_if(|front| front.cost <= 20), None);
+    assert_eq!(pq.front().id, 1);
+}
+
+#[test]
+fn test_pop_if_better_than() {
+    let mut pq = PriorityQueue::with_stats(2, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.pop_if_better_than(&Item::new(0, 100)), None);
+    pq.insert_many(vec![Item::new(1, 15), Item::new(2, 5), Item::new(3, 10)]);
+    pq.stats().reset();
+
+    // The threshold is compared by priority only and need not be queued
+    let threshold = Item::new(99, 10);
+    assert_eq!(pq.pop_if_better_than(&threshold).map(|x| x.id), Some(2));
+    // A tie is not better
+    assert_eq!(pq.pop_if_better_than(&threshold), None);
+    assert_eq!(pq.len(), 2);
+    assert!(pq.contains(&Item::new(3, 10)));
+    // Threshold comparisons count towards pop
+    assert_eq!(pq.stats().pop(), pq.stats().total());
+    assert!(pq.stats().pop() >= 2);
+}
+
+#[test]
+fn test_front_better_than() {
+    let mut pq = PriorityQueue::new(3, MaxBy(|x: &Item| x.cost)).unwrap();
+    assert!(!pq.front_better_than(&Item::new(0, 0)));
+    pq.insert_many(vec![Item::new(1, 15), Item::new(2, 5), Item::new(3, 10)]);
+
+    // The probe is compared by priority only and need not be queued
+    assert!(pq.front_better_than(&Item::new(99, 14)));
+    assert!(!pq.front_better_than(&Item::new(99, 15)));
+    assert!(!pq.front_better_than(&Item::new(1, 20)));
+
+    // Agrees with pop_if_better_than, which pops on the same test
+    let probe = Item::new(99, 7);
+    let mut popped = Vec::new();
+    while pq.front_better_than(&probe) {
+        popped.push(pq.pop_if_better_than(&probe).unwrap().id);
+    }
+    assert_eq!(popped, [1, 3]);
+    assert_eq!(pq.pop_if_better_than(&probe), None);
+    assert_eq!(pq.len(), 1);
+}
+
+#[test]
+fn test_push_pop() {
+    let mut pq = PriorityQueue::with_stats(3, MinBy(|x: &Item| x.cost)).unwrap();
+    // Empty heap: the item comes straight back
+    assert_eq!(pq.push_pop(Item::new(0, 1)).id, 0);
+    assert!(pq.is_empty());
+    pq.insert_many((1..=20).map(|i| Item::new(i, i * 10)));
+    pq.stats().reset();
+
+    // Not worse than the front (a tie included): returned, heap untouched
+    assert_eq!(pq.push_pop(Item::new(50, 10)).id, 50);
+    assert_eq!(pq.push_pop(Item::new(51, 5)).id, 51);
+    assert_eq!(pq.len(), 20);
+    // Worse than the front: the front comes out, the item goes in
+    assert_eq!(pq.push_pop(Item::new(52, 155)).id, 1);
+    assert_eq!(pq.len(), 20);
+    assert!(pq.contains(&Item::new(52, 0)));
+    assert!(!pq.contains(&Item::new(1, 0)));
+    for (position, item) in pq.iter_with_positions() {
+        assert_eq!(pq.get_position(item), Some(position));
+    }
+    assert_eq!(pq.stats().pop(), pq.stats().total());
+
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop().map(|x| x.cost)).collect();
+    let mut expected: Vec<u32> = (2..=20).map(|i| i * 10).chain([155]).collect();
+    expected.sort_unstable();
+    assert_eq!(costs, expected);
+}
+
+#[test]
+fn test_replace_front() {
+    let mut pq = PriorityQueue::with_stats(3, MinBy(|x: &Item| x.cost)).unwrap();
+    // Empty heap: the item goes in, nothing comes out
+    assert_eq!(pq.replace_front(Item::new(0, 100)), None);
+    assert_eq!(pq.front().id, 0);
+    pq.insert_many((1..=20).map(|i| Item::new(i, i * 10)));
+    pq.stats().reset();
+
+    // The front leaves whether the item ranks above it, below it, or is it
+    assert_eq!(pq.replace_front(Item::new(50, 5)).unwrap().id, 1);
+    assert_eq!(pq.front().id, 50);
+    assert_eq!(pq.replace_front(Item::new(51, 155)).unwrap().id, 50);
+    assert_eq!(pq.replace_front(Item::new(2, 205)).unwrap().cost, 20);
+    assert_eq!(pq.len(), 21);
+    assert_eq!(pq.stats().pop(), pq.stats().total());
+    for (position, item) in pq.iter_with_positions() {
+        assert_eq!(pq.get_position(item), Some(position));
+    }
+
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop().map(|x| x.cost)).collect();
+    let mut expected: Vec<u32> = (3..=20).map(|i| i * 10).chain([100, 155, 205]).collect();
+    expected.sort_unstable();
+    assert_eq!(costs, expected);
+}
+
+/// `replace_front` with an item equal to the front under `index`: the item
+/// must stay indexed, and the index must count it once.
+fn check_replace_front_with_the_front<P: PositionIndex<Item>>(index: P) {
+    let mut pq = PriorityQueue::with_position_index(3, MinBy(|x: &Item| x.cost), index).unwrap();
+    pq.insert_many((1..=10).map(|i| Item::new(i, i * 10)));
+
+    // Same id as the front, a new cost that sinks it
+    let old = pq.replace_front(Item::new(1, 75)).unwrap();
+    assert_eq!((old.id, old.cost), (1, 10));
+    assert_eq!(pq.len(), 10);
+    assert!(pq.contains(&Item::new(1, 0)));
+    let position = pq.get_position(&Item::new(1, 0)).unwrap();
+    assert_eq!(pq.to_array()[position.index()].cost, 75);
+    for (position, item) in pq.iter_with_positions() {
+        assert_eq!(pq.get_position(item), Some(position));
+    }
+
+    // Still reachable by the updates, and counted once by the index
+    pq.increase_priority(&Item::new(1, 1)).unwrap();
+    assert_eq!(pq.front().id, 1);
+    assert_eq!(pq.remove(&Item::new(1, 0)).map(|x| x.cost), Some(1));
+    let (container, positions, _) = pq.into_raw_parts();
+    assert_eq!(positions.len(), container.len());
+    assert_eq!(positions.len(), 9);
+}
+
+#[test]
+fn test_replace_front_with_the_front_keeps_it_indexed() {
+    check_replace_front_with_the_front(HashIndex::new());
+    check_replace_front_with_the_front(HashMap::new());
+    check_replace_front_with_the_front(DenseIndex::new(|x: &Item| x.id as usize));
+}
+
+#[test]
+fn test_pop_if_under_a_mutex_pops_each_due_item_once() {
+    use std::sync::{Arc, Mutex};
+
+    let queue = Arc::new(Mutex::new(
+        PriorityQueue::new(4, MinBy(|due: &u32| *due)).unwrap(),
+    ));
+    queue.lock().unwrap().insert_many(0..1000);
+    let now = 600;
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || {
+                let mut taken = Vec::new();
+                while let Some(due) = queue.lock().unwrap().pop_if(|&due| due < now) {
+                    taken.push(due);
+                }
+                taken
+            })
+        })
+        .collect();
+    let mut taken: Vec<u32> = workers
+        .into_iter()
+        .flat_map(|worker| worker.join().unwrap())
+        .collect();
+    taken.sort_unstable();
+    assert_eq!(taken, (0..now).collect::<Vec<_>>());
+    assert_eq!(queue.lock().unwrap().front(), &now);
+}
+
 // =============================================================================
 // Front/Peek Tests
 // =============================================================================
@@ -275,6 +458,41 @@ fn test_contains_empty() {
     assert!(!pq.contains(&Item::new(1, 10)));
 }
 
+#[test]
+fn test_contains_all_reports_missing() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((1..=5).map(|id| Item::new(id, id * 10)));
+
+    // Identity only: the costs of the queried items are irrelevant
+    let present = [Item::new(2, 0), Item::new(5, 0)];
+    assert_eq!(pq.contains_all(&present), Ok(()));
+    assert_eq!(pq.contains_all(Vec::<&Item>::new()), Ok(()));
+
+    let deps = [Item::new(7, 0), Item::new(1, 0), Item::new(9, 0)];
+    let missing = pq.contains_all(&deps).unwrap_err();
+    assert_eq!(
+        missing.iter().map(|item| item.id).collect::<Vec<_>>(),
+        vec![7, 9]
+    );
+
+    pq.pop();
+    assert_eq!(pq.contains_all(&present[..1]), Ok(()));
+    assert!(pq.contains_all(&[Item::new(1, 0)]).is_err());
+}
+
+#[test]
+fn test_contains_any() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(!pq.contains_any(&[Item::new(1, 0)]));
+
+    pq.insert(Item::new(1, 10));
+    assert!(pq.contains_any(&[Item::new(8, 0), Item::new(1, 0)]));
+    assert!(!pq.contains_any(&[Item::new(8, 0), Item::new(9, 0)]));
+    assert!(!pq.contains_any(Vec::<&Item>::new()));
+}
+
 #[test]
 fn test_get_position() {
     let mut pq: PriorityQueue<Item, MinBy<_>> =
@@ -284,7 +502,7 @@ fn test_get_position() {
     pq.insert(Item::new(3, 20));
 
     // Root (highest priority) is at position 0
-    assert_eq!(pq.get_position(&Item::new(2, 0)), Some(0));
+    assert_eq!(pq.get_position(&Item::new(2, 0)), Some(Position::ROOT));
     assert!(pq.get_position(&Item::new(1, 0)).is_some());
     assert!(pq.get_position(&Item::new(3, 0)).is_some());
     assert_eq!(pq.get_position(&Item::new(99, 0)), None);
@@ -324,6 +542,56 @@ fn test_increase_priority_not_found() {
     assert_eq!(result, Err(Error::ItemNotFound));
 }
 
+#[test]
+fn test_increase_priority_unchecked_matches_checked() {
+    let items: Vec<Item> = (0..40).map(|id| Item::new(id, 1000 - id * 7)).collect();
+    let mut checked = PriorityQueue::with_stats(3, MinBy(|x: &Item| x.cost)).unwrap();
+    let mut unchecked = PriorityQueue::with_stats(3, MinBy(|x: &Item| x.cost)).unwrap();
+    checked.insert_many(items.clone());
+    unchecked.insert_many(items);
+
+    for id in (0..40).step_by(3) {
+        let updated = Item::new(id, id * 2);
+        checked.increase_priority(&updated).unwrap();
+        unchecked.increase_priority_unchecked(&updated);
+    }
+    assert_eq!(unchecked.to_array(), checked.to_array());
+    assert_eq!(
+        unchecked.stats().increase_priority(),
+        checked.stats().increase_priority()
+    );
+
+    // A `HashMap` index, which keeps its own copy of each item, holds the
+    // updated item, not the stale one
+    let mut hashed =
+        PriorityQueue::with_position_index(3, MinBy(|x: &Item| x.cost), HashMap::new()).unwrap();
+    hashed.insert_many((0..40).map(|id| Item::new(id, 1000 - id * 7)));
+    hashed.increase_priority_unchecked(&Item::new(3, 6));
+    let (_, positions, _) = hashed.into_raw_parts();
+    let (key, _) = positions.get_key_value(&Item::new(3, 0)).unwrap();
+    assert_eq!(key.cost, 6);
+}
+
+#[test]
+fn test_increase_priority_by_index_unchecked() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    pq.insert_many(vec![4, 8, 6]);
+    let i = pq.get_position(&8).unwrap();
+    pq.increase_priority_by_index_unchecked(i);
+    assert_eq!(pq.get_position(&8), Some(i));
+    assert_eq!(pq.pop_many(3), vec![4, 6, 8]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "item not in heap")]
+fn test_increase_priority_unchecked_absent_item_asserts() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert(Item::new(1, 10));
+    pq.increase_priority_unchecked(&Item::new(99, 5));
+}
+
 #[test]
 fn test_decrease_priority() {
     let mut pq: PriorityQueue<Item, MinBy<_>> =
@@ -407,7 +675,7 @@ fn test_increase_priority_by_index_out_of_bounds() {
         PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
     pq.insert(Item::new(1, 10));
 
-    let result = pq.increase_priority_by_index(99);
+    let result = pq.increase_priority_by_index(Position::new(99));
     assert_eq!(result, Err(Error::IndexOutOfBounds));
 }
 
@@ -418,7 +686,7 @@ fn test_decrease_priority_by_index() {
     pq.insert(Item::new(1, 10));
     pq.insert(Item::new(2, 20));
 
-    pq.decrease_priority_by_index(0).unwrap();
+    pq.decrease_priority_by_index(Position::ROOT).unwrap();
     // Item should maintain heap property
     assert_eq!(pq.len(), 2);
 }
@@ -429,7 +697,7 @@ fn test_decrease_priority_by_index_out_of_bounds() {
         PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
     pq.insert(Item::new(1, 10));
 
-    let result = pq.decrease_priority_by_index(99);
+    let result = pq.decrease_priority_by_index(Position::new(99));
     assert_eq!(result, Err(Error::IndexOutOfBounds));
 }
 
@@ -440,7 +708,7 @@ fn test_update_priority_by_index() {
     pq.insert(Item::new(1, 10));
     pq.insert(Item::new(2, 20));
 
-    pq.update_priority_by_index(0).unwrap();
+    pq.update_priority_by_index(Position::ROOT).unwrap();
     assert_eq!(pq.len(), 2);
 }
 
@@ -450,10 +718,523 @@ fn test_update_priority_by_index_out_of_bounds() {
         PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
     pq.insert(Item::new(1, 10));
 
-    let result = pq.update_priority_by_index(99);
+    let result = pq.update_priority_by_index(Position::new(99));
     assert_eq!(result, Err(Error::IndexOutOfBounds));
 }
 
+// =============================================================================
+// Batch Update Tests
+// =============================================================================
+
+fn drain_costs(pq: &mut PriorityQueue<Item, MinBy<impl Fn(&Item) -> u32>>) -> Vec<(u32, u32)> {
+    std::iter::from_fn(|| pq.pop())
+        .map(|item| (item.cost, item.id))
+        .collect()
+}
+
+#[test]
+fn test_update_priorities_small_and_large_batches() {
+    // Batch sizes on both sides of the n / log_d n rebuild threshold
+    for d in [1, 2, 3, 4, 8] {
+        for batch in [0, 1, 5, 20, 64, 150] {
+            let mut batched = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            let mut single = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            for i in 0..64 {
+                batched.insert(Item::new(i, (i * 37) % 101));
+                single.insert(Item::new(i, (i * 37) % 101));
+            }
+
+            let updates: Vec<Item> = (0..batch)
+                .map(|k| Item::new((k * 13) % 64, (k * 53 + 7) % 97))
+                .collect();
+            for u in &updates {
+                single.update_priority(u).unwrap();
+            }
+            batched.update_priorities(updates).unwrap();
+
+            for i in 0..64 {
+                let pos = batched.get_position(&Item::new(i, 0)).unwrap();
+                assert_eq!(pos.get_at(&batched).unwrap().id, i, "d={d} batch={batch}");
+            }
+            let mut expected = drain_costs(&mut single);
+            let mut actual = drain_costs(&mut batched);
+            // Equal costs may pop in a different order; the cost sequence may not
+            assert!(
+                actual.windows(2).all(|w| w[0].0 <= w[1].0),
+                "d={d} batch={batch}"
+            );
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "d={d} batch={batch}");
+        }
+    }
+}
+
+#[test]
+fn test_update_priorities_last_update_wins() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..4).map(|i| Item::new(i, 10 + i)));
+
+    pq.update_priorities(vec![Item::new(3, 1), Item::new(2, 5), Item::new(3, 50)])
+        .unwrap();
+    assert_eq!(pq.front().id, 2);
+    assert_eq!(pq.len(), 4);
+    let costs: Vec<u32> = drain_costs(&mut pq).into_iter().map(|(c, _)| c).collect();
+    assert_eq!(costs, vec![5, 10, 11, 50]);
+}
+
+#[test]
+fn test_update_priorities_missing_item_leaves_heap_unchanged() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..10).map(|i| Item::new(i, i)));
+    let before: Vec<u32> = pq.to_array().iter().map(|x| x.cost).collect();
+
+    let result = pq.update_priorities(vec![Item::new(4, 0), Item::new(99, 0)]);
+    assert_eq!(result, Err(Error::ItemNotFound));
+    let after: Vec<u32> = pq.to_array().iter().map(|x| x.cost).collect();
+    assert_eq!(after, before);
+}
+
+#[test]
+fn test_adjust_priority_both_directions() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..20).map(|i| Item::new(i, i * 10 + 5)));
+
+    pq.adjust_priority(&Item::new(15, 0), |x| x.cost -= 154)
+        .unwrap();
+    assert_eq!(pq.front(), &Item::new(15, 0));
+    assert_eq!(pq.front().cost, 1);
+
+    pq.adjust_priority(&Item::new(0, 0), |x| x.cost += 1000)
+        .unwrap();
+    let costs: Vec<u32> = drain_costs(&mut pq).into_iter().map(|(c, _)| c).collect();
+    assert!(costs.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!((costs[0], costs[19]), (1, 1005));
+}
+
+#[test]
+fn test_adjust_priority_not_found() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert(Item::new(1, 10));
+    let result = pq.adjust_priority(&Item::new(2, 0), |x| x.cost += 1);
+    assert_eq!(result, Err(Error::ItemNotFound));
+}
+
+#[test]
+fn test_adjust_priority_identity_change_is_an_error() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert(Item::new(1, 10));
+    pq.insert(Item::new(3, 5));
+    let result = pq.adjust_priority(&Item::new(1, 0), |x| {
+        x.id = 2;
+        x.cost = 0;
+    });
+    assert_eq!(result, Err(Error::IdentityChanged));
+
+    // The stored item is untouched and still reachable by its identity
+    assert!(pq.contains(&Item::new(1, 0)));
+    assert!(!pq.contains(&Item::new(2, 0)));
+    assert_eq!(pq.front().id, 3);
+    assert_eq!(pq.pop().map(|x| x.cost), Some(5));
+    assert_eq!(pq.pop().map(|x| x.cost), Some(10));
+}
+
+// =============================================================================
+// Relaxed Pop Tests
+// =============================================================================
+
+#[test]
+fn test_pop_within_returns_exactly_the_tolerance_band() {
+    for d in [1, 2, 3, 5] {
+        for tolerance in [0, 3, 10, 60] {
+            let mut pq = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            pq.insert_many((0..80).map(|i| Item::new(i, (i * 29 + 11) % 53)));
+            let mut expected: Vec<(u32, u32)> =
+                pq.to_array().iter().map(|x| (x.cost, x.id)).collect();
+            let best = pq.front().cost;
+            expected.retain(|&(cost, _)| cost - best <= tolerance);
+            expected.sort_unstable();
+
+            let batch = pq.pop_within(|b, x| x.cost - b.cost <= tolerance);
+            assert_eq!(batch[0].cost, best, "d={d} tol={tolerance}");
+            let mut actual: Vec<(u32, u32)> = batch.iter().map(|x| (x.cost, x.id)).collect();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "d={d} tol={tolerance}");
+
+            // What remains is a valid heap with consistent positions
+            assert_eq!(pq.len(), 80 - batch.len());
+            for item in &batch {
+                assert!(!pq.contains(item));
+            }
+            for (pos, item) in pq.iter_with_positions() {
+                assert_eq!(pq.get_position(item), Some(pos));
+            }
+            let rest = drain_costs(&mut pq);
+            assert!(rest.windows(2).all(|w| w[0].0 <= w[1].0));
+            assert!(rest.iter().all(|&(cost, _)| cost - best > tolerance));
+        }
+    }
+}
+
+#[test]
+fn test_pop_within_empty_and_singleton() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(pq.pop_within(|_, _| true).is_empty());
+    pq.insert(Item::new(1, 10));
+    assert_eq!(pq.pop_within(|_, _| true), vec![Item::new(1, 10)]);
+    assert!(pq.is_empty());
+}
+
+// =============================================================================
+// Bulk Removal Tests
+// =============================================================================
+
+#[test]
+fn test_remove_from_every_position() {
+    for d in [1, 2, 3, 4, 8] {
+        for target in 0..40 {
+            let mut pq = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            pq.insert_many((0..40).map(|i| Item::new(i, (i * 23) % 41)));
+
+            let removed = pq.remove(&Item::new(target, 0)).unwrap();
+            assert_eq!((removed.id, removed.cost), (target, (target * 23) % 41));
+            assert!(pq.remove(&Item::new(target, 0)).is_none());
+            assert_eq!(pq.len(), 39);
+            for (pos, item) in pq.iter_with_positions() {
+                assert_eq!(pq.get_position(item), Some(pos), "d={d} target={target}");
+            }
+            let rest = drain_costs(&mut pq);
+            assert!(
+                rest.windows(2).all(|w| w[0].0 <= w[1].0),
+                "d={d} target={target}"
+            );
+        }
+    }
+
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(pq.remove(&Item::new(1, 0)).is_none());
+    pq.insert(Item::new(1, 5));
+    assert_eq!(pq.remove(&Item::new(1, 0)).map(|x| x.cost), Some(5));
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_remove_many_small_and_large_batches() {
+    // Batch sizes on both sides of the n / log_d n rebuild threshold
+    for d in [1, 2, 3, 4, 8] {
+        for batch in [0, 1, 5, 20, 64, 150] {
+            let mut pq = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            pq.insert_many((0..64).map(|i| Item::new(i, (i * 37) % 101)));
+
+            // Repeats and ids past 63 (not in the heap) included
+            let targets: Vec<Item> = (0..batch).map(|k| Item::new((k * 13) % 90, 0)).collect();
+            let mut expected: Vec<u32> = Vec::new();
+            for t in &targets {
+                if t.id < 64 && !expected.contains(&t.id) {
+                    expected.push(t.id);
+                }
+            }
+
+            let removed = pq.remove_many(&targets);
+            let ids: Vec<u32> = removed.iter().map(|x| x.id).collect();
+            assert_eq!(ids, expected, "d={d} batch={batch}");
+            assert!(removed.iter().all(|x| x.cost == (x.id * 37) % 101));
+
+            assert_eq!(pq.len(), 64 - removed.len(), "d={d} batch={batch}");
+            for (pos, item) in pq.iter_with_positions() {
+                assert!(!expected.contains(&item.id));
+                assert_eq!(pq.get_position(item), Some(pos), "d={d} batch={batch}");
+            }
+            let rest = drain_costs(&mut pq);
+            assert!(
+                rest.windows(2).all(|w| w[0].0 <= w[1].0),
+                "d={d} batch={batch}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_remove_many_everything_and_nothing() {
+    let mut pq = PriorityQueue::new(4, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(pq.remove_many(&[Item::new(1, 0)]).is_empty());
+
+    pq.insert_many((0..30).map(|i| Item::new(i, 30 - i)));
+    let all: Vec<Item> = (0..30).map(|i| Item::new(i, 0)).collect();
+    assert_eq!(pq.remove_many(&all).len(), 30);
+    assert!(pq.is_empty());
+    assert_eq!(pq.peek(), None);
+
+    pq.insert(Item::new(7, 1));
+    assert_eq!(pq.front().id, 7);
+}
+
+#[test]
+fn test_drain_where_removes_matches_and_keeps_heap_valid() {
+    for d in [1, 2, 3, 4, 8] {
+        for modulus in [1, 2, 7, 1000] {
+            let mut pq = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+            pq.insert_many((0..64).map(|i| Item::new(i, (i * 37) % 101)));
+
+            let mut calls = 0;
+            let drained = pq.drain_where(|x| {
+                calls += 1;
+                x.id % modulus == 0
+            });
+            assert_eq!(calls, 64, "d={d} modulus={modulus}");
+            let mut ids: Vec<u32> = drained.iter().map(|x| x.id).collect();
+            ids.sort_unstable();
+            let expected: Vec<u32> = (0..64).filter(|i| i % modulus == 0).collect();
+            assert_eq!(ids, expected, "d={d} modulus={modulus}");
+
+            assert_eq!(pq.len(), 64 - drained.len());
+            for (pos, item) in pq.iter_with_positions() {
+                assert_ne!(item.id % modulus, 0);
+                assert_eq!(pq.get_position(item), Some(pos), "d={d} modulus={modulus}");
+            }
+            for item in &drained {
+                assert!(!pq.contains(item));
+            }
+            let rest = drain_costs(&mut pq);
+            assert!(
+                rest.windows(2).all(|w| w[0].0 <= w[1].0),
+                "d={d} modulus={modulus}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_drain_where_without_matches_leaves_heap_untouched() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(pq.drain_where(|_| true).is_empty());
+
+    pq.insert_many((0..20).map(|i| Item::new(i, 20 - i)));
+    let before = pq.to_array();
+    assert!(pq.drain_where(|x| x.cost > 100).is_empty());
+    let after = pq.to_array();
+    assert!(before
+        .iter()
+        .zip(&after)
+        .all(|(a, b)| a.id == b.id && a.cost == b.cost));
+}
+
+#[test]
+fn test_retain_keeps_matching_items() {
+    let mut pq = PriorityQueue::new(4, MaxBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..50).map(|i| Item::new(i, i)));
+    pq.retain(|x| x.cost % 5 == 0);
+    assert_eq!(pq.len(), 10);
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop()).map(|x| x.cost).collect();
+    assert_eq!(costs, vec![45, 40, 35, 30, 25, 20, 15, 10, 5, 0]);
+
+    pq.insert(Item::new(99, 7));
+    pq.retain(|_| false);
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_intersect_and_difference_match_by_identity() {
+    // The store ranks by id and holds different costs: only identity counts
+    let mut store = PriorityQueue::new(2, MaxBy(|x: &Item| x.id)).unwrap();
+    store.insert_many((0..60).step_by(3).map(|i| Item::new(i, 1_000)));
+
+    for d in [1, 2, 4, 8] {
+        let mut kept = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+        kept.insert_many((0..40).map(|i| Item::new(i, (i * 29) % 41)));
+        let mut rest = PriorityQueue::new(d, MinBy(|x: &Item| x.cost)).unwrap();
+        rest.insert_many((0..40).map(|i| Item::new(i, (i * 29) % 41)));
+
+        let dropped = kept.intersect_with(&store);
+        let removed = rest.difference_with(&store);
+        assert_eq!(kept.len(), 14, "d={d}");
+        assert_eq!(dropped.len() + removed.len(), 40, "d={d}");
+        assert!(dropped.iter().all(|x| x.id % 3 != 0));
+        assert!(removed.iter().all(|x| x.id % 3 == 0));
+
+        for (pos, item) in kept.iter_with_positions() {
+            assert_eq!(kept.get_position(item), Some(pos));
+        }
+        let costs = drain_costs(&mut kept);
+        assert!(costs.windows(2).all(|w| w[0].0 <= w[1].0), "d={d}");
+        assert!(costs
+            .iter()
+            .all(|&(cost, id)| id % 3 == 0 && cost == (id * 29) % 41));
+        assert!(drain_costs(&mut rest).iter().all(|&(_, id)| id % 3 != 0));
+    }
+
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..5).map(|i| Item::new(i, i)));
+    assert!(pq
+        .difference_with(&PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap())
+        .is_empty());
+    assert_eq!(
+        pq.intersect_with(&PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap())
+            .len(),
+        5
+    );
+    assert!(pq.is_empty());
+}
+
+// =============================================================================
+// Budgeted Pop Tests
+// =============================================================================
+
+#[test]
+fn test_pop_for_drains_in_order_within_generous_budget() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..50).map(|i| Item::new(i, (i * 17) % 50)));
+
+    let mut costs = Vec::new();
+    let n = pq.pop_for(Duration::from_secs(30), |item| {
+        costs.push(item.cost);
+        ControlFlow::Continue(())
+    });
+    assert_eq!(n, 50);
+    assert_eq!(costs, (0..50).collect::<Vec<u32>>());
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_pop_for_zero_budget_pops_nothing() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert(Item::new(1, 1));
+    let n = pq.pop_for(Duration::ZERO, |_| ControlFlow::Continue(()));
+    assert_eq!(n, 0);
+    assert_eq!(pq.len(), 1);
+}
+
+#[test]
+fn test_pop_for_stops_once_budget_is_spent() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..10).map(|i| Item::new(i, i)));
+
+    // Each item overruns the budget on its own: exactly one is processed
+    let n = pq.pop_for(Duration::from_millis(1), |_| {
+        std::thread::sleep(Duration::from_millis(5));
+        ControlFlow::Continue(())
+    });
+    assert_eq!(n, 1);
+    assert_eq!(pq.front().cost, 1);
+}
+
+#[test]
+fn test_pop_for_break_keeps_the_rest_queued() {
+    let mut pq = PriorityQueue::new(4, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..10).map(|i| Item::new(i, i)));
+    let n = pq.pop_for(Duration::from_secs(30), |item| {
+        if item.cost == 4 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert_eq!(n, 5);
+    assert_eq!(pq.len(), 5);
+    assert_eq!(pq.front().cost, 5);
+}
+
+// =============================================================================
+// Sift Primitive Tests
+// =============================================================================
+
+// Item whose cost lives behind a shared Cell, so it can change in place
+#[derive(Clone, Debug)]
+struct CellItem {
+    id: u32,
+    cost: Rc<Cell<u32>>,
+}
+
+impl PartialEq for CellItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CellItem {}
+
+impl Hash for CellItem {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+#[test]
+fn test_move_up_after_in_place_change() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &CellItem| x.cost.get())).unwrap();
+    let costs: Vec<Rc<Cell<u32>>> = (0..10).map(|c| Rc::new(Cell::new(c * 10 + 10))).collect();
+    for (id, cost) in (0u32..).zip(&costs) {
+        pq.insert(CellItem {
+            id,
+            cost: Rc::clone(cost),
+        });
+    }
+
+    let target = CellItem {
+        id: 9,
+        cost: Rc::clone(&costs[9]),
+    };
+    let pos = pq.get_position(&target).unwrap();
+    costs[9].set(1);
+    assert_eq!(pq.move_up(pos), Ok(Position::ROOT));
+    assert_eq!(pq.get_position(&target), Some(Position::ROOT));
+    assert_eq!(pq.front().id, 9);
+}
+
+#[test]
+fn test_move_down_after_in_place_change() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &CellItem| x.cost.get())).unwrap();
+    let costs: Vec<Rc<Cell<u32>>> = (0..7).map(|c| Rc::new(Cell::new(c * 10 + 10))).collect();
+    for (id, cost) in (0u32..).zip(&costs) {
+        pq.insert(CellItem {
+            id,
+            cost: Rc::clone(cost),
+        });
+    }
+
+    costs[0].set(1000);
+    let end = pq.move_down(Position::ROOT).unwrap();
+    assert!(end > Position::ROOT);
+    assert_eq!(
+        pq.get_position(&CellItem {
+            id: 0,
+            cost: Rc::clone(&costs[0])
+        }),
+        Some(end)
+    );
+    assert_eq!(pq.front().id, 1);
+
+    let popped: Vec<u32> = std::iter::from_fn(|| pq.pop()).map(|i| i.id).collect();
+    assert_eq!(popped, vec![1, 2, 3, 4, 5, 6, 0]);
+}
+
+#[test]
+fn test_move_up_down_out_of_bounds() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.move_up(Position::ROOT), Err(Error::IndexOutOfBounds));
+    assert_eq!(pq.move_down(Position::ROOT), Err(Error::IndexOutOfBounds));
+}
+
+#[test]
+fn test_best_child_position() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many(vec![
+        Item::new(0, 1),
+        Item::new(1, 30),
+        Item::new(2, 10),
+        Item::new(3, 20),
+        Item::new(4, 40),
+    ]);
+
+    let best = pq.best_child_position(Position::ROOT).unwrap();
+    assert_eq!(best.get_at(&pq).unwrap().id, 2);
+    assert_eq!(pq.best_child_position(Position::new(4)), None);
+    assert_eq!(pq.best_child_position(Position::new(99)), None);
+    assert_eq!(pq.best_child_position(Position::new(usize::MAX)), None);
+}
+
 // =============================================================================
 // Min/Max Heap Tests
 // =============================================================================
@@ -490,6 +1271,131 @@ fn test_max_heap() {
     }
 }
 
+// =============================================================================
+// Equality Tests
+// =============================================================================
+
+type CostHeap = PriorityQueue<Item, MinBy<fn(&Item) -> u32>>;
+
+fn cost_heap(d: usize, items: &[(u32, u32)]) -> CostHeap {
+    let mut pq =
+        PriorityQueue::new(d, MinBy((|item: &Item| item.cost) as fn(&Item) -> u32)).unwrap();
+    for &(id, cost) in items {
+        pq.insert(Item::new(id, cost));
+    }
+    pq
+}
+
+#[test]
+fn test_eq_ignores_insertion_order() {
+    let a = cost_heap(3, &[(1, 50), (2, 10), (3, 30), (4, 20), (5, 40)]);
+    let b = cost_heap(3, &[(4, 20), (5, 40), (1, 50), (3, 30), (2, 10)]);
+    assert_eq!(a, b);
+    assert_eq!(b, a);
+}
+
+#[test]
+fn test_eq_after_different_operations() {
+    let a = cost_heap(2, &[(1, 5), (2, 7), (3, 9)]);
+    let mut b = cost_heap(2, &[(4, 1), (3, 2), (2, 8)]);
+    b.pop();
+    b.increase_priority(&Item::new(2, 7)).unwrap();
+    b.insert(Item::new(1, 5));
+    b.update_priority(&Item::new(3, 9)).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_ne_different_arity() {
+    let items = [(1, 5), (2, 7), (3, 9)];
+    assert_ne!(cost_heap(2, &items), cost_heap(4, &items));
+}
+
+#[test]
+fn test_ne_different_priority_same_identity() {
+    let a = cost_heap(2, &[(1, 5), (2, 7)]);
+    let b = cost_heap(2, &[(1, 5), (2, 8)]);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_ne_different_items_or_len() {
+    let a = cost_heap(2, &[(1, 5), (2, 7)]);
+    assert_ne!(a, cost_heap(2, &[(1, 5), (3, 7)]));
+    assert_ne!(a, cost_heap(2, &[(1, 5)]));
+    assert_ne!(a, cost_heap(2, &[(1, 5), (2, 7), (3, 9)]));
+    assert_eq!(cost_heap(2, &[]), cost_heap(2, &[]));
+}
+
+#[test]
+fn test_eq_across_comparator_and_stats_types() {
+    let plain = cost_heap(3, &[(1, 5), (2, 7), (3, 9)]);
+    let mut instrumented = PriorityQueue::with_stats(3, MinBy(|item: &Item| item.cost)).unwrap();
+    instrumented.insert_many(vec![Item::new(3, 9), Item::new(1, 5), Item::new(2, 7)]);
+    let before = instrumented.stats().total();
+    assert!(plain == instrumented);
+    assert!(instrumented == plain);
+    // Equality does not count as heap work
+    assert_eq!(instrumented.stats().total(), before);
+}
+
+#[test]
+fn test_eq_is_usable_in_derived_eq() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct State {
+        depth: u32,
+        frontier: PriorityQueue<u32, MaxOrd>,
+    }
+
+    let state = |depth, items: &[u32]| State {
+        depth,
+        frontier: PriorityQueue::from_vec(items.to_vec(), 2, MaxOrd).unwrap(),
+    };
+    assert_eq!(state(1, &[3, 1, 2]), state(1, &[2, 3, 1]));
+    assert_ne!(state(1, &[3, 1, 2]), state(2, &[3, 1, 2]));
+    assert_ne!(state(1, &[3, 1, 2]), state(1, &[3, 1]));
+}
+
+#[test]
+fn test_diff_reports_each_kind_of_difference() {
+    let left = cost_heap(2, &[(1, 5), (2, 7), (3, 9), (4, 11)]);
+    let right = cost_heap(4, &[(5, 1), (4, 11), (2, 8), (3, 9), (6, 2)]);
+
+    let diff = left.diff(&right);
+    let ids = |items: &[&Item]| {
+        let mut ids: Vec<u32> = items.iter().map(|x| x.id).collect();
+        ids.sort_unstable();
+        ids
+    };
+    assert_eq!(ids(&diff.only_left), vec![1]);
+    assert_eq!(ids(&diff.only_right), vec![5, 6]);
+    assert_eq!(diff.changed.len(), 1);
+    let (a, b) = diff.changed[0];
+    assert_eq!((a.id, a.cost, b.cost), (2, 7, 8));
+    assert!(!diff.is_empty());
+    assert!(diff
+        .to_string()
+        .contains("~ Item { id: 2, cost: 7 } -> Item { id: 2, cost: 8 }"));
+
+    // The reverse diff swaps the sides
+    let back = right.diff(&left);
+    assert_eq!(ids(&back.only_left), vec![5, 6]);
+    assert_eq!(ids(&back.only_right), vec![1]);
+    assert_eq!(back.changed[0].0.cost, 8);
+}
+
+#[test]
+fn test_diff_is_empty_for_equal_contents_in_any_layout() {
+    let a = cost_heap(3, &[(1, 50), (2, 10), (3, 30), (4, 20)]);
+    let b = cost_heap(3, &[(4, 20), (3, 30), (2, 10), (1, 50)]);
+    assert!(a.diff(&b).is_empty());
+    assert_eq!(a.diff(&b).to_string(), "");
+    // Arity is not part of the diff, unlike `==`
+    let c = cost_heap(5, &[(4, 20), (3, 30), (2, 10), (1, 50)]);
+    assert_ne!(a, c);
+    assert!(a.diff(&c).is_empty());
+}
+
 // =============================================================================
 // Different Arities Tests
 // =============================================================================
@@ -576,6 +1482,32 @@ fn test_clear_invalid_arity() {
     assert_eq!(result, Err(Error::InvalidArity));
 }
 
+#[test]
+fn test_set_arity_keeps_items() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    for (i, cost) in [50, 30, 70, 10, 40, 60, 20, 80, 90, 5]
+        .into_iter()
+        .enumerate()
+    {
+        pq.insert(Item::new(u32::try_from(i).unwrap(), cost));
+    }
+
+    for d in [5, 1, 3] {
+        pq.set_arity(d).unwrap();
+        assert_eq!(pq.d(), d);
+        assert_eq!(pq.len(), 10);
+        for (position, item) in pq.iter_with_positions() {
+            assert_eq!(pq.get_position(item), Some(position));
+        }
+    }
+    assert_eq!(pq.set_arity(0), Err(Error::InvalidArity));
+    assert_eq!(pq.d(), 3);
+
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop().map(|x| x.cost)).collect();
+    assert_eq!(costs, [5, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+}
+
 // =============================================================================
 // String Representation Tests
 // =============================================================================
@@ -611,6 +1543,181 @@ fn test_display_trait() {
     assert_eq!(display_output, to_string_output);
 }
 
+#[test]
+fn test_format_with_matches_display_layout() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.format_with(|x| x.id.to_string()).to_string(), "{}");
+
+    pq.insert_many((1..=5).map(|i| Item::new(i, 50 - i)));
+    let ids: Vec<String> = pq.to_array().iter().map(|x| x.id.to_string()).collect();
+    let shown = pq.format_with(|x| x.id.to_string());
+    assert_eq!(shown.to_string(), format!("{{{}}}", ids.join(", ")));
+    assert_eq!(format!("{shown:?}"), shown.to_string());
+    // With the item's own Display as the formatter, it is the queue's Display
+    assert_eq!(
+        pq.format_with(ToString::to_string).to_string(),
+        pq.to_string()
+    );
+}
+
+// =============================================================================
+// Conversion Tests
+// =============================================================================
+
+#[test]
+fn test_from_vec_with_arity_and_comparator() {
+    let items: Vec<Item> = [(1, 40), (2, 10), (3, 30), (4, 20)]
+        .iter()
+        .map(|&(id, cost)| Item::new(id, cost))
+        .collect();
+    let mut pq = PriorityQueue::from_vec(items, 3, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.d(), 3);
+    assert_eq!(pq.get_position(&Item::new(2, 10)), Some(Position::ROOT));
+    pq.increase_priority(&Item::new(1, 5)).unwrap();
+    let ids: Vec<u32> = pq.pop_many(4).iter().map(|item| item.id).collect();
+    assert_eq!(ids, vec![1, 2, 4, 3]);
+}
+
+#[test]
+fn test_from_vec_invalid_arity() {
+    let result = PriorityQueue::from_vec(vec![1, 2], 0, MinBy(|x: &i32| *x));
+    assert_eq!(result.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn test_from_sorted_vec() {
+    for d in [1, 2, 3, 8] {
+        let mut pq =
+            PriorityQueue::from_sorted_vec(vec![9, 8, 7, 4, 2, 0], d, MaxBy(|x: &i32| *x / 2))
+                .unwrap();
+        assert_eq!(pq.len(), 6);
+        assert_eq!(pq.get_position(&4), Some(Position::new(3)));
+        pq.insert(5);
+        let popped: Vec<i32> = std::iter::from_fn(|| pq.pop()).map(|x| x / 2).collect();
+        assert_eq!(popped, vec![4, 4, 3, 2, 2, 1, 0], "d = {d}");
+    }
+}
+
+#[test]
+fn test_from_sorted_vec_matches_from_vec() {
+    let items: Vec<Item> = (0..100).map(|id| Item::new(id, id / 3)).collect();
+    let sorted =
+        PriorityQueue::from_sorted_vec(items.clone(), 4, MinBy(|x: &Item| x.cost)).unwrap();
+    let heapified = PriorityQueue::from_vec(items, 4, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(sorted, heapified);
+}
+
+#[test]
+fn test_from_sorted_vec_invalid_arity() {
+    let result = PriorityQueue::from_sorted_vec(vec![1, 2], 0, MinBy(|x: &i32| *x));
+    assert_eq!(result.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "not sorted")]
+fn test_from_sorted_vec_unsorted_asserts() {
+    let _ = PriorityQueue::from_sorted_vec(vec![1, 3, 2], 2, MinBy(|x: &i32| *x));
+}
+
+#[test]
+fn test_from_binary_heap_keeps_pop_order() {
+    let values: Vec<u32> = (0..200).map(|i| (i * 7919) % 1009).collect();
+    let mut std_heap = BinaryHeap::from(values.clone());
+    let mut pq: PriorityQueue<u32, MaxOrd> = BinaryHeap::from(values).into();
+    assert_eq!(pq.d(), DEFAULT_ARITY);
+    assert_eq!(pq.len(), std_heap.len());
+    while let Some(expected) = std_heap.pop() {
+        assert_eq!(pq.pop(), Some(expected));
+    }
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_from_vec_reverse_is_min_heap() {
+    let mut pq: PriorityQueue<Reverse<i32>, MaxOrd> =
+        vec![Reverse(4), Reverse(-2), Reverse(9)].into();
+    assert_eq!(pq.pop(), Some(Reverse(-2)));
+    assert_eq!(pq.pop(), Some(Reverse(4)));
+    assert_eq!(pq.pop(), Some(Reverse(9)));
+}
+
+#[test]
+fn test_collect_matches_from_vec() {
+    let values: Vec<u32> = (0..200).map(|i| (i * 7919) % 1009).collect();
+    let collected: PriorityQueue<u32, MaxOrd> = values.iter().copied().collect();
+    let from_vec: PriorityQueue<u32, MaxOrd> = values.into();
+    assert_eq!(collected.d(), DEFAULT_ARITY);
+    assert_eq!(collected.to_array(), from_vec.to_array());
+}
+
+#[test]
+fn test_extend_keeps_heap_order() {
+    // Small batches sift their items up; a large one rebuilds once. Both
+    // leave a valid heap whose positions match the array.
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.extend((0..100).map(|i| Item::new(i, (i * 37) % 100)));
+    pq.extend([Item::new(100, 50), Item::new(101, 0)]);
+    pq.extend(std::iter::empty());
+    assert_eq!(pq.len(), 102);
+    for (position, item) in pq.iter_with_positions() {
+        assert_eq!(pq.get_position(item), Some(position));
+    }
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop().map(|x| x.cost)).collect();
+    assert_eq!(costs.len(), 102);
+    assert!(costs.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn test_min_and_max_heap_aliases() {
+    let values: Vec<u32> = (0..100).map(|i| (i * 7919) % 1009).collect();
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+
+    let mut low: MinHeap<u32> = MinHeap::with_arity(3).unwrap();
+    low.insert_many(values.clone());
+    assert_eq!(low.d(), 3);
+    assert_eq!(low.pop_many(100), sorted);
+
+    let mut high = MaxHeap::with_arity(5).unwrap();
+    high.insert_many(values);
+    sorted.reverse();
+    assert_eq!(high.pop_many(100), sorted);
+
+    assert_eq!(
+        MinHeap::<u32>::with_arity(0).unwrap_err(),
+        Error::InvalidArity
+    );
+}
+
+#[test]
+fn test_into_vec_is_storage_order() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    pq.insert_many(vec![6, 2, 8, 4]);
+    let expected = pq.to_array();
+    assert_eq!(Vec::from(pq), expected);
+}
+
+#[test]
+fn test_into_vec_with_live_snapshot() {
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    pq.insert_many(vec![3, 1, 2]);
+    let pq = SnapshotPriorityQueue::new(pq);
+    let snapshot = pq.snapshot();
+    let items = Vec::from(pq.into_inner());
+    assert_eq!(items.len(), 3);
+    assert_eq!(snapshot.len(), 3);
+}
+
+#[test]
+fn test_into_binary_heap_round_trip() {
+    let pq: PriorityQueue<u32, MaxOrd> = vec![5, 3, 9, 1].into();
+    let std_heap = BinaryHeap::from(pq);
+    assert_eq!(std_heap.peek(), Some(&9));
+    let mut back: PriorityQueue<u32, MaxOrd> = std_heap.into();
+    assert_eq!(back.pop_many(4), vec![9, 5, 3, 1]);
+}
+
 // =============================================================================
 // to_array Tests
 // =============================================================================
@@ -636,6 +1743,114 @@ fn test_to_array_empty() {
     assert!(arr.is_empty());
 }
 
+#[test]
+fn test_as_slice_matches_to_array_and_positions() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    assert!(pq.as_slice().is_empty());
+
+    pq.insert_many((0..20).map(|i| Item::new(i, (i * 7) % 20)));
+    pq.pop();
+    let ids: Vec<u32> = pq.as_slice().iter().map(|x| x.id).collect();
+    let copied: Vec<u32> = pq.to_array().iter().map(|x| x.id).collect();
+    assert_eq!(ids, copied);
+    for (i, item) in pq.as_slice().iter().enumerate() {
+        assert_eq!(pq.get_position(item), Some(Position::new(i)));
+    }
+}
+
+#[test]
+fn test_clone_is_independent_of_the_original() {
+    let mut pq = PriorityQueue::with_stats(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..30).map(|i| Item::new(i, (i * 7) % 30)));
+    let before = pq.stats().total();
+
+    let mut fork = pq.clone();
+    assert_eq!(fork, pq);
+    assert_eq!(fork.to_array(), pq.to_array());
+    assert_eq!(fork.stats().total(), before);
+
+    // Mutating either side leaves the other as it was, positions included
+    fork.increase_priority(&Item::new(29, 0)).unwrap();
+    fork.pop();
+    pq.insert(Item::new(99, 100));
+    assert_eq!(fork.len(), 29);
+    assert_eq!(pq.len(), 31);
+    assert!(!fork.contains(&Item::new(99, 0)));
+    assert!(pq.contains(&Item::new(29, 0)));
+    for heap in [&pq, &fork] {
+        for (position, item) in heap.iter_with_positions() {
+            assert_eq!(heap.get_position(item), Some(position));
+        }
+    }
+    assert_eq!(pq.front().id, 0);
+    assert!(pq.stats().total() > before);
+    assert!(fork.stats().total() > before);
+}
+
+#[test]
+fn test_capacity_reserve_and_shrink() {
+    let mut pq = PriorityQueue::with_capacity(3, MinBy(|x: &Item| x.cost), 64).unwrap();
+    assert!(pq.capacity() >= 64);
+    assert!(pq.is_empty());
+    assert_eq!(pq.d(), 3);
+    assert_eq!(
+        PriorityQueue::with_capacity(0, MinBy(|x: &Item| x.cost), 8).unwrap_err(),
+        Error::InvalidArity
+    );
+
+    pq.insert_many((0..10).map(|i| Item::new(i, (i * 3) % 10)));
+    pq.reserve(1_000);
+    assert!(pq.capacity() >= 1_010);
+    let copy = pq.clone();
+    pq.shrink_to_fit();
+    assert!(pq.capacity() < 1_000);
+    assert_eq!(copy.len(), 10);
+
+    for (position, item) in pq.iter_with_positions() {
+        assert_eq!(pq.get_position(item), Some(position));
+    }
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop().map(|x| x.cost)).collect();
+    assert_eq!(costs, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_iterators_in_array_and_priority_order() {
+    let mut pq = PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..30).map(|i| Item::new(i, (i * 7) % 30)));
+
+    // Borrowing iteration walks the array and leaves the heap alone
+    let ids: Vec<u32> = pq.iter().map(|x| x.id).collect();
+    let by_ref: Vec<u32> = (&pq).into_iter().map(|x| x.id).collect();
+    let array: Vec<u32> = pq.as_slice().iter().map(|x| x.id).collect();
+    assert_eq!(ids, array);
+    assert_eq!(by_ref, array);
+    assert_eq!(pq.len(), 30);
+
+    // Draining pops in priority order and empties the heap even if stopped
+    // early; a clone keeps the items it copied
+    let snapshot = pq.clone();
+    let mut drain = pq.drain_sorted();
+    assert_eq!(drain.len(), 30);
+    let first: Vec<u32> = drain.by_ref().take(5).map(|x| x.cost).collect();
+    assert_eq!(first, [0, 1, 2, 3, 4]);
+    assert_eq!(drain.len(), 25);
+    drop(drain);
+    assert!(pq.is_empty());
+    assert_eq!(snapshot.len(), 30);
+
+    pq.insert_many((0..30).map(|i| Item::new(i, (i * 11) % 30)));
+    let sorted = pq.into_iter_sorted();
+    assert_eq!(sorted.len(), 30);
+    let costs: Vec<u32> = sorted.map(|x| x.cost).collect();
+    assert_eq!(costs, (0..30).collect::<Vec<_>>());
+
+    let mut pq = PriorityQueue::new(2, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..10).map(|i| Item::new(i, 9 - i)));
+    let array: Vec<u32> = pq.as_slice().iter().map(|x| x.id).collect();
+    let owned: Vec<u32> = pq.into_iter().map(|x| x.id).collect();
+    assert_eq!(owned, array);
+}
+
 // =============================================================================
 // Heap Property Maintenance Tests
 // =============================================================================
@@ -788,7 +2003,7 @@ fn test_position_type_alias() {
     pq.insert(Item::new(1, 10));
 
     let pos: Position = pq.get_position(&Item::new(1, 0)).unwrap();
-    assert_eq!(pos, 0);
+    assert_eq!(pos, Position::ROOT);
 }
 
 // =============================================================================
@@ -810,6 +2025,130 @@ fn test_error_display() {
         format!("{}", Error::EmptyQueue),
         "Operation called on empty priority queue"
     );
+    assert_eq!(
+        format!("{}", Error::InconsistentPositions),
+        "Position map does not match the container"
+    );
+    assert_eq!(
+        format!("{}", Error::HeapInvariantViolated),
+        "Heap invariant violated"
+    );
+    assert_eq!(
+        format!("{}", Error::Incomparable),
+        "Priorities cannot be compared"
+    );
+    assert_eq!(
+        format!("{}", Error::IdentityChanged),
+        "Adjustment changed the item's identity"
+    );
+}
+
+// =============================================================================
+// Raw Parts Tests
+// =============================================================================
+
+#[test]
+fn test_raw_parts_round_trip() {
+    let mut pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::new(3, MinBy(|x: &Item| x.cost)).unwrap();
+    pq.insert_many((0..20).map(|i| Item::new(i, (i * 7) % 20)));
+    let before = pq.to_array();
+
+    let (container, positions, d) = pq.into_raw_parts();
+    assert_eq!(container, before);
+    assert_eq!(d, 3);
+    for (i, item) in container.iter().enumerate() {
+        assert_eq!(positions.get(item, &container), Some(Position::new(i)));
+    }
+
+    let mut pq =
+        PriorityQueue::from_raw_parts(container, positions, d, MinBy(|x: &Item| x.cost)).unwrap();
+    assert_eq!(pq.len(), 20);
+    assert_eq!(pq.get_position(&Item::new(0, 0)), Some(Position::ROOT));
+    pq.increase_priority(&Item::new(19, 0)).unwrap();
+    let costs: Vec<u32> = std::iter::from_fn(|| pq.pop()).map(|i| i.cost).collect();
+    assert!(costs.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn test_from_raw_parts_empty() {
+    let pq: PriorityQueue<Item, MinBy<_>> =
+        PriorityQueue::from_raw_parts(Vec::new(), HashIndex::new(), 2, MinBy(|x: &Item| x.cost))
+            .unwrap();
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_from_raw_parts_invalid_arity() {
+    let result: Result<PriorityQueue<Item, MinBy<_>>, Error> =
+        PriorityQueue::from_raw_parts(Vec::new(), HashIndex::new(), 0, MinBy(|x: &Item| x.cost));
+    assert_eq!(result.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn test_from_raw_parts_inconsistent_positions() {
+    let container = vec![Item::new(1, 10), Item::new(2, 20)];
+    let cmp = || MinBy(|x: &Item| x.cost);
+
+    // Wrong index
+    let positions = HashMap::from([
+        (Item::new(1, 10), Position::ROOT),
+        (Item::new(2, 20), Position::ROOT),
+    ]);
+    let result = PriorityQueue::from_raw_parts(container.clone(), positions, 2, cmp());
+    assert_eq!(result.err(), Some(Error::InconsistentPositions));
+
+    // Missing entry
+    let positions = HashMap::from([(Item::new(1, 10), Position::ROOT)]);
+    let result = PriorityQueue::from_raw_parts(container.clone(), positions, 2, cmp());
+    assert_eq!(result.err(), Some(Error::InconsistentPositions));
+
+    // Extra entry
+    let positions = HashMap::from([
+        (Item::new(1, 10), Position::ROOT),
+        (Item::new(2, 20), Position::new(1)),
+        (Item::new(3, 30), Position::new(2)),
+    ]);
+    let result = PriorityQueue::from_raw_parts(container, positions, 2, cmp());
+    assert_eq!(result.err(), Some(Error::InconsistentPositions));
+
+    // Duplicate item in the container
+    let container = vec![Item::new(1, 10), Item::new(1, 10)];
+    let positions = HashMap::from([
+        (Item::new(1, 10), Position::ROOT),
+        (Item::new(2, 20), Position::new(1)),
+    ]);
+    let result = PriorityQueue::from_raw_parts(container, positions, 2, cmp());
+    assert_eq!(result.err(), Some(Error::InconsistentPositions));
+}
+
+#[test]
+fn test_from_raw_parts_heap_invariant_violated() {
+    let container = vec![
+        Item::new(1, 10),
+        Item::new(2, 30),
+        Item::new(3, 20),
+        Item::new(4, 25),
+    ];
+    let positions: HashMap<Item, Position> = container
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, item)| (item, Position::new(i)))
+        .collect();
+
+    // With arity 3 all three are children of the root: valid
+    let result = PriorityQueue::from_raw_parts(
+        container.clone(),
+        positions.clone(),
+        3,
+        MinBy(|x: &Item| x.cost),
+    );
+    assert!(result.is_ok());
+
+    // With arity 2, item 4 (cost 25) sits under item 2 (cost 30)
+    let result = PriorityQueue::from_raw_parts(container, positions, 2, MinBy(|x: &Item| x.cost));
+    assert_eq!(result.err(), Some(Error::HeapInvariantViolated));
 }
 
 // =============================================================================
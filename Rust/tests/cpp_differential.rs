@@ -0,0 +1,327 @@
+//! Differential tests against the C++ implementation.
+//!
+//! `Cpp/PriorityQueue.h` is compiled, once per test run, into a small driver
+//! (`tests/cpp_differential/driver.cpp`) that reads operations on stdin and
+//! prints what each one observes. The same seeded random operation streams
+//! are replayed on a Rust heap and the two transcripts must agree line for
+//! line: every result, every error message, and the whole heap array after
+//! every operation. Sharing the array, not just the pop order, pins down the
+//! tie-breaking among equal priorities the README promises is identical.
+//!
+//! Needs a C++23 compiler: `c++`, or the one named by `CXX`.
+//!
+//! ```text
+//! cargo test --features cpp-differential --test cpp_differential
+//! ```
+
+use d_ary_heap::{MaxBy, MinBy, Position, PriorityCompare, PriorityQueue};
+use std::fmt::Write as _;
+use std::hash::{Hash, Hasher};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// Identity is the id; the priority is what the heap orders by.
+#[derive(Debug, Clone, Copy)]
+struct Task {
+    id: u32,
+    priority: u32,
+}
+
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Task {}
+
+impl Hash for Task {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+fn show(task: Task) -> String {
+    format!("{}:{}", task.id, task.priority)
+}
+
+fn show_all(tasks: &[Task]) -> String {
+    let shown: Vec<String> = tasks.iter().copied().map(show).collect();
+    format!("[{}]", shown.join(" "))
+}
+
+fn show_result(result: Result<(), d_ary_heap::Error>) -> String {
+    result.map_or_else(|e| e.to_string(), |()| "ok".to_string())
+}
+
+/// Compiles the C++ driver into the test's scratch directory.
+fn driver() -> &'static Path {
+    static DRIVER: OnceLock<PathBuf> = OnceLock::new();
+    DRIVER.get_or_init(|| {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let binary = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cpp_differential_driver");
+        let compiler = std::env::var("CXX").unwrap_or_else(|_| "c++".to_string());
+        let output = Command::new(&compiler)
+            .arg("-std=c++23")
+            .arg("-O1")
+            .arg("-I")
+            .arg(manifest.join("../Cpp"))
+            .arg(manifest.join("tests/cpp_differential/driver.cpp"))
+            .arg("-o")
+            .arg(&binary)
+            .output()
+            .unwrap_or_else(|e| panic!("cannot run {compiler}: {e}"));
+        assert!(
+            output.status.success(),
+            "{compiler} failed to build the driver:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        binary
+    })
+}
+
+/// Runs the C++ driver on `ops` and returns its transcript.
+fn cpp_transcript(d: usize, order: &str, ops: &[String]) -> Vec<String> {
+    let mut child = Command::new(driver())
+        .arg(d.to_string())
+        .arg(order)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("driver starts");
+    let mut input = String::new();
+    for op in ops {
+        writeln!(input, "{op}").unwrap();
+    }
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "driver failed");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Xorshift generator: a fixed seed gives a fixed stream.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u32) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        // CAST: reduced below a u32 first.
+        #[allow(clippy::cast_possible_truncation)]
+        let r = (self.0 % u64::from(n)) as u32;
+        r
+    }
+
+    fn index(&mut self, len: usize) -> usize {
+        self.below(u32::try_from(len).unwrap()) as usize
+    }
+}
+
+/// Applies `steps` random operations to a fresh Rust heap and returns them
+/// with the transcript they produce. Each operation is drawn from the heap's
+/// current state, so priority changes go the direction they claim, and a
+/// few name absent ids or positions to exercise the error paths.
+///
+/// Priorities are drawn from a small range so ties are frequent.
+fn rust_transcript<C: PriorityCompare<Task>>(
+    d: usize,
+    comparator: C,
+    max_heap: bool,
+    seed: u64,
+    steps: usize,
+) -> (Vec<String>, Vec<String>) {
+    let mut heap = PriorityQueue::new(d, comparator).unwrap();
+    let mut rng = Rng(seed);
+    let mut next_id = 0;
+    let mut ops = Vec::with_capacity(steps);
+    let mut transcript = Vec::with_capacity(steps);
+
+    for _ in 0..steps {
+        let items = heap.to_array();
+        let roll = rng.below(100);
+        let (op, outcome) = match roll {
+            0..=29 => {
+                let task = Task {
+                    id: next_id,
+                    priority: rng.below(40),
+                };
+                next_id += 1;
+                heap.insert(task);
+                (
+                    format!("insert {}", show(task).replace(':', " ")),
+                    heap.len().to_string(),
+                )
+            }
+            30..=34 => {
+                let batch: Vec<Task> = (0..rng.below(9))
+                    .map(|k| Task {
+                        id: next_id + k,
+                        priority: rng.below(40),
+                    })
+                    .collect();
+                next_id += u32::try_from(batch.len()).unwrap();
+                let mut op = "insert_many".to_string();
+                for task in &batch {
+                    write!(op, " {} {}", task.id, task.priority).unwrap();
+                }
+                heap.insert_many(batch);
+                (op, heap.len().to_string())
+            }
+            35..=49 => (
+                "pop".to_string(),
+                heap.pop().map_or("none".to_string(), show),
+            ),
+            50..=54 => {
+                let count = rng.below(5) as usize;
+                (format!("pop_many {count}"), show_all(&heap.pop_many(count)))
+            }
+            55..=59 => (
+                "peek".to_string(),
+                heap.peek().copied().map_or("none".to_string(), show),
+            ),
+            60..=84 => change_priority(&mut heap, &mut rng, &items, next_id, max_heap),
+            85..=87 => {
+                let i = rng.index(items.len() + 2);
+                let result = heap.update_priority_by_index(Position::new(i));
+                (format!("update_at {i}"), show_result(result))
+            }
+            88..=92 => {
+                let id = rng.below(next_id + 1);
+                let probe = Task { id, priority: 0 };
+                (format!("contains {id}"), heap.contains(&probe).to_string())
+            }
+            93..=97 => {
+                let id = rng.below(next_id + 1);
+                let probe = Task { id, priority: 0 };
+                let position = heap.get_position(&probe);
+                (
+                    format!("position {id}"),
+                    position.map_or("none".to_string(), |p| p.index().to_string()),
+                )
+            }
+            _ => {
+                if rng.below(2) == 0 {
+                    ("clear".to_string(), show_result(heap.clear(None)))
+                } else {
+                    let d = rng.index(5);
+                    (format!("clear {d}"), show_result(heap.clear(Some(d))))
+                }
+            }
+        };
+        transcript.push(format!(
+            "{outcome} {} d={}",
+            show_all(&heap.to_array()),
+            heap.d()
+        ));
+        ops.push(op);
+    }
+    (ops, transcript)
+}
+
+/// Raises, lowers or updates the priority of a queued item, or of an absent
+/// id one time in ten.
+fn change_priority<C: PriorityCompare<Task>>(
+    heap: &mut PriorityQueue<Task, C>,
+    rng: &mut Rng,
+    items: &[Task],
+    next_id: u32,
+    max_heap: bool,
+) -> (String, String) {
+    let kind = ["increase", "decrease", "update"][rng.index(3)];
+    let target = if items.is_empty() || rng.below(10) == 0 {
+        Task {
+            id: next_id + rng.below(3),
+            priority: rng.below(40),
+        }
+    } else {
+        items[rng.index(items.len())]
+    };
+    let step = rng.below(15);
+    // "Higher priority" is a lower value in a min-heap
+    let raise = (kind == "increase") != max_heap;
+    let priority = match kind {
+        "update" => rng.below(40),
+        _ if raise => target.priority.saturating_sub(step),
+        _ => target.priority + step,
+    };
+    let updated = Task {
+        id: target.id,
+        priority,
+    };
+    let result = match kind {
+        "increase" => heap.increase_priority(&updated),
+        "decrease" => heap.decrease_priority(&updated),
+        _ => heap.update_priority(&updated),
+    };
+    (
+        format!("{kind} {} {priority}", target.id),
+        show_result(result),
+    )
+}
+
+fn assert_same(d: usize, order: &str, ops: &[String], rust: &[String]) {
+    let cpp = cpp_transcript(d, order, ops);
+    assert_eq!(cpp.len(), rust.len(), "d={d} {order}: transcript lengths");
+    if let Some(at) = (0..rust.len()).find(|&i| rust[i] != cpp[i]) {
+        let from = at.saturating_sub(5);
+        panic!(
+            "d={d} {order}: first divergence at operation {at}\n\
+             operations {from}..={at}: {:?}\n\
+             rust: {}\n\
+             c++:  {}",
+            &ops[from..=at],
+            rust[at],
+            cpp[at]
+        );
+    }
+}
+
+#[test]
+fn min_heaps_agree_with_cpp() {
+    for d in [1, 2, 3, 4, 7] {
+        for seed in 1..=4u64 {
+            let (ops, rust) = rust_transcript(
+                d,
+                MinBy(|t: &Task| t.priority),
+                false,
+                seed * 0x9e37_79b9,
+                2_000,
+            );
+            assert_same(d, "min", &ops, &rust);
+        }
+    }
+}
+
+#[test]
+fn max_heaps_agree_with_cpp() {
+    for d in [1, 2, 3, 4, 7] {
+        for seed in 1..=4u64 {
+            let (ops, rust) = rust_transcript(
+                d,
+                MaxBy(|t: &Task| t.priority),
+                true,
+                seed * 0x2545_f491,
+                2_000,
+            );
+            assert_same(d, "max", &ops, &rust);
+        }
+    }
+}
+
+#[test]
+fn invalid_arity_reports_the_same_error() {
+    let rust = PriorityQueue::new(0, MinBy(|t: &Task| t.priority)).unwrap_err();
+    assert_eq!(cpp_transcript(0, "min", &[]), [rust.to_string()]);
+}
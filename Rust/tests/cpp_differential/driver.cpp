@@ -0,0 +1,177 @@
+/////////////////////////////////////////////////////////////////////////////////////////////////////////////////////// driver.cpp
+///
+/// Differential-testing driver for the C++ d-ary heap priority queue
+///
+/// Reads one operation per line on stdin, applies it to a TOOLS::PriorityQueue
+/// and prints its observable outcome on stdout, in the transcript format the
+/// Rust side of tests/cpp_differential.rs writes for its own heap.
+///
+/// Usage: driver <d> <min|max>
+///
+/// Copyright (c) 2023-2026 Eric Jacopin
+///
+/// Licensed under the Apache License, Version 2.0 (the "License")
+///
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#include "PriorityQueue.h"
+#include <cstdint>
+#include <cstdlib>
+#include <iostream>
+#include <sstream>
+#include <string>
+#include <vector>
+
+using namespace TOOLS;
+
+// ----- Identity is the id; the priority is the payload the heap orders by
+struct Task
+{
+	uint32_t id;
+	uint32_t priority;
+
+	bool operator==(const Task& other) const { return id == other.id; }
+};
+
+struct TaskHash
+{
+	size_t operator()(const Task& t) const { return std::hash<uint32_t>()(t.id); }
+};
+
+// ----- Min- and max-heap on the priority, as the Rust side's MinBy and MaxBy
+struct LowestFirst
+{
+	bool operator()(const Task& a, const Task& b) const { return a.priority < b.priority; }
+};
+
+struct HighestFirst
+{
+	bool operator()(const Task& a, const Task& b) const { return a.priority > b.priority; }
+};
+
+std::string show(const Task& t)
+{
+	return std::to_string(t.id) + ":" + std::to_string(t.priority);
+}
+
+std::string show(const std::vector<Task>& tasks)
+{
+	std::string out = "[";
+	for (size_t i = 0; i < tasks.size(); ++i)
+	{
+		if (i > 0) out += " ";
+		out += show(tasks[i]);
+	}
+	return out + "]";
+}
+
+std::string show(const std::expected<void, Error>& result)
+{
+	return result ? "ok" : to_string(result.error());
+}
+
+template <typename TComparisonPredicate>
+int run(size_t d)
+{
+	using Heap = PriorityQueue<Task, TaskHash, TComparisonPredicate>;
+
+	auto created = Heap::create(d);
+	if (!created)
+	{
+		std::cout << to_string(created.error()) << std::endl;
+		return 0;
+	}
+	Heap heap = std::move(*created);
+
+	std::string line;
+	while (std::getline(std::cin, line))
+	{
+		std::istringstream in(line);
+		std::string op;
+		in >> op;
+		std::string outcome;
+
+		if (op == "insert")
+		{
+			Task t{};
+			in >> t.id >> t.priority;
+			heap.insert(t);
+			outcome = std::to_string(heap.len());
+		}
+		else if (op == "insert_many")
+		{
+			std::vector<Task> batch;
+			Task t{};
+			while (in >> t.id >> t.priority) batch.push_back(t);
+			heap.insert_many(batch);
+			outcome = std::to_string(heap.len());
+		}
+		else if (op == "pop")
+		{
+			auto t = heap.pop_front();
+			outcome = t ? show(*t) : "none";
+		}
+		else if (op == "pop_many")
+		{
+			size_t count = 0;
+			in >> count;
+			outcome = show(heap.pop_many(count));
+		}
+		else if (op == "peek")
+		{
+			auto t = heap.peek();
+			outcome = t ? show(*t) : "none";
+		}
+		else if (op == "increase" || op == "decrease" || op == "update")
+		{
+			Task t{};
+			in >> t.id >> t.priority;
+			if (op == "increase") outcome = show(heap.try_increase_priority(t));
+			else if (op == "decrease") outcome = show(heap.try_decrease_priority(t));
+			else outcome = show(heap.try_update_priority(t));
+		}
+		else if (op == "update_at")
+		{
+			typename Heap::Position i = 0;
+			in >> i;
+			outcome = show(heap.update_priority_by_index(i));
+		}
+		else if (op == "contains")
+		{
+			Task t{};
+			in >> t.id;
+			outcome = heap.contains(t) ? "true" : "false";
+		}
+		else if (op == "position")
+		{
+			Task t{};
+			in >> t.id;
+			auto p = heap.get_position(t);
+			outcome = p ? std::to_string(*p) : "none";
+		}
+		else if (op == "clear")
+		{
+			size_t d = 0;
+			outcome = (in >> d) ? show(heap.try_clear(d)) : show(heap.try_clear());
+		}
+		else
+		{
+			std::cerr << "unknown operation: " << line << std::endl;
+			return 2;
+		}
+
+		std::cout << outcome << " " << show(heap.to_array()) << " d=" << heap.d() << std::endl;
+	}
+	return 0;
+}
+
+int main(int argc, char* argv[])
+{
+	if (argc != 3)
+	{
+		std::cerr << "usage: driver <d> <min|max>" << std::endl;
+		return 2;
+	}
+	size_t d = std::strtoul(argv[1], nullptr, 10);
+	return std::string(argv[2]) == "max" ? run<HighestFirst>(d) : run<LowestFirst>(d);
+}
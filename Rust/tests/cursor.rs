@@ -0,0 +1,160 @@
+//! Integration tests for read-only tree navigation (`Cursor`, `levels()`,
+//! `iter_with_positions()`).
+//!
+//! A cursor is pure index arithmetic over the backing array, so these tests
+//! check it against `to_array()` and the `i·d + 1 ..= i·d + d` layout across
+//! several arities, including the partially-filled last internal node.
+
+use d_ary_heap::{Cursor, MinBy, Position, PriorityQueue};
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+
+fn min_heap(d: usize, n: i32) -> PriorityQueue<i32, IdentityMinBy> {
+    let mut pq = PriorityQueue::new(d, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap();
+    pq.insert_many((0..n).rev());
+    pq
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+#[test]
+fn cursor_at_bounds() {
+    let pq = min_heap(3, 5);
+    assert!(pq.cursor_at(Position::ROOT).is_some());
+    assert!(pq.cursor_at(Position::new(4)).is_some());
+    assert!(pq.cursor_at(Position::new(5)).is_none());
+    assert!(min_heap(3, 0).cursor_at(Position::ROOT).is_none());
+}
+
+#[test]
+fn walk_visits_every_node_once_in_heap_order() {
+    for d in [1, 2, 3, 4, 7] {
+        let pq = min_heap(d, 50);
+        let mut seen = vec![false; pq.len()];
+        let mut stack = vec![pq.cursor_at(Position::ROOT).unwrap()];
+        while let Some(node) = stack.pop() {
+            assert!(
+                !seen[node.position().index()],
+                "d={d}: revisited {}",
+                node.position()
+            );
+            seen[node.position().index()] = true;
+            for child in node.children() {
+                assert!(node.item() <= child.item(), "d={d}: heap order");
+                assert_eq!(child.parent().unwrap().position(), node.position());
+                assert_eq!(child.level(), node.level() + 1);
+                stack.push(child);
+            }
+        }
+        assert!(seen.iter().all(|&s| s), "d={d}: unreached nodes");
+    }
+}
+
+#[test]
+fn children_match_array_layout() {
+    let d = 3;
+    let pq = min_heap(d, 8);
+    let arr = pq.to_array();
+
+    let root_children: Vec<usize> = pq
+        .cursor_at(Position::ROOT)
+        .unwrap()
+        .children()
+        .map(|c| c.position().index())
+        .collect();
+    assert_eq!(root_children, vec![1, 2, 3]);
+
+    // Position 1 has children 4..=6 but position 2 only has 7
+    let second = pq.cursor_at(Position::new(2)).unwrap();
+    assert_eq!(second.children().len(), 1);
+    let only = second.children().next().unwrap();
+    assert_eq!(only.item(), &arr[7]);
+    assert!(pq.cursor_at(Position::new(3)).unwrap().is_leaf());
+}
+
+#[test]
+fn root_has_no_parent() {
+    let pq = min_heap(2, 3);
+    let root: Cursor<'_, i32> = pq.cursor_at(Position::ROOT).unwrap();
+    assert!(root.is_root());
+    assert!(root.parent().is_none());
+    assert_eq!(root.level(), 0);
+    assert_eq!(root.item(), pq.front());
+}
+
+#[test]
+fn unary_heap_is_a_chain() {
+    let pq = min_heap(1, 6);
+    let mut node = pq.cursor_at(Position::ROOT).unwrap();
+    for expected in 1..6 {
+        assert_eq!(node.children().len(), 1);
+        node = node.children().next().unwrap();
+        assert_eq!(node.level(), expected);
+    }
+    assert!(node.is_leaf());
+}
+
+#[test]
+fn levels_of_empty_heap() {
+    assert_eq!(min_heap(4, 0).levels().count(), 0);
+}
+
+#[test]
+fn levels_partition_the_array() {
+    for d in [1, 2, 3, 4, 7] {
+        for n in [1, 2, 5, 13, 40, 57] {
+            let pq = min_heap(d, n);
+            let levels: Vec<&[i32]> = pq.levels().collect();
+            assert_eq!(levels.concat(), pq.to_array(), "d={d} n={n}");
+
+            // Every level but the last is full: d^k items
+            let mut width = 1;
+            for (k, level) in levels.iter().enumerate() {
+                if k + 1 < levels.len() {
+                    assert_eq!(level.len(), width, "d={d} n={n} k={k}");
+                } else {
+                    assert!(!level.is_empty() && level.len() <= width);
+                }
+                width *= d;
+            }
+        }
+    }
+}
+
+#[test]
+fn levels_agree_with_cursor_level() {
+    let d = 3;
+    let pq = min_heap(d, 30);
+    let mut position = 0;
+    for (k, level) in pq.levels().enumerate() {
+        for item in level {
+            let node = pq.cursor_at(Position::new(position)).unwrap();
+            assert_eq!(node.item(), item);
+            assert_eq!(node.level(), k);
+            position += 1;
+        }
+    }
+}
+
+#[test]
+fn iter_with_positions_matches_get_position() {
+    for d in [1, 2, 3, 5] {
+        for n in [0, 1, 6, 31] {
+            let pq = min_heap(d, n);
+            let pairs: Vec<(Position, &i32)> = pq.iter_with_positions().collect();
+            assert_eq!(pairs.len(), pq.len(), "d={d} n={n}");
+            assert_eq!(pq.iter_with_positions().len(), pq.len());
+            let array = pq.to_array();
+            for (position, item) in pairs {
+                assert_eq!(pq.get_position(item), Some(position), "d={d} n={n}");
+                assert_eq!(array.get(position.index()), Some(item));
+                assert_eq!(position.get_at(&pq), Some(item));
+                assert_eq!(pq.cursor_at(position).unwrap().item(), item);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Integration tests for deferred heapification (`bulk_insert_deferred`).
+
+use d_ary_heap::{MaxBy, MinBy, Position, PriorityQueue, StatsCollector};
+
+type MinHeap = PriorityQueue<u32, MinBy<fn(&u32) -> u32>>;
+
+/// Pops all `len` items, checking they come out in ascending order.
+fn assert_drains_sorted(heap: &mut MinHeap, len: usize) {
+    let popped: Vec<u32> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(popped.len(), len);
+    assert!(popped.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity(x: &u32) -> u32 {
+    *x
+}
+
+fn min_heap(d: usize) -> MinHeap {
+    PriorityQueue::new(d, MinBy(identity as fn(&u32) -> u32)).unwrap()
+}
+
+/// Scrambled distinct values: multiplying by an odd constant is a bijection
+/// on `u32`.
+fn values(range: std::ops::Range<u32>) -> impl Iterator<Item = u32> {
+    range.map(|i| i.wrapping_mul(2_654_435_761))
+}
+
+#[test]
+fn restores_order_on_drop_into_an_empty_heap() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut heap = min_heap(d);
+        let mut batch = heap.bulk_insert_deferred();
+        for x in values(0..500) {
+            batch.push(x);
+        }
+        drop(batch);
+        assert_eq!(heap.len(), 500);
+        assert_drains_sorted(&mut heap, 500);
+    }
+}
+
+#[test]
+fn small_batch_into_a_large_heap_is_sifted_item_by_item() {
+    let mut heap = min_heap(4);
+    heap.insert_many(values(1..1001));
+    {
+        let mut batch = heap.bulk_insert_deferred();
+        batch.extend([0, 1]);
+    }
+    assert_eq!(heap.front(), &0);
+    assert_drains_sorted(&mut heap, 1002);
+}
+
+#[test]
+fn large_batch_into_a_small_heap_is_heapified() {
+    let mut heap = min_heap(3);
+    heap.insert_many([5, 9]);
+    heap.bulk_insert_deferred().extend(values(1000..1400));
+    assert_drains_sorted(&mut heap, 402);
+}
+
+#[test]
+fn appended_items_are_addressable_after_the_batch() {
+    let mut heap = PriorityQueue::new(2, MaxBy(|x: &(u32, u32)| x.1)).unwrap();
+    heap.insert((1, 10));
+    heap.bulk_insert_deferred().extend([(2, 30), (3, 20)]);
+    assert!(heap.contains(&(3, 20)));
+    assert_eq!(heap.get_position(&(2, 30)), Some(Position::ROOT));
+    assert_eq!(heap.pop(), Some((2, 30)));
+}
+
+#[test]
+fn pending_counts_only_this_batch() {
+    let mut heap = min_heap(2);
+    heap.insert_many([1, 2, 3]);
+    let mut batch = heap.bulk_insert_deferred();
+    assert_eq!(batch.pending(), 0);
+    batch.push(7);
+    batch.extend([8, 9]);
+    assert_eq!(batch.pending(), 3);
+}
+
+#[test]
+fn an_empty_batch_is_not_an_operation() {
+    let mut heap = PriorityQueue::with_stats(2, MinBy(|x: &u32| *x)).unwrap();
+    heap.insert_many([4, 2, 6]);
+    heap.stats().reset();
+    drop(heap.bulk_insert_deferred());
+    assert_eq!(heap.stats().total(), 0);
+}
+
+#[test]
+fn the_restore_is_counted_as_one_insert() {
+    let mut heap = PriorityQueue::with_profile(2, MinBy(|x: &u32| *x)).unwrap();
+    heap.insert_many([40, 50]);
+    heap.bulk_insert_deferred().extend([30, 10, 20]);
+    let records = heap.stats().records();
+    assert_eq!(records.len(), 2);
+    // The operation starts from the length before the batch
+    assert_eq!(records[1].len, 2);
+    assert!(records[1].comparisons > 0);
+}
@@ -0,0 +1,122 @@
+//! Integration tests for timed events with cancellation (`EventQueue`).
+
+use d_ary_heap::{Error, EventQueue};
+use std::time::{Duration, Instant};
+
+fn queue() -> EventQueue<u64, u32> {
+    EventQueue::new(4).unwrap()
+}
+
+#[test]
+fn events_leave_in_time_then_scheduling_order() {
+    let mut q = queue();
+    for (i, at) in [50, 10, 30, 10, 50, 20].into_iter().enumerate() {
+        q.schedule(at, u32::try_from(i).unwrap());
+    }
+    let order: Vec<(u64, u32)> = std::iter::from_fn(|| q.pop()).collect();
+    assert_eq!(
+        order,
+        [(10, 1), (10, 3), (20, 5), (30, 2), (50, 0), (50, 4)]
+    );
+    assert!(q.is_empty());
+}
+
+#[test]
+fn pop_due_stops_at_now() {
+    let mut q = queue();
+    q.schedule(5, 0);
+    q.schedule(15, 1);
+    assert_eq!(q.pop_due(4), None);
+    assert_eq!(q.pop_due(5), Some((5, 0)));
+    assert_eq!(q.pop_due(10), None);
+    assert_eq!(q.next_time(), Some(15));
+    assert_eq!(q.len(), 1);
+}
+
+#[test]
+fn token_cancel_removes_the_event() {
+    let mut q = queue();
+    let tokens: Vec<_> = (0..10)
+        .map(|i| q.schedule(i, u32::try_from(i).unwrap()))
+        .collect();
+    tokens[0].cancel();
+    tokens[5].cancel();
+    tokens[5].cancel(); // twice is harmless
+    assert_eq!(q.len(), 8);
+    assert_eq!(q.next_time(), Some(1));
+    let left: Vec<u32> = std::iter::from_fn(|| q.pop()).map(|(_, e)| e).collect();
+    assert_eq!(left, [1, 2, 3, 4, 6, 7, 8, 9]);
+}
+
+#[test]
+fn queue_cancel_returns_the_event() {
+    let mut q = queue();
+    let a = q.schedule(1, 100);
+    let b = q.schedule(2, 200);
+    assert_eq!(q.cancel(&b), Some(200));
+    assert_eq!(q.cancel(&b), None);
+    assert_eq!(q.pop(), Some((1, 100)));
+    // Cancelling a fired event does nothing
+    assert_eq!(q.cancel(&a), None);
+    a.cancel();
+    assert!(q.is_empty());
+}
+
+#[test]
+fn tokens_are_tied_to_their_queue() {
+    let mut first = queue();
+    let mut second = queue();
+    let token = first.schedule(1, 1);
+    second.schedule(1, 2);
+    assert_eq!(second.cancel(&token), None);
+    assert_eq!(second.len(), 1);
+    // A token outliving its queue is inert
+    drop(first);
+    token.cancel();
+    assert_eq!(second.pop(), Some((1, 2)));
+}
+
+#[test]
+fn timeouts_cancelled_from_another_thread_never_fire() {
+    let mut q = queue();
+    let tokens: Vec<_> = (0..1_000)
+        .map(|i| q.schedule(i, u32::try_from(i).unwrap()))
+        .collect();
+    std::thread::spawn(move || {
+        for token in tokens.iter().skip(1).step_by(2) {
+            token.cancel();
+        }
+    })
+    .join()
+    .unwrap();
+    assert_eq!(q.len(), 500);
+    assert!(std::iter::from_fn(|| q.pop()).all(|(_, e)| e % 2 == 0));
+}
+
+#[test]
+fn armed_and_cancelled_timeouts_do_not_accumulate() {
+    let mut q = queue();
+    let heartbeat = q.schedule(1_000_000, 0);
+    for round in 0..10_000 {
+        let timeout = q.schedule(round + 100, 1);
+        timeout.cancel();
+        q.pop_due(round);
+        assert!(q.len() <= 2);
+    }
+    assert_eq!(q.len(), 1);
+    assert_eq!(q.cancel(&heartbeat), Some(0));
+}
+
+#[test]
+fn instants_as_times() {
+    let start = Instant::now();
+    let mut q = EventQueue::new(2).unwrap();
+    q.schedule(start + Duration::from_millis(20), "late");
+    q.schedule(start + Duration::from_millis(5), "early");
+    assert_eq!(q.pop_due(start), None);
+    assert_eq!(q.pop().map(|(_, e)| e), Some("early"));
+    assert_eq!(
+        EventQueue::<u64, ()>::new(0).err(),
+        Some(Error::InvalidArity)
+    );
+}
@@ -0,0 +1,136 @@
+//! Integration tests for explain mode (`ExplainTrace`).
+//!
+//! The trace is a `StatsCollector`, so the same heap code drives it; these
+//! tests pin down the recorded swap sequences, the JSON shape consumed by the
+//! TypeScript playground, and agreement with `ComparisonStats` counts.
+
+use d_ary_heap::{
+    ExplainedPriorityQueue, MinBy, OperationType, Position, PriorityQueue, StatsCollector,
+};
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+type TestHeap = ExplainedPriorityQueue<i32, IdentityMinBy>;
+
+fn fresh_explained_min_heap(d: usize) -> TestHeap {
+    PriorityQueue::with_explain(d, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap()
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+#[test]
+fn explain_rejects_zero_arity() {
+    assert!(PriorityQueue::with_explain(0, MinBy(identity_i32 as fn(&i32) -> i32)).is_err());
+}
+
+#[test]
+fn one_step_per_operation() {
+    let mut pq = fresh_explained_min_heap(2);
+    pq.insert(5);
+    pq.insert(3);
+    pq.insert(8);
+    pq.pop();
+    pq.decrease_priority_by_index(Position::ROOT).unwrap();
+
+    let ops: Vec<OperationType> = pq.stats().steps().iter().map(|s| s.operation).collect();
+    assert_eq!(
+        ops,
+        vec![
+            OperationType::Insert,
+            OperationType::Insert,
+            OperationType::Insert,
+            OperationType::Pop,
+            OperationType::DecreasePriority,
+        ]
+    );
+}
+
+#[test]
+fn insert_records_sift_up_swaps() {
+    let mut pq = fresh_explained_min_heap(2);
+    for v in [5, 7, 9, 1] {
+        pq.insert(v);
+    }
+    // 1 enters at position 3, climbs to 1 (parent of 3), then to the root.
+    let steps = pq.stats().take_steps();
+    assert_eq!(steps[3].swaps, vec![(3, 1), (1, 0)]);
+    assert_eq!(steps[3].comparisons, 2);
+    assert_eq!(pq.to_array(), vec![1, 5, 9, 7]);
+}
+
+#[test]
+fn pop_replay_matches_final_array() {
+    let mut pq = fresh_explained_min_heap(3);
+    for v in [4, 9, 2, 7, 1, 8, 3] {
+        pq.insert(v);
+    }
+    let before = pq.to_array();
+    pq.stats().reset();
+
+    assert_eq!(pq.pop(), Some(1));
+
+    let steps = pq.stats().take_steps();
+    assert_eq!(steps.len(), 1);
+    let step = &steps[0];
+    assert_eq!(step.operation, OperationType::Pop);
+    assert_eq!(step.swaps[0], (0, before.len() - 1));
+
+    let states = step.replay(&before);
+    assert_eq!(states.len(), step.swaps.len() + 1);
+    assert_eq!(states[0], before);
+    let last = states.last().unwrap();
+    assert_eq!(
+        last[before.len() - 1],
+        1,
+        "popped root parks in the last slot"
+    );
+    assert_eq!(&last[..before.len() - 1], pq.to_array().as_slice());
+}
+
+#[test]
+fn comparisons_match_comparison_stats() {
+    let input = [42, 17, 99, 3, 8, 25, 61, 5, 88, 1];
+    let mut explained = fresh_explained_min_heap(4);
+    let mut counted = PriorityQueue::with_stats(4, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap();
+    for v in input {
+        explained.insert(v);
+        counted.insert(v);
+    }
+    explained.pop();
+    counted.pop();
+    explained.update_priority(&61).unwrap();
+    counted.update_priority(&61).unwrap();
+
+    assert_eq!(explained.stats().total(), counted.stats().total());
+    assert_eq!(explained.to_array(), counted.to_array());
+}
+
+#[test]
+fn json_shape() {
+    let mut pq = fresh_explained_min_heap(2);
+    pq.insert(2);
+    pq.insert(1);
+    pq.pop();
+    assert_eq!(
+        pq.stats().to_json(),
+        concat!(
+            r#"[{"operation":"insert","comparisons":0,"swaps":[]},"#,
+            r#"{"operation":"insert","comparisons":1,"swaps":[[1,0]]},"#,
+            r#"{"operation":"pop","comparisons":0,"swaps":[[0,1]]}]"#
+        )
+    );
+}
+
+#[test]
+fn reset_clears_trace() {
+    let mut pq = fresh_explained_min_heap(2);
+    pq.insert(1);
+    pq.insert(0);
+    pq.stats().reset();
+    assert!(pq.stats().steps().is_empty());
+    assert_eq!(pq.stats().total(), 0);
+    assert_eq!(pq.stats().to_json(), "[]");
+}
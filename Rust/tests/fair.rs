@@ -0,0 +1,151 @@
+//! Integration tests for `FairQueue` (weighted deficit round robin).
+//!
+//! Shares are checked over whole rounds, where deficit round robin is exact,
+//! and starvation-freedom is checked against a class whose items all have
+//! worse priority than everything else queued.
+
+use d_ary_heap::{ClassStats, Error, FairQueue, MinBy};
+use std::num::NonZeroU32;
+
+type IdentityMinBy = MinBy<fn(&u32) -> u32>;
+
+fn fair(d: usize) -> FairQueue<&'static str, u32, IdentityMinBy> {
+    FairQueue::new(d, MinBy(identity_u32 as fn(&u32) -> u32)).unwrap()
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_u32(x: &u32) -> u32 {
+    *x
+}
+
+fn weight(w: u32) -> NonZeroU32 {
+    NonZeroU32::new(w).unwrap()
+}
+
+#[test]
+fn invalid_arity() {
+    let result = FairQueue::<u8, u32, _>::new(0, MinBy(identity_u32 as fn(&u32) -> u32));
+    assert_eq!(result.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn push_to_unknown_class_fails() {
+    let mut q = fair(2);
+    assert_eq!(q.push(&"nobody", 1), Err(Error::ItemNotFound));
+    assert!(q.is_empty());
+    assert_eq!(q.stats(&"nobody"), None);
+}
+
+#[test]
+fn shares_follow_weights_over_full_rounds() {
+    let mut q = fair(4);
+    q.add_class("a", weight(5));
+    q.add_class("b", weight(3));
+    q.add_class("c", weight(2));
+    for i in 0..1000 {
+        for class in ["a", "b", "c"] {
+            q.push(&class, i).unwrap();
+        }
+    }
+
+    // 100 rounds of 5 + 3 + 2 pops, all classes backlogged throughout
+    let mut served = [0u32; 3];
+    for _ in 0..1000 {
+        let (class, _) = q.pop().unwrap();
+        served[["a", "b", "c"].iter().position(|&c| c == class).unwrap()] += 1;
+    }
+    assert_eq!(served, [500, 300, 200]);
+    assert_eq!(q.stats(&"b").unwrap().turns, 100);
+}
+
+#[test]
+fn items_leave_each_class_in_priority_order() {
+    let mut q = fair(3);
+    q.add_class("x", weight(2));
+    q.add_class("y", weight(1));
+    for v in [9, 2, 7, 4] {
+        q.push(&"x", v).unwrap();
+        q.push(&"y", v + 100).unwrap();
+    }
+
+    let mut x = Vec::new();
+    let mut y = Vec::new();
+    while let Some((class, v)) = q.pop() {
+        if class == "x" {
+            x.push(v);
+        } else {
+            y.push(v);
+        }
+    }
+    assert_eq!(x, vec![2, 4, 7, 9]);
+    assert_eq!(y, vec![102, 104, 107, 109]);
+}
+
+#[test]
+fn low_weight_class_is_never_starved() {
+    let mut q = fair(2);
+    q.add_class("bulk", weight(50));
+    q.add_class("rare", weight(1));
+    q.push(&"rare", u32::MAX).unwrap();
+    for i in 0..500 {
+        q.push(&"bulk", i).unwrap();
+    }
+
+    // "rare" joined the rotation first, so it is served within one round
+    let position = std::iter::from_fn(|| q.pop())
+        .position(|(class, _)| class == "rare")
+        .unwrap();
+    assert!(position <= 50, "served after {position} pops");
+}
+
+#[test]
+fn emptied_class_forfeits_its_turn_and_rejoins_at_the_back() {
+    let mut q = fair(2);
+    q.add_class("a", weight(3));
+    q.add_class("b", weight(3));
+    q.push(&"a", 1).unwrap();
+    for v in 0..6 {
+        q.push(&"b", v).unwrap();
+    }
+
+    assert_eq!(q.pop(), Some(("a", 1)));
+    assert_eq!(q.class_len(&"a"), Some(0));
+    q.push(&"a", 2).unwrap();
+
+    let order: Vec<&str> = std::iter::from_fn(|| q.pop()).map(|(c, _)| c).collect();
+    assert_eq!(order, ["b", "b", "b", "a", "b", "b", "b"]);
+}
+
+#[test]
+fn peek_matches_pop_and_stats_add_up() {
+    let mut q = fair(4);
+    q.add_class("a", weight(2));
+    q.add_class("b", weight(1));
+    for v in 0..5 {
+        q.push(&"a", v).unwrap();
+        q.push(&"b", v).unwrap();
+    }
+    assert_eq!(q.len(), 10);
+
+    while let Some((&class, &v)) = q.peek() {
+        assert_eq!(q.pop(), Some((class, v)));
+    }
+    assert!(q.is_empty());
+    for class in ["a", "b"] {
+        let ClassStats {
+            enqueued, dequeued, ..
+        } = q.stats(&class).unwrap();
+        assert_eq!((enqueued, dequeued), (5, 5));
+    }
+}
+
+#[test]
+fn reweighting_keeps_queued_items() {
+    let mut q = fair(2);
+    q.add_class("a", weight(1));
+    q.push(&"a", 3).unwrap();
+    q.add_class("a", weight(4));
+    assert_eq!(q.weight(&"a"), Some(weight(4)));
+    assert_eq!(q.class_len(&"a"), Some(1));
+}
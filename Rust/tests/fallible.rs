@@ -0,0 +1,180 @@
+//! Integration tests for fallible comparators (`TryPriorityCompare`,
+//! `TryPriorityQueue`).
+
+use d_ary_heap::{
+    Error, MaxBy, MinBy, PriorityCompare, PriorityQueue, TryMaxBy, TryMinBy, TryPriorityCompare,
+    TryPriorityQueue,
+};
+use std::hash::{Hash, Hasher};
+
+/// A job costed in floating point, identified by id.
+#[derive(Debug, Clone, Copy)]
+struct Job {
+    id: u32,
+    cost: f64,
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Job {}
+
+impl Hash for Job {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+fn job(id: u32, cost: f64) -> Job {
+    Job { id, cost }
+}
+
+fn ids(jobs: &[Job]) -> Vec<u32> {
+    jobs.iter().map(|j| j.id).collect()
+}
+
+/// Pseudo-random values in `0..modulus`, from a fixed seed.
+fn values(seed: u64, len: usize, modulus: u64) -> Vec<u32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            u32::try_from(state % modulus).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn invalid_arity_is_rejected() {
+    let pq = TryPriorityQueue::new(0, TryMinBy(|j: &Job| j.cost));
+    assert_eq!(pq.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn comparable_priorities_behave_like_a_plain_heap() {
+    // With no NaN the fallible heap must make the very same moves as a
+    // PriorityQueue. The bits of a non-negative f64 order like its value.
+    for d in [1, 2, 3, 5] {
+        let mut fallible = TryPriorityQueue::new(d, TryMinBy(|j: &Job| j.cost)).unwrap();
+        let mut plain = PriorityQueue::new(d, MinBy(|j: &Job| j.cost.to_bits())).unwrap();
+        let draws = values(d as u64 * 7919, 3_000, 50);
+        for (step, draw) in draws.chunks(3).enumerate() {
+            let id = u32::try_from(step).unwrap();
+            let cost = f64::from(draw[2]);
+            match draw[0] % 4 {
+                0 | 1 => {
+                    fallible.insert(job(id, cost)).unwrap();
+                    plain.insert(job(id, cost));
+                }
+                2 => {
+                    let popped = fallible.pop().unwrap().map(|j| j.id);
+                    assert_eq!(popped, plain.pop().map(|j| j.id));
+                }
+                _ if !plain.is_empty() => {
+                    let target = plain.to_array()[draw[1] as usize % plain.len()].id;
+                    fallible.update_priority(&job(target, cost)).unwrap();
+                    plain.update_priority(&job(target, cost)).unwrap();
+                }
+                _ => {}
+            }
+            assert_eq!(
+                ids(&fallible.to_array()),
+                ids(&plain.to_array()),
+                "d={d} step={step}"
+            );
+        }
+    }
+}
+
+#[test]
+fn nan_is_rejected_and_leaves_the_heap_unchanged() {
+    let mut pq = TryPriorityQueue::new(3, TryMaxBy(|j: &Job| j.cost)).unwrap();
+    for (id, cost) in [(1, 4.0), (2, 9.0), (3, 1.5), (4, 7.0), (5, 3.0)] {
+        pq.insert(job(id, cost)).unwrap();
+    }
+    let before = ids(&pq.to_array());
+
+    assert_eq!(pq.insert(job(6, f64::NAN)), Err(Error::Incomparable));
+    assert!(!pq.contains(&job(6, 0.0)));
+    assert_eq!(
+        pq.update_priority(&job(4, f64::NAN)),
+        Err(Error::Incomparable)
+    );
+    assert_eq!(pq.update_priority(&job(9, 1.0)), Err(Error::ItemNotFound));
+    assert_eq!(ids(&pq.to_array()), before);
+    assert!(pq.to_array().iter().all(|j| !j.cost.is_nan()));
+
+    // Still a working max-heap
+    let mut drained = Vec::new();
+    while let Some(j) = pq.pop().unwrap() {
+        drained.push(j.id);
+    }
+    assert_eq!(drained, [2, 4, 1, 5, 3]);
+    assert_eq!(pq.pop(), Ok(None));
+}
+
+/// Bit sets ranked by strict inclusion: a superset comes first, and two
+/// sets neither of which contains the other cannot be ranked.
+struct Inclusion;
+
+impl TryPriorityCompare<u8> for Inclusion {
+    fn try_higher_priority(&self, a: &u8, b: &u8) -> Option<bool> {
+        if a & b == *b {
+            Some(a != b)
+        } else if a & b == *a {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn incomparable_siblings_fail_the_pop_that_needs_them() {
+    let mut pq = TryPriorityQueue::new(2, Inclusion).unwrap();
+    pq.insert(0b111).unwrap();
+    // Each is only compared with the root, which contains both
+    pq.insert(0b001).unwrap();
+    pq.insert(0b010).unwrap();
+    assert_eq!(pq.len(), 3);
+
+    // Popping the root makes {0b001} and {0b010} compete for it
+    assert_eq!(pq.pop(), Err(Error::Incomparable));
+    assert_eq!(pq.to_array(), [0b111, 0b001, 0b010]);
+    assert_eq!(pq.peek(), Some(&0b111));
+
+    // Rejected on the way up: 0b100 is incomparable with its parent 0b001
+    assert_eq!(pq.insert(0b100), Err(Error::Incomparable));
+    // Whereas 0b011 contains 0b001 and rises past it
+    pq.insert(0b011).unwrap();
+    assert_eq!(pq.to_array(), [0b111, 0b011, 0b010, 0b001]);
+    pq.clear();
+    assert!(pq.is_empty());
+    assert_eq!(pq.d(), 2);
+}
+
+#[test]
+fn try_comparators_rank_like_their_infallible_counterparts() {
+    let keys = values(42, 200, 1_000);
+    let min = TryMinBy(|x: &u32| f64::from(*x));
+    let max = TryMaxBy(|x: &u32| f64::from(*x));
+    let (plain_min, plain_max) = (MinBy(|x: &u32| *x), MaxBy(|x: &u32| *x));
+    for pair in keys.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        assert_eq!(
+            min.try_higher_priority(a, b),
+            Some(plain_min.higher_priority(a, b))
+        );
+        assert_eq!(
+            max.try_higher_priority(a, b),
+            Some(plain_max.higher_priority(a, b))
+        );
+    }
+    let nan = TryMinBy(|_: &u32| f64::NAN);
+    assert_eq!(nan.try_higher_priority(&1, &1), None);
+}
@@ -0,0 +1,117 @@
+//! Integration tests for sift histograms (`HistogramStats`).
+//!
+//! The histograms are cross-checked against quantities that can be derived
+//! independently: comparison counts from the embedded `ComparisonStats`, and
+//! the exact sift depths of hand-built workloads.
+
+use d_ary_heap::{
+    HistogramPriorityQueue, MinBy, OperationType, Position, PriorityQueue, SiftHistogram,
+    SiftHistograms, StatsCollector,
+};
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+
+fn fresh_min_heap_with_histograms(d: usize) -> HistogramPriorityQueue<i32, IdentityMinBy> {
+    PriorityQueue::with_histograms(d, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap()
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+#[test]
+fn initial_state_is_empty() {
+    let pq = fresh_min_heap_with_histograms(3);
+    assert_eq!(pq.stats().snapshot(), SiftHistograms::default());
+    assert_eq!(pq.stats().total(), 0);
+    assert_eq!(
+        pq.stats()
+            .histogram(OperationType::Pop)
+            .mean_sift_down_depth(),
+        None
+    );
+}
+
+#[test]
+fn descending_inserts_climb_to_the_root() {
+    // Each new minimum climbs all the way: depth == level of its slot.
+    let d = 2;
+    let mut pq = fresh_min_heap_with_histograms(d);
+    for v in (0..7).rev() {
+        pq.insert(v);
+    }
+    let insert = pq.stats().histogram(OperationType::Insert);
+    // Slots 0 | 1 2 | 3 4 5 6 sit at levels 0 | 1 | 2.
+    assert_eq!(insert.sift_up_depth, vec![1, 2, 4]);
+    assert!(insert.sift_down_depth.is_empty());
+    assert!(insert.children_scanned.is_empty());
+    assert_eq!(pq.stats().snapshot().pop, SiftHistogram::default());
+}
+
+#[test]
+fn pop_children_scanned_matches_comparisons() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut pq = fresh_min_heap_with_histograms(d);
+        for v in 0..200 {
+            pq.insert((v * 37) % 101);
+        }
+        while pq.pop().is_some() {}
+
+        let pop = pq.stats().histogram(OperationType::Pop);
+        assert_eq!(
+            pop.total_children_scanned(),
+            pq.stats().comparisons().pop(),
+            "d={d}"
+        );
+        // Every scan covers between 1 and d children.
+        assert!(pop.children_scanned.len() <= d + 1, "d={d}");
+        assert_eq!(pop.children_scanned.first().copied().unwrap_or(0), 0);
+        // One sift-down per pop that leaves a non-empty heap.
+        assert_eq!(pop.sift_down_depth.iter().sum::<u64>(), 199, "d={d}");
+    }
+}
+
+#[test]
+fn wider_heaps_sift_down_fewer_levels() {
+    let mean_depth = |d| {
+        let mut pq = fresh_min_heap_with_histograms(d);
+        for v in 0..500 {
+            pq.insert((v * 7919) % 1009);
+        }
+        while pq.pop().is_some() {}
+        pq.stats()
+            .histogram(OperationType::Pop)
+            .mean_sift_down_depth()
+            .unwrap()
+    };
+    assert!(mean_depth(8) < mean_depth(4));
+    assert!(mean_depth(4) < mean_depth(2));
+}
+
+#[test]
+fn priority_updates_land_in_their_own_bucket() {
+    let mut pq = fresh_min_heap_with_histograms(2);
+    pq.insert_many(vec![10, 20, 30, 40, 50, 60, 70]);
+    let before = pq.stats().snapshot();
+
+    // Nothing moves: the re-sift is still recorded, at depth 0.
+    pq.increase_priority_by_index(Position::new(6)).unwrap();
+    let after = pq.stats().snapshot();
+    assert_eq!(after.increase_priority.sift_up_depth, vec![1]);
+    assert_eq!(after.insert, before.insert);
+    assert_eq!(after.pop, before.pop);
+}
+
+#[test]
+fn reset_clears_histograms() {
+    let mut pq = fresh_min_heap_with_histograms(4);
+    for v in 0..10 {
+        pq.insert(v);
+    }
+    pq.pop();
+    pq.stats().reset();
+    assert_eq!(pq.stats().snapshot(), SiftHistograms::default());
+    assert_eq!(pq.stats().comparisons().total(), 0);
+}
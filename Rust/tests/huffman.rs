@@ -0,0 +1,98 @@
+//! Integration tests for Huffman coding (`huffman::code_table`).
+
+use d_ary_heap::huffman::code_table;
+use std::collections::{HashMap, VecDeque};
+
+/// Total encoded bits of `frequencies` under `table`.
+fn cost(table: &HashMap<u32, String>, frequencies: &[(u32, u64)]) -> u64 {
+    frequencies
+        .iter()
+        .map(|(s, w)| w * table[s].len() as u64)
+        .sum()
+}
+
+/// Optimal total bits by the two-queue method on sorted weights, which
+/// needs no heap: merged weights come out in non-decreasing order.
+fn optimal_cost(frequencies: &[(u32, u64)]) -> u64 {
+    let mut leaves: Vec<u64> = frequencies.iter().map(|&(_, w)| w).collect();
+    leaves.sort_unstable();
+    let mut leaves: VecDeque<u64> = leaves.into();
+    let mut merged = VecDeque::new();
+    let mut total = 0;
+    while leaves.len() + merged.len() > 1 {
+        let a = lighter_front(&mut leaves, &mut merged);
+        let b = lighter_front(&mut leaves, &mut merged);
+        total += a + b;
+        merged.push_back(a + b);
+    }
+    total
+}
+
+/// Pops the lighter of the two queues' fronts.
+fn lighter_front(leaves: &mut VecDeque<u64>, merged: &mut VecDeque<u64>) -> u64 {
+    match (leaves.front(), merged.front()) {
+        (Some(l), Some(m)) if m < l => merged.pop_front(),
+        (Some(_), _) => leaves.pop_front(),
+        (None, _) => merged.pop_front(),
+    }
+    .unwrap()
+}
+
+/// Scrambled weights in `1..=max` for symbols `0..n`.
+fn frequencies(n: u32, max: u64) -> Vec<(u32, u64)> {
+    (0..n)
+        .map(|s| (s, u64::from(s).wrapping_mul(2_654_435_761) % max + 1))
+        .collect()
+}
+
+#[test]
+fn codes_are_optimal() {
+    for (n, max) in [(2, 10), (3, 1), (17, 5), (100, 1_000), (1_000, 3)] {
+        let freqs = frequencies(n, max);
+        let table = code_table(freqs.iter().copied());
+        assert_eq!(table.len(), n as usize);
+        assert_eq!(cost(&table, &freqs), optimal_cost(&freqs), "n={n}");
+    }
+}
+
+#[test]
+fn codes_are_prefix_free() {
+    let table = code_table(frequencies(200, 50));
+    let mut codes: Vec<&String> = table.values().collect();
+    codes.sort();
+    for pair in codes.windows(2) {
+        assert!(!pair[1].starts_with(pair[0].as_str()), "{pair:?}");
+    }
+    assert!(codes
+        .iter()
+        .all(|c| c.chars().all(|b| b == '0' || b == '1')));
+}
+
+#[test]
+fn equal_weights_give_balanced_codes() {
+    let table = code_table((0..8u32).map(|s| (s, 7)));
+    assert!(table.values().all(|c| c.len() == 3));
+}
+
+#[test]
+fn repeated_symbols_add_up() {
+    let split = code_table([("x", 2), ("y", 3), ("x", 2), ("z", 1)]);
+    let summed = code_table([("x", 4), ("y", 3), ("z", 1)]);
+    assert_eq!(split, summed);
+    assert_eq!(split["x"], "0"); // ties the merged {z, y} and was seen first
+}
+
+#[test]
+fn degenerate_inputs() {
+    assert!(code_table(Vec::<(char, u64)>::new()).is_empty());
+    assert_eq!(code_table([('a', 9)])[&'a'], "0");
+    let with_zero = code_table([('a', 0), ('b', 0), ('c', 1)]);
+    assert_eq!(with_zero[&'c'], "1");
+    assert_eq!(with_zero[&'a'].len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "frequencies sum past u64::MAX")]
+fn overflowing_frequencies_panic() {
+    let _ = code_table([('a', u64::MAX), ('b', 1)]);
+}
@@ -6,7 +6,8 @@
 //! `assert_eq!` (Rust has no `static_assert` analog over `size_of`).
 
 use d_ary_heap::{
-    ComparisonStats, InstrumentedPriorityQueue, MinBy, NoOpStats, PriorityQueue, StatsCollector,
+    ComparisonStats, InstrumentedPriorityQueue, MinBy, NoOpStats, Position, PriorityQueue,
+    StatsCollector,
 };
 
 type IdentityMinBy = MinBy<fn(&i32) -> i32>;
@@ -82,7 +83,7 @@ fn decrease_priority_bucket() {
     }
     pq.stats().reset();
 
-    pq.decrease_priority_by_index(0)
+    pq.decrease_priority_by_index(Position::ROOT)
         .expect("index 0 is in bounds");
 
     let s = pq.stats();
@@ -104,7 +105,7 @@ fn increase_priority_bucket() {
     }
     pq.stats().reset();
 
-    pq.increase_priority_by_index(3)
+    pq.increase_priority_by_index(Position::new(3))
         .expect("index 3 is in bounds for a 4-element heap");
 
     let s = pq.stats();
@@ -177,9 +178,9 @@ fn total_equals_sum_of_buckets() {
     }
     pq.pop();
     pq.pop();
-    pq.decrease_priority_by_index(0).unwrap();
-    pq.update_priority_by_index(0).unwrap();
-    let last = pq.len() - 1;
+    pq.decrease_priority_by_index(Position::ROOT).unwrap();
+    pq.update_priority_by_index(Position::ROOT).unwrap();
+    let last = Position::new(pq.len() - 1);
     pq.increase_priority_by_index(last).unwrap();
 
     let s = pq.stats();
@@ -0,0 +1,161 @@
+//! Integration tests for queues with separate keys and priorities
+//! (`KeyedPriorityQueue`).
+
+use d_ary_heap::{Error, KeyedPriorityQueue, MaxOrd, MinBy, MinOrd};
+use std::collections::HashMap;
+
+/// Scrambled distinct values: multiplying by an odd constant is a bijection
+/// on `u32`.
+fn scrambled(i: u32) -> u32 {
+    i.wrapping_mul(2_654_435_761)
+}
+
+#[test]
+fn pops_keys_in_priority_order_after_changes() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut queue = KeyedPriorityQueue::new(d, MinBy(|p: &u32| *p)).unwrap();
+        for key in 0..500 {
+            assert_eq!(queue.insert(key, scrambled(key)), None);
+        }
+        // Move every third key, half of them up and half down
+        for key in (0..500).step_by(3) {
+            let new = scrambled(key + 1_000);
+            assert_eq!(queue.change_priority(&key, new), Ok(scrambled(key)));
+        }
+
+        let mut expected: Vec<(u32, u32)> = (0..500)
+            .map(|key| {
+                let p = if key % 3 == 0 { key + 1_000 } else { key };
+                (key, scrambled(p))
+            })
+            .collect();
+        expected.sort_by_key(|&(_, p)| p);
+        let popped: Vec<(u32, u32)> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(popped, expected, "d={d}");
+    }
+}
+
+#[test]
+fn insert_of_a_queued_key_changes_its_priority() {
+    let mut queue = KeyedPriorityQueue::new(2, MaxOrd).unwrap();
+    assert_eq!(queue.insert("a", 1), None);
+    assert_eq!(queue.insert("b", 5), None);
+    assert_eq!(queue.insert("a", 9), Some(1));
+
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.peek(), Some((&"a", &9)));
+    assert_eq!(queue.get_priority(&"b"), Some(&5));
+}
+
+#[test]
+fn missing_keys_are_reported() {
+    let mut queue = KeyedPriorityQueue::new(4, MaxOrd).unwrap();
+    queue.insert(1, 10);
+
+    assert_eq!(queue.change_priority(&2, 3), Err(Error::ItemNotFound));
+    assert_eq!(queue.get_priority(&2), None);
+    assert!(!queue.contains_key(&2));
+    assert_eq!(queue.remove(&2), None);
+    assert_eq!(queue.len(), 1);
+    assert!(KeyedPriorityQueue::<u32, u32, _>::new(0, MaxOrd).is_err());
+}
+
+#[test]
+fn remove_keeps_heap_order() {
+    let mut queue = KeyedPriorityQueue::new(3, MinBy(|p: &u32| *p)).unwrap();
+    for key in 0..200 {
+        queue.insert(key, scrambled(key));
+    }
+    for key in (0..200).filter(|k| k % 4 == 1) {
+        assert_eq!(queue.remove(&key), Some(scrambled(key)));
+        assert!(!queue.contains_key(&key));
+    }
+    assert_eq!(queue.iter().len(), 150);
+
+    let popped: Vec<u32> = std::iter::from_fn(|| queue.pop()).map(|(_, p)| p).collect();
+    assert_eq!(popped.len(), 150);
+    assert!(popped.windows(2).all(|w| w[0] <= w[1]));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn converts_from_and_back_into_a_hash_map() {
+    let priorities: HashMap<u32, u32> = (0..300).map(|key| (key, scrambled(key))).collect();
+    let mut queue: KeyedPriorityQueue<u32, u32, MinOrd> = priorities.clone().into();
+    assert_eq!(queue.len(), 300);
+    assert_eq!(queue.d(), d_ary_heap::DEFAULT_ARITY);
+    for key in (0..300).step_by(7) {
+        assert_eq!(queue.get_priority(&key), Some(&scrambled(key)));
+    }
+
+    // Changes made in the queue show up in the map it converts back into
+    queue.change_priority(&5, 0).unwrap();
+    queue.remove(&6);
+    let mut expected = priorities;
+    expected.insert(5, 0);
+    expected.remove(&6);
+    let back: HashMap<u32, u32> = queue.into();
+    assert_eq!(back, expected);
+
+    let mut queue: KeyedPriorityQueue<u32, u32, MinOrd> = back.into();
+    let popped: Vec<u32> = std::iter::from_fn(|| queue.pop()).map(|(_, p)| p).collect();
+    assert_eq!(popped.len(), 299);
+    assert_eq!(popped[0], 0);
+    assert!(popped.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn keys_and_items_list_every_queued_key() {
+    let mut queue = KeyedPriorityQueue::new(3, MaxOrd).unwrap();
+    for key in 0..100 {
+        queue.insert(key, scrambled(key));
+    }
+    queue.remove(&50);
+    queue.change_priority(&7, 0).unwrap();
+
+    let mut keys: Vec<u32> = queue.keys().copied().collect();
+    keys.sort_unstable();
+    assert_eq!(keys, (0..100).filter(|&k| k != 50).collect::<Vec<_>>());
+
+    assert_eq!(queue.items().len(), 99);
+    assert_eq!(queue.items().next(), queue.peek());
+    for (key, priority) in queue.items() {
+        let expected = if *key == 7 { 0 } else { scrambled(*key) };
+        assert_eq!(*priority, expected);
+    }
+}
+
+#[test]
+fn adjust_priority_counts_up_and_down() {
+    for d in [1, 2, 4] {
+        // Retries remaining per job, most first
+        let mut queue = KeyedPriorityQueue::new(d, MaxOrd).unwrap();
+        for key in 0..50_i32 {
+            queue.insert(key, 10);
+        }
+        let mut expected: HashMap<i32, i32> = (0..50).map(|key| (key, 10)).collect();
+        for round in 0..5 {
+            for key in (0..50).filter(|k| (k + round) % 3 == 0) {
+                // Decrement, as a failed attempt does
+                let counter = expected.get_mut(&key).unwrap();
+                *counter -= 1;
+                assert_eq!(queue.adjust_priority(&key, -1), Ok(*counter), "d={d}");
+            }
+            for key in (0..50).filter(|k| (k + round) % 7 == 0) {
+                // and increment by more than one
+                let counter = expected.get_mut(&key).unwrap();
+                *counter += 3;
+                assert_eq!(queue.adjust_priority(&key, 3), Ok(*counter), "d={d}");
+            }
+        }
+        assert_eq!(queue.adjust_priority(&99, 1), Err(Error::ItemNotFound));
+
+        let mut popped = Vec::new();
+        while let Some((key, retries)) = queue.pop() {
+            assert_eq!(expected[&key], retries, "d={d}");
+            popped.push(retries);
+        }
+        assert_eq!(popped.len(), 50);
+        assert!(popped.windows(2).all(|w| w[0] >= w[1]), "d={d}");
+    }
+}
@@ -0,0 +1,165 @@
+//! Integration tests for `MetricsStats` (`metrics` feature).
+//!
+//! Each test registers its queue with a local in-memory recorder and reads
+//! the published values back, so the tests neither install a global
+//! recorder nor interfere with each other.
+
+use d_ary_heap::{MetricsPriorityQueue, MinBy, Position, PriorityQueue, StatsCollector};
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Stores each metric as an atomic, keyed by `name{queue}`.
+#[derive(Default)]
+struct MemoryRecorder(Mutex<HashMap<String, Arc<AtomicU64>>>);
+
+impl MemoryRecorder {
+    fn slot(&self, key: &Key) -> Arc<AtomicU64> {
+        let queue = key
+            .labels()
+            .find(|label| label.key() == "queue")
+            .map_or("", |label| label.value());
+        let name = format!("{}{{{queue}}}", key.name());
+        Arc::clone(self.0.lock().unwrap().entry(name).or_default())
+    }
+
+    fn counter(&self, name: &str, queue: &str) -> u64 {
+        self.0.lock().unwrap()[&format!("{name}{{{queue}}}")].load(Ordering::SeqCst)
+    }
+
+    // Exact comparison: the gauges hold small whole numbers (lengths, levels).
+    #[allow(clippy::float_cmp)]
+    fn assert_gauge(&self, name: &str, queue: &str, expected: u32) {
+        let value = f64::from_bits(self.counter(name, queue));
+        assert_eq!(value, f64::from(expected), "{name}{{{queue}}}");
+    }
+}
+
+impl Recorder for MemoryRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.slot(key))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(self.slot(key))
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+fn metered(recorder: &MemoryRecorder, queue: &str) -> MetricsPriorityQueue<i32, IdentityMinBy> {
+    metrics::with_local_recorder(recorder, || {
+        PriorityQueue::with_metrics(2, MinBy(identity_i32 as fn(&i32) -> i32), queue).unwrap()
+    })
+}
+
+#[test]
+fn counts_items_not_calls() {
+    let recorder = MemoryRecorder::default();
+    let mut pq = metered(&recorder, "jobs");
+    pq.insert(10);
+    pq.insert_many(vec![4, 8, 6, 2]);
+    assert_eq!(recorder.counter("d_ary_heap_inserts_total", "jobs"), 5);
+    recorder.assert_gauge("d_ary_heap_len", "jobs", 5);
+
+    pq.pop();
+    let popped = pq.pop_many(2);
+    assert_eq!(popped, vec![4, 6]);
+    pq.remove_many(&[10]);
+    assert_eq!(recorder.counter("d_ary_heap_pops_total", "jobs"), 4);
+    recorder.assert_gauge("d_ary_heap_len", "jobs", 1);
+
+    // Popping an empty queue removes nothing
+    pq.pop();
+    pq.pop();
+    assert_eq!(recorder.counter("d_ary_heap_pops_total", "jobs"), 5);
+}
+
+#[test]
+fn counts_priority_updates() {
+    let recorder = MemoryRecorder::default();
+    let mut pq = metered(&recorder, "jobs");
+    pq.insert_many(vec![1, 2, 3]);
+    pq.update_priority(&2).unwrap();
+    pq.increase_priority(&3).unwrap();
+    pq.decrease_priority(&1).unwrap();
+    pq.update_priority_by_index(Position::ROOT).unwrap();
+    assert_eq!(recorder.counter("d_ary_heap_updates_total", "jobs"), 4);
+}
+
+#[test]
+fn comparisons_match_the_collector_total() {
+    let recorder = MemoryRecorder::default();
+    let mut pq = metered(&recorder, "jobs");
+    for x in (0..50).rev() {
+        pq.insert(x);
+    }
+    pq.pop_many(10);
+    assert!(pq.stats().total() > 0);
+    assert_eq!(
+        recorder.counter("d_ary_heap_comparisons_total", "jobs"),
+        pq.stats().total()
+    );
+
+    // Resetting the collector leaves the monotonic counter alone
+    let published = pq.stats().total();
+    pq.stats().reset();
+    assert_eq!(pq.stats().total(), 0);
+    assert_eq!(
+        recorder.counter("d_ary_heap_comparisons_total", "jobs"),
+        published
+    );
+}
+
+#[test]
+fn max_sift_depth_tracks_the_deepest_sift() {
+    let recorder = MemoryRecorder::default();
+    let mut pq = metered(&recorder, "jobs");
+    // Descending inserts into a binary min-heap: each new item rises to the root
+    for x in (0..15).rev() {
+        pq.insert(x);
+    }
+    assert_eq!(pq.stats().max_sift_depth(), 3);
+    recorder.assert_gauge("d_ary_heap_max_sift_depth", "jobs", 3);
+
+    // A shallower sift does not lower it
+    pq.insert(100);
+    recorder.assert_gauge("d_ary_heap_max_sift_depth", "jobs", 3);
+}
+
+#[test]
+fn clear_publishes_an_empty_queue() {
+    let recorder = MemoryRecorder::default();
+    let mut pq = metered(&recorder, "jobs");
+    pq.insert_many(vec![1, 2, 3]);
+    pq.clear(None).unwrap();
+    recorder.assert_gauge("d_ary_heap_len", "jobs", 0);
+    assert_eq!(recorder.counter("d_ary_heap_pops_total", "jobs"), 0);
+}
+
+#[test]
+fn queues_are_told_apart_by_label() {
+    let recorder = MemoryRecorder::default();
+    let mut fast = metered(&recorder, "fast");
+    let mut slow = metered(&recorder, "slow");
+    fast.insert_many(vec![1, 2, 3]);
+    slow.insert(9);
+    assert_eq!(fast.stats().queue(), "fast");
+    recorder.assert_gauge("d_ary_heap_len", "fast", 3);
+    recorder.assert_gauge("d_ary_heap_len", "slow", 1);
+}
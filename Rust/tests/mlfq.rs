@@ -0,0 +1,162 @@
+//! Integration tests for `MlfqScheduler`.
+//!
+//! Each MLFQ rule (level order, in-level priority, demotion on a spent
+//! allotment, keeping the level on early yield or block, periodic boost) gets
+//! its own scenario, driven tick-accurately through `dispatch` / `complete`.
+
+use d_ary_heap::{Error, MinBy, MlfqConfig, MlfqScheduler, RunOutcome};
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
+
+type IdentityMinBy = MinBy<fn(&u32) -> u32>;
+
+fn scheduler(config: MlfqConfig) -> MlfqScheduler<u32, IdentityMinBy> {
+    MlfqScheduler::new(2, MinBy(identity_u32 as fn(&u32) -> u32), config).unwrap()
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_u32(x: &u32) -> u32 {
+    *x
+}
+
+fn config(levels: usize, base: u32, growth: u32, boost: u64) -> MlfqConfig {
+    MlfqConfig {
+        levels: NonZeroUsize::new(levels).unwrap(),
+        base_quantum: NonZeroU32::new(base).unwrap(),
+        quantum_growth: NonZeroU32::new(growth).unwrap(),
+        boost_interval: NonZeroU64::new(boost),
+    }
+}
+
+/// Dispatches once and reports the whole quantum used.
+fn burn(sched: &mut MlfqScheduler<u32, IdentityMinBy>) -> (u32, usize) {
+    let run = sched.dispatch().unwrap();
+    let (job, level) = (run.job, run.level);
+    sched
+        .complete(run.job, run.quantum, RunOutcome::Runnable)
+        .unwrap();
+    (job, level)
+}
+
+#[test]
+fn invalid_arity() {
+    let result = MlfqScheduler::<u32, _>::new(
+        0,
+        MinBy(identity_u32 as fn(&u32) -> u32),
+        MlfqConfig::default(),
+    );
+    assert_eq!(result.err(), Some(Error::InvalidArity));
+}
+
+#[test]
+fn allotments_grow_per_level() {
+    let sched = scheduler(config(4, 5, 3, 0));
+    let allotments: Vec<u32> = (0..4).map(|l| sched.allotment(l)).collect();
+    assert_eq!(allotments, vec![5, 15, 45, 135]);
+}
+
+#[test]
+fn cpu_bound_job_sinks_to_the_last_level() {
+    let mut sched = scheduler(config(3, 2, 2, 0));
+    assert!(sched.submit(1));
+    assert!(!sched.submit(1), "duplicate submit is rejected");
+
+    let levels: Vec<usize> = (0..5).map(|_| burn(&mut sched).1).collect();
+    assert_eq!(levels, vec![0, 1, 2, 2, 2]);
+}
+
+#[test]
+fn higher_levels_and_then_priority_go_first() {
+    let mut sched = scheduler(config(3, 4, 1, 0));
+    for job in [30, 10, 20] {
+        sched.submit(job);
+    }
+    // 10 burns its allotment and drops to level 1 behind 20 and 30
+    assert_eq!(burn(&mut sched), (10, 0));
+    assert_eq!(burn(&mut sched), (20, 0));
+    assert_eq!(burn(&mut sched), (30, 0));
+    assert_eq!(burn(&mut sched), (10, 1));
+    assert_eq!(sched.level_len(1), 2);
+    assert_eq!(sched.len(), 3);
+}
+
+#[test]
+fn interactive_job_keeps_its_level() {
+    let mut sched = scheduler(config(3, 10, 2, 0));
+    sched.submit(1);
+    for expected_quantum in [10, 7, 4, 1] {
+        let run = sched.dispatch().unwrap();
+        assert_eq!((run.level, run.quantum), (0, expected_quantum));
+        sched
+            .complete(run.job, 3.min(run.quantum), RunOutcome::Runnable)
+            .unwrap();
+    }
+    // The fourth run spent the last tick of the allotment
+    assert_eq!(sched.level_of(&1), Some(1));
+}
+
+#[test]
+fn blocked_job_waits_for_wake() {
+    let mut sched = scheduler(config(2, 10, 1, 0));
+    sched.submit(1);
+    let run = sched.dispatch().unwrap();
+    sched.complete(run.job, 4, RunOutcome::Blocked).unwrap();
+    assert!(sched.is_empty());
+    assert_eq!(sched.dispatch(), None);
+    assert_eq!(sched.level_of(&1), Some(0));
+
+    assert_eq!(sched.wake(&2), Err(Error::ItemNotFound));
+    sched.wake(&1).unwrap();
+    assert_eq!(sched.wake(&1), Err(Error::ItemNotFound), "already queued");
+    assert_eq!(sched.dispatch().unwrap().quantum, 6);
+}
+
+#[test]
+fn complete_requires_a_dispatched_job() {
+    let mut sched = scheduler(MlfqConfig::default());
+    sched.submit(1);
+    assert_eq!(
+        sched.complete(1, 1, RunOutcome::Finished),
+        Err(Error::ItemNotFound)
+    );
+    let run = sched.dispatch().unwrap();
+    sched.complete(run.job, 1, RunOutcome::Finished).unwrap();
+    assert_eq!(sched.level_of(&1), None);
+    assert!(sched.submit(1), "a finished job can be resubmitted");
+}
+
+#[test]
+fn periodic_boost_lifts_every_job() {
+    let mut sched = scheduler(config(3, 2, 1, 10));
+    for job in 0..3 {
+        sched.submit(job);
+    }
+    // Four full quanta (8 ticks): jobs 0..3 demoted, job 0 demoted twice
+    for _ in 0..4 {
+        burn(&mut sched);
+    }
+    assert_eq!(sched.level_of(&0), Some(2));
+    assert_eq!(sched.level_len(0), 0);
+
+    // The fifth quantum crosses 10 ticks and boosts everyone
+    burn(&mut sched);
+    assert_eq!(sched.level_len(0), 3);
+    assert!((0..3).all(|job| sched.level_of(&job) == Some(0)));
+}
+
+#[test]
+fn manual_boost_keeps_blocked_jobs_blocked() {
+    let mut sched = scheduler(config(2, 1, 1, 0));
+    sched.submit(1);
+    sched.submit(2);
+    burn(&mut sched); // 1 -> level 1
+    let run = sched.dispatch().unwrap(); // 2
+    sched.complete(run.job, 1, RunOutcome::Blocked).unwrap();
+    assert_eq!(sched.level_of(&2), Some(1));
+
+    sched.boost();
+    assert_eq!(sched.level_len(0), 1);
+    assert_eq!(sched.level_of(&2), Some(0));
+    sched.wake(&2).unwrap();
+    assert_eq!(sched.level_len(0), 2);
+}
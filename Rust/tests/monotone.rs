@@ -0,0 +1,163 @@
+//! Integration tests for debug-checked monotone queues (`MonotoneQueue`).
+
+use d_ary_heap::{MinBy, MonotoneQueue, PriorityQueue};
+
+/// A `side × side` grid with pseudo-random weights on the edges to the
+/// right and down, both ways.
+fn grid(side: usize, seed: u64) -> Vec<Vec<(usize, u32)>> {
+    let mut state = seed;
+    let mut weight = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        u32::try_from(state % 20).unwrap()
+    };
+    let mut adjacency = vec![Vec::new(); side * side];
+    for u in 0..side * side {
+        let (row, col) = (u / side, u % side);
+        if col + 1 < side {
+            let w = weight();
+            adjacency[u].push((u + 1, w));
+            adjacency[u + 1].push((u, w));
+        }
+        if row + 1 < side {
+            let w = weight();
+            adjacency[u].push((u + side, w));
+            adjacency[u + side].push((u, w));
+        }
+    }
+    adjacency
+}
+
+/// Dijkstra with decrease-key, on a monotone queue of `(distance, node)`
+/// identified by node.
+fn dijkstra(adjacency: &[Vec<(usize, u32)>], d: usize) -> Vec<u32> {
+    #[derive(Debug, Clone, Copy)]
+    struct Label(u32, usize);
+    impl PartialEq for Label {
+        fn eq(&self, other: &Self) -> bool {
+            self.1 == other.1
+        }
+    }
+    impl Eq for Label {}
+    impl std::hash::Hash for Label {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.1.hash(state);
+        }
+    }
+
+    let mut dist = vec![u32::MAX; adjacency.len()];
+    let mut queue = MonotoneQueue::new(d, MinBy(|l: &Label| l.0)).unwrap();
+    dist[0] = 0;
+    queue.insert(Label(0, 0));
+    while let Some(Label(du, u)) = queue.pop() {
+        for &(v, w) in &adjacency[u] {
+            let candidate = du + w;
+            if candidate < dist[v] {
+                let label = Label(candidate, v);
+                if queue.contains(&label) {
+                    queue.increase_priority(&label).unwrap();
+                } else {
+                    queue.insert(label);
+                }
+                dist[v] = candidate;
+            }
+        }
+    }
+    dist
+}
+
+/// Bellman-Ford, as a reference.
+fn bellman_ford(adjacency: &[Vec<(usize, u32)>]) -> Vec<u32> {
+    let mut dist = vec![u32::MAX; adjacency.len()];
+    dist[0] = 0;
+    for _ in 0..adjacency.len() {
+        for (u, edges) in adjacency.iter().enumerate() {
+            if dist[u] == u32::MAX {
+                continue;
+            }
+            for &(v, w) in edges {
+                dist[v] = dist[v].min(dist[u] + w);
+            }
+        }
+    }
+    dist
+}
+
+#[test]
+fn dijkstra_runs_clean_on_non_negative_weights() {
+    for seed in 1..=4 {
+        let adjacency = grid(12, seed);
+        let expected = bellman_ford(&adjacency);
+        for d in [1, 2, 4, 8] {
+            assert_eq!(dijkstra(&adjacency, d), expected, "seed={seed} d={d}");
+        }
+    }
+}
+
+#[test]
+fn ties_with_the_last_popped_item_are_allowed() {
+    let mut queue = MonotoneQueue::new(2, MinBy(|x: &(u32, char)| x.0)).unwrap();
+    queue.insert((5, 'a'));
+    queue.insert((5, 'b'));
+    assert_eq!(queue.pop().map(|x| x.0), Some(5));
+    queue.insert((5, 'c'));
+    queue.insert((8, 'd'));
+    assert_eq!(queue.len(), 3);
+    let rest: Vec<u32> = std::iter::from_fn(|| queue.pop()).map(|x| x.0).collect();
+    assert_eq!(rest, [5, 5, 8]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn into_inner_keeps_the_contents() {
+    let mut queue = MonotoneQueue::new(3, MinBy(|x: &u32| *x)).unwrap();
+    for x in [4, 1, 3] {
+        queue.insert(x);
+    }
+    assert_eq!(queue.peek(), Some(&1));
+    assert_eq!(queue.as_heap().len(), 3);
+    let mut heap: PriorityQueue<u32, _> = queue.into_inner();
+    assert_eq!(heap.pop_many(3), [1, 3, 4]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "inserted an item that outranks the last one popped")]
+fn inserting_below_the_last_pop_panics() {
+    let mut queue = MonotoneQueue::new(2, MinBy(|x: &i32| *x)).unwrap();
+    queue.insert(3);
+    queue.pop();
+    // A negative edge relaxed after its tail was settled
+    queue.insert(2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "raised an item that outranks the last one popped")]
+fn raising_past_the_last_pop_panics() {
+    let mut queue = MonotoneQueue::new(2, MinBy(|x: &(i32, char)| x.0)).unwrap();
+    queue.insert((3, 'a'));
+    queue.insert((8, 'b'));
+    queue.pop();
+    let _ = queue.increase_priority(&(1, 'b'));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "popped an item that outranks the last one popped")]
+fn a_comparator_that_changes_its_mind_is_caught_on_pop() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let reversed = Rc::new(Cell::new(false));
+    let flag = Rc::clone(&reversed);
+    let mut queue =
+        MonotoneQueue::new(2, MinBy(move |x: &i32| if flag.get() { -x } else { *x })).unwrap();
+    for x in [1, 2, 3, 4] {
+        queue.insert(x);
+    }
+    queue.pop();
+    reversed.set(true);
+    queue.pop();
+}
@@ -0,0 +1,301 @@
+//! Integration tests for the petgraph adapter (`petgraph` feature).
+//!
+//! The adapter promises petgraph's results, so each test runs both
+//! implementations on the same seeded random graphs and compares them.
+
+use d_ary_heap::petgraph::{
+    astar, astar_with_arity, dijkstra, dijkstra_adaptive, dijkstra_with_arity, AdaptiveArity,
+};
+use d_ary_heap::Error;
+use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::{algo, EdgeType, Graph};
+use std::collections::HashMap;
+
+/// Deterministic xorshift generator, so failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// `nodes` nodes and `edges` random edges of weight 1..=20.
+fn random_graph<Ty: EdgeType>(seed: u64, nodes: u32, edges: u32) -> Graph<(), u32, Ty> {
+    let mut rng = Rng(seed);
+    let mut graph = Graph::default();
+    for _ in 0..nodes {
+        graph.add_node(());
+    }
+    for _ in 0..edges {
+        let a = NodeIndex::new(usize::try_from(rng.below(u64::from(nodes))).unwrap());
+        let b = NodeIndex::new(usize::try_from(rng.below(u64::from(nodes))).unwrap());
+        graph.add_edge(a, b, u32::try_from(rng.below(20)).unwrap() + 1);
+    }
+    graph
+}
+
+#[test]
+fn dijkstra_matches_petgraph_on_random_digraphs() {
+    for seed in 1..=20 {
+        let graph: DiGraph<(), u32> = random_graph(seed, 60, 400);
+        let start = NodeIndex::new(0);
+        let expected: HashMap<_, _> = algo::dijkstra(&graph, start, None, |e| *e.weight())
+            .into_iter()
+            .collect();
+        assert_eq!(
+            dijkstra(&graph, start, None, |e| *e.weight()),
+            expected,
+            "seed {seed}"
+        );
+        for d in [1, 2, 3, 8] {
+            let actual = dijkstra_with_arity(&graph, start, None, |e| *e.weight(), d).unwrap();
+            assert_eq!(actual, expected, "seed {seed}, d = {d}");
+        }
+    }
+}
+
+#[test]
+fn dijkstra_matches_petgraph_on_undirected_float_graphs() {
+    for seed in 1..=10 {
+        let graph: UnGraph<(), u32> = random_graph(seed, 40, 150);
+        let weighted = graph.map(|_, ()| (), |_, &w| f64::from(w) / 8.0);
+        let start = NodeIndex::new(3);
+        let expected: HashMap<_, _> = algo::dijkstra(&weighted, start, None, |e| *e.weight())
+            .into_iter()
+            .collect();
+        assert_eq!(
+            dijkstra(&weighted, start, None, |e| *e.weight()),
+            expected,
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn dijkstra_goal_distance_matches_petgraph() {
+    for seed in 1..=20 {
+        let graph: DiGraph<(), u32> = random_graph(seed, 60, 300);
+        let (start, goal) = (NodeIndex::new(0), NodeIndex::new(59));
+        let expected = algo::dijkstra(&graph, start, Some(goal), |e| *e.weight());
+        let actual = dijkstra(&graph, start, Some(goal), |e| *e.weight());
+        assert_eq!(actual.get(&goal), expected.get(&goal), "seed {seed}");
+    }
+}
+
+#[test]
+fn dijkstra_leaves_unreachable_nodes_out() {
+    let mut graph = DiGraph::<(), u32>::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let island = graph.add_node(());
+    graph.add_edge(a, b, 1);
+    let distances = dijkstra(&graph, a, None, |e| *e.weight());
+    assert_eq!(distances.len(), 2);
+    assert!(!distances.contains_key(&island));
+}
+
+/// Sums the cheapest edge along each step of `path`, checking every step exists.
+fn path_cost(graph: &DiGraph<(), u32>, path: &[NodeIndex]) -> u32 {
+    path.windows(2)
+        .map(|step| {
+            graph
+                .edges(step[0])
+                .filter(|e| e.target() == step[1])
+                .map(|e| *e.weight())
+                .min()
+                .expect("consecutive path nodes are joined by an edge")
+        })
+        .sum()
+}
+
+#[test]
+fn astar_finds_paths_as_cheap_as_petgraph() {
+    for seed in 1..=20 {
+        let graph: DiGraph<(), u32> = random_graph(seed, 60, 300);
+        let (start, goal) = (NodeIndex::new(0), NodeIndex::new(59));
+        let expected = algo::astar(&graph, start, |n| n == goal, |e| *e.weight(), |_| 0);
+        for d in [2, 4, 5] {
+            let actual =
+                astar_with_arity(&graph, start, |n| n == goal, |e| *e.weight(), |_| 0, d).unwrap();
+            assert_eq!(
+                actual.as_ref().map(|(cost, _)| *cost),
+                expected.as_ref().map(|(cost, _)| *cost),
+                "seed {seed}, d = {d}"
+            );
+            if let Some((cost, path)) = actual {
+                assert_eq!(path.first(), Some(&start));
+                assert_eq!(path.last(), Some(&goal));
+                assert_eq!(path_cost(&graph, &path), cost);
+            }
+        }
+    }
+}
+
+#[test]
+fn astar_with_an_admissible_estimate_matches_dijkstra() {
+    // Nodes on a line, edges only forward: distance to the goal is at least
+    // the number of hops left, since every edge costs at least 1.
+    let mut rng = Rng(7);
+    let mut graph = DiGraph::<(), u32>::new();
+    let nodes: Vec<NodeIndex> = (0..50).map(|_| graph.add_node(())).collect();
+    for i in 0..49 {
+        graph.add_edge(nodes[i], nodes[i + 1], 5);
+        for _ in 0..3 {
+            let hop = usize::try_from(rng.below(5)).unwrap() + 1;
+            if i + hop < 50 {
+                graph.add_edge(nodes[i], nodes[i + hop], u32::try_from(hop).unwrap() * 2);
+            }
+        }
+    }
+    let goal = nodes[49];
+    let hops_left = |n: NodeIndex| u32::try_from(49 - n.index()).unwrap();
+    let (cost, path) = astar(&graph, nodes[0], |n| n == goal, |e| *e.weight(), hops_left).unwrap();
+    assert_eq!(
+        cost,
+        dijkstra(&graph, nodes[0], None, |e| *e.weight())[&goal]
+    );
+    assert_eq!(path_cost(&graph, &path), cost);
+}
+
+#[test]
+fn astar_returns_none_without_a_path() {
+    let mut graph = DiGraph::<(), u32>::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    graph.add_edge(b, a, 1);
+    assert_eq!(astar(&graph, a, |n| n == b, |e| *e.weight(), |_| 0), None);
+    assert_eq!(
+        astar(&graph, a, |n| n == a, |e| *e.weight(), |_| 0),
+        Some((0, vec![a]))
+    );
+}
+
+#[test]
+fn zero_arity_is_rejected() {
+    let graph = DiGraph::<(), u32>::from_edges([(0, 1, 1)]);
+    let start = NodeIndex::new(0);
+    assert_eq!(
+        dijkstra_with_arity(&graph, start, None, |e| *e.weight(), 0),
+        Err(Error::InvalidArity)
+    );
+    assert_eq!(
+        astar_with_arity(&graph, start, |_| true, |e| *e.weight(), |_| 0, 0),
+        Err(Error::InvalidArity)
+    );
+}
+
+#[test]
+fn dijkstra_adaptive_matches_petgraph_under_any_policy() {
+    let policies = [
+        AdaptiveArity::default(),
+        AdaptiveArity::fixed(3),
+        AdaptiveArity {
+            initial: 64,
+            min: 2,
+            max: 64,
+            window: 4,
+        },
+        AdaptiveArity {
+            initial: 1,
+            min: 1,
+            max: 5,
+            window: 1,
+        },
+    ];
+    for seed in 1..=10 {
+        let graph: DiGraph<(), u32> = random_graph(seed, 200, 4_000);
+        let start = NodeIndex::new(0);
+        let expected: HashMap<_, _> = algo::dijkstra(&graph, start, None, |e| *e.weight())
+            .into_iter()
+            .collect();
+        for policy in policies {
+            let (distances, report) =
+                dijkstra_adaptive(&graph, start, None, |e| *e.weight(), policy).unwrap();
+            assert_eq!(distances, expected, "seed {seed}, {policy:?}");
+            assert_eq!(report.pops, distances.len() as u64);
+            assert_eq!(report.inserts, distances.len() as u64);
+            assert!(report.comparisons >= report.rebuild_comparisons());
+            for switch in &report.switches {
+                assert_ne!(switch.from, switch.to);
+                assert!((policy.min.max(2)..=policy.max).contains(&switch.to));
+            }
+            let last = report.switches.last().map_or(policy.initial, |s| s.to);
+            assert_eq!(report.final_arity, last);
+        }
+    }
+}
+
+#[test]
+fn fixed_policy_never_switches() {
+    let graph: DiGraph<(), u32> = random_graph(7, 300, 6_000);
+    let start = NodeIndex::new(0);
+    let (distances, report) = dijkstra_adaptive(
+        &graph,
+        start,
+        None,
+        |e| *e.weight(),
+        AdaptiveArity::fixed(8),
+    )
+    .unwrap();
+    assert!(report.switches.is_empty());
+    assert_eq!(report.final_arity, 8);
+    assert_eq!(
+        Ok(distances),
+        dijkstra_with_arity(&graph, start, None, |e| *e.weight(), 8)
+    );
+}
+
+#[test]
+fn adapting_away_from_a_poor_arity_pays_off() {
+    let graph: DiGraph<(), u32> = random_graph(3, 2_000, 100_000);
+    let start = NodeIndex::new(0);
+    let from_64 = AdaptiveArity {
+        initial: 64,
+        ..AdaptiveArity::default()
+    };
+    let (_, adaptive) = dijkstra_adaptive(&graph, start, None, |e| *e.weight(), from_64).unwrap();
+    let (_, fixed) = dijkstra_adaptive(
+        &graph,
+        start,
+        None,
+        |e| *e.weight(),
+        AdaptiveArity::fixed(64),
+    )
+    .unwrap();
+    assert!(adaptive.final_arity < 64, "{adaptive:?}");
+    assert!(adaptive.paid_off(&fixed), "{adaptive:?} vs {fixed:?}");
+}
+
+#[test]
+fn invalid_adaptive_policies_are_rejected() {
+    let graph = DiGraph::<(), u32>::from_edges([(0, 1, 1)]);
+    let start = NodeIndex::new(0);
+    let default = AdaptiveArity::default();
+    for policy in [
+        AdaptiveArity {
+            initial: 0,
+            ..default
+        },
+        AdaptiveArity { min: 0, ..default },
+        AdaptiveArity {
+            min: 8,
+            max: 4,
+            ..default
+        },
+        AdaptiveArity {
+            window: 0,
+            ..default
+        },
+    ] {
+        assert_eq!(
+            dijkstra_adaptive(&graph, start, None, |e| *e.weight(), policy),
+            Err(Error::InvalidArity),
+            "{policy:?}"
+        );
+    }
+}
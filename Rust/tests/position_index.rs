@@ -0,0 +1,234 @@
+//! Integration tests for pluggable position indexes (`with_position_index`).
+
+use d_ary_heap::{
+    DenseIndex, HashIndex, MinBy, Position, PositionIndex, PriorityQueue, SnapshotPriorityQueue,
+};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A graph node queued at `dist`; identity is the node alone.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    node: usize,
+    dist: u64,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl Eq for Entry {}
+
+impl std::hash::Hash for Entry {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+    }
+}
+
+fn entry(node: usize, dist: u64) -> Entry {
+    Entry { node, dist }
+}
+
+fn by_node(e: &Entry) -> usize {
+    e.node
+}
+
+fn by_dist(e: &Entry) -> u64 {
+    e.dist
+}
+
+type DenseHeap = PriorityQueue<
+    Entry,
+    MinBy<fn(&Entry) -> u64>,
+    d_ary_heap::NoOpStats,
+    DenseIndex<fn(&Entry) -> usize>,
+>;
+
+fn dense_heap(d: usize) -> DenseHeap {
+    let index = DenseIndex::new(by_node as fn(&Entry) -> usize);
+    PriorityQueue::with_position_index(d, MinBy(by_dist as fn(&Entry) -> u64), index).unwrap()
+}
+
+/// Scrambled distances for nodes `0..n`.
+fn dists(n: usize) -> Vec<u64> {
+    (0..n as u64)
+        .map(|i| i.wrapping_mul(2_654_435_761) % 10_007)
+        .collect()
+}
+
+#[test]
+fn dense_index_tracks_every_position() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut heap = dense_heap(d);
+        heap.insert_many(dists(300).into_iter().enumerate().map(|(n, x)| entry(n, x)));
+        for (position, item) in heap.iter_with_positions() {
+            assert_eq!(heap.get_position(item), Some(position));
+        }
+        assert!(!heap.contains(&entry(300, 0)));
+        assert_eq!(heap.get_position(&entry(10_000, 0)), None);
+    }
+}
+
+#[test]
+fn dense_index_matches_hash_map_through_updates() {
+    let mut dense = dense_heap(4);
+    let mut hashed = PriorityQueue::new(4, MinBy(by_dist as fn(&Entry) -> u64)).unwrap();
+    for (node, dist) in dists(500).into_iter().enumerate() {
+        dense.insert(entry(node, dist + 20_000));
+        hashed.insert(entry(node, dist + 20_000));
+    }
+    // Lower every third node's distance, then remove every seventh node
+    for (node, dist) in dists(500).into_iter().enumerate().step_by(3) {
+        dense.increase_priority(&entry(node, dist)).unwrap();
+        hashed.increase_priority(&entry(node, dist)).unwrap();
+    }
+    let gone: Vec<Entry> = (0..500).step_by(7).map(|node| entry(node, 0)).collect();
+    assert_eq!(dense.remove_many(&gone), hashed.remove_many(&gone));
+    assert_eq!(dense.len(), hashed.len());
+    while let Some(e) = dense.pop() {
+        assert_eq!(Some(e.dist), hashed.pop().map(|h| h.dist));
+    }
+}
+
+#[test]
+fn removed_ids_can_be_reinserted() {
+    let mut heap = dense_heap(2);
+    heap.insert_many([entry(0, 5), entry(1, 3), entry(2, 8)]);
+    assert_eq!(heap.pop(), Some(entry(1, 3)));
+    assert!(!heap.contains(&entry(1, 0)));
+    heap.insert(entry(1, 1));
+    assert_eq!(heap.get_position(&entry(1, 0)), Some(Position::ROOT));
+}
+
+#[test]
+fn clear_leaves_a_snapshot_intact() {
+    let mut heap = dense_heap(3);
+    heap.insert_many([entry(4, 40), entry(2, 20)]);
+    let mut heap = SnapshotPriorityQueue::new(heap);
+    let snapshot = heap.snapshot();
+    heap.modify(|heap| heap.clear(None)).unwrap();
+    assert!(heap.is_empty());
+    assert!(!heap.view().contains(&entry(2, 0)));
+    assert_eq!(snapshot.get_position(&entry(2, 0)), Some(Position::ROOT));
+    heap.insert(entry(9, 1));
+    assert_eq!(heap.view().get_position(&entry(9, 0)), Some(Position::ROOT));
+}
+
+#[test]
+fn into_raw_parts_returns_the_index() {
+    let mut heap = dense_heap(2);
+    heap.insert_many([entry(3, 30), entry(1, 10)]);
+    let (container, index, d) = heap.into_raw_parts();
+    assert_eq!(d, 2);
+    for (position, item) in container.iter().enumerate() {
+        assert_eq!(index.get(item, &container), Some(Position::new(position)));
+    }
+}
+
+#[test]
+fn hash_map_with_another_hasher_is_an_index() {
+    let index: std::collections::HashMap<
+        u32,
+        Position,
+        std::hash::BuildHasherDefault<std::hash::DefaultHasher>,
+    > = std::collections::HashMap::default();
+    let mut heap = PriorityQueue::with_position_index(2, MinBy(|x: &u32| *x), index).unwrap();
+    heap.insert_many([7, 3, 5]);
+    heap.clear(None).unwrap();
+    heap.insert(4);
+    assert_eq!(heap.pop(), Some(4));
+}
+
+#[test]
+fn shrinking_a_dense_index_keeps_every_position() {
+    let mut heap = dense_heap(3);
+    heap.insert_many(dists(200).into_iter().enumerate().map(|(n, x)| entry(n, x)));
+    let high: Vec<Entry> = (100..200).map(|node| entry(node, 0)).collect();
+    heap.remove_many(&high);
+    heap.reserve(50);
+    heap.shrink_to_fit();
+
+    let (container, index, _) = heap.into_raw_parts();
+    for (position, item) in container.iter().enumerate() {
+        assert_eq!(index.get(item, &container), Some(Position::new(position)));
+    }
+    // The slots past the largest id still queued were released
+    assert_eq!(format!("{index:?}"), "DenseIndex { ids: 100, .. }");
+}
+
+#[test]
+fn the_default_index_stores_each_item_once() {
+    use std::rc::Rc;
+
+    let paths: Vec<Rc<String>> = ["a/b/c", "a", "a/b"]
+        .into_iter()
+        .map(|p| Rc::new(p.to_string()))
+        .collect();
+    let by_len = || MinBy(|p: &Rc<String>| p.len());
+    let mut heap = PriorityQueue::new(2, by_len()).unwrap();
+    heap.insert_many(paths.iter().cloned());
+    heap.update_priority(&paths[0]).unwrap();
+
+    // Only the array holds a handle; the index keeps hashes and positions
+    for path in &paths {
+        assert_eq!(Rc::strong_count(path), 2);
+    }
+    // A `HashMap` index keys each entry by a copy of its item
+    let mut hashed = PriorityQueue::with_position_index(2, by_len(), HashMap::new()).unwrap();
+    hashed.insert_many(paths.iter().cloned());
+    for path in &paths {
+        assert_eq!(Rc::strong_count(path), 4);
+    }
+
+    // Lookups go by the pointee, so a fresh handle finds the queued item
+    assert!(heap.contains(&Rc::new("a/b".to_string())));
+    let first = heap.pop().unwrap();
+    assert!(Rc::ptr_eq(&first, &paths[1]));
+}
+
+/// Hashes everything to 0, so every item collides with every other.
+#[derive(Default)]
+struct Collide;
+
+impl Hasher for Collide {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {}
+}
+
+#[test]
+fn colliding_hashes_are_told_apart_by_the_array() {
+    for d in [1, 2, 3, 4] {
+        let index = HashIndex::with_hasher(BuildHasherDefault::<Collide>::default());
+        let mut colliding =
+            PriorityQueue::with_position_index(d, MinBy(by_dist as fn(&Entry) -> u64), index)
+                .unwrap();
+        let mut hashed = PriorityQueue::new(d, MinBy(by_dist as fn(&Entry) -> u64)).unwrap();
+        for (node, dist) in dists(60).into_iter().enumerate() {
+            colliding.insert(entry(node, dist + 20_000));
+            hashed.insert(entry(node, dist + 20_000));
+        }
+        for (node, dist) in dists(60).into_iter().enumerate().step_by(3) {
+            colliding.increase_priority(&entry(node, dist)).unwrap();
+            hashed.increase_priority(&entry(node, dist)).unwrap();
+        }
+        let gone: Vec<Entry> = (0..60).step_by(7).map(|node| entry(node, 0)).collect();
+        assert_eq!(colliding.remove_many(&gone), hashed.remove_many(&gone));
+        assert_eq!(
+            colliding.drain_where(|e| e.node % 5 == 1),
+            hashed.drain_where(|e| e.node % 5 == 1)
+        );
+        for (position, item) in colliding.iter_with_positions() {
+            assert_eq!(colliding.get_position(item), Some(position));
+        }
+        assert!(!colliding.contains(&entry(7, 0)));
+        while let Some(e) = colliding.pop() {
+            assert_eq!(Some(e.dist), hashed.pop().map(|h| h.dist));
+        }
+        assert!(hashed.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+//! Integration tests for `PriorityFutures` (`async` feature).
+//!
+//! Futures here are driven by hand with a counting waker, so each test
+//! controls exactly which tasks are woken between polls and can check the
+//! order in which the ready index hands them back.
+
+use d_ary_heap::PriorityFutures;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Counts how often the outer task was woken.
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Default)]
+struct GateState {
+    open: bool,
+    polls: usize,
+    waker: Option<Waker>,
+}
+
+/// A future that stays pending until its `Gate` is opened.
+struct Gated {
+    label: &'static str,
+    state: Arc<Mutex<GateState>>,
+}
+
+impl Future for Gated {
+    type Output = &'static str;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        state.polls += 1;
+        if state.open {
+            Poll::Ready(self.label)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Gate(Arc<Mutex<GateState>>);
+
+impl Gate {
+    fn future(&self, label: &'static str) -> Gated {
+        Gated {
+            label,
+            state: Arc::clone(&self.0),
+        }
+    }
+
+    fn open(&self) {
+        let waker = {
+            let mut state = self.0.lock().unwrap();
+            state.open = true;
+            state.waker.clone()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn polls(&self) -> usize {
+        self.0.lock().unwrap().polls
+    }
+}
+
+fn open_gate() -> Gate {
+    let gate = Gate::default();
+    gate.0.lock().unwrap().open = true;
+    gate
+}
+
+fn counting_context() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(Arc::clone(&counter));
+    (counter, waker)
+}
+
+fn drain(set: &mut PriorityFutures<Gated, u32>, cx: &mut Context<'_>) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    while let Poll::Ready(Some(label)) = set.poll_next(cx) {
+        out.push(label);
+    }
+    out
+}
+
+#[test]
+fn empty_set_is_exhausted() {
+    let (_, waker) = counting_context();
+    let mut set: PriorityFutures<Gated, u32> = PriorityFutures::new(2);
+    assert!(set.is_empty());
+    assert_eq!(
+        set.poll_next(&mut Context::from_waker(&waker)),
+        Poll::Ready(None)
+    );
+}
+
+#[test]
+fn ready_futures_complete_highest_priority_first() {
+    let (_, waker) = counting_context();
+    let mut cx = Context::from_waker(&waker);
+    let gate = open_gate();
+    let mut set = PriorityFutures::new(4);
+    for (label, priority) in [("low", 1), ("high", 9), ("mid", 5), ("top", 12)] {
+        set.push(gate.future(label), priority);
+    }
+    assert_eq!(set.len(), 4);
+    assert_eq!(drain(&mut set, &mut cx), vec!["top", "high", "mid", "low"]);
+    assert_eq!(set.poll_next(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn equal_priorities_complete_in_push_order() {
+    let (_, waker) = counting_context();
+    let gate = open_gate();
+    let mut set = PriorityFutures::new(3);
+    for label in ["a", "b", "c", "d", "e"] {
+        set.push(gate.future(label), 7);
+    }
+    assert_eq!(
+        drain(&mut set, &mut Context::from_waker(&waker)),
+        vec!["a", "b", "c", "d", "e"]
+    );
+}
+
+#[test]
+fn pending_futures_are_not_repolled_until_woken() {
+    let (counter, waker) = counting_context();
+    let mut cx = Context::from_waker(&waker);
+    let gate = Gate::default();
+    let mut set = PriorityFutures::new(2);
+    set.push(gate.future("gated"), 3);
+
+    assert_eq!(set.poll_next(&mut cx), Poll::Pending);
+    assert_eq!(set.poll_next(&mut cx), Poll::Pending);
+    assert_eq!(gate.polls(), 1);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    gate.open();
+    assert_eq!(
+        counter.0.load(Ordering::SeqCst),
+        1,
+        "wake reaches the outer task"
+    );
+    assert_eq!(set.poll_next(&mut cx), Poll::Ready(Some("gated")));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn woken_futures_are_ordered_by_priority() {
+    let (_, waker) = counting_context();
+    let mut cx = Context::from_waker(&waker);
+    let gates: Vec<Gate> = (0..4).map(|_| Gate::default()).collect();
+    let mut set = PriorityFutures::new(2);
+    for (gate, (label, priority)) in gates
+        .iter()
+        .zip([("p1", 1), ("p4", 4), ("p2", 2), ("p3", 3)])
+    {
+        set.push(gate.future(label), priority);
+    }
+    assert_eq!(set.poll_next(&mut cx), Poll::Pending);
+
+    // Wake in push order; a duplicate wake must not queue a task twice.
+    for gate in &gates {
+        gate.open();
+    }
+    gates[0].open();
+    assert_eq!(drain(&mut set, &mut cx), vec!["p4", "p3", "p2", "p1"]);
+    assert!(gates.iter().all(|g| g.polls() == 2));
+}
+
+#[test]
+fn next_resolves_through_async_fn() {
+    let (_, waker) = counting_context();
+    let mut cx = Context::from_waker(&waker);
+    let gate = open_gate();
+    let mut set = PriorityFutures::new(2);
+    set.push(gate.future("second"), 1);
+    set.push(gate.future("first"), 2);
+
+    let mut next = Box::pin(set.next());
+    assert_eq!(next.as_mut().poll(&mut cx), Poll::Ready(Some("first")));
+}
@@ -0,0 +1,153 @@
+//! Integration tests for per-operation profiling (`ProfileTrace`).
+//!
+//! Timings are not deterministic, so these tests pin down everything else:
+//! one record per operation, the heap size it started at, comparison and swap
+//! counts that agree with the other collectors, and the CSV layout.
+
+use d_ary_heap::{
+    MinBy, OperationType, PriorityQueue, ProfiledPriorityQueue, StatsCollector, PROFILE_CSV_HEADER,
+};
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+
+fn fresh_min_heap_with_profile(d: usize) -> ProfiledPriorityQueue<i32, IdentityMinBy> {
+    PriorityQueue::with_profile(d, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap()
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+#[test]
+fn one_record_per_operation_with_starting_len() {
+    let mut pq = fresh_min_heap_with_profile(3);
+    for v in 0..5 {
+        pq.insert(v);
+    }
+    pq.pop();
+    pq.insert_many(vec![9, 8, 7]);
+    pq.pop();
+
+    let records = pq.stats().records();
+    let summary: Vec<(OperationType, usize)> =
+        records.iter().map(|r| (r.operation, r.len)).collect();
+    assert_eq!(
+        summary,
+        vec![
+            (OperationType::Insert, 0),
+            (OperationType::Insert, 1),
+            (OperationType::Insert, 2),
+            (OperationType::Insert, 3),
+            (OperationType::Insert, 4),
+            (OperationType::Pop, 5),
+            (OperationType::Insert, 4),
+            (OperationType::Pop, 7),
+        ]
+    );
+}
+
+#[test]
+fn comparisons_agree_with_comparison_stats() {
+    let mut profiled = fresh_min_heap_with_profile(4);
+    let mut counted = PriorityQueue::with_stats(4, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap();
+    for v in 0..300 {
+        let v = (v * 7919) % 1009;
+        profiled.insert(v);
+        counted.insert(v);
+    }
+    while profiled.pop().is_some() {
+        counted.pop();
+    }
+
+    assert_eq!(profiled.stats().total(), counted.stats().total());
+    let pop_comparisons: u64 = profiled
+        .stats()
+        .records()
+        .iter()
+        .filter(|r| r.operation == OperationType::Pop)
+        .map(|r| r.comparisons)
+        .sum();
+    assert_eq!(pop_comparisons, counted.stats().pop());
+}
+
+#[test]
+fn swaps_agree_with_explain_trace() {
+    let mut profiled = fresh_min_heap_with_profile(2);
+    let mut explained =
+        PriorityQueue::with_explain(2, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap();
+    for v in [5, 3, 8, 1, 9, 2] {
+        profiled.insert(v);
+        explained.insert(v);
+    }
+    profiled.pop();
+    explained.pop();
+
+    let swaps: Vec<u64> = profiled.stats().records().iter().map(|r| r.swaps).collect();
+    let expected: Vec<u64> = explained
+        .stats()
+        .steps()
+        .iter()
+        .map(|s| s.swaps.len() as u64)
+        .collect();
+    assert_eq!(swaps, expected);
+}
+
+#[test]
+fn csv_layout() {
+    let mut pq = fresh_min_heap_with_profile(2);
+    pq.insert(5);
+    pq.insert(3);
+    pq.pop();
+
+    let mut out = Vec::new();
+    pq.stats().write_csv(&mut out).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(PROFILE_CSV_HEADER.trim_end()));
+
+    let rows: Vec<Vec<&str>> = lines.map(|l| l.split(',').collect()).collect();
+    assert_eq!(rows.len(), 3);
+    for row in &rows {
+        assert_eq!(row.len(), 5);
+        assert!(row[4].parse::<u128>().is_ok());
+    }
+    assert_eq!(rows[1][..4], ["insert", "1", "1", "1"]);
+    assert_eq!(rows[2][..4], ["pop", "2", "0", "1"]);
+}
+
+#[test]
+fn append_csv_streams_and_clears() {
+    let path = std::env::temp_dir().join(format!(
+        "d_ary_heap_profile_test_{}.csv",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let mut pq = fresh_min_heap_with_profile(2);
+    for v in 0..4 {
+        pq.insert(v);
+    }
+    assert_eq!(pq.stats().append_csv(&path).unwrap(), 4);
+    assert!(pq.stats().records().is_empty());
+    pq.pop();
+    assert_eq!(pq.stats().append_csv(&path).unwrap(), 1);
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(csv.matches("operation,").count(), 1, "header written once");
+    assert_eq!(csv.lines().count(), 1 + 5);
+    assert!(csv.lines().last().unwrap().starts_with("pop,4,"));
+}
+
+#[test]
+fn reset_and_take_clear_records() {
+    let mut pq = fresh_min_heap_with_profile(2);
+    pq.insert(1);
+    assert_eq!(pq.stats().take_records().len(), 1);
+    pq.insert(2);
+    pq.stats().reset();
+    assert!(pq.stats().records().is_empty());
+    assert_eq!(pq.stats().total(), 0);
+}
@@ -0,0 +1,111 @@
+//! Integration tests for time-sliced rebuilds (`IncrementalRebuild`).
+
+use d_ary_heap::{MinBy, PriorityQueue, StatsCollector};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Scrambled distinct values: multiplying by an odd constant is a bijection
+/// on `u32`.
+fn values(range: std::ops::Range<u32>) -> impl Iterator<Item = u32> {
+    range.map(|i| i.wrapping_mul(2_654_435_761))
+}
+
+/// A min-heap on `x ^ mask`, where `mask` can change under it.
+fn masked_heap(d: usize, mask: &Rc<Cell<u32>>) -> PriorityQueue<u32, MinBy<impl Fn(&u32) -> u32>> {
+    let mask = Rc::clone(mask);
+    PriorityQueue::new(d, MinBy(move |x: &u32| *x ^ mask.get())).unwrap()
+}
+
+#[test]
+fn steps_produce_the_layout_of_one_heapify() {
+    for d in [1, 2, 3, 4, 8] {
+        for budget in [1, 7, 100, usize::MAX] {
+            let mask = Rc::new(Cell::new(0));
+            let mut heap = masked_heap(d, &mask);
+            heap.insert_many(values(0..1_000));
+
+            mask.set(0xdead_beef);
+            // One heapify of the same array, for reference
+            let expected =
+                PriorityQueue::from_vec(heap.to_array(), d, MinBy(|x: &u32| *x ^ 0xdead_beef))
+                    .unwrap();
+
+            let mut rebuild = heap.start_rebuild();
+            let sifts = rebuild.remaining();
+            assert_eq!(sifts, (1_000 - 2) / d + 1, "d={d}");
+            let mut calls = 1;
+            while !rebuild.rebuild_step(budget) {
+                calls += 1;
+            }
+            assert_eq!(
+                calls,
+                sifts.div_ceil(budget).max(1),
+                "d={d} budget={budget}"
+            );
+            let mut heap = rebuild.into_heap().unwrap();
+
+            assert_eq!(
+                heap.to_array(),
+                expected.to_array(),
+                "d={d} budget={budget}"
+            );
+            for (pos, item) in heap.iter_with_positions() {
+                assert_eq!(heap.get_position(item), Some(pos));
+            }
+            let popped: Vec<u32> = std::iter::from_fn(|| heap.pop())
+                .map(|x| x ^ 0xdead_beef)
+                .collect();
+            assert!(popped.windows(2).all(|w| w[0] < w[1]), "d={d}");
+        }
+    }
+}
+
+#[test]
+fn into_heap_refuses_an_unfinished_rebuild() {
+    let mut heap = PriorityQueue::new(2, MinBy(|x: &u32| *x)).unwrap();
+    heap.insert_many(values(0..100));
+
+    let mut rebuild = heap.start_rebuild();
+    assert!(!rebuild.rebuild_step(0));
+    assert_eq!(rebuild.remaining(), 50);
+    assert!(!rebuild.rebuild_step(20));
+    let rebuild = rebuild.into_heap().unwrap_err();
+    assert_eq!(rebuild.remaining(), 30);
+    assert_eq!(rebuild.len(), 100);
+
+    let heap = rebuild.finish();
+    assert_eq!(heap.front(), &values(0..100).min().unwrap());
+}
+
+#[test]
+fn tiny_heaps_need_no_steps() {
+    let rebuild = PriorityQueue::new(4, MinBy(|x: &u32| *x))
+        .unwrap()
+        .start_rebuild();
+    assert!(rebuild.is_done());
+    assert!(rebuild.is_empty());
+    let mut heap = rebuild.into_heap().unwrap();
+
+    heap.insert(7);
+    let mut rebuild = heap.start_rebuild();
+    assert!(rebuild.is_done());
+    assert!(rebuild.rebuild_step(1));
+    assert!(!rebuild.is_empty());
+    assert_eq!(rebuild.into_heap().unwrap().front(), &7);
+}
+
+#[test]
+fn each_step_is_one_update_operation_for_stats() {
+    let mut heap = PriorityQueue::with_stats(3, MinBy(|x: &u32| *x)).unwrap();
+    heap.insert_many(values(0..300));
+    let before = heap.stats().update_priority();
+
+    let mut rebuild = heap.start_rebuild();
+    rebuild.rebuild_step(10);
+    let heap = rebuild.finish();
+    // Floyd's heapify over an already valid heap: d - 1 or fewer child
+    // comparisons plus one against the parent, per non-leaf
+    let counted = heap.stats().update_priority() - before;
+    assert!(counted > 0 && counted <= 3 * 100, "{counted}");
+    assert_eq!(heap.stats().total() - heap.stats().insert(), counted);
+}
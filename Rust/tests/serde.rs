@@ -0,0 +1,87 @@
+//! Integration tests for serialization (`serde` feature).
+
+use d_ary_heap::{Error, MaxHeap, MinBy, MinHeap, PriorityQueue};
+use std::hash::{Hash, Hasher};
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct Job {
+    id: u32,
+    due: u32,
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Job {}
+
+impl Hash for Job {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+fn load<C>(json: &str, comparator: C) -> Result<PriorityQueue<Job, C>, serde_json::Error>
+where
+    C: d_ary_heap::PriorityCompare<Job>,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    PriorityQueue::deserialize_with(&mut deserializer, comparator)
+}
+
+#[test]
+fn round_trip_keeps_layout_and_positions() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut heap = PriorityQueue::new(d, MinBy(|j: &Job| j.due)).unwrap();
+        heap.insert_many((0..100).map(|id| Job {
+            id,
+            due: (id * 37) % 50,
+        }));
+        heap.pop_many(10);
+
+        let json = serde_json::to_string(&heap).unwrap();
+        let mut loaded = load(&json, MinBy(|j: &Job| j.due)).unwrap();
+        assert_eq!(loaded.d(), d);
+        let saved: Vec<u32> = heap.iter().map(|j| j.id).collect();
+        let restored: Vec<u32> = loaded.iter().map(|j| j.id).collect();
+        assert_eq!(restored, saved, "d={d}");
+        for (position, job) in loaded.iter_with_positions() {
+            assert_eq!(loaded.get_position(job), Some(position));
+        }
+
+        let popped: Vec<u32> = std::iter::from_fn(|| loaded.pop().map(|j| j.due)).collect();
+        assert!(popped.windows(2).all(|w| w[0] <= w[1]), "d={d}");
+    }
+}
+
+#[test]
+fn default_comparators_deserialize_directly() {
+    let mut heap: MaxHeap<String> = MaxHeap::with_arity(3).unwrap();
+    heap.insert_many(["b", "d", "a", "c"].map(String::from));
+    let json = serde_json::to_value(&heap).unwrap();
+    assert_eq!(json["d"], 3);
+    assert_eq!(json["items"][0], "d");
+
+    let loaded: MaxHeap<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded, heap);
+}
+
+#[test]
+fn arrays_out_of_heap_order_are_reheapified() {
+    let mut loaded: MinHeap<u32> = serde_json::from_str(r#"{"d":2,"items":[9,4,7,1]}"#).unwrap();
+    assert_eq!(loaded.pop_many(4), vec![1, 4, 7, 9]);
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    let err = serde_json::from_str::<MinHeap<u32>>(r#"{"d":0,"items":[1]}"#).unwrap_err();
+    assert_eq!(err.to_string(), Error::InvalidArity.to_string());
+
+    let duplicated = r#"{"d":4,"items":[{"id":1,"due":5},{"id":1,"due":3}]}"#;
+    let err = load(duplicated, MinBy(|j: &Job| j.due)).unwrap_err();
+    assert_eq!(err.to_string(), Error::InconsistentPositions.to_string());
+
+    assert!(serde_json::from_str::<MinHeap<u32>>(r#"{"items":[1]}"#).is_err());
+}
@@ -0,0 +1,308 @@
+//! Integration tests for copy-on-write snapshots (`SnapshotPriorityQueue`,
+//! `Snapshot`) and frozen heaps (`freeze()`, `FrozenHeap`).
+//!
+//! A snapshot must keep showing the state it was taken from whatever the queue
+//! does next, and the queue must behave exactly as if no snapshot existed.
+
+use d_ary_heap::{
+    FrozenHeap, MaxBy, MinBy, Position, PriorityQueue, Snapshot, SnapshotPriorityQueue,
+};
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+type IdentityMinBy = MinBy<fn(&i32) -> i32>;
+
+fn min_heap(d: usize, n: i32) -> SnapshotPriorityQueue<i32, IdentityMinBy> {
+    let mut pq = PriorityQueue::new(d, MinBy(identity_i32 as fn(&i32) -> i32)).unwrap();
+    pq.insert_many((0..n).rev());
+    SnapshotPriorityQueue::new(pq)
+}
+
+// See tests/instrumentation.rs: the signature is dictated by `MinBy<F>`.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn identity_i32(x: &i32) -> i32 {
+    *x
+}
+
+fn assert_matches(snapshot: &Snapshot<i32>, expected: &[i32]) {
+    assert_eq!(snapshot.as_slice(), expected);
+    assert_eq!(snapshot.len(), expected.len());
+    for (position, item) in snapshot.iter_with_positions() {
+        assert_eq!(snapshot.get_position(item), Some(position));
+        assert_eq!(snapshot.cursor_at(position).unwrap().item(), item);
+    }
+}
+
+#[test]
+fn snapshot_survives_every_mutation() {
+    let mutations: [fn(&mut PriorityQueue<i32, IdentityMinBy>); 7] = [
+        |pq| pq.insert(-1),
+        |pq| {
+            pq.pop();
+        },
+        |pq| pq.insert_many(vec![100, -5, 42]),
+        |pq| pq.update_priority(&7).unwrap(),
+        |pq| {
+            pq.remove_many(&[3, 9, 11]);
+        },
+        |pq| {
+            pq.pop_many(5);
+        },
+        |pq| pq.clear(Some(2)).unwrap(),
+    ];
+    for d in [1, 2, 4] {
+        for (k, mutate) in mutations.iter().enumerate() {
+            let mut pq = min_heap(d, 20);
+            let before = pq.view().as_slice().to_vec();
+            let snapshot = pq.snapshot();
+
+            pq.modify(mutate);
+            assert_matches(&snapshot, &before);
+            assert_eq!(snapshot.d(), d, "mutation {k}");
+
+            // The queue itself is unaffected by the snapshot
+            for (position, item) in pq.view().iter_with_positions() {
+                assert_eq!(pq.view().get_position(item), Some(position), "mutation {k}");
+            }
+            let mut pq = pq.into_inner();
+            for (position, item) in pq.iter_with_positions() {
+                assert_eq!(pq.get_position(item), Some(position), "mutation {k}");
+            }
+            let drained: Vec<i32> = std::iter::from_fn(|| pq.pop()).collect();
+            assert!(drained.windows(2).all(|w| w[0] <= w[1]), "mutation {k}");
+        }
+    }
+}
+
+#[test]
+fn snapshots_taken_at_different_times() {
+    let mut pq = min_heap(3, 5);
+    let first = pq.snapshot();
+    pq.insert(-1);
+    let second = pq.snapshot();
+    let second_clone = second.clone();
+    pq.pop();
+    pq.pop();
+
+    assert_eq!(first.peek(), Some(&0));
+    assert!(!first.contains(&-1));
+    assert_eq!(second.peek(), Some(&-1));
+    assert_eq!(second.len(), 6);
+    assert_eq!(second_clone.as_slice(), second.as_slice());
+    assert_eq!(pq.peek(), Some(&1));
+    assert_eq!(pq.len(), 4);
+}
+
+#[test]
+fn snapshot_of_empty_heap() {
+    let mut pq = min_heap(2, 0);
+    let snapshot = pq.snapshot();
+    pq.insert(1);
+    assert!(snapshot.is_empty());
+    assert_eq!(snapshot.peek(), None);
+    assert!(snapshot.cursor_at(Position::ROOT).is_none());
+}
+
+#[test]
+fn snapshot_is_read_on_another_thread() {
+    let mut pq = min_heap(4, 1000);
+    let snapshot = pq.snapshot();
+    let reader = std::thread::spawn(move || {
+        let sum: i32 = snapshot.as_slice().iter().sum();
+        (snapshot.len(), sum, snapshot.peek().copied())
+    });
+    pq.modify(|pq| pq.pop_many(500));
+    assert_eq!(reader.join().unwrap(), (1000, 999 * 1000 / 2, Some(0)));
+    assert_eq!(pq.len(), 500);
+}
+
+// -----------------------------------------------------------------------------
+// FrozenHeap
+// -----------------------------------------------------------------------------
+
+/// Scrambled values with duplicates, so ties are exercised.
+fn scrambled(n: i32) -> Vec<i32> {
+    (0..n).map(|i| (i * 37 + 11) % 53).collect()
+}
+
+#[test]
+fn k_smallest_matches_a_sort() {
+    for d in [1, 2, 3, 4, 8] {
+        let mut min = PriorityQueue::new(d, MinBy(|x: &(i32, i32)| x.1)).unwrap();
+        let mut max = PriorityQueue::new(d, MaxBy(|x: &(i32, i32)| x.1)).unwrap();
+        let items: Vec<(i32, i32)> = scrambled(120)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (i32::try_from(i).unwrap(), v))
+            .collect();
+        min.insert_many(items.clone());
+        max.insert_many(items.clone());
+        let (min, max) = (
+            SnapshotPriorityQueue::new(min).freeze(),
+            SnapshotPriorityQueue::new(max).freeze(),
+        );
+
+        let mut ascending: Vec<i32> = items.iter().map(|x| x.1).collect();
+        ascending.sort_unstable();
+        for k in [0, 1, 2, 7, 50, 120, 200] {
+            let best: Vec<i32> = min.k_smallest(k).iter().map(|x| x.1).collect();
+            assert_eq!(best, ascending[..k.min(120)], "d={d} k={k}");
+            let best: Vec<i32> = max.k_smallest(k).iter().map(|x| x.1).collect();
+            let descending: Vec<i32> = ascending.iter().rev().take(k).copied().collect();
+            assert_eq!(best, descending, "d={d} k={k}");
+        }
+        // Every item ranked once
+        let mut all: Vec<(i32, i32)> = min.k_smallest(120).into_iter().copied().collect();
+        all.sort_unstable();
+        assert_eq!(all, items);
+    }
+}
+
+#[test]
+fn frozen_heap_keeps_its_generation() {
+    let mut pq = min_heap(3, 20);
+    let frozen = pq.freeze();
+    pq.modify(|pq| pq.pop_many(5));
+    pq.insert(-4);
+
+    assert_eq!(frozen.len(), 20);
+    assert_eq!(frozen.d(), 3);
+    assert_eq!(frozen.peek(), Some(&0));
+    assert!(frozen.contains(&2));
+    assert!(!frozen.contains(&-4));
+    assert_eq!(frozen.k_smallest(3), [&0, &1, &2]);
+    assert_eq!(frozen.as_slice(), frozen.snapshot().as_slice());
+    for item in &frozen {
+        assert_eq!(
+            frozen.as_slice()[frozen.get_position(item).unwrap().index()],
+            *item
+        );
+    }
+    assert_eq!(frozen.iter().count(), 20);
+
+    let empty = min_heap(2, 0).freeze();
+    assert!(empty.is_empty());
+    assert_eq!(empty.peek(), None);
+    assert!(empty.k_smallest(4).is_empty());
+}
+
+#[test]
+fn frozen_heap_is_shared_by_readers() {
+    fn assert_send_sync<X: Send + Sync>(_: &X) {}
+
+    let mut pq = min_heap(4, 1000);
+    let frozen: Arc<FrozenHeap<i32, IdentityMinBy>> = Arc::new(pq.freeze());
+    assert_send_sync(&frozen);
+    let readers: Vec<_> = (0..4)
+        .map(|r| {
+            let frozen = Arc::clone(&frozen);
+            std::thread::spawn(move || {
+                let best: Vec<i32> = frozen.k_smallest(10 * r).into_iter().copied().collect();
+                (best, frozen.contains(&999))
+            })
+        })
+        .collect();
+    pq.modify(|pq| pq.pop_many(1000));
+
+    for (r, reader) in (0..4).zip(readers) {
+        let expected: Vec<i32> = (0..10 * r).collect();
+        assert_eq!(reader.join().unwrap(), (expected, true));
+    }
+    assert!(pq.is_empty());
+    // Clones share the captured storage
+    let copy = (*frozen).clone();
+    assert!(std::ptr::eq(copy.as_slice(), frozen.as_slice()));
+}
+
+// -----------------------------------------------------------------------------
+// The wrapper and the plain heap
+// -----------------------------------------------------------------------------
+
+#[test]
+fn the_queue_comes_back_out_with_its_contents() {
+    let mut pq = min_heap(4, 50);
+    let snapshot = pq.snapshot();
+    let fork = pq.clone();
+    pq.modify(|pq| pq.update_priority(&49).unwrap());
+    pq.insert(-1);
+    assert_eq!(pq.len(), 51);
+    assert_eq!(pq.peek(), Some(&-1));
+    assert!(!pq.is_empty());
+
+    // The clone shared the storage and kept the old contents when `pq` moved on
+    assert_eq!(fork.view().as_slice(), snapshot.as_slice());
+    let mut heap = pq.into_inner();
+    assert_eq!(heap.pop(), Some(-1));
+    assert_eq!(heap.pop_many(50), (0..50).collect::<Vec<_>>());
+    // Taken out while the snapshot shares the storage: copied, not stolen
+    let heap = fork.into_inner();
+    assert_eq!(heap.len(), 50);
+    assert_eq!(snapshot.len(), 50);
+}
+
+#[test]
+fn a_panicking_modify_leaves_the_queue_whole() {
+    let mut pq = min_heap(2, 10);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pq.modify(|pq| {
+            pq.insert(-1);
+            panic!("mid-operation");
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(pq.len(), 11);
+    assert_eq!(pq.pop(), Some(-1));
+    assert_eq!(pq.view().get_position(&0), Some(Position::ROOT));
+}
+
+/// A job whose priority lives in a `Cell`: `Send` but not `Sync`.
+#[derive(Debug)]
+struct Job {
+    id: u32,
+    priority: Cell<u32>,
+}
+
+impl Clone for Job {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            priority: Cell::new(self.priority.get()),
+        }
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Job {}
+
+impl Hash for Job {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+#[test]
+fn a_plain_queue_of_non_sync_items_is_send() {
+    fn assert_send<X: Send>(_: &X) {}
+
+    let mut heap = PriorityQueue::new(2, MinBy(|job: &Job| job.priority.get())).unwrap();
+    heap.insert(Job {
+        id: 1,
+        priority: Cell::new(5),
+    });
+    heap.insert(Job {
+        id: 2,
+        priority: Cell::new(3),
+    });
+    // Only the plain heap: the copy-on-write wrapper needs `T: Sync` as well
+    assert_send(&heap);
+    let popped = std::thread::spawn(move || heap.pop().map(|job| job.id))
+        .join()
+        .unwrap();
+    assert_eq!(popped, Some(2));
+}
@@ -0,0 +1,122 @@
+//! Integration tests for streaming top-k (`TopK`, Space-Saving).
+
+use d_ary_heap::{Error, TopK};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+fn top(d: usize, k: usize) -> TopK<u32> {
+    TopK::new(d, NonZeroUsize::new(k).unwrap()).unwrap()
+}
+
+/// A skewed stream: item `i` of `0..distinct` drawn with weight ~ 1/(i+1),
+/// in a scrambled order.
+fn zipf_stream(distinct: u32, len: usize) -> Vec<u32> {
+    let cumulative: Vec<f64> = (0..distinct)
+        .scan(0.0, |sum, i| {
+            *sum += 1.0 / f64::from(i + 1);
+            Some(*sum)
+        })
+        .collect();
+    let total = cumulative[cumulative.len() - 1];
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            // CAST: 53-bit values, exact in f64.
+            #[allow(clippy::cast_precision_loss)]
+            let target = (state >> 11) as f64 / (1u64 << 53) as f64 * total;
+            let i = cumulative.partition_point(|&c| c < target);
+            u32::try_from(i.min(cumulative.len() - 1)).unwrap()
+        })
+        .collect()
+}
+
+fn true_counts(stream: &[u32]) -> HashMap<u32, u64> {
+    let mut counts = HashMap::new();
+    for &x in stream {
+        *counts.entry(x).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[test]
+fn counts_are_exact_while_everything_fits() {
+    let stream = zipf_stream(20, 5_000);
+    let mut tracker = top(4, 20);
+    for &x in &stream {
+        tracker.observe(x);
+    }
+    let counts = true_counts(&stream);
+    let ranked = tracker.top_k();
+    assert_eq!(ranked.len(), counts.len());
+    for estimate in &ranked {
+        assert_eq!(estimate.error, 0);
+        assert_eq!(estimate.count, counts[&estimate.item]);
+    }
+    assert!(ranked.windows(2).all(|w| w[0].count >= w[1].count));
+    assert_eq!(ranked[0].item, 0);
+}
+
+#[test]
+fn space_saving_guarantees_hold() {
+    let stream = zipf_stream(5_000, 100_000);
+    let counts = true_counts(&stream);
+    let n = stream.len() as u64;
+    for (d, k) in [(2, 10), (4, 50), (8, 200)] {
+        let mut tracker = top(d, k);
+        for &x in &stream {
+            tracker.observe(x);
+        }
+        assert_eq!(tracker.observed(), n);
+        assert_eq!(tracker.len(), k);
+        let ranked = tracker.top_k();
+        let bound = n / k as u64;
+        for estimate in &ranked {
+            let truth = counts[&estimate.item];
+            assert!(estimate.guaranteed() <= truth, "{estimate:?} vs {truth}");
+            assert!(truth <= estimate.count, "{estimate:?} vs {truth}");
+            assert!(estimate.error <= bound, "{estimate:?}, n/k = {bound}");
+        }
+        // Every item seen more than n/k times is tracked
+        for (item, &truth) in &counts {
+            if truth > bound {
+                assert!(ranked.iter().any(|e| e.item == *item), "item {item}");
+            }
+        }
+        // The estimated counts add up to the stream length
+        assert_eq!(ranked.iter().map(|e| e.count).sum::<u64>(), n);
+    }
+}
+
+#[test]
+fn heavy_hitters_come_first() {
+    let stream = zipf_stream(10_000, 200_000);
+    let mut tracker = top(4, 100);
+    for &x in &stream {
+        tracker.observe(x);
+    }
+    let leaders: Vec<u32> = tracker.top_k().iter().take(5).map(|e| e.item).collect();
+    assert_eq!(leaders, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn construction() {
+    assert!(top(2, 3).is_empty());
+    assert_eq!(top(2, 3).k().get(), 3);
+    assert_eq!(
+        TopK::<u32>::new(0, NonZeroUsize::MIN).err(),
+        Some(Error::InvalidArity)
+    );
+    let mut single = top(3, 1);
+    single.observe(7);
+    single.observe(7);
+    single.observe(8);
+    let ranked = single.top_k();
+    assert_eq!(ranked.len(), 1);
+    assert_eq!(
+        (ranked[0].item, ranked[0].count, ranked[0].error),
+        (8, 3, 2)
+    );
+}
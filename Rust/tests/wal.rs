@@ -0,0 +1,326 @@
+//! Integration tests for write-ahead log persistence (`DurablePriorityQueue`).
+//!
+//! A recovered queue must equal the queue that wrote the log, layout
+//! included, whatever mix of operations, torn writes and compactions led to
+//! it; damage before the last record must be reported, never skipped.
+
+use d_ary_heap::{DurablePriorityQueue, Error, MinBy, PriorityQueue, WalError, WalItem};
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::num::NonZeroU64;
+use std::path::PathBuf;
+
+/// A job identified by `id`, ordered by `cost`.
+#[derive(Clone, Debug)]
+struct Job {
+    id: u32,
+    cost: u32,
+}
+
+impl Job {
+    fn new(id: u32, cost: u32) -> Self {
+        Self { id, cost }
+    }
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Job {}
+
+impl Hash for Job {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl WalItem for Job {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (self.id, self.cost).encode(out);
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let (id, cost) = <(u32, u32)>::decode(bytes)?;
+        Some(Self::new(id, cost))
+    }
+}
+
+type ByCost = MinBy<fn(&Job) -> u32>;
+
+fn by_cost() -> ByCost {
+    MinBy(|job: &Job| job.cost)
+}
+
+/// A fresh log path per test, so tests can run in parallel.
+fn log_path(test: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("d_ary_heap_wal_{test}_{}.log", std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn recover(path: &PathBuf) -> DurablePriorityQueue<Job, ByCost> {
+    DurablePriorityQueue::recover(path, 3, by_cost()).unwrap()
+}
+
+/// Runs a fixed mix of every logged operation on both queues.
+fn run_workload(
+    durable: &mut DurablePriorityQueue<Job, ByCost>,
+    plain: &mut PriorityQueue<Job, ByCost>,
+) {
+    for id in 0..20 {
+        durable.insert(Job::new(id, (id * 7) % 11)).unwrap();
+        plain.insert(Job::new(id, (id * 7) % 11));
+    }
+    let batch: Vec<Job> = (20..40).map(|id| Job::new(id, id % 5)).collect();
+    durable.insert_many(batch.clone()).unwrap();
+    plain.insert_many(batch);
+    for _ in 0..5 {
+        assert_eq!(durable.pop().unwrap(), plain.pop());
+    }
+    durable.increase_priority(&Job::new(21, 0)).unwrap();
+    plain.increase_priority(&Job::new(21, 0)).unwrap();
+    durable.decrease_priority(&Job::new(22, 9)).unwrap();
+    plain.decrease_priority(&Job::new(22, 9)).unwrap();
+    durable.update_priority(&Job::new(12, 1)).unwrap();
+    plain.update_priority(&Job::new(12, 1)).unwrap();
+    assert_eq!(
+        durable.remove(&Job::new(33, 0)).unwrap().map(|job| job.id),
+        Some(33)
+    );
+    plain.remove_many([&Job::new(33, 0)]);
+}
+
+#[test]
+fn recover_creates_an_empty_log() {
+    let path = log_path("creates");
+    let q = recover(&path);
+    assert!(q.is_empty());
+    assert_eq!(q.path(), path.as_path());
+    assert!(fs::metadata(&path).unwrap().len() > 0);
+    drop(q);
+    assert!(recover(&path).is_empty());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn recover_rebuilds_the_same_heap() {
+    let path = log_path("rebuilds");
+    let mut durable = recover(&path);
+    let mut plain = PriorityQueue::new(3, by_cost()).unwrap();
+    run_workload(&mut durable, &mut plain);
+    drop(durable);
+
+    let recovered = recover(&path);
+    assert_eq!(recovered.heap().to_array(), plain.to_array());
+    assert_eq!(*recovered.heap(), plain);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn equal_priorities_pop_in_the_same_order_after_recovery() {
+    let path = log_path("ties");
+    let mut durable = recover(&path);
+    let mut plain = PriorityQueue::new(3, by_cost()).unwrap();
+    for id in 0..30 {
+        durable.insert(Job::new(id, id % 3)).unwrap();
+        plain.insert(Job::new(id, id % 3));
+    }
+    durable.pop().unwrap();
+    plain.pop();
+    drop(durable);
+
+    let mut recovered = recover(&path);
+    while let Some(expected) = plain.pop() {
+        assert_eq!(
+            recovered.pop().unwrap().map(|job| job.id),
+            Some(expected.id)
+        );
+    }
+    assert_eq!(recovered.pop().unwrap(), None);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn clear_is_replayed() {
+    let path = log_path("clear");
+    let mut q = recover(&path);
+    q.insert_many((0..5).map(|id| Job::new(id, id))).unwrap();
+    q.clear().unwrap();
+    q.insert(Job::new(9, 9)).unwrap();
+    drop(q);
+
+    let q = recover(&path);
+    assert_eq!(q.len(), 1);
+    assert_eq!(q.peek().map(|job| job.id), Some(9));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejected_operations_log_nothing() {
+    let path = log_path("rejected");
+    let mut q = recover(&path);
+    q.insert(Job::new(1, 5)).unwrap();
+    let size = fs::metadata(&path).unwrap().len();
+
+    assert!(matches!(
+        q.update_priority(&Job::new(2, 1)),
+        Err(WalError::Heap(Error::ItemNotFound))
+    ));
+    assert!(matches!(
+        q.increase_priority(&Job::new(2, 1)),
+        Err(WalError::Heap(Error::ItemNotFound))
+    ));
+    assert_eq!(q.remove(&Job::new(2, 1)).unwrap(), None);
+    q.pop().unwrap();
+    let size_after_pop = fs::metadata(&path).unwrap().len();
+    assert!(size_after_pop > size);
+    assert_eq!(q.pop().unwrap(), None);
+    assert_eq!(fs::metadata(&path).unwrap().len(), size_after_pop);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn torn_final_record_is_dropped_and_cut() {
+    let path = log_path("torn");
+    let mut q = recover(&path);
+    q.insert(Job::new(1, 5)).unwrap();
+    q.insert(Job::new(2, 3)).unwrap();
+    drop(q);
+    let intact = fs::metadata(&path).unwrap().len();
+
+    // A crash midway through appending a third record
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(&[1, 12, 0, 0, 0, 3, 0]).unwrap();
+    drop(file);
+
+    let mut q = recover(&path);
+    assert_eq!(q.len(), 2);
+    assert_eq!(fs::metadata(&path).unwrap().len(), intact);
+    q.insert(Job::new(3, 1)).unwrap();
+    drop(q);
+
+    let q = recover(&path);
+    assert_eq!(q.len(), 3);
+    assert_eq!(q.peek().map(|job| job.id), Some(3));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn last_record_failing_its_checksum_is_a_torn_write() {
+    let path = log_path("torn_checksum");
+    let mut q = recover(&path);
+    q.insert(Job::new(1, 5)).unwrap();
+    q.insert(Job::new(2, 3)).unwrap();
+    drop(q);
+
+    let mut bytes = fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    fs::write(&path, &bytes).unwrap();
+
+    let q = recover(&path);
+    assert_eq!(q.len(), 1);
+    assert_eq!(q.peek().map(|job| job.id), Some(1));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn damage_before_the_last_record_is_corruption() {
+    let path = log_path("corrupt");
+    let mut q = recover(&path);
+    q.insert(Job::new(1, 5)).unwrap();
+    q.insert(Job::new(2, 3)).unwrap();
+    drop(q);
+
+    // Flip a payload byte of the first record, just past the 8-byte header
+    let mut bytes = fs::read(&path).unwrap();
+    bytes[8 + 5] ^= 0xff;
+    fs::write(&path, &bytes).unwrap();
+
+    let err = DurablePriorityQueue::recover(&path, 3, by_cost()).unwrap_err();
+    assert!(matches!(err, WalError::Corrupt { offset: 8 }), "{err}");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn foreign_file_is_corruption() {
+    let path = log_path("foreign");
+    fs::write(&path, b"not a queue log").unwrap();
+    let err = DurablePriorityQueue::recover(&path, 3, by_cost()).unwrap_err();
+    assert!(matches!(err, WalError::Corrupt { offset: 0 }));
+    assert_eq!(fs::read(&path).unwrap(), b"not a queue log");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn zero_arity_is_rejected() {
+    let path = log_path("arity");
+    let err = DurablePriorityQueue::recover(&path, 0, by_cost()).unwrap_err();
+    assert!(matches!(err, WalError::Heap(Error::InvalidArity)));
+}
+
+#[test]
+fn periodic_compaction_bounds_the_log() {
+    let path = log_path("compaction");
+    let mut q = recover(&path);
+    q.set_compact_every(NonZeroU64::new(16));
+    let mut plain = PriorityQueue::new(3, by_cost()).unwrap();
+    let mut largest = 0;
+    for round in 0..50 {
+        q.insert(Job::new(round, round % 7)).unwrap();
+        plain.insert(Job::new(round, round % 7));
+        if round % 3 == 0 {
+            assert_eq!(q.pop().unwrap(), plain.pop());
+        }
+        largest = largest.max(fs::metadata(&path).unwrap().len());
+    }
+    // 34 items plus at most 16 appended records, ~21 bytes each
+    assert!(largest < 21 * (34 + 17) + 8, "log grew to {largest} bytes");
+    drop(q);
+
+    let recovered = recover(&path);
+    assert_eq!(recovered.heap().to_array(), plain.to_array());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn explicit_compaction_preserves_layout() {
+    let path = log_path("compact");
+    let mut q = recover(&path);
+    q.set_compact_every(None);
+    let mut plain = PriorityQueue::new(3, by_cost()).unwrap();
+    run_workload(&mut q, &mut plain);
+    let before = fs::metadata(&path).unwrap().len();
+    q.compact().unwrap();
+    assert!(fs::metadata(&path).unwrap().len() < before);
+
+    // The compacted log keeps accepting records
+    q.insert(Job::new(99, 0)).unwrap();
+    plain.insert(Job::new(99, 0));
+    drop(q);
+
+    let recovered = recover(&path);
+    assert_eq!(recovered.heap().to_array(), plain.to_array());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn builtin_items_round_trip() {
+    let path = log_path("builtin");
+    let mut q = DurablePriorityQueue::recover(&path, 2, MinBy(|x: &(String, i64)| x.1)).unwrap();
+    q.insert(("late".to_string(), 30)).unwrap();
+    q.insert(("ünïcode".to_string(), -4)).unwrap();
+    q.insert((String::new(), 10)).unwrap();
+    drop(q);
+
+    let mut q = DurablePriorityQueue::recover(&path, 2, MinBy(|x: &(String, i64)| x.1)).unwrap();
+    assert_eq!(q.pop().unwrap(), Some(("ünïcode".to_string(), -4)));
+    assert_eq!(q.pop().unwrap(), Some((String::new(), 10)));
+    assert_eq!(q.pop().unwrap(), Some(("late".to_string(), 30)));
+    fs::remove_file(&path).unwrap();
+}
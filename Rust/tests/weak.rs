@@ -0,0 +1,127 @@
+//! Integration tests for weak-entry mode (`WeakEntry`, `pop_live`,
+//! `purge_dead`).
+//!
+//! Entries are identified by allocation, so these tests pin down that equal
+//! values in distinct `Rc`s stay distinct, that priority updates find an entry
+//! through any handle on the same `Rc`, and that expired entries never surface.
+
+use d_ary_heap::{MinBy, PriorityQueue, WeakEntry, WeakPriorityQueue};
+use std::rc::Rc;
+
+type ByPriority = MinBy<fn(&WeakEntry<String, u32>) -> u32>;
+
+fn fresh_weak_min_heap(d: usize) -> WeakPriorityQueue<String, u32, ByPriority> {
+    PriorityQueue::new(
+        d,
+        MinBy(entry_priority as fn(&WeakEntry<String, u32>) -> u32),
+    )
+    .unwrap()
+}
+
+fn entry_priority(e: &WeakEntry<String, u32>) -> u32 {
+    *e.priority()
+}
+
+fn task(name: &str) -> Rc<String> {
+    Rc::new(name.to_string())
+}
+
+#[test]
+fn identity_is_the_allocation() {
+    let mut pq = fresh_weak_min_heap(2);
+    let a = task("same");
+    let b = task("same");
+    pq.insert(WeakEntry::new(&a, 2));
+    pq.insert(WeakEntry::new(&b, 1));
+
+    assert_eq!(pq.len(), 2);
+    assert!(pq.contains(&WeakEntry::new(&a, 0)));
+    assert!(Rc::ptr_eq(&pq.pop_live().unwrap(), &b));
+    assert!(Rc::ptr_eq(&pq.pop_live().unwrap(), &a));
+}
+
+#[test]
+fn priority_update_through_a_fresh_handle() {
+    let mut pq = fresh_weak_min_heap(3);
+    let tasks: Vec<Rc<String>> = (0..6).map(|i| task(&format!("t{i}"))).collect();
+    for (p, t) in (10u32..).step_by(10).zip(&tasks) {
+        pq.insert(WeakEntry::new(t, p));
+    }
+
+    pq.increase_priority(&WeakEntry::new(&tasks[5], 1)).unwrap();
+    assert_eq!(*pq.front().priority(), 1);
+    assert!(Rc::ptr_eq(&pq.pop_live().unwrap(), &tasks[5]));
+}
+
+#[test]
+fn pop_live_skips_dropped_items() {
+    let mut pq = fresh_weak_min_heap(4);
+    let mut tasks: Vec<Option<Rc<String>>> =
+        (0..10).map(|i| Some(task(&format!("t{i}")))).collect();
+    for (p, t) in (0u32..).zip(&tasks) {
+        pq.insert(WeakEntry::new(t.as_ref().unwrap(), p));
+    }
+
+    // Cancel every even task by dropping its only strong reference
+    for t in tasks.iter_mut().step_by(2) {
+        *t = None;
+    }
+
+    let popped: Vec<String> = std::iter::from_fn(|| pq.pop_live())
+        .map(|t| t.as_str().to_owned())
+        .collect();
+    assert_eq!(popped, vec!["t1", "t3", "t5", "t7", "t9"]);
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn pop_live_on_all_expired_empties_the_heap() {
+    let mut pq = fresh_weak_min_heap(2);
+    for p in 0..5 {
+        pq.insert(WeakEntry::new(&task("gone"), p));
+    }
+    assert_eq!(pq.len(), 5);
+    assert_eq!(pq.pop_live(), None);
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn purge_dead_restores_heap_and_positions() {
+    let mut pq = fresh_weak_min_heap(3);
+    let keep: Vec<Rc<String>> = (0..8).map(|i| task(&format!("k{i}"))).collect();
+    for (i, t) in keep.iter().enumerate() {
+        let p = u32::try_from(i).unwrap();
+        pq.insert(WeakEntry::new(t, 100 - p));
+        pq.insert(WeakEntry::new(&task("transient"), p));
+    }
+
+    assert_eq!(pq.purge_dead(), 8);
+    assert_eq!(pq.purge_dead(), 0);
+    assert_eq!(pq.len(), 8);
+    for t in &keep {
+        let pos = pq.get_position(&WeakEntry::new(t, 0)).unwrap();
+        assert!(Rc::ptr_eq(
+            &pq.cursor_at(pos).unwrap().item().upgrade().unwrap(),
+            t
+        ));
+    }
+
+    let priorities: Vec<u32> = std::iter::from_fn(|| pq.pop())
+        .map(|e| *e.priority())
+        .collect();
+    assert_eq!(priorities, vec![93, 94, 95, 96, 97, 98, 99, 100]);
+}
+
+#[test]
+fn purge_dead_counts_as_pop_for_stats() {
+    let mut pq =
+        PriorityQueue::with_stats(2, MinBy(|e: &WeakEntry<u32, u32>| *e.priority())).unwrap();
+    let keep: Vec<Rc<u32>> = (0..6).map(Rc::new).collect();
+    for t in &keep {
+        pq.insert(WeakEntry::new(t, 10 - **t));
+        pq.insert(WeakEntry::new(&Rc::new(99), **t));
+    }
+    let before = pq.stats().pop();
+    assert_eq!(pq.purge_dead(), 6);
+    assert!(pq.stats().pop() > before);
+}
@@ -1,7 +1,9 @@
 //! dijkstra.rs - Dijkstra's shortest path algorithm implementation
 
 use crate::types::{DijkstraResult, Graph, Vertex};
-use d_ary_heap::{ComparisonStats, MinBy, PriorityCompare, PriorityQueue, StatsCollector};
+use d_ary_heap::{
+    ChildScan, ComparisonStats, MinBy, PriorityCompare, PriorityQueue, StatsCollector,
+};
 use std::collections::HashMap;
 
 /// Infinity represents an unreachable distance.
@@ -17,12 +19,14 @@ pub const INFINITY: i32 = i32::MAX;
 /// * `graph` - The input graph with vertices and weighted edges
 /// * `source` - The source vertex to find shortest paths from
 /// * `d` - The arity of the heap (typically 4 for optimal performance)
+/// * `scan` - The heap's sift-down child scan policy
 ///
 /// # Returns
 ///
 /// A `DijkstraResult` containing distances and predecessors for path reconstruction.
-pub fn dijkstra(graph: &Graph, source: &str, d: usize) -> DijkstraResult {
+pub fn dijkstra(graph: &Graph, source: &str, d: usize, scan: ChildScan) -> DijkstraResult {
     let mut pq = PriorityQueue::new(d, MinBy(|v: &Vertex| v.distance)).unwrap();
+    pq.set_child_scan(scan);
     dijkstra_with_pq(graph, source, &mut pq)
 }
 
@@ -33,8 +37,10 @@ pub fn dijkstra_instrumented(
     graph: &Graph,
     source: &str,
     d: usize,
+    scan: ChildScan,
 ) -> (DijkstraResult, ComparisonStats) {
     let mut pq = PriorityQueue::with_stats(d, MinBy(|v: &Vertex| v.distance)).unwrap();
+    pq.set_child_scan(scan);
     let result = dijkstra_with_pq(graph, source, &mut pq);
     (result, pq.stats().clone())
 }
@@ -68,7 +74,9 @@ where
     let mut distances: HashMap<String, i32> = HashMap::new();
     let mut predecessors: HashMap<String, Option<String>> = HashMap::new();
 
-    // Set initial distances and add to priority queue
+    // Set initial distances and add to priority queue, sized for every
+    // vertex up front
+    pq.reserve(graph.vertices.len());
     for vertex in &graph.vertices {
         let distance = if vertex == source { 0 } else { INFINITY };
         distances.insert(vertex.clone(), distance);
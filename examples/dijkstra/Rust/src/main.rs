@@ -5,8 +5,8 @@
 mod dijkstra;
 mod types;
 
-use clap::Parser;
-use d_ary_heap::StatsCollector;
+use clap::{Parser, ValueEnum};
+use d_ary_heap::{ChildScan, StatsCollector};
 use dijkstra::{dijkstra, dijkstra_instrumented, reconstruct_path, INFINITY};
 use std::fs;
 use std::hint::black_box;
@@ -61,6 +61,35 @@ struct Args {
     /// Requires --arity=<d>. Output is one JSON object on stdout.
     #[arg(long)]
     report_rss: bool,
+
+    /// Sift-down child scan policy; recorded in --json output when given.
+    #[arg(long, value_enum)]
+    child_scan: Option<ScanPolicy>,
+}
+
+/// Command-line names for `d_ary_heap::ChildScan`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScanPolicy {
+    Leftmost,
+    FollowBest,
+}
+
+impl Args {
+    fn scan(&self) -> ChildScan {
+        match self.child_scan {
+            None | Some(ScanPolicy::Leftmost) => ChildScan::Leftmost,
+            Some(ScanPolicy::FollowBest) => ChildScan::FollowBest,
+        }
+    }
+
+    /// Adds the scan policy to a JSON record if one was asked for.
+    fn tag(&self, mut record: serde_json::Value) -> serde_json::Value {
+        if let Some(policy) = self.child_scan {
+            let name = policy.to_possible_value().unwrap();
+            record["child_scan"] = serde_json::json!(name.get_name());
+        }
+        record
+    }
 }
 
 #[cfg(windows)]
@@ -154,7 +183,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if args.report_rss {
         let d = args.arity.ok_or("--report-rss requires --arity=<d>")?;
         // black_box ensures the call (and its allocations) are not elided.
-        let _ = black_box(dijkstra(&graph, &source, d));
+        let _ = black_box(dijkstra(&graph, &source, d, args.scan()));
         let peak = peak_rss_kb().unwrap_or(0);
         let record = serde_json::json!({
             "schema_version": 1,
@@ -163,7 +192,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "arity": d,
             "peak_rss_kb": peak,
         });
-        println!("{}", record);
+        println!("{}", args.tag(record));
         return Ok(());
     }
 
@@ -192,10 +221,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let start = Instant::now();
         let (result, stats) = if args.stats {
-            let (r, s) = dijkstra_instrumented(&graph, &source, d);
+            let (r, s) = dijkstra_instrumented(&graph, &source, d, args.scan());
             (r, Some(s))
         } else {
-            (dijkstra(&graph, &source, d), None)
+            (dijkstra(&graph, &source, d, args.scan()), None)
         };
         let elapsed = start.elapsed();
 
@@ -244,7 +273,7 @@ fn run_json(
     env: Option<&serde_json::Value>,
 ) {
     if args.stats {
-        let (_result, stats) = dijkstra_instrumented(graph, source, d);
+        let (_result, stats) = dijkstra_instrumented(graph, source, d, args.scan());
         let record = serde_json::json!({
             "schema_version": 1,
             "language": "Rust",
@@ -259,18 +288,18 @@ fn run_json(
                 "total": stats.total(),
             }
         });
-        println!("{}", record);
+        println!("{}", args.tag(record));
         return;
     }
 
     // black_box on both warmup and timed calls — the result is discarded, and
     // we don't want the optimizer to weaken or elide the work between timer reads.
     for _ in 0..args.warmup {
-        let _ = black_box(dijkstra(graph, source, d));
+        let _ = black_box(dijkstra(graph, source, d, args.scan()));
     }
     for rep in 1..=args.repetitions {
         let start = Instant::now();
-        let _ = black_box(dijkstra(graph, source, d));
+        let _ = black_box(dijkstra(graph, source, d, args.scan()));
         let elapsed = start.elapsed();
         let wall_time_us = elapsed.as_secs_f64() * 1_000_000.0;
         let record = match env {
@@ -296,6 +325,6 @@ fn run_json(
                 "wall_time_us": wall_time_us,
             }),
         };
-        println!("{}", record);
+        println!("{}", args.tag(record));
     }
 }
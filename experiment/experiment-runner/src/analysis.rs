@@ -0,0 +1,729 @@
+//! Syntax-Aware Test Counting
+//!
+//! Counts the tests in a generated file by parsing it, instead of matching
+//! lines: a `#[test]` in a comment, a `test "..."` inside a string, or a
+//! `#[tokio::test]` no longer skew the counts the test-mimicking study is
+//! built on.
+//!
+//! | Language | Parser | A test is |
+//! |----------|--------|-----------|
+//! | rust | `syn` | a `fn` with a `#[test]` or `#[<runtime>::test]` attribute |
+//! | go | tree-sitter | a `func TestXxx(t *testing.T)` |
+//! | cpp | tree-sitter | a GoogleTest `TEST` / `TEST_F` / `TEST_P` / `TYPED_TEST` |
+//! | typescript | tree-sitter | an `it(...)` / `test(...)` call, including `.only` / `.skip` |
+//! | zig | comment- and string-aware scan | a `test` declaration |
+//!
+//! Zig has no tree-sitter grammar in the crates.io ecosystem we build
+//! against, so it gets a scanner that skips comments and string literals
+//! before looking for the `test` keyword.
+//!
+//! tree-sitter recovers from syntax errors, so code cut off at `max_tokens`
+//! still yields the tests before the cut; `syn` does not, and a Rust file
+//! that does not parse is reported as an error rather than guessed at.
+//!
+//! [`compare`] scores how closely generated code follows the repository's
+//! reference implementation of its language ([`reference_file`]), on three
+//! scales from 0 (nothing in common) to 1 (identical):
+//!
+//! - `token_ratio`: 2·LCS / (n + m) over the two token sequences, comments
+//!   and whitespace excluded, so reformatting and comments do not count
+//! - `ast_overlap`: weighted Jaccard of the parent → child node-kind pairs of
+//!   the two tree-sitter syntax trees (structure regardless of names);
+//!   `None` for Zig, which has no grammar
+//! - `identifier_jaccard`: Jaccard of the two sets of identifiers (naming
+//!   regardless of structure)
+//!
+//! `analyze --embeddings` adds a fourth, the cosine similarity of the two
+//! files' embeddings, which needs no grammar; see [`crate::embedding`].
+
+use crate::embedding::EmbeddingSimilarity;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use syn::visit::Visit;
+
+/// The tests found in one source file
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TestInventory {
+    /// Test names in source order (GoogleTest tests as `Suite.Name`)
+    pub names: Vec<String>,
+}
+
+impl TestInventory {
+    pub fn count(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// Contents of the `{prefix}_analysis.json` file that `analyze` writes
+/// next to each `{prefix}_code.{ext}` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeAnalysis {
+    pub language: String,
+    /// Number of tests, `None` if the code could not be analyzed
+    pub test_count: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_names: Vec<String>,
+    /// Why the code could not be analyzed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Similarity to the reference implementation, when it was available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<Similarity>,
+}
+
+/// Similarity of generated code to a reference implementation; see the
+/// [module docs](self) for the metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Similarity {
+    /// Reference file, relative to the repository root
+    pub reference: String,
+    pub token_ratio: f64,
+    pub ast_overlap: Option<f64>,
+    pub identifier_jaccard: f64,
+    /// Set by `analyze --embeddings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<EmbeddingSimilarity>,
+}
+
+impl CodeAnalysis {
+    /// Analyze `source`, recording a failure instead of returning it
+    pub fn of(language: &str, source: &str) -> Self {
+        let (test_count, test_names, error) = match count_tests(language, source) {
+            Ok(tests) => (Some(tests.count()), tests.names, None),
+            Err(e) => (None, Vec::new(), Some(e.to_string())),
+        };
+        Self {
+            language: language.to_string(),
+            test_count,
+            test_names,
+            error,
+            similarity: None,
+        }
+    }
+}
+
+/// The repository's reference implementation of `language`, relative to the
+/// repository root
+pub fn reference_file(language: &str) -> Option<&'static str> {
+    match language {
+        "rust" => Some("Rust/src/lib.rs"),
+        "go" => Some("Go/src/dheap.go"),
+        "cpp" => Some("Cpp/PriorityQueue.h"),
+        "typescript" => Some("TypeScript/src/PriorityQueue.ts"),
+        "zig" => Some("zig/src/d_heap.zig"),
+        _ => None,
+    }
+}
+
+/// Split a result file name `{prefix}_code.{ext}` into its prefix and
+/// language; `None` for any other file
+pub fn code_file_prefix(file_name: &str) -> Option<(&str, &'static str)> {
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    let prefix = stem.strip_suffix("_code")?;
+    let language = match ext {
+        "rs" => "rust",
+        "go" => "go",
+        "hpp" | "cpp" | "h" | "cc" => "cpp",
+        "ts" => "typescript",
+        "zig" => "zig",
+        _ => return None,
+    };
+    Some((prefix, language))
+}
+
+/// Count the tests in `source`, written in `language` (a normalized
+/// language name)
+pub fn count_tests(language: &str, source: &str) -> Result<TestInventory> {
+    let names = match language {
+        "rust" => rust_tests(source)?,
+        "go" => tree_sitter_tests(tree_sitter_go::LANGUAGE.into(), source, go_test)?,
+        "cpp" => tree_sitter_tests(tree_sitter_cpp::LANGUAGE.into(), source, cpp_test)?,
+        "typescript" => tree_sitter_tests(
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            source,
+            typescript_test,
+        )?,
+        "zig" => zig_tests(source),
+        _ => return Err(anyhow!("No test counter for language: {}", language)),
+    };
+    Ok(TestInventory { names })
+}
+
+/// Score `source` against `reference_source`, the contents of the reference
+/// file `reference`, both written in `language`
+pub fn compare(language: &str, source: &str, reference: &str, reference_source: &str) -> Result<Similarity> {
+    let tokens = lex(language, source);
+    let reference_tokens = lex(language, reference_source);
+
+    let (ast_overlap, identifiers, reference_identifiers) = match grammar(language) {
+        Some(grammar) => {
+            let (pairs, identifiers) = syntax_profile(&grammar, source)?;
+            let (reference_pairs, reference_identifiers) = syntax_profile(&grammar, reference_source)?;
+            (
+                Some(weighted_jaccard(&pairs, &reference_pairs)),
+                identifiers,
+                reference_identifiers,
+            )
+        }
+        None => (
+            None,
+            lexical_identifiers(language, &tokens),
+            lexical_identifiers(language, &reference_tokens),
+        ),
+    };
+
+    Ok(Similarity {
+        reference: reference.to_string(),
+        token_ratio: token_ratio(&tokens, &reference_tokens),
+        ast_overlap,
+        identifier_jaccard: jaccard(&identifiers, &reference_identifiers),
+        embedding: None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Rust (syn)
+// ---------------------------------------------------------------------------
+
+fn rust_tests(source: &str) -> Result<Vec<String>> {
+    let file = syn::parse_file(source).map_err(|e| anyhow!("Rust parse error: {}", e))?;
+
+    #[derive(Default)]
+    struct Tests(Vec<String>);
+
+    impl<'ast> Visit<'ast> for Tests {
+        fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+            let is_test = item.attrs.iter().any(|attr| {
+                attr.path()
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "test")
+            });
+            if is_test {
+                self.0.push(item.sig.ident.to_string());
+            }
+            syn::visit::visit_item_fn(self, item);
+        }
+    }
+
+    let mut tests = Tests::default();
+    tests.visit_file(&file);
+    Ok(tests.0)
+}
+
+// ---------------------------------------------------------------------------
+// Go, C++, TypeScript (tree-sitter)
+// ---------------------------------------------------------------------------
+
+/// Walk every node of `source`'s syntax tree, collecting the names that
+/// `test_name` extracts from test nodes
+fn tree_sitter_tests(
+    language: tree_sitter::Language,
+    source: &str,
+    test_name: fn(tree_sitter::Node, &[u8]) -> Option<String>,
+) -> Result<Vec<String>> {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&language)
+        .map_err(|e| anyhow!("Incompatible tree-sitter grammar: {}", e))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow!("tree-sitter parse was cancelled"))?;
+
+    let bytes = source.as_bytes();
+    let mut names = Vec::new();
+    let mut cursor = tree.walk();
+    'walk: loop {
+        if let Some(name) = test_name(cursor.node(), bytes) {
+            names.push(name);
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+    Ok(names)
+}
+
+fn text<'a>(node: tree_sitter::Node, bytes: &'a [u8]) -> &'a str {
+    node.utf8_text(bytes).unwrap_or("")
+}
+
+/// `func TestXxx(t *testing.T)`; `Xxx` must not start with a lowercase letter
+fn go_test(node: tree_sitter::Node, bytes: &[u8]) -> Option<String> {
+    if node.kind() != "function_declaration" {
+        return None;
+    }
+    let name = text(node.child_by_field_name("name")?, bytes);
+    let rest = name.strip_prefix("Test")?;
+    if rest.starts_with(|c: char| c.is_lowercase()) {
+        return None;
+    }
+    let params = text(node.child_by_field_name("parameters")?, bytes);
+    params.contains("testing.T").then(|| name.to_string())
+}
+
+/// `TEST(Suite, Name)` and friends, which parse as either a function
+/// declarator or a call depending on what follows
+fn cpp_test(node: tree_sitter::Node, bytes: &[u8]) -> Option<String> {
+    let (callee, arguments) = match node.kind() {
+        "function_declarator" => ("declarator", "parameters"),
+        "call_expression" => ("function", "arguments"),
+        _ => return None,
+    };
+    let macro_name = text(node.child_by_field_name(callee)?, bytes);
+    if !matches!(macro_name, "TEST" | "TEST_F" | "TEST_P" | "TYPED_TEST") {
+        return None;
+    }
+    let arguments = text(node.child_by_field_name(arguments)?, bytes);
+    let parts: Vec<&str> = arguments
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .collect();
+    Some(parts.join("."))
+}
+
+/// `it("...", ...)`, `test("...", ...)`, `it.only(...)`, `test.skip(...)`
+fn typescript_test(node: tree_sitter::Node, bytes: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let callee = node.child_by_field_name("function")?;
+    let base = match callee.kind() {
+        "identifier" => callee,
+        "member_expression" => {
+            let property = text(callee.child_by_field_name("property")?, bytes);
+            if !matches!(property, "only" | "skip" | "todo" | "concurrent") {
+                return None;
+            }
+            callee.child_by_field_name("object")?
+        }
+        _ => return None,
+    };
+    if !matches!(text(base, bytes), "it" | "test") {
+        return None;
+    }
+    let arguments = node.child_by_field_name("arguments")?;
+    let mut walk = arguments.walk();
+    let first = arguments.named_children(&mut walk).next()?;
+    Some(
+        text(first, bytes)
+            .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+            .to_string(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Zig (scanner)
+// ---------------------------------------------------------------------------
+
+/// `test "name" {`, `test name {` and `test {` declarations, skipping
+/// comments, string and character literals, and `\\` multiline strings
+fn zig_tests(source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut names = Vec::new();
+    let mut i = 0;
+
+    // Skip a quoted literal starting at `start`; returns the index after it
+    let skip_quoted = |start: usize, quote: char| {
+        let mut j = start + 1;
+        while j < chars.len() && chars[j] != quote && chars[j] != '\n' {
+            j += if chars[j] == '\\' { 2 } else { 1 };
+        }
+        j + 1
+    };
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    while i < chars.len() {
+        let c = chars[i];
+        if (c == '/' || c == '\\') && chars.get(i + 1) == Some(&c) {
+            // Line comment or multiline string line: skip to end of line
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '"' || c == '\'' {
+            i = skip_quoted(i, c);
+        } else if is_ident(c) {
+            let start = i;
+            while i < chars.len() && is_ident(chars[i]) {
+                i += 1;
+            }
+            let preceded_by_dot = start > 0 && chars[start - 1] == '.';
+            if !preceded_by_dot && chars[start..i].iter().collect::<String>() == "test" {
+                let mut j = i;
+                while j < chars.len() && chars[j].is_whitespace() {
+                    j += 1;
+                }
+                match chars.get(j) {
+                    Some('"') => {
+                        let end = skip_quoted(j, '"').min(chars.len() + 1);
+                        names.push(chars[j + 1..end - 1].iter().collect());
+                        i = end;
+                    }
+                    Some('{') => names.push(String::new()),
+                    Some(&n) if is_ident(n) => {
+                        let name_start = j;
+                        while j < chars.len() && is_ident(chars[j]) {
+                            j += 1;
+                        }
+                        if chars[j..].iter().find(|c| !c.is_whitespace()) == Some(&'{') {
+                            names.push(chars[name_start..j].iter().collect());
+                        }
+                        i = j;
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            i += 1;
+        }
+    }
+    names
+}
+
+// ---------------------------------------------------------------------------
+// Similarity
+// ---------------------------------------------------------------------------
+
+fn grammar(language: &str) -> Option<tree_sitter::Language> {
+    match language {
+        "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "go" => Some(tree_sitter_go::LANGUAGE.into()),
+        "cpp" => Some(tree_sitter_cpp::LANGUAGE.into()),
+        "typescript" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        _ => None,
+    }
+}
+
+/// Occurrences of each (parent kind, child kind) pair of a syntax tree
+type KindPairs = HashMap<(u16, u16), usize>;
+
+/// The parent → child named-node-kind pairs and the set of identifiers of
+/// `source`'s syntax tree
+fn syntax_profile(grammar: &tree_sitter::Language, source: &str) -> Result<(KindPairs, HashSet<String>)> {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(grammar)
+        .map_err(|e| anyhow!("Incompatible tree-sitter grammar: {}", e))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow!("tree-sitter parse was cancelled"))?;
+
+    let bytes = source.as_bytes();
+    let mut pairs = HashMap::new();
+    let mut identifiers = HashSet::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let mut walk = node.walk();
+        for child in node.named_children(&mut walk) {
+            if child.kind().contains("comment") {
+                continue;
+            }
+            *pairs.entry((node.kind_id(), child.kind_id())).or_insert(0) += 1;
+            if child.kind().ends_with("identifier") {
+                identifiers.insert(text(child, bytes).to_string());
+            }
+            stack.push(child);
+        }
+    }
+    Ok((pairs, identifiers))
+}
+
+/// Σ min / Σ max over the keys of two multisets
+fn weighted_jaccard<K: Eq + std::hash::Hash>(a: &HashMap<K, usize>, b: &HashMap<K, usize>) -> f64 {
+    let (mut shared, mut total) = (0, 0);
+    for (key, &count) in a {
+        let other = b.get(key).copied().unwrap_or(0);
+        shared += count.min(other);
+        total += count.max(other);
+    }
+    total += b.iter().filter(|(key, _)| !a.contains_key(key)).map(|(_, &count)| count).sum::<usize>();
+    if total == 0 {
+        1.0
+    } else {
+        shared as f64 / total as f64
+    }
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        1.0
+    } else {
+        a.intersection(b).count() as f64 / union as f64
+    }
+}
+
+/// 2·LCS / (n + m), the normalized similarity of two token sequences
+fn token_ratio<'a>(a: &'a [String], b: &'a [String]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    // Intern the tokens so the O(n·m) table compares integers
+    let mut ids: HashMap<&str, u32> = HashMap::new();
+    let mut intern = |token: &'a str| {
+        let next = ids.len() as u32;
+        *ids.entry(token).or_insert(next)
+    };
+    let a: Vec<u32> = a.iter().map(|token| intern(token)).collect();
+    let b: Vec<u32> = b.iter().map(|token| intern(token)).collect();
+
+    let mut previous = vec![0u32; b.len() + 1];
+    let mut current = vec![0u32; b.len() + 1];
+    for &x in &a {
+        for (j, &y) in b.iter().enumerate() {
+            current[j + 1] = if x == y {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    2.0 * f64::from(previous[b.len()]) / (a.len() + b.len()) as f64
+}
+
+/// Zig keywords, left out of its identifier set
+const ZIG_KEYWORDS: &[&str] = &[
+    "addrspace", "align", "allowzero", "and", "anyframe", "anytype", "asm", "async", "await", "break",
+    "callconv", "catch", "comptime", "const", "continue", "defer", "else", "enum", "errdefer", "error",
+    "export", "extern", "fn", "for", "if", "inline", "linksection", "noalias", "noinline", "nosuspend",
+    "opaque", "or", "orelse", "packed", "pub", "resume", "return", "struct", "suspend", "switch", "test",
+    "threadlocal", "try", "union", "unreachable", "usingnamespace", "var", "volatile", "while",
+];
+
+fn lexical_identifiers(language: &str, tokens: &[String]) -> HashSet<String> {
+    tokens
+        .iter()
+        .filter(|token| token.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .filter(|token| language != "zig" || !ZIG_KEYWORDS.contains(&token.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Split `source` into tokens, dropping whitespace and comments: words
+/// (identifiers, keywords, numbers), string and character literals, and
+/// single punctuation characters. Covers the lexical syntax of all five
+/// languages closely enough for comparison.
+fn lex(language: &str, source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    // Index after the literal that starts at `start` with `quote`
+    let end_of_quoted = |start: usize, quote: char| {
+        let mut j = start + 1;
+        while j < chars.len() && chars[j] != quote && (quote == '`' || chars[j] != '\n') {
+            j += if chars[j] == '\\' { 2 } else { 1 };
+        }
+        (j + 1).min(chars.len())
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i - 1] == '*' && chars[i] == '/') {
+                i += 1;
+            }
+            i += 1;
+            continue;
+        } else if language == "zig" && c == '\\' && next == Some('\\') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '"' || c == '`' {
+            i = end_of_quoted(i, c);
+        } else if c == '\'' {
+            // A Rust lifetime or label ('a, 'outer) unless it closes like a
+            // character literal ('a', '\n')
+            let lifetime = language == "rust"
+                && next.is_some_and(is_word)
+                && chars.get(i + 2) != Some(&'\'');
+            if lifetime {
+                i += 1;
+                while i < chars.len() && is_word(chars[i]) {
+                    i += 1;
+                }
+            } else {
+                i = end_of_quoted(i, c);
+            }
+        } else if is_word(c) {
+            while i < chars.len() && is_word(chars[i]) {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+        tokens.push(chars[start..i.min(chars.len())].iter().collect());
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(language: &str, source: &str) -> Vec<String> {
+        count_tests(language, source).unwrap().names
+    }
+
+    #[test]
+    fn rust_counts_test_attributes_of_any_runtime() {
+        let source = r##"
+            /// Not a test: #[test]
+            fn helper() -> &'static str { "#[test] fn fake() {}" }
+
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn inserts() {}
+
+                #[tokio::test]
+                async fn pops() {}
+
+                fn not_a_test() {}
+            }
+        "##;
+        assert_eq!(names("rust", source), ["inserts", "pops"]);
+    }
+
+    #[test]
+    fn rust_that_does_not_parse_is_an_error() {
+        let analysis = CodeAnalysis::of("rust", "#[test]\nfn cut_off() {");
+        assert_eq!(analysis.test_count, None);
+        assert!(analysis.error.unwrap().contains("Rust parse error"));
+    }
+
+    #[test]
+    fn go_counts_test_functions_taking_testing_t() {
+        let source = r#"
+            package dheap
+
+            import "testing"
+
+            // func TestInComment(t *testing.T) {}
+            func TestInsert(t *testing.T) {}
+            func Test_Pop(t *testing.T) {}
+            func Testify(t *testing.T) {}
+            func TestHelper(n int) {}
+            func BenchmarkInsert(b *testing.B) {}
+        "#;
+        assert_eq!(names("go", source), ["TestInsert", "Test_Pop"]);
+    }
+
+    #[test]
+    fn cpp_counts_googletest_macros_as_suite_dot_name() {
+        let source = r#"
+            #include <gtest/gtest.h>
+
+            // TEST(Commented, Out) {}
+            TEST(PriorityQueue, Insert) {
+                EXPECT_EQ(1, 1);
+            }
+
+            TEST_F(HeapFixture, Pop) {
+                const char* s = "TEST(In, String)";
+            }
+
+            TYPED_TEST(Typed, Peek) {}
+        "#;
+        assert_eq!(
+            names("cpp", source),
+            ["PriorityQueue.Insert", "HeapFixture.Pop", "Typed.Peek"]
+        );
+    }
+
+    #[test]
+    fn typescript_counts_it_and_test_calls_with_modifiers() {
+        let source = r#"
+            describe('PriorityQueue', () => {
+                it('inserts', () => {});
+                test("pops", () => {});
+                it.only(`peeks`, () => {});
+                test.skip('removes', () => {});
+                it.each([1, 2])('is not counted', () => {});
+                const label = "it('in a string', () => {})";
+                // test('in a comment', () => {});
+            });
+        "#;
+        assert_eq!(
+            names("typescript", source),
+            ["inserts", "pops", "peeks", "removes"]
+        );
+    }
+
+    #[test]
+    fn typescript_cut_off_mid_file_keeps_the_earlier_tests() {
+        let source = "it('first', () => {});\nit('second', () => {\n  expect(";
+        assert_eq!(names("typescript", source), ["first"]);
+    }
+
+    #[test]
+    fn zig_counts_test_declarations_outside_comments_and_strings() {
+        let source = r#"
+            const std = @import("std");
+            const testing = std.testing;
+
+            // test "in a comment" {}
+            const message = "test \"in a string\" {}";
+            const doc =
+                \\test "in a multiline string" {}
+            ;
+
+            test "insert keeps order" {
+                try testing.expect(true);
+            }
+
+            test decl_name {}
+
+            test {
+                std.testing.refAllDecls(@This());
+            }
+        "#;
+        assert_eq!(
+            names("zig", source),
+            ["insert keeps order", "decl_name", ""]
+        );
+    }
+
+    #[test]
+    fn unknown_languages_have_no_counter() {
+        assert!(count_tests("cobol", "").is_err());
+    }
+
+    #[test]
+    fn code_file_prefix_maps_extensions_to_languages() {
+        assert_eq!(code_file_prefix("run_3_code.hpp"), Some(("run_3", "cpp")));
+        assert_eq!(code_file_prefix("a_code.ts"), Some(("a", "typescript")));
+        assert_eq!(code_file_prefix("a_meta.json"), None);
+        assert_eq!(code_file_prefix("a_response.rs"), None);
+    }
+
+    #[test]
+    fn identical_code_is_fully_similar() {
+        let source = "fn main() { let x = 1; }";
+        let similarity = compare("rust", source, "ref.rs", source).unwrap();
+        assert_eq!(similarity.token_ratio, 1.0);
+        assert_eq!(similarity.ast_overlap, Some(1.0));
+        assert_eq!(similarity.identifier_jaccard, 1.0);
+    }
+}
@@ -1,6 +1,7 @@
 //! Anthropic Claude Provider
 
-use crate::provider::{LlmProvider, LlmResponse, RequestConfig};
+use crate::provider::{ask_to_continue, LlmProvider, LlmResponse, Message, RequestConfig, Role};
+use crate::structured;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -9,6 +10,9 @@ const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_MODELS_URL: &str = "https://api.anthropic.com/v1/models";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
+/// Model used when no `--model` is given
+pub const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
+
 pub struct AnthropicProvider {
     api_key: String,
     client: reqwest::Client,
@@ -28,18 +32,88 @@ impl AnthropicProvider {
 }
 
 #[derive(Serialize)]
-struct AnthropicRequest {
+struct AnthropicRequest<'a> {
     model: String,
     max_tokens: u32,
-    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<Content<'a>>,
+    messages: Vec<AnthropicMessage<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<Thinking>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<Tool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<ToolChoice>,
+}
+
+/// A tool the model may call, its input constrained by a JSON schema
+#[derive(Serialize)]
+struct Tool {
+    name: &'static str,
+    description: &'static str,
+    input_schema: serde_json::Value,
+}
+
+/// The tool the model must call
+#[derive(Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: &'static str,
+    name: &'static str,
+}
+
+/// Extended thinking, with a budget of tokens to reason in
+#[derive(Serialize)]
+struct Thinking {
+    #[serde(rename = "type")]
+    thinking_type: &'static str,
+    budget_tokens: u32,
+}
+
+#[derive(Serialize)]
+struct AnthropicMessage<'a> {
+    role: Role,
+    content: Content<'a>,
+}
+
+/// Plain text, or a text block marked as the end of a prefix to cache
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Content<'a> {
+    Text(&'a str),
+    Cached([TextBlock<'a>; 1]),
+}
+
+impl<'a> Content<'a> {
+    fn new(text: &'a str, cache: bool) -> Self {
+        if cache {
+            Content::Cached([TextBlock {
+                block_type: "text",
+                text,
+                cache_control: CacheControl { cache_type: "ephemeral" },
+            }])
+        } else {
+            Content::Text(text)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TextBlock<'a> {
+    #[serde(rename = "type")]
+    block_type: &'static str,
+    text: &'a str,
+    cache_control: CacheControl,
 }
 
 #[derive(Serialize)]
-struct Message {
-    role: String,
-    content: String,
+struct CacheControl {
+    #[serde(rename = "type")]
+    cache_type: &'static str,
 }
 
 #[derive(Deserialize)]
@@ -47,6 +121,8 @@ struct AnthropicResponse {
     content: Vec<ContentBlock>,
     usage: Usage,
     model: String,
+    #[serde(default)]
+    stop_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -54,12 +130,20 @@ struct ContentBlock {
     #[serde(rename = "type")]
     content_type: String,
     text: Option<String>,
+    /// Summarized reasoning of a `thinking` block
+    thinking: Option<String>,
+    /// Arguments of a `tool_use` block
+    input: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
 struct Usage {
     input_tokens: usize,
     output_tokens: usize,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<usize>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -118,23 +202,52 @@ impl AnthropicProvider {
 #[async_trait]
 impl LlmProvider for AnthropicProvider {
     fn default_model(&self) -> &str {
-        "claude-sonnet-4-20250514"
+        DEFAULT_MODEL
     }
 
-    async fn complete(&self, prompt: &str, config: &RequestConfig) -> Result<LlmResponse> {
+    async fn chat(&self, messages: &[Message], config: &RequestConfig) -> Result<LlmResponse> {
         let model = config
             .model
             .as_deref()
             .unwrap_or_else(|| self.default_model());
 
+        // With caching, the system prompt and the last message end cached
+        // prefixes: a repeated prompt reads the first, and each repair turn
+        // reads the conversation the previous turn cached
+        let last = messages.len().saturating_sub(1);
         let request = AnthropicRequest {
             model: model.to_string(),
             max_tokens: config.max_tokens,
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-            temperature: Some(config.temperature),
+            system: config.system.as_deref().map(|system| Content::new(system, config.cache)),
+            messages: messages
+                .iter()
+                .enumerate()
+                .map(|(i, message)| AnthropicMessage {
+                    role: message.role,
+                    content: Content::new(&message.content, config.cache && i == last),
+                })
+                .collect(),
+            // Thinking requires the default temperature (1)
+            temperature: config.thinking_budget.is_none().then_some(config.temperature),
+            top_p: config.top_p,
+            thinking: config.thinking_budget.map(|budget_tokens| Thinking {
+                thinking_type: "enabled",
+                budget_tokens,
+            }),
+            // A structured answer is the input of a forced tool call
+            tools: if config.structured {
+                vec![Tool {
+                    name: structured::TOOL_NAME,
+                    description: structured::TOOL_DESCRIPTION,
+                    input_schema: structured::schema(),
+                }]
+            } else {
+                Vec::new()
+            },
+            tool_choice: config.structured.then_some(ToolChoice {
+                choice_type: "tool",
+                name: structured::TOOL_NAME,
+            }),
         };
 
         let response = self
@@ -167,25 +280,53 @@ impl LlmProvider for AnthropicProvider {
 
         let result: AnthropicResponse = response.json().await?;
 
-        let content = result
-            .content
-            .into_iter()
-            .filter_map(|block| {
-                if block.content_type == "text" {
-                    block.text
-                } else {
-                    None
+        let mut content = String::new();
+        let mut thinking: Option<String> = None;
+        for block in result.content {
+            match block.content_type.as_str() {
+                "text" => content.push_str(&block.text.unwrap_or_default()),
+                "tool_use" => {
+                    if let Some(input) = &block.input {
+                        content.push_str(&serde_json::to_string_pretty(input)?);
+                    }
                 }
-            })
-            .collect::<Vec<_>>()
-            .join("");
+                "thinking" => thinking.get_or_insert_with(String::new).push_str(&block.thinking.unwrap_or_default()),
+                // Encrypted by the safety systems: counted in the usage, unreadable
+                "redacted_thinking" => thinking.get_or_insert_with(String::new).push_str("[redacted thinking]\n"),
+                _ => {}
+            }
+        }
 
+        // The API counts cached input apart from `input_tokens`
+        let cache_write_tokens = result.usage.cache_creation_input_tokens.unwrap_or(0);
+        let cache_read_tokens = result.usage.cache_read_input_tokens.unwrap_or(0);
         Ok(LlmResponse {
             content,
-            input_tokens: result.usage.input_tokens,
+            input_tokens: result.usage.input_tokens + cache_write_tokens + cache_read_tokens,
             output_tokens: result.usage.output_tokens,
+            cache_write_tokens,
+            cache_read_tokens,
+            thinking,
+            // Not reported apart from output_tokens
+            thinking_tokens: None,
+            truncated: result.stop_reason.as_deref() == Some("max_tokens"),
             model: result.model,
             provider: "anthropic".to_string(),
         })
     }
+
+    async fn continue_chat(&self, messages: &[Message], partial: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        // Thinking cannot follow a prefilled answer: ask for the rest instead
+        if config.thinking_budget.is_some() {
+            return ask_to_continue(self, messages, partial, config).await;
+        }
+        // A last assistant message is prefilled: the model picks up where it
+        // ends, which may not be whitespace
+        let partial = partial.trim_end();
+        let mut conversation = messages.to_vec();
+        conversation.push(Message::assistant(partial));
+        let mut response = self.chat(&conversation, config).await?;
+        response.content = format!("{}{}", partial, response.content);
+        Ok(response)
+    }
 }
@@ -0,0 +1,474 @@
+//! Batch Run Configuration
+//!
+//! A study is declared in a TOML file passed with `--config runs.toml`
+//! instead of a shell loop over CLI flags, so the exact set of runs is
+//! checked in next to its results. Each `[[run]]` table expands to one run
+//! per model × condition × language × temperature × top_p × thinking budget
+//! × replicate:
+//!
+//! ```toml
+//! [defaults]
+//! provider = "anthropic"
+//! max_tokens = 8192
+//!
+//! [[run]]
+//! model = ["claude-sonnet-4-20250514", "claude-opus-4-1-20250805"]
+//! condition = "test_guided"
+//! language = ["rust", "go"]
+//! replications = 3
+//! ```
+//!
+//! Any field except `condition` and `language` may be set in `[defaults]`;
+//! `model`, `condition`, `language`, `temperature`, `top_p` and
+//! `thinking_budget` accept a single value or a list; a thinking budget of 0
+//! turns extended thinking off. A run may name a single `replicate` instead
+//! of `replications`, as in the file [`BatchConfig::from_specs`] writes for
+//! the runs an interrupted batch did not start. `seed` sets the sampling
+//! seed on providers that take one, replicate `n` being sent
+//! `seed + n - 1`. `system` names a file holding a system prompt, and
+//! `repair_turns` allows that many follow-up turns returning compiler errors
+//! or failing tests to the model. `structured = true` asks for the answer
+//! as a JSON object instead of fenced code.
+//!
+//! [`Matrix`] builds the same expansion from comma-separated CLI flags
+//! (`--matrix`), for one-off studies not worth a file.
+
+use crate::provider::RequestConfig;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One API call to make: the CLI flags of a single run, plus its replicate
+/// number when the run is repeated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSpec {
+    pub provider: String,
+    pub condition: String,
+    pub language: String,
+    /// Model override (provider default if `None`)
+    pub model: Option<String>,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    /// Nucleus sampling cutoff, `None` for the provider default
+    pub top_p: Option<f32>,
+    /// Extended-thinking budget in tokens, `None` for no thinking
+    pub thinking_budget: Option<u32>,
+    /// 1-based replicate number, `None` when the run is not repeated
+    pub replicate: Option<u32>,
+    /// Sampling seed sent to the provider, `None` for none
+    pub seed: Option<u64>,
+    /// File holding a system prompt to send, `None` for none
+    pub system: Option<String>,
+    /// Follow-up turns that return compiler errors or failing tests to the
+    /// model for a fix; 0 for a single-shot run
+    pub repair_turns: u32,
+    /// Ask for the answer as a JSON object (see [`crate::structured`])
+    /// instead of fenced code
+    pub structured: bool,
+}
+
+impl RunSpec {
+    /// File-name suffix of the sampling parameters: empty for the default
+    /// (temperature 0, provider top_p, no thinking), so earlier results keep
+    /// their names
+    pub fn sampling_suffix(&self) -> String {
+        let mut suffix = String::new();
+        if self.temperature != 0.0 {
+            suffix.push_str(&format!("_t{}", self.temperature));
+        }
+        if let Some(top_p) = self.top_p {
+            suffix.push_str(&format!("_p{}", top_p));
+        }
+        if let Some(budget) = self.thinking_budget {
+            suffix.push_str(&format!("_think{}", budget));
+        }
+        suffix
+    }
+
+    /// File-name suffix of everything but the condition, language and model:
+    /// sampling, system prompt (`_sys-{file stem}`), repair turns
+    /// (`_repair{n}`), structured output (`_structured`), seed (`_seed{n}`)
+    /// and replicate (`_r{n}`); empty for a default run
+    pub fn suffix(&self) -> String {
+        let mut suffix = self.sampling_suffix();
+        if let Some(system) = &self.system {
+            let stem = Path::new(system).file_stem().map(|stem| stem.to_string_lossy());
+            suffix.push_str(&format!("_sys-{}", stem.unwrap_or_default()));
+        }
+        if self.repair_turns > 0 {
+            suffix.push_str(&format!("_repair{}", self.repair_turns));
+        }
+        if self.structured {
+            suffix.push_str("_structured");
+        }
+        if let Some(seed) = self.seed {
+            suffix.push_str(&format!("_seed{}", seed));
+        }
+        if let Some(replicate) = self.replicate {
+            suffix.push_str(&format!("_r{}", replicate));
+        }
+        suffix
+    }
+
+    /// Human-readable sampling parameters, e.g. `temperature 0.7, top_p 0.9`
+    pub fn sampling(&self) -> String {
+        let mut sampling = format!("temperature {}", self.temperature);
+        if let Some(top_p) = self.top_p {
+            sampling.push_str(&format!(", top_p {}", top_p));
+        }
+        if let Some(budget) = self.thinking_budget {
+            sampling.push_str(&format!(", thinking budget {}", budget));
+        }
+        sampling
+    }
+}
+
+/// Seed of `replicate` in a run seeded with `seed`: `seed + n - 1` for
+/// replicate `n`, so replicates differ yet each is reproducible
+pub fn replicate_seed(seed: u64, replicate: Option<u32>) -> u64 {
+    seed.wrapping_add(u64::from(replicate.unwrap_or(1)) - 1)
+}
+
+/// Smallest extended-thinking budget Anthropic accepts
+pub const MIN_THINKING_BUDGET: u32 = 1024;
+
+/// Check that an extended-thinking budget is one Anthropic accepts: at least
+/// [`MIN_THINKING_BUDGET`], below `max_tokens` (which includes the thinking),
+/// and with the temperature left at 1
+pub fn validate_thinking(budget: Option<u32>, max_tokens: u32, temperature: f32) -> Result<()> {
+    let Some(budget) = budget else {
+        return Ok(());
+    };
+    if budget < MIN_THINKING_BUDGET {
+        return Err(anyhow!("thinking budget must be at least {}, got {}", MIN_THINKING_BUDGET, budget));
+    }
+    if budget >= max_tokens {
+        return Err(anyhow!("thinking budget {} must be below max_tokens {}", budget, max_tokens));
+    }
+    if temperature != 1.0 {
+        return Err(anyhow!(
+            "extended thinking requires temperature 1, got {} (compare against unthinking runs at temperature 1)",
+            temperature
+        ));
+    }
+    Ok(())
+}
+
+/// Check that sampling parameters are in the range providers accept
+pub fn validate_sampling(temperature: f32, top_p: Option<f32>) -> Result<()> {
+    if !(0.0..=2.0).contains(&temperature) {
+        return Err(anyhow!("temperature must be between 0 and 2, got {}", temperature));
+    }
+    if let Some(top_p) = top_p {
+        if !(top_p > 0.0 && top_p <= 1.0) {
+            return Err(anyhow!("top_p must be in (0, 1], got {}", top_p));
+        }
+    }
+    Ok(())
+}
+
+/// A value that may be written as `x` or `[x, y]`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T: Clone> OneOrMany<T> {
+    fn values(&self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value.clone()],
+            OneOrMany::Many(values) => values.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunDefaults {
+    provider: Option<String>,
+    model: Option<OneOrMany<String>>,
+    max_tokens: Option<u32>,
+    temperature: Option<OneOrMany<f32>>,
+    top_p: Option<OneOrMany<f32>>,
+    thinking_budget: Option<OneOrMany<u32>>,
+    replications: Option<u32>,
+    seed: Option<u64>,
+    system: Option<String>,
+    repair_turns: Option<u32>,
+    structured: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RunEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<OneOrMany<String>>,
+    condition: OneOrMany<String>,
+    language: OneOrMany<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "short_floats")]
+    temperature: Option<OneOrMany<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "short_floats")]
+    top_p: Option<OneOrMany<f32>>,
+    /// Extended-thinking budgets, 0 for no thinking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_budget: Option<OneOrMany<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replications: Option<u32>,
+    /// Run only this replicate (files suffixed `_r{replicate}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replicate: Option<u32>,
+    /// Seed of the first replicate; replicate `n` gets `seed + n - 1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repair_turns: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured: Option<bool>,
+}
+
+/// Write `f32`s as their shortest decimal form: TOML floats are `f64`, and
+/// `0.7f32` widened to `f64` reads `0.699999988079071`
+fn short_floats<S: serde::Serializer>(value: &Option<OneOrMany<f32>>, serializer: S) -> Result<S::Ok, S::Error> {
+    let widen = |x: f32| x.to_string().parse::<f64>().unwrap_or(f64::from(x));
+    match value {
+        Some(OneOrMany::One(x)) => serializer.serialize_some(&widen(*x)),
+        Some(OneOrMany::Many(xs)) => serializer.serialize_some(&xs.iter().map(|&x| widen(x)).collect::<Vec<_>>()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Parsed contents of a batch configuration file
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchConfig {
+    #[serde(default, skip_serializing)]
+    defaults: RunDefaults,
+    #[serde(rename = "run", default)]
+    runs: Vec<RunEntry>,
+}
+
+impl BatchConfig {
+    /// Read and parse a batch configuration file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read config file {}: {}", path.display(), e))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Parse a batch configuration from TOML text
+    pub fn from_toml(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// A configuration of exactly `specs`, one `[[run]]` table each
+    pub fn from_specs(specs: &[RunSpec]) -> Self {
+        let runs = specs
+            .iter()
+            .map(|spec| RunEntry {
+                provider: Some(spec.provider.clone()),
+                model: spec.model.clone().map(OneOrMany::One),
+                condition: OneOrMany::One(spec.condition.clone()),
+                language: OneOrMany::One(spec.language.clone()),
+                max_tokens: Some(spec.max_tokens),
+                temperature: Some(OneOrMany::One(spec.temperature)),
+                top_p: spec.top_p.map(OneOrMany::One),
+                thinking_budget: spec.thinking_budget.map(OneOrMany::One),
+                replications: None,
+                replicate: spec.replicate,
+                // The seed sent, undoing the replicate offset `runs` adds
+                seed: spec.seed.map(|seed| seed.wrapping_sub(u64::from(spec.replicate.unwrap_or(1)) - 1)),
+                system: spec.system.clone(),
+                repair_turns: (spec.repair_turns > 0).then_some(spec.repair_turns),
+                structured: spec.structured.then_some(true),
+            })
+            .collect();
+        Self {
+            defaults: RunDefaults::default(),
+            runs,
+        }
+    }
+
+    /// Render as TOML, the inverse of [`BatchConfig::from_toml`]
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Expand every `[[run]]` table into the individual runs, in file order
+    pub fn runs(&self) -> Result<Vec<RunSpec>> {
+        if self.runs.is_empty() {
+            return Err(anyhow!("No [[run]] entries in config"));
+        }
+
+        let mut specs = Vec::new();
+        for (index, entry) in self.runs.iter().enumerate() {
+            let provider = entry
+                .provider
+                .as_ref()
+                .or(self.defaults.provider.as_ref())
+                .ok_or_else(|| anyhow!("run #{}: no provider (set it in the run or in [defaults])", index + 1))?;
+            let models = match entry.model.as_ref().or(self.defaults.model.as_ref()) {
+                Some(models) => models.values().into_iter().map(Some).collect(),
+                None => vec![None],
+            };
+            let max_tokens = entry
+                .max_tokens
+                .or(self.defaults.max_tokens)
+                .unwrap_or_else(|| RequestConfig::default().max_tokens);
+            let temperatures = match entry.temperature.as_ref().or(self.defaults.temperature.as_ref()) {
+                Some(temperatures) => temperatures.values(),
+                None => vec![RequestConfig::default().temperature],
+            };
+            let top_ps = match entry.top_p.as_ref().or(self.defaults.top_p.as_ref()) {
+                Some(top_ps) => top_ps.values().into_iter().map(Some).collect(),
+                None => vec![None],
+            };
+            let thinking_budgets = match entry.thinking_budget.as_ref().or(self.defaults.thinking_budget.as_ref()) {
+                Some(budgets) => budgets.values().into_iter().map(|budget| (budget > 0).then_some(budget)).collect(),
+                None => vec![None],
+            };
+            for &temperature in &temperatures {
+                for &top_p in &top_ps {
+                    validate_sampling(temperature, top_p).map_err(|e| anyhow!("run #{}: {}", index + 1, e))?;
+                }
+                for &budget in &thinking_budgets {
+                    validate_thinking(budget, max_tokens, temperature)
+                        .map_err(|e| anyhow!("run #{}: {}", index + 1, e))?;
+                }
+            }
+            let replications = entry.replications.or(self.defaults.replications).unwrap_or(1);
+            if replications == 0 {
+                return Err(anyhow!("run #{}: replications must be at least 1", index + 1));
+            }
+            let seed = entry.seed.or(self.defaults.seed);
+            let system = entry.system.as_ref().or(self.defaults.system.as_ref());
+            let repair_turns = entry.repair_turns.or(self.defaults.repair_turns).unwrap_or(0);
+            let structured = entry.structured.or(self.defaults.structured).unwrap_or(false);
+            let replicates: Vec<Option<u32>> = match entry.replicate {
+                Some(_) if entry.replications.is_some() => {
+                    return Err(anyhow!("run #{}: set replicate or replications, not both", index + 1))
+                }
+                Some(0) => return Err(anyhow!("run #{}: replicate numbers start at 1", index + 1)),
+                Some(replicate) => vec![Some(replicate)],
+                None if replications > 1 => (1..=replications).map(Some).collect(),
+                None => vec![None],
+            };
+
+            for model in &models {
+                for condition in entry.condition.values() {
+                    for language in entry.language.values() {
+                        for &temperature in &temperatures {
+                            for &top_p in &top_ps {
+                                for &thinking_budget in &thinking_budgets {
+                                    for &replicate in &replicates {
+                                        specs.push(RunSpec {
+                                            provider: provider.clone(),
+                                            condition: condition.clone(),
+                                            language: language.clone(),
+                                            model: model.clone(),
+                                            max_tokens,
+                                            temperature,
+                                            top_p,
+                                            thinking_budget,
+                                            replicate,
+                                            seed: seed.map(|seed| replicate_seed(seed, replicate)),
+                                            system: system.cloned(),
+                                            repair_turns,
+                                            structured,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(specs)
+    }
+}
+
+/// A full run matrix given on the command line: every provider × model ×
+/// condition × language × temperature × top_p × thinking budget × replicate.
+///
+/// A model may be qualified with its provider (`mistral:mistral-large-latest`)
+/// to run on that provider only; unqualified models run on every provider,
+/// and a provider left without models runs its default model.
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    pub providers: Vec<String>,
+    pub models: Vec<String>,
+    pub conditions: Vec<String>,
+    pub languages: Vec<String>,
+    pub max_tokens: u32,
+    pub temperatures: Vec<f32>,
+    pub top_ps: Vec<f32>,
+    /// Extended-thinking budgets, 0 for no thinking; empty for none
+    pub thinking_budgets: Vec<u32>,
+    pub replications: u32,
+    pub seed: Option<u64>,
+    pub system: Option<String>,
+    pub repair_turns: u32,
+    pub structured: bool,
+}
+
+impl Matrix {
+    /// Expand the matrix into individual runs, provider by provider
+    pub fn runs(&self) -> Result<Vec<RunSpec>> {
+        let list = |values: &[f32]| match values {
+            [] => None,
+            [value] => Some(OneOrMany::One(*value)),
+            values => Some(OneOrMany::Many(values.to_vec())),
+        };
+
+        let mut runs = Vec::new();
+        for provider in &self.providers {
+            let models: Vec<String> = self
+                .models
+                .iter()
+                .filter_map(|model| match model.split_once(':') {
+                    Some((qualifier, model)) if self.is_provider(qualifier) => {
+                        qualifier.eq_ignore_ascii_case(provider).then(|| model.to_string())
+                    }
+                    _ => Some(model.clone()),
+                })
+                .collect();
+            runs.push(RunEntry {
+                provider: Some(provider.clone()),
+                model: (!models.is_empty()).then_some(OneOrMany::Many(models)),
+                condition: OneOrMany::Many(self.conditions.clone()),
+                language: OneOrMany::Many(self.languages.clone()),
+                max_tokens: Some(self.max_tokens),
+                temperature: list(&self.temperatures),
+                top_p: list(&self.top_ps),
+                thinking_budget: match self.thinking_budgets.as_slice() {
+                    [] => None,
+                    budgets => Some(OneOrMany::Many(budgets.to_vec())),
+                },
+                replications: Some(self.replications),
+                replicate: None,
+                seed: self.seed,
+                system: self.system.clone(),
+                repair_turns: Some(self.repair_turns),
+                structured: Some(self.structured),
+            });
+        }
+
+        BatchConfig {
+            defaults: RunDefaults::default(),
+            runs,
+        }
+        .runs()
+    }
+
+    fn is_provider(&self, name: &str) -> bool {
+        self.providers.iter().any(|provider| provider.eq_ignore_ascii_case(name))
+    }
+}
@@ -0,0 +1,90 @@
+//! Generate the per-language test corpus from the canonical case list.
+//!
+//! Every test in `test-corpus/{go,rust,cpp,typescript,zig}/` is rendered from
+//! one description in `src/corpus/cases.rs`, so the five languages cannot
+//! drift apart.
+//!
+//! Usage:
+//!   cargo run --bin gen_corpus
+//!   cargo run --bin gen_corpus -- --language zig
+//!   cargo run --bin gen_corpus -- --check
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use experiment_runner::corpus::{self, cases, LANGUAGES};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "gen_corpus")]
+#[command(about = "Generate test-corpus/ from the canonical test descriptions")]
+struct Args {
+    /// Output directory (default: the repository's test-corpus/)
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Only generate this language (go, rust, cpp, typescript, zig)
+    #[arg(long)]
+    language: Option<String>,
+
+    /// Do not write anything; exit non-zero if any file on disk differs
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let out_dir = args.out.unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("..")
+            .join("test-corpus")
+    });
+    let languages: Vec<&str> = match &args.language {
+        Some(lang) => vec![lang.as_str()],
+        None => LANGUAGES.to_vec(),
+    };
+
+    let cases = cases::all();
+    let mut stale = Vec::new();
+    let mut written = 0;
+
+    for language in languages {
+        for file in corpus::render(language, &cases)? {
+            let path = out_dir.join(language).join(&file.path);
+            if args.check {
+                let current = fs::read_to_string(&path).unwrap_or_default();
+                if current != file.contents {
+                    stale.push(path);
+                }
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, &file.contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("  wrote {}", path.display());
+            written += 1;
+        }
+    }
+
+    if args.check {
+        if !stale.is_empty() {
+            for path in &stale {
+                eprintln!("  out of date: {}", path.display());
+            }
+            bail!(
+                "{} corpus file(s) out of date; run `cargo run --bin gen_corpus`",
+                stale.len()
+            );
+        }
+        println!("Corpus is up to date ({} tests)", cases.len());
+    } else {
+        println!("Generated {} tests into {} files", cases.len(), written);
+    }
+
+    Ok(())
+}
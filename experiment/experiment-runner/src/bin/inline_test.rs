@@ -6,6 +6,7 @@
 //! Usage: cargo run --bin inline_test
 
 use anyhow::Result;
+use experiment_runner::analysis;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::time::Instant;
@@ -615,7 +616,7 @@ fn main() -> Result<()> {
     };
 
     // Count tests in generated code
-    let test_count = code.lines().filter(|line: &&str| line.trim().starts_with("test ")).count();
+    let test_count = analysis::count_tests("zig", &code)?.count();
 
     println!("=== RESULTS ===");
     println!("Tests generated: {}", test_count);
@@ -31,7 +31,7 @@ async fn main() -> Result<()> {
     } else {
         println!("Available Anthropic Models (newest first):");
         println!("{}", "=".repeat(70));
-        println!("{:<45} {}", "Model ID", "Display Name");
+        println!("{:<45} Display Name", "Model ID");
         println!("{}", "-".repeat(70));
         for model in &models {
             println!("{:<45} {}", model.id, model.display_name);
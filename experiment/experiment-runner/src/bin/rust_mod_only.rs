@@ -7,6 +7,7 @@
 //! Usage: cargo run --bin rust_mod_only
 
 use anyhow::Result;
+use experiment_runner::analysis;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::time::Instant;
@@ -428,10 +429,13 @@ fn main() -> Result<()> {
     };
 
     // Count tests in generated code
-    let output_test_count = code
-        .lines()
-        .filter(|line| line.trim() == "#[test]")
-        .count();
+    let output_test_count = match analysis::count_tests("rust", &code) {
+        Ok(tests) => tests.count(),
+        Err(e) => {
+            eprintln!("⚠️  Could not count tests ({}); recording 0, re-count with `analyze`", e);
+            0
+        }
+    };
 
     // Check for mod tests wrapper and cfg(test)
     let has_mod_wrapper = code.contains("mod tests") || code.contains("mod test");
@@ -8,6 +8,7 @@
 //! Usage: cargo run --bin rust_no_module
 
 use anyhow::Result;
+use experiment_runner::analysis;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::time::Instant;
@@ -425,10 +426,13 @@ fn main() -> Result<()> {
     };
 
     // Count tests in generated code
-    let output_test_count = code
-        .lines()
-        .filter(|line| line.trim() == "#[test]")
-        .count();
+    let output_test_count = match analysis::count_tests("rust", &code) {
+        Ok(tests) => tests.count(),
+        Err(e) => {
+            eprintln!("⚠️  Could not count tests ({}); recording 0, re-count with `analyze`", e);
+            0
+        }
+    };
 
     // Check for mod tests wrapper
     let has_mod_wrapper = code.contains("mod tests") || code.contains("mod test");
@@ -0,0 +1,363 @@
+//! The canonical corpus: 22 tests over insert / pop / front /
+//! increase_priority / decrease_priority, all on 4-ary min-heaps.
+//!
+//! To add a test, append a [`TestCase`] here and re-run
+//! `cargo run --bin gen_corpus`.
+
+use super::{Category, Operation, Step, Step::*, TestCase};
+
+fn case(
+    operation: Operation,
+    category: Category,
+    slug: &'static str,
+    property: &'static str,
+    steps: Vec<Step>,
+) -> TestCase {
+    TestCase {
+        operation,
+        category,
+        slug,
+        property,
+        arity: 4,
+        steps,
+    }
+}
+
+pub fn all() -> Vec<TestCase> {
+    use Category::*;
+    use Operation as Op;
+
+    vec![
+        // ----- insert ---------------------------------------------------------
+        case(
+            Op::Insert,
+            Postcondition,
+            "item_findable",
+            "inserted item can be found via contains() after insertion",
+            vec![
+                Insert("test-item", 50),
+                Comment("identity is the id alone: a lookup with any priority finds the item"),
+                ExpectContains("test-item", true),
+                ExpectContains("other-item", false),
+            ],
+        ),
+        case(
+            Op::Insert,
+            Invariant,
+            "heap_property",
+            "heap invariant holds after every insertion",
+            vec![
+                Comment("insert items in arbitrary order"),
+                Insert("a", 30),
+                ExpectHeapInvariant,
+                Insert("b", 10),
+                ExpectHeapInvariant,
+                Insert("c", 50),
+                ExpectHeapInvariant,
+                Insert("d", 20),
+                ExpectHeapInvariant,
+                Insert("e", 40),
+                ExpectHeapInvariant,
+                ExpectFront("b", 10),
+            ],
+        ),
+        case(
+            Op::Insert,
+            Size,
+            "increments",
+            "heap size increases by 1 after each insertion",
+            vec![
+                ExpectLen(0),
+                Insert("item0", 0),
+                ExpectLen(1),
+                Insert("item1", 10),
+                ExpectLen(2),
+                Insert("item2", 20),
+                ExpectLen(3),
+                Insert("item3", 30),
+                ExpectLen(4),
+                Insert("item4", 40),
+                ExpectLen(5),
+            ],
+        ),
+        case(
+            Op::Insert,
+            Edge,
+            "becomes_front_if_highest_priority",
+            "if inserted item has highest priority, it becomes front()",
+            vec![
+                Comment("decreasing priority values = increasing importance in a min-heap"),
+                Insert("low", 100),
+                Insert("medium", 50),
+                Insert("high", 10),
+                ExpectFront("high", 10),
+                Insert("urgent", 1),
+                ExpectFront("urgent", 1),
+            ],
+        ),
+        // ----- pop ------------------------------------------------------------
+        case(
+            Op::Pop,
+            Postcondition,
+            "returns_minimum",
+            "pop() removes and returns the item with lowest priority value",
+            vec![
+                Insert("a", 30),
+                Insert("b", 10),
+                Insert("c", 20),
+                ExpectPop("b", 10),
+                ExpectContains("b", false),
+            ],
+        ),
+        case(
+            Op::Pop,
+            Invariant,
+            "maintains_heap_property",
+            "after each pop(), the heap invariant holds and front() is the minimum",
+            vec![
+                Insert("a", 50),
+                Insert("b", 20),
+                Insert("c", 80),
+                Insert("d", 10),
+                Insert("e", 60),
+                Insert("f", 30),
+                Insert("g", 70),
+                Insert("h", 40),
+                ExpectPop("d", 10),
+                ExpectHeapInvariant,
+                ExpectPop("b", 20),
+                ExpectHeapInvariant,
+                ExpectPop("f", 30),
+                ExpectHeapInvariant,
+                ExpectPop("h", 40),
+                ExpectHeapInvariant,
+                ExpectFront("a", 50),
+            ],
+        ),
+        case(
+            Op::Pop,
+            Size,
+            "decrements",
+            "size decreases by 1 after each successful pop()",
+            vec![
+                Insert("a", 10),
+                Insert("b", 20),
+                Insert("c", 30),
+                Pop,
+                ExpectLen(2),
+                Pop,
+                ExpectLen(1),
+                Pop,
+                ExpectLen(0),
+                ExpectEmpty(true),
+            ],
+        ),
+        case(
+            Op::Pop,
+            Edge,
+            "empty_returns_nothing",
+            "pop() on an empty heap reports nothing and leaves it empty",
+            vec![ExpectPopEmpty, ExpectEmpty(true)],
+        ),
+        // ----- front ----------------------------------------------------------
+        case(
+            Op::Front,
+            Postcondition,
+            "returns_minimum",
+            "front() returns the item with lowest priority value without removing it",
+            vec![
+                Insert("a", 30),
+                Insert("b", 10),
+                Insert("c", 20),
+                ExpectFront("b", 10),
+                ExpectContains("b", true),
+            ],
+        ),
+        case(
+            Op::Front,
+            Invariant,
+            "no_modification",
+            "front() does not modify the heap: repeated calls return the same item",
+            vec![
+                Insert("a", 30),
+                Insert("b", 10),
+                Insert("c", 20),
+                ExpectFront("b", 10),
+                ExpectFront("b", 10),
+                ExpectFront("b", 10),
+                ExpectHeapInvariant,
+            ],
+        ),
+        case(
+            Op::Front,
+            Size,
+            "unchanged",
+            "size is unchanged by front()",
+            vec![
+                Insert("a", 10),
+                Insert("b", 20),
+                Insert("c", 30),
+                ExpectLen(3),
+                ExpectFront("a", 10),
+                ExpectLen(3),
+            ],
+        ),
+        case(
+            Op::Front,
+            Edge,
+            "empty_peek_returns_nothing",
+            "peek() on an empty heap reports nothing",
+            vec![ExpectPeekEmpty, ExpectEmpty(true)],
+        ),
+        // ----- increase_priority ----------------------------------------------
+        case(
+            Op::IncreasePriority,
+            Postcondition,
+            "priority_changed",
+            "item's priority is updated to the new value",
+            vec![
+                Insert("target", 50),
+                Insert("other", 30),
+                ExpectFront("other", 30),
+                Comment("min-heap: a lower value is a higher priority"),
+                IncreasePriority("target", 10),
+                ExpectFront("target", 10),
+            ],
+        ),
+        case(
+            Op::IncreasePriority,
+            Invariant,
+            "heap_property",
+            "heap invariant holds after a priority increase",
+            vec![
+                Insert("a", 80),
+                Insert("b", 60),
+                Insert("c", 40),
+                Insert("d", 20),
+                Insert("e", 100),
+                Insert("f", 50),
+                ExpectFront("d", 20),
+                IncreasePriority("a", 5),
+                ExpectHeapInvariant,
+                ExpectFront("a", 5),
+            ],
+        ),
+        case(
+            Op::IncreasePriority,
+            Position,
+            "item_moves_up",
+            "item moves toward the root and becomes front if highest",
+            vec![
+                Insert("root", 10),
+                Insert("middle", 50),
+                Insert("leaf", 100),
+                ExpectFrontNot("leaf"),
+                IncreasePriority("leaf", 1),
+                ExpectFront("leaf", 1),
+            ],
+        ),
+        case(
+            Op::IncreasePriority,
+            Size,
+            "unchanged",
+            "size is unchanged by a priority update",
+            vec![
+                Insert("a", 50),
+                Insert("b", 30),
+                Insert("c", 70),
+                ExpectLen(3),
+                IncreasePriority("c", 10),
+                ExpectLen(3),
+            ],
+        ),
+        case(
+            Op::IncreasePriority,
+            Edge,
+            "not_found",
+            "updating an item that is not in the heap reports not-found",
+            vec![
+                Insert("existing", 50),
+                ExpectIncreaseNotFound("nonexistent", 10),
+                ExpectLen(1),
+                ExpectFront("existing", 50),
+            ],
+        ),
+        // ----- decrease_priority ----------------------------------------------
+        case(
+            Op::DecreasePriority,
+            Postcondition,
+            "priority_changed",
+            "item's priority is updated to the new value",
+            vec![
+                Insert("target", 10),
+                Insert("other", 30),
+                ExpectFront("target", 10),
+                Comment("min-heap: a higher value is a lower priority"),
+                DecreasePriority("target", 50),
+                ExpectFront("other", 30),
+                Pop,
+                ExpectFront("target", 50),
+            ],
+        ),
+        case(
+            Op::DecreasePriority,
+            Invariant,
+            "heap_property",
+            "heap invariant holds after a priority decrease",
+            vec![
+                Insert("a", 10),
+                Insert("b", 30),
+                Insert("c", 50),
+                Insert("d", 70),
+                Insert("e", 20),
+                Insert("f", 40),
+                ExpectFront("a", 10),
+                DecreasePriority("a", 100),
+                ExpectHeapInvariant,
+                ExpectFront("e", 20),
+            ],
+        ),
+        case(
+            Op::DecreasePriority,
+            Position,
+            "item_moves_down",
+            "item moves toward the leaves and is no longer front",
+            vec![
+                Insert("root", 10),
+                Insert("child1", 50),
+                Insert("child2", 60),
+                Insert("child3", 70),
+                ExpectFront("root", 10),
+                DecreasePriority("root", 100),
+                ExpectFrontNot("root"),
+                ExpectFront("child1", 50),
+            ],
+        ),
+        case(
+            Op::DecreasePriority,
+            Size,
+            "unchanged",
+            "size is unchanged by a priority update",
+            vec![
+                Insert("a", 10),
+                Insert("b", 30),
+                Insert("c", 50),
+                ExpectLen(3),
+                DecreasePriority("a", 100),
+                ExpectLen(3),
+            ],
+        ),
+        case(
+            Op::DecreasePriority,
+            Edge,
+            "not_found",
+            "updating an item that is not in the heap reports not-found",
+            vec![
+                Insert("existing", 50),
+                ExpectDecreaseNotFound("nonexistent", 100),
+                ExpectLen(1),
+                ExpectFront("existing", 50),
+            ],
+        ),
+    ]
+}
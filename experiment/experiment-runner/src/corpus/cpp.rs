@@ -0,0 +1,133 @@
+//! C++ layout: `test_common.h` (Item, hash, comparator, invariant helper)
+//! plus one GoogleTest file per operation, targeting `TOOLS::PriorityQueue`.
+
+use super::{
+    banner, cases_for, pascal_case, CorpusFile, Operation, Step, TestCase, HEADER_GENERATED,
+    HEADER_ORIGIN,
+};
+use std::fmt::Write;
+
+const TEST_COMMON_H: &str = r#"#pragma once
+
+#include <gtest/gtest.h>
+#include <cstddef>
+#include <functional>
+#include <string>
+#include "PriorityQueue.h"
+
+// Test item type with separate ID (identity) and priority
+struct Item {
+    std::string id;
+    int priority;
+
+    // Item equality is based on ID only (not priority)
+    bool operator==(const Item& other) const { return id == other.id; }
+};
+
+// Hash is based on ID only (not priority)
+struct ItemHash {
+    std::size_t operator()(const Item& item) const { return std::hash<std::string>{}(item.id); }
+};
+
+// Min-heap: lower priority value = higher importance
+struct ItemMinComparator {
+    bool operator()(const Item& a, const Item& b) const { return a.priority < b.priority; }
+};
+
+using TestPriorityQueue = TOOLS::PriorityQueue<Item, ItemHash, ItemMinComparator>;
+
+// Helper: every parent's priority is <= each of its children's
+inline bool heapInvariantHolds(const TestPriorityQueue& pq) {
+    const auto arr = pq.to_array();
+    const std::size_t d = pq.d();
+    for (std::size_t i = 0; i < arr.size(); ++i) {
+        for (std::size_t j = 1; j <= d; ++j) {
+            const std::size_t child = i * d + j;
+            if (child < arr.size() && arr[i].priority > arr[child].priority) {
+                return false;
+            }
+        }
+    }
+    return true;
+}
+"#;
+
+pub fn render(cases: &[TestCase]) -> Vec<CorpusFile> {
+    let mut files = vec![CorpusFile {
+        path: "test_common.h".to_string(),
+        contents: format!(
+            "// Test corpus common definitions\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\n{TEST_COMMON_H}"
+        ),
+    }];
+
+    for op in Operation::ALL {
+        let mut out = format!(
+            "// Test corpus for {name}() operation\n// Spec: specifications/{name}.md\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\n#include \"test_common.h\"\n\n",
+            name = op.name()
+        );
+        out.push_str(&banner("//", &format!("{}() Tests", op.name())));
+        let suite = format!("{}Test", pascal_case(op.name()));
+        for case in cases_for(cases, op) {
+            let _ = write!(
+                out,
+                "\n// Test: {}\n// Spec: {}\n// Property: {}\nTEST({suite}, {}_{}) {{\n    TestPriorityQueue pq({});\n\n",
+                case.name(),
+                case.spec(),
+                case.property,
+                pascal_case(case.category.name()),
+                pascal_case(case.slug),
+                case.arity
+            );
+            for step in &case.steps {
+                out.push_str(&step_lines(step));
+            }
+            out.push_str("}\n");
+        }
+        files.push(CorpusFile {
+            path: format!("{}_test.cpp", op.name()),
+            contents: out,
+        });
+    }
+    files
+}
+
+fn item(id: &str, p: i32) -> String {
+    format!("Item{{{id:?}, {p}}}")
+}
+
+fn step_lines(step: &Step) -> String {
+    match *step {
+        Step::Comment(text) => format!("    // {text}\n"),
+        Step::Insert(id, p) => format!("    pq.insert({});\n", item(id, p)),
+        Step::Pop => "    pq.pop();\n".to_string(),
+        Step::ExpectPop(id, p) => format!(
+            "    {{\n        auto popped = pq.pop_front();\n        ASSERT_TRUE(popped.has_value());\n        EXPECT_EQ(popped->id, {id:?});\n        EXPECT_EQ(popped->priority, {p});\n    }}\n"
+        ),
+        Step::ExpectPopEmpty => "    EXPECT_FALSE(pq.pop_front().has_value());\n".to_string(),
+        Step::IncreasePriority(id, p) => format!("    pq.increase_priority({});\n", item(id, p)),
+        Step::DecreasePriority(id, p) => format!("    pq.decrease_priority({});\n", item(id, p)),
+        Step::ExpectIncreaseNotFound(id, p) => format!(
+            "    {{\n        auto result = pq.try_increase_priority({});\n        ASSERT_FALSE(result.has_value());\n        EXPECT_EQ(result.error(), TOOLS::Error::ItemNotFound);\n    }}\n",
+            item(id, p)
+        ),
+        Step::ExpectDecreaseNotFound(id, p) => format!(
+            "    {{\n        auto result = pq.try_decrease_priority({});\n        ASSERT_FALSE(result.has_value());\n        EXPECT_EQ(result.error(), TOOLS::Error::ItemNotFound);\n    }}\n",
+            item(id, p)
+        ),
+        Step::ExpectFront(id, p) => format!(
+            "    EXPECT_EQ(pq.front().id, {id:?});\n    EXPECT_EQ(pq.front().priority, {p});\n"
+        ),
+        Step::ExpectFrontNot(id) => format!("    EXPECT_NE(pq.front().id, {id:?});\n"),
+        Step::ExpectPeekEmpty => "    EXPECT_FALSE(pq.peek().has_value());\n".to_string(),
+        Step::ExpectContains(id, expected) => {
+            let check = if expected { "EXPECT_TRUE" } else { "EXPECT_FALSE" };
+            format!("    {check}(pq.contains({}));\n", item(id, 999))
+        }
+        Step::ExpectLen(n) => format!("    EXPECT_EQ(pq.len(), {n}u);\n"),
+        Step::ExpectEmpty(expected) => {
+            let check = if expected { "EXPECT_TRUE" } else { "EXPECT_FALSE" };
+            format!("    {check}(pq.is_empty());\n")
+        }
+        Step::ExpectHeapInvariant => "    EXPECT_TRUE(heapInvariantHolds(pq));\n".to_string(),
+    }
+}
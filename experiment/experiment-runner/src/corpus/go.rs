@@ -0,0 +1,154 @@
+//! Go layout: one `{operation}_test.go` per operation in `package corpus`;
+//! the shared `Item` type and helpers live in `insert_test.go`, which
+//! `load_test_code` presents first.
+
+use super::{
+    banner, cases_for, pascal_case, CorpusFile, Operation, Step, TestCase, HEADER_GENERATED,
+    HEADER_ORIGIN,
+};
+use std::fmt::Write;
+
+const DHEAP_IMPORT: &str = "dheap \"github.com/PCfVW/d-Heap-priority-queue/Go/v2/src\"";
+
+const HELPERS: &str = r#"// Test item type
+type Item struct {
+	ID       string
+	Priority int
+}
+
+// Helper: create min-heap of Items
+func newItemMinHeap(d int) *dheap.PriorityQueue[Item, string] {
+	return dheap.New(dheap.Options[Item, string]{
+		D:            d,
+		Comparator:   dheap.MinBy(func(i Item) int { return i.Priority }),
+		KeyExtractor: func(i Item) string { return i.ID },
+	})
+}
+
+// Helper: verify heap invariant for min-heap (parent priority <= children)
+func verifyHeapInvariant(t *testing.T, pq *dheap.PriorityQueue[Item, string]) {
+	t.Helper()
+	arr := pq.ToArray()
+	d := pq.D()
+	for i := 0; i < len(arr); i++ {
+		for j := 1; j <= d; j++ {
+			childIdx := i*d + j
+			if childIdx < len(arr) && arr[i].Priority > arr[childIdx].Priority {
+				t.Fatalf("heap invariant violated: parent[%d].Priority=%d > child[%d].Priority=%d",
+					i, arr[i].Priority, childIdx, arr[childIdx].Priority)
+			}
+		}
+	}
+}
+"#;
+
+pub fn render(cases: &[TestCase]) -> Vec<CorpusFile> {
+    Operation::ALL
+        .into_iter()
+        .map(|op| {
+            let mut body = String::new();
+            if op == Operation::Insert {
+                body.push_str(HELPERS);
+                body.push('\n');
+            }
+            body.push_str(&banner("//", &format!("{}() Tests", op.name())));
+            for case in cases_for(cases, op) {
+                let _ = write!(
+                    body,
+                    "\n// Test: {}\n// Spec: {}\n// Property: {}\nfunc Test{}_{}_{}(t *testing.T) {{\n\tpq := newItemMinHeap({})\n\n",
+                    case.name(),
+                    case.spec(),
+                    case.property,
+                    pascal_case(op.name()),
+                    pascal_case(case.category.name()),
+                    pascal_case(case.slug),
+                    case.arity
+                );
+                for step in &case.steps {
+                    body.push_str(&step_lines(step));
+                }
+                body.push_str("}\n");
+            }
+
+            let mut imports = Vec::new();
+            if body.contains("errors.") {
+                imports.push("\"errors\"");
+            }
+            imports.push("\"testing\"");
+            let mut out = format!(
+                "// Test corpus for {name}() operation\n// Spec: specifications/{name}.md\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\npackage corpus\n\nimport (\n",
+                name = op.name()
+            );
+            for import in imports {
+                let _ = writeln!(out, "\t{import}");
+            }
+            if body.contains("dheap.") {
+                let _ = writeln!(out, "\n\t{DHEAP_IMPORT}");
+            }
+            out.push_str(")\n\n");
+            out.push_str(&body);
+            CorpusFile {
+                path: format!("{}_test.go", op.name()),
+                contents: out,
+            }
+        })
+        .collect()
+}
+
+fn item(id: &str, p: i32) -> String {
+    format!("Item{{ID: {id:?}, Priority: {p}}}")
+}
+
+fn step_lines(step: &Step) -> String {
+    match *step {
+        Step::Comment(text) => format!("\t// {text}\n"),
+        Step::Insert(id, p) => format!("\tpq.Insert({})\n", item(id, p)),
+        Step::Pop => "\tpq.Pop()\n".to_string(),
+        Step::ExpectPop(id, p) => format!(
+            "\tif got, ok := pq.Pop(); !ok || got.ID != {id:?} || got.Priority != {p} {{\n\t\tt.Errorf(\"expected Pop() = {{{id} {p}}}, got %+v (ok=%v)\", got, ok)\n\t}}\n"
+        ),
+        Step::ExpectPopEmpty => {
+            "\tif got, ok := pq.Pop(); ok {\n\t\tt.Errorf(\"expected Pop() on empty heap to return ok=false, got %+v\", got)\n\t}\n".to_string()
+        }
+        Step::IncreasePriority(id, p) => format!(
+            "\tif err := pq.IncreasePriority({}); err != nil {{\n\t\tt.Fatalf(\"IncreasePriority: %v\", err)\n\t}}\n",
+            item(id, p)
+        ),
+        Step::DecreasePriority(id, p) => format!(
+            "\tif err := pq.DecreasePriority({}); err != nil {{\n\t\tt.Fatalf(\"DecreasePriority: %v\", err)\n\t}}\n",
+            item(id, p)
+        ),
+        Step::ExpectIncreaseNotFound(id, p) => format!(
+            "\tif err := pq.IncreasePriority({}); !errors.Is(err, dheap.ErrItemNotFound) {{\n\t\tt.Errorf(\"expected ErrItemNotFound, got %v\", err)\n\t}}\n",
+            item(id, p)
+        ),
+        Step::ExpectDecreaseNotFound(id, p) => format!(
+            "\tif err := pq.DecreasePriority({}); !errors.Is(err, dheap.ErrItemNotFound) {{\n\t\tt.Errorf(\"expected ErrItemNotFound, got %v\", err)\n\t}}\n",
+            item(id, p)
+        ),
+        Step::ExpectFront(id, p) => format!(
+            "\tif front, err := pq.Front(); err != nil {{\n\t\tt.Fatalf(\"Front: %v\", err)\n\t}} else if front.ID != {id:?} || front.Priority != {p} {{\n\t\tt.Errorf(\"expected Front() = {{{id} {p}}}, got %+v\", front)\n\t}}\n"
+        ),
+        Step::ExpectFrontNot(id) => format!(
+            "\tif front, err := pq.Front(); err != nil {{\n\t\tt.Fatalf(\"Front: %v\", err)\n\t}} else if front.ID == {id:?} {{\n\t\tt.Errorf(\"expected Front() not to be {id:?}\")\n\t}}\n"
+        ),
+        Step::ExpectPeekEmpty => {
+            "\tif got, ok := pq.Peek(); ok {\n\t\tt.Errorf(\"expected Peek() on empty heap to return ok=false, got %+v\", got)\n\t}\n".to_string()
+        }
+        Step::ExpectContains(id, expected) => {
+            let not = if expected { "!" } else { "" };
+            format!(
+                "\tif {not}pq.Contains({}) {{\n\t\tt.Errorf(\"expected Contains({id:?}) = {expected}\")\n\t}}\n",
+                item(id, 999)
+            )
+        }
+        Step::ExpectLen(n) => format!(
+            "\tif got := pq.Len(); got != {n} {{\n\t\tt.Errorf(\"expected Len() = {n}, got %d\", got)\n\t}}\n"
+        ),
+        Step::ExpectEmpty(expected) => {
+            let not = if expected { "!" } else { "" };
+            format!("\tif {not}pq.IsEmpty() {{\n\t\tt.Errorf(\"expected IsEmpty() = {expected}\")\n\t}}\n")
+        }
+        Step::ExpectHeapInvariant => "\tverifyHeapInvariant(t, pq)\n".to_string(),
+    }
+}
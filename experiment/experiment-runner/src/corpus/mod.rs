@@ -0,0 +1,207 @@
+//! Canonical test corpus and per-language emitters.
+//!
+//! Every corpus test is described once, in [`cases::all`], as a sequence of
+//! language-neutral [`Step`]s against a min-heap of `Item { id, priority }`.
+//! The emitters in this module render those steps into the five files layouts
+//! that `load_test_code` in `main.rs` reads from `test-corpus/{language}/`.
+//! Adding a corpus test therefore means adding one [`TestCase`], not writing
+//! it five times.
+//!
+//! The emitted code targets the reference implementations in this repository
+//! (Rust `d_ary_heap`, Go `dheap`, C++ `TOOLS::PriorityQueue`, TypeScript
+//! `d-ary-heap`, Zig `d_heap`), so the generated corpus can be run against
+//! them to validate it before it is put in front of a model.
+
+pub mod cases;
+mod cpp;
+mod go;
+mod rust;
+mod typescript;
+mod zig;
+
+use anyhow::{anyhow, Result};
+
+/// Operation a corpus test exercises; one file (or section) per operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Insert,
+    Pop,
+    Front,
+    IncreasePriority,
+    DecreasePriority,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 5] = [
+        Operation::Insert,
+        Operation::Pop,
+        Operation::Front,
+        Operation::IncreasePriority,
+        Operation::DecreasePriority,
+    ];
+
+    /// snake_case name, used for file names, test names and spec paths.
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Insert => "insert",
+            Operation::Pop => "pop",
+            Operation::Front => "front",
+            Operation::IncreasePriority => "increase_priority",
+            Operation::DecreasePriority => "decrease_priority",
+        }
+    }
+}
+
+/// What kind of property a test checks; the middle segment of its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Postcondition,
+    Invariant,
+    Position,
+    Size,
+    Edge,
+}
+
+impl Category {
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Postcondition => "postcondition",
+            Category::Invariant => "invariant",
+            Category::Position => "position",
+            Category::Size => "size",
+            Category::Edge => "edge",
+        }
+    }
+}
+
+/// One language-neutral test step. Items are identified by their string id;
+/// emitters for languages whose item type has a numeric identity (Zig) map
+/// ids to numbers in order of first appearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Explanatory comment copied into every language.
+    Comment(&'static str),
+    Insert(&'static str, i32),
+    /// Pop and discard the result.
+    Pop,
+    /// Pop and check the removed item.
+    ExpectPop(&'static str, i32),
+    /// Pop on an empty heap must not fail and must report "nothing".
+    ExpectPopEmpty,
+    IncreasePriority(&'static str, i32),
+    DecreasePriority(&'static str, i32),
+    /// Updating an item that is not in the heap must report not-found.
+    ExpectIncreaseNotFound(&'static str, i32),
+    ExpectDecreaseNotFound(&'static str, i32),
+    ExpectFront(&'static str, i32),
+    ExpectFrontNot(&'static str),
+    /// `peek` on an empty heap reports "nothing".
+    ExpectPeekEmpty,
+    /// `contains` for an item with this id (and an arbitrary priority).
+    ExpectContains(&'static str, bool),
+    ExpectLen(usize),
+    ExpectEmpty(bool),
+    /// Every parent ranks no lower than each of its children.
+    ExpectHeapInvariant,
+}
+
+impl Step {
+    /// Whether the step needs a mutable heap.
+    fn mutates(&self) -> bool {
+        matches!(
+            self,
+            Step::Insert(..)
+                | Step::Pop
+                | Step::ExpectPop(..)
+                | Step::ExpectPopEmpty
+                | Step::IncreasePriority(..)
+                | Step::DecreasePriority(..)
+                | Step::ExpectIncreaseNotFound(..)
+                | Step::ExpectDecreaseNotFound(..)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub operation: Operation,
+    pub category: Category,
+    /// Remainder of the test name after `{operation}_{category}_`.
+    pub slug: &'static str,
+    /// One-line statement of the property under test.
+    pub property: &'static str,
+    pub arity: usize,
+    pub steps: Vec<Step>,
+}
+
+impl TestCase {
+    /// Canonical snake_case name, e.g. `insert_postcondition_item_findable`.
+    pub fn name(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            self.operation.name(),
+            self.category.name(),
+            self.slug
+        )
+    }
+
+    fn spec(&self) -> String {
+        format!("specifications/{}.md", self.operation.name())
+    }
+
+    fn uses(&self, pred: impl Fn(&Step) -> bool) -> bool {
+        self.steps.iter().any(pred)
+    }
+}
+
+/// Languages with a corpus layout, matching the `--language` values.
+pub const LANGUAGES: [&str; 5] = ["go", "rust", "cpp", "typescript", "zig"];
+
+/// A generated file: path relative to `test-corpus/{language}/`, and contents.
+pub struct CorpusFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Render the corpus for one language.
+pub fn render(language: &str, cases: &[TestCase]) -> Result<Vec<CorpusFile>> {
+    match language {
+        "go" => Ok(go::render(cases)),
+        "rust" => Ok(rust::render(cases)),
+        "cpp" => Ok(cpp::render(cases)),
+        "typescript" => Ok(typescript::render(cases)),
+        "zig" => Ok(zig::render(cases)),
+        _ => Err(anyhow!(
+            "Unknown language for corpus generation: {}",
+            language
+        )),
+    }
+}
+
+const HEADER_ORIGIN: &str = "Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research";
+const HEADER_GENERATED: &str =
+    "Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.";
+
+fn cases_for(cases: &[TestCase], op: Operation) -> impl Iterator<Item = &TestCase> {
+    cases.iter().filter(move |c| c.operation == op)
+}
+
+/// `"item_findable"` -> `"ItemFindable"`.
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Section banner shared by the C-family emitters.
+fn banner(comment: &str, title: &str) -> String {
+    let rule = "=".repeat(77);
+    format!("{comment} {rule}\n{comment} {title}\n{comment} {rule}\n")
+}
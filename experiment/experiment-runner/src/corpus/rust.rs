@@ -0,0 +1,166 @@
+//! Rust layout: `src/tests/mod.rs` (shared helpers) plus one module per
+//! operation, targeting the `d_ary_heap` crate.
+
+use super::{
+    banner, cases_for, CorpusFile, Operation, Step, TestCase, HEADER_GENERATED, HEADER_ORIGIN,
+};
+use std::fmt::Write;
+
+const MOD_RS: &str = r#"use d_ary_heap::{MinBy, PriorityCompare, PriorityQueue};
+use std::hash::{Hash, Hasher};
+
+/// Test item type with separate ID (identity) and priority
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub id: String,
+    pub priority: i32,
+}
+
+impl Item {
+    pub fn new(id: &str, priority: i32) -> Self {
+        Self { id: id.to_string(), priority }
+    }
+}
+
+// Item equality is based on ID only (not priority)
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Item {}
+
+// Hash is based on ID only (not priority)
+impl Hash for Item {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Helper: create min-heap of Items (lower priority value = higher importance)
+pub fn new_item_min_heap(d: usize) -> PriorityQueue<Item, MinBy<impl Fn(&Item) -> i32>> {
+    PriorityQueue::new(d, MinBy(|i: &Item| i.priority)).expect("arity is valid")
+}
+
+/// Helper: assert every parent's priority is <= each of its children's
+pub fn assert_heap_invariant(pq: &PriorityQueue<Item, impl PriorityCompare<Item>>) {
+    let arr = pq.to_array();
+    let d = pq.d();
+    for (i, parent) in arr.iter().enumerate() {
+        for child in arr.iter().skip(i * d + 1).take(d) {
+            assert!(
+                parent.priority <= child.priority,
+                "heap invariant violated: parent {:?} > child {:?}",
+                parent,
+                child
+            );
+        }
+    }
+}
+"#;
+
+pub fn render(cases: &[TestCase]) -> Vec<CorpusFile> {
+    let mut mod_rs = format!("// Test corpus common definitions\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\n{MOD_RS}\n");
+    for op in Operation::ALL {
+        let _ = writeln!(mod_rs, "pub mod {};", op.name());
+    }
+    let mut files = vec![CorpusFile {
+        path: "src/tests/mod.rs".to_string(),
+        contents: mod_rs,
+    }];
+
+    for op in Operation::ALL {
+        let mut out = format!(
+            "// Test corpus for {name}() operation\n// Spec: specifications/{name}.md\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\n",
+            name = op.name()
+        );
+        let ops: Vec<&TestCase> = cases_for(cases, op).collect();
+        let mut imports = vec!["new_item_min_heap"];
+        if ops
+            .iter()
+            .any(|c| c.uses(|s| matches!(s, Step::ExpectHeapInvariant)))
+        {
+            imports.push("assert_heap_invariant");
+        }
+        imports.push("Item");
+        let _ = writeln!(out, "use super::{{{}}};", imports.join(", "));
+        if ops.iter().any(|c| {
+            c.uses(|s| {
+                matches!(
+                    s,
+                    Step::ExpectIncreaseNotFound(..) | Step::ExpectDecreaseNotFound(..)
+                )
+            })
+        }) {
+            out.push_str("use d_ary_heap::Error;\n");
+        }
+        out.push('\n');
+        out.push_str(&banner("//", &format!("{}() Tests", op.name())));
+
+        for case in ops {
+            let _ = write!(
+                out,
+                "\n/// Test: {}\n/// Spec: {}\n/// Property: {}\n#[test]\nfn {}() {{\n    let {}pq = new_item_min_heap({});\n\n",
+                case.name(),
+                case.spec(),
+                case.property,
+                case.name(),
+                if case.uses(Step::mutates) { "mut " } else { "" },
+                case.arity
+            );
+            for step in &case.steps {
+                out.push_str(&step_line(step));
+            }
+            out.push_str("}\n");
+        }
+        files.push(CorpusFile {
+            path: format!("src/tests/{}.rs", op.name()),
+            contents: out,
+        });
+    }
+    files
+}
+
+fn step_line(step: &Step) -> String {
+    match *step {
+        Step::Comment(text) => format!("    // {text}\n"),
+        Step::Insert(id, p) => format!("    pq.insert(Item::new({id:?}, {p}));\n"),
+        Step::Pop => "    pq.pop();\n".to_string(),
+        Step::ExpectPop(id, p) => format!(
+            "    let popped = pq.pop().expect(\"pop() on a non-empty heap returns an item\");\n    assert_eq!(popped.id, {id:?});\n    assert_eq!(popped.priority, {p});\n"
+        ),
+        Step::ExpectPopEmpty => {
+            "    assert!(pq.pop().is_none(), \"pop() on an empty heap returns None\");\n".to_string()
+        }
+        Step::IncreasePriority(id, p) => format!(
+            "    pq.increase_priority(&Item::new({id:?}, {p})).expect(\"item is in the heap\");\n"
+        ),
+        Step::DecreasePriority(id, p) => format!(
+            "    pq.decrease_priority(&Item::new({id:?}, {p})).expect(\"item is in the heap\");\n"
+        ),
+        Step::ExpectIncreaseNotFound(id, p) => format!(
+            "    assert_eq!(pq.increase_priority(&Item::new({id:?}, {p})), Err(Error::ItemNotFound));\n"
+        ),
+        Step::ExpectDecreaseNotFound(id, p) => format!(
+            "    assert_eq!(pq.decrease_priority(&Item::new({id:?}, {p})), Err(Error::ItemNotFound));\n"
+        ),
+        Step::ExpectFront(id, p) => format!(
+            "    assert_eq!(pq.front().id, {id:?});\n    assert_eq!(pq.front().priority, {p});\n"
+        ),
+        Step::ExpectFrontNot(id) => format!("    assert_ne!(pq.front().id, {id:?});\n"),
+        Step::ExpectPeekEmpty => {
+            "    assert!(pq.peek().is_none(), \"peek() on an empty heap returns None\");\n".to_string()
+        }
+        Step::ExpectContains(id, expected) => {
+            let not = if expected { "" } else { "!" };
+            format!("    assert!({not}pq.contains(&Item::new({id:?}, 999)));\n")
+        }
+        Step::ExpectLen(n) => format!("    assert_eq!(pq.len(), {n});\n"),
+        Step::ExpectEmpty(expected) => {
+            let not = if expected { "" } else { "!" };
+            format!("    assert!({not}pq.is_empty());\n")
+        }
+        Step::ExpectHeapInvariant => "    assert_heap_invariant(&pq);\n".to_string(),
+    }
+}
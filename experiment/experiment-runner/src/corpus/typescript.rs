@@ -0,0 +1,133 @@
+//! TypeScript layout: one self-contained vitest file per operation,
+//! targeting the `d-ary-heap` package.
+
+use super::{
+    banner, cases_for, CorpusFile, Operation, Step, TestCase, HEADER_GENERATED, HEADER_ORIGIN,
+};
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"import { describe, it, expect } from 'vitest';
+import { PriorityQueue, minBy } from 'd-ary-heap';
+
+// Test item type with separate ID (identity) and priority
+interface Item {
+  id: string;
+  priority: number;
+}
+
+// Helper: create min-heap of Items (lower priority value = higher importance)
+function newItemMinHeap(d: number): PriorityQueue<Item, string> {
+  return new PriorityQueue<Item, string>({
+    d,
+    comparator: minBy((i: Item) => i.priority),
+    keyExtractor: (i) => i.id,
+  });
+}
+"#;
+
+const INVARIANT_HELPER: &str = r#"
+// Helper: every parent's priority is <= each of its children's
+function expectHeapInvariant(pq: PriorityQueue<Item, string>): void {
+  const arr = pq.toArray();
+  const d = pq.d();
+  for (let i = 0; i < arr.length; i++) {
+    for (let j = 1; j <= d; j++) {
+      const child = i * d + j;
+      if (child < arr.length) {
+        expect(arr[i]!.priority).toBeLessThanOrEqual(arr[child]!.priority);
+      }
+    }
+  }
+}
+"#;
+
+pub fn render(cases: &[TestCase]) -> Vec<CorpusFile> {
+    Operation::ALL
+        .into_iter()
+        .map(|op| {
+            let ops: Vec<&TestCase> = cases_for(cases, op).collect();
+            let mut out = format!(
+                "// Test corpus for {name}() operation\n// Spec: specifications/{name}.md\n// {HEADER_ORIGIN}\n// {HEADER_GENERATED}\n\n{PRELUDE}",
+                name = op.name()
+            );
+            if ops.iter().any(|c| c.uses(|s| matches!(s, Step::ExpectHeapInvariant))) {
+                out.push_str(INVARIANT_HELPER);
+            }
+            out.push('\n');
+            out.push_str(&banner("//", &format!("{}() Tests", op.name())));
+            let _ = writeln!(out, "\ndescribe('{}()', () => {{", camel_case(op.name()));
+            for (i, case) in ops.into_iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                let _ = write!(
+                    out,
+                    "  // Test: {}\n  // Spec: {}\n  // Property: {}\n  it('{}: {}', () => {{\n    const pq = newItemMinHeap({});\n\n",
+                    case.name(),
+                    case.spec(),
+                    case.property,
+                    case.category.name(),
+                    case.slug.replace('_', " "),
+                    case.arity
+                );
+                for step in &case.steps {
+                    out.push_str(&step_lines(step));
+                }
+                out.push_str("  });\n");
+            }
+            out.push_str("});\n");
+            CorpusFile {
+                path: format!("{}.test.ts", op.name()),
+                contents: out,
+            }
+        })
+        .collect()
+}
+
+/// `"increase_priority"` -> `"increasePriority"`.
+fn camel_case(snake: &str) -> String {
+    let mut words = snake.split('_');
+    let mut out = words.next().unwrap_or_default().to_string();
+    for w in words {
+        let mut chars = w.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+fn item(id: &str, p: i32) -> String {
+    format!("{{ id: '{id}', priority: {p} }}")
+}
+
+fn step_lines(step: &Step) -> String {
+    match *step {
+        Step::Comment(text) => format!("    // {text}\n"),
+        Step::Insert(id, p) => format!("    pq.insert({});\n", item(id, p)),
+        Step::Pop => "    pq.pop();\n".to_string(),
+        Step::ExpectPop(id, p) => format!("    expect(pq.pop()).toEqual({});\n", item(id, p)),
+        Step::ExpectPopEmpty => "    expect(pq.pop()).toBeUndefined();\n".to_string(),
+        Step::IncreasePriority(id, p) => format!("    pq.increasePriority({});\n", item(id, p)),
+        Step::DecreasePriority(id, p) => format!("    pq.decreasePriority({});\n", item(id, p)),
+        Step::ExpectIncreaseNotFound(id, p) => format!(
+            "    expect(() => pq.increasePriority({})).toThrow('Item not found');\n",
+            item(id, p)
+        ),
+        Step::ExpectDecreaseNotFound(id, p) => format!(
+            "    expect(() => pq.decreasePriority({})).toThrow('Item not found');\n",
+            item(id, p)
+        ),
+        Step::ExpectFront(id, p) => format!("    expect(pq.front()).toEqual({});\n", item(id, p)),
+        Step::ExpectFrontNot(id) => format!("    expect(pq.front().id).not.toBe('{id}');\n"),
+        Step::ExpectPeekEmpty => "    expect(pq.peek()).toBeUndefined();\n".to_string(),
+        Step::ExpectContains(id, expected) => format!(
+            "    expect(pq.contains({})).toBe({expected});\n",
+            item(id, 999)
+        ),
+        Step::ExpectLen(n) => format!("    expect(pq.len()).toBe({n});\n"),
+        Step::ExpectEmpty(expected) => format!("    expect(pq.isEmpty()).toBe({expected});\n"),
+        Step::ExpectHeapInvariant => "    expectHeapInvariant(pq);\n".to_string(),
+    }
+}
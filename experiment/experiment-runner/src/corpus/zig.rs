@@ -0,0 +1,148 @@
+//! Zig layout: a single `src/corpus_tests.zig` targeting the `d_heap`
+//! module. Zig's `Item` is identified by a `u32` number, so each test maps
+//! its string ids to 1, 2, ... in order of first appearance and records the
+//! mapping in a comment.
+
+use super::{banner, CorpusFile, Operation, Step, TestCase, HEADER_GENERATED, HEADER_ORIGIN};
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"const std = @import("std");
+const testing = std.testing;
+
+const d_heap = @import("d_heap");
+const DHeapItem = d_heap.DHeapItem;
+const MinByCost = d_heap.MinByCost;
+const Item = d_heap.Item;
+
+// Helper: every parent's cost is <= each of its children's (min-heap)
+fn expectHeapInvariant(pq: *const DHeapItem) !void {
+    const arr = try pq.toArray();
+    defer if (arr.len > 0) pq.allocator.free(arr);
+    const d = pq.d();
+    for (arr, 0..) |parent, i| {
+        var j: usize = 1;
+        while (j <= d) : (j += 1) {
+            const child = i * d + j;
+            if (child < arr.len) {
+                try testing.expect(parent.cost <= arr[child].cost);
+            }
+        }
+    }
+}
+"#;
+
+pub fn render(cases: &[TestCase]) -> Vec<CorpusFile> {
+    let mut out = format!(
+        "//! Test corpus for insert / pop / front / increasePriority / decreasePriority\n//! Spec: specifications/*.md\n//! {HEADER_ORIGIN}\n//! {HEADER_GENERATED}\n\n{PRELUDE}"
+    );
+    for op in Operation::ALL {
+        out.push('\n');
+        out.push_str(&banner("//", &format!("{}() Tests", op.name())));
+        for case in cases.iter().filter(|c| c.operation == op) {
+            let ids = Ids::collect(case);
+            let _ = write!(
+                out,
+                "\n// Test: {}\n// Spec: {}\n// Property: {}\n// Ids: {}\ntest \"{}\" {{\n    var gpa = std.heap.GeneralPurposeAllocator(.{{}}){{}};\n    defer _ = gpa.deinit();\n    const allocator = gpa.allocator();\n\n    var pq = try DHeapItem.init({}, MinByCost, allocator);\n    defer pq.deinit();\n\n",
+                case.name(),
+                case.spec(),
+                case.property,
+                ids.describe(),
+                case.name(),
+                case.arity
+            );
+            for step in &case.steps {
+                out.push_str(&step_lines(step, &ids));
+            }
+            out.push_str("}\n");
+        }
+    }
+    vec![CorpusFile {
+        path: "src/corpus_tests.zig".to_string(),
+        contents: out,
+    }]
+}
+
+/// String id -> Zig item number, in order of first appearance.
+struct Ids(Vec<&'static str>);
+
+impl Ids {
+    fn collect(case: &TestCase) -> Self {
+        let mut ids = Vec::new();
+        for step in &case.steps {
+            let id = match *step {
+                Step::Insert(id, _)
+                | Step::ExpectPop(id, _)
+                | Step::IncreasePriority(id, _)
+                | Step::DecreasePriority(id, _)
+                | Step::ExpectIncreaseNotFound(id, _)
+                | Step::ExpectDecreaseNotFound(id, _)
+                | Step::ExpectFront(id, _)
+                | Step::ExpectFrontNot(id)
+                | Step::ExpectContains(id, _) => id,
+                _ => continue,
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        Ids(ids)
+    }
+
+    fn number(&self, id: &str) -> usize {
+        self.0.iter().position(|&i| i == id).expect("id collected") + 1
+    }
+
+    fn describe(&self) -> String {
+        if self.0.is_empty() {
+            return "(none)".to_string();
+        }
+        self.0
+            .iter()
+            .map(|id| format!("{id}={}", self.number(id)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn step_lines(step: &Step, ids: &Ids) -> String {
+    let item = |id: &str, p: i32| format!("Item.init({}, {p})", ids.number(id));
+    match *step {
+        Step::Comment(text) => format!("    // {text}\n"),
+        Step::Insert(id, p) => format!("    try pq.insert({});\n", item(id, p)),
+        Step::Pop => "    _ = try pq.pop();\n".to_string(),
+        Step::ExpectPop(id, p) => format!(
+            "    {{\n        const popped = (try pq.pop()).?;\n        try testing.expectEqual(@as(u32, {}), popped.number);\n        try testing.expectEqual(@as(u32, {p}), popped.cost);\n    }}\n",
+            ids.number(id)
+        ),
+        Step::ExpectPopEmpty => "    try testing.expect((try pq.pop()) == null);\n".to_string(),
+        Step::IncreasePriority(id, p) => format!("    try pq.increasePriority({});\n", item(id, p)),
+        Step::DecreasePriority(id, p) => format!("    try pq.decreasePriority({});\n", item(id, p)),
+        Step::ExpectIncreaseNotFound(id, p) => format!(
+            "    try testing.expectError(error.ItemNotFound, pq.increasePriority({}));\n",
+            item(id, p)
+        ),
+        Step::ExpectDecreaseNotFound(id, p) => format!(
+            "    try testing.expectError(error.ItemNotFound, pq.decreasePriority({}));\n",
+            item(id, p)
+        ),
+        Step::ExpectFront(id, p) => format!(
+            "    try testing.expectEqual(@as(u32, {}), pq.front().?.number);\n    try testing.expectEqual(@as(u32, {p}), pq.front().?.cost);\n",
+            ids.number(id)
+        ),
+        Step::ExpectFrontNot(id) => format!(
+            "    try testing.expect(pq.front().?.number != {});\n",
+            ids.number(id)
+        ),
+        Step::ExpectPeekEmpty => "    try testing.expect(pq.peek() == null);\n".to_string(),
+        Step::ExpectContains(id, expected) => {
+            let not = if expected { "" } else { "!" };
+            format!("    try testing.expect({not}pq.contains({}));\n", item(id, 999))
+        }
+        Step::ExpectLen(n) => format!("    try testing.expectEqual(@as(usize, {n}), pq.len());\n"),
+        Step::ExpectEmpty(expected) => {
+            let not = if expected { "" } else { "!" };
+            format!("    try testing.expect({not}pq.isEmpty());\n")
+        }
+        Step::ExpectHeapInvariant => "    try expectHeapInvariant(&pq);\n".to_string(),
+    }
+}
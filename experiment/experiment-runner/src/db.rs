@@ -0,0 +1,486 @@
+//! Results Database
+//!
+//! Every run is recorded, in addition to its flat files, as a row of the
+//! `runs` table of a SQLite database (`results/results.db` next to the flat
+//! files), so analysis across hundreds of runs selects and groups on columns
+//! instead of parsing file names:
+//!
+//! ```sql
+//! SELECT model, condition, AVG(test_count) FROM runs WHERE language = 'rust' GROUP BY 1, 2;
+//! ```
+//!
+//! A row holds the run's configuration, token usage, timing and cost, its
+//! analysis metrics and the paths of its files; the columns are those of
+//! [`ReportRow`] plus the file paths. The flat files remain the source of
+//! truth: [`ResultsDb::import`] (re)builds the rows of a results directory
+//! from them, and recording a run again (same file prefix and timestamp)
+//! replaces its row.
+
+use crate::report::{self, ReportRow, RunFiles};
+use anyhow::{anyhow, Result};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params, Connection};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::path::Path;
+use std::time::Duration;
+
+/// File name of the database inside a results directory
+pub const DB_FILE: &str = "results.db";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id                  INTEGER PRIMARY KEY,
+    run                 TEXT NOT NULL,
+    condition           TEXT NOT NULL,
+    language            TEXT NOT NULL,
+    model               TEXT NOT NULL,
+    provider            TEXT NOT NULL,
+    temperature         REAL,
+    top_p               REAL,
+    thinking_budget     INTEGER,
+    seed                INTEGER,
+    replicate           INTEGER,
+    timestamp           TEXT NOT NULL,
+    input_tokens        INTEGER NOT NULL,
+    output_tokens       INTEGER NOT NULL,
+    thinking_tokens     INTEGER,
+    truncated           INTEGER,
+    parts               INTEGER,
+    elapsed_ms          INTEGER,
+    cost_usd            REAL,
+    test_count          INTEGER,
+    token_ratio         REAL,
+    ast_overlap         REAL,
+    identifier_jaccard  REAL,
+    embedding_cosine    REAL,
+    compiles            INTEGER,
+    tests_passed        INTEGER,
+    tests_failed        INTEGER,
+    tests_timed_out     INTEGER,
+    structured_valid    INTEGER,
+    reported_test_count INTEGER,
+    detected_language   TEXT,
+    language_confidence REAL,
+    meta_file           TEXT NOT NULL,
+    prompt_file         TEXT,
+    response_file       TEXT,
+    code_file           TEXT,
+    UNIQUE (run, timestamp)
+);
+CREATE INDEX IF NOT EXISTS runs_by_setting ON runs (condition, language, model);
+";
+
+/// Columns added to `runs` since its first version, added to older
+/// databases when they are opened
+const ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("thinking_budget", "INTEGER"),
+    ("thinking_tokens", "INTEGER"),
+    ("seed", "INTEGER"),
+    ("structured_valid", "INTEGER"),
+    ("reported_test_count", "INTEGER"),
+    ("detected_language", "TEXT"),
+    ("language_confidence", "REAL"),
+    ("truncated", "INTEGER"),
+    ("parts", "INTEGER"),
+    ("embedding_cosine", "REAL"),
+];
+
+/// An open results database
+pub struct ResultsDb {
+    conn: Connection,
+}
+
+/// Result of [`ResultsDb::query`]: column names and rows of values
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl ResultsDb {
+    /// Open the database at `path`, creating it and its schema if needed
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .map_err(|e| anyhow!("Failed to open results database {}: {}", path.display(), e))?;
+        // Concurrent batch runs each open their own connection
+        conn.busy_timeout(Duration::from_secs(10))?;
+        conn.execute_batch(SCHEMA)?;
+        migrate(&conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Open an existing database at `path` for reading
+    pub fn open_existing(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(anyhow!(
+                "No results database at {} (runs record into results/{}; `import` builds it from existing results)",
+                path.display(),
+                DB_FILE
+            ));
+        }
+        Self::open(path)
+    }
+
+    /// Record one run, replacing an earlier row of the same run and timestamp
+    pub fn record(&self, row: &ReportRow, files: &RunFiles) -> Result<()> {
+        record(&self.conn, row, files)
+    }
+
+    /// Record every run of the results directory `dir`, returning how many
+    pub fn import(&mut self, dir: &Path) -> Result<usize> {
+        let runs = report::collect_runs(dir)?;
+        let tx = self.conn.transaction()?;
+        for (row, files) in &runs {
+            record(&tx, row, files)?;
+        }
+        tx.commit()?;
+        Ok(runs.len())
+    }
+
+    /// Run the SQL statement `sql` and collect its result
+    pub fn query(&self, sql: &str) -> Result<QueryResult> {
+        let mut statement = self.conn.prepare(sql)?;
+        let columns: Vec<String> = statement.column_names().iter().map(|name| name.to_string()).collect();
+        let mut rows = Vec::new();
+        let mut cursor = statement.query([])?;
+        while let Some(row) = cursor.next()? {
+            let values = (0..columns.len())
+                .map(|i| row.get_ref(i).map(json_value))
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows.push(values);
+        }
+        Ok(QueryResult { columns, rows })
+    }
+}
+
+fn migrate(conn: &Connection) -> Result<()> {
+    let existing = conn
+        .prepare("SELECT name FROM pragma_table_info('runs')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (column, kind) in ADDED_COLUMNS {
+        if !existing.iter().any(|name| name == column) {
+            conn.execute_batch(&format!("ALTER TABLE runs ADD COLUMN {} {}", column, kind))?;
+        }
+    }
+    Ok(())
+}
+
+fn record(conn: &Connection, row: &ReportRow, files: &RunFiles) -> Result<()> {
+    let path = |path: Option<&Path>| path.map(|path| path.display().to_string());
+    conn.execute(
+        "INSERT OR REPLACE INTO runs (
+            run, condition, language, model, provider, temperature, top_p, thinking_budget,
+            seed, replicate, timestamp, input_tokens, output_tokens, thinking_tokens,
+            truncated, parts, elapsed_ms, cost_usd, test_count, token_ratio, ast_overlap,
+            identifier_jaccard, embedding_cosine, compiles, tests_passed, tests_failed,
+            tests_timed_out, structured_valid, reported_test_count, detected_language,
+            language_confidence, meta_file, prompt_file, response_file, code_file
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+            ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32,
+            ?33, ?34, ?35)",
+        params![
+            row.run,
+            row.condition,
+            row.language,
+            row.model,
+            row.provider,
+            row.temperature,
+            row.top_p,
+            row.thinking_budget,
+            row.seed,
+            row.replicate,
+            row.timestamp,
+            row.input_tokens,
+            row.output_tokens,
+            row.thinking_tokens,
+            row.truncated,
+            row.parts,
+            row.elapsed_ms,
+            row.cost_usd,
+            row.test_count,
+            row.token_ratio,
+            row.ast_overlap,
+            row.identifier_jaccard,
+            row.embedding_cosine,
+            row.compiles,
+            row.tests_passed,
+            row.tests_failed,
+            row.tests_timed_out,
+            row.structured_valid,
+            row.reported_test_count,
+            row.detected_language,
+            row.language_confidence,
+            files.meta.display().to_string(),
+            path(files.prompt.as_deref()),
+            path(files.response.as_deref()),
+            path(files.code.as_deref()),
+        ],
+    )?;
+    Ok(())
+}
+
+fn json_value(value: ValueRef<'_>) -> serde_json::Value {
+    match Value::from(value) {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(n) => n.into(),
+        Value::Real(x) => x.into(),
+        Value::Text(text) => text.into(),
+        Value::Blob(bytes) => format!("<{} bytes>", bytes.len()).into(),
+    }
+}
+
+impl QueryResult {
+    /// Render as CSV with a header line; NULLs are empty cells
+    pub fn to_csv(&self) -> String {
+        let mut out = self.columns.iter().map(|column| report::quote(column)).collect::<Vec<_>>().join(",");
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&row.iter().map(|value| report::quote(&cell(value))).collect::<Vec<_>>().join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Render as a JSON array of objects keyed by column name
+    pub fn to_json(&self) -> Result<String> {
+        let objects: Vec<JsonRow<'_>> = self.rows.iter().map(|row| JsonRow(&self.columns, row)).collect();
+        Ok(serde_json::to_string_pretty(&objects)? + "\n")
+    }
+
+    /// Render as a table with aligned columns, for the terminal
+    pub fn to_table(&self) -> String {
+        let cells: Vec<Vec<String>> = self.rows.iter().map(|row| row.iter().map(cell).collect()).collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| cells.iter().map(|row| row[i].chars().count()).fold(column.chars().count(), usize::max))
+            .collect();
+        let line = |fields: &[String]| {
+            let padded: Vec<String> = fields
+                .iter()
+                .zip(&widths)
+                .map(|(field, &width)| format!("{:<width$}", field, width = width))
+                .collect();
+            padded.join("  ").trim_end().to_string() + "\n"
+        };
+
+        let mut out = line(&self.columns);
+        out.push_str(&line(&widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>()));
+        for row in &cells {
+            out.push_str(&line(row));
+        }
+        out
+    }
+}
+
+/// A row as a JSON object, its keys in column order
+struct JsonRow<'a>(&'a [String], &'a [serde_json::Value]);
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (column, value) in self.0.iter().zip(self.1) {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+/// A value as text, NULL as empty
+fn cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A results directory under the system temp dir, removed on drop
+    struct ResultsDir(PathBuf);
+
+    impl ResultsDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "experiment-runner-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            std::fs::write(self.0.join(file), contents).unwrap();
+        }
+    }
+
+    impl Drop for ResultsDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn meta(condition: &str, timestamp: &str, extra: &str) -> String {
+        format!(
+            r#"{{"condition": "{condition}", "language": "rust", "model": "claude-sonnet-4",
+                "provider": "anthropic", "timestamp": "{timestamp}", "input_tokens": 1200,
+                "output_tokens": 800{extra}}}"#
+        )
+    }
+
+    /// Two runs: one analyzed from its code file, with every newer field;
+    /// one from an older meta file, without code
+    fn results(name: &str) -> ResultsDir {
+        let dir = ResultsDir::new(name);
+        dir.write(
+            "baseline_rust_1_meta.json",
+            &meta(
+                "baseline",
+                "2025-01-01T10:00:00Z",
+                r#", "cost_usd": 0.0156, "truncated": true, "parts": 2, "seed": 7,
+                   "compile": {"success": true},
+                   "tests": {"passed": 3, "failed": 1, "timed_out": false},
+                   "detected_language": {"language": "rust", "confidence": 0.9}"#,
+            ),
+        );
+        dir.write("baseline_rust_1_prompt.md", "Write a d-ary heap");
+        dir.write(
+            "baseline_rust_1_code.rs",
+            "#[test]\nfn a() {}\n#[test]\nfn b() {}\n",
+        );
+        dir.write(
+            "test_guided_rust_1_meta.json",
+            &meta("test_guided", "2024-06-01T09:00:00Z", ""),
+        );
+        dir
+    }
+
+    fn open(dir: &ResultsDir) -> ResultsDb {
+        ResultsDb::open(&dir.0.join(DB_FILE)).unwrap()
+    }
+
+    #[test]
+    fn import_records_every_run_of_a_results_directory() {
+        let dir = results("import");
+        let mut db = open(&dir);
+        assert_eq!(db.import(&dir.0).unwrap(), 2);
+
+        let result = db
+            .query(
+                "SELECT run, test_count, cost_usd, truncated, parts, compiles, tests_failed,
+                        detected_language, prompt_file IS NOT NULL, code_file IS NOT NULL
+                 FROM runs ORDER BY run",
+            )
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            [
+                serde_json::json!(["baseline_rust_1", 2, 0.0156, 1, 2, 1, 1, "rust", 1, 1]),
+                serde_json::json!([
+                    "test_guided_rust_1",
+                    null,
+                    null,
+                    null,
+                    null,
+                    null,
+                    null,
+                    null,
+                    0,
+                    0
+                ]),
+            ]
+            .map(|row| row.as_array().unwrap().clone())
+        );
+    }
+
+    #[test]
+    fn importing_again_replaces_rows_instead_of_adding_them() {
+        let dir = results("reimport");
+        let mut db = open(&dir);
+        db.import(&dir.0).unwrap();
+        dir.write("baseline_rust_1_code.rs", "#[test]\nfn only() {}\n");
+        assert_eq!(db.import(&dir.0).unwrap(), 2);
+
+        let result = db
+            .query("SELECT COUNT(*), SUM(test_count) FROM runs")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            [vec![serde_json::json!(2), serde_json::json!(1)]]
+        );
+    }
+
+    #[test]
+    fn opening_an_older_database_adds_the_new_columns() {
+        let dir = ResultsDir::new("migrate");
+        let path = dir.0.join(DB_FILE);
+        // The first version of the schema: today's without the added columns
+        let first_version: String = SCHEMA
+            .lines()
+            .filter(|line| {
+                let column = line.split_whitespace().next().unwrap_or("");
+                !ADDED_COLUMNS.iter().any(|(added, _)| *added == column)
+            })
+            .flat_map(|line| [line, "\n"])
+            .collect();
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(&first_version)
+            .unwrap();
+
+        let db = ResultsDb::open(&path).unwrap();
+        let columns = db
+            .query("SELECT name FROM pragma_table_info('runs')")
+            .unwrap();
+        for (column, _) in ADDED_COLUMNS {
+            assert!(
+                columns.rows.contains(&vec![serde_json::json!(column)]),
+                "{} missing",
+                column
+            );
+        }
+    }
+
+    #[test]
+    fn open_existing_refuses_a_missing_database() {
+        let dir = ResultsDir::new("missing");
+        let error = ResultsDb::open_existing(&dir.0.join(DB_FILE))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("No results database"));
+    }
+
+    #[test]
+    fn query_results_render_as_csv_json_and_table() {
+        let result = QueryResult {
+            columns: vec!["model".to_string(), "mean, tests".to_string()],
+            rows: vec![
+                vec![
+                    serde_json::json!("claude-sonnet-4"),
+                    serde_json::json!(12.5),
+                ],
+                vec![serde_json::json!("codestral"), serde_json::Value::Null],
+            ],
+        };
+        assert_eq!(
+            result.to_csv(),
+            "model,\"mean, tests\"\nclaude-sonnet-4,12.5\ncodestral,\n"
+        );
+        assert_eq!(
+            result.to_table(),
+            "model            mean, tests\n---------------  -----------\nclaude-sonnet-4  12.5\ncodestral\n"
+        );
+        let json: serde_json::Value = serde_json::from_str(&result.to_json().unwrap()).unwrap();
+        assert_eq!(json[0]["mean, tests"], 12.5);
+        assert_eq!(json[1]["model"], "codestral");
+        assert!(json[1]["mean, tests"].is_null());
+    }
+}
@@ -0,0 +1,120 @@
+//! Embedding Similarity
+//!
+//! The syntax metrics of [`analysis::compare`](crate::analysis::compare)
+//! depend on a grammar: `ast_overlap` is missing for Zig, and none of them
+//! compare across languages on one scale. An embedding model maps code of
+//! any language into one vector space, so the cosine similarity of a
+//! generated file and its reference implementation is a fourth, grammar-free
+//! score, from `analyze --embeddings`.
+//!
+//! Reference implementations run to tens of thousands of tokens, beyond the
+//! input limit of embedding models, so a document is split into chunks of at
+//! most [`CHUNK_CHARS`] on line boundaries, each chunk embedded, and the
+//! chunk vectors averaged, weighted by their length.
+
+use crate::provider::LlmProvider;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Longest chunk embedded at once: about 4,000 tokens at 3 characters per
+/// token, half the 8,192-token input of current code embedding models
+pub const CHUNK_CHARS: usize = 12_000;
+
+/// Cosine similarity of the embeddings of generated code and its reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingSimilarity {
+    /// Embedding model, as the provider reported it
+    pub model: String,
+    pub cosine: f64,
+}
+
+/// A document embedded chunk by chunk
+#[derive(Debug, Clone)]
+pub struct DocumentEmbedding {
+    /// Length-weighted mean of the chunk vectors
+    pub vector: Vec<f32>,
+    pub chunks: usize,
+    pub input_tokens: usize,
+    pub model: String,
+}
+
+/// `text` cut into chunks of at most [`CHUNK_CHARS`] bytes, on line
+/// boundaries where a line fits
+pub fn chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    for line in text.split_inclusive('\n') {
+        if end - start + line.len() > CHUNK_CHARS && end > start {
+            chunks.push(&text[start..end]);
+            start = end;
+        }
+        end += line.len();
+        // A line longer than a chunk is cut on character boundaries
+        while end - start > CHUNK_CHARS {
+            let mut cut = start + CHUNK_CHARS;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            chunks.push(&text[start..cut]);
+            start = cut;
+        }
+    }
+    if end > start && !text[start..end].trim().is_empty() {
+        chunks.push(&text[start..end]);
+    }
+    chunks
+}
+
+/// Embed `text` with `provider`, one request per chunk
+pub async fn embed_document(provider: &dyn LlmProvider, text: &str, model: Option<&str>) -> Result<DocumentEmbedding> {
+    let chunks = chunks(text);
+    if chunks.is_empty() {
+        return Err(anyhow!("Nothing to embed"));
+    }
+    let mut sum: Vec<f64> = Vec::new();
+    let mut input_tokens = 0;
+    let mut used_model = String::new();
+    for chunk in &chunks {
+        let embeddings = provider.embed(&[chunk], model).await?;
+        let vector = embeddings
+            .vectors
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No embedding returned"))?;
+        if sum.is_empty() {
+            sum = vec![0.0; vector.len()];
+        } else if vector.len() != sum.len() {
+            return Err(anyhow!("Embeddings of different dimensions ({} and {})", sum.len(), vector.len()));
+        }
+        let weight = chunk.len() as f64;
+        for (total, value) in sum.iter_mut().zip(&vector) {
+            *total += f64::from(*value) * weight;
+        }
+        input_tokens += embeddings.input_tokens;
+        used_model = embeddings.model;
+    }
+    let total = text.len() as f64;
+    Ok(DocumentEmbedding {
+        vector: sum.into_iter().map(|value| (value / total) as f32).collect(),
+        chunks: chunks.len(),
+        input_tokens,
+        model: used_model,
+    })
+}
+
+/// Cosine similarity of two vectors of one dimension; `None` for vectors
+/// of different dimensions or a zero vector
+pub fn cosine(a: &[f32], b: &[f32]) -> Option<f64> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0, 0.0, 0.0);
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (f64::from(x), f64::from(y));
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    (norm_a > 0.0 && norm_b > 0.0).then(|| dot / (norm_a.sqrt() * norm_b.sqrt()))
+}
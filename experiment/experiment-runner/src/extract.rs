@@ -0,0 +1,562 @@
+//! Code Extraction
+//!
+//! A response is markdown; the code to score is in its fenced blocks. A
+//! response may hold several — the implementation, a test file, a build
+//! file, a usage example, a revised implementation after "wait, let me
+//! fix that" — so every block is parsed and classified, and one is chosen
+//! as the run's code:
+//!
+//! - **Fences** follow CommonMark: three or more backticks or tildes, closed
+//!   by a fence of the same character at least as long, so a ```` fence can
+//!   hold ``` lines. A fence left open runs to the end of the response.
+//! - **Languages** come from the info string (```` ```rust ````) or, when it
+//!   is missing, from the extension of the block's file name. A block with
+//!   neither is matched against markers of each language (`fn `, `:= `,
+//!   `@import(`, ...) and takes the language most of the markers found point
+//!   to, with that share as its confidence ([`Detection`]), so Zig code
+//!   answering a Rust prompt is flagged rather than scored as Rust.
+//! - **File names** come from the info string (`rust title="src/lib.rs"`,
+//!   `rust:src/lib.rs`), a first-line comment (`// dheap_test.go`), a C++
+//!   include guard, or a name in the prose line introducing the block.
+//! - **Kinds** ([`BlockKind`]) follow from the file name, the language and
+//!   the content (`package heap_test`, `#include <gtest/gtest.h>`, an entry
+//!   point, "here's how to use it").
+//!
+//! The run's code is the last implementation block at least half as long as
+//! the longest one, so a revision wins over the draft it replaces but not
+//! over a one-line snippet; without an implementation block, an example,
+//! then a test block, then any block, then the whole response stands in.
+
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// What a fenced block holds, as far as its name, language and content tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockKind {
+    /// Code in the target language, none of the kinds below
+    Implementation,
+    /// A test file or test support (`*_test.go`, `test_common.h`, vitest)
+    Test,
+    /// A usage example: an entry point (`main`) or introduced as one
+    Example,
+    /// A build or project file (`Cargo.toml`, `CMakeLists.txt`, `go.mod`)
+    Build,
+    /// Another language (shell commands, Python, ...)
+    Other,
+}
+
+impl BlockKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Implementation => "implementation",
+            Self::Test => "test",
+            Self::Example => "example",
+            Self::Build => "build",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// A language guessed from the content of code
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Detection {
+    pub language: &'static str,
+    /// Share of the language markers found that point to `language`, from
+    /// 0 to 1
+    pub confidence: f64,
+}
+
+/// Confidence from which a detected language is taken as the block's
+pub const CONFIDENT: f64 = 0.5;
+
+/// One fenced block of a response
+#[derive(Debug, Clone)]
+pub struct CodeBlock<'a> {
+    /// Position among the response's blocks, from 1
+    pub index: usize,
+    /// First word of the info string, as written
+    pub tag: Option<&'a str>,
+    /// Normalized language (`rust`, `go`, `cpp`, `typescript`, `zig`,
+    /// `python`, `toml`, `shell`, ...), from the tag or the file name, else
+    /// detected with at least [`CONFIDENT`] confidence
+    pub language: Option<&'static str>,
+    /// Language guessed from the content, for a block with neither a tag
+    /// nor a file name naming one
+    pub detected: Option<Detection>,
+    pub filename: Option<String>,
+    pub kind: BlockKind,
+    pub code: Cow<'a, str>,
+    /// Whether a closing fence was found
+    pub closed: bool,
+}
+
+impl CodeBlock<'_> {
+    pub fn lines(&self) -> usize {
+        self.code.lines().count()
+    }
+
+    /// Extension to save the block under: the file name's, else the
+    /// language's (`target` for an untagged block)
+    pub fn extension(&self, target: &str) -> &str {
+        if let Some((_, ext)) = self.filename.as_deref().and_then(|name| name.rsplit_once('.')) {
+            return ext;
+        }
+        match self.language.unwrap_or(target) {
+            "go" => "go",
+            "rust" => "rs",
+            "cpp" => "hpp",
+            "typescript" => "ts",
+            "javascript" => "js",
+            "zig" => "zig",
+            "python" => "py",
+            "toml" => "toml",
+            "json" => "json",
+            "cmake" => "cmake",
+            "makefile" => "mk",
+            "shell" => "sh",
+            _ => "txt",
+        }
+    }
+}
+
+/// The blocks of a response and the one chosen as its code
+#[derive(Debug, Clone)]
+pub struct Extraction<'a> {
+    pub blocks: Vec<CodeBlock<'a>>,
+    /// Index into `blocks` of the run's code; `None` when the response has
+    /// no block and the whole response stands in
+    pub primary: Option<usize>,
+    response: &'a str,
+}
+
+impl Extraction<'_> {
+    /// The run's code
+    pub fn code(&self) -> &str {
+        match self.primary {
+            Some(primary) => &self.blocks[primary].code,
+            None => self.response,
+        }
+    }
+
+    /// Language guessed from the run's code, unless its block names one
+    pub fn detected(&self) -> Option<Detection> {
+        match self.primary {
+            Some(primary) => self.blocks[primary].detected,
+            None => detect_language(self.response),
+        }
+    }
+}
+
+/// Parse and classify the fenced blocks of `response`, whose code should be
+/// in `language` (a normalized language name), and choose the run's code
+pub fn extract<'a>(response: &'a str, language: &str) -> Extraction<'a> {
+    let blocks: Vec<CodeBlock<'a>> = fences(response)
+        .into_iter()
+        .enumerate()
+        .map(|(i, fence)| classify(i + 1, fence, language))
+        .collect();
+    let primary = [BlockKind::Implementation, BlockKind::Example, BlockKind::Test]
+        .iter()
+        .find_map(|&kind| choose(&blocks, |block| block.kind == kind))
+        .or_else(|| choose(&blocks, |_| true));
+    Extraction { blocks, primary, response }
+}
+
+/// The last of the blocks matching `wanted` at least half as long as the
+/// longest of them
+fn choose(blocks: &[CodeBlock<'_>], wanted: impl Fn(&CodeBlock<'_>) -> bool) -> Option<usize> {
+    let longest = blocks.iter().filter(|block| wanted(block)).map(CodeBlock::lines).max()?;
+    blocks.iter().rposition(|block| wanted(block) && block.lines() * 2 >= longest)
+}
+
+/// A fenced block as found, before classification
+struct Fence<'a> {
+    info: &'a str,
+    code: Cow<'a, str>,
+    closed: bool,
+    /// Last non-blank line of the prose before the block
+    intro: &'a str,
+}
+
+fn fences(response: &str) -> Vec<Fence<'_>> {
+    let mut fences = Vec::new();
+    let mut lines = line_spans(response).peekable();
+    let mut prose_start = 0;
+    while let Some((start, line)) = lines.next() {
+        let Some((indent, marker, count, info)) = opening_fence(line) else {
+            continue;
+        };
+        let intro = response[prose_start..start].lines().rev().map(str::trim).find(|line| !line.is_empty());
+
+        let code_start = lines.peek().map_or(response.len(), |&(offset, _)| offset);
+        let mut code_end = response.len();
+        let mut closed = false;
+        for (offset, line) in lines.by_ref() {
+            if closing_fence(line, marker, count) {
+                code_end = offset;
+                closed = true;
+                break;
+            }
+        }
+        prose_start = lines.peek().map_or(response.len(), |&(offset, _)| offset);
+
+        let body = response[code_start..code_end].trim_end_matches(['\n', '\r']);
+        let code = if indent == 0 {
+            Cow::Borrowed(body)
+        } else {
+            // Inside a list item: strip the fence's indentation
+            Cow::Owned(
+                body.lines()
+                    .map(|line| {
+                        let strip = line.len() - line.trim_start_matches(' ').len();
+                        &line[strip.min(indent)..]
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        };
+        fences.push(Fence { info, code, closed, intro: intro.unwrap_or("") });
+    }
+    fences
+}
+
+/// Lines of `text` with their byte offsets
+fn line_spans(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_matches(['\n', '\r'])))
+    })
+}
+
+/// `(indentation, fence character, fence length, info string)` of an opening
+/// fence line
+fn opening_fence(line: &str) -> Option<(usize, char, usize, &str)> {
+    let rest = line.trim_start_matches(' ');
+    let indent = line.len() - rest.len();
+    let marker = rest.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let count = rest.len() - rest.trim_start_matches(marker).len();
+    let info = rest[count..].trim();
+    // A backtick fence's info string cannot hold backticks (that is inline code)
+    if count < 3 || (marker == '`' && info.contains('`')) {
+        return None;
+    }
+    Some((indent, marker, count, info))
+}
+
+fn closing_fence(line: &str, marker: char, count: usize) -> bool {
+    let rest = line.trim();
+    rest.len() >= count && rest.chars().all(|c| c == marker)
+}
+
+fn classify<'a>(index: usize, fence: Fence<'a>, target: &str) -> CodeBlock<'a> {
+    let mut words = fence.info.split_whitespace();
+    // `rust:src/lib.rs` names the file in the tag
+    let (tag, tag_file) = match words.next() {
+        Some(word) => match word.split_once(':') {
+            Some((tag, file)) => (Some(tag), Some(file)),
+            None => (Some(word), None),
+        },
+        None => (None, None),
+    };
+    let filename = tag_file
+        .into_iter()
+        .chain(words.map(|word| {
+            word.split_once('=')
+                .map_or(word, |(_, value)| value)
+                .trim_matches(|c| c == '"' || c == '\'' || c == '{' || c == '}')
+        }))
+        .find(|word| is_filename(word))
+        .map(str::to_string)
+        .or_else(|| comment_filename(&fence.code))
+        .or_else(|| include_guard_filename(&fence.code))
+        .or_else(|| prose_filename(fence.intro));
+
+    let named = tag
+        .and_then(tag_language)
+        .or_else(|| filename.as_deref().and_then(|name| name.rsplit_once('.')).and_then(|(_, ext)| tag_language(ext)));
+    let detected = if named.is_none() { detect_language(&fence.code) } else { None };
+    let language = named.or_else(|| {
+        detected.filter(|detection| detection.confidence >= CONFIDENT).map(|detection| detection.language)
+    });
+    let kind = kind(language, filename.as_deref(), &fence, target);
+    CodeBlock { index, tag, language, detected, filename, kind, code: fence.code, closed: fence.closed }
+}
+
+fn kind(language: Option<&str>, filename: Option<&str>, fence: &Fence<'_>, target: &str) -> BlockKind {
+    let name = filename.map(|name| name.rsplit('/').next().unwrap_or(name).to_lowercase());
+    let stem = name.as_deref().map(|name| name.split('.').next().unwrap_or(name));
+    let code = &fence.code;
+
+    if name.as_deref().is_some_and(|name| BUILD_FILES.contains(&name))
+        || language.is_some_and(|language| ["toml", "json", "cmake", "makefile"].contains(&language))
+    {
+        return BlockKind::Build;
+    }
+    if language.is_some_and(|language| language != target) {
+        return BlockKind::Other;
+    }
+    let test_content = code.lines().any(|line| {
+        let line = line.trim();
+        (line.starts_with("package ") && line.ends_with("_test"))
+            || (line.starts_with("#include") && line.contains("gtest"))
+            || (line.starts_with("import ") && ["vitest", "@jest/globals", "mocha"].iter().any(|lib| line.contains(lib)))
+    });
+    // `dheap_test.go`, `test_common.h`, `tests/insert.rs`
+    let test_path = filename.is_some_and(|name| {
+        let name = name.to_lowercase();
+        name.rsplit_once('.').map_or(name.as_str(), |(path, _)| path).contains("test")
+    });
+    if test_path || test_content {
+        return BlockKind::Test;
+    }
+    let intro = fence.intro.to_lowercase();
+    let entry_point = code.lines().any(|line| {
+        let line = line.trim_start();
+        ["fn main(", "pub fn main(", "func main(", "int main("].iter().any(|main| line.starts_with(main))
+    });
+    if stem.is_some_and(|stem| ["main", "usage", "demo"].contains(&stem) || stem.starts_with("example"))
+        || ["example", "usage", "how to use", "to use this", "you might use", "you can use"]
+            .iter()
+            .any(|hint| intro.contains(hint))
+        || entry_point
+    {
+        return BlockKind::Example;
+    }
+    BlockKind::Implementation
+}
+
+const BUILD_FILES: &[&str] = &[
+    "cargo.toml",
+    "go.mod",
+    "go.sum",
+    "cmakelists.txt",
+    "makefile",
+    "package.json",
+    "tsconfig.json",
+    "build.zig",
+    "build.zig.zon",
+];
+
+/// Normalized language of an info-string tag or a file extension
+fn tag_language(tag: &str) -> Option<&'static str> {
+    Some(match tag.to_lowercase().as_str() {
+        "go" | "golang" => "go",
+        "rust" | "rs" => "rust",
+        "cpp" | "c++" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "h" | "c" => "cpp",
+        "typescript" | "ts" | "tsx" => "typescript",
+        "javascript" | "js" | "mjs" => "javascript",
+        "zig" => "zig",
+        "python" | "py" => "python",
+        "toml" => "toml",
+        "json" => "json",
+        "cmake" => "cmake",
+        "makefile" | "make" | "mk" => "makefile",
+        "bash" | "sh" | "shell" | "console" | "zsh" => "shell",
+        "text" | "txt" | "plaintext" => "text",
+        _ => return None,
+    })
+}
+
+/// Markers of each language detected from content: syntax rarely seen in
+/// the others, each counted once however often it occurs
+const MARKERS: &[(&str, &[&str])] = &[
+    ("rust", &[
+        "fn ", "let mut ", "impl ", "impl<", "&self", "&mut ", "use std::", "#[derive(", "#[test]",
+        "-> Self", "Vec<", "Option<", "Some(", "unwrap()", "mod tests", "println!(", "assert_eq!(",
+    ]),
+    ("go", &[
+        "func ", "package ", ":= ", "import (", " nil", "*testing.T", "fmt.", "t.Errorf(", "t.Fatalf(",
+        "map[", "append(", "range ", "interface{}",
+    ]),
+    ("cpp", &[
+        "#include", "std::vector", "std::unordered_map", "template <", "template<", "namespace ", "public:",
+        "private:", "nullptr", "#pragma once", "#ifndef ", "size_t", "TEST(", "EXPECT_EQ(",
+    ]),
+    ("typescript", &[
+        "export class ", "export function ", "interface ", ": number", ": string", ": boolean",
+        "constructor(", "this.", "import {", "readonly ", "describe(", "expect(", "undefined", "===",
+    ]),
+    ("zig", &[
+        "@import(", "comptime", "errdefer", "!void", "= try ", "orelse", "std.testing", "std.mem.",
+        "Allocator", ".{", "test \"", "const std =", "@intCast", "anytype", "pub const ",
+    ]),
+    ("python", &["def ", "elif ", "__init__", "self, ", "from typing", "print(", "    pass"]),
+];
+
+/// Guess the language of `code` from its markers, outside `//` and `/* */`
+/// comment lines (doc comments name other languages' syntax); `None` when
+/// fewer than two markers of any language are found, or two languages tie
+pub fn detect_language(code: &str) -> Option<Detection> {
+    let code: String = code
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !["//", "/*", "* ", "*/"].iter().any(|comment| line.starts_with(comment)) && line != "*"
+        })
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    let scores: Vec<(&'static str, usize)> = MARKERS
+        .iter()
+        .map(|&(language, markers)| (language, markers.iter().filter(|marker| code.contains(*marker)).count()))
+        .collect();
+    let total: usize = scores.iter().map(|&(_, score)| score).sum();
+    let best = scores.iter().map(|&(_, score)| score).max()?;
+    let mut leaders = scores.iter().filter(|&&(_, score)| score == best);
+    let (language, _) = *leaders.next()?;
+    if best < 2 || leaders.next().is_some() {
+        return None;
+    }
+    let confidence = best as f64 / total as f64;
+    Some(Detection { language, confidence })
+}
+
+/// Whether `word` looks like a file name: a known build file, or a known
+/// source extension after a name of path characters
+fn is_filename(word: &str) -> bool {
+    let name = word.rsplit('/').next().unwrap_or(word);
+    if BUILD_FILES.contains(&name.to_lowercase().as_str()) {
+        return true;
+    }
+    let Some((stem, ext)) = name.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || "_-./".contains(c))
+        && ["rs", "go", "h", "hpp", "hh", "cc", "cpp", "cxx", "ts", "tsx", "js", "mjs", "zig", "py", "toml", "json", "cmake", "txt"]
+            .contains(&ext)
+}
+
+/// `// dheap_test.go`, `// File: src/lib.rs`, `# CMakeLists.txt` on the
+/// first line
+fn comment_filename(code: &str) -> Option<String> {
+    let line = code.lines().next()?.trim();
+    let text = ["//", "#", "--", "/*"].iter().find_map(|prefix| line.strip_prefix(prefix))?.trim();
+    let lower = text.to_lowercase();
+    let text = ["file:", "filename:"]
+        .iter()
+        .find_map(|label| lower.starts_with(label).then(|| text[label.len()..].trim()))
+        .unwrap_or(text);
+    let word = text.split_whitespace().next()?;
+    is_filename(word).then(|| word.to_string())
+}
+
+/// `priority_queue.h` from an include guard `#ifndef PRIORITY_QUEUE_H`
+fn include_guard_filename(code: &str) -> Option<String> {
+    let mut lines = code.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with("//"));
+    let guard = lines.next()?.strip_prefix("#ifndef ")?.trim();
+    let define = lines.next()?.strip_prefix("#define ")?.trim();
+    let stem = guard.strip_suffix("_HPP").map(|stem| (stem, "hpp")).or_else(|| guard.strip_suffix("_H").map(|stem| (stem, "h")));
+    match stem {
+        Some((stem, ext)) if define == guard && !stem.is_empty() => Some(format!("{}.{}", stem.to_lowercase(), ext)),
+        _ => None,
+    }
+}
+
+/// The last file name in the line introducing a block, e.g. "you'll need
+/// this `test_common.h` file:"
+fn prose_filename(intro: &str) -> Option<String> {
+    intro
+        .split_whitespace()
+        .rev()
+        .map(|word| word.trim_matches(|c: char| "`*_:;,()[]\"'#".contains(c)).trim_end_matches('.'))
+        .find(|word| is_filename(word))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(code: &str) -> &'static str {
+        detect_language(code).expect("a language").language
+    }
+
+    #[test]
+    fn detects_each_corpus_language() {
+        let rust = "use std::collections::HashMap;\n\nimpl<T> Heap<T> {\n    fn len(&self) -> usize { self.items.len() }\n}";
+        let go =
+            "package dheap\n\nfunc (h *Heap) Len() int {\n    n := len(h.items)\n    return n\n}";
+        let cpp = "#pragma once\n#include <vector>\n\ntemplate <typename T>\nclass Heap {\npublic:\n    size_t len() const;\n};";
+        let typescript = "export class Heap {\n  private readonly items: number[] = [];\n  size(): number { return this.items.length; }\n}";
+        let zig = "const std = @import(\"std\");\n\npub fn Heap(comptime T: type) type {\n    return struct { items: std.ArrayList(T) };\n}";
+        assert_eq!(detected(rust), "rust");
+        assert_eq!(detected(go), "go");
+        assert_eq!(detected(cpp), "cpp");
+        assert_eq!(detected(typescript), "typescript");
+        assert_eq!(detected(zig), "zig");
+    }
+
+    #[test]
+    fn comments_do_not_count_as_markers() {
+        // Rust doc comments describing Go and Zig syntax
+        let code = "/// Like Go's `func (h *Heap)` and `:= `, or Zig's `@import(` and `comptime`\n\
+                    impl Heap {\n    fn len(&self) -> usize { 0 }\n}";
+        let detection = detect_language(code).unwrap();
+        assert_eq!(detection.language, "rust");
+        assert_eq!(detection.confidence, 1.0);
+    }
+
+    #[test]
+    fn too_few_markers_or_a_tie_detect_nothing() {
+        assert_eq!(detect_language("x = 1"), None);
+        assert_eq!(detect_language("fn f() {}"), None);
+        // Two Rust markers, two Go markers
+        assert_eq!(
+            detect_language("fn a() { let mut x = 1; }\nfunc b() { y := 2 }"),
+            None
+        );
+    }
+
+    #[test]
+    fn confidence_is_the_leaders_share_of_markers() {
+        // Three Rust markers (`fn `, `let mut `, `Vec<`), one Go marker (`:= `)
+        let code =
+            "fn f() {\n    let mut v: Vec<u8> = vec![];\n    // x := 1\n}\nlet s = \"a := b\";";
+        let detection = detect_language(code).unwrap();
+        assert_eq!(detection.language, "rust");
+        assert_eq!(detection.confidence, 0.75);
+    }
+
+    #[test]
+    fn untagged_block_in_another_language_is_flagged() {
+        let response = "Here is the heap:\n\n```\nconst std = @import(\"std\");\npub fn main() !void {\n    try std.io.getStdOut().writer().print(\"hi\", .{});\n}\n```\n";
+        let extraction = extract(response, "rust");
+        let block = &extraction.blocks[0];
+        assert_eq!(block.tag, None);
+        assert_eq!(block.language, Some("zig"));
+        assert_eq!(block.kind, BlockKind::Other);
+        assert_eq!(extraction.detected().map(|d| d.language), Some("zig"));
+    }
+
+    #[test]
+    fn a_tag_or_file_name_takes_precedence_over_detection() {
+        let go_code = "package dheap\n\nfunc New() *Heap {\n    h := &Heap{}\n    return h\n}";
+        let response = format!("```rust\n{go_code}\n```");
+        let tagged = extract(&response, "go");
+        assert_eq!(tagged.blocks[0].language, Some("rust"));
+        assert_eq!(tagged.blocks[0].detected, None);
+
+        let response = format!("```\n// dheap.go\n{go_code}\n```");
+        let named = extract(&response, "go");
+        assert_eq!(named.blocks[0].filename.as_deref(), Some("dheap.go"));
+        assert_eq!(named.blocks[0].language, Some("go"));
+        assert_eq!(named.blocks[0].detected, None);
+    }
+
+    #[test]
+    fn an_unconfident_detection_leaves_the_language_unset() {
+        // Two Rust markers against one each of Go, C++ and TypeScript
+        let code = "fn f() {\n    let mut x = 1;\n}\nfunc g() {}\nint *p = nullptr;\nok = a === b;";
+        let response = format!("```\n{code}\n```");
+        let extraction = extract(&response, "rust");
+        let block = &extraction.blocks[0];
+        assert_eq!(
+            block.detected.map(|d| (d.language, d.confidence)),
+            Some(("rust", 0.4))
+        );
+        assert_eq!(block.language, None);
+        assert_eq!(block.kind, BlockKind::Implementation);
+    }
+}
@@ -1,8 +1,26 @@
 //! Experiment Runner Library
 //!
-//! Provides LLM providers for the d-ary heap code generation research.
+//! Provides LLM providers for the d-ary heap code generation research, the
+//! batch configuration format read by `--config`, the study definitions read
+//! by `--study`, per-model pricing, code extraction from responses,
+//! validation of structured (JSON) answers, compile checks for generated
+//! code, syntax-aware test counting, embedding similarity of generated and
+//! reference code, aggregate reports over a results directory, the SQLite
+//! results database, and the canonical test corpus rendered into
+//! `test-corpus/` by `gen_corpus`.
 
+pub mod analysis;
 pub mod anthropic;
+pub mod batch;
+pub mod corpus;
+pub mod db;
+pub mod embedding;
+pub mod extract;
 pub mod lmstudio;
 pub mod mistral;
+pub mod pricing;
 pub mod provider;
+pub mod report;
+pub mod structured;
+pub mod study;
+pub mod toolchain;
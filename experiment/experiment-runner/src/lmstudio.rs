@@ -1,15 +1,25 @@
 //! LM Studio Provider (OpenAI-compatible local server)
 
-use crate::provider::{LlmProvider, LlmResponse, RequestConfig};
+use crate::provider::{split_think_tags, Embeddings, LlmProvider, LlmResponse, Message, RequestConfig};
+use crate::structured;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+/// How long `/models` may take to answer before LM Studio is reported down
+const HEALTH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 pub struct LmStudioProvider {
     base_url: String,
     client: reqwest::Client,
 }
 
+impl Default for LmStudioProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LmStudioProvider {
     pub fn new() -> Self {
         let base_url = std::env::var("LMSTUDIO_BASE_URL")
@@ -23,21 +33,70 @@ impl LmStudioProvider {
         Self { base_url, client }
     }
 
+    /// Server address, from `LMSTUDIO_BASE_URL`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Check if LM Studio is running and get the loaded model
     pub async fn get_loaded_model(&self) -> Result<String> {
+        self.loaded_models()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No model loaded in LM Studio"))
+    }
+
+    /// The loaded embedding model: the first whose id mentions "embed"
+    pub async fn get_embedding_model(&self) -> Result<String> {
+        self.loaded_models()
+            .await?
+            .into_iter()
+            .find(|id| id.contains("embed"))
+            .ok_or_else(|| anyhow!("No embedding model loaded in LM Studio"))
+    }
+
+    /// Ids of the models LM Studio serves (with just-in-time loading on,
+    /// every downloaded model)
+    pub async fn loaded_models(&self) -> Result<Vec<String>> {
         let url = format!("{}/models", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self
+            .client
+            .get(&url)
+            .timeout(HEALTH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| anyhow!("LM Studio not reachable at {} (is its server running?): {}", self.base_url, e))?;
 
         if !response.status().is_success() {
-            return Err(anyhow!("LM Studio not reachable at {}", self.base_url));
+            return Err(anyhow!("LM Studio not reachable at {} ({})", self.base_url, response.status()));
         }
 
         let models: ModelsResponse = response.json().await?;
-        models
-            .data
-            .first()
-            .map(|m| m.id.clone())
-            .ok_or_else(|| anyhow!("No model loaded in LM Studio"))
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
+
+    /// Check that LM Studio is up, serves a model, and serves each of
+    /// `models`, returning the models it serves
+    pub async fn check_models(&self, models: &[&str]) -> Result<Vec<String>> {
+        let loaded = self.loaded_models().await?;
+        if loaded.is_empty() {
+            return Err(anyhow!("No model loaded in LM Studio at {}", self.base_url));
+        }
+        let missing: Vec<&str> = models
+            .iter()
+            .copied()
+            .filter(|model| !loaded.iter().any(|id| id == model))
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!(
+                "Not loaded in LM Studio at {}: {} (loaded: {})",
+                self.base_url,
+                missing.join(", "),
+                loaded.join(", ")
+            ));
+        }
+        Ok(loaded)
     }
 }
 
@@ -48,6 +107,12 @@ struct ChatRequest {
     messages: Vec<ChatMessage>,
     max_tokens: u32,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -56,6 +121,20 @@ struct ChatMessage {
     content: String,
 }
 
+/// The conversation in OpenAI form, the system prompt as its first message
+fn chat_messages(messages: &[Message], config: &RequestConfig) -> Vec<ChatMessage> {
+    let system = config.system.iter().map(|system| ChatMessage {
+        role: "system".to_string(),
+        content: system.clone(),
+    });
+    system
+        .chain(messages.iter().map(|message| ChatMessage {
+            role: message.role.as_str().to_string(),
+            content: message.content.clone(),
+        }))
+        .collect()
+}
+
 #[derive(Deserialize)]
 struct ChatResponse {
     choices: Vec<Choice>,
@@ -67,17 +146,55 @@ struct ChatResponse {
 #[derive(Deserialize)]
 struct Choice {
     message: ResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct ResponseMessage {
     content: String,
+    /// Reasoning, when LM Studio separates it from the content
+    #[serde(default)]
+    reasoning_content: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct ChatUsage {
     prompt_tokens: usize,
     completion_tokens: usize,
+    #[serde(default)]
+    completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+#[derive(Deserialize)]
+struct CompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+    model: String,
+    #[serde(default)]
+    usage: Option<EmbeddingUsage>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingUsage {
+    prompt_tokens: usize,
 }
 
 #[derive(Deserialize)]
@@ -96,7 +213,7 @@ impl LlmProvider for LmStudioProvider {
         "loaded-model" // Will be replaced with actual model from /models endpoint
     }
 
-    async fn complete(&self, prompt: &str, config: &RequestConfig) -> Result<LlmResponse> {
+    async fn chat(&self, messages: &[Message], config: &RequestConfig) -> Result<LlmResponse> {
         // Get the actual loaded model name
         let model = match &config.model {
             Some(m) => m.clone(),
@@ -105,12 +222,12 @@ impl LlmProvider for LmStudioProvider {
 
         let request = ChatRequest {
             model: model.clone(),
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+            messages: chat_messages(messages, config),
             max_tokens: config.max_tokens,
             temperature: config.temperature,
+            top_p: config.top_p,
+            seed: config.seed,
+            response_format: config.structured.then(structured::response_format),
         };
 
         let url = format!("{}/chat/completions", self.base_url);
@@ -131,17 +248,29 @@ impl LlmProvider for LmStudioProvider {
 
         let result: ChatResponse = response.json().await?;
 
-        let content = result
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default();
+        let choice = result.choices.into_iter().next();
+        let truncated = choice.as_ref().and_then(|c| c.finish_reason.as_deref()) == Some("length");
+
+        // Reasoning arrives apart, or inline in <think> tags
+        let (content, thinking) = match choice.map(|c| c.message) {
+            Some(ResponseMessage {
+                content,
+                reasoning_content: Some(reasoning),
+            }) if !reasoning.is_empty() => (content, Some(reasoning)),
+            Some(message) => split_think_tags(message.content),
+            None => (String::new(), None),
+        };
 
         // LM Studio may not always return usage stats
         let (input_tokens, output_tokens) = result
             .usage
+            .as_ref()
             .map(|u| (u.prompt_tokens, u.completion_tokens))
             .unwrap_or((0, 0));
+        let thinking_tokens = result
+            .usage
+            .and_then(|u| u.completion_tokens_details)
+            .and_then(|details| details.reasoning_tokens);
 
         Ok(LlmResponse {
             content,
@@ -149,6 +278,43 @@ impl LlmProvider for LmStudioProvider {
             output_tokens,
             model: result.model,
             provider: "lmstudio".to_string(),
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+            thinking,
+            thinking_tokens,
+            truncated,
+        })
+    }
+
+    async fn embed(&self, inputs: &[&str], model: Option<&str>) -> Result<Embeddings> {
+        let model = match model {
+            Some(m) => m.to_string(),
+            None => self.get_embedding_model().await?,
+        };
+        let request = EmbeddingRequest { model: &model, input: inputs };
+
+        let url = format!("{}/embeddings", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("LM Studio error ({}): {}", status, error_text));
+        }
+
+        let mut result: EmbeddingResponse = response.json().await?;
+        result.data.sort_by_key(|data| data.index);
+        Ok(Embeddings {
+            vectors: result.data.into_iter().map(|data| data.embedding).collect(),
+            input_tokens: result.usage.map_or(0, |u| u.prompt_tokens),
+            model: result.model,
         })
     }
 }
@@ -3,41 +3,68 @@
 //! Usage:
 //!   cargo run -- --provider anthropic --condition baseline --language go
 //!   cargo run -- --provider lmstudio --condition baseline --language rust --dry-run
-
-mod anthropic;
-mod lmstudio;
-mod mistral;
-mod provider;
+//!   cargo run -- --config runs.toml
+//!   cargo run -- --study studies/test_mimicking.toml
 
 use anyhow::{anyhow, Result};
 use chrono::Utc;
-use clap::Parser;
-use provider::{LlmProvider, LlmResponse, RequestConfig};
+use clap::{Parser, Subcommand};
+use experiment_runner::analysis::{self, CodeAnalysis};
+use experiment_runner::batch::{self, BatchConfig, Matrix, RunSpec};
+use experiment_runner::db::{self, ResultsDb};
+use experiment_runner::embedding::{self, DocumentEmbedding, EmbeddingSimilarity};
+use experiment_runner::extract::{self, BlockKind, Detection};
+use experiment_runner::pricing::{self, CostBudget, Price};
+use experiment_runner::report::{self, ReportFormat, Summary};
+use experiment_runner::provider::{LlmProvider, LlmResponse, Message, RequestConfig};
+use experiment_runner::structured::{self, Validation};
+use experiment_runner::study::Study;
+use experiment_runner::toolchain::{self, CompileCheck, Sandbox, TestRun};
+use experiment_runner::{anthropic, lmstudio, mistral};
+use indicatif::{HumanDuration, MultiProgress, ProgressBar, ProgressStyle};
 use serde::Serialize;
 use std::borrow::Cow;
-use std::path::Path;
-use std::time::Instant;
+use std::collections::HashMap;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "experiment-runner")]
 #[command(about = "Run LLM experiments for d-ary heap code generation")]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Args {
-    /// LLM provider: anthropic, mistral, lmstudio
-    #[arg(short, long)]
-    provider: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Experimental condition: baseline, doc_guided, struct_guided, test_guided, combined
-    #[arg(short, long)]
-    condition: String,
+    /// LLM provider: anthropic, mistral, lmstudio (comma-separated with --matrix)
+    #[arg(short, long, required_unless_present_any = ["config", "study_run"])]
+    provider: Option<String>,
 
-    /// Target language: go, rust, cpp, typescript, zig
-    #[arg(short, long)]
-    language: String,
+    /// Experimental condition: baseline, doc_guided, struct_guided, test_guided, combined,
+    /// fim_guided (fill-in-the-middle, mistral only)
+    /// (comma-separated with --matrix)
+    #[arg(short, long, required_unless_present_any = ["config", "study_run"])]
+    condition: Option<String>,
 
-    /// Model override (uses provider default if not specified)
+    /// Target language: go, rust, cpp, typescript, zig (comma-separated with --matrix)
+    #[arg(short, long, required_unless_present_any = ["config", "study_run"])]
+    language: Option<String>,
+
+    /// Model override (uses provider default if not specified; comma-separated,
+    /// optionally `provider:model`, with --matrix)
     #[arg(short, long)]
     model: Option<String>,
 
+    /// Run every combination of the comma-separated --provider, --model,
+    /// --condition and --language values as a batch
+    #[arg(long, conflicts_with = "study_run")]
+    matrix: bool,
+
     /// Maximum tokens for response
     #[arg(long, default_value = "8192")]
     max_tokens: u32,
@@ -50,25 +77,469 @@ struct Args {
     #[arg(long)]
     base_dir: Option<String>,
 
+    /// Run the study defined in a TOML file across its models (see `study`
+    /// module docs)
+    #[arg(long, group = "study_run", conflicts_with_all = ["provider", "condition", "language", "model"])]
+    study: Option<String>,
+
     /// Run test-mimicking study across multiple Claude models
-    #[arg(long)]
+    /// (`--study studies/test_mimicking.toml`)
+    #[arg(long, group = "study_run", conflicts_with_all = ["provider", "condition", "language", "model"])]
     test_mimicking_study: bool,
+
+    /// Run the configuration N times (files suffixed `_r1`..`_rN`) and summarize the spread
+    #[arg(long, default_value = "1", conflicts_with = "study_run")]
+    replications: NonZeroU32,
+
+    /// Sampling temperatures to sweep, comma-separated (e.g. 0,0.3,0.7)
+    #[arg(long, value_delimiter = ',', default_value = "0", conflicts_with = "study_run")]
+    temperatures: Vec<f32>,
+
+    /// top_p values to sweep, comma-separated (default: provider default)
+    #[arg(long, value_delimiter = ',', conflicts_with = "study_run")]
+    top_p: Vec<f32>,
+
+    /// Extended-thinking budgets in tokens to sweep, comma-separated, 0 for
+    /// none (Anthropic; needs --temperatures 1)
+    #[arg(long, value_delimiter = ',', conflicts_with = "study_run")]
+    thinking_budget: Vec<u32>,
+
+    /// Sampling seed for providers that take one (Mistral, LM Studio);
+    /// replicate N is sent SEED + N - 1
+    #[arg(long, conflicts_with = "study_run")]
+    seed: Option<u64>,
+
+    /// File holding a system prompt to send ahead of the prompt
+    #[arg(long, conflicts_with = "study_run")]
+    system: Option<String>,
+
+    /// Follow-up turns returning compiler errors (and, with --run-tests, failing
+    /// tests) to the model for a fix; implies --compile-check
+    #[arg(long, default_value = "0", conflicts_with = "study_run")]
+    repair_turns: u32,
+
+    /// Ask for the answer as a JSON object (code, self-reported test count,
+    /// notes) validated against a schema, instead of fenced code
+    #[arg(long, conflicts_with = "study_run")]
+    structured: bool,
+
+    /// TOML file listing the runs of a batch (see `batch` module docs)
+    #[arg(long, conflicts_with_all = [
+        "provider", "condition", "language", "model", "study_run", "replications",
+        "temperatures", "top_p", "thinking_budget", "seed", "matrix", "system", "repair_turns",
+        "structured",
+    ])]
+    config: Option<String>,
+
+    /// Batch runs to execute concurrently (with --config or --matrix)
+    #[arg(long, default_value = "1")]
+    jobs: NonZeroUsize,
+
+    /// Maximum concurrent batch runs against any one provider (with --config or --matrix; default: --jobs)
+    #[arg(long)]
+    per_provider_limit: Option<NonZeroUsize>,
+
+    /// Skip runs whose results already exist (always on for --config and --matrix batches)
+    #[arg(long)]
+    skip_existing: bool,
+
+    /// Re-run and overwrite runs whose results already exist
+    #[arg(long, conflicts_with = "skip_existing")]
+    force: bool,
+
+    /// Budget in USD: refuse to start a run whose worst-case cost could exceed it
+    #[arg(long)]
+    max_cost: Option<f64>,
+
+    /// Refuse to send a prompt (with its system prompt) estimated above N
+    /// tokens, and end repair turns before the conversation grows past it
+    #[arg(long, value_name = "N")]
+    max_input_tokens: Option<usize>,
+
+    /// Cut a prompt over --max-input-tokens to fit, instead of refusing it
+    #[arg(long, requires = "max_input_tokens")]
+    truncate_prompt: bool,
+
+    /// Refuse any run whose --max-tokens exceeds N
+    #[arg(long, value_name = "N")]
+    max_output_tokens_hard: Option<u32>,
+
+    /// Continue a response cut off at --max-tokens with up to N further
+    /// requests, stitching the parts into one answer
+    #[arg(long, value_name = "N", default_value = "0")]
+    continuations: u32,
+
+    /// Compile-check the extracted code with the language toolchain and record the outcome
+    #[arg(long)]
+    compile_check: bool,
+
+    /// Run the tests contained in the extracted code (rust, go, zig) and record the counts
+    #[arg(long)]
+    run_tests: bool,
+
+    /// Where --run-tests executes the generated code
+    #[arg(long, value_enum, default_value = "local")]
+    sandbox: Sandbox,
+
+    /// Seconds before a --run-tests run is killed
+    #[arg(long, default_value = "300")]
+    test_timeout: u64,
+
+    /// Do not record runs in the results database (results/results.db)
+    #[arg(long)]
+    no_db: bool,
+
+    /// Cache the prompt on the provider (Anthropic), so repeated prompts are billed at a tenth of the input price
+    #[arg(long)]
+    prompt_cache: bool,
+
+    /// Save the model's reasoning, when it returns any, as `{prefix}_thinking.md`
+    #[arg(long)]
+    save_thinking: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Count the tests in every `*_code.*` file of a results directory, score
+    /// its similarity to the reference implementation, and write a
+    /// `*_analysis.json` file next to each
+    Analyze {
+        /// Results directory to analyze
+        #[arg(default_value = "results")]
+        dir: PathBuf,
+
+        /// Repository root holding the reference implementations
+        #[arg(long, default_value = "..")]
+        repo_root: PathBuf,
+
+        /// Also score the cosine similarity of code and reference embeddings
+        /// from this provider: mistral, lmstudio
+        #[arg(long, value_name = "PROVIDER")]
+        embeddings: Option<String>,
+
+        /// Embedding model (default: codestral-embed on Mistral, the loaded
+        /// embedding model on LM Studio)
+        #[arg(long, requires = "embeddings")]
+        embedding_model: Option<String>,
+    },
+    /// Join every run's meta JSON and test count into one table (one row per run)
+    Report {
+        /// Results directory to report on
+        #[arg(default_value = "results")]
+        dir: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "csv")]
+        format: ReportFormat,
+
+        /// Write the table to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Record every run of a results directory in its results database
+    /// (runs made before the database, or edited by hand)
+    Import {
+        /// Results directory to import
+        #[arg(default_value = "results")]
+        dir: PathBuf,
+    },
+    /// List the models LM Studio serves, checking that its server is up
+    Models {
+        /// Also check that these models are loaded
+        models: Vec<String>,
+    },
+    /// Run a SQL query against the results database (table `runs`)
+    Query {
+        /// SQL statement, e.g. "SELECT model, AVG(test_count) FROM runs GROUP BY model"
+        sql: String,
+
+        /// Results database
+        #[arg(long, default_value = "results/results.db")]
+        db: PathBuf,
+
+        /// Output format (default: an aligned table)
+        #[arg(long, value_enum)]
+        format: Option<ReportFormat>,
+    },
+}
+
+impl Args {
+    fn base_dir(&self) -> &Path {
+        self.base_dir.as_deref().map(Path::new).unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Settings shared by every run of an invocation; build once and clone,
+    /// so that all runs draw on the same `--max-cost` budget
+    fn run_options(&self) -> Result<RunOptions> {
+        let budget = match self.max_cost {
+            Some(limit) if !(limit.is_finite() && limit >= 0.0) => {
+                return Err(anyhow!("--max-cost must be a non-negative amount in USD"))
+            }
+            Some(limit) => Some(Arc::new(CostBudget::new(limit))),
+            None => None,
+        };
+        Ok(RunOptions {
+            base_dir: self.base_dir().to_path_buf(),
+            dry_run: self.dry_run,
+            skip_existing: !self.force && (self.skip_existing || self.config.is_some() || self.matrix),
+            budget,
+            compile_check: self.compile_check,
+            tests: self
+                .run_tests
+                .then(|| (self.sandbox, Duration::from_secs(self.test_timeout))),
+            record: !self.no_db,
+            prompt_cache: self.prompt_cache,
+            save_thinking: self.save_thinking,
+            max_input_tokens: self.max_input_tokens,
+            truncate_prompt: self.truncate_prompt,
+            max_output_tokens: self.max_output_tokens_hard,
+            continuations: self.continuations,
+            output: Output::Stdout,
+        })
+    }
+
+    /// The runs of `--matrix`: every combination of the comma-separated
+    /// CLI values
+    fn matrix(&self) -> Result<Vec<RunSpec>> {
+        let list = |value: &Option<String>, flag: &str| -> Result<Vec<String>> {
+            let value = value.as_deref().ok_or_else(|| anyhow!("--{} is required with --matrix", flag))?;
+            Ok(value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect())
+        };
+        Matrix {
+            providers: list(&self.provider, "provider")?,
+            models: match &self.model {
+                Some(_) => list(&self.model, "model")?,
+                None => Vec::new(),
+            },
+            conditions: list(&self.condition, "condition")?,
+            languages: list(&self.language, "language")?,
+            max_tokens: self.max_tokens,
+            temperatures: self.temperatures.clone(),
+            top_ps: self.top_p.clone(),
+            thinking_budgets: self.thinking_budget.clone(),
+            replications: self.replications.get(),
+            seed: self.seed,
+            system: self.system.clone(),
+            repair_turns: self.repair_turns,
+            structured: self.structured,
+        }
+        .runs()
+    }
+
+    /// The runs described by the CLI flags: one per `--temperatures` ×
+    /// `--top-p` × `--thinking-budget` value × replicate
+    fn run_specs(&self) -> Result<Vec<RunSpec>> {
+        let missing = |flag: &str| anyhow!("--{} is required without --config", flag);
+        let base = RunSpec {
+            provider: self.provider.clone().ok_or_else(|| missing("provider"))?,
+            condition: self.condition.clone().ok_or_else(|| missing("condition"))?,
+            language: self.language.clone().ok_or_else(|| missing("language"))?,
+            model: self.model.clone(),
+            max_tokens: self.max_tokens,
+            temperature: RequestConfig::default().temperature,
+            top_p: None,
+            thinking_budget: None,
+            replicate: None,
+            seed: None,
+            system: self.system.clone(),
+            repair_turns: self.repair_turns,
+            structured: self.structured,
+        };
+        let top_ps: Vec<Option<f32>> = if self.top_p.is_empty() {
+            vec![None]
+        } else {
+            self.top_p.iter().copied().map(Some).collect()
+        };
+        let thinking_budgets: Vec<Option<u32>> = if self.thinking_budget.is_empty() {
+            vec![None]
+        } else {
+            self.thinking_budget.iter().map(|&budget| (budget > 0).then_some(budget)).collect()
+        };
+        let replications = self.replications.get();
+
+        let mut specs = Vec::new();
+        for &temperature in &self.temperatures {
+            for &top_p in &top_ps {
+                batch::validate_sampling(temperature, top_p)?;
+                for &thinking_budget in &thinking_budgets {
+                    batch::validate_thinking(thinking_budget, self.max_tokens, temperature)?;
+                    for replicate in 1..=replications {
+                        specs.push(RunSpec {
+                            temperature,
+                            top_p,
+                            thinking_budget,
+                            replicate: (replications > 1).then_some(replicate),
+                            seed: self.seed.map(|seed| batch::replicate_seed(seed, Some(replicate))),
+                            ..base.clone()
+                        });
+                    }
+                }
+            }
+        }
+        Ok(specs)
+    }
+}
+
+/// File in the results directory listing, as a batch configuration, the runs
+/// a batch did not start (interrupted, out of credit or over budget)
+const REMAINING_RUNS_FILE: &str = "remaining_runs.toml";
+
+/// Study definition run by `--test-mimicking-study`
+const TEST_MIMICKING_STUDY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/studies/test_mimicking.toml");
+
+/// Settings that apply to every run, as opposed to the per-run `RunSpec`
+#[derive(Debug, Clone)]
+struct RunOptions {
+    base_dir: PathBuf,
+    dry_run: bool,
+    /// Return `RunStatus::AlreadyDone` instead of calling the API when the
+    /// run's meta file exists
+    skip_existing: bool,
+    /// Shared `--max-cost` budget, if any
+    budget: Option<Arc<CostBudget>>,
+    /// Run `toolchain::compile_check` on the extracted code
+    compile_check: bool,
+    /// Run `toolchain::run_tests` on the extracted code, with this sandbox
+    /// and timeout
+    tests: Option<(Sandbox, Duration)>,
+    /// Record completed runs in the results database
+    record: bool,
+    /// Set `RequestConfig::cache` on every request
+    prompt_cache: bool,
+    /// Save the reasoning a model returns apart from its answer
+    save_thinking: bool,
+    /// `--max-input-tokens`, and whether to truncate a prompt over it
+    max_input_tokens: Option<usize>,
+    truncate_prompt: bool,
+    /// `--max-output-tokens-hard`
+    max_output_tokens: Option<u32>,
+    /// `--continuations`: requests allowed per turn to continue a response
+    /// cut off at `max_tokens`
+    continuations: u32,
+    /// Where the run reports its steps
+    output: Output,
 }
 
-/// Models to test for the test-mimicking emergence study
-/// Format: (model_id, max_tokens)
-const TEST_MIMICKING_MODELS: &[(&str, u32)] = &[
-    // Already tested:
-    // ("claude-3-haiku-20240307", 4096),      // Haiku 3: 1,899 tokens, 0 tests
-    // ("claude-haiku-4-5-20251001", 8192),    // Haiku 4.5: 6,788 tokens, 22 tests
-    // ("claude-opus-4-5-20251101", 8192),     // Opus 4.5: 2,233 tokens, 0 tests
-    // ("claude-sonnet-4-20250514", 8192),     // Sonnet 4: 6,370 tokens, 22 tests (original)
+/// Where `run_experiment` reports: stdout, or in a batch the run's progress
+/// bar. A bar shows the latest step as its message and prints notes
+/// (verdicts, warnings, the dry-run prompt) above the bars; a hidden bar
+/// (stderr is not a terminal) prints everything, so logs keep every line.
+#[derive(Debug, Clone, Default)]
+enum Output {
+    #[default]
+    Stdout,
+    Bar(ProgressBar),
+}
 
-    // Remaining to test:
-    ("claude-opus-4-20250514", 8192),       // Opus 4 (May 2025)
-    ("claude-opus-4-1-20250805", 8192),     // Opus 4.1 (Aug 2025)
-    ("claude-sonnet-4-5-20250929", 8192),   // Sonnet 4.5 latest (Sep 2025)
-];
+impl Output {
+    /// Report a step of the run, superseded by the next one on a bar
+    fn step(&self, message: impl AsRef<str>) {
+        match self {
+            Output::Bar(bar) if !bar.is_hidden() => bar.set_message(message.as_ref().trim().to_string()),
+            _ => println!("{}", message.as_ref()),
+        }
+    }
+
+    /// Report something worth keeping, prefixed with the run's label on a bar
+    fn note(&self, message: impl AsRef<str>) {
+        match self {
+            Output::Bar(bar) if !bar.is_hidden() => bar.println(format!("{}: {}", bar.prefix(), message.as_ref().trim())),
+            _ => println!("{}", message.as_ref()),
+        }
+    }
+}
+
+/// What `run_experiment` did with a run; `run` is the file prefix of its
+/// results
+#[derive(Debug, Clone, PartialEq)]
+enum RunStatus {
+    /// Results saved (`run` is `None` for a dry run); the cost in USD is
+    /// `None` for models missing from the pricing table
+    Completed { cost_usd: Option<f64>, run: Option<String> },
+    AlreadyDone { run: String },
+    /// Not started: its worst-case cost could exceed `--max-cost`
+    OverBudget,
+    /// `--dry-run`: nothing sent, only projected
+    DryRun(Estimate),
+}
+
+/// Projected size and cost of a run, from the prompt alone
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Estimate {
+    /// Heuristic token count of the prompt (`pricing::estimate_tokens`)
+    input_tokens: usize,
+    max_output_tokens: usize,
+    /// Cost of the input, `None` for models missing from the pricing table
+    input_cost_usd: Option<f64>,
+    /// Cost if the whole `max_tokens` of output is used
+    max_cost_usd: Option<f64>,
+}
+
+impl Estimate {
+    fn new(provider: &str, model: &str, prompt: &str, max_tokens: u32) -> Self {
+        let input_tokens = pricing::estimate_tokens(prompt);
+        let max_output_tokens = max_tokens as usize;
+        let price = pricing::price_for(provider, model);
+        Self {
+            input_tokens,
+            max_output_tokens,
+            input_cost_usd: price.map(|price| price.cost(input_tokens, 0)),
+            max_cost_usd: price.map(|price| price.cost(input_tokens, max_output_tokens)),
+        }
+    }
+}
+
+/// Sum of the `Estimate`s of a dry-run batch, sweep or study
+#[derive(Debug, Default)]
+struct Projection {
+    runs: usize,
+    input_tokens: usize,
+    max_output_tokens: usize,
+    input_cost_usd: f64,
+    max_cost_usd: f64,
+    /// Runs on models missing from the pricing table (not in the costs)
+    unpriced: usize,
+}
+
+impl Projection {
+    fn add(&mut self, estimate: &Estimate) {
+        self.runs += 1;
+        self.input_tokens += estimate.input_tokens;
+        self.max_output_tokens += estimate.max_output_tokens;
+        match (estimate.input_cost_usd, estimate.max_cost_usd) {
+            (Some(input), Some(max)) => {
+                self.input_cost_usd += input;
+                self.max_cost_usd += max;
+            }
+            _ => self.unpriced += 1,
+        }
+    }
+
+    fn print(&self) {
+        println!(
+            "Projected: {} runs, ~{} input tokens, at most {} output tokens",
+            self.runs,
+            human_count(self.input_tokens),
+            human_count(self.max_output_tokens)
+        );
+        println!(
+            "Projected cost: ~${:.2} of input, at most ${:.2} with a full --max-tokens of output",
+            self.input_cost_usd, self.max_cost_usd
+        );
+        if self.unpriced > 0 {
+            println!("  (+ {} runs on models missing from the pricing table)", self.unpriced);
+        }
+    }
+}
+
+/// `3100000` as `3.1M`, `42000` as `42.0k`
+fn human_count(n: usize) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.1}k", n as f64 / 1e3),
+        _ => format!("{:.1}M", n as f64 / 1e6),
+    }
+}
 
 /// Check if an error indicates credit exhaustion
 fn is_credit_error(err: &anyhow::Error) -> bool {
@@ -76,7 +547,7 @@ fn is_credit_error(err: &anyhow::Error) -> bool {
 }
 
 #[derive(Serialize)]
-struct ExperimentResult {
+struct ExperimentResult<'a> {
     experiment_id: String,
     condition: String,
     language: String,
@@ -85,14 +556,250 @@ struct ExperimentResult {
     timestamp: String,
     input_tokens: usize,
     output_tokens: usize,
+    /// Of `input_tokens`, those written to and read from the prompt cache
+    /// (`--prompt-cache`)
+    #[serde(skip_serializing_if = "is_zero")]
+    cache_write_tokens: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    cache_read_tokens: usize,
+    /// Of `output_tokens`, those spent thinking: as reported by the
+    /// provider, or estimated (`thinking_tokens_estimated`) as the output
+    /// tokens the answer does not account for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_tokens: Option<usize>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    thinking_tokens_estimated: bool,
+    /// Whether the (last) answer still ended at `max_tokens`, and the
+    /// requests it was stitched from (`--continuations`)
+    truncated: bool,
+    parts: u32,
     elapsed_ms: u128,
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    /// Extended-thinking budget, with `--thinking-budget`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_budget: Option<u32>,
+    /// Sampling seed sent to the provider, with `--seed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replicate: Option<u32>,
+    /// List-price cost in USD, absent for models missing from the pricing table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost_usd: Option<f64>,
+    /// Toolchain verdict on the extracted code, with `--compile-check`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compile: Option<CompileCheck>,
+    /// Outcome of the extracted code's own tests, with `--run-tests`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tests: Option<TestRun>,
+    /// Schema check of the JSON answer, with `--structured`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured: Option<Validation>,
+    /// Language guessed from the code's content, when its block named none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detected_language: Option<Detection>,
+    /// File of the system prompt, with `--system`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_prompt: Option<String>,
+    /// How the prompt was cut to fit `--max-input-tokens`, with
+    /// `--truncate-prompt`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_truncated: Option<PromptTruncation>,
+    /// Every turn of a `--repair-turns` run; the fields above are the totals
+    /// and the last turn's verdicts
+    #[serde(skip_serializing_if = "<[Turn]>::is_empty")]
+    turns: &'a [Turn],
+    /// The fenced blocks of the (last) response, when there are several or
+    /// one is unclosed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    blocks: Vec<BlockEntry>,
 }
 
-fn get_provider(name: &str) -> Result<Box<dyn LlmProvider>> {
+/// A fenced block of the response, as classified by `extract`
+#[derive(Debug, Serialize)]
+struct BlockEntry {
+    index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'static str>,
+    /// Language guessed from the content, for an untagged, unnamed block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detected: Option<Detection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+    kind: BlockKind,
+    lines: usize,
+    closed: bool,
+    /// Whether this block is the run's code
+    primary: bool,
+    /// Where the block was saved, for blocks other than the run's code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+}
+
+/// A prompt cut to fit `--max-input-tokens`
+#[derive(Debug, Clone, Serialize)]
+struct PromptTruncation {
+    /// Estimated tokens of the prompt as loaded
+    estimated_tokens: usize,
+    /// Estimated tokens of the prompt as sent
+    kept_tokens: usize,
+    /// Characters cut from the middle of the prompt
+    removed_chars: usize,
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+fn is_one(count: &u32) -> bool {
+    *count == 1
+}
+
+/// One request of a run and the checks of the code it returned
+#[derive(Debug, Serialize)]
+struct Turn {
+    turn: u32,
+    input_tokens: usize,
+    output_tokens: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    cache_write_tokens: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    cache_read_tokens: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_tokens: Option<usize>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    thinking_tokens_estimated: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    #[serde(skip_serializing_if = "is_one")]
+    parts: u32,
+    elapsed_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compile: Option<CompileCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tests: Option<TestRun>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured: Option<Validation>,
+    /// The message that asked for this turn
+    #[serde(skip)]
+    prompt: String,
+    #[serde(skip)]
+    response: LlmResponse,
+    #[serde(skip)]
+    code: String,
+}
+
+/// A request's worst-case cost against `--max-cost`
+enum Reservation<'a> {
+    /// No `--max-cost`
+    Unlimited,
+    /// Reserved: settle it with the actual cost
+    Reserved(&'a CostBudget, f64),
+    /// Not reserved: it could exceed what is left of the budget
+    Refused(&'a CostBudget, f64),
+}
+
+/// Reserve the worst-case cost of a request against `--max-cost`: every
+/// byte of its input a token, and the full `max_tokens` of output
+fn reserve(options: &RunOptions, price: Option<Price>, input_bytes: usize, max_tokens: u32) -> Reservation<'_> {
+    let (Some(budget), Some(price)) = (options.budget.as_deref(), price) else {
+        return Reservation::Unlimited;
+    };
+    // A cache write costs more than plain input
+    let cache_writes = if options.prompt_cache { input_bytes } else { 0 };
+    let estimate = price.cached_cost(input_bytes, cache_writes, 0, max_tokens as usize);
+    if budget.try_reserve(estimate) {
+        Reservation::Reserved(budget, estimate)
+    } else {
+        Reservation::Refused(budget, estimate)
+    }
+}
+
+/// Normalize the provider of `spec`, checking it supports the features the
+/// run asks for
+fn check_provider(spec: &RunSpec) -> Result<&'static str> {
+    let provider = normalize_provider(&spec.provider)?;
+    if spec.thinking_budget.is_some() && provider != "anthropic" {
+        return Err(anyhow!(
+            "A thinking budget is only supported on anthropic, not {} (reasoning models elsewhere think unasked)",
+            spec.provider
+        ));
+    }
+    if spec.seed.is_some() && provider == "anthropic" {
+        return Err(anyhow!("A seed is not supported on anthropic, whose API samples without one"));
+    }
+    if spec.structured && spec.thinking_budget.is_some() {
+        return Err(anyhow!(
+            "Structured output and a thinking budget cannot be combined (anthropic does not force a tool call while thinking)"
+        ));
+    }
+    if normalize_condition(&spec.condition)? == "fim_guided" {
+        if provider != "mistral" {
+            return Err(anyhow!(
+                "The fim_guided condition needs a fill-in-the-middle endpoint, only on mistral, not {}",
+                spec.provider
+            ));
+        }
+        if spec.system.is_some() || spec.repair_turns > 0 || spec.structured {
+            return Err(anyhow!(
+                "The fim_guided condition is a single completion: no system prompt, repair turns or structured output"
+            ));
+        }
+    }
+    Ok(provider)
+}
+
+/// Refuse a run whose `max_tokens` exceeds `--max-output-tokens-hard`
+fn check_output_limit(spec: &RunSpec, limit: Option<u32>) -> Result<()> {
+    match limit {
+        Some(limit) if spec.max_tokens > limit => Err(anyhow!(
+            "max_tokens {} exceeds --max-output-tokens-hard {} ({}_{})",
+            spec.max_tokens,
+            limit,
+            spec.condition,
+            spec.language
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Marker left where `truncate_middle` cut text
+const TRUNCATION_MARKER: &str = "[... {} characters cut to fit --max-input-tokens ...]";
+
+/// `text` cut to at most `max_chars` characters by removing whole lines from
+/// its middle, where a prompt's `{TEST_CODE}` lands, so that the
+/// requirements before it and the closing instruction after it survive;
+/// also returns the number of characters removed
+fn truncate_middle(text: &str, max_chars: usize) -> (String, usize) {
+    let total = text.chars().count();
+    if total <= max_chars {
+        return (text.to_string(), 0);
+    }
+    // Room for the marker, then three quarters of the rest for the head
+    let keep = max_chars.saturating_sub(TRUNCATION_MARKER.len() + 8);
+    let head_chars = keep * 3 / 4;
+    let tail_chars = keep - head_chars;
+    let byte_at = |n: usize| text.char_indices().nth(n).map_or(text.len(), |(i, _)| i);
+    let head_end = byte_at(head_chars);
+    let head_end = text[..head_end].rfind('\n').map_or(head_end, |i| i + 1);
+    let tail_start = byte_at(total - tail_chars);
+    let tail_start = text[tail_start..].find('\n').map_or(tail_start, |i| tail_start + i + 1);
+    let removed = text[head_end..tail_start].chars().count();
+    let marker = TRUNCATION_MARKER.replace("{}", &removed.to_string());
+    (format!("{}{}\n{}", &text[..head_end], marker, &text[tail_start..]), removed)
+}
+
+fn normalize_provider(name: &str) -> Result<&'static str> {
     match name.to_lowercase().as_str() {
-        "anthropic" | "claude" => Ok(Box::new(anthropic::AnthropicProvider::new()?)),
-        "mistral" => Ok(Box::new(mistral::MistralProvider::new()?)),
-        "lmstudio" | "lm-studio" => Ok(Box::new(lmstudio::LmStudioProvider::new())),
+        "anthropic" | "claude" => Ok("anthropic"),
+        "mistral" => Ok("mistral"),
+        "lmstudio" | "lm-studio" => Ok("lmstudio"),
         _ => Err(anyhow!(
             "Unknown provider: {}. Valid: anthropic, mistral, lmstudio",
             name
@@ -100,6 +807,44 @@ fn get_provider(name: &str) -> Result<Box<dyn LlmProvider>> {
     }
 }
 
+/// Check that LM Studio is up and serves every model `specs` run on it, so
+/// that a local batch fails before its first run rather than midway
+async fn check_local_models(specs: &[RunSpec]) -> Result<()> {
+    let mut local = false;
+    let mut models: Vec<&str> = Vec::new();
+    for spec in specs {
+        if normalize_provider(&spec.provider)? != "lmstudio" {
+            continue;
+        }
+        local = true;
+        if let Some(model) = spec.model.as_deref().filter(|model| !models.contains(model)) {
+            models.push(model);
+        }
+    }
+    if local {
+        lmstudio::LmStudioProvider::new().check_models(&models).await?;
+    }
+    Ok(())
+}
+
+fn get_provider(name: &str) -> Result<Box<dyn LlmProvider>> {
+    match normalize_provider(name)? {
+        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new()?)),
+        "mistral" => Ok(Box::new(mistral::MistralProvider::new()?)),
+        _ => Ok(Box::new(lmstudio::LmStudioProvider::new())),
+    }
+}
+
+/// Model a provider uses when no `--model` is given, known without
+/// connecting to it
+fn default_model(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => anthropic::DEFAULT_MODEL,
+        "mistral" => mistral::DEFAULT_MODEL,
+        _ => "loaded-model",
+    }
+}
+
 fn get_file_extension(language: &str) -> &'static str {
     match language {
         "go" => "go",
@@ -125,6 +870,12 @@ fn sanitize_model_name(model: &str) -> Cow<'_, str> {
     }
 }
 
+/// Common prefix of a run's result files:
+/// `{condition}_{language}_{model}{suffix}`, see [`RunSpec::suffix`]
+fn file_prefix(condition: &str, language: &str, model: &str, spec: &RunSpec) -> String {
+    format!("{}_{}_{}{}", condition, language, sanitize_model_name(model), spec.suffix())
+}
+
 fn normalize_language(language: &str) -> Result<&'static str> {
     match language.to_lowercase().as_str() {
         "go" => Ok("go"),
@@ -146,8 +897,9 @@ fn normalize_condition(condition: &str) -> Result<&'static str> {
         "struct_guided" | "structguided" | "c3" => Ok("struct_guided"),
         "test_guided" | "testguided" | "c4" => Ok("test_guided"),
         "combined" | "c5" => Ok("combined"),
+        "fim_guided" | "fimguided" | "fim" | "c6" => Ok("fim_guided"),
         _ => Err(anyhow!(
-            "Unknown condition: {}. Valid: baseline, doc_guided, struct_guided, test_guided, combined",
+            "Unknown condition: {}. Valid: baseline, doc_guided, struct_guided, test_guided, combined, fim_guided",
             condition
         )),
     }
@@ -242,28 +994,8 @@ fn load_prompt(base_dir: &Path, condition: &str, language: &str) -> Result<Strin
     // For test_guided and combined, use the generic "### Prompt Text" template
     // and inject test code via {TEST_CODE} placeholder
     if condition == "test_guided" || condition == "combined" {
-        if let Some(start) = content.find("### Prompt Text") {
-            let section = &content[start..];
-            if let Some(code_start) = section.find("```") {
-                let after_backticks = &section[code_start + 3..];
-                if let Some(newline_pos) = after_backticks.find('\n') {
-                    let code_content = &after_backticks[newline_pos + 1..];
-                    if let Some(code_end) = code_content.find("```") {
-                        let mut template = code_content[..code_end].trim_end().to_string();
-
-                        // Replace {LANGUAGE} placeholder
-                        template = template.replace("{LANGUAGE}", &cap_lang);
-
-                        // Inject test code
-                        if template.contains("{TEST_CODE}") {
-                            let test_code = load_test_code(base_dir, language)?;
-                            template = template.replace("{TEST_CODE}", &test_code);
-                        }
-
-                        return Ok(template);
-                    }
-                }
-            }
+        if let Some(template) = section_code(&content, "### Prompt Text") {
+            return fill_template(base_dir, template, language);
         }
         return Err(anyhow!(
             "Could not find ### Prompt Text section for {} in {}",
@@ -277,21 +1009,8 @@ fn load_prompt(base_dir: &Path, condition: &str, language: &str) -> Result<Strin
     let headers = [format!("### {}", cap_lang), format!("## {}", cap_lang)];
 
     for language_header in &headers {
-        if let Some(start) = content.find(language_header.as_str()) {
-            let section = &content[start..];
-            // Find the code block - handle both ```\n and ```\r\n
-            if let Some(code_start) = section.find("```") {
-                let after_backticks = &section[code_start + 3..];
-                // Skip to the newline after ```
-                if let Some(newline_pos) = after_backticks.find('\n') {
-                    let code_content = &after_backticks[newline_pos + 1..];
-                    // Find closing ``` (could be on its own line)
-                    if let Some(code_end) = code_content.find("```") {
-                        let extracted = code_content[..code_end].trim_end();
-                        return Ok(extracted.to_string());
-                    }
-                }
-            }
+        if let Some(extracted) = section_code(&content, language_header) {
+            return Ok(extracted.to_string());
         }
     }
 
@@ -303,6 +1022,49 @@ fn load_prompt(base_dir: &Path, condition: &str, language: &str) -> Result<Strin
     ))
 }
 
+/// The code block following `header` in a prompt file, without its trailing
+/// whitespace
+fn section_code<'a>(content: &'a str, header: &str) -> Option<&'a str> {
+    let section = &content[content.find(header)?..];
+    // Skip the info string, whether the fence ends in \n or \r\n
+    let after_backticks = &section[section.find("```")? + 3..];
+    let code_content = &after_backticks[after_backticks.find('\n')? + 1..];
+    // The closing ``` may be on its own line
+    let code_end = code_content.find("```")?;
+    Some(code_content[..code_end].trim_end())
+}
+
+/// `template` with its `{LANGUAGE}` and `{TEST_CODE}` placeholders filled in
+fn fill_template(base_dir: &Path, template: &str, language: &str) -> Result<String> {
+    let mut template = template.replace("{LANGUAGE}", &capitalize(language));
+    if template.contains("{TEST_CODE}") {
+        let test_code = load_test_code(base_dir, language)?;
+        template = template.replace("{TEST_CODE}", &test_code);
+    }
+    Ok(template)
+}
+
+/// Line standing for the model's completion in the saved prompt of a
+/// fill-in-the-middle run
+const FIM_MARKER: &str = "<FILL_IN_THE_MIDDLE>";
+
+/// Prefix and suffix of the fill-in-the-middle condition, from the
+/// `### Prefix Text` and `### Suffix Text` blocks of `prompts/fim_guided.md`
+fn load_fim_prompt(base_dir: &Path, language: &str) -> Result<(String, String)> {
+    let prompt_file = base_dir.join("prompts").join("fim_guided.md");
+    let content = std::fs::read_to_string(&prompt_file)
+        .map_err(|e| anyhow!("Failed to read prompt file {}: {}", prompt_file.display(), e))?;
+    let part = |header: &str| {
+        section_code(&content, header)
+            .ok_or_else(|| anyhow!("Could not find {} section in {}", header, prompt_file.display()))
+            .and_then(|template| fill_template(base_dir, template, language))
+    };
+    // The completion starts on a line of its own
+    let prefix = format!("{}\n", part("### Prefix Text")?);
+    let suffix = part("### Suffix Text")?;
+    Ok((prefix, suffix))
+}
+
 fn capitalize(s: &str) -> String {
     // Handle special cases for language names in prompt files
     match s {
@@ -318,116 +1080,567 @@ fn capitalize(s: &str) -> String {
     }
 }
 
-fn extract_code<'a>(response: &'a str, language: &str) -> Cow<'a, str> {
-    // Try to find code block with language tag - use static arrays to avoid heap allocation
-    let lang_tags: &[&str] = match language {
-        "go" => &["```go", "```golang"],
-        "rust" => &["```rust", "```rs"],
-        "cpp" => &["```cpp", "```c++", "```hpp"],
-        "typescript" => &["```typescript", "```ts"],
-        "zig" => &["```zig"],
-        _ => &["```"],
+/// Run `toolchain::compile_check` on `code`; a missing toolchain skips the check
+async fn compile_code(language: &'static str, code: &str, output: &Output) -> Result<Option<CompileCheck>> {
+    let source = code.to_string();
+    Ok(match tokio::task::spawn_blocking(move || toolchain::compile_check(language, &source)).await? {
+        Ok(check) => {
+            output.note(format!(
+                "Compile check ({}): {} in {} ms",
+                check.command,
+                if check.success { "passed" } else { "FAILED" },
+                check.elapsed_ms
+            ));
+            Some(check)
+        }
+        Err(e) => {
+            output.note(format!("⚠️  Compile check skipped: {}", e));
+            None
+        }
+    })
+}
+
+/// Run `toolchain::run_tests` on `code`; a missing toolchain skips the run
+async fn test_code(
+    language: &'static str,
+    code: &str,
+    sandbox: Sandbox,
+    timeout: Duration,
+    output: &Output,
+) -> Result<Option<TestRun>> {
+    let source = code.to_string();
+    Ok(
+        match tokio::task::spawn_blocking(move || toolchain::run_tests(language, &source, sandbox, timeout)).await? {
+            Ok(run) => {
+                output.note(format!(
+                    "Tests ({}): {} passed, {} failed{} in {} ms",
+                    run.command,
+                    run.passed,
+                    run.failed,
+                    if run.timed_out { ", TIMED OUT" } else { "" },
+                    run.elapsed_ms
+                ));
+                Some(run)
+            }
+            Err(e) => {
+                output.note(format!("⚠️  Test run skipped: {}", e));
+                None
+            }
+        },
+    )
+}
+
+/// The follow-up message of a repair turn: the compiler errors, else the
+/// failing tests' output; `None` when the code passed every check that ran
+fn repair_prompt(language: &str, compile: Option<&CompileCheck>, tests: Option<&TestRun>) -> Option<String> {
+    let (problem, command, log) = match (compile, tests) {
+        (Some(check), _) if !check.success => ("does not compile", &check.command, &check.diagnostics),
+        (_, Some(run)) if run.timed_out => ("compiles, but its tests did not finish in time", &run.command, &run.output),
+        (_, Some(run)) if !run.success || run.failed > 0 => ("compiles, but its tests fail", &run.command, &run.output),
+        _ => return None,
     };
+    Some(format!(
+        "Your code {}. `{}` reports:\n\n```\n{}\n```\n\nFix the problems and reply with the complete corrected {} file in a single code block.",
+        problem,
+        command,
+        log.trim_end(),
+        language
+    ))
+}
 
-    for tag in lang_tags {
-        if let Some(start) = response.find(tag) {
-            let code_start = start + tag.len();
-            let code_content = &response[code_start..];
-            let code_content = code_content.trim_start_matches('\n');
-            if let Some(end) = code_content.find("\n```") {
-                return Cow::Borrowed(&code_content[..end]);
+async fn run_experiment(spec: &RunSpec, options: &RunOptions) -> Result<RunStatus> {
+    let base_dir = options.base_dir.as_path();
+    let output = &options.output;
+    check_provider(spec)?;
+    check_output_limit(spec, options.max_output_tokens)?;
+    let condition = normalize_condition(&spec.condition)?;
+    let language = normalize_language(&spec.language)?;
+
+    output.step(format!("=== Experiment: {}_{} ===", condition, language));
+    output.step(format!("Provider: {}", spec.provider));
+    if !spec.sampling_suffix().is_empty() {
+        output.step(format!("Sampling: {}", spec.sampling()));
+    }
+    if let Some(seed) = spec.seed {
+        output.step(format!("Seed: {}", seed));
+    }
+    output.step(format!("Base dir: {}", base_dir.display()));
+
+    // Load prompt; a fill-in-the-middle run sends a prefix and a suffix,
+    // saved around a marker where the completion goes
+    let fim = match condition {
+        "fim_guided" => Some(load_fim_prompt(base_dir, language)?),
+        _ => None,
+    };
+    let mut prompt = match &fim {
+        Some((prefix, suffix)) => format!("{}{}\n{}", prefix, FIM_MARKER, suffix),
+        None => load_prompt(base_dir, condition, language)?,
+    };
+    output.step(format!("Prompt loaded ({} chars)", prompt.len()));
+    if spec.structured {
+        prompt = format!("{}\n\n{}", prompt.trim_end(), structured::INSTRUCTIONS);
+        output.step("Structured output: JSON answer validated against the schema");
+    }
+    let system = match &spec.system {
+        Some(path) => {
+            let system = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("Failed to read system prompt {}: {}", path, e))?;
+            output.step(format!("System prompt loaded from {} ({} chars)", path, system.len()));
+            Some(system)
+        }
+        None => None,
+    };
+    if spec.repair_turns > 0 {
+        output.step(format!("Repair turns: up to {}", spec.repair_turns));
+    }
+    if options.continuations > 0 {
+        output.step(format!("Continuations: up to {} per response", options.continuations));
+    }
+
+    // --max-input-tokens guards against an accidentally huge prompt (a
+    // runaway {TEST_CODE}): refused, or cut in the middle with --truncate-prompt
+    let mut prompt_truncated = None;
+    if let Some(limit) = options.max_input_tokens {
+        let system_tokens = system.as_deref().map_or(0, pricing::estimate_tokens);
+        let estimated_tokens = system_tokens + pricing::estimate_tokens(&prompt);
+        if estimated_tokens > limit {
+            if system_tokens >= limit {
+                return Err(anyhow!(
+                    "The system prompt alone (~{} tokens) exceeds --max-input-tokens {}",
+                    system_tokens,
+                    limit
+                ));
+            }
+            if !options.truncate_prompt || fim.is_some() {
+                return Err(anyhow!(
+                    "Prompt of ~{} tokens exceeds --max-input-tokens {}{}",
+                    estimated_tokens,
+                    limit,
+                    if fim.is_some() {
+                        " (a fill-in-the-middle prompt cannot be truncated)"
+                    } else {
+                        " (--truncate-prompt cuts it to fit)"
+                    }
+                ));
             }
+            let (cut, removed_chars) = truncate_middle(&prompt, (limit - system_tokens) * pricing::CHARS_PER_TOKEN);
+            prompt = cut;
+            let kept_tokens = system_tokens + pricing::estimate_tokens(&prompt);
+            output.note(format!(
+                "⚠️  Prompt truncated to fit --max-input-tokens {}: ~{} → ~{} tokens, {} characters cut",
+                limit, estimated_tokens, kept_tokens, removed_chars
+            ));
+            prompt_truncated = Some(PromptTruncation {
+                estimated_tokens,
+                kept_tokens,
+                removed_chars,
+            });
         }
     }
 
-    // Fallback: try generic code block
-    if let Some(start) = response.find("```\n") {
-        let code_content = &response[start + 4..];
-        if let Some(end) = code_content.find("\n```") {
-            return Cow::Borrowed(&code_content[..end]);
+    if options.dry_run {
+        if let Some(system) = &system {
+            output.note(format!("\n--- DRY RUN: System prompt ---\n\n{}\n\n--- End of system prompt ---", system));
         }
+        output.note(format!("\n--- DRY RUN: Prompt ---\n\n{}\n\n--- End of prompt ---", prompt));
+
+        // The first turn only: repair turns depend on the response
+        let provider = normalize_provider(&spec.provider)?;
+        let model_name = match (&spec.model, &fim) {
+            (Some(model), _) => model.as_str(),
+            (None, Some(_)) => mistral::FIM_MODEL,
+            (None, None) => default_model(provider),
+        };
+        let input = format!("{}{}", system.as_deref().unwrap_or_default(), prompt);
+        let estimate = Estimate::new(provider, model_name, &input, spec.max_tokens);
+        match (estimate.input_cost_usd, estimate.max_cost_usd) {
+            (Some(input), Some(max)) => output.note(format!(
+                "Estimated input: ~{} tokens on {}; cost ~${:.4}, at most ${:.4} with {} output tokens",
+                estimate.input_tokens, model_name, input, max, spec.max_tokens
+            )),
+            _ => output.note(format!(
+                "Estimated input: ~{} tokens on {} (no price in src/pricing.rs)",
+                estimate.input_tokens, model_name
+            )),
+        }
+        return Ok(RunStatus::DryRun(estimate));
     }
 
-    // Last resort: return full response (borrowed, no allocation)
-    Cow::Borrowed(response)
-}
+    // Create provider and send request
+    let provider = get_provider(&spec.provider)?;
+    let model_name = match (&spec.model, &fim) {
+        (Some(model), _) => model.as_str(),
+        (None, Some(_)) => provider.fim_model().unwrap_or(provider.default_model()),
+        (None, None) => provider.default_model(),
+    };
+    output.step(format!("Using model: {}", model_name));
 
-async fn run_experiment(args: Args) -> Result<()> {
-    let condition = normalize_condition(&args.condition)?;
-    let language = normalize_language(&args.language)?;
+    // Results are named after the model the provider reports, which for
+    // aliases can differ from the requested name; a resumed run is matched
+    // on the requested name.
+    let results_dir = base_dir.join("results");
+    if options.skip_existing {
+        let run = file_prefix(condition, language, model_name, spec);
+        let meta_file = results_dir.join(format!("{}_meta.json", run));
+        if meta_file.exists() {
+            output.note(format!("Skipping: {} exists (use --force to re-run)", meta_file.display()));
+            return Ok(RunStatus::AlreadyDone { run });
+        }
+    }
 
-    // Determine base directory
-    let base_dir = args
-        .base_dir
-        .as_deref()
-        .map(Path::new)
-        .unwrap_or_else(|| Path::new("."));
+    let config = RequestConfig {
+        model: spec.model.clone(),
+        max_tokens: spec.max_tokens,
+        temperature: spec.temperature,
+        top_p: spec.top_p,
+        system: system.clone(),
+        cache: options.prompt_cache,
+        thinking_budget: spec.thinking_budget,
+        seed: spec.seed,
+        structured: spec.structured,
+    };
+    let price = pricing::price_for(normalize_provider(&spec.provider)?, model_name);
+    if options.budget.is_some() && price.is_none() {
+        return Err(anyhow!(
+            "No price for {} on {}; cannot enforce --max-cost (add it to src/pricing.rs)",
+            model_name,
+            spec.provider
+        ));
+    }
+    let cost_of = |r: &LlmResponse| {
+        pricing::price_for(&r.provider, &r.model)
+            .or(price)
+            .map(|p| p.cached_cost(r.input_tokens, r.cache_write_tokens, r.cache_read_tokens, r.output_tokens))
+    };
+    let check_code = options.compile_check || spec.repair_turns > 0;
+
+    // One turn per request: the prompt, then while the code fails its checks
+    // and --repair-turns allow, the compiler errors or failing tests
+    let mut messages = vec![Message::user(prompt.as_str())];
+    let mut turns: Vec<Turn> = Vec::new();
+    loop {
+        let number = turns.len() as u32 + 1;
+
+        // Reserve the worst case against --max-cost: every byte of the
+        // conversation a token, and the full max_tokens of output
+        let input_bytes = config.system.as_ref().map_or(0, String::len)
+            + messages.iter().map(|message| message.content.len()).sum::<usize>();
+        if let Some(limit) = options.max_input_tokens.filter(|_| number > 1) {
+            let tokens = config.system.as_deref().map_or(0, pricing::estimate_tokens)
+                + messages.iter().map(|message| pricing::estimate_tokens(&message.content)).sum::<usize>();
+            if tokens > limit {
+                output.note(format!(
+                    "Repair stopped: the conversation (~{} tokens) would exceed --max-input-tokens {}",
+                    tokens, limit
+                ));
+                break;
+            }
+        }
+        let reservation = reserve(options, price, input_bytes, spec.max_tokens);
+        if let Reservation::Refused(budget, estimate) = reservation {
+            output.note(format!(
+                "{}: worst-case cost ${:.4} could exceed --max-cost ${:.2} (spent ${:.4})",
+                if number == 1 { "Not started" } else { "Repair stopped" },
+                estimate,
+                budget.limit(),
+                budget.spent()
+            ));
+            if number == 1 {
+                return Ok(RunStatus::OverBudget);
+            }
+            break;
+        }
 
-    println!("=== Experiment: {}_{} ===", condition, language);
-    println!("Provider: {}", args.provider);
-    println!("Base dir: {}", base_dir.display());
+        if number == 1 {
+            output.step("Sending request...");
+        } else {
+            output.step(format!("Sending repair turn {}/{}...", number - 1, spec.repair_turns));
+        }
+        let start_time = Instant::now();
+        let response = match &fim {
+            Some((prefix, suffix)) => provider.fill_in_middle(prefix, suffix, &config).await,
+            None => provider.chat(&messages, &config).await,
+        };
+        let mut elapsed = start_time.elapsed();
 
-    // Load prompt
-    let prompt = load_prompt(base_dir, condition, language)?;
-    println!("Prompt loaded ({} chars)", prompt.len());
+        let mut cost_usd = response.as_ref().ok().and_then(cost_of);
+        if let Reservation::Reserved(budget, estimate) = reservation {
+            budget.settle(estimate, cost_usd.unwrap_or(0.0));
+        }
+        // A failed repair turn keeps the turns already paid for
+        let mut response: LlmResponse = match response {
+            Ok(response) => response,
+            Err(e) if number > 1 && !is_credit_error(&e) => {
+                output.note(format!("⚠️  Repair turn {} failed: {}", number - 1, e));
+                break;
+            }
+            Err(e) => return Err(e),
+        };
 
-    if args.dry_run {
-        println!("\n--- DRY RUN: Prompt ---\n");
-        println!("{}", prompt);
-        println!("\n--- End of prompt ---");
-        return Ok(());
-    }
+        // A response cut off at max_tokens is continued, with --continuations,
+        // and the parts stitched into one answer
+        let mut parts = 1;
+        while response.truncated && parts <= options.continuations {
+            if spec.structured {
+                output.note("⚠️  A structured answer cut off at max_tokens cannot be continued");
+                break;
+            }
+            if let Some(limit) = options.max_input_tokens {
+                let tokens = config.system.as_deref().map_or(0, pricing::estimate_tokens)
+                    + messages.iter().map(|message| pricing::estimate_tokens(&message.content)).sum::<usize>()
+                    + pricing::estimate_tokens(&response.content);
+                if tokens > limit {
+                    output.note(format!(
+                        "Continuation stopped: the conversation (~{} tokens) would exceed --max-input-tokens {}",
+                        tokens, limit
+                    ));
+                    break;
+                }
+            }
+            let reservation = reserve(options, price, input_bytes + response.content.len(), spec.max_tokens);
+            if let Reservation::Refused(budget, estimate) = reservation {
+                output.note(format!(
+                    "Continuation stopped: worst-case cost ${:.4} could exceed --max-cost ${:.2} (spent ${:.4})",
+                    estimate,
+                    budget.limit(),
+                    budget.spent()
+                ));
+                break;
+            }
 
-    // Create provider and send request
-    let provider = get_provider(&args.provider)?;
-    let model_name = args.model.as_deref().unwrap_or(provider.default_model());
-    println!("Using model: {}", model_name);
+            output.step(format!(
+                "Response cut off at max_tokens; continuing ({}/{})...",
+                parts, options.continuations
+            ));
+            let start_time = Instant::now();
+            let next = match &fim {
+                // The completion so far extends the prefix
+                Some((prefix, suffix)) => provider
+                    .fill_in_middle(&format!("{}{}", prefix, response.content), suffix, &config)
+                    .await
+                    .map(|mut next| {
+                        next.content = format!("{}{}", response.content, next.content);
+                        next
+                    }),
+                None => provider.continue_chat(&messages, &response.content, &config).await,
+            };
+            elapsed += start_time.elapsed();
+
+            let next_cost = next.as_ref().ok().and_then(cost_of);
+            if let Reservation::Reserved(budget, estimate) = reservation {
+                budget.settle(estimate, next_cost.unwrap_or(0.0));
+            }
+            match next {
+                Ok(next) => {
+                    cost_usd = cost_usd.zip(next_cost).map(|(cost, next)| cost + next);
+                    response.extend(next);
+                    parts += 1;
+                }
+                Err(e) if !is_credit_error(&e) => {
+                    output.note(format!("⚠️  Continuation failed: {}", e));
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
 
-    let config = RequestConfig {
-        model: args.model,
-        max_tokens: args.max_tokens,
-        temperature: 0.0,
-    };
+        output.step(format!(
+            "Response received: {} chars, {} input tokens, {} output tokens, {:.2}s{}",
+            response.content.len(),
+            response.input_tokens,
+            response.output_tokens,
+            elapsed.as_secs_f64(),
+            if parts > 1 { format!(" ({} parts)", parts) } else { String::new() }
+        ));
+        if response.truncated {
+            output.note(format!(
+                "⚠️  Response cut off at max_tokens{}",
+                if parts > 1 { format!(" after {} parts", parts) } else { String::new() }
+            ));
+        }
+        if response.cache_write_tokens + response.cache_read_tokens > 0 {
+            output.step(format!(
+                "Prompt cache: {} tokens written, {} read",
+                response.cache_write_tokens, response.cache_read_tokens
+            ));
+        }
+        if let Some(cost) = cost_usd {
+            output.step(format!("Cost: ${:.4}", cost));
+        }
 
-    println!("Sending request...");
-    let start_time = Instant::now();
-    let response: LlmResponse = provider.complete(&prompt, &config).await?;
-    let elapsed = start_time.elapsed();
+        // Anthropic bills thinking as output without counting it apart: it
+        // is what the answer does not account for
+        let thinking_tokens = match (response.thinking_tokens, &response.thinking) {
+            (Some(tokens), _) => Some((tokens, false)),
+            (None, Some(_)) => Some((
+                response.output_tokens.saturating_sub(pricing::estimate_tokens(&response.content)),
+                true,
+            )),
+            (None, None) => None,
+        };
+        if let Some((tokens, estimated)) = thinking_tokens {
+            output.step(format!("Thinking: {}{} tokens", if estimated { "~" } else { "" }, tokens));
+        }
 
-    println!(
-        "Response received: {} chars, {} input tokens, {} output tokens, {:.2}s",
-        response.content.len(),
-        response.input_tokens,
-        response.output_tokens,
-        elapsed.as_secs_f64()
-    );
+        // A structured answer holds the code in a JSON field; one too
+        // malformed to yield it falls back to the code fences of its text
+        let validation = spec.structured.then(|| structured::validate(&response.content));
+        match &validation {
+            Some(validation) if validation.valid => output.step(format!(
+                "Structured answer: valid, {} tests reported",
+                validation.test_count.unwrap_or_default()
+            )),
+            Some(validation) => {
+                output.note(format!("⚠️  Structured answer invalid: {}", validation.errors.join("; ")))
+            }
+            None => {}
+        }
+        let field = validation.as_ref().and_then(|v| v.code.as_deref());
+        let extraction = extract::extract(field.unwrap_or(&response.content), language);
+        match extraction.primary.map(|primary| &extraction.blocks[primary]) {
+            Some(block) if extraction.blocks.len() > 1 => output.step(format!(
+                "Code: block {} of {} ({}{})",
+                block.index,
+                extraction.blocks.len(),
+                block.kind.as_str(),
+                block.filename.as_deref().map(|name| format!(", {}", name)).unwrap_or_default()
+            )),
+            Some(_) => {}
+            // A FIM completion or a JSON field is bare code
+            None if fim.is_some() || field.is_some() => {}
+            None => output.note("⚠️  No code block in the response; using the whole response as code"),
+        }
+        if !response.truncated && extraction.blocks.iter().any(|block| !block.closed) {
+            output.note("⚠️  Unclosed code block in the response (truncated output?)");
+        }
+        if let Some(detection) = extraction
+            .detected()
+            .filter(|detection| detection.language != language && detection.confidence >= extract::CONFIDENT)
+        {
+            output.note(format!(
+                "⚠️  Code looks like {}, not {} ({:.0}% of language markers)",
+                detection.language,
+                language,
+                detection.confidence * 100.0
+            ));
+        }
+        let code = extraction.code().to_string();
+        let compile = if check_code {
+            compile_code(language, &code, output).await?
+        } else {
+            None
+        };
+        let tests = match options.tests {
+            Some((sandbox, timeout)) => test_code(language, &code, sandbox, timeout, output).await?,
+            None => None,
+        };
+
+        let feedback = repair_prompt(language, compile.as_ref(), tests.as_ref());
+        let done = feedback.is_none() || number > spec.repair_turns;
+        turns.push(Turn {
+            turn: number,
+            input_tokens: response.input_tokens,
+            output_tokens: response.output_tokens,
+            cache_write_tokens: response.cache_write_tokens,
+            cache_read_tokens: response.cache_read_tokens,
+            thinking_tokens: thinking_tokens.map(|(tokens, _)| tokens),
+            thinking_tokens_estimated: thinking_tokens.is_some_and(|(_, estimated)| estimated),
+            truncated: response.truncated,
+            parts,
+            elapsed_ms: elapsed.as_millis(),
+            cost_usd,
+            compile,
+            tests,
+            structured: validation,
+            prompt: messages.last().map(|message| message.content.clone()).unwrap_or_default(),
+            response,
+            code,
+        });
+        match feedback {
+            Some(feedback) if !done => {
+                let content = turns.last().map(|turn| turn.response.content.clone()).unwrap_or_default();
+                messages.push(Message::assistant(content));
+                messages.push(Message::user(feedback));
+            }
+            _ => break,
+        }
+    }
 
     // Create results directory if needed
-    let results_dir = base_dir.join("results");
     std::fs::create_dir_all(&results_dir)?;
 
-    // Build file prefix with model name: {condition}_{language}_{model}
-    let safe_model = sanitize_model_name(&response.model);
-    let file_prefix = format!("{}_{}_{}", condition, language, safe_model);
+    // The run's results are those of its last turn; its usage, the sum over
+    // all turns
+    let last = turns.last().ok_or_else(|| anyhow!("No turn completed"))?;
+    let file_prefix = file_prefix(condition, language, &last.response.model, spec);
     let timestamp = Utc::now();
-
-    // Extract code (zero-copy when possible)
-    let code = extract_code(&response.content, language);
     let ext = get_file_extension(language);
+    let cost_usd = turns.iter().map(|turn| turn.cost_usd).sum::<Option<f64>>();
+
+    // Every block of the last response but the run's code is saved as
+    // `{prefix}_block{n}_{kind}.{ext}`
+    let answer = last.structured.as_ref().and_then(|v| v.code.as_deref());
+    let extraction = extract::extract(answer.unwrap_or(&last.response.content), language);
+    let mut blocks: Vec<BlockEntry> = Vec::new();
+    let mut block_files: Vec<(PathBuf, String)> = Vec::new();
+    if extraction.blocks.len() > 1 || extraction.blocks.iter().any(|block| !block.closed) {
+        for (i, block) in extraction.blocks.iter().enumerate() {
+            let primary = extraction.primary == Some(i);
+            let file = (!primary).then(|| {
+                format!("{}_block{}_{}.{}", file_prefix, block.index, block.kind.as_str(), block.extension(language))
+            });
+            if let Some(file) = &file {
+                block_files.push((results_dir.join(file), block.code.to_string()));
+            }
+            blocks.push(BlockEntry {
+                index: block.index,
+                tag: block.tag.map(str::to_string),
+                language: block.language,
+                detected: block.detected,
+                filename: block.filename.clone(),
+                kind: block.kind,
+                lines: block.lines(),
+                closed: block.closed,
+                primary,
+                file,
+            });
+        }
+    }
 
     // Build metadata
     let result = ExperimentResult {
         experiment_id: format!("{}_{}", condition, language),
         condition: condition.to_string(),
         language: language.to_string(),
-        model: response.model.clone(),
-        provider: response.provider,
+        model: last.response.model.clone(),
+        provider: last.response.provider.clone(),
         timestamp: timestamp.to_rfc3339(),
-        input_tokens: response.input_tokens,
-        output_tokens: response.output_tokens,
-        elapsed_ms: elapsed.as_millis(),
+        input_tokens: turns.iter().map(|turn| turn.input_tokens).sum(),
+        output_tokens: turns.iter().map(|turn| turn.output_tokens).sum(),
+        cache_write_tokens: turns.iter().map(|turn| turn.cache_write_tokens).sum(),
+        cache_read_tokens: turns.iter().map(|turn| turn.cache_read_tokens).sum(),
+        thinking_tokens: turns.iter().filter_map(|turn| turn.thinking_tokens).reduce(|a, b| a + b),
+        thinking_tokens_estimated: turns.iter().any(|turn| turn.thinking_tokens_estimated),
+        truncated: last.truncated,
+        parts: last.parts,
+        elapsed_ms: turns.iter().map(|turn| turn.elapsed_ms).sum(),
+        temperature: spec.temperature,
+        top_p: spec.top_p,
+        thinking_budget: spec.thinking_budget,
+        seed: spec.seed,
+        replicate: spec.replicate,
+        system_prompt: spec.system.clone(),
+        prompt_truncated,
+        cost_usd,
+        compile: last.compile.clone(),
+        tests: last.tests.clone(),
+        structured: last.structured.clone(),
+        detected_language: extraction.detected(),
+        turns: if spec.repair_turns > 0 { turns.as_slice() } else { &[] },
+        blocks,
     };
     let meta_json = serde_json::to_string_pretty(&result)?;
+    let code_source = last.code.clone();
 
     // File paths
     let prompt_file = results_dir.join(format!("{}_prompt.md", file_prefix));
@@ -444,12 +1657,12 @@ async fn run_experiment(args: Args) -> Result<()> {
         }),
         tokio::task::spawn_blocking({
             let path = response_file.clone();
-            let data = response.content.clone();
+            let data = last.response.content.clone();
             move || std::fs::write(&path, data)
         }),
         tokio::task::spawn_blocking({
             let path = code_file.clone();
-            let data = code.into_owned();
+            let data = code_source.clone();
             move || std::fs::write(&path, data)
         }),
         tokio::task::spawn_blocking({
@@ -464,67 +1677,448 @@ async fn run_experiment(args: Args) -> Result<()> {
     r3??;
     r4??;
 
-    println!("Saved: {}", prompt_file.display());
-    println!("Saved: {}", response_file.display());
-    println!("Saved: {}", code_file.display());
-    println!("Saved: {}", meta_file.display());
+    output.step(format!("Saved: {}", prompt_file.display()));
+    output.step(format!("Saved: {}", response_file.display()));
+    output.step(format!("Saved: {}", code_file.display()));
+    output.step(format!("Saved: {}", meta_file.display()));
+
+    // The other blocks, the system prompt, the thinking with
+    // --save-thinking, and each turn of a repair run: the message sent (after
+    // the first), the response, its thinking and the code extracted from it
+    let mut extra_files = block_files;
+    if let Some(system) = &system {
+        extra_files.push((results_dir.join(format!("{}_system.md", file_prefix)), system.clone()));
+    }
+    let thinking = |turn: &Turn| turn.response.thinking.clone().filter(|_| options.save_thinking);
+    if let Some(thinking) = thinking(last) {
+        extra_files.push((results_dir.join(format!("{}_thinking.md", file_prefix)), thinking));
+    }
+    if spec.repair_turns > 0 {
+        for turn in &turns {
+            let name = |suffix: &str| results_dir.join(format!("{}_turn{}_{}", file_prefix, turn.turn, suffix));
+            if turn.turn > 1 {
+                extra_files.push((name("prompt.md"), turn.prompt.clone()));
+            }
+            extra_files.push((name("response.md"), turn.response.content.clone()));
+            if let Some(thinking) = thinking(turn) {
+                extra_files.push((name("thinking.md"), thinking));
+            }
+            extra_files.push((name(&format!("code.{}", ext)), turn.code.clone()));
+        }
+    }
+    for (path, data) in extra_files {
+        tokio::fs::write(&path, data).await?;
+        output.step(format!("Saved: {}", path.display()));
+    }
+
+    // The analysis and the database row are derived from the saved files;
+    // failing to produce them must not fail a paid-for run
+    let repo_root = base_dir.join("..");
+    let analysis = tokio::task::spawn_blocking({
+        let output = output.clone();
+        move || {
+            let reference = read_reference(&repo_root, language, &output);
+            analyze_code(language, &code_source, reference.as_ref(), &output)
+        }
+    })
+    .await?;
+    let analysis_file = results_dir.join(format!("{}_analysis.json", file_prefix));
+    match std::fs::write(&analysis_file, serde_json::to_string_pretty(&analysis)?) {
+        Ok(()) => output.step(format!("Saved: {}", analysis_file.display())),
+        Err(e) => output.note(format!("⚠️  Analysis not saved: {}", e)),
+    }
+    if options.record {
+        let db_file = results_dir.join(db::DB_FILE);
+        let recorded = report::row(&results_dir, &file_prefix, Some((&code_file, language)))
+            .and_then(|(row, files)| ResultsDb::open(&db_file)?.record(&row, &files));
+        match recorded {
+            Ok(()) => output.step(format!("Recorded in {}", db_file.display())),
+            Err(e) => output.note(format!("⚠️  Not recorded in {}: {}", db_file.display(), e)),
+        }
+    }
+
+    output.step("\n=== Experiment complete ===");
 
-    println!("\n=== Experiment complete ===");
+    Ok(RunStatus::Completed {
+        cost_usd,
+        run: Some(file_prefix),
+    })
+}
+
+/// Run every configuration of the CLI flags (`--temperatures` × `--top-p` ×
+/// `--replications`), then summarize output tokens and test counts per
+/// sampling setting
+async fn run_sweep(specs: &[RunSpec], options: &RunOptions) -> Result<()> {
+    // (sampling setting, runs) in sweep order
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    let mut total_cost = 0.0;
+    let mut projection = Projection::default();
+    for (index, spec) in specs.iter().enumerate() {
+        let sampling = spec.sampling();
+        match spec.replicate {
+            Some(replicate) => println!("\n[{}/{}] {}, replicate {}", index + 1, specs.len(), sampling, replicate),
+            None => println!("\n[{}/{}] {}", index + 1, specs.len(), sampling),
+        }
+        let run = match run_experiment(spec, options).await? {
+            RunStatus::Completed { cost_usd, run } => {
+                total_cost += cost_usd.unwrap_or(0.0);
+                run
+            }
+            RunStatus::AlreadyDone { run } => Some(run),
+            RunStatus::OverBudget => {
+                println!("💰 BUDGET REACHED - stopping before run {} of {}", index + 1, specs.len());
+                break;
+            }
+            RunStatus::DryRun(estimate) => {
+                projection.add(&estimate);
+                None
+            }
+        };
+        if let Some(run) = run {
+            match groups.iter_mut().find(|(setting, _)| *setting == sampling) {
+                Some((_, runs)) => runs.push(run),
+                None => groups.push((sampling, vec![run])),
+            }
+        }
+    }
+    if projection.runs > 0 {
+        println!();
+        projection.print();
+    }
+    if groups.is_empty() {
+        return Ok(());
+    }
 
+    // Read the runs back from disk, so runs skipped as already done count too
+    let rows = report::collect(&options.base_dir.join("results"))?;
+
+    println!();
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("                      SWEEP SUMMARY");
+    println!("═══════════════════════════════════════════════════════════════");
+    for (sampling, runs) in &groups {
+        let rows: Vec<_> = rows.iter().filter(|row| runs.contains(&row.run)).collect();
+        let output_tokens: Vec<f64> = rows.iter().map(|row| row.output_tokens as f64).collect();
+        let test_counts: Vec<f64> = rows
+            .iter()
+            .filter_map(|row| row.test_count)
+            .map(|count| count as f64)
+            .collect();
+
+        println!();
+        println!("{} ({} runs)", sampling, rows.len());
+        println!("  {:<15} {:>10} {:>10} {:>10} {:>10}", "", "mean", "stddev", "min", "max");
+        for (label, values) in [("Output tokens", &output_tokens), ("Tests", &test_counts)] {
+            if let Some(summary) = Summary::of(values) {
+                println!(
+                    "  {:<15} {:>10.1} {:>10.1} {:>10} {:>10}",
+                    label, summary.mean, summary.stddev, summary.min, summary.max
+                );
+            }
+        }
+        if test_counts.len() < rows.len() {
+            println!(
+                "  ({} of {} runs could not be analyzed and are left out of Tests)",
+                rows.len() - test_counts.len(),
+                rows.len()
+            );
+        }
+    }
+    println!();
+    println!("Total cost: ${:.4}", total_cost);
     Ok(())
 }
 
-/// Run the test-mimicking emergence study
-async fn run_test_mimicking_study(base_args: Args) -> Result<()> {
+/// A reference implementation: its path relative to the repository root,
+/// and its contents
+type Reference = (&'static str, String);
+
+/// Read the reference implementation of `language` under `repo_root`,
+/// warning on `output` if it is missing
+fn read_reference(repo_root: &Path, language: &str, output: &Output) -> Option<Reference> {
+    let file = analysis::reference_file(language)?;
+    match std::fs::read_to_string(repo_root.join(file)) {
+        Ok(contents) => Some((file, contents)),
+        Err(e) => {
+            output.note(format!(
+                "⚠️  Cannot read {} reference {}: {} (similarity not scored)",
+                language,
+                repo_root.join(file).display(),
+                e
+            ));
+            None
+        }
+    }
+}
+
+/// Count the tests of `source` and, given its language's reference, score
+/// its similarity
+fn analyze_code(language: &str, source: &str, reference: Option<&Reference>, output: &Output) -> CodeAnalysis {
+    let mut result = CodeAnalysis::of(language, source);
+    if let Some((file, contents)) = reference {
+        match analysis::compare(language, source, file, contents) {
+            Ok(similarity) => result.similarity = Some(similarity),
+            Err(e) => output.note(format!("⚠️  Similarity not scored: {}", e)),
+        }
+    }
+    result
+}
+
+/// Provider and model `analyze --embeddings` embeds code with
+struct Embedder<'a> {
+    provider: &'a dyn LlmProvider,
+    model: Option<&'a str>,
+}
+
+impl Embedder<'_> {
+    async fn embed(&self, text: &str, chunks: &mut usize, tokens: &mut usize) -> Result<DocumentEmbedding> {
+        let embedded = embedding::embed_document(self.provider, text, self.model).await?;
+        *chunks += embedded.chunks;
+        *tokens += embedded.input_tokens;
+        Ok(embedded)
+    }
+}
+
+/// The embedding score of an earlier `analyze --embeddings`, kept by a run
+/// without the flag while the reference it was scored against is unchanged
+fn previous_embedding(analysis_file: &Path, reference: &str) -> Option<EmbeddingSimilarity> {
+    let previous: CodeAnalysis = serde_json::from_str(&std::fs::read_to_string(analysis_file).ok()?).ok()?;
+    previous
+        .similarity
+        .filter(|similarity| similarity.reference == reference)?
+        .embedding
+}
+
+/// Count the tests of every generated code file in `dir` and score it against
+/// the reference implementation under `repo_root`, writing
+/// `{prefix}_analysis.json` next to each `{prefix}_code.{ext}`. A missing
+/// reference is warned about once and leaves its language unscored. With an
+/// `embedder`, each file and reference is also embedded and their cosine
+/// similarity scored; a reference that cannot be embedded stops the run.
+async fn run_analyze(dir: &Path, repo_root: &Path, embedder: Option<&Embedder<'_>>) -> Result<()> {
+    let mut files: Vec<(PathBuf, String, &str)> = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .map_err(|e| anyhow!("Failed to read results directory {}: {}", dir.display(), e))?
+    {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Some((prefix, language)) = analysis::code_file_prefix(file_name) {
+            files.push((path.clone(), prefix.to_string(), language));
+        }
+    }
+    if files.is_empty() {
+        return Err(anyhow!("No *_code.* files in {}", dir.display()));
+    }
+    files.sort();
+
+    // Reference implementation per language, None if it could not be read
+    let mut references: HashMap<&str, Option<Reference>> = HashMap::new();
+    // Its embedding, computed once per language
+    let mut reference_embeddings: HashMap<&str, DocumentEmbedding> = HashMap::new();
+    let (mut embedded_chunks, mut embedded_tokens) = (0, 0);
+
+    let mut failed = 0;
+    println!(
+        "{:<70} {:>6} {:>7} {:>7} {:>7} {:>7}",
+        "Code file", "Tests", "Tokens", "AST", "Idents", "Embed"
+    );
+    for (path, prefix, language) in &files {
+        let source = std::fs::read_to_string(path)?;
+        let reference = references
+            .entry(language)
+            .or_insert_with(|| read_reference(repo_root, language, &Output::Stdout));
+        let mut result = analyze_code(language, &source, reference.as_ref(), &Output::Stdout);
+        let analysis_file = dir.join(format!("{}_analysis.json", prefix));
+
+        if let (Some(similarity), Some((file, contents))) = (result.similarity.as_mut(), reference.as_ref()) {
+            match embedder {
+                Some(embedder) => {
+                    if !reference_embeddings.contains_key(language) {
+                        let embedded = embedder
+                            .embed(contents, &mut embedded_chunks, &mut embedded_tokens)
+                            .await
+                            .map_err(|e| anyhow!("Failed to embed reference {}: {}", file, e))?;
+                        reference_embeddings.insert(language, embedded);
+                    }
+                    let reference_embedding = &reference_embeddings[language];
+                    match embedder.embed(&source, &mut embedded_chunks, &mut embedded_tokens).await {
+                        Ok(embedded) => {
+                            similarity.embedding = embedding::cosine(&embedded.vector, &reference_embedding.vector)
+                                .map(|cosine| EmbeddingSimilarity {
+                                    model: embedded.model,
+                                    cosine,
+                                });
+                        }
+                        Err(e) => Output::Stdout.note(format!("⚠️  {}: embedding not scored: {}", prefix, e)),
+                    }
+                }
+                None => similarity.embedding = previous_embedding(&analysis_file, file),
+            }
+        }
+
+        let score = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |value| format!("{:.3}", value));
+        let similarity = result.similarity.as_ref();
+        let scores = format!(
+            "{:>7} {:>7} {:>7} {:>7}",
+            score(similarity.map(|s| s.token_ratio)),
+            score(similarity.and_then(|s| s.ast_overlap)),
+            score(similarity.map(|s| s.identifier_jaccard)),
+            score(similarity.and_then(|s| s.embedding.as_ref()).map(|e| e.cosine)),
+        );
+        match (result.test_count, &result.error) {
+            (Some(count), _) => println!("{:<70} {:>6} {}", prefix, count, scores),
+            (None, error) => {
+                failed += 1;
+                println!(
+                    "{:<70} {:>6} {}  ({})",
+                    prefix,
+                    "-",
+                    scores,
+                    error.as_deref().unwrap_or("unknown error")
+                );
+            }
+        }
+        std::fs::write(&analysis_file, serde_json::to_string_pretty(&result)?)?;
+    }
+
+    println!();
+    println!("Analyzed {} code files ({} could not be parsed)", files.len(), failed);
+    if let Some(model) = reference_embeddings.values().next().map(|embedded| &embedded.model) {
+        println!(
+            "Embedded {} chunks ({} input tokens) with {}",
+            embedded_chunks, embedded_tokens, model
+        );
+    }
+
+    // Keep the database's analysis columns in step with the files
+    let db_file = dir.join(db::DB_FILE);
+    if db_file.exists() {
+        let count = ResultsDb::open(&db_file)?.import(dir)?;
+        println!("Updated {} runs in {}", count, db_file.display());
+    }
+    Ok(())
+}
+
+/// Write the aggregate table of the runs in `dir` to `output` (stdout if `None`)
+fn run_report(dir: &Path, format: ReportFormat, output: Option<&Path>) -> Result<()> {
+    let rows = report::collect(dir)?;
+    if rows.is_empty() {
+        return Err(anyhow!("No *_meta.json files in {}", dir.display()));
+    }
+    let table = match format {
+        ReportFormat::Csv => report::to_csv(&rows),
+        ReportFormat::Json => serde_json::to_string_pretty(&rows)? + "\n",
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, table)?;
+            println!("Wrote {} runs to {}", rows.len(), path.display());
+        }
+        None => print!("{}", table),
+    }
+    Ok(())
+}
+
+/// Record every run of `dir` in `dir/results.db`
+fn run_import(dir: &Path) -> Result<()> {
+    let path = dir.join(db::DB_FILE);
+    let count = ResultsDb::open(&path)?.import(dir)?;
+    println!("Recorded {} runs in {}", count, path.display());
+    Ok(())
+}
+
+/// List the models LM Studio serves, failing if it is down or does not
+/// serve one of `models`
+async fn run_models(models: &[String]) -> Result<()> {
+    let provider = lmstudio::LmStudioProvider::new();
+    let models: Vec<&str> = models.iter().map(String::as_str).collect();
+    let loaded = provider.check_models(&models).await?;
+    println!("LM Studio at {}: {} models", provider.base_url(), loaded.len());
+    for id in &loaded {
+        let embedding = if id.contains("embed") { "  (embedding)" } else { "" };
+        println!("  {}{}", id, embedding);
+    }
+    if !models.is_empty() {
+        println!("✓ Loaded: {}", models.join(", "));
+    }
+    Ok(())
+}
+
+/// Print the result of `sql` against the database at `path`
+fn run_query(sql: &str, path: &Path, format: Option<ReportFormat>) -> Result<()> {
+    let result = ResultsDb::open_existing(path)?.query(sql)?;
+    match format {
+        None => print!("{}", result.to_table()),
+        Some(ReportFormat::Csv) => print!("{}", result.to_csv()),
+        Some(ReportFormat::Json) => print!("{}", result.to_json()?),
+    }
+    Ok(())
+}
+
+/// Run a study: its condition and language across its models
+async fn run_study(base_args: &Args, study: &Study) -> Result<()> {
     println!("╔══════════════════════════════════════════════════════════════╗");
-    println!("║       TEST-MIMICKING EMERGENCE STUDY                         ║");
-    println!("║  Testing: When did Claude start mimicking test patterns?     ║");
+    println!("║{:^62}║", study.title.to_uppercase());
+    if let Some(description) = &study.description {
+        println!("║{:^62}║", description);
+    }
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
-    println!("Models to test: {:?}", TEST_MIMICKING_MODELS);
-    println!("Condition: test_guided");
-    println!("Language: rust (highest test count difference observed)");
+    let pending: Vec<&str> = study.pending().map(|model| model.id.as_str()).collect();
+    println!("Models to test: {:?}", pending);
+    println!("Provider: {}", study.provider);
+    println!("Condition: {}", study.condition);
+    println!("Language: {}", study.language);
+    if study.replications > 1 {
+        println!("Replications: {}", study.replications);
+    }
     println!();
 
-    let base_dir = base_args
-        .base_dir
-        .as_deref()
-        .map(Path::new)
-        .unwrap_or_else(|| Path::new("."));
+    let options = base_args.run_options()?;
+    let results_dir = options.base_dir.join("results");
+    let specs = study.runs();
+    if !options.dry_run {
+        check_local_models(&specs).await?;
+    }
 
     let mut completed = 0;
-    let mut results_summary: Vec<(String, usize)> = Vec::new();
+    let mut total_cost = 0.0;
+    let mut results_summary: Vec<(String, usize, Option<usize>)> = Vec::new();
+    let mut projection = Projection::default();
 
-    for (model, max_tokens) in TEST_MIMICKING_MODELS {
+    for spec in &specs {
+        let model = spec.model.as_deref().unwrap_or_default();
         println!("────────────────────────────────────────────────────────────────");
-        println!("Testing model: {} (max_tokens: {})", model, max_tokens);
+        match spec.replicate {
+            Some(replicate) => println!(
+                "Testing model: {} (max_tokens: {}, replicate {})",
+                model, spec.max_tokens, replicate
+            ),
+            None => println!("Testing model: {} (max_tokens: {})", model, spec.max_tokens),
+        }
         println!("────────────────────────────────────────────────────────────────");
 
-        let args = Args {
-            provider: "anthropic".to_string(),
-            condition: "test_guided".to_string(),
-            language: "rust".to_string(),
-            model: Some(model.to_string()),
-            max_tokens: *max_tokens,
-            dry_run: base_args.dry_run,
-            base_dir: base_args.base_dir.clone(),
-            test_mimicking_study: false,
-        };
-
-        match run_experiment(args).await {
-            Ok(()) => {
+        match run_experiment(spec, &options).await {
+            Ok(RunStatus::OverBudget) => {
+                println!();
+                println!("💰 BUDGET REACHED - stopping before {}", model);
+                break;
+            }
+            Ok(RunStatus::DryRun(estimate)) => projection.add(&estimate),
+            Ok(status) => {
                 completed += 1;
-                // Try to read the output tokens from the meta file
-                let safe_model = sanitize_model_name(model);
-                let meta_path = base_dir.join("results").join(
-                    format!("test_guided_rust_{}_meta.json", safe_model)
-                );
-                if let Ok(content) = std::fs::read_to_string(&meta_path) {
-                    if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&content) {
-                        if let Some(tokens) = meta.get("output_tokens").and_then(|v| v.as_u64()) {
-                            results_summary.push((model.to_string(), tokens as usize));
-                        }
+                let run = match status {
+                    RunStatus::Completed { cost_usd, run } => {
+                        total_cost += cost_usd.unwrap_or(0.0);
+                        run
                     }
+                    RunStatus::AlreadyDone { run } => Some(run),
+                    _ => None,
+                };
+                if let Some(Ok((row, _))) = run.map(|run| report::row(&results_dir, &run, None)) {
+                    results_summary.push((row.model, row.output_tokens, row.test_count));
                 }
                 println!("✓ {} completed successfully\n", model);
             }
@@ -535,8 +2129,8 @@ async fn run_test_mimicking_study(base_args: Args) -> Result<()> {
                     println!("║  ⚠️  CREDIT EXHAUSTED - STOPPING GRACEFULLY                  ║");
                     println!("╚══════════════════════════════════════════════════════════════╝");
                     println!();
-                    println!("Completed {}/{} models before running out of credits.",
-                             completed, TEST_MIMICKING_MODELS.len());
+                    println!("Completed {}/{} runs before running out of credits.",
+                             completed, specs.len());
                     break;
                 } else {
                     println!("✗ {} failed: {}\n", model, e);
@@ -552,35 +2146,353 @@ async fn run_test_mimicking_study(base_args: Args) -> Result<()> {
     println!("║                    STUDY RESULTS SUMMARY                     ║");
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
-    println!("Completed: {}/{} models", completed, TEST_MIMICKING_MODELS.len());
+    if projection.runs > 0 {
+        projection.print();
+    } else {
+        println!("Completed: {}/{} runs", completed, specs.len());
+        println!("Cost: ${:.4}", total_cost);
+    }
     println!();
 
     if !results_summary.is_empty() {
-        println!("Output tokens by model (test_guided rust):");
-        println!("┌─────────────────────────────────────┬──────────────┐");
-        println!("│ Model                               │ Output Tokens│");
-        println!("├─────────────────────────────────────┼──────────────┤");
-        for (model, tokens) in &results_summary {
-            println!("│ {:35} │ {:>12} │", model, tokens);
-        }
-        println!("└─────────────────────────────────────┴──────────────┘");
+        println!("Output tokens by model ({} {}):", study.condition, study.language);
+        println!("┌─────────────────────────────────────┬──────────────┬───────┐");
+        println!("│ Model                               │ Output Tokens│ Tests │");
+        println!("├─────────────────────────────────────┼──────────────┼───────┤");
+        for (model, tokens, tests) in &results_summary {
+            let tests = tests.map_or_else(|| "-".to_string(), |tests| tests.to_string());
+            println!("│ {:35} │ {:>12} │ {:>5} │", model, tokens, tests);
+        }
+        println!("└─────────────────────────────────────┴──────────────┴───────┘");
         println!();
 
-        // Reference: Claude Sonnet 4 produced 6,370 tokens with 22 tests
-        println!("Reference: claude-sonnet-4-20250514 produced 6,370 tokens (22 tests)");
-        println!("Reference: mistral-medium-latest produced 1,950 tokens (0 tests)");
+        for reference in &study.references {
+            match reference.tests {
+                Some(tests) => println!(
+                    "Reference: {} produced {} tokens ({} tests)",
+                    reference.model, reference.output_tokens, tests
+                ),
+                None => println!("Reference: {} produced {} tokens", reference.model, reference.output_tokens),
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Run every experiment of a `--config` or `--matrix` batch, in order;
+/// `source` says where the runs came from
+async fn run_batch(args: &Args, specs: Vec<RunSpec>, source: &str) -> Result<()> {
+    // Reject typos before the first API call rather than midway through
+    for spec in &specs {
+        check_provider(spec)?;
+        check_output_limit(spec, args.max_output_tokens_hard)?;
+        normalize_condition(&spec.condition)?;
+        normalize_language(&spec.language)?;
+    }
+    if !args.dry_run {
+        check_local_models(&specs).await?;
+    }
+
+    println!("Batch: {} runs from {}", specs.len(), source);
+    println!();
+
+    // A run needs one of the --jobs permits and one of its provider's
+    // permits; tokio semaphores are fair, so runs start in file order as
+    // far as the limits allow, and a saturated provider does not hold up
+    // runs against the others.
+    let jobs = Arc::new(Semaphore::new(args.jobs.get()));
+    let per_provider = args.per_provider_limit.unwrap_or(args.jobs).get();
+    let mut provider_limits: HashMap<&'static str, Arc<Semaphore>> = HashMap::new();
+    let credit_exhausted = Arc::new(AtomicBool::new(false));
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let total = specs.len();
+    let options = args.run_options()?;
+
+    // One bar for the batch, plus a spinner per run in flight showing its
+    // latest step; finished runs leave a one-line verdict above them
+    let bars = MultiProgress::new();
+    let overall = bars.add(ProgressBar::new(total as u64));
+    overall.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} runs  {elapsed} elapsed, ~{eta} left  {msg}")
+            .expect("valid template"),
+    );
+    let spinner = ProgressStyle::with_template("  {spinner} {prefix}  {wide_msg}").expect("valid template");
+
+    // Ctrl-C once: let the runs in flight finish and save, start no more.
+    // Twice: abort at once.
+    tokio::spawn({
+        let interrupted = Arc::clone(&interrupted);
+        let overall = overall.clone();
+        async move {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                if interrupted.swap(true, Ordering::SeqCst) {
+                    std::process::exit(130);
+                }
+                overall.set_message("interrupted: finishing runs in flight (Ctrl-C again to abort)");
+                if overall.is_hidden() {
+                    println!("Interrupted: finishing runs in flight, starting no more (Ctrl-C again to abort)");
+                }
+            }
+        }
+    });
+
+    let mut tasks = JoinSet::new();
+    for (index, spec) in specs.iter().cloned().enumerate() {
+        let provider_limit = Arc::clone(
+            provider_limits
+                .entry(normalize_provider(&spec.provider)?)
+                .or_insert_with(|| Arc::new(Semaphore::new(per_provider))),
+        );
+        let jobs = Arc::clone(&jobs);
+        let credit_exhausted = Arc::clone(&credit_exhausted);
+        let interrupted = Arc::clone(&interrupted);
+        let mut options = options.clone();
+        let bars = bars.clone();
+        let overall = overall.clone();
+        let spinner = spinner.clone();
+
+        tasks.spawn(async move {
+            let label = format!(
+                "{}_{}_{}{}",
+                spec.condition,
+                spec.language,
+                spec.model.as_deref().unwrap_or("default"),
+                spec.suffix()
+            );
+            let _provider_permit = provider_limit.acquire_owned().await?;
+            let _job_permit = jobs.acquire_owned().await?;
+            if credit_exhausted.load(Ordering::SeqCst) || interrupted.load(Ordering::SeqCst) {
+                return Ok((index, label, None));
+            }
+
+            let bar = bars.insert_before(&overall, ProgressBar::new_spinner());
+            bar.set_style(spinner);
+            bar.set_prefix(format!("[{}/{}] {} via {}", index + 1, total, label, spec.provider));
+            bar.enable_steady_tick(Duration::from_millis(120));
+            if bar.is_hidden() {
+                println!("────────────────────────────────────────────────────────────────");
+                println!("{}", bar.prefix());
+                println!("────────────────────────────────────────────────────────────────");
+            }
+            options.output = Output::Bar(bar.clone());
+
+            let outcome = run_experiment(&spec, &options).await;
+            let verdict = match &outcome {
+                Ok(RunStatus::Completed { cost_usd: Some(cost), .. }) => format!("✓ {} done (${:.4})", label, cost),
+                Ok(RunStatus::Completed { cost_usd: None, .. }) => format!("✓ {} done", label),
+                Ok(RunStatus::AlreadyDone { .. }) => format!("↷ {} already done", label),
+                Ok(RunStatus::OverBudget) => format!("💰 {} not started: could exceed --max-cost", label),
+                Ok(RunStatus::DryRun(_)) => format!("· {} dry run", label),
+                Err(e) => {
+                    if is_credit_error(e) {
+                        credit_exhausted.store(true, Ordering::SeqCst);
+                    }
+                    format!("✗ {} failed: {}", label, e)
+                }
+            };
+            if bar.is_hidden() {
+                println!("{}\n", verdict);
+            } else {
+                let _ = bars.println(verdict);
+            }
+            bar.finish_and_clear();
+            bars.remove(&bar);
+            overall.inc(1);
+            if overall.is_hidden() {
+                println!(
+                    "── Progress: {}/{} runs finished, {} elapsed, ~{} remaining\n",
+                    overall.position(),
+                    total,
+                    HumanDuration(overall.elapsed()),
+                    HumanDuration(overall.eta())
+                );
+            }
+            anyhow::Ok((index, label, Some(outcome)))
+        });
+    }
+
+    let mut outcomes = Vec::with_capacity(total);
+    while let Some(joined) = tasks.join_next().await {
+        outcomes.push(joined??);
+    }
+    overall.finish_and_clear();
+    outcomes.sort_by_key(|(index, _, _)| *index);
+
+    let mut completed = 0;
+    let mut already_done = 0;
+    let mut over_budget = false;
+    let mut total_cost = 0.0;
+    let mut unpriced = 0;
+    let mut projection = Projection::default();
+    let mut failed: Vec<(String, String)> = Vec::new();
+    let mut remaining: Vec<RunSpec> = Vec::new();
+    for (index, label, outcome) in outcomes {
+        match outcome {
+            Some(Ok(RunStatus::Completed { cost_usd, .. })) => {
+                completed += 1;
+                match cost_usd {
+                    Some(cost) => total_cost += cost,
+                    None => unpriced += 1,
+                }
+            }
+            Some(Ok(RunStatus::AlreadyDone { .. })) => already_done += 1,
+            Some(Ok(RunStatus::OverBudget)) => {
+                over_budget = true;
+                remaining.push(specs[index].clone());
+            }
+            Some(Ok(RunStatus::DryRun(estimate))) => projection.add(&estimate),
+            Some(Err(e)) => failed.push((label, e.to_string())),
+            None => remaining.push(specs[index].clone()),
+        }
+    }
+
+    let not_started = remaining.len();
+    let interrupted = interrupted.load(Ordering::SeqCst);
+    if interrupted {
+        println!();
+        println!("⏸  INTERRUPTED - {} runs not started; re-run the same command to resume", not_started);
+    } else if credit_exhausted.load(Ordering::SeqCst) {
+        println!();
+        println!("⚠️  CREDIT EXHAUSTED - {} runs not started; re-run the same command to resume", not_started);
+    } else if over_budget {
+        println!();
+        println!("💰 BUDGET REACHED - {} runs not started; raise --max-cost and re-run to resume", not_started);
+    }
+    if !remaining.is_empty() {
+        let path = options.base_dir.join("results").join(REMAINING_RUNS_FILE);
+        let listing = format!(
+            "# {} runs of the batch from {} that were not started\n# Resume with: --config {}\n\n{}",
+            not_started,
+            source,
+            path.display(),
+            BatchConfig::from_specs(&remaining).to_toml()?
+        );
+        std::fs::create_dir_all(options.base_dir.join("results"))?;
+        std::fs::write(&path, listing)?;
+        println!("   Runs not started are listed in {}", path.display());
+    }
+    println!();
+    if projection.runs > 0 {
+        projection.print();
+        for (label, error) in &failed {
+            println!("  ✗ {}: {}", label, error);
+        }
+        return Ok(());
+    }
+    println!(
+        "Batch {}: {}/{} runs succeeded, {} already done",
+        if interrupted { "interrupted" } else { "complete" },
+        completed,
+        total,
+        already_done
+    );
+    if unpriced > 0 {
+        println!("Cost: ${:.4} (+ {} runs on models missing from the pricing table)", total_cost, unpriced);
+    } else {
+        println!("Cost: ${:.4}", total_cost);
+    }
+    for (label, error) in &failed {
+        println!("  ✗ {}: {}", label, error);
+    }
+
+    if interrupted {
+        return Err(anyhow!("Batch interrupted"));
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    if args.test_mimicking_study {
-        run_test_mimicking_study(args).await
+    if let Some(Command::Analyze {
+        dir,
+        repo_root,
+        embeddings,
+        embedding_model,
+    }) = &args.command
+    {
+        let embedder = match embeddings {
+            Some(provider) => Some(get_provider(provider)?),
+            None => None,
+        };
+        let embedder = embedder.as_deref().map(|provider| Embedder {
+            provider,
+            model: embedding_model.as_deref(),
+        });
+        run_analyze(dir, repo_root, embedder.as_ref()).await
+    } else if let Some(Command::Report { dir, format, output }) = &args.command {
+        run_report(dir, *format, output.as_deref())
+    } else if let Some(Command::Import { dir }) = &args.command {
+        run_import(dir)
+    } else if let Some(Command::Models { models }) = &args.command {
+        run_models(models).await
+    } else if let Some(Command::Query { sql, db, format }) = &args.command {
+        run_query(sql, db, *format)
+    } else if let Some(config) = &args.config {
+        let specs = BatchConfig::load(Path::new(config))?.runs()?;
+        run_batch(&args, specs, config).await
+    } else if args.matrix {
+        run_batch(&args, args.matrix()?, "--matrix").await
+    } else if args.jobs.get() > 1 || args.per_provider_limit.is_some() {
+        Err(anyhow!("--jobs and --per-provider-limit only apply to --config and --matrix batches"))
+    } else if let Some(path) = &args.study {
+        run_study(&args, &Study::load(Path::new(path))?).await
+    } else if args.test_mimicking_study {
+        run_study(&args, &Study::load(Path::new(TEST_MIMICKING_STUDY))?).await
     } else {
-        run_experiment(args).await
+        let specs = args.run_specs()?;
+        let options = args.run_options()?;
+        if !options.dry_run {
+            check_local_models(&specs).await?;
+        }
+        match specs.as_slice() {
+            [spec] => match run_experiment(spec, &options).await? {
+                RunStatus::OverBudget => Err(anyhow!("Run not started: it could exceed --max-cost")),
+                _ => Ok(()),
+            },
+            specs => run_sweep(specs, &options).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_prices_the_prompt_and_the_whole_output_budget() {
+        let prompt = "x".repeat(3_000);
+        let estimate = Estimate::new("anthropic", "claude-sonnet-4-20250514", &prompt, 4_000);
+        assert_eq!(estimate.input_tokens, 1_000);
+        assert_eq!(estimate.max_output_tokens, 4_000);
+        let input = estimate.input_cost_usd.unwrap();
+        let max = estimate.max_cost_usd.unwrap();
+        assert!((input - 0.003).abs() < 1e-12);
+        assert!((max - (0.003 + 0.06)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimate_of_an_unpriced_model_has_tokens_but_no_cost() {
+        let estimate = Estimate::new("mistral", "not-a-model", "abcdef", 10);
+        assert_eq!(estimate.input_tokens, 2);
+        assert_eq!(estimate.input_cost_usd, None);
+        assert_eq!(estimate.max_cost_usd, None);
+    }
+
+    #[test]
+    fn projection_sums_priced_runs_and_counts_unpriced_ones() {
+        let mut projection = Projection::default();
+        let haiku = "claude-3-haiku-20240307";
+        projection.add(&Estimate::new("lmstudio", "local", "abc", 100));
+        projection.add(&Estimate::new("anthropic", haiku, "abcdef", 1_000));
+        projection.add(&Estimate::new("anthropic", "unknown", "abcdefghi", 10));
+        assert_eq!(projection.runs, 3);
+        assert_eq!(projection.input_tokens, 1 + 2 + 3);
+        assert_eq!(projection.max_output_tokens, 1_110);
+        assert_eq!(projection.unpriced, 1);
+        let haiku_max = (2.0 * 0.25 + 1_000.0 * 1.25) / 1_000_000.0;
+        assert!((projection.max_cost_usd - haiku_max).abs() < 1e-12);
     }
 }
@@ -1,14 +1,30 @@
 //! Mistral AI Provider
 
-use crate::provider::{LlmProvider, LlmResponse, RequestConfig};
+use crate::provider::{split_think_tags, Embeddings, LlmProvider, LlmResponse, Message, RequestConfig};
+use crate::structured;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 const MISTRAL_API_URL: &str = "https://api.mistral.ai/v1/chat/completions";
+const MISTRAL_FIM_URL: &str = "https://api.mistral.ai/v1/fim/completions";
+const MISTRAL_EMBEDDINGS_URL: &str = "https://api.mistral.ai/v1/embeddings";
+/// Codestral's own endpoint, which takes a key of its own
+const CODESTRAL_FIM_URL: &str = "https://codestral.mistral.ai/v1/fim/completions";
+
+/// Model used when no `--model` is given
+pub const DEFAULT_MODEL: &str = "mistral-medium-latest";
+
+/// Model used for fill-in-the-middle when no `--model` is given
+pub const FIM_MODEL: &str = "codestral-latest";
+
+/// Model used for embeddings when no `--embedding-model` is given
+pub const EMBEDDING_MODEL: &str = "codestral-embed";
 
 pub struct MistralProvider {
     api_key: String,
+    /// Key for the Codestral endpoint, which FIM requests then go to
+    codestral_api_key: Option<String>,
     client: reqwest::Client,
 }
 
@@ -16,12 +32,72 @@ impl MistralProvider {
     pub fn new() -> Result<Self> {
         let api_key = std::env::var("MISTRAL_API_KEY")
             .map_err(|_| anyhow!("MISTRAL_API_KEY environment variable not set"))?;
+        let codestral_api_key = std::env::var("CODESTRAL_API_KEY").ok();
 
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(300))
             .build()?;
 
-        Ok(Self { api_key, client })
+        Ok(Self {
+            api_key,
+            codestral_api_key,
+            client,
+        })
+    }
+
+    /// POST `request` to `url`, failing on an error status
+    async fn post<T: Serialize + Sync>(&self, url: &str, api_key: &str, request: &T) -> Result<reqwest::Response> {
+        let response = self
+            .client
+            .post(url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(request)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Mistral API error ({}): {}", status, error_text));
+        }
+        Ok(response)
+    }
+
+    /// POST `request` to `url`, returning the parsed completion
+    async fn send<T: Serialize + Sync>(&self, url: &str, api_key: &str, request: &T) -> Result<LlmResponse> {
+        let result: ChatResponse = self.post(url, api_key, request).await?.json().await?;
+
+        let choice = result.choices.into_iter().next();
+        let truncated = choice.as_ref().and_then(|c| c.finish_reason.as_deref()) == Some("length");
+        let (content, thinking) = match choice.map(|c| c.message.content) {
+            Some(ResponseContent::Text(text)) => split_think_tags(text),
+            Some(ResponseContent::Chunks(chunks)) => {
+                let thinking: String = chunks
+                    .iter()
+                    .map(|chunk| match chunk {
+                        Chunk::Thinking { thinking } => Chunk::text(thinking),
+                        _ => String::new(),
+                    })
+                    .collect();
+                (Chunk::text(&chunks), (!thinking.is_empty()).then_some(thinking))
+            }
+            None => (String::new(), None),
+        };
+
+        Ok(LlmResponse {
+            content,
+            input_tokens: result.usage.prompt_tokens,
+            output_tokens: result.usage.completion_tokens,
+            model: result.model,
+            provider: "mistral".to_string(),
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+            thinking,
+            thinking_tokens: None,
+            truncated,
+        })
     }
 }
 
@@ -32,12 +108,76 @@ struct ChatRequest {
     messages: Vec<ChatMessage>,
     max_tokens: u32,
     temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    random_seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+}
+
+/// Fill-in-the-middle request: the completion goes between `prompt` and
+/// `suffix`
+#[derive(Serialize)]
+struct FimRequest<'a> {
+    model: String,
+    prompt: &'a str,
+    suffix: &'a str,
+    max_tokens: u32,
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    random_seed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+    model: String,
+    usage: EmbeddingUsage,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingUsage {
+    prompt_tokens: usize,
 }
 
 #[derive(Serialize)]
 struct ChatMessage {
     role: String,
     content: String,
+    /// On a last assistant message: the answer starts with it
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    prefix: bool,
+}
+
+/// The conversation in OpenAI form, the system prompt as its first message
+fn chat_messages(messages: &[Message], config: &RequestConfig) -> Vec<ChatMessage> {
+    let system = config.system.iter().map(|system| ChatMessage {
+        role: "system".to_string(),
+        content: system.clone(),
+        prefix: false,
+    });
+    system
+        .chain(messages.iter().map(|message| ChatMessage {
+            role: message.role.as_str().to_string(),
+            content: message.content.clone(),
+            prefix: false,
+        }))
+        .collect()
 }
 
 #[derive(Deserialize)]
@@ -50,11 +190,42 @@ struct ChatResponse {
 #[derive(Deserialize)]
 struct Choice {
     message: ResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct ResponseMessage {
-    content: String,
+    content: ResponseContent,
+}
+
+/// Reasoning models (Magistral) answer with chunks, thinking apart from text
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ResponseContent {
+    Text(String),
+    Chunks(Vec<Chunk>),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Chunk {
+    Text { text: String },
+    Thinking { thinking: Vec<Chunk> },
+    #[serde(other)]
+    Other,
+}
+
+impl Chunk {
+    fn text(chunks: &[Chunk]) -> String {
+        chunks
+            .iter()
+            .map(|chunk| match chunk {
+                Chunk::Text { text } => text.as_str(),
+                _ => "",
+            })
+            .collect()
+    }
 }
 
 #[derive(Deserialize)]
@@ -66,10 +237,10 @@ struct ChatUsage {
 #[async_trait]
 impl LlmProvider for MistralProvider {
     fn default_model(&self) -> &str {
-        "mistral-medium-latest"
+        DEFAULT_MODEL
     }
 
-    async fn complete(&self, prompt: &str, config: &RequestConfig) -> Result<LlmResponse> {
+    async fn chat(&self, messages: &[Message], config: &RequestConfig) -> Result<LlmResponse> {
         let model = config
             .model
             .as_deref()
@@ -77,44 +248,88 @@ impl LlmProvider for MistralProvider {
 
         let request = ChatRequest {
             model: model.to_string(),
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+            messages: chat_messages(messages, config),
             max_tokens: config.max_tokens,
             temperature: config.temperature,
+            top_p: config.top_p,
+            random_seed: config.seed,
+            response_format: config.structured.then(structured::response_format),
         };
 
-        let response = self
-            .client
-            .post(MISTRAL_API_URL)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+        self.send(MISTRAL_API_URL, &self.api_key, &request).await
+    }
 
-        let status = response.status();
+    async fn continue_chat(&self, messages: &[Message], partial: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        let model = config
+            .model
+            .as_deref()
+            .unwrap_or_else(|| self.default_model());
 
-        if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Mistral API error ({}): {}", status, error_text));
+        // The partial answer as a prefix the model carries on from
+        let mut conversation = chat_messages(messages, config);
+        conversation.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: partial.to_string(),
+            prefix: true,
+        });
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: conversation,
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+            top_p: config.top_p,
+            random_seed: config.seed,
+            response_format: None,
+        };
+
+        // The answer starts with the prefix
+        let mut response = self.send(MISTRAL_API_URL, &self.api_key, &request).await?;
+        if !response.content.starts_with(partial) {
+            response.content = format!("{}{}", partial, response.content);
         }
+        Ok(response)
+    }
+
+    fn fim_model(&self) -> Option<&str> {
+        Some(FIM_MODEL)
+    }
+
+    async fn fill_in_middle(&self, prefix: &str, suffix: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        let request = FimRequest {
+            model: config.model.as_deref().unwrap_or(FIM_MODEL).to_string(),
+            prompt: prefix,
+            suffix,
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+            top_p: config.top_p,
+            random_seed: config.seed,
+        };
 
-        let result: ChatResponse = response.json().await?;
+        match &self.codestral_api_key {
+            Some(key) => self.send(CODESTRAL_FIM_URL, key, &request).await,
+            None => self.send(MISTRAL_FIM_URL, &self.api_key, &request).await,
+        }
+    }
 
-        let content = result
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default();
+    fn embedding_model(&self) -> Option<&str> {
+        Some(EMBEDDING_MODEL)
+    }
 
-        Ok(LlmResponse {
-            content,
+    async fn embed(&self, inputs: &[&str], model: Option<&str>) -> Result<Embeddings> {
+        let request = EmbeddingRequest {
+            model: model.unwrap_or(EMBEDDING_MODEL),
+            input: inputs,
+        };
+        let mut result: EmbeddingResponse = self
+            .post(MISTRAL_EMBEDDINGS_URL, &self.api_key, &request)
+            .await?
+            .json()
+            .await?;
+        result.data.sort_by_key(|data| data.index);
+        Ok(Embeddings {
+            vectors: result.data.into_iter().map(|data| data.embedding).collect(),
             input_tokens: result.usage.prompt_tokens,
-            output_tokens: result.usage.completion_tokens,
             model: result.model,
-            provider: "mistral".to_string(),
         })
     }
 }
@@ -0,0 +1,252 @@
+//! Per-Model Pricing and Cost Budgets
+//!
+//! Prices are list prices in USD per million tokens, matched on the longest
+//! model-id prefix so dated snapshots (`claude-sonnet-4-20250514`) and
+//! aliases (`mistral-medium-latest`) share one row. Local LM Studio models
+//! are free. Update the table when providers change their prices; costs
+//! already written to meta files are not recomputed. Prompt-cache writes and
+//! reads are priced relative to the input price, as Anthropic bills them.
+
+use std::sync::Mutex;
+
+/// Price of one model, in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl Price {
+    /// Cost in USD of a call with the given token counts
+    pub fn cost(&self, input_tokens: usize, output_tokens: usize) -> f64 {
+        (input_tokens as f64 * self.input_per_mtok + output_tokens as f64 * self.output_per_mtok)
+            / 1_000_000.0
+    }
+
+    /// Cost in USD of a call whose `input_tokens` include
+    /// `cache_write_tokens` written to and `cache_read_tokens` read from the
+    /// prompt cache, billed at [`CACHE_WRITE_FACTOR`] and
+    /// [`CACHE_READ_FACTOR`] times the input price
+    pub fn cached_cost(
+        &self,
+        input_tokens: usize,
+        cache_write_tokens: usize,
+        cache_read_tokens: usize,
+        output_tokens: usize,
+    ) -> f64 {
+        let uncached = input_tokens.saturating_sub(cache_write_tokens + cache_read_tokens) as f64;
+        let input = uncached
+            + cache_write_tokens as f64 * CACHE_WRITE_FACTOR
+            + cache_read_tokens as f64 * CACHE_READ_FACTOR;
+        (input * self.input_per_mtok + output_tokens as f64 * self.output_per_mtok) / 1_000_000.0
+    }
+}
+
+/// Price of a prompt-cache write (5-minute lifetime), relative to the input
+/// price
+pub const CACHE_WRITE_FACTOR: f64 = 1.25;
+
+/// Price of a prompt-cache read, relative to the input price
+pub const CACHE_READ_FACTOR: f64 = 0.1;
+
+/// Characters per token assumed by [`estimate_tokens`]. On this study's
+/// prompts the Claude and Mistral tokenizers give 2.7 (code-heavy
+/// `test_guided` prompts) to 4.2 (prose `baseline` prompts) characters per
+/// token; 3 errs towards overestimating.
+pub const CHARS_PER_TOKEN: usize = 3;
+
+/// Heuristic input-token count of `text`, for projections made without
+/// calling the provider
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// (provider, model-id prefix, input USD/MTok, output USD/MTok)
+const PRICES: &[(&str, &str, f64, f64)] = &[
+    ("anthropic", "claude-3-haiku", 0.25, 1.25),
+    ("anthropic", "claude-3-5-haiku", 0.80, 4.0),
+    ("anthropic", "claude-haiku-4-5", 1.0, 5.0),
+    ("anthropic", "claude-3-5-sonnet", 3.0, 15.0),
+    ("anthropic", "claude-3-7-sonnet", 3.0, 15.0),
+    ("anthropic", "claude-sonnet-4", 3.0, 15.0),
+    ("anthropic", "claude-3-opus", 15.0, 75.0),
+    ("anthropic", "claude-opus-4", 15.0, 75.0),
+    ("anthropic", "claude-opus-4-5", 5.0, 25.0),
+    ("mistral", "mistral-large", 2.0, 6.0),
+    ("mistral", "mistral-medium", 0.4, 2.0),
+    ("mistral", "mistral-small", 0.1, 0.3),
+    ("mistral", "codestral", 0.3, 0.9),
+    ("mistral", "devstral-small", 0.1, 0.3),
+    ("mistral", "devstral-medium", 0.4, 2.0),
+];
+
+/// Look up the price of `model` on `provider` (a normalized provider name:
+/// `anthropic`, `mistral` or `lmstudio`). Returns `None` for unknown models.
+pub fn price_for(provider: &str, model: &str) -> Option<Price> {
+    if provider == "lmstudio" {
+        return Some(Price {
+            input_per_mtok: 0.0,
+            output_per_mtok: 0.0,
+        });
+    }
+    PRICES
+        .iter()
+        .filter(|(p, prefix, _, _)| *p == provider && model.starts_with(prefix))
+        .max_by_key(|(_, prefix, _, _)| prefix.len())
+        .map(|&(_, _, input_per_mtok, output_per_mtok)| Price {
+            input_per_mtok,
+            output_per_mtok,
+        })
+}
+
+/// A spending limit shared by concurrent runs.
+///
+/// A run reserves its worst-case cost before calling the API and settles
+/// the reservation with its actual cost afterwards, so runs in flight can
+/// never jointly overshoot the limit. The first refused reservation closes
+/// the budget: later runs are refused too, even cheaper ones, so a batch
+/// stops at a clear point instead of skipping around.
+#[derive(Debug)]
+pub struct CostBudget {
+    limit_usd: f64,
+    state: Mutex<BudgetState>,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    spent: f64,
+    reserved: f64,
+    closed: bool,
+}
+
+impl CostBudget {
+    pub fn new(limit_usd: f64) -> Self {
+        Self {
+            limit_usd,
+            state: Mutex::new(BudgetState::default()),
+        }
+    }
+
+    /// Reserve `estimate_usd`, or return `false` (and close the budget) if
+    /// that could take spending past the limit
+    pub fn try_reserve(&self, estimate_usd: f64) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.closed || state.spent + state.reserved + estimate_usd > self.limit_usd {
+            state.closed = true;
+            return false;
+        }
+        state.reserved += estimate_usd;
+        true
+    }
+
+    /// Replace a reservation of `estimate_usd` with the `actual_usd` spent
+    /// (0 if the call failed before being billed)
+    pub fn settle(&self, estimate_usd: f64, actual_usd: f64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.reserved -= estimate_usd;
+        state.spent += actual_usd;
+    }
+
+    /// Total settled spending so far
+    pub fn spent(&self) -> f64 {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).spent
+    }
+
+    pub fn limit(&self) -> f64 {
+        self.limit_usd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let opus_4 = price_for("anthropic", "claude-opus-4-20250514").unwrap();
+        let opus_4_5 = price_for("anthropic", "claude-opus-4-5-20251101").unwrap();
+        assert_eq!(opus_4.input_per_mtok, 15.0);
+        assert_eq!(opus_4_5.input_per_mtok, 5.0);
+        assert_eq!(opus_4_5.output_per_mtok, 25.0);
+    }
+
+    #[test]
+    fn prices_are_per_provider() {
+        assert!(price_for("mistral", "claude-sonnet-4-20250514").is_none());
+        assert!(price_for("anthropic", "gpt-4o").is_none());
+        let medium = price_for("mistral", "mistral-medium-latest").unwrap();
+        assert_eq!(medium.output_per_mtok, 2.0);
+    }
+
+    #[test]
+    fn local_models_are_free() {
+        let price = price_for("lmstudio", "qwen2.5-coder-32b").unwrap();
+        assert_eq!(price.cost(1_000_000, 1_000_000), 0.0);
+    }
+
+    #[test]
+    fn cost_is_per_million_tokens() {
+        let sonnet = price_for("anthropic", "claude-sonnet-4-20250514").unwrap();
+        assert_close(sonnet.cost(1_000_000, 0), 3.0);
+        assert_close(sonnet.cost(2_000, 1_000), 0.006 + 0.015);
+    }
+
+    #[test]
+    fn cached_tokens_are_billed_at_their_factors() {
+        let price = Price {
+            input_per_mtok: 10.0,
+            output_per_mtok: 0.0,
+        };
+        // 100k plain, 200k written to and 700k read from the cache
+        let cost = price.cached_cost(1_000_000, 200_000, 700_000, 0);
+        let expected = 1.0 + 2.0 * CACHE_WRITE_FACTOR + 7.0 * CACHE_READ_FACTOR;
+        assert_close(cost, expected);
+        assert_close(price.cached_cost(1_000, 0, 0, 0), price.cost(1_000, 0));
+    }
+
+    #[test]
+    fn cache_counts_beyond_the_input_do_not_go_negative() {
+        let price = Price {
+            input_per_mtok: 1.0,
+            output_per_mtok: 0.0,
+        };
+        let cost = price.cached_cost(10, 0, 1_000_000, 0);
+        assert_close(cost, CACHE_READ_FACTOR);
+    }
+
+    #[test]
+    fn token_estimate_rounds_up_by_character() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcd"), 2);
+        // Characters, not bytes: three 3-byte characters are one token
+        assert_eq!(estimate_tokens("→→→"), 1);
+    }
+
+    #[test]
+    fn a_refused_reservation_closes_the_budget() {
+        let budget = CostBudget::new(1.0);
+        assert!(budget.try_reserve(0.6));
+        assert!(!budget.try_reserve(0.5));
+        assert!(
+            !budget.try_reserve(0.1),
+            "cheaper runs are refused too once closed"
+        );
+        budget.settle(0.6, 0.25);
+        assert_close(budget.spent(), 0.25);
+        assert!(!budget.try_reserve(0.1));
+    }
+
+    #[test]
+    fn settling_frees_the_unspent_reservation() {
+        let budget = CostBudget::new(1.0);
+        assert!(budget.try_reserve(0.9));
+        budget.settle(0.9, 0.1);
+        assert!(budget.try_reserve(0.9));
+        assert_close(budget.limit(), 1.0);
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Defines the core abstraction for LLM providers.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -10,18 +10,122 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmResponse {
     pub content: String,
+    /// All input tokens, including those written to or read from the
+    /// prompt cache
     pub input_tokens: usize,
     pub output_tokens: usize,
+    /// Input tokens written to the provider's prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: usize,
+    /// Input tokens read from the provider's prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: usize,
+    /// Reasoning returned apart from the answer (summarized by some models)
+    #[serde(default)]
+    pub thinking: Option<String>,
+    /// Of `output_tokens`, those spent on reasoning, when the provider
+    /// reports them
+    #[serde(default)]
+    pub thinking_tokens: Option<usize>,
+    /// Whether generation stopped at `max_tokens` rather than at the end of
+    /// the answer
+    #[serde(default)]
+    pub truncated: bool,
     pub model: String,
     pub provider: String,
 }
 
+impl LlmResponse {
+    /// Take in `next`, the continuation of this response: its content (the
+    /// stitched answer) replaces this one's, and the usage adds up
+    pub fn extend(&mut self, next: LlmResponse) {
+        self.content = next.content;
+        self.input_tokens += next.input_tokens;
+        self.output_tokens += next.output_tokens;
+        self.cache_write_tokens += next.cache_write_tokens;
+        self.cache_read_tokens += next.cache_read_tokens;
+        self.thinking = match (self.thinking.take(), next.thinking) {
+            (Some(thinking), Some(more)) => Some(format!("{}\n\n{}", thinking, more)),
+            (thinking, more) => thinking.or(more),
+        };
+        self.thinking_tokens = match (self.thinking_tokens, next.thinking_tokens) {
+            (Some(tokens), Some(more)) => Some(tokens + more),
+            (tokens, more) => tokens.or(more),
+        };
+        self.truncated = next.truncated;
+    }
+}
+
+/// Response of an embeddings request
+#[derive(Debug, Clone)]
+pub struct Embeddings {
+    /// One vector per input, in the order of the inputs
+    pub vectors: Vec<Vec<f32>>,
+    pub input_tokens: usize,
+    pub model: String,
+}
+
+/// Author of a conversation message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        }
+    }
+}
+
+/// One message of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
 /// Configuration for LLM requests
 #[derive(Debug, Clone)]
 pub struct RequestConfig {
     pub model: Option<String>,
     pub max_tokens: u32,
     pub temperature: f32,
+    /// Nucleus sampling cutoff (`None` leaves the provider default)
+    pub top_p: Option<f32>,
+    /// System prompt sent ahead of the conversation (`None` for none)
+    pub system: Option<String>,
+    /// Ask the provider to cache the system prompt and the conversation so
+    /// far, for later requests sharing them as a prefix (Anthropic only)
+    pub cache: bool,
+    /// Extended-thinking budget in tokens (Anthropic; `None` for no thinking)
+    pub thinking_budget: Option<u32>,
+    /// Sampling seed, for providers that sample deterministically given one
+    /// (Mistral, LM Studio; `None` for none)
+    pub seed: Option<u64>,
+    /// Ask for the answer as a JSON object conforming to
+    /// [`structured::schema`](crate::structured::schema)
+    pub structured: bool,
 }
 
 impl Default for RequestConfig {
@@ -30,8 +134,74 @@ impl Default for RequestConfig {
             model: None,
             max_tokens: 8192,
             temperature: 0.0, // Deterministic for reproducibility
+            top_p: None,
+            system: None,
+            cache: false,
+            thinking_budget: None,
+            seed: None,
+            structured: false,
+        }
+    }
+}
+
+/// Request for the rest of an answer cut off at `max_tokens`, for providers
+/// that cannot continue an assistant message in place
+pub const CONTINUE_PROMPT: &str = "Your answer was cut off. Continue exactly where it stopped, \
+without repeating anything and without restarting the code block you were in.";
+
+/// Shortest overlap `stitch` removes, so that a continuation starting with
+/// `}` does not lose it to a partial ending with one
+const MIN_OVERLAP: usize = 16;
+
+/// `partial`, an answer cut off at `max_tokens`, followed by `continuation`,
+/// a new message that may restart the code block the answer was in or
+/// repeat its last lines
+pub fn stitch(partial: &str, continuation: &str) -> String {
+    let mut continuation = continuation;
+    // An odd number of fence lines: the partial stopped inside a block
+    let fences = partial.lines().filter(|line| line.trim_start().starts_with("```")).count();
+    if fences % 2 == 1 {
+        let trimmed = continuation.trim_start_matches(['\n', '\r']);
+        if trimmed.starts_with("```") {
+            continuation = trimmed.split_once('\n').map_or("", |(_, rest)| rest);
+        }
+    }
+    // The longest end of the partial the continuation starts with
+    let longest = partial.len().min(continuation.len()).min(2000);
+    let overlap = (MIN_OVERLAP..=longest).rev().find(|&n| {
+        partial.is_char_boundary(partial.len() - n)
+            && continuation.is_char_boundary(n)
+            && partial.ends_with(&continuation[..n])
+    });
+    format!("{}{}", partial, &continuation[overlap.unwrap_or(0)..])
+}
+
+/// Continue `partial` with a further user turn asking for the rest, and
+/// [`stitch`] the answer on
+pub async fn ask_to_continue<P: LlmProvider + ?Sized>(
+    provider: &P,
+    messages: &[Message],
+    partial: &str,
+    config: &RequestConfig,
+) -> Result<LlmResponse> {
+    let mut conversation = messages.to_vec();
+    conversation.push(Message::assistant(partial));
+    conversation.push(Message::user(CONTINUE_PROMPT));
+    let mut response = provider.chat(&conversation, config).await?;
+    response.content = stitch(partial, &response.content);
+    Ok(response)
+}
+
+/// Split the reasoning of models that write it inline, as
+/// `<think>...</think>` ahead of the answer, from the answer
+pub fn split_think_tags(content: String) -> (String, Option<String>) {
+    let trimmed = content.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("<think>") {
+        if let Some((thinking, answer)) = rest.split_once("</think>") {
+            return (answer.trim_start().to_string(), Some(thinking.trim().to_string()));
         }
     }
+    (content, None)
 }
 
 /// Trait for LLM providers
@@ -40,6 +210,185 @@ pub trait LlmProvider: Send + Sync {
     /// Default model for this provider
     fn default_model(&self) -> &str;
 
-    /// Send a completion request
-    async fn complete(&self, prompt: &str, config: &RequestConfig) -> Result<LlmResponse>;
+    /// Send a conversation: messages alternating between user and assistant,
+    /// starting and ending with the user
+    async fn chat(&self, messages: &[Message], config: &RequestConfig) -> Result<LlmResponse>;
+
+    /// Send a single-prompt completion request
+    async fn complete(&self, prompt: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        self.chat(&[Message::user(prompt)], config).await
+    }
+
+    /// Continue `partial`, the answer to `messages` cut off at `max_tokens`;
+    /// the response's content is the whole answer, `partial` included. By
+    /// default, [`ask_to_continue`]
+    async fn continue_chat(&self, messages: &[Message], partial: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        ask_to_continue(self, messages, partial, config).await
+    }
+
+    /// Model used for fill-in-the-middle when no `--model` is given, `None`
+    /// if the provider has no FIM endpoint
+    fn fim_model(&self) -> Option<&str> {
+        None
+    }
+
+    /// Ask a code model for what goes between `prefix` and `suffix`
+    async fn fill_in_middle(&self, prefix: &str, suffix: &str, config: &RequestConfig) -> Result<LlmResponse> {
+        let _ = (prefix, suffix, config);
+        Err(anyhow!("Fill-in-the-middle is not supported by this provider"))
+    }
+
+    /// Model used for embeddings when none is given, `None` if the provider
+    /// has no embeddings endpoint or picks the model when asked
+    fn embedding_model(&self) -> Option<&str> {
+        None
+    }
+
+    /// Embed each of `inputs` with `model` (default: the provider's)
+    async fn embed(&self, inputs: &[&str], model: Option<&str>) -> Result<Embeddings> {
+        let _ = (inputs, model);
+        Err(anyhow!("Embeddings are not supported by this provider"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn response(content: &str, truncated: bool) -> LlmResponse {
+        LlmResponse {
+            content: content.to_string(),
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+            thinking: None,
+            thinking_tokens: None,
+            truncated,
+            model: "model".to_string(),
+            provider: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn stitch_drops_a_restarted_code_block() {
+        let partial = "Here it is:\n\n```rust\nfn one() {}\n";
+        let continuation = "```rust\nfn two() {}\n```\n";
+        assert_eq!(
+            stitch(partial, continuation),
+            "Here it is:\n\n```rust\nfn one() {}\nfn two() {}\n```\n"
+        );
+    }
+
+    #[test]
+    fn stitch_keeps_a_new_block_after_a_closed_one() {
+        let partial = "```rust\nfn one() {}\n```\n\nAnd the tests:\n";
+        let continuation = "```rust\n#[test]\nfn t() {}\n```";
+        assert_eq!(
+            stitch(partial, continuation),
+            format!("{}{}", partial, continuation)
+        );
+    }
+
+    #[test]
+    fn stitch_removes_repeated_lines() {
+        let partial = "fn one() {}\nfn two() -> usize {\n    let total = ";
+        let continuation = "fn two() -> usize {\n    let total = 2;\n    total\n}\n";
+        assert_eq!(
+            stitch(partial, continuation),
+            "fn one() {}\nfn two() -> usize {\n    let total = 2;\n    total\n}\n"
+        );
+    }
+
+    #[test]
+    fn stitch_keeps_short_coincidental_overlaps() {
+        // The partial ends with `}` and the continuation starts with one
+        let partial = "    if a {\n        b();\n    }";
+        let continuation = "\n}";
+        assert_eq!(stitch(partial, continuation), format!("{}\n}}", partial));
+        assert_eq!(stitch("x }", "}"), "x }}");
+    }
+
+    #[test]
+    fn stitch_respects_character_boundaries() {
+        let partial = "// naïve → heap, ordered by";
+        let continuation = "→ heap, ordered by priority";
+        assert_eq!(
+            stitch(partial, continuation),
+            "// naïve → heap, ordered by priority"
+        );
+    }
+
+    #[test]
+    fn extend_adds_up_usage_and_keeps_the_last_truncation() {
+        let mut first = response("part one", true);
+        first.thinking = Some("first thought".to_string());
+        first.thinking_tokens = Some(10);
+        let mut next = response("part one, part two", false);
+        next.cache_read_tokens = 80;
+        next.thinking = Some("second thought".to_string());
+        first.extend(next);
+        assert_eq!(first.content, "part one, part two");
+        assert_eq!(first.input_tokens, 200);
+        assert_eq!(first.output_tokens, 100);
+        assert_eq!(first.cache_read_tokens, 80);
+        assert_eq!(
+            first.thinking.as_deref(),
+            Some("first thought\n\nsecond thought")
+        );
+        assert_eq!(first.thinking_tokens, Some(10));
+        assert!(!first.truncated);
+    }
+
+    /// Answers every chat with `reply`, recording the conversations sent
+    struct Scripted {
+        reply: &'static str,
+        sent: Mutex<Vec<Vec<Message>>>,
+    }
+
+    #[async_trait]
+    impl LlmProvider for Scripted {
+        fn default_model(&self) -> &str {
+            "scripted"
+        }
+
+        async fn chat(&self, messages: &[Message], _: &RequestConfig) -> Result<LlmResponse> {
+            self.sent.lock().unwrap().push(messages.to_vec());
+            Ok(response(self.reply, false))
+        }
+    }
+
+    #[tokio::test]
+    async fn default_continuation_asks_for_the_rest_and_stitches_it() {
+        let provider = Scripted {
+            reply: "```go\nfunc b() {}\n```",
+            sent: Mutex::new(Vec::new()),
+        };
+        let messages = [Message::user("Write a heap")];
+        let partial = "```go\nfunc a() {}\n";
+        let config = RequestConfig::default();
+        let continued = provider
+            .continue_chat(&messages, partial, &config)
+            .await
+            .unwrap();
+        assert_eq!(continued.content, "```go\nfunc a() {}\nfunc b() {}\n```");
+
+        let sent = provider.sent.lock().unwrap();
+        let roles: Vec<Role> = sent[0].iter().map(|message| message.role).collect();
+        assert_eq!(roles, [Role::User, Role::Assistant, Role::User]);
+        assert_eq!(sent[0][1].content, partial);
+        assert_eq!(sent[0][2].content, CONTINUE_PROMPT);
+    }
+
+    #[test]
+    fn think_tags_are_split_from_the_answer() {
+        let (answer, thinking) =
+            split_think_tags("<think>\nplan it\n</think>\n\nfn main() {}".to_string());
+        assert_eq!(answer, "fn main() {}");
+        assert_eq!(thinking.as_deref(), Some("plan it"));
+        let (answer, thinking) = split_think_tags("<think>never closed".to_string());
+        assert_eq!(answer, "<think>never closed");
+        assert_eq!(thinking, None);
+    }
 }
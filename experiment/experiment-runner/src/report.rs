@@ -0,0 +1,373 @@
+//! Aggregate Reports
+//!
+//! Joins every `{prefix}_meta.json` of a results directory with the test
+//! count of its generated code into one tidy table, one row per run, ready
+//! for R / pandas without assembling a spreadsheet by hand.
+//!
+//! The test count comes from the run's `{prefix}_analysis.json` (written by
+//! `analyze`); when that file is missing, the `{prefix}_code.{ext}` file is
+//! analyzed in memory (without similarity scores, which need the reference
+//! implementations). Fields that older meta files lack (elapsed time,
+//! sampling parameters, thinking, seed, replicate, cost, compile and test
+//! outcomes) are left empty rather than guessed.
+
+use crate::analysis::{self, CodeAnalysis};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Output format of `report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+/// The fields of a meta file that go into the report
+#[derive(Debug, Deserialize)]
+struct Meta {
+    condition: String,
+    language: String,
+    model: String,
+    provider: String,
+    timestamp: String,
+    input_tokens: usize,
+    output_tokens: usize,
+    #[serde(default)]
+    elapsed_ms: Option<u64>,
+    #[serde(default)]
+    temperature: Option<f32>,
+    #[serde(default)]
+    top_p: Option<f32>,
+    #[serde(default)]
+    thinking_budget: Option<u32>,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    thinking_tokens: Option<usize>,
+    #[serde(default)]
+    truncated: Option<bool>,
+    #[serde(default)]
+    parts: Option<u32>,
+    #[serde(default)]
+    replicate: Option<u32>,
+    #[serde(default)]
+    cost_usd: Option<f64>,
+    #[serde(default)]
+    compile: Option<MetaCompile>,
+    #[serde(default)]
+    tests: Option<MetaTests>,
+    #[serde(default)]
+    structured: Option<MetaStructured>,
+    #[serde(default)]
+    detected_language: Option<MetaDetection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaCompile {
+    success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaStructured {
+    valid: bool,
+    #[serde(default)]
+    test_count: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaDetection {
+    language: String,
+    confidence: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaTests {
+    passed: usize,
+    failed: usize,
+    timed_out: bool,
+}
+
+/// One run of the report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportRow {
+    /// File prefix shared by the run's result files
+    pub run: String,
+    pub condition: String,
+    pub language: String,
+    pub model: String,
+    pub provider: String,
+    pub temperature: Option<f32>,
+    /// `None` when the provider default was used (or not recorded)
+    pub top_p: Option<f32>,
+    /// Extended-thinking budget, `None` without thinking
+    pub thinking_budget: Option<u32>,
+    /// Sampling seed, `None` when none was sent
+    pub seed: Option<u64>,
+    pub replicate: Option<u32>,
+    pub timestamp: String,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// Of `output_tokens`, those spent thinking (possibly estimated)
+    pub thinking_tokens: Option<usize>,
+    /// Whether the answer ended at `max_tokens`, and the requests it was
+    /// stitched from with `--continuations`
+    pub truncated: Option<bool>,
+    pub parts: Option<u32>,
+    pub elapsed_ms: Option<u64>,
+    pub cost_usd: Option<f64>,
+    /// Tests in the generated code, `None` if it could not be analyzed
+    pub test_count: Option<usize>,
+    /// Similarity to the reference implementation, from `analyze`
+    pub token_ratio: Option<f64>,
+    pub ast_overlap: Option<f64>,
+    pub identifier_jaccard: Option<f64>,
+    /// Embedding cosine similarity, from `analyze --embeddings`
+    pub embedding_cosine: Option<f64>,
+    /// `--compile-check` verdict
+    pub compiles: Option<bool>,
+    /// `--run-tests` outcome
+    pub tests_passed: Option<usize>,
+    pub tests_failed: Option<usize>,
+    pub tests_timed_out: Option<bool>,
+    /// `--structured`: whether the JSON answer conformed to the schema, and
+    /// the test count the model reported in it
+    pub structured_valid: Option<bool>,
+    pub reported_test_count: Option<usize>,
+    /// Language guessed from the code of an untagged block, and the share of
+    /// language markers behind the guess; differs from `language` on a mixup
+    pub detected_language: Option<String>,
+    pub language_confidence: Option<f64>,
+}
+
+const CSV_HEADER: &[&str] = &[
+    "run",
+    "condition",
+    "language",
+    "model",
+    "provider",
+    "temperature",
+    "top_p",
+    "thinking_budget",
+    "seed",
+    "replicate",
+    "timestamp",
+    "input_tokens",
+    "output_tokens",
+    "thinking_tokens",
+    "truncated",
+    "parts",
+    "elapsed_ms",
+    "cost_usd",
+    "test_count",
+    "token_ratio",
+    "ast_overlap",
+    "identifier_jaccard",
+    "embedding_cosine",
+    "compiles",
+    "tests_passed",
+    "tests_failed",
+    "tests_timed_out",
+    "structured_valid",
+    "reported_test_count",
+    "detected_language",
+    "language_confidence",
+];
+
+/// Mean, standard deviation and range of one measurement across runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Summary {
+    pub n: usize,
+    pub mean: f64,
+    /// Sample standard deviation (n - 1 denominator), 0 for a single run
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    /// Summarize `values`, or `None` if there are none
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len();
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let stddev = if n > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Some(Self {
+            n,
+            mean,
+            stddev,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+/// The files of one run in a results directory
+#[derive(Debug, Clone)]
+pub struct RunFiles {
+    pub meta: PathBuf,
+    /// `None` for files that do not exist
+    pub prompt: Option<PathBuf>,
+    pub response: Option<PathBuf>,
+    pub code: Option<PathBuf>,
+}
+
+/// Build the report rows of every run in `dir`, sorted by run
+pub fn collect(dir: &Path) -> Result<Vec<ReportRow>> {
+    Ok(collect_runs(dir)?.into_iter().map(|(row, _)| row).collect())
+}
+
+/// [`collect`], with the files each row was built from
+pub fn collect_runs(dir: &Path) -> Result<Vec<(ReportRow, RunFiles)>> {
+    let mut prefixes: Vec<String> = Vec::new();
+    let mut code_files: HashMap<String, (PathBuf, &'static str)> = HashMap::new();
+    for entry in std::fs::read_dir(dir)
+        .map_err(|e| anyhow!("Failed to read results directory {}: {}", dir.display(), e))?
+    {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Some(prefix) = file_name.strip_suffix("_meta.json") {
+            prefixes.push(prefix.to_string());
+        } else if let Some((prefix, language)) = analysis::code_file_prefix(file_name) {
+            code_files.insert(prefix.to_string(), (path.clone(), language));
+        }
+    }
+    prefixes.sort();
+
+    let mut rows = Vec::with_capacity(prefixes.len());
+    for prefix in prefixes {
+        let code = code_files.get(&prefix).map(|(path, language)| (path.as_path(), *language));
+        rows.push(row(dir, &prefix, code)?);
+    }
+    Ok(rows)
+}
+
+/// Build the report row of the run `prefix` in `dir` from its meta file and
+/// its analysis, or its code file (`path`, language) when it has not been
+/// analyzed
+pub fn row(dir: &Path, prefix: &str, code: Option<(&Path, &str)>) -> Result<(ReportRow, RunFiles)> {
+    let meta_path = dir.join(format!("{}_meta.json", prefix));
+    let meta: Meta = serde_json::from_str(&std::fs::read_to_string(&meta_path)?)
+        .map_err(|e| anyhow!("Invalid meta file {}: {}", meta_path.display(), e))?;
+
+    let analysis_path = dir.join(format!("{}_analysis.json", prefix));
+    let analysis: Option<CodeAnalysis> = if analysis_path.exists() {
+        Some(
+            serde_json::from_str(&std::fs::read_to_string(&analysis_path)?)
+                .map_err(|e| anyhow!("Invalid analysis file {}: {}", analysis_path.display(), e))?,
+        )
+    } else if let Some((code_path, language)) = code {
+        Some(CodeAnalysis::of(language, &std::fs::read_to_string(code_path)?))
+    } else {
+        None
+    };
+
+    let existing = |suffix: &str| Some(dir.join(format!("{}_{}", prefix, suffix))).filter(|path| path.exists());
+    let files = RunFiles {
+        meta: meta_path,
+        prompt: existing("prompt.md"),
+        response: existing("response.md"),
+        code: code.map(|(path, _)| path.to_path_buf()),
+    };
+
+    let similarity = analysis.as_ref().and_then(|analysis| analysis.similarity.as_ref());
+    let row = ReportRow {
+        run: prefix.to_string(),
+        condition: meta.condition,
+        language: meta.language,
+        model: meta.model,
+        provider: meta.provider,
+        temperature: meta.temperature,
+        top_p: meta.top_p,
+        thinking_budget: meta.thinking_budget,
+        seed: meta.seed,
+        replicate: meta.replicate,
+        timestamp: meta.timestamp,
+        input_tokens: meta.input_tokens,
+        output_tokens: meta.output_tokens,
+        thinking_tokens: meta.thinking_tokens,
+        truncated: meta.truncated,
+        parts: meta.parts,
+        elapsed_ms: meta.elapsed_ms,
+        cost_usd: meta.cost_usd,
+        test_count: analysis.as_ref().and_then(|analysis| analysis.test_count),
+        token_ratio: similarity.map(|similarity| similarity.token_ratio),
+        ast_overlap: similarity.and_then(|similarity| similarity.ast_overlap),
+        identifier_jaccard: similarity.map(|similarity| similarity.identifier_jaccard),
+        embedding_cosine: similarity.and_then(|similarity| similarity.embedding.as_ref()).map(|e| e.cosine),
+        compiles: meta.compile.map(|compile| compile.success),
+        tests_passed: meta.tests.as_ref().map(|tests| tests.passed),
+        tests_failed: meta.tests.as_ref().map(|tests| tests.failed),
+        tests_timed_out: meta.tests.as_ref().map(|tests| tests.timed_out),
+        structured_valid: meta.structured.as_ref().map(|structured| structured.valid),
+        reported_test_count: meta.structured.as_ref().and_then(|structured| structured.test_count),
+        language_confidence: meta.detected_language.as_ref().map(|detection| detection.confidence),
+        detected_language: meta.detected_language.map(|detection| detection.language),
+    };
+    Ok((row, files))
+}
+
+/// `field` as a CSV field, quoted if it needs to be
+pub(crate) fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render `rows` as CSV with a header line; missing values are empty cells
+pub fn to_csv(rows: &[ReportRow]) -> String {
+    fn cell<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+    let mut out = CSV_HEADER.join(",");
+    out.push('\n');
+    for row in rows {
+        let fields = [
+            quote(&row.run),
+            quote(&row.condition),
+            quote(&row.language),
+            quote(&row.model),
+            quote(&row.provider),
+            cell(row.temperature),
+            cell(row.top_p),
+            cell(row.thinking_budget),
+            cell(row.seed),
+            cell(row.replicate),
+            quote(&row.timestamp),
+            row.input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            cell(row.thinking_tokens),
+            cell(row.truncated),
+            cell(row.parts),
+            cell(row.elapsed_ms),
+            cell(row.cost_usd),
+            cell(row.test_count),
+            cell(row.token_ratio),
+            cell(row.ast_overlap),
+            cell(row.identifier_jaccard),
+            cell(row.embedding_cosine),
+            cell(row.compiles),
+            cell(row.tests_passed),
+            cell(row.tests_failed),
+            cell(row.tests_timed_out),
+            cell(row.structured_valid),
+            cell(row.reported_test_count),
+            row.detected_language.as_deref().map(quote).unwrap_or_default(),
+            cell(row.language_confidence),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,150 @@
+//! Structured output: the answer as a JSON object instead of fenced code
+//!
+//! With `--structured`, the prompt ends with [`INSTRUCTIONS`] and providers
+//! constrain the answer to [`schema`]: Anthropic through a forced call of the
+//! [`TOOL_NAME`] tool, Mistral and LM Studio through a JSON-schema
+//! [`response_format`]. [`validate`] then checks the answer against the
+//! schema by hand, recording every violation, and recovers what it can: the
+//! code, the number of tests the model says it wrote, and its notes. Whether
+//! the self-reported count matches the tests actually in the code is what
+//! sets structured elicitation apart from free-form code fences.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Name of the tool, and of the schema, the answer is given through
+pub const TOOL_NAME: &str = "submit_solution";
+
+/// What the tool is for, as the model sees it
+pub const TOOL_DESCRIPTION: &str = "Submit the complete solution: the source code, the number of tests it contains, and any notes.";
+
+/// Appended to the prompt of a structured run, so that every provider is
+/// told the same thing whatever its native mechanism
+pub const INSTRUCTIONS: &str = "\
+Answer with a single JSON object and nothing else, with these fields:
+
+- `code`: the complete source code as one string, tests included if you write any, without Markdown code fences
+- `test_count`: the number of test cases in `code` (0 if there are none)
+- `notes`: anything else you want to say, or an empty string";
+
+const FIELDS: [&str; 3] = ["code", "test_count", "notes"];
+
+/// JSON schema of the answer; every field is required and no other is
+/// allowed, as OpenAI-style strict mode demands
+pub fn schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "code": {
+                "type": "string",
+                "description": "The complete source code, tests included if any, without Markdown code fences"
+            },
+            "test_count": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Number of test cases in the code"
+            },
+            "notes": {
+                "type": "string",
+                "description": "Anything else about the solution, or an empty string"
+            }
+        },
+        "required": FIELDS,
+        "additionalProperties": false
+    })
+}
+
+/// The `response_format` of an OpenAI-compatible request (Mistral, LM Studio)
+/// constraining the answer to [`schema`]
+pub fn response_format() -> Value {
+    json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": TOOL_NAME,
+            "schema": schema(),
+            "strict": true
+        }
+    })
+}
+
+/// An answer checked against [`schema`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Validation {
+    /// Whether the answer is a JSON object conforming to the schema
+    pub valid: bool,
+    /// Every way the answer departs from the schema
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// The code, when the answer holds it as a string, valid or not
+    #[serde(skip)]
+    pub code: Option<String>,
+    /// Number of tests the model says the code holds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Check `content`, the text of a structured answer, against [`schema`]
+///
+/// A JSON object wrapped in a single code fence, as local models tend to
+/// send despite the instructions, is accepted but recorded as an error.
+pub fn validate(content: &str) -> Validation {
+    let mut validation = Validation::default();
+    let trimmed = content.trim();
+    let value = match serde_json::from_str::<Value>(trimmed) {
+        Ok(value) => value,
+        Err(e) => match unfence(trimmed).and_then(|inner| serde_json::from_str::<Value>(inner).ok()) {
+            Some(value) => {
+                validation.errors.push("JSON wrapped in a code fence".to_string());
+                value
+            }
+            None => {
+                validation.errors.push(format!("not JSON: {}", e));
+                return validation;
+            }
+        },
+    };
+    let Value::Object(object) = value else {
+        validation.errors.push("not a JSON object".to_string());
+        return validation;
+    };
+
+    validation.code = string_field(&object, "code", &mut validation.errors);
+    validation.notes = string_field(&object, "notes", &mut validation.errors);
+    match object.get("test_count") {
+        None => validation.errors.push("missing `test_count`".to_string()),
+        Some(count) => match count.as_u64().and_then(|count| usize::try_from(count).ok()) {
+            Some(count) => validation.test_count = Some(count),
+            None => validation.errors.push(format!("`test_count` is not a non-negative integer: {}", count)),
+        },
+    }
+    for key in object.keys().filter(|key| !FIELDS.contains(&key.as_str())) {
+        validation.errors.push(format!("unexpected field `{}`", key));
+    }
+
+    validation.valid = validation.errors.is_empty();
+    validation
+}
+
+/// The string field `name` of `object`, recording in `errors` why there is none
+fn string_field(object: &Map<String, Value>, name: &str, errors: &mut Vec<String>) -> Option<String> {
+    match object.get(name) {
+        None => {
+            errors.push(format!("missing `{}`", name));
+            None
+        }
+        Some(Value::String(text)) => Some(text.clone()),
+        Some(_) => {
+            errors.push(format!("`{}` is not a string", name));
+            None
+        }
+    }
+}
+
+/// The inside of `text` when it is a single fenced block
+fn unfence(text: &str) -> Option<&str> {
+    let body = text.strip_prefix("```")?.strip_suffix("```")?;
+    let (_info, inner) = body.split_once('\n')?;
+    Some(inner)
+}
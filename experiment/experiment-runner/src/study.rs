@@ -0,0 +1,147 @@
+//! Study Definitions
+//!
+//! A study runs one condition and language across a list of models, to see
+//! in which model generation a behavior appears: the test-mimicking study
+//! asks when Claude started reproducing the tests it was shown. The models
+//! live in a TOML file passed with `--study`, so a new model generation is a
+//! new table rather than a recompile:
+//!
+//! ```toml
+//! title = "Test-mimicking emergence"
+//! provider = "anthropic"
+//! condition = "test_guided"
+//! language = "rust"
+//! max_tokens = 8192
+//!
+//! [[model]]
+//! id = "claude-3-haiku-20240307"
+//! max_tokens = 4096
+//! done = true
+//!
+//! [[model]]
+//! id = "claude-opus-4-20250514"
+//! note = "Opus 4 (May 2025)"
+//!
+//! [[reference]]
+//! model = "claude-sonnet-4-20250514"
+//! output_tokens = 6370
+//! tests = 22
+//! ```
+//!
+//! `provider` defaults to `anthropic`, `max_tokens` to 8192 and
+//! `replications` to 1; a model may override `max_tokens`. Models marked
+//! `done` were measured already and are skipped, but stay listed as the
+//! study's record. `reference` results are printed next to the study's own.
+
+use crate::batch::RunSpec;
+use crate::provider::RequestConfig;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// A study: one condition and language, across models
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Study {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "default_provider")]
+    pub provider: String,
+    pub condition: String,
+    pub language: String,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+    #[serde(default = "default_replications")]
+    pub replications: u32,
+    #[serde(rename = "model", default)]
+    pub models: Vec<StudyModel>,
+    #[serde(rename = "reference", default)]
+    pub references: Vec<Reference>,
+}
+
+/// A model of a study
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StudyModel {
+    pub id: String,
+    /// Overrides the study's `max_tokens`
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Free text, such as the release date or the result once `done`
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Measured already: listed, not run
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// A result from elsewhere to compare the study's against
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Reference {
+    pub model: String,
+    pub output_tokens: usize,
+    #[serde(default)]
+    pub tests: Option<usize>,
+}
+
+fn default_provider() -> String {
+    "anthropic".to_string()
+}
+
+fn default_max_tokens() -> u32 {
+    8192
+}
+
+fn default_replications() -> u32 {
+    1
+}
+
+impl Study {
+    /// Read and parse a study definition file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read study file {}: {}", path.display(), e))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid study file {}", path.display()))
+    }
+
+    /// Parse a study definition from TOML text
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let study: Self = toml::from_str(content)?;
+        if study.replications == 0 {
+            return Err(anyhow!("replications must be at least 1"));
+        }
+        Ok(study)
+    }
+
+    /// The models still to run, in file order
+    pub fn pending(&self) -> impl Iterator<Item = &StudyModel> {
+        self.models.iter().filter(|model| !model.done)
+    }
+
+    /// One run per pending model × replicate, model by model
+    pub fn runs(&self) -> Vec<RunSpec> {
+        let mut specs = Vec::new();
+        for model in self.pending() {
+            for replicate in 1..=self.replications {
+                specs.push(RunSpec {
+                    provider: self.provider.clone(),
+                    condition: self.condition.clone(),
+                    language: self.language.clone(),
+                    model: Some(model.id.clone()),
+                    max_tokens: model.max_tokens.unwrap_or(self.max_tokens),
+                    temperature: RequestConfig::default().temperature,
+                    top_p: None,
+                    thinking_budget: None,
+                    replicate: (self.replications > 1).then_some(replicate),
+                    seed: None,
+                    system: None,
+                    repair_turns: 0,
+                    structured: false,
+                });
+            }
+        }
+        specs
+    }
+}
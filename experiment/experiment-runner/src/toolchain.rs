@@ -0,0 +1,347 @@
+//! Compile Checks and Test Runs for Generated Code
+//!
+//! Runs each language's own toolchain over extracted code, in a scratch
+//! directory, so "did it compile" and "do its tests pass" are recorded next
+//! to the token counts:
+//!
+//! | Language | Compile check | Test run |
+//! |----------|---------------|----------|
+//! | rust | `cargo check` on a one-file library crate | `cargo test` |
+//! | go | `go vet` | `go test -v` (code saved as a `_test.go` file) |
+//! | cpp | `g++ -std=c++17 -fsyntax-only` | — |
+//! | typescript | `tsc --noEmit` | — |
+//! | zig | `zig build-obj` | `zig test` |
+//!
+//! C++ and TypeScript tests need a framework (GoogleTest, vitest) installed
+//! around the code, so they have no test run yet.
+//!
+//! The toolchains must be on `PATH` (or, for [`Sandbox::Docker`], `docker`
+//! must be); a missing one is an error, not a failed check, so it cannot be
+//! mistaken for a model's mistake.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Diagnostics beyond this many bytes are cut, to keep meta files readable
+const MAX_DIAGNOSTICS: usize = 16 * 1024;
+
+const RUST_MANIFEST: &str =
+    "[package]\nname = \"generated\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n[lib]\npath = \"src/lib.rs\"\n";
+
+/// Outcome of compiling one generated file
+#[derive(Debug, Clone, Serialize)]
+pub struct CompileCheck {
+    /// The command that was run, for the record
+    pub command: String,
+    pub success: bool,
+    /// Compiler output (stderr, then stdout), truncated to 16 KiB
+    pub diagnostics: String,
+    pub elapsed_ms: u128,
+}
+
+/// Where generated tests are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Sandbox {
+    /// Directly, with the toolchains on this machine
+    Local,
+    /// In a throwaway container with no network access
+    Docker,
+}
+
+/// Outcome of running the tests contained in one generated file
+#[derive(Debug, Clone, Serialize)]
+pub struct TestRun {
+    /// The command that was run, for the record
+    pub command: String,
+    pub sandbox: Sandbox,
+    /// Whether the test command exited successfully
+    pub success: bool,
+    pub passed: usize,
+    pub failed: usize,
+    /// Killed after exceeding the timeout (counts are then unreliable)
+    pub timed_out: bool,
+    /// Toolchain output (stderr, then stdout), truncated to 16 KiB
+    pub output: String,
+    pub elapsed_ms: u128,
+}
+
+/// A directory under the system temp dir, removed on drop
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new(purpose: &str) -> Result<Self> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "experiment-runner-{}-{}-{}",
+            purpose,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compile-check `code` written in `language` (a normalized language name).
+///
+/// Blocking: call it from `spawn_blocking` inside async code.
+pub fn compile_check(language: &str, code: &str) -> Result<CompileCheck> {
+    let scratch = ScratchDir::new("check")?;
+    let dir = scratch.path();
+
+    let (program, args): (&str, Vec<String>) = match language {
+        "rust" => {
+            write_rust_crate(dir, code)?;
+            ("cargo", vec!["check".into(), "--quiet".into(), "--message-format=short".into()])
+        }
+        "go" => {
+            std::fs::write(dir.join("generated.go"), code)?;
+            ("go", vec!["vet".into(), "generated.go".into()])
+        }
+        "cpp" => {
+            std::fs::write(dir.join("generated.hpp"), code)?;
+            (
+                "g++",
+                vec!["-std=c++17".into(), "-fsyntax-only".into(), "-x".into(), "c++".into(), "generated.hpp".into()],
+            )
+        }
+        "typescript" => {
+            std::fs::write(dir.join("generated.ts"), code)?;
+            (
+                "tsc",
+                vec!["--noEmit".into(), "--target".into(), "es2020".into(), "generated.ts".into()],
+            )
+        }
+        "zig" => {
+            std::fs::write(dir.join("generated.zig"), code)?;
+            ("zig", vec!["build-obj".into(), "-fno-emit-bin".into(), "generated.zig".into()])
+        }
+        _ => return Err(anyhow!("No compile check for language: {}", language)),
+    };
+
+    let mut command = Command::new(program);
+    command.args(&args).current_dir(dir);
+    // Own process group, so the terminal's Ctrl-C reaches the batch (which
+    // lets runs in flight finish) and not the check
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let start = Instant::now();
+    let output = command
+        .output()
+        .map_err(|e| anyhow!("Failed to run {} (is it installed and on PATH?): {}", program, e))?;
+    let elapsed = start.elapsed();
+
+    Ok(CompileCheck {
+        command: format!("{} {}", program, args.join(" ")),
+        success: output.status.success(),
+        diagnostics: combined_output(&output.stderr, &output.stdout),
+        elapsed_ms: elapsed.as_millis(),
+    })
+}
+
+/// Run the tests contained in `code` written in `language` (a normalized
+/// language name), killing the run after `timeout`.
+///
+/// Code without tests yields a successful run with zero tests, which is
+/// itself a data point for the test-mimicking study. Blocking: call it from
+/// `spawn_blocking` inside async code.
+pub fn run_tests(language: &str, code: &str, sandbox: Sandbox, timeout: Duration) -> Result<TestRun> {
+    let scratch = ScratchDir::new("test")?;
+    let dir = scratch.path();
+
+    let (image, program, args): (&str, &str, Vec<String>) = match language {
+        "rust" => {
+            write_rust_crate(dir, code)?;
+            ("rust:1", "cargo", vec!["test".into(), "--quiet".into()])
+        }
+        "go" => {
+            std::fs::write(dir.join("go.mod"), "module generated\n\ngo 1.21\n")?;
+            std::fs::write(dir.join("generated_test.go"), code)?;
+            ("golang:1", "go", vec!["test".into(), "-v".into(), "./...".into()])
+        }
+        "zig" => {
+            std::fs::write(dir.join("generated.zig"), code)?;
+            ("", "zig", vec!["test".into(), "generated.zig".into()])
+        }
+        _ => return Err(anyhow!("No test harness for language: {}", language)),
+    };
+
+    let mut command = match sandbox {
+        Sandbox::Local => {
+            let mut command = Command::new(program);
+            command.args(&args).current_dir(dir);
+            command
+        }
+        Sandbox::Docker => {
+            if image.is_empty() {
+                return Err(anyhow!("No Docker image for language: {}", language));
+            }
+            let mut command = Command::new("docker");
+            command
+                .args(["run", "--rm", "--network", "none", "--name"])
+                .arg(container_name(dir))
+                .arg("-v")
+                .arg(format!("{}:/work", dir.display()))
+                .args(["-w", "/work", image, program])
+                .args(&args);
+            command
+        }
+    };
+    // Own process group, so a timeout can kill the test binary that `cargo
+    // test` / `go test` spawn, not just the driver, and the terminal's Ctrl-C
+    // reaches the batch (which lets runs in flight finish) and not the tests
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let runner = if sandbox == Sandbox::Docker { "docker" } else { program };
+
+    let start = Instant::now();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run {} (is it installed and on PATH?): {}", runner, e))?;
+
+    // Drain both pipes on their own threads so a chatty test cannot block
+    // on a full pipe while we wait for it
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            timed_out = true;
+            match sandbox {
+                // Killing the CLI would leave the container running
+                Sandbox::Docker => {
+                    let _ = Command::new("docker").args(["kill"]).arg(container_name(dir)).output();
+                }
+                Sandbox::Local => kill_tree(child.id()),
+            }
+            let _ = child.kill();
+            break child.wait()?;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let elapsed = start.elapsed();
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let text = format!("{}{}", String::from_utf8_lossy(&stderr), String::from_utf8_lossy(&stdout));
+    let (passed, failed) = count_tests(language, &text);
+
+    Ok(TestRun {
+        command: format!("{} {}", program, args.join(" ")),
+        sandbox,
+        success: status.success() && !timed_out,
+        passed,
+        failed,
+        timed_out,
+        output: combined_output(&stderr, &stdout),
+        elapsed_ms: elapsed.as_millis(),
+    })
+}
+
+fn write_rust_crate(dir: &Path, code: &str) -> Result<()> {
+    std::fs::create_dir_all(dir.join("src"))?;
+    std::fs::write(dir.join("Cargo.toml"), RUST_MANIFEST)?;
+    std::fs::write(dir.join("src").join("lib.rs"), code)?;
+    Ok(())
+}
+
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Kill a process and everything it spawned, since a test binary left
+/// running would also hold our output pipes open
+fn kill_tree(pid: u32) {
+    #[cfg(unix)]
+    let _ = Command::new("kill").args(["-s", "KILL", "--"]).arg(format!("-{}", pid)).output();
+    #[cfg(windows)]
+    let _ = Command::new("taskkill").args(["/T", "/F", "/PID"]).arg(pid.to_string()).output();
+}
+
+fn container_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "experiment-runner-test".to_string())
+}
+
+/// Passed and failed test counts from a test runner's output
+fn count_tests(language: &str, output: &str) -> (usize, usize) {
+    let mut passed = 0;
+    let mut failed = 0;
+    for line in output.lines().map(str::trim) {
+        match language {
+            // test result: FAILED. 20 passed; 2 failed; 0 ignored; ...
+            "rust" if line.starts_with("test result:") => {
+                passed += number_before(line, " passed").unwrap_or(0);
+                failed += number_before(line, " failed").unwrap_or(0);
+            }
+            // --- PASS: TestInsert (0.00s), also for subtests
+            "go" if line.starts_with("--- PASS:") => passed += 1,
+            "go" if line.starts_with("--- FAIL:") => failed += 1,
+            // All 22 tests passed.  |  20 passed; 0 skipped; 2 failed.
+            "zig" if line.starts_with("All ") && line.contains(" tests passed") => {
+                passed += number_before(line, " tests passed").unwrap_or(0);
+            }
+            "zig" if line.contains(" passed;") && line.contains(" failed") => {
+                passed += number_before(line, " passed").unwrap_or(0);
+                failed += number_before(line, " failed").unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+    (passed, failed)
+}
+
+/// The integer written immediately before the first occurrence of `word`
+fn number_before(line: &str, word: &str) -> Option<usize> {
+    let end = line.find(word)?;
+    let start = line[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    line[start..end].parse().ok()
+}
+
+/// stderr then stdout, truncated to `MAX_DIAGNOSTICS`
+fn combined_output(stderr: &[u8], stdout: &[u8]) -> String {
+    let mut text = String::from_utf8_lossy(stderr).into_owned();
+    text.push_str(&String::from_utf8_lossy(stdout));
+    if text.len() > MAX_DIAGNOSTICS {
+        let mut cut = MAX_DIAGNOSTICS;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        text.truncate(cut);
+        text.push_str("\n[truncated]");
+    }
+    text
+}
@@ -0,0 +1,98 @@
+// Test corpus for decrease_priority() operation
+// Spec: specifications/decrease_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#include "test_common.h"
+
+// =============================================================================
+// decrease_priority() Tests
+// =============================================================================
+
+// Test: decrease_priority_postcondition_priority_changed
+// Spec: specifications/decrease_priority.md
+// Property: item's priority is updated to the new value
+TEST(DecreasePriorityTest, Postcondition_PriorityChanged) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"target", 10});
+    pq.insert(Item{"other", 30});
+    EXPECT_EQ(pq.front().id, "target");
+    EXPECT_EQ(pq.front().priority, 10);
+    // min-heap: a higher value is a lower priority
+    pq.decrease_priority(Item{"target", 50});
+    EXPECT_EQ(pq.front().id, "other");
+    EXPECT_EQ(pq.front().priority, 30);
+    pq.pop();
+    EXPECT_EQ(pq.front().id, "target");
+    EXPECT_EQ(pq.front().priority, 50);
+}
+
+// Test: decrease_priority_invariant_heap_property
+// Spec: specifications/decrease_priority.md
+// Property: heap invariant holds after a priority decrease
+TEST(DecreasePriorityTest, Invariant_HeapProperty) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 10});
+    pq.insert(Item{"b", 30});
+    pq.insert(Item{"c", 50});
+    pq.insert(Item{"d", 70});
+    pq.insert(Item{"e", 20});
+    pq.insert(Item{"f", 40});
+    EXPECT_EQ(pq.front().id, "a");
+    EXPECT_EQ(pq.front().priority, 10);
+    pq.decrease_priority(Item{"a", 100});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    EXPECT_EQ(pq.front().id, "e");
+    EXPECT_EQ(pq.front().priority, 20);
+}
+
+// Test: decrease_priority_position_item_moves_down
+// Spec: specifications/decrease_priority.md
+// Property: item moves toward the leaves and is no longer front
+TEST(DecreasePriorityTest, Position_ItemMovesDown) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"root", 10});
+    pq.insert(Item{"child1", 50});
+    pq.insert(Item{"child2", 60});
+    pq.insert(Item{"child3", 70});
+    EXPECT_EQ(pq.front().id, "root");
+    EXPECT_EQ(pq.front().priority, 10);
+    pq.decrease_priority(Item{"root", 100});
+    EXPECT_NE(pq.front().id, "root");
+    EXPECT_EQ(pq.front().id, "child1");
+    EXPECT_EQ(pq.front().priority, 50);
+}
+
+// Test: decrease_priority_size_unchanged
+// Spec: specifications/decrease_priority.md
+// Property: size is unchanged by a priority update
+TEST(DecreasePriorityTest, Size_Unchanged) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 10});
+    pq.insert(Item{"b", 30});
+    pq.insert(Item{"c", 50});
+    EXPECT_EQ(pq.len(), 3u);
+    pq.decrease_priority(Item{"a", 100});
+    EXPECT_EQ(pq.len(), 3u);
+}
+
+// Test: decrease_priority_edge_not_found
+// Spec: specifications/decrease_priority.md
+// Property: updating an item that is not in the heap reports not-found
+TEST(DecreasePriorityTest, Edge_NotFound) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"existing", 50});
+    {
+        auto result = pq.try_decrease_priority(Item{"nonexistent", 100});
+        ASSERT_FALSE(result.has_value());
+        EXPECT_EQ(result.error(), TOOLS::Error::ItemNotFound);
+    }
+    EXPECT_EQ(pq.len(), 1u);
+    EXPECT_EQ(pq.front().id, "existing");
+    EXPECT_EQ(pq.front().priority, 50);
+}
@@ -0,0 +1,67 @@
+// Test corpus for front() operation
+// Spec: specifications/front.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#include "test_common.h"
+
+// =============================================================================
+// front() Tests
+// =============================================================================
+
+// Test: front_postcondition_returns_minimum
+// Spec: specifications/front.md
+// Property: front() returns the item with lowest priority value without removing it
+TEST(FrontTest, Postcondition_ReturnsMinimum) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 30});
+    pq.insert(Item{"b", 10});
+    pq.insert(Item{"c", 20});
+    EXPECT_EQ(pq.front().id, "b");
+    EXPECT_EQ(pq.front().priority, 10);
+    EXPECT_TRUE(pq.contains(Item{"b", 999}));
+}
+
+// Test: front_invariant_no_modification
+// Spec: specifications/front.md
+// Property: front() does not modify the heap: repeated calls return the same item
+TEST(FrontTest, Invariant_NoModification) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 30});
+    pq.insert(Item{"b", 10});
+    pq.insert(Item{"c", 20});
+    EXPECT_EQ(pq.front().id, "b");
+    EXPECT_EQ(pq.front().priority, 10);
+    EXPECT_EQ(pq.front().id, "b");
+    EXPECT_EQ(pq.front().priority, 10);
+    EXPECT_EQ(pq.front().id, "b");
+    EXPECT_EQ(pq.front().priority, 10);
+    EXPECT_TRUE(heapInvariantHolds(pq));
+}
+
+// Test: front_size_unchanged
+// Spec: specifications/front.md
+// Property: size is unchanged by front()
+TEST(FrontTest, Size_Unchanged) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 10});
+    pq.insert(Item{"b", 20});
+    pq.insert(Item{"c", 30});
+    EXPECT_EQ(pq.len(), 3u);
+    EXPECT_EQ(pq.front().id, "a");
+    EXPECT_EQ(pq.front().priority, 10);
+    EXPECT_EQ(pq.len(), 3u);
+}
+
+// Test: front_edge_empty_peek_returns_nothing
+// Spec: specifications/front.md
+// Property: peek() on an empty heap reports nothing
+TEST(FrontTest, Edge_EmptyPeekReturnsNothing) {
+    TestPriorityQueue pq(4);
+
+    EXPECT_FALSE(pq.peek().has_value());
+    EXPECT_TRUE(pq.is_empty());
+}
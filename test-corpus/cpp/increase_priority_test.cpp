@@ -0,0 +1,92 @@
+// Test corpus for increase_priority() operation
+// Spec: specifications/increase_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#include "test_common.h"
+
+// =============================================================================
+// increase_priority() Tests
+// =============================================================================
+
+// Test: increase_priority_postcondition_priority_changed
+// Spec: specifications/increase_priority.md
+// Property: item's priority is updated to the new value
+TEST(IncreasePriorityTest, Postcondition_PriorityChanged) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"target", 50});
+    pq.insert(Item{"other", 30});
+    EXPECT_EQ(pq.front().id, "other");
+    EXPECT_EQ(pq.front().priority, 30);
+    // min-heap: a lower value is a higher priority
+    pq.increase_priority(Item{"target", 10});
+    EXPECT_EQ(pq.front().id, "target");
+    EXPECT_EQ(pq.front().priority, 10);
+}
+
+// Test: increase_priority_invariant_heap_property
+// Spec: specifications/increase_priority.md
+// Property: heap invariant holds after a priority increase
+TEST(IncreasePriorityTest, Invariant_HeapProperty) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 80});
+    pq.insert(Item{"b", 60});
+    pq.insert(Item{"c", 40});
+    pq.insert(Item{"d", 20});
+    pq.insert(Item{"e", 100});
+    pq.insert(Item{"f", 50});
+    EXPECT_EQ(pq.front().id, "d");
+    EXPECT_EQ(pq.front().priority, 20);
+    pq.increase_priority(Item{"a", 5});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    EXPECT_EQ(pq.front().id, "a");
+    EXPECT_EQ(pq.front().priority, 5);
+}
+
+// Test: increase_priority_position_item_moves_up
+// Spec: specifications/increase_priority.md
+// Property: item moves toward the root and becomes front if highest
+TEST(IncreasePriorityTest, Position_ItemMovesUp) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"root", 10});
+    pq.insert(Item{"middle", 50});
+    pq.insert(Item{"leaf", 100});
+    EXPECT_NE(pq.front().id, "leaf");
+    pq.increase_priority(Item{"leaf", 1});
+    EXPECT_EQ(pq.front().id, "leaf");
+    EXPECT_EQ(pq.front().priority, 1);
+}
+
+// Test: increase_priority_size_unchanged
+// Spec: specifications/increase_priority.md
+// Property: size is unchanged by a priority update
+TEST(IncreasePriorityTest, Size_Unchanged) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 50});
+    pq.insert(Item{"b", 30});
+    pq.insert(Item{"c", 70});
+    EXPECT_EQ(pq.len(), 3u);
+    pq.increase_priority(Item{"c", 10});
+    EXPECT_EQ(pq.len(), 3u);
+}
+
+// Test: increase_priority_edge_not_found
+// Spec: specifications/increase_priority.md
+// Property: updating an item that is not in the heap reports not-found
+TEST(IncreasePriorityTest, Edge_NotFound) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"existing", 50});
+    {
+        auto result = pq.try_increase_priority(Item{"nonexistent", 10});
+        ASSERT_FALSE(result.has_value());
+        EXPECT_EQ(result.error(), TOOLS::Error::ItemNotFound);
+    }
+    EXPECT_EQ(pq.len(), 1u);
+    EXPECT_EQ(pq.front().id, "existing");
+    EXPECT_EQ(pq.front().priority, 50);
+}
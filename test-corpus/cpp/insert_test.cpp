@@ -0,0 +1,79 @@
+// Test corpus for insert() operation
+// Spec: specifications/insert.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#include "test_common.h"
+
+// =============================================================================
+// insert() Tests
+// =============================================================================
+
+// Test: insert_postcondition_item_findable
+// Spec: specifications/insert.md
+// Property: inserted item can be found via contains() after insertion
+TEST(InsertTest, Postcondition_ItemFindable) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"test-item", 50});
+    // identity is the id alone: a lookup with any priority finds the item
+    EXPECT_TRUE(pq.contains(Item{"test-item", 999}));
+    EXPECT_FALSE(pq.contains(Item{"other-item", 999}));
+}
+
+// Test: insert_invariant_heap_property
+// Spec: specifications/insert.md
+// Property: heap invariant holds after every insertion
+TEST(InsertTest, Invariant_HeapProperty) {
+    TestPriorityQueue pq(4);
+
+    // insert items in arbitrary order
+    pq.insert(Item{"a", 30});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    pq.insert(Item{"b", 10});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    pq.insert(Item{"c", 50});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    pq.insert(Item{"d", 20});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    pq.insert(Item{"e", 40});
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    EXPECT_EQ(pq.front().id, "b");
+    EXPECT_EQ(pq.front().priority, 10);
+}
+
+// Test: insert_size_increments
+// Spec: specifications/insert.md
+// Property: heap size increases by 1 after each insertion
+TEST(InsertTest, Size_Increments) {
+    TestPriorityQueue pq(4);
+
+    EXPECT_EQ(pq.len(), 0u);
+    pq.insert(Item{"item0", 0});
+    EXPECT_EQ(pq.len(), 1u);
+    pq.insert(Item{"item1", 10});
+    EXPECT_EQ(pq.len(), 2u);
+    pq.insert(Item{"item2", 20});
+    EXPECT_EQ(pq.len(), 3u);
+    pq.insert(Item{"item3", 30});
+    EXPECT_EQ(pq.len(), 4u);
+    pq.insert(Item{"item4", 40});
+    EXPECT_EQ(pq.len(), 5u);
+}
+
+// Test: insert_edge_becomes_front_if_highest_priority
+// Spec: specifications/insert.md
+// Property: if inserted item has highest priority, it becomes front()
+TEST(InsertTest, Edge_BecomesFrontIfHighestPriority) {
+    TestPriorityQueue pq(4);
+
+    // decreasing priority values = increasing importance in a min-heap
+    pq.insert(Item{"low", 100});
+    pq.insert(Item{"medium", 50});
+    pq.insert(Item{"high", 10});
+    EXPECT_EQ(pq.front().id, "high");
+    EXPECT_EQ(pq.front().priority, 10);
+    pq.insert(Item{"urgent", 1});
+    EXPECT_EQ(pq.front().id, "urgent");
+    EXPECT_EQ(pq.front().priority, 1);
+}
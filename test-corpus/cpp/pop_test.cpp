@@ -0,0 +1,102 @@
+// Test corpus for pop() operation
+// Spec: specifications/pop.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#include "test_common.h"
+
+// =============================================================================
+// pop() Tests
+// =============================================================================
+
+// Test: pop_postcondition_returns_minimum
+// Spec: specifications/pop.md
+// Property: pop() removes and returns the item with lowest priority value
+TEST(PopTest, Postcondition_ReturnsMinimum) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 30});
+    pq.insert(Item{"b", 10});
+    pq.insert(Item{"c", 20});
+    {
+        auto popped = pq.pop_front();
+        ASSERT_TRUE(popped.has_value());
+        EXPECT_EQ(popped->id, "b");
+        EXPECT_EQ(popped->priority, 10);
+    }
+    EXPECT_FALSE(pq.contains(Item{"b", 999}));
+}
+
+// Test: pop_invariant_maintains_heap_property
+// Spec: specifications/pop.md
+// Property: after each pop(), the heap invariant holds and front() is the minimum
+TEST(PopTest, Invariant_MaintainsHeapProperty) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 50});
+    pq.insert(Item{"b", 20});
+    pq.insert(Item{"c", 80});
+    pq.insert(Item{"d", 10});
+    pq.insert(Item{"e", 60});
+    pq.insert(Item{"f", 30});
+    pq.insert(Item{"g", 70});
+    pq.insert(Item{"h", 40});
+    {
+        auto popped = pq.pop_front();
+        ASSERT_TRUE(popped.has_value());
+        EXPECT_EQ(popped->id, "d");
+        EXPECT_EQ(popped->priority, 10);
+    }
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    {
+        auto popped = pq.pop_front();
+        ASSERT_TRUE(popped.has_value());
+        EXPECT_EQ(popped->id, "b");
+        EXPECT_EQ(popped->priority, 20);
+    }
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    {
+        auto popped = pq.pop_front();
+        ASSERT_TRUE(popped.has_value());
+        EXPECT_EQ(popped->id, "f");
+        EXPECT_EQ(popped->priority, 30);
+    }
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    {
+        auto popped = pq.pop_front();
+        ASSERT_TRUE(popped.has_value());
+        EXPECT_EQ(popped->id, "h");
+        EXPECT_EQ(popped->priority, 40);
+    }
+    EXPECT_TRUE(heapInvariantHolds(pq));
+    EXPECT_EQ(pq.front().id, "a");
+    EXPECT_EQ(pq.front().priority, 50);
+}
+
+// Test: pop_size_decrements
+// Spec: specifications/pop.md
+// Property: size decreases by 1 after each successful pop()
+TEST(PopTest, Size_Decrements) {
+    TestPriorityQueue pq(4);
+
+    pq.insert(Item{"a", 10});
+    pq.insert(Item{"b", 20});
+    pq.insert(Item{"c", 30});
+    pq.pop();
+    EXPECT_EQ(pq.len(), 2u);
+    pq.pop();
+    EXPECT_EQ(pq.len(), 1u);
+    pq.pop();
+    EXPECT_EQ(pq.len(), 0u);
+    EXPECT_TRUE(pq.is_empty());
+}
+
+// Test: pop_edge_empty_returns_nothing
+// Spec: specifications/pop.md
+// Property: pop() on an empty heap reports nothing and leaves it empty
+TEST(PopTest, Edge_EmptyReturnsNothing) {
+    TestPriorityQueue pq(4);
+
+    EXPECT_FALSE(pq.pop_front().has_value());
+    EXPECT_TRUE(pq.is_empty());
+}
@@ -0,0 +1,47 @@
+// Test corpus common definitions
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+#pragma once
+
+#include <gtest/gtest.h>
+#include <cstddef>
+#include <functional>
+#include <string>
+#include "PriorityQueue.h"
+
+// Test item type with separate ID (identity) and priority
+struct Item {
+    std::string id;
+    int priority;
+
+    // Item equality is based on ID only (not priority)
+    bool operator==(const Item& other) const { return id == other.id; }
+};
+
+// Hash is based on ID only (not priority)
+struct ItemHash {
+    std::size_t operator()(const Item& item) const { return std::hash<std::string>{}(item.id); }
+};
+
+// Min-heap: lower priority value = higher importance
+struct ItemMinComparator {
+    bool operator()(const Item& a, const Item& b) const { return a.priority < b.priority; }
+};
+
+using TestPriorityQueue = TOOLS::PriorityQueue<Item, ItemHash, ItemMinComparator>;
+
+// Helper: every parent's priority is <= each of its children's
+inline bool heapInvariantHolds(const TestPriorityQueue& pq) {
+    const auto arr = pq.to_array();
+    const std::size_t d = pq.d();
+    for (std::size_t i = 0; i < arr.size(); ++i) {
+        for (std::size_t j = 1; j <= d; ++j) {
+            const std::size_t child = i * d + j;
+            if (child < arr.size() && arr[i].priority > arr[child].priority) {
+                return false;
+            }
+        }
+    }
+    return true;
+}
@@ -0,0 +1,145 @@
+// Test corpus for decrease_priority() operation
+// Spec: specifications/decrease_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+package corpus
+
+import (
+	"errors"
+	"testing"
+
+	dheap "github.com/PCfVW/d-Heap-priority-queue/Go/v2/src"
+)
+
+// =============================================================================
+// decrease_priority() Tests
+// =============================================================================
+
+// Test: decrease_priority_postcondition_priority_changed
+// Spec: specifications/decrease_priority.md
+// Property: item's priority is updated to the new value
+func TestDecreasePriority_Postcondition_PriorityChanged(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "target", Priority: 10})
+	pq.Insert(Item{ID: "other", Priority: 30})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "target" || front.Priority != 10 {
+		t.Errorf("expected Front() = {target 10}, got %+v", front)
+	}
+	// min-heap: a higher value is a lower priority
+	if err := pq.DecreasePriority(Item{ID: "target", Priority: 50}); err != nil {
+		t.Fatalf("DecreasePriority: %v", err)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "other" || front.Priority != 30 {
+		t.Errorf("expected Front() = {other 30}, got %+v", front)
+	}
+	pq.Pop()
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "target" || front.Priority != 50 {
+		t.Errorf("expected Front() = {target 50}, got %+v", front)
+	}
+}
+
+// Test: decrease_priority_invariant_heap_property
+// Spec: specifications/decrease_priority.md
+// Property: heap invariant holds after a priority decrease
+func TestDecreasePriority_Invariant_HeapProperty(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 10})
+	pq.Insert(Item{ID: "b", Priority: 30})
+	pq.Insert(Item{ID: "c", Priority: 50})
+	pq.Insert(Item{ID: "d", Priority: 70})
+	pq.Insert(Item{ID: "e", Priority: 20})
+	pq.Insert(Item{ID: "f", Priority: 40})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "a" || front.Priority != 10 {
+		t.Errorf("expected Front() = {a 10}, got %+v", front)
+	}
+	if err := pq.DecreasePriority(Item{ID: "a", Priority: 100}); err != nil {
+		t.Fatalf("DecreasePriority: %v", err)
+	}
+	verifyHeapInvariant(t, pq)
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "e" || front.Priority != 20 {
+		t.Errorf("expected Front() = {e 20}, got %+v", front)
+	}
+}
+
+// Test: decrease_priority_position_item_moves_down
+// Spec: specifications/decrease_priority.md
+// Property: item moves toward the leaves and is no longer front
+func TestDecreasePriority_Position_ItemMovesDown(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "root", Priority: 10})
+	pq.Insert(Item{ID: "child1", Priority: 50})
+	pq.Insert(Item{ID: "child2", Priority: 60})
+	pq.Insert(Item{ID: "child3", Priority: 70})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "root" || front.Priority != 10 {
+		t.Errorf("expected Front() = {root 10}, got %+v", front)
+	}
+	if err := pq.DecreasePriority(Item{ID: "root", Priority: 100}); err != nil {
+		t.Fatalf("DecreasePriority: %v", err)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID == "root" {
+		t.Errorf("expected Front() not to be "root"")
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "child1" || front.Priority != 50 {
+		t.Errorf("expected Front() = {child1 50}, got %+v", front)
+	}
+}
+
+// Test: decrease_priority_size_unchanged
+// Spec: specifications/decrease_priority.md
+// Property: size is unchanged by a priority update
+func TestDecreasePriority_Size_Unchanged(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 10})
+	pq.Insert(Item{ID: "b", Priority: 30})
+	pq.Insert(Item{ID: "c", Priority: 50})
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+	if err := pq.DecreasePriority(Item{ID: "a", Priority: 100}); err != nil {
+		t.Fatalf("DecreasePriority: %v", err)
+	}
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+}
+
+// Test: decrease_priority_edge_not_found
+// Spec: specifications/decrease_priority.md
+// Property: updating an item that is not in the heap reports not-found
+func TestDecreasePriority_Edge_NotFound(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "existing", Priority: 50})
+	if err := pq.DecreasePriority(Item{ID: "nonexistent", Priority: 100}); !errors.Is(err, dheap.ErrItemNotFound) {
+		t.Errorf("expected ErrItemNotFound, got %v", err)
+	}
+	if got := pq.Len(); got != 1 {
+		t.Errorf("expected Len() = 1, got %d", got)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "existing" || front.Priority != 50 {
+		t.Errorf("expected Front() = {existing 50}, got %+v", front)
+	}
+}
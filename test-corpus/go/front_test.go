@@ -0,0 +1,96 @@
+// Test corpus for front() operation
+// Spec: specifications/front.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+package corpus
+
+import (
+	"testing"
+)
+
+// =============================================================================
+// front() Tests
+// =============================================================================
+
+// Test: front_postcondition_returns_minimum
+// Spec: specifications/front.md
+// Property: front() returns the item with lowest priority value without removing it
+func TestFront_Postcondition_ReturnsMinimum(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 30})
+	pq.Insert(Item{ID: "b", Priority: 10})
+	pq.Insert(Item{ID: "c", Priority: 20})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "b" || front.Priority != 10 {
+		t.Errorf("expected Front() = {b 10}, got %+v", front)
+	}
+	if !pq.Contains(Item{ID: "b", Priority: 999}) {
+		t.Errorf("expected Contains("b") = true")
+	}
+}
+
+// Test: front_invariant_no_modification
+// Spec: specifications/front.md
+// Property: front() does not modify the heap: repeated calls return the same item
+func TestFront_Invariant_NoModification(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 30})
+	pq.Insert(Item{ID: "b", Priority: 10})
+	pq.Insert(Item{ID: "c", Priority: 20})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "b" || front.Priority != 10 {
+		t.Errorf("expected Front() = {b 10}, got %+v", front)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "b" || front.Priority != 10 {
+		t.Errorf("expected Front() = {b 10}, got %+v", front)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "b" || front.Priority != 10 {
+		t.Errorf("expected Front() = {b 10}, got %+v", front)
+	}
+	verifyHeapInvariant(t, pq)
+}
+
+// Test: front_size_unchanged
+// Spec: specifications/front.md
+// Property: size is unchanged by front()
+func TestFront_Size_Unchanged(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 10})
+	pq.Insert(Item{ID: "b", Priority: 20})
+	pq.Insert(Item{ID: "c", Priority: 30})
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "a" || front.Priority != 10 {
+		t.Errorf("expected Front() = {a 10}, got %+v", front)
+	}
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+}
+
+// Test: front_edge_empty_peek_returns_nothing
+// Spec: specifications/front.md
+// Property: peek() on an empty heap reports nothing
+func TestFront_Edge_EmptyPeekReturnsNothing(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	if got, ok := pq.Peek(); ok {
+		t.Errorf("expected Peek() on empty heap to return ok=false, got %+v", got)
+	}
+	if !pq.IsEmpty() {
+		t.Errorf("expected IsEmpty() = true")
+	}
+}
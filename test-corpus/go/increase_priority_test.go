@@ -0,0 +1,133 @@
+// Test corpus for increase_priority() operation
+// Spec: specifications/increase_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+package corpus
+
+import (
+	"errors"
+	"testing"
+
+	dheap "github.com/PCfVW/d-Heap-priority-queue/Go/v2/src"
+)
+
+// =============================================================================
+// increase_priority() Tests
+// =============================================================================
+
+// Test: increase_priority_postcondition_priority_changed
+// Spec: specifications/increase_priority.md
+// Property: item's priority is updated to the new value
+func TestIncreasePriority_Postcondition_PriorityChanged(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "target", Priority: 50})
+	pq.Insert(Item{ID: "other", Priority: 30})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "other" || front.Priority != 30 {
+		t.Errorf("expected Front() = {other 30}, got %+v", front)
+	}
+	// min-heap: a lower value is a higher priority
+	if err := pq.IncreasePriority(Item{ID: "target", Priority: 10}); err != nil {
+		t.Fatalf("IncreasePriority: %v", err)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "target" || front.Priority != 10 {
+		t.Errorf("expected Front() = {target 10}, got %+v", front)
+	}
+}
+
+// Test: increase_priority_invariant_heap_property
+// Spec: specifications/increase_priority.md
+// Property: heap invariant holds after a priority increase
+func TestIncreasePriority_Invariant_HeapProperty(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 80})
+	pq.Insert(Item{ID: "b", Priority: 60})
+	pq.Insert(Item{ID: "c", Priority: 40})
+	pq.Insert(Item{ID: "d", Priority: 20})
+	pq.Insert(Item{ID: "e", Priority: 100})
+	pq.Insert(Item{ID: "f", Priority: 50})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "d" || front.Priority != 20 {
+		t.Errorf("expected Front() = {d 20}, got %+v", front)
+	}
+	if err := pq.IncreasePriority(Item{ID: "a", Priority: 5}); err != nil {
+		t.Fatalf("IncreasePriority: %v", err)
+	}
+	verifyHeapInvariant(t, pq)
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "a" || front.Priority != 5 {
+		t.Errorf("expected Front() = {a 5}, got %+v", front)
+	}
+}
+
+// Test: increase_priority_position_item_moves_up
+// Spec: specifications/increase_priority.md
+// Property: item moves toward the root and becomes front if highest
+func TestIncreasePriority_Position_ItemMovesUp(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "root", Priority: 10})
+	pq.Insert(Item{ID: "middle", Priority: 50})
+	pq.Insert(Item{ID: "leaf", Priority: 100})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID == "leaf" {
+		t.Errorf("expected Front() not to be "leaf"")
+	}
+	if err := pq.IncreasePriority(Item{ID: "leaf", Priority: 1}); err != nil {
+		t.Fatalf("IncreasePriority: %v", err)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "leaf" || front.Priority != 1 {
+		t.Errorf("expected Front() = {leaf 1}, got %+v", front)
+	}
+}
+
+// Test: increase_priority_size_unchanged
+// Spec: specifications/increase_priority.md
+// Property: size is unchanged by a priority update
+func TestIncreasePriority_Size_Unchanged(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 50})
+	pq.Insert(Item{ID: "b", Priority: 30})
+	pq.Insert(Item{ID: "c", Priority: 70})
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+	if err := pq.IncreasePriority(Item{ID: "c", Priority: 10}); err != nil {
+		t.Fatalf("IncreasePriority: %v", err)
+	}
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+}
+
+// Test: increase_priority_edge_not_found
+// Spec: specifications/increase_priority.md
+// Property: updating an item that is not in the heap reports not-found
+func TestIncreasePriority_Edge_NotFound(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "existing", Priority: 50})
+	if err := pq.IncreasePriority(Item{ID: "nonexistent", Priority: 10}); !errors.Is(err, dheap.ErrItemNotFound) {
+		t.Errorf("expected ErrItemNotFound, got %v", err)
+	}
+	if got := pq.Len(); got != 1 {
+		t.Errorf("expected Len() = 1, got %d", got)
+	}
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "existing" || front.Priority != 50 {
+		t.Errorf("expected Front() = {existing 50}, got %+v", front)
+	}
+}
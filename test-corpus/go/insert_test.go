@@ -0,0 +1,141 @@
+// Test corpus for insert() operation
+// Spec: specifications/insert.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+package corpus
+
+import (
+	"testing"
+
+	dheap "github.com/PCfVW/d-Heap-priority-queue/Go/v2/src"
+)
+
+// Test item type
+type Item struct {
+	ID       string
+	Priority int
+}
+
+// Helper: create min-heap of Items
+func newItemMinHeap(d int) *dheap.PriorityQueue[Item, string] {
+	return dheap.New(dheap.Options[Item, string]{
+		D:            d,
+		Comparator:   dheap.MinBy(func(i Item) int { return i.Priority }),
+		KeyExtractor: func(i Item) string { return i.ID },
+	})
+}
+
+// Helper: verify heap invariant for min-heap (parent priority <= children)
+func verifyHeapInvariant(t *testing.T, pq *dheap.PriorityQueue[Item, string]) {
+	t.Helper()
+	arr := pq.ToArray()
+	d := pq.D()
+	for i := 0; i < len(arr); i++ {
+		for j := 1; j <= d; j++ {
+			childIdx := i*d + j
+			if childIdx < len(arr) && arr[i].Priority > arr[childIdx].Priority {
+				t.Fatalf("heap invariant violated: parent[%d].Priority=%d > child[%d].Priority=%d",
+					i, arr[i].Priority, childIdx, arr[childIdx].Priority)
+			}
+		}
+	}
+}
+
+// =============================================================================
+// insert() Tests
+// =============================================================================
+
+// Test: insert_postcondition_item_findable
+// Spec: specifications/insert.md
+// Property: inserted item can be found via contains() after insertion
+func TestInsert_Postcondition_ItemFindable(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "test-item", Priority: 50})
+	// identity is the id alone: a lookup with any priority finds the item
+	if !pq.Contains(Item{ID: "test-item", Priority: 999}) {
+		t.Errorf("expected Contains("test-item") = true")
+	}
+	if pq.Contains(Item{ID: "other-item", Priority: 999}) {
+		t.Errorf("expected Contains("other-item") = false")
+	}
+}
+
+// Test: insert_invariant_heap_property
+// Spec: specifications/insert.md
+// Property: heap invariant holds after every insertion
+func TestInsert_Invariant_HeapProperty(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	// insert items in arbitrary order
+	pq.Insert(Item{ID: "a", Priority: 30})
+	verifyHeapInvariant(t, pq)
+	pq.Insert(Item{ID: "b", Priority: 10})
+	verifyHeapInvariant(t, pq)
+	pq.Insert(Item{ID: "c", Priority: 50})
+	verifyHeapInvariant(t, pq)
+	pq.Insert(Item{ID: "d", Priority: 20})
+	verifyHeapInvariant(t, pq)
+	pq.Insert(Item{ID: "e", Priority: 40})
+	verifyHeapInvariant(t, pq)
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "b" || front.Priority != 10 {
+		t.Errorf("expected Front() = {b 10}, got %+v", front)
+	}
+}
+
+// Test: insert_size_increments
+// Spec: specifications/insert.md
+// Property: heap size increases by 1 after each insertion
+func TestInsert_Size_Increments(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	if got := pq.Len(); got != 0 {
+		t.Errorf("expected Len() = 0, got %d", got)
+	}
+	pq.Insert(Item{ID: "item0", Priority: 0})
+	if got := pq.Len(); got != 1 {
+		t.Errorf("expected Len() = 1, got %d", got)
+	}
+	pq.Insert(Item{ID: "item1", Priority: 10})
+	if got := pq.Len(); got != 2 {
+		t.Errorf("expected Len() = 2, got %d", got)
+	}
+	pq.Insert(Item{ID: "item2", Priority: 20})
+	if got := pq.Len(); got != 3 {
+		t.Errorf("expected Len() = 3, got %d", got)
+	}
+	pq.Insert(Item{ID: "item3", Priority: 30})
+	if got := pq.Len(); got != 4 {
+		t.Errorf("expected Len() = 4, got %d", got)
+	}
+	pq.Insert(Item{ID: "item4", Priority: 40})
+	if got := pq.Len(); got != 5 {
+		t.Errorf("expected Len() = 5, got %d", got)
+	}
+}
+
+// Test: insert_edge_becomes_front_if_highest_priority
+// Spec: specifications/insert.md
+// Property: if inserted item has highest priority, it becomes front()
+func TestInsert_Edge_BecomesFrontIfHighestPriority(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	// decreasing priority values = increasing importance in a min-heap
+	pq.Insert(Item{ID: "low", Priority: 100})
+	pq.Insert(Item{ID: "medium", Priority: 50})
+	pq.Insert(Item{ID: "high", Priority: 10})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "high" || front.Priority != 10 {
+		t.Errorf("expected Front() = {high 10}, got %+v", front)
+	}
+	pq.Insert(Item{ID: "urgent", Priority: 1})
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "urgent" || front.Priority != 1 {
+		t.Errorf("expected Front() = {urgent 1}, got %+v", front)
+	}
+}
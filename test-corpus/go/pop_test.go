@@ -0,0 +1,108 @@
+// Test corpus for pop() operation
+// Spec: specifications/pop.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+package corpus
+
+import (
+	"testing"
+)
+
+// =============================================================================
+// pop() Tests
+// =============================================================================
+
+// Test: pop_postcondition_returns_minimum
+// Spec: specifications/pop.md
+// Property: pop() removes and returns the item with lowest priority value
+func TestPop_Postcondition_ReturnsMinimum(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 30})
+	pq.Insert(Item{ID: "b", Priority: 10})
+	pq.Insert(Item{ID: "c", Priority: 20})
+	if got, ok := pq.Pop(); !ok || got.ID != "b" || got.Priority != 10 {
+		t.Errorf("expected Pop() = {b 10}, got %+v (ok=%v)", got, ok)
+	}
+	if pq.Contains(Item{ID: "b", Priority: 999}) {
+		t.Errorf("expected Contains("b") = false")
+	}
+}
+
+// Test: pop_invariant_maintains_heap_property
+// Spec: specifications/pop.md
+// Property: after each pop(), the heap invariant holds and front() is the minimum
+func TestPop_Invariant_MaintainsHeapProperty(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 50})
+	pq.Insert(Item{ID: "b", Priority: 20})
+	pq.Insert(Item{ID: "c", Priority: 80})
+	pq.Insert(Item{ID: "d", Priority: 10})
+	pq.Insert(Item{ID: "e", Priority: 60})
+	pq.Insert(Item{ID: "f", Priority: 30})
+	pq.Insert(Item{ID: "g", Priority: 70})
+	pq.Insert(Item{ID: "h", Priority: 40})
+	if got, ok := pq.Pop(); !ok || got.ID != "d" || got.Priority != 10 {
+		t.Errorf("expected Pop() = {d 10}, got %+v (ok=%v)", got, ok)
+	}
+	verifyHeapInvariant(t, pq)
+	if got, ok := pq.Pop(); !ok || got.ID != "b" || got.Priority != 20 {
+		t.Errorf("expected Pop() = {b 20}, got %+v (ok=%v)", got, ok)
+	}
+	verifyHeapInvariant(t, pq)
+	if got, ok := pq.Pop(); !ok || got.ID != "f" || got.Priority != 30 {
+		t.Errorf("expected Pop() = {f 30}, got %+v (ok=%v)", got, ok)
+	}
+	verifyHeapInvariant(t, pq)
+	if got, ok := pq.Pop(); !ok || got.ID != "h" || got.Priority != 40 {
+		t.Errorf("expected Pop() = {h 40}, got %+v (ok=%v)", got, ok)
+	}
+	verifyHeapInvariant(t, pq)
+	if front, err := pq.Front(); err != nil {
+		t.Fatalf("Front: %v", err)
+	} else if front.ID != "a" || front.Priority != 50 {
+		t.Errorf("expected Front() = {a 50}, got %+v", front)
+	}
+}
+
+// Test: pop_size_decrements
+// Spec: specifications/pop.md
+// Property: size decreases by 1 after each successful pop()
+func TestPop_Size_Decrements(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	pq.Insert(Item{ID: "a", Priority: 10})
+	pq.Insert(Item{ID: "b", Priority: 20})
+	pq.Insert(Item{ID: "c", Priority: 30})
+	pq.Pop()
+	if got := pq.Len(); got != 2 {
+		t.Errorf("expected Len() = 2, got %d", got)
+	}
+	pq.Pop()
+	if got := pq.Len(); got != 1 {
+		t.Errorf("expected Len() = 1, got %d", got)
+	}
+	pq.Pop()
+	if got := pq.Len(); got != 0 {
+		t.Errorf("expected Len() = 0, got %d", got)
+	}
+	if !pq.IsEmpty() {
+		t.Errorf("expected IsEmpty() = true")
+	}
+}
+
+// Test: pop_edge_empty_returns_nothing
+// Spec: specifications/pop.md
+// Property: pop() on an empty heap reports nothing and leaves it empty
+func TestPop_Edge_EmptyReturnsNothing(t *testing.T) {
+	pq := newItemMinHeap(4)
+
+	if got, ok := pq.Pop(); ok {
+		t.Errorf("expected Pop() on empty heap to return ok=false, got %+v", got)
+	}
+	if !pq.IsEmpty() {
+		t.Errorf("expected IsEmpty() = true")
+	}
+}
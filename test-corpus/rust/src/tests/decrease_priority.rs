@@ -0,0 +1,100 @@
+// Test corpus for decrease_priority() operation
+// Spec: specifications/decrease_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use super::{new_item_min_heap, assert_heap_invariant, Item};
+use d_ary_heap::Error;
+
+// =============================================================================
+// decrease_priority() Tests
+// =============================================================================
+
+/// Test: decrease_priority_postcondition_priority_changed
+/// Spec: specifications/decrease_priority.md
+/// Property: item's priority is updated to the new value
+#[test]
+fn decrease_priority_postcondition_priority_changed() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("target", 10));
+    pq.insert(Item::new("other", 30));
+    assert_eq!(pq.front().id, "target");
+    assert_eq!(pq.front().priority, 10);
+    // min-heap: a higher value is a lower priority
+    pq.decrease_priority(&Item::new("target", 50)).expect("item is in the heap");
+    assert_eq!(pq.front().id, "other");
+    assert_eq!(pq.front().priority, 30);
+    pq.pop();
+    assert_eq!(pq.front().id, "target");
+    assert_eq!(pq.front().priority, 50);
+}
+
+/// Test: decrease_priority_invariant_heap_property
+/// Spec: specifications/decrease_priority.md
+/// Property: heap invariant holds after a priority decrease
+#[test]
+fn decrease_priority_invariant_heap_property() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 10));
+    pq.insert(Item::new("b", 30));
+    pq.insert(Item::new("c", 50));
+    pq.insert(Item::new("d", 70));
+    pq.insert(Item::new("e", 20));
+    pq.insert(Item::new("f", 40));
+    assert_eq!(pq.front().id, "a");
+    assert_eq!(pq.front().priority, 10);
+    pq.decrease_priority(&Item::new("a", 100)).expect("item is in the heap");
+    assert_heap_invariant(&pq);
+    assert_eq!(pq.front().id, "e");
+    assert_eq!(pq.front().priority, 20);
+}
+
+/// Test: decrease_priority_position_item_moves_down
+/// Spec: specifications/decrease_priority.md
+/// Property: item moves toward the leaves and is no longer front
+#[test]
+fn decrease_priority_position_item_moves_down() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("root", 10));
+    pq.insert(Item::new("child1", 50));
+    pq.insert(Item::new("child2", 60));
+    pq.insert(Item::new("child3", 70));
+    assert_eq!(pq.front().id, "root");
+    assert_eq!(pq.front().priority, 10);
+    pq.decrease_priority(&Item::new("root", 100)).expect("item is in the heap");
+    assert_ne!(pq.front().id, "root");
+    assert_eq!(pq.front().id, "child1");
+    assert_eq!(pq.front().priority, 50);
+}
+
+/// Test: decrease_priority_size_unchanged
+/// Spec: specifications/decrease_priority.md
+/// Property: size is unchanged by a priority update
+#[test]
+fn decrease_priority_size_unchanged() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 10));
+    pq.insert(Item::new("b", 30));
+    pq.insert(Item::new("c", 50));
+    assert_eq!(pq.len(), 3);
+    pq.decrease_priority(&Item::new("a", 100)).expect("item is in the heap");
+    assert_eq!(pq.len(), 3);
+}
+
+/// Test: decrease_priority_edge_not_found
+/// Spec: specifications/decrease_priority.md
+/// Property: updating an item that is not in the heap reports not-found
+#[test]
+fn decrease_priority_edge_not_found() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("existing", 50));
+    assert_eq!(pq.decrease_priority(&Item::new("nonexistent", 100)), Err(Error::ItemNotFound));
+    assert_eq!(pq.len(), 1);
+    assert_eq!(pq.front().id, "existing");
+    assert_eq!(pq.front().priority, 50);
+}
@@ -0,0 +1,71 @@
+// Test corpus for front() operation
+// Spec: specifications/front.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use super::{new_item_min_heap, assert_heap_invariant, Item};
+
+// =============================================================================
+// front() Tests
+// =============================================================================
+
+/// Test: front_postcondition_returns_minimum
+/// Spec: specifications/front.md
+/// Property: front() returns the item with lowest priority value without removing it
+#[test]
+fn front_postcondition_returns_minimum() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 30));
+    pq.insert(Item::new("b", 10));
+    pq.insert(Item::new("c", 20));
+    assert_eq!(pq.front().id, "b");
+    assert_eq!(pq.front().priority, 10);
+    assert!(pq.contains(&Item::new("b", 999)));
+}
+
+/// Test: front_invariant_no_modification
+/// Spec: specifications/front.md
+/// Property: front() does not modify the heap: repeated calls return the same item
+#[test]
+fn front_invariant_no_modification() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 30));
+    pq.insert(Item::new("b", 10));
+    pq.insert(Item::new("c", 20));
+    assert_eq!(pq.front().id, "b");
+    assert_eq!(pq.front().priority, 10);
+    assert_eq!(pq.front().id, "b");
+    assert_eq!(pq.front().priority, 10);
+    assert_eq!(pq.front().id, "b");
+    assert_eq!(pq.front().priority, 10);
+    assert_heap_invariant(&pq);
+}
+
+/// Test: front_size_unchanged
+/// Spec: specifications/front.md
+/// Property: size is unchanged by front()
+#[test]
+fn front_size_unchanged() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 10));
+    pq.insert(Item::new("b", 20));
+    pq.insert(Item::new("c", 30));
+    assert_eq!(pq.len(), 3);
+    assert_eq!(pq.front().id, "a");
+    assert_eq!(pq.front().priority, 10);
+    assert_eq!(pq.len(), 3);
+}
+
+/// Test: front_edge_empty_peek_returns_nothing
+/// Spec: specifications/front.md
+/// Property: peek() on an empty heap reports nothing
+#[test]
+fn front_edge_empty_peek_returns_nothing() {
+    let pq = new_item_min_heap(4);
+
+    assert!(pq.peek().is_none(), "peek() on an empty heap returns None");
+    assert!(pq.is_empty());
+}
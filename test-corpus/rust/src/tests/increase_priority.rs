@@ -0,0 +1,94 @@
+// Test corpus for increase_priority() operation
+// Spec: specifications/increase_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use super::{new_item_min_heap, assert_heap_invariant, Item};
+use d_ary_heap::Error;
+
+// =============================================================================
+// increase_priority() Tests
+// =============================================================================
+
+/// Test: increase_priority_postcondition_priority_changed
+/// Spec: specifications/increase_priority.md
+/// Property: item's priority is updated to the new value
+#[test]
+fn increase_priority_postcondition_priority_changed() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("target", 50));
+    pq.insert(Item::new("other", 30));
+    assert_eq!(pq.front().id, "other");
+    assert_eq!(pq.front().priority, 30);
+    // min-heap: a lower value is a higher priority
+    pq.increase_priority(&Item::new("target", 10)).expect("item is in the heap");
+    assert_eq!(pq.front().id, "target");
+    assert_eq!(pq.front().priority, 10);
+}
+
+/// Test: increase_priority_invariant_heap_property
+/// Spec: specifications/increase_priority.md
+/// Property: heap invariant holds after a priority increase
+#[test]
+fn increase_priority_invariant_heap_property() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 80));
+    pq.insert(Item::new("b", 60));
+    pq.insert(Item::new("c", 40));
+    pq.insert(Item::new("d", 20));
+    pq.insert(Item::new("e", 100));
+    pq.insert(Item::new("f", 50));
+    assert_eq!(pq.front().id, "d");
+    assert_eq!(pq.front().priority, 20);
+    pq.increase_priority(&Item::new("a", 5)).expect("item is in the heap");
+    assert_heap_invariant(&pq);
+    assert_eq!(pq.front().id, "a");
+    assert_eq!(pq.front().priority, 5);
+}
+
+/// Test: increase_priority_position_item_moves_up
+/// Spec: specifications/increase_priority.md
+/// Property: item moves toward the root and becomes front if highest
+#[test]
+fn increase_priority_position_item_moves_up() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("root", 10));
+    pq.insert(Item::new("middle", 50));
+    pq.insert(Item::new("leaf", 100));
+    assert_ne!(pq.front().id, "leaf");
+    pq.increase_priority(&Item::new("leaf", 1)).expect("item is in the heap");
+    assert_eq!(pq.front().id, "leaf");
+    assert_eq!(pq.front().priority, 1);
+}
+
+/// Test: increase_priority_size_unchanged
+/// Spec: specifications/increase_priority.md
+/// Property: size is unchanged by a priority update
+#[test]
+fn increase_priority_size_unchanged() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 50));
+    pq.insert(Item::new("b", 30));
+    pq.insert(Item::new("c", 70));
+    assert_eq!(pq.len(), 3);
+    pq.increase_priority(&Item::new("c", 10)).expect("item is in the heap");
+    assert_eq!(pq.len(), 3);
+}
+
+/// Test: increase_priority_edge_not_found
+/// Spec: specifications/increase_priority.md
+/// Property: updating an item that is not in the heap reports not-found
+#[test]
+fn increase_priority_edge_not_found() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("existing", 50));
+    assert_eq!(pq.increase_priority(&Item::new("nonexistent", 10)), Err(Error::ItemNotFound));
+    assert_eq!(pq.len(), 1);
+    assert_eq!(pq.front().id, "existing");
+    assert_eq!(pq.front().priority, 50);
+}
@@ -0,0 +1,83 @@
+// Test corpus for insert() operation
+// Spec: specifications/insert.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use super::{new_item_min_heap, assert_heap_invariant, Item};
+
+// =============================================================================
+// insert() Tests
+// =============================================================================
+
+/// Test: insert_postcondition_item_findable
+/// Spec: specifications/insert.md
+/// Property: inserted item can be found via contains() after insertion
+#[test]
+fn insert_postcondition_item_findable() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("test-item", 50));
+    // identity is the id alone: a lookup with any priority finds the item
+    assert!(pq.contains(&Item::new("test-item", 999)));
+    assert!(!pq.contains(&Item::new("other-item", 999)));
+}
+
+/// Test: insert_invariant_heap_property
+/// Spec: specifications/insert.md
+/// Property: heap invariant holds after every insertion
+#[test]
+fn insert_invariant_heap_property() {
+    let mut pq = new_item_min_heap(4);
+
+    // insert items in arbitrary order
+    pq.insert(Item::new("a", 30));
+    assert_heap_invariant(&pq);
+    pq.insert(Item::new("b", 10));
+    assert_heap_invariant(&pq);
+    pq.insert(Item::new("c", 50));
+    assert_heap_invariant(&pq);
+    pq.insert(Item::new("d", 20));
+    assert_heap_invariant(&pq);
+    pq.insert(Item::new("e", 40));
+    assert_heap_invariant(&pq);
+    assert_eq!(pq.front().id, "b");
+    assert_eq!(pq.front().priority, 10);
+}
+
+/// Test: insert_size_increments
+/// Spec: specifications/insert.md
+/// Property: heap size increases by 1 after each insertion
+#[test]
+fn insert_size_increments() {
+    let mut pq = new_item_min_heap(4);
+
+    assert_eq!(pq.len(), 0);
+    pq.insert(Item::new("item0", 0));
+    assert_eq!(pq.len(), 1);
+    pq.insert(Item::new("item1", 10));
+    assert_eq!(pq.len(), 2);
+    pq.insert(Item::new("item2", 20));
+    assert_eq!(pq.len(), 3);
+    pq.insert(Item::new("item3", 30));
+    assert_eq!(pq.len(), 4);
+    pq.insert(Item::new("item4", 40));
+    assert_eq!(pq.len(), 5);
+}
+
+/// Test: insert_edge_becomes_front_if_highest_priority
+/// Spec: specifications/insert.md
+/// Property: if inserted item has highest priority, it becomes front()
+#[test]
+fn insert_edge_becomes_front_if_highest_priority() {
+    let mut pq = new_item_min_heap(4);
+
+    // decreasing priority values = increasing importance in a min-heap
+    pq.insert(Item::new("low", 100));
+    pq.insert(Item::new("medium", 50));
+    pq.insert(Item::new("high", 10));
+    assert_eq!(pq.front().id, "high");
+    assert_eq!(pq.front().priority, 10);
+    pq.insert(Item::new("urgent", 1));
+    assert_eq!(pq.front().id, "urgent");
+    assert_eq!(pq.front().priority, 1);
+}
@@ -0,0 +1,62 @@
+// Test corpus common definitions
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use d_ary_heap::{MinBy, PriorityCompare, PriorityQueue};
+use std::hash::{Hash, Hasher};
+
+/// Test item type with separate ID (identity) and priority
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub id: String,
+    pub priority: i32,
+}
+
+impl Item {
+    pub fn new(id: &str, priority: i32) -> Self {
+        Self { id: id.to_string(), priority }
+    }
+}
+
+// Item equality is based on ID only (not priority)
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Item {}
+
+// Hash is based on ID only (not priority)
+impl Hash for Item {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Helper: create min-heap of Items (lower priority value = higher importance)
+pub fn new_item_min_heap(d: usize) -> PriorityQueue<Item, MinBy<impl Fn(&Item) -> i32>> {
+    PriorityQueue::new(d, MinBy(|i: &Item| i.priority)).expect("arity is valid")
+}
+
+/// Helper: assert every parent's priority is <= each of its children's
+pub fn assert_heap_invariant(pq: &PriorityQueue<Item, impl PriorityCompare<Item>>) {
+    let arr = pq.to_array();
+    let d = pq.d();
+    for (i, parent) in arr.iter().enumerate() {
+        for child in arr.iter().skip(i * d + 1).take(d) {
+            assert!(
+                parent.priority <= child.priority,
+                "heap invariant violated: parent {:?} > child {:?}",
+                parent,
+                child
+            );
+        }
+    }
+}
+
+pub mod insert;
+pub mod pop;
+pub mod front;
+pub mod increase_priority;
+pub mod decrease_priority;
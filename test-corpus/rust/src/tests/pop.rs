@@ -0,0 +1,91 @@
+// Test corpus for pop() operation
+// Spec: specifications/pop.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+use super::{new_item_min_heap, assert_heap_invariant, Item};
+
+// =============================================================================
+// pop() Tests
+// =============================================================================
+
+/// Test: pop_postcondition_returns_minimum
+/// Spec: specifications/pop.md
+/// Property: pop() removes and returns the item with lowest priority value
+#[test]
+fn pop_postcondition_returns_minimum() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 30));
+    pq.insert(Item::new("b", 10));
+    pq.insert(Item::new("c", 20));
+    let popped = pq.pop().expect("pop() on a non-empty heap returns an item");
+    assert_eq!(popped.id, "b");
+    assert_eq!(popped.priority, 10);
+    assert!(!pq.contains(&Item::new("b", 999)));
+}
+
+/// Test: pop_invariant_maintains_heap_property
+/// Spec: specifications/pop.md
+/// Property: after each pop(), the heap invariant holds and front() is the minimum
+#[test]
+fn pop_invariant_maintains_heap_property() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 50));
+    pq.insert(Item::new("b", 20));
+    pq.insert(Item::new("c", 80));
+    pq.insert(Item::new("d", 10));
+    pq.insert(Item::new("e", 60));
+    pq.insert(Item::new("f", 30));
+    pq.insert(Item::new("g", 70));
+    pq.insert(Item::new("h", 40));
+    let popped = pq.pop().expect("pop() on a non-empty heap returns an item");
+    assert_eq!(popped.id, "d");
+    assert_eq!(popped.priority, 10);
+    assert_heap_invariant(&pq);
+    let popped = pq.pop().expect("pop() on a non-empty heap returns an item");
+    assert_eq!(popped.id, "b");
+    assert_eq!(popped.priority, 20);
+    assert_heap_invariant(&pq);
+    let popped = pq.pop().expect("pop() on a non-empty heap returns an item");
+    assert_eq!(popped.id, "f");
+    assert_eq!(popped.priority, 30);
+    assert_heap_invariant(&pq);
+    let popped = pq.pop().expect("pop() on a non-empty heap returns an item");
+    assert_eq!(popped.id, "h");
+    assert_eq!(popped.priority, 40);
+    assert_heap_invariant(&pq);
+    assert_eq!(pq.front().id, "a");
+    assert_eq!(pq.front().priority, 50);
+}
+
+/// Test: pop_size_decrements
+/// Spec: specifications/pop.md
+/// Property: size decreases by 1 after each successful pop()
+#[test]
+fn pop_size_decrements() {
+    let mut pq = new_item_min_heap(4);
+
+    pq.insert(Item::new("a", 10));
+    pq.insert(Item::new("b", 20));
+    pq.insert(Item::new("c", 30));
+    pq.pop();
+    assert_eq!(pq.len(), 2);
+    pq.pop();
+    assert_eq!(pq.len(), 1);
+    pq.pop();
+    assert_eq!(pq.len(), 0);
+    assert!(pq.is_empty());
+}
+
+/// Test: pop_edge_empty_returns_nothing
+/// Spec: specifications/pop.md
+/// Property: pop() on an empty heap reports nothing and leaves it empty
+#[test]
+fn pop_edge_empty_returns_nothing() {
+    let mut pq = new_item_min_heap(4);
+
+    assert!(pq.pop().is_none(), "pop() on an empty heap returns None");
+    assert!(pq.is_empty());
+}
@@ -0,0 +1,118 @@
+// Test corpus for decrease_priority() operation
+// Spec: specifications/decrease_priority.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+import { describe, it, expect } from 'vitest';
+import { PriorityQueue, minBy } from 'd-ary-heap';
+
+// Test item type with separate ID (identity) and priority
+interface Item {
+  id: string;
+  priority: number;
+}
+
+// Helper: create min-heap of Items (lower priority value = higher importance)
+function newItemMinHeap(d: number): PriorityQueue<Item, string> {
+  return new PriorityQueue<Item, string>({
+    d,
+    comparator: minBy((i: Item) => i.priority),
+    keyExtractor: (i) => i.id,
+  });
+}
+
+// Helper: every parent's priority is <= each of its children's
+function expectHeapInvariant(pq: PriorityQueue<Item, string>): void {
+  const arr = pq.toArray();
+  const d = pq.d();
+  for (let i = 0; i < arr.length; i++) {
+    for (let j = 1; j <= d; j++) {
+      const child = i * d + j;
+      if (child < arr.length) {
+        expect(arr[i]!.priority).toBeLessThanOrEqual(arr[child]!.priority);
+      }
+    }
+  }
+}
+
+// =============================================================================
+// decrease_priority() Tests
+// =============================================================================
+
+describe('decreasePriority()', () => {
+  // Test: decrease_priority_postcondition_priority_changed
+  // Spec: specifications/decrease_priority.md
+  // Property: item's priority is updated to the new value
+  it('postcondition: priority changed', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'target', priority: 10 });
+    pq.insert({ id: 'other', priority: 30 });
+    expect(pq.front()).toEqual({ id: 'target', priority: 10 });
+    // min-heap: a higher value is a lower priority
+    pq.decreasePriority({ id: 'target', priority: 50 });
+    expect(pq.front()).toEqual({ id: 'other', priority: 30 });
+    pq.pop();
+    expect(pq.front()).toEqual({ id: 'target', priority: 50 });
+  });
+
+  // Test: decrease_priority_invariant_heap_property
+  // Spec: specifications/decrease_priority.md
+  // Property: heap invariant holds after a priority decrease
+  it('invariant: heap property', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 10 });
+    pq.insert({ id: 'b', priority: 30 });
+    pq.insert({ id: 'c', priority: 50 });
+    pq.insert({ id: 'd', priority: 70 });
+    pq.insert({ id: 'e', priority: 20 });
+    pq.insert({ id: 'f', priority: 40 });
+    expect(pq.front()).toEqual({ id: 'a', priority: 10 });
+    pq.decreasePriority({ id: 'a', priority: 100 });
+    expectHeapInvariant(pq);
+    expect(pq.front()).toEqual({ id: 'e', priority: 20 });
+  });
+
+  // Test: decrease_priority_position_item_moves_down
+  // Spec: specifications/decrease_priority.md
+  // Property: item moves toward the leaves and is no longer front
+  it('position: item moves down', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'root', priority: 10 });
+    pq.insert({ id: 'child1', priority: 50 });
+    pq.insert({ id: 'child2', priority: 60 });
+    pq.insert({ id: 'child3', priority: 70 });
+    expect(pq.front()).toEqual({ id: 'root', priority: 10 });
+    pq.decreasePriority({ id: 'root', priority: 100 });
+    expect(pq.front().id).not.toBe('root');
+    expect(pq.front()).toEqual({ id: 'child1', priority: 50 });
+  });
+
+  // Test: decrease_priority_size_unchanged
+  // Spec: specifications/decrease_priority.md
+  // Property: size is unchanged by a priority update
+  it('size: unchanged', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 10 });
+    pq.insert({ id: 'b', priority: 30 });
+    pq.insert({ id: 'c', priority: 50 });
+    expect(pq.len()).toBe(3);
+    pq.decreasePriority({ id: 'a', priority: 100 });
+    expect(pq.len()).toBe(3);
+  });
+
+  // Test: decrease_priority_edge_not_found
+  // Spec: specifications/decrease_priority.md
+  // Property: updating an item that is not in the heap reports not-found
+  it('edge: not found', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'existing', priority: 50 });
+    expect(() => pq.decreasePriority({ id: 'nonexistent', priority: 100 })).toThrow('Item not found');
+    expect(pq.len()).toBe(1);
+    expect(pq.front()).toEqual({ id: 'existing', priority: 50 });
+  });
+});
@@ -0,0 +1,94 @@
+// Test corpus for front() operation
+// Spec: specifications/front.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+import { describe, it, expect } from 'vitest';
+import { PriorityQueue, minBy } from 'd-ary-heap';
+
+// Test item type with separate ID (identity) and priority
+interface Item {
+  id: string;
+  priority: number;
+}
+
+// Helper: create min-heap of Items (lower priority value = higher importance)
+function newItemMinHeap(d: number): PriorityQueue<Item, string> {
+  return new PriorityQueue<Item, string>({
+    d,
+    comparator: minBy((i: Item) => i.priority),
+    keyExtractor: (i) => i.id,
+  });
+}
+
+// Helper: every parent's priority is <= each of its children's
+function expectHeapInvariant(pq: PriorityQueue<Item, string>): void {
+  const arr = pq.toArray();
+  const d = pq.d();
+  for (let i = 0; i < arr.length; i++) {
+    for (let j = 1; j <= d; j++) {
+      const child = i * d + j;
+      if (child < arr.length) {
+        expect(arr[i]!.priority).toBeLessThanOrEqual(arr[child]!.priority);
+      }
+    }
+  }
+}
+
+// =============================================================================
+// front() Tests
+// =============================================================================
+
+describe('front()', () => {
+  // Test: front_postcondition_returns_minimum
+  // Spec: specifications/front.md
+  // Property: front() returns the item with lowest priority value without removing it
+  it('postcondition: returns minimum', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 30 });
+    pq.insert({ id: 'b', priority: 10 });
+    pq.insert({ id: 'c', priority: 20 });
+    expect(pq.front()).toEqual({ id: 'b', priority: 10 });
+    expect(pq.contains({ id: 'b', priority: 999 })).toBe(true);
+  });
+
+  // Test: front_invariant_no_modification
+  // Spec: specifications/front.md
+  // Property: front() does not modify the heap: repeated calls return the same item
+  it('invariant: no modification', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 30 });
+    pq.insert({ id: 'b', priority: 10 });
+    pq.insert({ id: 'c', priority: 20 });
+    expect(pq.front()).toEqual({ id: 'b', priority: 10 });
+    expect(pq.front()).toEqual({ id: 'b', priority: 10 });
+    expect(pq.front()).toEqual({ id: 'b', priority: 10 });
+    expectHeapInvariant(pq);
+  });
+
+  // Test: front_size_unchanged
+  // Spec: specifications/front.md
+  // Property: size is unchanged by front()
+  it('size: unchanged', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 10 });
+    pq.insert({ id: 'b', priority: 20 });
+    pq.insert({ id: 'c', priority: 30 });
+    expect(pq.len()).toBe(3);
+    expect(pq.front()).toEqual({ id: 'a', priority: 10 });
+    expect(pq.len()).toBe(3);
+  });
+
+  // Test: front_edge_empty_peek_returns_nothing
+  // Spec: specifications/front.md
+  // Property: peek() on an empty heap reports nothing
+  it('edge: empty peek returns nothing', () => {
+    const pq = newItemMinHeap(4);
+
+    expect(pq.peek()).toBeUndefined();
+    expect(pq.isEmpty()).toBe(true);
+  });
+});
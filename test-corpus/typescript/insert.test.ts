@@ -0,0 +1,108 @@
+// Test corpus for insert() operation
+// Spec: specifications/insert.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+import { describe, it, expect } from 'vitest';
+import { PriorityQueue, minBy } from 'd-ary-heap';
+
+// Test item type with separate ID (identity) and priority
+interface Item {
+  id: string;
+  priority: number;
+}
+
+// Helper: create min-heap of Items (lower priority value = higher importance)
+function newItemMinHeap(d: number): PriorityQueue<Item, string> {
+  return new PriorityQueue<Item, string>({
+    d,
+    comparator: minBy((i: Item) => i.priority),
+    keyExtractor: (i) => i.id,
+  });
+}
+
+// Helper: every parent's priority is <= each of its children's
+function expectHeapInvariant(pq: PriorityQueue<Item, string>): void {
+  const arr = pq.toArray();
+  const d = pq.d();
+  for (let i = 0; i < arr.length; i++) {
+    for (let j = 1; j <= d; j++) {
+      const child = i * d + j;
+      if (child < arr.length) {
+        expect(arr[i]!.priority).toBeLessThanOrEqual(arr[child]!.priority);
+      }
+    }
+  }
+}
+
+// =============================================================================
+// insert() Tests
+// =============================================================================
+
+describe('insert()', () => {
+  // Test: insert_postcondition_item_findable
+  // Spec: specifications/insert.md
+  // Property: inserted item can be found via contains() after insertion
+  it('postcondition: item findable', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'test-item', priority: 50 });
+    // identity is the id alone: a lookup with any priority finds the item
+    expect(pq.contains({ id: 'test-item', priority: 999 })).toBe(true);
+    expect(pq.contains({ id: 'other-item', priority: 999 })).toBe(false);
+  });
+
+  // Test: insert_invariant_heap_property
+  // Spec: specifications/insert.md
+  // Property: heap invariant holds after every insertion
+  it('invariant: heap property', () => {
+    const pq = newItemMinHeap(4);
+
+    // insert items in arbitrary order
+    pq.insert({ id: 'a', priority: 30 });
+    expectHeapInvariant(pq);
+    pq.insert({ id: 'b', priority: 10 });
+    expectHeapInvariant(pq);
+    pq.insert({ id: 'c', priority: 50 });
+    expectHeapInvariant(pq);
+    pq.insert({ id: 'd', priority: 20 });
+    expectHeapInvariant(pq);
+    pq.insert({ id: 'e', priority: 40 });
+    expectHeapInvariant(pq);
+    expect(pq.front()).toEqual({ id: 'b', priority: 10 });
+  });
+
+  // Test: insert_size_increments
+  // Spec: specifications/insert.md
+  // Property: heap size increases by 1 after each insertion
+  it('size: increments', () => {
+    const pq = newItemMinHeap(4);
+
+    expect(pq.len()).toBe(0);
+    pq.insert({ id: 'item0', priority: 0 });
+    expect(pq.len()).toBe(1);
+    pq.insert({ id: 'item1', priority: 10 });
+    expect(pq.len()).toBe(2);
+    pq.insert({ id: 'item2', priority: 20 });
+    expect(pq.len()).toBe(3);
+    pq.insert({ id: 'item3', priority: 30 });
+    expect(pq.len()).toBe(4);
+    pq.insert({ id: 'item4', priority: 40 });
+    expect(pq.len()).toBe(5);
+  });
+
+  // Test: insert_edge_becomes_front_if_highest_priority
+  // Spec: specifications/insert.md
+  // Property: if inserted item has highest priority, it becomes front()
+  it('edge: becomes front if highest priority', () => {
+    const pq = newItemMinHeap(4);
+
+    // decreasing priority values = increasing importance in a min-heap
+    pq.insert({ id: 'low', priority: 100 });
+    pq.insert({ id: 'medium', priority: 50 });
+    pq.insert({ id: 'high', priority: 10 });
+    expect(pq.front()).toEqual({ id: 'high', priority: 10 });
+    pq.insert({ id: 'urgent', priority: 1 });
+    expect(pq.front()).toEqual({ id: 'urgent', priority: 1 });
+  });
+});
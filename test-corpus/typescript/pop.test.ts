@@ -0,0 +1,108 @@
+// Test corpus for pop() operation
+// Spec: specifications/pop.md
+// Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+// Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+import { describe, it, expect } from 'vitest';
+import { PriorityQueue, minBy } from 'd-ary-heap';
+
+// Test item type with separate ID (identity) and priority
+interface Item {
+  id: string;
+  priority: number;
+}
+
+// Helper: create min-heap of Items (lower priority value = higher importance)
+function newItemMinHeap(d: number): PriorityQueue<Item, string> {
+  return new PriorityQueue<Item, string>({
+    d,
+    comparator: minBy((i: Item) => i.priority),
+    keyExtractor: (i) => i.id,
+  });
+}
+
+// Helper: every parent's priority is <= each of its children's
+function expectHeapInvariant(pq: PriorityQueue<Item, string>): void {
+  const arr = pq.toArray();
+  const d = pq.d();
+  for (let i = 0; i < arr.length; i++) {
+    for (let j = 1; j <= d; j++) {
+      const child = i * d + j;
+      if (child < arr.length) {
+        expect(arr[i]!.priority).toBeLessThanOrEqual(arr[child]!.priority);
+      }
+    }
+  }
+}
+
+// =============================================================================
+// pop() Tests
+// =============================================================================
+
+describe('pop()', () => {
+  // Test: pop_postcondition_returns_minimum
+  // Spec: specifications/pop.md
+  // Property: pop() removes and returns the item with lowest priority value
+  it('postcondition: returns minimum', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 30 });
+    pq.insert({ id: 'b', priority: 10 });
+    pq.insert({ id: 'c', priority: 20 });
+    expect(pq.pop()).toEqual({ id: 'b', priority: 10 });
+    expect(pq.contains({ id: 'b', priority: 999 })).toBe(false);
+  });
+
+  // Test: pop_invariant_maintains_heap_property
+  // Spec: specifications/pop.md
+  // Property: after each pop(), the heap invariant holds and front() is the minimum
+  it('invariant: maintains heap property', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 50 });
+    pq.insert({ id: 'b', priority: 20 });
+    pq.insert({ id: 'c', priority: 80 });
+    pq.insert({ id: 'd', priority: 10 });
+    pq.insert({ id: 'e', priority: 60 });
+    pq.insert({ id: 'f', priority: 30 });
+    pq.insert({ id: 'g', priority: 70 });
+    pq.insert({ id: 'h', priority: 40 });
+    expect(pq.pop()).toEqual({ id: 'd', priority: 10 });
+    expectHeapInvariant(pq);
+    expect(pq.pop()).toEqual({ id: 'b', priority: 20 });
+    expectHeapInvariant(pq);
+    expect(pq.pop()).toEqual({ id: 'f', priority: 30 });
+    expectHeapInvariant(pq);
+    expect(pq.pop()).toEqual({ id: 'h', priority: 40 });
+    expectHeapInvariant(pq);
+    expect(pq.front()).toEqual({ id: 'a', priority: 50 });
+  });
+
+  // Test: pop_size_decrements
+  // Spec: specifications/pop.md
+  // Property: size decreases by 1 after each successful pop()
+  it('size: decrements', () => {
+    const pq = newItemMinHeap(4);
+
+    pq.insert({ id: 'a', priority: 10 });
+    pq.insert({ id: 'b', priority: 20 });
+    pq.insert({ id: 'c', priority: 30 });
+    pq.pop();
+    expect(pq.len()).toBe(2);
+    pq.pop();
+    expect(pq.len()).toBe(1);
+    pq.pop();
+    expect(pq.len()).toBe(0);
+    expect(pq.isEmpty()).toBe(true);
+  });
+
+  // Test: pop_edge_empty_returns_nothing
+  // Spec: specifications/pop.md
+  // Property: pop() on an empty heap reports nothing and leaves it empty
+  it('edge: empty returns nothing', () => {
+    const pq = newItemMinHeap(4);
+
+    expect(pq.pop()).toBeUndefined();
+    expect(pq.isEmpty()).toBe(true);
+  });
+});
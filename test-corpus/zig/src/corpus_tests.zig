@@ -0,0 +1,555 @@
+//! Test corpus for insert / pop / front / increasePriority / decreasePriority
+//! Spec: specifications/*.md
+//! Part of: Amphigraphic-Strict × d-ary Heap Priority Queue research
+//! Generated by `cargo run --bin gen_corpus` from src/corpus/cases.rs; do not edit by hand.
+
+const std = @import("std");
+const testing = std.testing;
+
+const d_heap = @import("d_heap");
+const DHeapItem = d_heap.DHeapItem;
+const MinByCost = d_heap.MinByCost;
+const Item = d_heap.Item;
+
+// Helper: every parent's cost is <= each of its children's (min-heap)
+fn expectHeapInvariant(pq: *const DHeapItem) !void {
+    const arr = try pq.toArray();
+    defer if (arr.len > 0) pq.allocator.free(arr);
+    const d = pq.d();
+    for (arr, 0..) |parent, i| {
+        var j: usize = 1;
+        while (j <= d) : (j += 1) {
+            const child = i * d + j;
+            if (child < arr.len) {
+                try testing.expect(parent.cost <= arr[child].cost);
+            }
+        }
+    }
+}
+
+// =============================================================================
+// insert() Tests
+// =============================================================================
+
+// Test: insert_postcondition_item_findable
+// Spec: specifications/insert.md
+// Property: inserted item can be found via contains() after insertion
+// Ids: test-item=1, other-item=2
+test "insert_postcondition_item_findable" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    // identity is the id alone: a lookup with any priority finds the item
+    try testing.expect(pq.contains(Item.init(1, 999)));
+    try testing.expect(!pq.contains(Item.init(2, 999)));
+}
+
+// Test: insert_invariant_heap_property
+// Spec: specifications/insert.md
+// Property: heap invariant holds after every insertion
+// Ids: a=1, b=2, c=3, d=4, e=5
+test "insert_invariant_heap_property" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    // insert items in arbitrary order
+    try pq.insert(Item.init(1, 30));
+    try expectHeapInvariant(&pq);
+    try pq.insert(Item.init(2, 10));
+    try expectHeapInvariant(&pq);
+    try pq.insert(Item.init(3, 50));
+    try expectHeapInvariant(&pq);
+    try pq.insert(Item.init(4, 20));
+    try expectHeapInvariant(&pq);
+    try pq.insert(Item.init(5, 40));
+    try expectHeapInvariant(&pq);
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+}
+
+// Test: insert_size_increments
+// Spec: specifications/insert.md
+// Property: heap size increases by 1 after each insertion
+// Ids: item0=1, item1=2, item2=3, item3=4, item4=5
+test "insert_size_increments" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try testing.expectEqual(@as(usize, 0), pq.len());
+    try pq.insert(Item.init(1, 0));
+    try testing.expectEqual(@as(usize, 1), pq.len());
+    try pq.insert(Item.init(2, 10));
+    try testing.expectEqual(@as(usize, 2), pq.len());
+    try pq.insert(Item.init(3, 20));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+    try pq.insert(Item.init(4, 30));
+    try testing.expectEqual(@as(usize, 4), pq.len());
+    try pq.insert(Item.init(5, 40));
+    try testing.expectEqual(@as(usize, 5), pq.len());
+}
+
+// Test: insert_edge_becomes_front_if_highest_priority
+// Spec: specifications/insert.md
+// Property: if inserted item has highest priority, it becomes front()
+// Ids: low=1, medium=2, high=3, urgent=4
+test "insert_edge_becomes_front_if_highest_priority" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    // decreasing priority values = increasing importance in a min-heap
+    try pq.insert(Item.init(1, 100));
+    try pq.insert(Item.init(2, 50));
+    try pq.insert(Item.init(3, 10));
+    try testing.expectEqual(@as(u32, 3), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try pq.insert(Item.init(4, 1));
+    try testing.expectEqual(@as(u32, 4), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 1), pq.front().?.cost);
+}
+
+// =============================================================================
+// pop() Tests
+// =============================================================================
+
+// Test: pop_postcondition_returns_minimum
+// Spec: specifications/pop.md
+// Property: pop() removes and returns the item with lowest priority value
+// Ids: a=1, b=2, c=3
+test "pop_postcondition_returns_minimum" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 30));
+    try pq.insert(Item.init(2, 10));
+    try pq.insert(Item.init(3, 20));
+    {
+        const popped = (try pq.pop()).?;
+        try testing.expectEqual(@as(u32, 2), popped.number);
+        try testing.expectEqual(@as(u32, 10), popped.cost);
+    }
+    try testing.expect(!pq.contains(Item.init(2, 999)));
+}
+
+// Test: pop_invariant_maintains_heap_property
+// Spec: specifications/pop.md
+// Property: after each pop(), the heap invariant holds and front() is the minimum
+// Ids: a=1, b=2, c=3, d=4, e=5, f=6, g=7, h=8
+test "pop_invariant_maintains_heap_property" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    try pq.insert(Item.init(2, 20));
+    try pq.insert(Item.init(3, 80));
+    try pq.insert(Item.init(4, 10));
+    try pq.insert(Item.init(5, 60));
+    try pq.insert(Item.init(6, 30));
+    try pq.insert(Item.init(7, 70));
+    try pq.insert(Item.init(8, 40));
+    {
+        const popped = (try pq.pop()).?;
+        try testing.expectEqual(@as(u32, 4), popped.number);
+        try testing.expectEqual(@as(u32, 10), popped.cost);
+    }
+    try expectHeapInvariant(&pq);
+    {
+        const popped = (try pq.pop()).?;
+        try testing.expectEqual(@as(u32, 2), popped.number);
+        try testing.expectEqual(@as(u32, 20), popped.cost);
+    }
+    try expectHeapInvariant(&pq);
+    {
+        const popped = (try pq.pop()).?;
+        try testing.expectEqual(@as(u32, 6), popped.number);
+        try testing.expectEqual(@as(u32, 30), popped.cost);
+    }
+    try expectHeapInvariant(&pq);
+    {
+        const popped = (try pq.pop()).?;
+        try testing.expectEqual(@as(u32, 8), popped.number);
+        try testing.expectEqual(@as(u32, 40), popped.cost);
+    }
+    try expectHeapInvariant(&pq);
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 50), pq.front().?.cost);
+}
+
+// Test: pop_size_decrements
+// Spec: specifications/pop.md
+// Property: size decreases by 1 after each successful pop()
+// Ids: a=1, b=2, c=3
+test "pop_size_decrements" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 20));
+    try pq.insert(Item.init(3, 30));
+    _ = try pq.pop();
+    try testing.expectEqual(@as(usize, 2), pq.len());
+    _ = try pq.pop();
+    try testing.expectEqual(@as(usize, 1), pq.len());
+    _ = try pq.pop();
+    try testing.expectEqual(@as(usize, 0), pq.len());
+    try testing.expect(pq.isEmpty());
+}
+
+// Test: pop_edge_empty_returns_nothing
+// Spec: specifications/pop.md
+// Property: pop() on an empty heap reports nothing and leaves it empty
+// Ids: (none)
+test "pop_edge_empty_returns_nothing" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try testing.expect((try pq.pop()) == null);
+    try testing.expect(pq.isEmpty());
+}
+
+// =============================================================================
+// front() Tests
+// =============================================================================
+
+// Test: front_postcondition_returns_minimum
+// Spec: specifications/front.md
+// Property: front() returns the item with lowest priority value without removing it
+// Ids: a=1, b=2, c=3
+test "front_postcondition_returns_minimum" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 30));
+    try pq.insert(Item.init(2, 10));
+    try pq.insert(Item.init(3, 20));
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try testing.expect(pq.contains(Item.init(2, 999)));
+}
+
+// Test: front_invariant_no_modification
+// Spec: specifications/front.md
+// Property: front() does not modify the heap: repeated calls return the same item
+// Ids: a=1, b=2, c=3
+test "front_invariant_no_modification" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 30));
+    try pq.insert(Item.init(2, 10));
+    try pq.insert(Item.init(3, 20));
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try expectHeapInvariant(&pq);
+}
+
+// Test: front_size_unchanged
+// Spec: specifications/front.md
+// Property: size is unchanged by front()
+// Ids: a=1, b=2, c=3
+test "front_size_unchanged" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 20));
+    try pq.insert(Item.init(3, 30));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try testing.expectEqual(@as(usize, 3), pq.len());
+}
+
+// Test: front_edge_empty_peek_returns_nothing
+// Spec: specifications/front.md
+// Property: peek() on an empty heap reports nothing
+// Ids: (none)
+test "front_edge_empty_peek_returns_nothing" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try testing.expect(pq.peek() == null);
+    try testing.expect(pq.isEmpty());
+}
+
+// =============================================================================
+// increase_priority() Tests
+// =============================================================================
+
+// Test: increase_priority_postcondition_priority_changed
+// Spec: specifications/increase_priority.md
+// Property: item's priority is updated to the new value
+// Ids: target=1, other=2
+test "increase_priority_postcondition_priority_changed" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    try pq.insert(Item.init(2, 30));
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 30), pq.front().?.cost);
+    // min-heap: a lower value is a higher priority
+    try pq.increasePriority(Item.init(1, 10));
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+}
+
+// Test: increase_priority_invariant_heap_property
+// Spec: specifications/increase_priority.md
+// Property: heap invariant holds after a priority increase
+// Ids: a=1, b=2, c=3, d=4, e=5, f=6
+test "increase_priority_invariant_heap_property" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 80));
+    try pq.insert(Item.init(2, 60));
+    try pq.insert(Item.init(3, 40));
+    try pq.insert(Item.init(4, 20));
+    try pq.insert(Item.init(5, 100));
+    try pq.insert(Item.init(6, 50));
+    try testing.expectEqual(@as(u32, 4), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 20), pq.front().?.cost);
+    try pq.increasePriority(Item.init(1, 5));
+    try expectHeapInvariant(&pq);
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 5), pq.front().?.cost);
+}
+
+// Test: increase_priority_position_item_moves_up
+// Spec: specifications/increase_priority.md
+// Property: item moves toward the root and becomes front if highest
+// Ids: root=1, middle=2, leaf=3
+test "increase_priority_position_item_moves_up" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 50));
+    try pq.insert(Item.init(3, 100));
+    try testing.expect(pq.front().?.number != 3);
+    try pq.increasePriority(Item.init(3, 1));
+    try testing.expectEqual(@as(u32, 3), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 1), pq.front().?.cost);
+}
+
+// Test: increase_priority_size_unchanged
+// Spec: specifications/increase_priority.md
+// Property: size is unchanged by a priority update
+// Ids: a=1, b=2, c=3
+test "increase_priority_size_unchanged" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    try pq.insert(Item.init(2, 30));
+    try pq.insert(Item.init(3, 70));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+    try pq.increasePriority(Item.init(3, 10));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+}
+
+// Test: increase_priority_edge_not_found
+// Spec: specifications/increase_priority.md
+// Property: updating an item that is not in the heap reports not-found
+// Ids: existing=1, nonexistent=2
+test "increase_priority_edge_not_found" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    try testing.expectError(error.ItemNotFound, pq.increasePriority(Item.init(2, 10)));
+    try testing.expectEqual(@as(usize, 1), pq.len());
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 50), pq.front().?.cost);
+}
+
+// =============================================================================
+// decrease_priority() Tests
+// =============================================================================
+
+// Test: decrease_priority_postcondition_priority_changed
+// Spec: specifications/decrease_priority.md
+// Property: item's priority is updated to the new value
+// Ids: target=1, other=2
+test "decrease_priority_postcondition_priority_changed" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 30));
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    // min-heap: a higher value is a lower priority
+    try pq.decreasePriority(Item.init(1, 50));
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 30), pq.front().?.cost);
+    _ = try pq.pop();
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 50), pq.front().?.cost);
+}
+
+// Test: decrease_priority_invariant_heap_property
+// Spec: specifications/decrease_priority.md
+// Property: heap invariant holds after a priority decrease
+// Ids: a=1, b=2, c=3, d=4, e=5, f=6
+test "decrease_priority_invariant_heap_property" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 30));
+    try pq.insert(Item.init(3, 50));
+    try pq.insert(Item.init(4, 70));
+    try pq.insert(Item.init(5, 20));
+    try pq.insert(Item.init(6, 40));
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try pq.decreasePriority(Item.init(1, 100));
+    try expectHeapInvariant(&pq);
+    try testing.expectEqual(@as(u32, 5), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 20), pq.front().?.cost);
+}
+
+// Test: decrease_priority_position_item_moves_down
+// Spec: specifications/decrease_priority.md
+// Property: item moves toward the leaves and is no longer front
+// Ids: root=1, child1=2, child2=3, child3=4
+test "decrease_priority_position_item_moves_down" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 50));
+    try pq.insert(Item.init(3, 60));
+    try pq.insert(Item.init(4, 70));
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 10), pq.front().?.cost);
+    try pq.decreasePriority(Item.init(1, 100));
+    try testing.expect(pq.front().?.number != 1);
+    try testing.expectEqual(@as(u32, 2), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 50), pq.front().?.cost);
+}
+
+// Test: decrease_priority_size_unchanged
+// Spec: specifications/decrease_priority.md
+// Property: size is unchanged by a priority update
+// Ids: a=1, b=2, c=3
+test "decrease_priority_size_unchanged" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 10));
+    try pq.insert(Item.init(2, 30));
+    try pq.insert(Item.init(3, 50));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+    try pq.decreasePriority(Item.init(1, 100));
+    try testing.expectEqual(@as(usize, 3), pq.len());
+}
+
+// Test: decrease_priority_edge_not_found
+// Spec: specifications/decrease_priority.md
+// Property: updating an item that is not in the heap reports not-found
+// Ids: existing=1, nonexistent=2
+test "decrease_priority_edge_not_found" {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var pq = try DHeapItem.init(4, MinByCost, allocator);
+    defer pq.deinit();
+
+    try pq.insert(Item.init(1, 50));
+    try testing.expectError(error.ItemNotFound, pq.decreasePriority(Item.init(2, 100)));
+    try testing.expectEqual(@as(usize, 1), pq.len());
+    try testing.expectEqual(@as(u32, 1), pq.front().?.number);
+    try testing.expectEqual(@as(u32, 50), pq.front().?.cost);
+}
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Languages the checker knows how to extract. The order here is the column
+/// order of the report.
+pub const LANGUAGES: [&str; 5] = ["rust", "cpp", "go", "typescript", "zig"];
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    /// Source path per language, relative to the repository root.
+    pub sources: BTreeMap<String, String>,
+    /// Per-language renames applied after snake_case normalisation, e.g.
+    /// C++ `create` -> canonical `new`.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, BTreeMap<String, String>>,
+    /// Per-language methods that are idiomatic extras (camelCase twins,
+    /// `try_*` variants, lifecycle helpers) and never count towards parity.
+    #[serde(default)]
+    pub ignore: BTreeMap<String, BTreeSet<String>>,
+    /// Per-language canonical methods known to be missing. Reported, but
+    /// they do not fail the check.
+    #[serde(default)]
+    pub known_gaps: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Config {
+    pub fn alias<'a>(&'a self, lang: &str, name: &'a str) -> &'a str {
+        self.aliases
+            .get(lang)
+            .and_then(|m| m.get(name))
+            .map_or(name, String::as_str)
+    }
+
+    pub fn is_ignored(&self, lang: &str, name: &str) -> bool {
+        self.ignore.get(lang).is_some_and(|s| s.contains(name))
+    }
+
+    pub fn is_known_gap(&self, lang: &str, name: &str) -> bool {
+        self.known_gaps.get(lang).is_some_and(|s| s.contains(name))
+    }
+}
+
+pub fn load(path: &Path) -> Result<Config> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| anyhow!("reading {}: {}", path.display(), e))?;
+    let cfg: Config = toml::from_str(&text)?;
+    for lang in cfg
+        .sources
+        .keys()
+        .chain(cfg.aliases.keys())
+        .chain(cfg.ignore.keys())
+        .chain(cfg.known_gaps.keys())
+    {
+        if !LANGUAGES.contains(&lang.as_str()) {
+            return Err(anyhow!("unknown language `{}` in {}", lang, path.display()));
+        }
+    }
+    Ok(cfg)
+}
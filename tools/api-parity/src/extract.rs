@@ -0,0 +1,399 @@
+//! Public-surface extractors, one per language.
+//!
+//! Rust is parsed properly with `syn`; the other four use line-oriented
+//! regexes tuned to the layout of their single source file. Each extractor
+//! returns the *raw* method names of the main priority-queue type; the caller
+//! normalises them to snake_case and applies aliases.
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::Path;
+use syn::{ImplItem, Item, Type, Visibility};
+
+pub fn extract(lang: &str, path: &Path) -> Result<BTreeSet<String>> {
+    let names = match lang {
+        "rust" => rust(path)?,
+        "cpp" => cpp(&read(path)?),
+        "go" => go(&read(path)?),
+        "typescript" => typescript(&read(path)?),
+        "zig" => zig(&read(path)?),
+        other => return Err(anyhow!("no extractor for language `{}`", other)),
+    };
+    if names.is_empty() {
+        return Err(anyhow!(
+            "no public methods found for {} in {}; has the source layout changed?",
+            lang,
+            path.display()
+        ));
+    }
+    Ok(names)
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
+/// Convert camelCase / PascalCase / Go's `Snake_case` aliases to snake_case.
+pub fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower_or_digit = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower_or_digit {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower_or_digit = false;
+        } else {
+            out.push(c);
+            prev_lower_or_digit = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------- Rust ----
+
+/// Every `pub fn` in an inherent `impl ... PriorityQueue<...>` block across
+/// all `.rs` files under `dir`. A `Display` impl contributes `to_string`,
+/// which is how the other languages spell it.
+fn rust(dir: &Path) -> Result<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(p) = stack.pop() {
+        if p.is_dir() {
+            for entry in
+                std::fs::read_dir(&p).with_context(|| format!("listing {}", p.display()))?
+            {
+                stack.push(entry?.path());
+            }
+        } else if p.extension().is_some_and(|e| e == "rs") {
+            let file =
+                syn::parse_file(&read(&p)?).with_context(|| format!("parsing {}", p.display()))?;
+            collect_rust_items(&file.items, &mut names);
+        }
+    }
+    Ok(names)
+}
+
+fn collect_rust_items(items: &[Item], names: &mut BTreeSet<String>) {
+    for item in items {
+        match item {
+            Item::Impl(imp) if is_priority_queue(&imp.self_ty) => match &imp.trait_ {
+                None => {
+                    for member in &imp.items {
+                        if let ImplItem::Fn(f) = member {
+                            if matches!(f.vis, Visibility::Public(_)) {
+                                names.insert(f.sig.ident.to_string());
+                            }
+                        }
+                    }
+                }
+                Some((_, path, _)) => {
+                    if path.segments.last().is_some_and(|s| s.ident == "Display") {
+                        names.insert("to_string".to_string());
+                    }
+                }
+            },
+            Item::Mod(m) => {
+                if let Some((_, inner)) = &m.content {
+                    collect_rust_items(inner, names);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_priority_queue(ty: &Type) -> bool {
+    match ty {
+        Type::Path(tp) => tp
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "PriorityQueue"),
+        _ => false,
+    }
+}
+
+// ----------------------------------------------------------------- C++ ----
+
+/// Member functions declared in the `public:` sections of
+/// `class PriorityQueue`, at member depth (one brace level inside the class).
+fn cpp(src: &str) -> BTreeSet<String> {
+    let class_start = Regex::new(r"\bclass\s+PriorityQueue\b").unwrap();
+    let access = Regex::new(r"^\s*(public|private|protected)\s*:").unwrap();
+    let method = Regex::new(
+        r"^\s*(?:\[\[nodiscard\]\]\s*)?(?:(?:static|constexpr|inline|virtual|explicit)\s+)*[\w:<>,&*\s]+?\b(\w+)\s*\(",
+    )
+    .unwrap();
+    let block_comment = Regex::new(r"/\*.*?\*/").unwrap();
+    const NOT_METHODS: [&str; 6] = ["if", "for", "while", "switch", "return", "PriorityQueue"];
+
+    let mut names = BTreeSet::new();
+    let mut depth: i32 = 0;
+    let mut class_depth: Option<i32> = None;
+    let mut public = false;
+    for raw in src.lines() {
+        let line = block_comment.replace_all(raw, "");
+        let line = line.split("//").next().unwrap_or("");
+        if class_depth.is_none() && class_start.is_match(line) && !line.trim_end().ends_with(';') {
+            class_depth = Some(depth);
+            public = false;
+        }
+        if let Some(cd) = class_depth {
+            if let Some(c) = access.captures(line) {
+                public = &c[1] == "public";
+            } else if public && depth == cd + 1 {
+                if let Some(c) = method.captures(line) {
+                    let name = &c[1];
+                    if !NOT_METHODS.contains(&name) && !line.contains("operator") {
+                        names.insert(name.to_string());
+                    }
+                }
+            }
+        }
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        if class_depth.is_some_and(|cd| depth <= cd) && line.contains('}') {
+            class_depth = None;
+        }
+    }
+    names
+}
+
+// ------------------------------------------------------------------ Go ----
+
+/// Exported methods on `*PriorityQueue[...]` plus exported top-level
+/// constructors returning one.
+fn go(src: &str) -> BTreeSet<String> {
+    let method = Regex::new(r"^func \(\w+ \*?PriorityQueue\[[^\]]*\]\) ([A-Z]\w*)\(").unwrap();
+    let ctor = Regex::new(r"^func ([A-Z]\w*)\[.*\*PriorityQueue\[").unwrap();
+    src.lines()
+        .filter_map(|l| method.captures(l).or_else(|| ctor.captures(l)))
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+// ---------------------------------------------------------- TypeScript ----
+
+/// Non-private members of `export class PriorityQueue`, including static
+/// factories and `get` accessors; `constructor` is reported as-is.
+fn typescript(src: &str) -> BTreeSet<String> {
+    let member = Regex::new(r"^  (?:public\s+)?(?:static\s+)?(?:get\s+)?(\w+)\s*[(<]").unwrap();
+    let mut names = BTreeSet::new();
+    let mut inside = false;
+    for line in src.lines() {
+        if line.starts_with("export class PriorityQueue") {
+            inside = true;
+            continue;
+        }
+        if inside && line.starts_with('}') {
+            break;
+        }
+        if inside {
+            if let Some(c) = member.captures(line) {
+                names.insert(c[1].to_string());
+            }
+        }
+    }
+    names
+}
+
+// ----------------------------------------------------------------- Zig ----
+
+/// `pub fn` members of the struct returned by `DHeapWithStats`, i.e. the
+/// lines between that declaration and the next top-level declaration.
+fn zig(src: &str) -> BTreeSet<String> {
+    let member = Regex::new(r"^\s+pub fn (\w+)\(").unwrap();
+    let top_level = Regex::new(r"^(pub )?(fn|const|var) ").unwrap();
+    let mut names = BTreeSet::new();
+    let mut inside = false;
+    for line in src.lines() {
+        if line.starts_with("pub fn DHeapWithStats(") {
+            inside = true;
+            continue;
+        }
+        if inside && top_level.is_match(line) {
+            break;
+        }
+        if inside {
+            if let Some(c) = member.captures(line) {
+                names.insert(c[1].to_string());
+            }
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn rust_takes_pub_fns_of_inherent_impls_and_display() {
+        let src = r#"
+            pub struct PriorityQueue<T, C> { items: Vec<T>, cmp: C }
+            pub struct Other;
+
+            impl<T, C> PriorityQueue<T, C> {
+                pub fn new() -> Self { todo!() }
+                pub fn insert(&mut self, item: T) {}
+                pub(crate) fn sift_up(&mut self, i: usize) {}
+                fn sift_down(&mut self, i: usize) {}
+            }
+
+            impl<T, C> std::fmt::Display for PriorityQueue<T, C> {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+            }
+
+            impl<T, C> Clone for PriorityQueue<T, C> {
+                fn clone(&self) -> Self { todo!() }
+            }
+
+            impl Other {
+                pub fn unrelated(&self) {}
+            }
+
+            mod stats {
+                impl<T, C> crate::PriorityQueue<T, C> {
+                    pub fn stats(&self) {}
+                }
+            }
+        "#;
+        let mut names = BTreeSet::new();
+        collect_rust_items(&syn::parse_file(src).unwrap().items, &mut names);
+        assert_eq!(names, set(&["insert", "new", "stats", "to_string"]));
+    }
+
+    #[test]
+    fn cpp_takes_public_members_of_the_class_only() {
+        let src = r#"
+            template <typename T> class PriorityQueue;
+
+            class PriorityQueue {
+            public:
+                explicit PriorityQueue(std::size_t d);
+                [[nodiscard]] std::size_t len() const { return container.size(); }
+                void insert(const T& item) {
+                    if (ready()) { helper(item); }
+                }
+                static PriorityQueue withCapacity(std::size_t n);
+                bool operator==(const PriorityQueue& other) const;
+                // void commented(int x);
+                /* void alsoCommented(); */ void peek() const;
+            private:
+                void siftUp(std::size_t i);
+            public:
+                std::string to_string() const;
+            };
+
+            void freeFunction(int x);
+        "#;
+        assert_eq!(
+            cpp(src),
+            set(&["insert", "len", "peek", "to_string", "withCapacity"])
+        );
+    }
+
+    #[test]
+    fn go_takes_exported_methods_and_constructors() {
+        let src = r#"
+func New[T any, K comparable](opts Options[T, K]) (*PriorityQueue[T, K], error) {
+func WithCapacity[T any, K comparable](n int) *PriorityQueue[T, K] {
+func (pq *PriorityQueue[T, K]) Insert(item T) {
+func (pq PriorityQueue[T, K]) Len() int {
+func (pq *PriorityQueue[T, K]) siftUp(i int) {
+func (o *Options[T, K]) Validate() error {
+func MinNumber(a, b int) bool {
+"#;
+        assert_eq!(go(src), set(&["Insert", "Len", "New", "WithCapacity"]));
+    }
+
+    #[test]
+    fn typescript_takes_members_of_the_exported_class() {
+        let src = r#"
+export interface Options {
+  d: number;
+  compare(a: number, b: number): boolean;
+}
+
+export class PriorityQueue<T, K> {
+  private container: T[] = [];
+
+  constructor(options: Options) {
+    this.container = [];
+  }
+
+  static withCapacity<T, K>(capacity: number): PriorityQueue<T, K> {
+  }
+
+  get size(): number {
+  }
+
+  public insert(item: T): void {
+  }
+
+  isEmpty(): boolean {
+  }
+}
+
+export function minBy<T>(key: (item: T) => number) {
+}
+"#;
+        assert_eq!(
+            typescript(src),
+            set(&["constructor", "insert", "isEmpty", "size", "withCapacity"])
+        );
+    }
+
+    #[test]
+    fn zig_takes_pub_fns_of_the_heap_struct() {
+        let src = r#"
+const std = @import("std");
+
+pub fn DHeapWithStats(comptime T: type, comptime Stats: type) type {
+    return struct {
+        const Self = @This();
+
+        pub fn init(d: usize, allocator: std.mem.Allocator) !Self {
+        }
+
+        pub fn insert(self: *Self, item: T) !void {
+        }
+
+        fn siftUp(self: *Self, i: usize) void {
+        }
+    };
+}
+
+pub fn DHeap(comptime T: type) type {
+    return DHeapWithStats(T, NoOpStats);
+}
+
+pub fn helper() void {
+}
+"#;
+        assert_eq!(zig(src), set(&["init", "insert"]));
+    }
+
+    #[test]
+    fn snake_case_handles_each_naming_style() {
+        assert_eq!(to_snake_case("increasePriority"), "increase_priority");
+        assert_eq!(to_snake_case("IncreasePriority"), "increase_priority");
+        assert_eq!(to_snake_case("Increase_priority"), "increase_priority");
+        assert_eq!(to_snake_case("peek"), "peek");
+    }
+
+    #[test]
+    fn unknown_languages_are_an_error() {
+        let err = extract("cobol", Path::new("queue.cbl")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("no extractor for language `cobol`"));
+    }
+}
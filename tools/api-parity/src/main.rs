@@ -0,0 +1,60 @@
+use anyhow::Result;
+use clap::Parser;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod config;
+mod extract;
+mod report;
+
+#[derive(Parser)]
+#[command(
+    name = "api-parity",
+    about = "Report public priority-queue methods present in one language but missing in another",
+    version
+)]
+struct Cli {
+    /// Path to parity.toml
+    #[arg(long, default_value = "parity.toml")]
+    config: PathBuf,
+    /// Repository root that the `[sources]` paths are relative to
+    #[arg(long, default_value = "../..")]
+    root: PathBuf,
+    /// Also print methods that every language already provides
+    #[arg(long)]
+    verbose: bool,
+}
+
+/// Exit codes: 0 = parity (known gaps allowed), 1 = unexpected gaps,
+/// 2 = the checker itself failed (bad config, unreadable or unparsable source).
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<bool> {
+    let cfg = config::load(&cli.config)?;
+    let mut surfaces: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for lang in config::LANGUAGES {
+        let Some(rel) = cfg.sources.get(lang) else {
+            continue;
+        };
+        let raw = extract::extract(lang, &cli.root.join(rel))?;
+        let canonical = raw
+            .iter()
+            .map(|name| extract::to_snake_case(name))
+            .filter(|name| !cfg.is_ignored(lang, name))
+            .map(|name| cfg.alias(lang, &name).to_string())
+            .collect();
+        surfaces.insert(lang, canonical);
+    }
+    Ok(report::print(&cfg, &surfaces, cli.verbose))
+}
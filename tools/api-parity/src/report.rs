@@ -0,0 +1,69 @@
+use crate::config::Config;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Print the parity matrix and return `true` when there are no unexpected
+/// gaps. Cells read `ok` (present), `known` (missing, listed in
+/// `[known_gaps]`) or `MISSING`.
+pub fn print(cfg: &Config, surfaces: &BTreeMap<&str, BTreeSet<String>>, verbose: bool) -> bool {
+    let langs: Vec<&str> = crate::config::LANGUAGES
+        .into_iter()
+        .filter(|l| surfaces.contains_key(l))
+        .collect();
+    let all: BTreeSet<&String> = surfaces.values().flatten().collect();
+    let width = all
+        .iter()
+        .map(|n| n.len())
+        .max()
+        .unwrap_or(0)
+        .max("method".len());
+
+    let mut header = format!("{:width$}", "method");
+    for lang in &langs {
+        header.push_str(&format!("  {lang:>10}"));
+    }
+    println!("{header}");
+
+    let mut unexpected = 0usize;
+    let mut known = 0usize;
+    for name in &all {
+        let mut row = format!("{name:width$}");
+        let mut complete = true;
+        for lang in &langs {
+            let cell = if surfaces[lang].contains(*name) {
+                "ok"
+            } else if cfg.is_known_gap(lang, name) {
+                complete = false;
+                known += 1;
+                "known"
+            } else {
+                complete = false;
+                unexpected += 1;
+                "MISSING"
+            };
+            row.push_str(&format!("  {cell:>10}"));
+        }
+        if verbose || !complete {
+            println!("{row}");
+        }
+    }
+
+    // Known gaps that have since been closed should be dropped from the
+    // config so they cannot mask a future regression.
+    for (lang, gaps) in &cfg.known_gaps {
+        for gap in gaps {
+            if surfaces.get(lang.as_str()).is_some_and(|s| s.contains(gap)) {
+                eprintln!("note: `{gap}` is listed as a known gap for {lang} but is present");
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} methods across {} languages: {} unexpected gap(s), {} known gap(s)",
+        all.len(),
+        langs.len(),
+        unexpected,
+        known
+    );
+    unexpected == 0
+}